@@ -0,0 +1,183 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compares the cost of header protection with the cost of payload protection
+//!
+//! Header protection is a fixed cost per packet while payload protection scales with the size of
+//! the packet, which makes the two good candidates to offload independently. Each measurement is
+//! taken with the software keys and with keys created through an [`offload::Offload`], which shows
+//! the cost of dispatching to an offload implementation.
+
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+use s2n_codec::{encoder::scatter, Encoder, EncoderBuffer};
+use s2n_quic_core::{
+    crypto::{packet_protection, HeaderKey, HeaderProtectionMask, Key},
+    endpoint,
+};
+use s2n_quic_crypto::{
+    hkdf,
+    offload::{self, Offload},
+    one_rtt::OneRttKey,
+    ring_aead as aead, Prk, SecretPair,
+};
+
+const SAMPLE: [u8; 16] = [123; 16];
+const BATCH_SIZES: &[usize] = &[1, 16, 64];
+const PAYLOAD_SIZES: &[usize] = &[64, 512, 1200];
+
+pub fn benchmarks(c: &mut Criterion) {
+    let suites = [
+        ("aes128", &aead::AES_128_GCM, hkdf::HKDF_SHA256),
+        ("aes256", &aead::AES_256_GCM, hkdf::HKDF_SHA384),
+        ("chacha20", &aead::CHACHA20_POLY1305, hkdf::HKDF_SHA256),
+    ];
+
+    let providers = [
+        ("software", Offload::default()),
+        (
+            "offload",
+            Offload::default()
+                .with_header_protection(SoftwareOffload)
+                .with_packet_protection(SoftwareOffload),
+        ),
+    ];
+
+    for (name, algorithm, digest) in suites {
+        for (provider, offload) in providers.iter() {
+            let secrets = SecretPair {
+                server: Prk::new_less_safe(digest, &[1; 48]),
+                client: Prk::new_less_safe(digest, &[2; 48]),
+            };
+            let (key, header_key) =
+                OneRttKey::new_with_offload(endpoint::Type::Server, algorithm, secrets, offload)
+                    .unwrap();
+
+            let mut group =
+                c.benchmark_group(format!("crypto/packet_protection/{name}/{provider}/header"));
+            for batch in BATCH_SIZES.iter().copied() {
+                group.throughput(Throughput::Elements(batch as _));
+                group.bench_with_input(BenchmarkId::new("masks", batch), &batch, |b, &batch| {
+                    let samples = vec![&SAMPLE[..]; batch];
+                    let mut masks = vec![HeaderProtectionMask::default(); batch];
+                    b.iter(|| {
+                        header_key.sealing_header_protection_masks(black_box(&samples), &mut masks)
+                    });
+                });
+            }
+            group.finish();
+
+            let mut group = c.benchmark_group(format!(
+                "crypto/packet_protection/{name}/{provider}/payload"
+            ));
+            for size in PAYLOAD_SIZES.iter().copied() {
+                group.throughput(Throughput::Bytes(size as _));
+                group.bench_with_input(BenchmarkId::new("encrypt", size), &size, |b, &size| {
+                    let header = black_box([123u8; 20]);
+                    let mut input = vec![123u8; size + key.tag_len()];
+                    b.iter(|| {
+                        let mut payload = EncoderBuffer::new(&mut input);
+                        payload.advance_position(size);
+                        let mut payload = scatter::Buffer::new(payload);
+                        let _ = key.encrypt(black_box(1), &header, &mut payload);
+                    });
+                });
+            }
+            group.finish();
+        }
+    }
+}
+
+/// Offloads keys to ring so the offload dispatch can be compared with the software keys
+struct SoftwareOffload;
+
+impl offload::HeaderProtection for SoftwareOffload {
+    fn new_key(
+        &self,
+        cipher: offload::Cipher,
+        key: &[u8],
+    ) -> Option<Box<dyn offload::HeaderProtectionKey>> {
+        let algorithm = match cipher {
+            offload::Cipher::Aes128 => &aead::quic::AES_128,
+            offload::Cipher::Aes256 => &aead::quic::AES_256,
+            offload::Cipher::ChaCha20 => &aead::quic::CHACHA20,
+            _ => return None,
+        };
+        let key = aead::quic::HeaderProtectionKey::new(algorithm, key).ok()?;
+        Some(Box::new(SoftwareHeaderKey(key)))
+    }
+}
+
+struct SoftwareHeaderKey(aead::quic::HeaderProtectionKey);
+
+impl offload::HeaderProtectionKey for SoftwareHeaderKey {
+    #[inline]
+    fn sample_len(&self) -> usize {
+        self.0.algorithm().sample_len()
+    }
+
+    #[inline]
+    fn mask(&self, sample: &[u8]) -> HeaderProtectionMask {
+        self.0.new_mask(sample).unwrap()
+    }
+}
+
+impl offload::PacketProtection for SoftwareOffload {
+    fn new_key(
+        &self,
+        cipher: offload::Cipher,
+        key: &[u8],
+    ) -> Option<Box<dyn offload::PacketProtectionKey>> {
+        let algorithm = match cipher {
+            offload::Cipher::Aes128 => &aead::AES_128_GCM,
+            offload::Cipher::Aes256 => &aead::AES_256_GCM,
+            offload::Cipher::ChaCha20 => &aead::CHACHA20_POLY1305,
+            _ => return None,
+        };
+        let key = aead::UnboundKey::new(algorithm, key).ok()?;
+        Some(Box::new(SoftwarePacketKey(aead::LessSafeKey::new(key))))
+    }
+}
+
+struct SoftwarePacketKey(aead::LessSafeKey);
+
+impl offload::PacketProtectionKey for SoftwarePacketKey {
+    #[inline]
+    fn encrypt(
+        &self,
+        nonce: &[u8; aead::NONCE_LEN],
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), packet_protection::Error> {
+        let nonce = aead::Nonce::assume_unique_for_key(*nonce);
+        let aad = aead::Aad::from(header);
+        let buffer = payload.flatten();
+        let tag = {
+            let (input, _) = buffer.split_mut();
+            self.0
+                .seal_in_place_separate_tag(nonce, aad, input)
+                .map_err(|_| packet_protection::Error::INTERNAL_ERROR)?
+        };
+        buffer.write_slice(tag.as_ref());
+        Ok(())
+    }
+
+    #[inline]
+    fn decrypt(
+        &self,
+        nonce: &[u8; aead::NONCE_LEN],
+        header: &[u8],
+        payload: &mut [u8],
+        tag: &[u8; offload::TAG_LEN],
+    ) -> Result<(), packet_protection::Error> {
+        // only encryption is measured so the payload is copied to keep this simple
+        let nonce = aead::Nonce::assume_unique_for_key(*nonce);
+        let aad = aead::Aad::from(header);
+        let mut input = [&payload[..], &tag[..]].concat();
+        let output = self
+            .0
+            .open_in_place(nonce, aad, &mut input)
+            .map_err(|_| packet_protection::Error::DECRYPT_ERROR)?;
+        payload.copy_from_slice(output);
+        Ok(())
+    }
+}
@@ -8,6 +8,8 @@ mod crypto;
 mod frame;
 mod inet;
 mod packet;
+#[cfg(target_os = "linux")]
+mod platform;
 mod sync;
 mod varint;
 mod xdp;
@@ -18,6 +20,8 @@ pub fn benchmarks(c: &mut Criterion) {
     frame::benchmarks(c);
     inet::benchmarks(c);
     packet::benchmarks(c);
+    #[cfg(target_os = "linux")]
+    platform::benchmarks(c);
     sync::benchmarks(c);
     varint::benchmarks(c);
     xdp::benchmarks(c);
@@ -0,0 +1,148 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use s2n_quic_platform::{
+    message::{default::Message, Message as _},
+    socket::{
+        packet,
+        ring::{self, Consumer, Producer},
+        task::rx::Events,
+    },
+    syscall::{self, SocketType, UnixMessage},
+};
+use std::{
+    ffi::CString,
+    net::{SocketAddr, UdpSocket},
+    os::unix::io::AsRawFd,
+    time::{Duration, Instant},
+};
+
+const PAYLOAD_LEN: usize = 1200;
+const ENTRIES: u32 = 1024;
+const MTU: u32 = 1500;
+
+pub fn benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("platform/rx");
+
+    for burst in [16, 64, 256] {
+        group.throughput(Throughput::Elements(burst as u64));
+
+        group.bench_with_input(BenchmarkId::new("recvmmsg", burst), &burst, |b, &burst| {
+            let receiver = syscall::udp_socket("127.0.0.1:0".parse().unwrap()).unwrap();
+            receiver
+                .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+                .unwrap();
+            receiver.set_nonblocking(true).unwrap();
+            let receiver: UdpSocket = receiver.into();
+            let addr = receiver.local_addr().unwrap();
+
+            let mut ring = Ring::new();
+            let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+            b.iter_custom(|iters| {
+                measure(iters, &sender, addr, burst, |events| {
+                    ring.recv(events, |entries, events| {
+                        Message::recv(
+                            receiver.as_raw_fd(),
+                            SocketType::NonBlocking,
+                            entries,
+                            events,
+                        )
+                    })
+                })
+            });
+        });
+
+        // packet sockets require the CAP_NET_RAW capability so skip the benchmark otherwise
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = receiver.local_addr().unwrap();
+        let interface = CString::new("lo").unwrap();
+        let config = packet::Config {
+            // use smaller blocks so each burst fills blocks rather than waiting for them to retire
+            block_size: 1 << 16,
+            ..Default::default()
+        };
+        let mut socket = match packet::Socket::open(&interface, addr.into(), false, config) {
+            Ok(socket) => socket,
+            Err(err) => {
+                eprintln!("skipping packet socket benchmarks: {err}");
+                continue;
+            }
+        };
+        syscall::attach_drop_filter(&receiver.try_clone().unwrap().into()).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("packet", burst), &burst, |b, &burst| {
+            let mut ring = Ring::new();
+            let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+            b.iter_custom(|iters| {
+                measure(iters, &sender, addr, burst, |events| {
+                    ring.recv(events, |entries, events| socket.recv(entries, events))
+                })
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Sends bursts of datagrams to the address and measures the time to receive them
+fn measure<R: FnMut(&mut Events) -> usize>(
+    iters: u64,
+    sender: &UdpSocket,
+    addr: SocketAddr,
+    burst: usize,
+    mut recv: R,
+) -> Duration {
+    let payload = [123u8; PAYLOAD_LEN];
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..iters {
+        for _ in 0..burst {
+            sender.send_to(&payload, addr).unwrap();
+        }
+
+        let start = Instant::now();
+        let mut received = 0;
+        while received < burst {
+            let mut events = Events::default();
+            received += recv(&mut events);
+        }
+        elapsed += start.elapsed();
+    }
+
+    elapsed
+}
+
+/// Message storage which is recycled after each receive call
+struct Ring {
+    producer: Producer<Message>,
+    consumer: Consumer<Message>,
+}
+
+impl Ring {
+    fn new() -> Self {
+        let (producer, consumer) = ring::pair(ENTRIES, MTU);
+        Self { producer, consumer }
+    }
+
+    fn recv<F: FnOnce(&mut [Message], &mut Events)>(&mut self, events: &mut Events, f: F) -> usize {
+        self.producer.acquire(u32::MAX);
+        f(self.producer.data(), events);
+
+        let count = events.take_count() as u32;
+        self.producer.release(count);
+
+        // reset the messages so they can be filled again
+        self.consumer.acquire(u32::MAX);
+        for message in self.consumer.data() {
+            unsafe {
+                message.reset(MTU as _);
+            }
+        }
+        self.consumer.release(count);
+
+        count as _
+    }
+}
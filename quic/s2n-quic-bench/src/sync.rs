@@ -3,10 +3,18 @@
 
 use criterion::{BenchmarkId, Criterion, Throughput};
 use crossbeam_channel::bounded;
-use s2n_quic_core::sync::spsc;
+use s2n_quic_core::sync::{
+    spsc,
+    wait_queue::{Ticket, WaitQueue},
+};
+use std::{
+    sync::Arc,
+    task::{Wake, Waker},
+};
 
 pub fn benchmarks(c: &mut Criterion) {
     spsc_benches(c);
+    wait_queue_benches(c);
 }
 
 fn spsc_benches(c: &mut Criterion) {
@@ -78,3 +86,64 @@ fn spsc_benches(c: &mut Criterion) {
     }
     group.finish();
 }
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Simulates many tasks blocked on opening a stream, with capacity being released one stream at
+/// a time
+fn wait_queue_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wait_queue");
+    let waker = Waker::from(Arc::new(NoopWaker));
+
+    for i in [1, 64, 1024, 4096] {
+        group.throughput(Throughput::Elements(i as _));
+        group.bench_with_input(
+            BenchmarkId::new("s2n/open_contention", i),
+            &i,
+            |b, input| {
+                let mut queue = WaitQueue::new();
+                let mut tickets: Vec<_> = (0..*input).map(|_| Ticket::new()).collect();
+                b.iter(|| {
+                    for ticket in tickets.iter_mut() {
+                        queue.register(ticket, &waker);
+                    }
+
+                    // tasks which are woken spuriously poll again with their existing ticket
+                    for ticket in tickets.iter_mut() {
+                        queue.register(ticket, &waker);
+                    }
+
+                    while queue.wake(1) > 0 {}
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("vec/open_contention", i),
+            &i,
+            |b, input| {
+                let mut wakers = Vec::new();
+                b.iter(|| {
+                    for _ in 0..*input {
+                        wakers.push(waker.clone());
+                    }
+
+                    for prev in wakers.iter_mut() {
+                        if !prev.will_wake(&waker) {
+                            *prev = waker.clone();
+                        }
+                    }
+
+                    while !wakers.is_empty() {
+                        wakers.drain(..1).for_each(|waker| waker.wake());
+                    }
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
@@ -1,12 +1,14 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod mode;
 #[cfg(feature = "alloc")]
 pub mod ranges;
 pub mod set;
 pub mod settings;
 pub mod transmission;
 
+pub use mode::Mode;
 #[cfg(feature = "alloc")]
 pub use ranges::Ranges;
 pub use set::Set;
@@ -0,0 +1,41 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Controls the transmission of ACK frames for a packet number space
+///
+/// This is intended for constructing reproducible loss recovery scenarios in tests, e.g.
+/// withholding acknowledgements to trigger probe timeouts at the peer. Applications should
+/// otherwise leave the mode as [`Mode::Default`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Mode {
+    /// ACK frames are transmitted according to the configured ACK settings
+    #[default]
+    Default,
+
+    /// Every ack-eliciting packet is acknowledged without delay
+    ///
+    /// Any pending acknowledgements are transmitted as soon as the mode is set.
+    Immediate,
+
+    /// ACK frames are withheld until the mode is changed
+    ///
+    /// Received packets are still recorded, so the acknowledgements are transmitted once the
+    /// mode is changed. Note that the peer will likely declare its packets lost and close the
+    /// connection after its idle timeout if ACK frames are withheld for too long.
+    Withhold,
+}
+
+impl Mode {
+    /// Returns `true` if ACK frames are currently being withheld
+    #[inline]
+    pub fn is_withholding(&self) -> bool {
+        matches!(self, Self::Withhold)
+    }
+
+    /// Returns `true` if every ack-eliciting packet is acknowledged without delay
+    #[inline]
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, Self::Immediate)
+    }
+}
@@ -7,4 +7,4 @@ pub(crate) mod server_name;
 
 pub use error::Error;
 #[cfg(feature = "alloc")]
-pub use server_name::ServerName;
+pub use server_name::{matches_server_name, ServerName};
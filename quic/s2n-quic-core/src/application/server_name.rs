@@ -73,3 +73,38 @@ impl core::ops::Deref for ServerName {
         self.as_str()
     }
 }
+
+/// Returns `true` if `name` matches the server name `pattern`
+///
+/// A pattern is either an exact server name or a wildcard of the form `*.example.com`, which
+/// matches any subdomain of `example.com`. Names are compared case-insensitively.
+pub fn matches_server_name(pattern: &str, name: &str) -> bool {
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+        let (name, suffix) = (name.as_bytes(), suffix.as_bytes());
+        let Some(dot) = name.len().checked_sub(suffix.len() + 1) else {
+            return false;
+        };
+
+        // the wildcard must match at least one non-empty label
+        dot > 0 && name[dot] == b'.' && name[dot + 1..].eq_ignore_ascii_case(suffix)
+    } else {
+        pattern.eq_ignore_ascii_case(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_name_patterns() {
+        assert!(matches_server_name("example.com", "example.com"));
+        assert!(matches_server_name("example.com", "EXAMPLE.com"));
+        assert!(!matches_server_name("example.com", "www.example.com"));
+        assert!(matches_server_name("*.example.com", "www.example.com"));
+        assert!(matches_server_name("*.example.com", "a.b.example.com"));
+        assert!(!matches_server_name("*.example.com", "example.com"));
+        assert!(!matches_server_name("*.example.com", ".example.com"));
+        assert!(!matches_server_name("*.example.com", "wwwexample.com"));
+    }
+}
@@ -556,6 +556,22 @@ impl Reassembler {
         offset
     }
 
+    /// Releases any allocations which don't hold received data
+    ///
+    /// Slots are only released if none of them hold any data, since the remaining slots would
+    /// otherwise need to be realigned. The buffer will allocate new slots as more data is
+    /// received.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        if self.slots.iter().all(|slot| slot.is_empty()) {
+            self.slots = VecDeque::new();
+        } else {
+            self.slots.shrink_to_fit();
+        }
+
+        self.invariants();
+    }
+
     /// Resets the receive buffer.
     ///
     /// This will drop all previously received data.
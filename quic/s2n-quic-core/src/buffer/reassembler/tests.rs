@@ -22,6 +22,7 @@ enum Op {
     Skip {
         len: VarInt,
     },
+    ShrinkToFit,
 }
 
 #[test]
@@ -63,6 +64,13 @@ fn model_test() {
                         recv.seek_forward(len.as_u64());
                     }
                 }
+                Op::ShrinkToFit => {
+                    let len = buffer.len();
+                    let total_received_len = buffer.total_received_len();
+                    buffer.shrink_to_fit();
+                    assert_eq!(buffer.len(), len);
+                    assert_eq!(buffer.total_received_len(), total_received_len);
+                }
             }
         }
 
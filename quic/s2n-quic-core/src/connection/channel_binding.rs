@@ -0,0 +1,102 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::crypto::tls::TlsSession;
+use core::fmt;
+
+//= https://www.rfc-editor.org/rfc/rfc9266#section-2
+//# The name of this channel binding type is "tls-exporter".
+//# It MUST be 32 bytes in length, with the label "EXPORTER-Channel-Binding"
+//# and with no context (i.e., the "context_value" used in the TLS 1.3
+//# exporter is empty).
+const LABEL: &[u8] = b"EXPORTER-Channel-Binding";
+
+/// A `tls-exporter` channel binding value, as defined in [RFC 9266](https://www.rfc-editor.org/rfc/rfc9266)
+///
+/// Authentication protocols which run over the connection's streams can include the value in
+/// their exchanges to prove that both ends of the exchange share the same QUIC connection.
+///
+/// The value should be treated as sensitive, since it's derived from the connection secrets.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ChannelBinding([u8; ChannelBinding::LEN]);
+
+impl ChannelBinding {
+    /// The length of the channel binding value, in bytes
+    pub const LEN: usize = 32;
+
+    /// The name of the channel binding type
+    pub const TYPE: &'static str = "tls-exporter";
+
+    /// Derives the channel binding from the TLS session
+    ///
+    /// Returns `None` if the session does not support exporting keying material.
+    #[inline]
+    pub fn new<S: TlsSession + ?Sized>(session: &S) -> Option<Self> {
+        let mut value = [0; Self::LEN];
+        session.tls_exporter(LABEL, &[], &mut value).ok()?;
+        Some(Self(value))
+    }
+
+    /// Returns the channel binding value as a byte slice
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; ChannelBinding::LEN]> for ChannelBinding {
+    #[inline]
+    fn from(value: [u8; ChannelBinding::LEN]) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for ChannelBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // avoid leaking the value into logs
+        f.debug_tuple("ChannelBinding")
+            .field(&"[REDACTED]")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::tls::{CipherSuite, TlsExportError};
+
+    struct Session(Option<u8>);
+
+    impl TlsSession for Session {
+        fn tls_exporter(
+            &self,
+            label: &[u8],
+            context: &[u8],
+            output: &mut [u8],
+        ) -> Result<(), TlsExportError> {
+            assert_eq!(label, LABEL);
+            assert!(context.is_empty());
+            let secret = self.0.ok_or_else(TlsExportError::failure)?;
+            for (idx, byte) in output.iter_mut().enumerate() {
+                *byte = secret ^ idx as u8;
+            }
+            Ok(())
+        }
+
+        fn cipher_suite(&self) -> CipherSuite {
+            CipherSuite::TLS_AES_128_GCM_SHA256
+        }
+    }
+
+    #[test]
+    fn channel_binding_test() {
+        let binding = ChannelBinding::new(&Session(Some(0xf0))).unwrap();
+        assert_eq!(binding, ChannelBinding::new(&Session(Some(0xf0))).unwrap());
+        assert_ne!(binding, ChannelBinding::new(&Session(Some(0x0f))).unwrap());
+        assert_eq!(binding.as_bytes().len(), ChannelBinding::LEN);
+        assert_eq!(binding.as_bytes()[1], 0xf1);
+        assert_eq!(format!("{binding:?}"), "ChannelBinding(\"[REDACTED]\")");
+
+        assert!(ChannelBinding::new(&Session(None)).is_none());
+    }
+}
@@ -74,6 +74,13 @@ pub enum Error {
         source: &'static panic::Location<'static>,
     },
 
+    /// The server didn't respond to any of the client's first flight transmissions
+    #[non_exhaustive]
+    NoServerResponse {
+        transmissions: u32,
+        source: &'static panic::Location<'static>,
+    },
+
     /// The connection should be closed immediately without notifying the peer
     #[non_exhaustive]
     ImmediateClose {
@@ -142,6 +149,11 @@ impl fmt::Display for Error {
                 "The connection was closed because the handshake took longer than the max handshake \
                 duration of {max_handshake_duration:?}"
             ),
+            Self::NoServerResponse { transmissions, .. } => write!(
+                f,
+                "The connection attempt was abandoned because the server didn't respond after \
+                {transmissions} transmissions of the first flight"
+            ),
             Self::ImmediateClose { reason, .. } => write!(
                 f,
                 "The connection was closed due to: {reason}"
@@ -209,6 +221,14 @@ impl PartialEq for Error {
                     ..
                 },
             ) => a.eq(b),
+            (
+                Error::NoServerResponse {
+                    transmissions: a, ..
+                },
+                Error::NoServerResponse {
+                    transmissions: b, ..
+                },
+            ) => a.eq(b),
             (Error::ImmediateClose { reason: a, .. }, Error::ImmediateClose { reason: b, .. }) => {
                 a.eq(b)
             }
@@ -233,6 +253,7 @@ impl Error {
             Error::NoValidPath { source } => source,
             Error::StreamIdExhausted { source } => source,
             Error::MaxHandshakeDurationExceeded { source, .. } => source,
+            Error::NoServerResponse { source, .. } => source,
             Error::ImmediateClose { source, .. } => source,
             Error::EndpointClosing { source } => source,
             Error::Unspecified { source } => source,
@@ -319,6 +340,17 @@ impl Error {
         }
     }
 
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn no_server_response(transmissions: u32) -> Error {
+        let source = panic::Location::caller();
+        Error::NoServerResponse {
+            transmissions,
+            source,
+        }
+    }
+
     #[inline]
     #[track_caller]
     #[doc(hidden)]
@@ -438,6 +470,8 @@ pub fn as_frame<'a, F: connection::close::Formatter>(
             Some((early, one_rtt))
         }
         Error::MaxHandshakeDurationExceeded { .. } => None,
+        // The server hasn't responded so there's no one to notify
+        Error::NoServerResponse { .. } => None,
         Error::ImmediateClose { .. } => None,
         Error::EndpointClosing { .. } => None,
         Error::Unspecified { .. } => {
@@ -516,6 +550,7 @@ impl From<Error> for std::io::ErrorKind {
             Error::NoValidPath { .. } => ErrorKind::Other,
             Error::StreamIdExhausted { .. } => ErrorKind::Other,
             Error::MaxHandshakeDurationExceeded { .. } => ErrorKind::TimedOut,
+            Error::NoServerResponse { .. } => ErrorKind::TimedOut,
             Error::ImmediateClose { .. } => ErrorKind::Other,
             Error::EndpointClosing { .. } => ErrorKind::Other,
             Error::Unspecified { .. } => ErrorKind::Other,
@@ -529,6 +564,8 @@ impl From<Error> for std::io::ErrorKind {
 pub enum ProcessingError {
     ConnectionError(Error),
     DecryptError,
+    /// The packet number was already processed
+    DuplicatePacket,
     Other,
 }
 
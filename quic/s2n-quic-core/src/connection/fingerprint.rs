@@ -0,0 +1,114 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::crypto::tls::TlsSession;
+use core::fmt;
+
+/// The TLS exporter label used to derive connection fingerprints
+///
+/// Exporter labels are required to begin with "EXPORTER" to avoid collisions with labels
+/// used by TLS itself.
+const LABEL: &[u8] = b"EXPORTER-s2n-quic connection fingerprint";
+
+/// A stable, non-reversible identifier for a connection which is shared by both peers
+///
+/// The fingerprint is exported from the TLS session, using the original destination connection
+/// ID as the exporter context. Both peers derive the same value once the handshake completes,
+/// which makes it usable as a correlation key across client and server logs without exposing
+/// any connection IDs or secrets.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint([u8; Fingerprint::LEN]);
+
+impl Fingerprint {
+    /// The length of the fingerprint, in bytes
+    pub const LEN: usize = 16;
+
+    /// Derives the fingerprint from the TLS session
+    ///
+    /// Returns `None` if the session does not support exporting keying material.
+    #[inline]
+    pub fn new<S: TlsSession + ?Sized>(
+        session: &S,
+        original_destination_connection_id: &[u8],
+    ) -> Option<Self> {
+        let mut value = [0; Self::LEN];
+        session
+            .tls_exporter(LABEL, original_destination_connection_id, &mut value)
+            .ok()?;
+        Some(Self(value))
+    }
+
+    /// Returns the fingerprint as a byte slice
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; Fingerprint::LEN]> for Fingerprint {
+    #[inline]
+    fn from(value: [u8; Fingerprint::LEN]) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fingerprint({self})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::tls::{CipherSuite, TlsExportError};
+
+    struct Session(Option<u8>);
+
+    impl TlsSession for Session {
+        fn tls_exporter(
+            &self,
+            label: &[u8],
+            context: &[u8],
+            output: &mut [u8],
+        ) -> Result<(), TlsExportError> {
+            assert_eq!(label, LABEL);
+            let secret = self.0.ok_or_else(TlsExportError::failure)?;
+            for (idx, byte) in output.iter_mut().enumerate() {
+                *byte = secret ^ context[idx % context.len()];
+            }
+            Ok(())
+        }
+
+        fn cipher_suite(&self) -> CipherSuite {
+            CipherSuite::TLS_AES_128_GCM_SHA256
+        }
+    }
+
+    #[test]
+    fn fingerprint_test() {
+        let dcid = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        let fingerprint = Fingerprint::new(&Session(Some(0xf0)), &dcid).unwrap();
+        assert_eq!(
+            fingerprint,
+            Fingerprint::new(&Session(Some(0xf0)), &dcid).unwrap()
+        );
+        assert_ne!(
+            fingerprint,
+            Fingerprint::new(&Session(Some(0x0f)), &dcid).unwrap()
+        );
+        assert_eq!(fingerprint.to_string(), "f1f2f3f4f5f6f7f8f1f2f3f4f5f6f7f8");
+
+        assert!(Fingerprint::new(&Session(None), &dcid).is_none());
+    }
+}
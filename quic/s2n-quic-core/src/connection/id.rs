@@ -5,7 +5,7 @@
 
 use crate::{
     event::{api::SocketAddress, IntoEvent},
-    inet, transport,
+    inet, random, transport,
 };
 use core::time::Duration;
 use s2n_codec::{decoder_value, Encoder, EncoderValue};
@@ -385,6 +385,21 @@ pub trait Generator {
     /// otherwise the endpoint may terminate.
     fn generate(&mut self, connection_info: &ConnectionInfo) -> LocalId;
 
+    /// Generates a connection ID using the endpoint's random generator
+    ///
+    /// The endpoint always calls this method, which allows formats that rely on randomness to
+    /// use the random provider configured on the endpoint. The default implementation ignores
+    /// `random` and calls [`Self::generate`].
+    #[inline]
+    fn generate_with_random(
+        &mut self,
+        connection_info: &ConnectionInfo,
+        random: &mut dyn random::Generator,
+    ) -> LocalId {
+        let _ = random;
+        self.generate(connection_info)
+    }
+
     /// The maximum amount of time each generated connection ID should be
     /// used for. By default there is no maximum, though connection IDs
     /// may be retired due to rotation requirements or peer requests.
@@ -2,14 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack,
+    ack, application,
     event::{api::SocketAddress, IntoEvent},
-    inet, recovery, stream,
+    inet, path, recovery,
+    stream::{self, limits::LocalLimits as _},
     transport::parameters::{
-        AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
-        InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
-        InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, TransportParameters,
+        AckDelayExponent, ActiveConnectionIdLimit, AddressDiscovery, InitialFlowControlLimits,
+        InitialMaxData, InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote,
+        InitialMaxStreamDataUni, InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits,
+        MaxAckDelay, MaxDatagramFrameSize, MaxIdleTimeout, MaxUdpPayloadSize, TransportParameters,
     },
 };
 use core::time::Duration;
@@ -29,6 +30,9 @@ const MAX_HANDSHAKE_DURATION_DEFAULT: Duration = Duration::from_secs(10);
 //# middleboxes from losing state for UDP flows [GATEWAY].
 const MAX_KEEP_ALIVE_PERIOD_DEFAULT: Duration = Duration::from_secs(30);
 
+/// The percentage of a receive window the peer consumes before an update is sent
+const WINDOW_UPDATE_THRESHOLD_DEFAULT: u8 = 10;
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct ConnectionInfo<'a> {
@@ -45,6 +49,93 @@ impl<'a> ConnectionInfo<'a> {
     }
 }
 
+/// Controls the size of datagrams carrying Initial packets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InitialPadding {
+    /// Datagrams are filled and padded up to the maximum datagram size of the path
+    #[default]
+    Full,
+    /// Datagrams are limited to, and padded up to, the provided length
+    ///
+    /// A ClientHello which doesn't fit in a single datagram of this length is split across
+    /// multiple datagrams of the same length. This is useful for large ClientHello messages, such
+    /// as those carrying post-quantum key shares, which some middleboxes mishandle when they are
+    /// sent in a single jumbo datagram.
+    Fixed(u16),
+}
+
+impl InitialPadding {
+    /// Datagrams are padded to the smallest allowed maximum datagram size of 1200 bytes
+    pub const MINIMUM: Self = Self::Fixed(path::MINIMUM_MAX_DATAGRAM_SIZE);
+}
+
+/// Controls whether packets from different packet number spaces share a datagram
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PacketCoalescing {
+    /// Packets are coalesced up to the maximum datagram size of the path
+    #[default]
+    Enabled,
+    /// Datagrams carrying packets from more than one packet number space are limited to the
+    /// provided length
+    Limited(u16),
+    /// Each datagram carries packets from a single packet number space
+    ///
+    /// Some middleboxes mishandle coalesced datagrams, which can be worked around at the cost of
+    /// sending more datagrams during the handshake.
+    Disabled,
+}
+
+/// Controls which datagrams are padded with PADDING frames beyond what the protocol requires
+///
+/// Padding hides the size of the data carried by each datagram from on-path observers, at the
+/// cost of additional bandwidth. The amount of padding sent is reported in the connection
+/// statistics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PaddingPolicy {
+    /// Datagrams are only padded when required by the protocol
+    #[default]
+    Disabled,
+    /// Every datagram is padded up to the maximum datagram size of the path
+    Full,
+    /// Every datagram is limited to, and padded up to, the provided length
+    ///
+    /// The length is clamped to the maximum datagram size of the path, so datagrams only all
+    /// have the same size while the path supports the provided length. A length of 1200 bytes is
+    /// supported by every QUIC path and is never clamped.
+    Fixed(u16),
+    /// Each datagram is padded up to the maximum datagram size of the path with the provided
+    /// percent chance
+    Random(u8),
+    /// Only datagrams carrying Initial or Handshake packets are padded up to the maximum
+    /// datagram size of the path
+    ///
+    /// This hides the size of the certificate chain and other handshake messages.
+    Handshake,
+}
+
+/// Controls the order in which streams with pending data are given transmission capacity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StreamSchedulingPolicy {
+    /// Streams transmit in the order they became ready, regardless of priority
+    ///
+    /// A stream keeps transmitting until it runs out of data before the next stream is
+    /// serviced.
+    Fifo,
+    /// Streams take turns transmitting, with each stream given `priority + 1` consecutive turns
+    /// per round
+    #[default]
+    RoundRobin,
+    /// Streams with a higher priority transmit all of their data before any stream with a lower
+    /// priority is serviced
+    ///
+    /// Streams with the same priority transmit in the order they became ready.
+    StrictPriority,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Limits {
     pub(crate) max_idle_timeout: MaxIdleTimeout,
@@ -62,10 +153,25 @@ pub struct Limits {
     pub(crate) ack_elicitation_interval: u8,
     pub(crate) ack_ranges_limit: u8,
     pub(crate) max_send_buffer_size: stream::limits::MaxSendBufferSize,
+    pub(crate) stream_window_update_threshold: u8,
+    pub(crate) connection_window_update_threshold: u8,
     pub(crate) max_handshake_duration: Duration,
     pub(crate) max_keep_alive_period: Duration,
+    pub(crate) keep_alive_period: Option<Duration>,
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
+    pub(crate) max_udp_payload_size: MaxUdpPayloadSize,
     pub(crate) initial_round_trip_time: Duration,
+    pub(crate) first_flight_probe_timeout: Option<Duration>,
+    pub(crate) max_first_flight_transmissions: Option<u32>,
+    pub(crate) handshake_only: Option<application::Error>,
+    pub(crate) address_discovery: Option<AddressDiscovery>,
+    pub(crate) hibernation_timeout: Option<Duration>,
+    pub(crate) initial_padding: InitialPadding,
+    pub(crate) packet_coalescing: PacketCoalescing,
+    pub(crate) padding_policy: PaddingPolicy,
+    pub(crate) stream_scheduling_policy: StreamSchedulingPolicy,
+    pub(crate) reserved_local_bidirectional_streams: u8,
+    pub(crate) reserved_local_unidirectional_streams: u8,
 }
 
 impl Default for Limits {
@@ -106,10 +212,25 @@ impl Limits {
             ack_elicitation_interval: ack::Settings::RECOMMENDED.ack_elicitation_interval,
             ack_ranges_limit: ack::Settings::RECOMMENDED.ack_ranges_limit,
             max_send_buffer_size: stream::Limits::RECOMMENDED.max_send_buffer_size,
+            stream_window_update_threshold: WINDOW_UPDATE_THRESHOLD_DEFAULT,
+            connection_window_update_threshold: WINDOW_UPDATE_THRESHOLD_DEFAULT,
             max_handshake_duration: MAX_HANDSHAKE_DURATION_DEFAULT,
             max_keep_alive_period: MAX_KEEP_ALIVE_PERIOD_DEFAULT,
+            keep_alive_period: None,
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
+            max_udp_payload_size: MaxUdpPayloadSize::RECOMMENDED,
             initial_round_trip_time: recovery::DEFAULT_INITIAL_RTT,
+            first_flight_probe_timeout: None,
+            max_first_flight_transmissions: None,
+            handshake_only: None,
+            address_discovery: None,
+            hibernation_timeout: None,
+            initial_padding: InitialPadding::Full,
+            packet_coalescing: PacketCoalescing::Enabled,
+            padding_policy: PaddingPolicy::Disabled,
+            stream_scheduling_policy: StreamSchedulingPolicy::RoundRobin,
+            reserved_local_bidirectional_streams: 0,
+            reserved_local_unidirectional_streams: 0,
         }
     }
 
@@ -159,6 +280,45 @@ impl Limits {
         }
     );
 
+    setter!(
+        /// Sets the percentage of a stream's receive window the peer must consume before a
+        /// `MAX_STREAM_DATA` frame is sent (default: 10)
+        ///
+        /// Lower values send earlier and more frequent window updates, which keeps high
+        /// bandwidth-delay product transfers from stalling on flow control. Higher values reduce
+        /// the number of control frames, at the risk of the peer being blocked while the update
+        /// is in flight. The value must be between 1 and 100.
+        with_stream_window_update_threshold,
+        stream_window_update_threshold,
+        u8,
+        |validate_value| {
+            ensure!(
+                (1..=100).contains(&validate_value),
+                Err(ValidationError(
+                    "window update threshold must be between 1 and 100"
+                ))
+            );
+        }
+    );
+    setter!(
+        /// Sets the percentage of the connection's receive window the peer must consume before a
+        /// `MAX_DATA` frame is sent (default: 10)
+        ///
+        /// See [`Self::with_stream_window_update_threshold`] for the tradeoffs. The value must be
+        /// between 1 and 100.
+        with_connection_window_update_threshold,
+        connection_window_update_threshold,
+        u8,
+        |validate_value| {
+            ensure!(
+                (1..=100).contains(&validate_value),
+                Err(ValidationError(
+                    "window update threshold must be between 1 and 100"
+                ))
+            );
+        }
+    );
+
     setter!(with_max_idle_timeout, max_idle_timeout, Duration);
 
     /// Sets both the max local and remote limits for bidirectional streams.
@@ -237,6 +397,37 @@ impl Limits {
     );
     setter!(with_max_keep_alive_period, max_keep_alive_period, Duration);
 
+    /// Enables keep-alive for new connections and sends PING frames at the provided period
+    ///
+    /// A PING is only sent if no other ack-eliciting packet was transmitted within the period,
+    /// which keeps otherwise idle connections from being dropped by the peer's idle timeout
+    /// or by middleboxes such as NATs. The period is capped at 3/4 of the negotiated idle
+    /// timeout so the PING is delivered before the connection times out.
+    ///
+    /// Keep-alive can still be disabled on individual connections with `Connection::keep_alive`.
+    pub fn with_keep_alive_period(mut self, value: Duration) -> Result<Self, ValidationError> {
+        ensure!(
+            value > Duration::ZERO,
+            Err(ValidationError("provided value must be greater than zero"))
+        );
+
+        self.keep_alive_period = Some(value);
+        Ok(self)
+    }
+    setter!(
+        /// Sets the largest UDP payload the endpoint is willing to receive
+        ///
+        /// The value is advertised to the peer with the `max_udp_payload_size` transport
+        /// parameter and any received datagrams with a larger payload are dropped. This can be
+        /// used in environments with a constrained MTU, such as tunnels, to ask the peer to keep
+        /// its packets small rather than relying on path MTU discovery alone.
+        ///
+        /// The value must be between 1200 and 65527 bytes.
+        with_max_udp_payload_size,
+        max_udp_payload_size,
+        u16
+    );
+
     /// Sets the initial round trip time (RTT) for use in recovery mechanisms prior to
     /// measuring an actual RTT sample.
     ///
@@ -257,6 +448,229 @@ impl Limits {
         Ok(self)
     }
 
+    /// Sets the probe timeout (PTO) of a client's first flight, before the server has responded
+    ///
+    /// By default, the timeout is derived from the initial round trip time. Each retransmission
+    /// of the first flight doubles the timeout. Latency-sensitive clients can lower it to
+    /// retransmit sooner when the first flight is lost.
+    ///
+    /// This setting has no effect on server connections.
+    pub fn with_first_flight_probe_timeout(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            value >= recovery::K_GRANULARITY,
+            Err(ValidationError(
+                "provided value must be at least the timer granularity of 1 millisecond",
+            ))
+        );
+
+        self.first_flight_probe_timeout = Some(value);
+        Ok(self)
+    }
+
+    /// Sets how many times a client transmits its first flight before giving up on the server
+    ///
+    /// The count includes the original transmission. Once the last transmission times out without
+    /// a response, the connection attempt fails with
+    /// [`Error::NoServerResponse`](crate::connection::Error::NoServerResponse) rather than
+    /// waiting for the max handshake duration. By default, the first flight is retransmitted until
+    /// the handshake or idle timer expires.
+    ///
+    /// This setting has no effect on server connections.
+    pub fn with_max_first_flight_transmissions(
+        mut self,
+        value: u32,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            value > 0,
+            Err(ValidationError("provided value must be greater than zero"))
+        );
+
+        self.max_first_flight_transmissions = Some(value);
+        Ok(self)
+    }
+
+    /// Closes server connections with the provided error code as soon as the handshake completes
+    ///
+    /// The connections are never handed to the application. This is intended for load testing
+    /// the handshake capacity of an endpoint, or for exercising the handshake in isolation. Handshake
+    /// progress can still be observed with the connection events.
+    ///
+    /// This setting has no effect on client connections.
+    pub fn with_handshake_only(
+        mut self,
+        error: application::Error,
+    ) -> Result<Self, ValidationError> {
+        self.handshake_only = Some(error);
+        Ok(self)
+    }
+
+    /// Enables the QUIC address discovery extension
+    ///
+    /// Endpoints that provide observed addresses report the address they see on the active path
+    /// to the peer. Endpoints that receive observed addresses learn their own address as seen by
+    /// the peer, which can be used to discover the public address assigned by a NAT. The
+    /// extension is only used if the peer also supports it.
+    pub fn with_address_discovery(
+        mut self,
+        mode: AddressDiscovery,
+    ) -> Result<Self, ValidationError> {
+        self.address_discovery = Some(mode);
+        Ok(self)
+    }
+
+    /// Compacts the state of connections which have been idle for the provided duration
+    ///
+    /// Hibernating connections release transient buffers, such as stream reassembly buffers,
+    /// sent packet history and connection ID storage, down to their minimum sizes. The buffers
+    /// are allocated again as traffic resumes. This reduces the memory footprint of workloads
+    /// which hold many long-lived connections that rarely exchange data, at the cost of
+    /// additional allocations when a connection becomes active again.
+    ///
+    /// Hibernation is disabled by default.
+    pub fn with_hibernation_timeout(mut self, value: Duration) -> Result<Self, ValidationError> {
+        ensure!(
+            value > Duration::ZERO,
+            Err(ValidationError("provided value must be greater than zero"))
+        );
+
+        self.hibernation_timeout = Some(value);
+        Ok(self)
+    }
+
+    /// Sets how datagrams carrying Initial packets are sized and padded
+    ///
+    /// By default, Initial packets fill the entire datagram, up to the maximum datagram size of
+    /// the path. A fixed length must be at least 1200 bytes.
+    pub fn with_initial_padding(
+        mut self,
+        padding: InitialPadding,
+    ) -> Result<Self, ValidationError> {
+        if let InitialPadding::Fixed(len) = padding {
+            ensure!(
+                len >= path::MINIMUM_MAX_DATAGRAM_SIZE,
+                Err(ValidationError(
+                    "initial datagrams must be at least 1200 bytes"
+                ))
+            );
+        }
+
+        self.initial_padding = padding;
+        Ok(self)
+    }
+
+    /// Sets whether packets from different packet number spaces are coalesced into a single
+    /// datagram
+    ///
+    /// By default, Initial, Handshake and 1-RTT packets are coalesced whenever they fit in the
+    /// same datagram. A limited length must be at least 1200 bytes, since coalesced datagrams can
+    /// carry Initial packets.
+    pub fn with_packet_coalescing(
+        mut self,
+        coalescing: PacketCoalescing,
+    ) -> Result<Self, ValidationError> {
+        if let PacketCoalescing::Limited(len) = coalescing {
+            ensure!(
+                len >= path::MINIMUM_MAX_DATAGRAM_SIZE,
+                Err(ValidationError(
+                    "coalesced datagrams must be at least 1200 bytes"
+                ))
+            );
+        }
+
+        self.packet_coalescing = coalescing;
+        Ok(self)
+    }
+
+    /// Sets which datagrams are padded to resist traffic analysis
+    ///
+    /// Padding is disabled by default. A fixed length must be at least 1200 bytes and a random
+    /// percent chance must be at most 100. Fixed lengths larger than the maximum datagram size
+    /// of a path are clamped to that size.
+    pub fn with_padding_policy(mut self, policy: PaddingPolicy) -> Result<Self, ValidationError> {
+        if let PaddingPolicy::Fixed(len) = policy {
+            ensure!(
+                len >= path::MINIMUM_MAX_DATAGRAM_SIZE,
+                Err(ValidationError(
+                    "padded datagrams must be at least 1200 bytes"
+                ))
+            );
+        }
+
+        if let PaddingPolicy::Random(percent) = policy {
+            ensure!(
+                percent <= 100,
+                Err(ValidationError("padding percent must be at most 100"))
+            );
+        }
+
+        self.padding_policy = policy;
+        Ok(self)
+    }
+
+    /// Selects the built-in policy used to schedule the transmission of streams on each
+    /// connection
+    ///
+    /// Defaults to [`StreamSchedulingPolicy::RoundRobin`].
+    pub fn with_stream_scheduling_policy(
+        mut self,
+        policy: StreamSchedulingPolicy,
+    ) -> Result<Self, ValidationError> {
+        self.stream_scheduling_policy = policy;
+        Ok(self)
+    }
+
+    /// Reserves the first locally-initiated bidirectional stream IDs on each connection
+    ///
+    /// The reserved streams are opened as soon as the handshake completes, before any other
+    /// local streams, and can only be obtained with `open_reserved_bidirectional_stream`. This
+    /// allows protocols which assign meaning to specific stream IDs to open their control streams
+    /// deterministically, regardless of the order in which the application opens other streams.
+    ///
+    /// Streams which exceed the peer's initial stream limit are not reserved.
+    pub fn with_reserved_local_bidirectional_streams(
+        mut self,
+        value: u8,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            u64::from(value)
+                <= self
+                    .max_open_local_bidirectional_streams
+                    .as_varint()
+                    .as_u64(),
+            Err(ValidationError(
+                "reserved streams must not exceed max_open_local_bidirectional_streams"
+            ))
+        );
+
+        self.reserved_local_bidirectional_streams = value;
+        Ok(self)
+    }
+
+    /// Reserves the first locally-initiated unidirectional stream IDs on each connection
+    ///
+    /// See [`Self::with_reserved_local_bidirectional_streams`].
+    pub fn with_reserved_local_unidirectional_streams(
+        mut self,
+        value: u8,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            u64::from(value)
+                <= self
+                    .max_open_local_unidirectional_streams
+                    .as_varint()
+                    .as_u64(),
+            Err(ValidationError(
+                "reserved streams must not exceed max_open_local_unidirectional_streams"
+            ))
+        );
+
+        self.reserved_local_unidirectional_streams = value;
+        Ok(self)
+    }
+
     // internal APIs
 
     #[doc(hidden)]
@@ -312,6 +726,18 @@ impl Limits {
         }
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn stream_window_update_threshold(&self) -> u8 {
+        self.stream_window_update_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn connection_window_update_threshold(&self) -> u8 {
+        self.connection_window_update_threshold
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn max_idle_timeout(&self) -> Option<Duration> {
@@ -330,11 +756,86 @@ impl Limits {
         self.max_keep_alive_period
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn keep_alive_period(&self) -> Option<Duration> {
+        self.keep_alive_period
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn initial_round_trip_time(&self) -> Duration {
         self.initial_round_trip_time
     }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_udp_payload_size(&self) -> u64 {
+        self.max_udp_payload_size.as_u64()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn first_flight_probe_timeout(&self) -> Option<Duration> {
+        self.first_flight_probe_timeout
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_first_flight_transmissions(&self) -> Option<u32> {
+        self.max_first_flight_transmissions
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn handshake_only(&self) -> Option<application::Error> {
+        self.handshake_only
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn address_discovery(&self) -> Option<AddressDiscovery> {
+        self.address_discovery
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn hibernation_timeout(&self) -> Option<Duration> {
+        self.hibernation_timeout
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn initial_padding(&self) -> InitialPadding {
+        self.initial_padding
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn packet_coalescing(&self) -> PacketCoalescing {
+        self.packet_coalescing
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn padding_policy(&self) -> PaddingPolicy {
+        self.padding_policy
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn stream_scheduling_policy(&self) -> StreamSchedulingPolicy {
+        self.stream_scheduling_policy
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn reserved_local_streams(&self, stream_type: stream::StreamType) -> u8 {
+        match stream_type {
+            stream::StreamType::Bidirectional => self.reserved_local_bidirectional_streams,
+            stream::StreamType::Unidirectional => self.reserved_local_unidirectional_streams,
+        }
+    }
 }
 
 /// Creates limits for a given connection
@@ -369,4 +870,135 @@ mod tests {
         assert!(limits.with_bidirectional_remote_data_window(data).is_ok());
         assert!(limits.with_unidirectional_data_window(data).is_ok());
     }
+
+    #[test]
+    fn window_update_threshold_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.stream_window_update_threshold(), 10);
+        assert_eq!(limits.connection_window_update_threshold(), 10);
+
+        for value in [0, 101] {
+            assert!(limits.with_stream_window_update_threshold(value).is_err());
+            assert!(limits
+                .with_connection_window_update_threshold(value)
+                .is_err());
+        }
+
+        let limits = limits
+            .with_stream_window_update_threshold(50)
+            .unwrap()
+            .with_connection_window_update_threshold(12)
+            .unwrap();
+        assert_eq!(limits.stream_window_update_threshold(), 50);
+        assert_eq!(limits.connection_window_update_threshold(), 12);
+    }
+
+    #[test]
+    fn initial_padding_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.initial_padding(), InitialPadding::Full);
+        assert!(limits
+            .with_initial_padding(InitialPadding::Fixed(1199))
+            .is_err());
+        assert!(limits.with_initial_padding(InitialPadding::MINIMUM).is_ok());
+        assert_eq!(
+            limits
+                .with_initial_padding(InitialPadding::Fixed(1400))
+                .unwrap()
+                .initial_padding(),
+            InitialPadding::Fixed(1400)
+        );
+    }
+
+    #[test]
+    fn packet_coalescing_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.packet_coalescing(), PacketCoalescing::Enabled);
+        assert!(limits
+            .with_packet_coalescing(PacketCoalescing::Limited(1199))
+            .is_err());
+        assert_eq!(
+            limits
+                .with_packet_coalescing(PacketCoalescing::Limited(1200))
+                .unwrap()
+                .packet_coalescing(),
+            PacketCoalescing::Limited(1200)
+        );
+        assert_eq!(
+            limits
+                .with_packet_coalescing(PacketCoalescing::Disabled)
+                .unwrap()
+                .packet_coalescing(),
+            PacketCoalescing::Disabled
+        );
+    }
+
+    #[test]
+    fn padding_policy_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.padding_policy(), PaddingPolicy::Disabled);
+        assert!(limits
+            .with_padding_policy(PaddingPolicy::Fixed(1199))
+            .is_err());
+        assert!(limits
+            .with_padding_policy(PaddingPolicy::Random(101))
+            .is_err());
+
+        for policy in [
+            PaddingPolicy::Full,
+            PaddingPolicy::Fixed(1200),
+            PaddingPolicy::Random(100),
+            PaddingPolicy::Handshake,
+        ] {
+            assert_eq!(
+                limits.with_padding_policy(policy).unwrap().padding_policy(),
+                policy
+            );
+        }
+    }
+
+    #[test]
+    fn stream_scheduling_policy_test() {
+        let limits = Limits::default();
+        assert_eq!(
+            limits.stream_scheduling_policy(),
+            StreamSchedulingPolicy::RoundRobin
+        );
+
+        for policy in [
+            StreamSchedulingPolicy::Fifo,
+            StreamSchedulingPolicy::RoundRobin,
+            StreamSchedulingPolicy::StrictPriority,
+        ] {
+            assert_eq!(
+                limits
+                    .with_stream_scheduling_policy(policy)
+                    .unwrap()
+                    .stream_scheduling_policy(),
+                policy
+            );
+        }
+    }
+
+    #[test]
+    fn reserved_streams_validation() {
+        let limits = Limits::default()
+            .with_max_open_local_unidirectional_streams(3)
+            .unwrap();
+        assert!(limits
+            .with_reserved_local_unidirectional_streams(4)
+            .is_err());
+
+        let limits = limits
+            .with_reserved_local_unidirectional_streams(3)
+            .unwrap();
+        assert_eq!(
+            limits.reserved_local_streams(stream::StreamType::Unidirectional),
+            3
+        );
+        assert_eq!(
+            limits.reserved_local_streams(stream::StreamType::Bidirectional),
+            0
+        );
+    }
 }
@@ -1,11 +1,21 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod channel_binding;
 pub mod close;
 pub mod error;
+pub mod fingerprint;
 pub mod id;
 pub mod limits;
+pub mod recovery_stats;
+pub mod statistics;
+pub mod transmission_status;
 
+pub use channel_binding::ChannelBinding;
 pub use error::{Error, ProcessingError};
+pub use fingerprint::Fingerprint;
 pub use id::{InitialId, LocalId, PeerId, UnboundedId};
 pub use limits::Limits;
+pub use recovery_stats::RecoveryStats;
+pub use statistics::Statistics;
+pub use transmission_status::TransmissionStatus;
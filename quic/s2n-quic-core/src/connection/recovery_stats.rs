@@ -0,0 +1,38 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+
+/// A snapshot of the loss recovery and congestion control state of a connection's active path
+///
+/// This provides the same values as the `RecoveryMetrics` event without needing to subscribe to
+/// every update, e.g. for sampling the state of a connection when handling a request.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecoveryStats {
+    /// The exponentially-weighted moving average of the RTT samples
+    pub smoothed_rtt: Duration,
+
+    /// The mean deviation of the RTT samples
+    pub rttvar: Duration,
+
+    /// The minimum RTT observed on the path
+    pub min_rtt: Duration,
+
+    /// The most recent RTT sample
+    pub latest_rtt: Duration,
+
+    /// The number of consecutive probe timeouts (PTO) which expired without receiving an
+    /// acknowledgement
+    pub pto_count: u32,
+
+    /// The congestion window of the active path, in bytes
+    pub congestion_window: u32,
+
+    /// The number of bytes in flight on the active path
+    pub bytes_in_flight: u32,
+
+    /// The rate at which packets are paced, in bytes per second, or `None` if packets are not
+    /// being paced
+    pub pacing_rate: Option<u64>,
+}
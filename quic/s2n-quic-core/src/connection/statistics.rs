@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::ops::AddAssign;
+
+/// Counters describing how efficiently data was delivered on a connection or endpoint
+///
+/// Every byte which is retransmitted adds to the cost of delivering the application's data so
+/// these counters can be used to attribute that overhead.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// The number of received packets which were discarded since they had already been
+    /// processed
+    pub duplicate_packets: u64,
+
+    /// The number of stream payload bytes which were sent again after being declared lost
+    pub retransmitted_bytes: u64,
+
+    /// The number of packets which were declared lost but were later acknowledged by the peer
+    ///
+    /// The data contained in these packets was retransmitted unnecessarily.
+    pub spurious_retransmissions: u64,
+
+    /// The number of Initial packets which were routed to a handshake that was already in
+    /// progress
+    ///
+    /// These packets are still addressed to the client's original destination connection ID,
+    /// which usually means the client retransmitted its first flight before receiving a response.
+    /// Rather than starting a new handshake, they are coalesced into the existing one, or
+    /// discarded if the server has already moved past the Initial packet space.
+    pub coalesced_initial_packets: u64,
+
+    /// The number of 1-RTT packets which were sent
+    pub application_packets_sent: u64,
+
+    /// The number of bytes in the 1-RTT packets which were sent
+    pub application_packet_bytes_sent: u64,
+
+    /// The sum of the maximum datagram sizes at the time each 1-RTT packet was sent
+    ///
+    /// This is the number of bytes the packets could have carried if each one was full.
+    pub application_packet_capacity: u64,
+
+    /// The number of bytes of PADDING frames which were sent
+    ///
+    /// This includes the padding required by the protocol, such as for Initial packets, as well
+    /// as any padding added by the configured padding policy.
+    pub padding_bytes_sent: u64,
+}
+
+impl Statistics {
+    /// Returns `true` if none of the counters have been incremented
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the fraction of the available space that was used by the 1-RTT packets which
+    /// were sent
+    ///
+    /// Applications which issue many small writes will have a low fill rate. `None` is returned
+    /// if no packets have been sent.
+    #[inline]
+    pub fn application_packet_fill_rate(&self) -> Option<f64> {
+        if self.application_packet_capacity == 0 {
+            return None;
+        }
+
+        Some(self.application_packet_bytes_sent as f64 / self.application_packet_capacity as f64)
+    }
+}
+
+impl AddAssign for Statistics {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.duplicate_packets += rhs.duplicate_packets;
+        self.retransmitted_bytes += rhs.retransmitted_bytes;
+        self.spurious_retransmissions += rhs.spurious_retransmissions;
+        self.coalesced_initial_packets += rhs.coalesced_initial_packets;
+        self.application_packets_sent += rhs.application_packets_sent;
+        self.application_packet_bytes_sent += rhs.application_packet_bytes_sent;
+        self.application_packet_capacity += rhs.application_packet_capacity;
+        self.padding_bytes_sent += rhs.padding_bytes_sent;
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+/// A snapshot of the limits which currently apply to sending data on a connection
+///
+/// This is intended for diagnosing stalled transfers, e.g. to determine whether a connection is
+/// waiting on the network, on the peer granting more flow control credits, or on the
+/// application.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransmissionStatus {
+    /// The congestion window of the active path, in bytes
+    pub congestion_window: u32,
+
+    /// The number of bytes in flight on the active path
+    pub bytes_in_flight: u32,
+
+    /// `true` if the congestion window of the active path is full
+    pub is_congestion_limited: bool,
+
+    /// `true` if transmission is delayed until the pacer allows the next packet to be sent
+    pub is_pacing_limited: bool,
+
+    /// `true` if the peer's address has not been validated and the anti-amplification limit has
+    /// been reached
+    pub is_amplification_limited: bool,
+
+    /// The connection flow control limit set by the peer, in bytes
+    pub connection_flow_limit: u64,
+
+    /// The number of bytes sent against the connection flow control limit
+    pub connection_flow_consumed: u64,
+
+    /// The number of streams waiting for the peer to raise the connection flow control limit
+    pub connection_flow_blocked_streams: usize,
+
+    /// The number of streams waiting for the peer to raise their stream flow control limit
+    pub stream_flow_blocked_streams: usize,
+
+    /// The number of bidirectional streams which can still be opened before reaching the peer's
+    /// `MAX_STREAMS` limit
+    pub available_bidirectional_streams: u64,
+
+    /// The number of unidirectional streams which can still be opened before reaching the peer's
+    /// `MAX_STREAMS` limit
+    pub available_unidirectional_streams: u64,
+}
+
+impl TransmissionStatus {
+    /// Returns `true` if the connection flow control limit has been reached
+    #[inline]
+    pub fn is_connection_flow_limited(&self) -> bool {
+        self.connection_flow_consumed >= self.connection_flow_limit
+    }
+
+    /// Returns `true` if at least one stream is waiting for its stream flow control limit to be
+    /// raised
+    #[inline]
+    pub fn is_stream_flow_limited(&self) -> bool {
+        self.stream_flow_blocked_streams > 0
+    }
+
+    /// Returns `true` if the connection is prevented from sending new data by any of the
+    /// congestion controller, pacer, anti-amplification limit or flow control
+    ///
+    /// Stream concurrency limits are not included, since they only affect opening new streams.
+    #[inline]
+    pub fn is_blocked(&self) -> bool {
+        self.is_congestion_limited
+            || self.is_pacing_limited
+            || self.is_amplification_limited
+            || self.is_connection_flow_limited()
+            || self.is_stream_flow_limited()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocked_test() {
+        let mut status = TransmissionStatus {
+            connection_flow_limit: 100,
+            ..Default::default()
+        };
+        assert!(!status.is_blocked());
+
+        status.connection_flow_consumed = 100;
+        assert!(status.is_connection_flow_limited());
+        assert!(status.is_blocked());
+
+        status.connection_flow_limit = 200;
+        status.stream_flow_blocked_streams = 1;
+        assert!(!status.is_connection_flow_limited());
+        assert!(status.is_stream_flow_limited());
+        assert!(status.is_blocked());
+
+        status.stream_flow_blocked_streams = 0;
+        status.is_pacing_limited = true;
+        assert!(status.is_blocked());
+    }
+}
@@ -28,6 +28,38 @@ pub trait HeaderKey: Send {
     /// Returns the sample size needed for the header protection
     /// buffer
     fn sealing_sample_len(&self) -> usize;
+
+    /// Derives a header protection mask for each of the samples, to be
+    /// used for opening packets.
+    ///
+    /// Implementations which are able to process multiple samples at once
+    /// can override this method to amortize the cost of each mask.
+    #[inline]
+    fn opening_header_protection_masks(
+        &self,
+        ciphertext_samples: &[&[u8]],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        for (sample, mask) in ciphertext_samples.iter().zip(masks.iter_mut()) {
+            *mask = self.opening_header_protection_mask(sample);
+        }
+    }
+
+    /// Derives a header protection mask for each of the samples, to be
+    /// used for sealing packets.
+    ///
+    /// Implementations which are able to process multiple samples at once
+    /// can override this method to amortize the cost of each mask.
+    #[inline]
+    fn sealing_header_protection_masks(
+        &self,
+        ciphertext_samples: &[&[u8]],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        for (sample, mask) in ciphertext_samples.iter().zip(masks.iter_mut()) {
+            *mask = self.sealing_header_protection_mask(sample);
+        }
+    }
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9001#section-5.4.1
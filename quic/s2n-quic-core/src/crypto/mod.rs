@@ -141,6 +141,7 @@ pub mod one_rtt;
 pub mod packet_protection;
 pub mod payload;
 pub mod retry;
+pub mod test_vectors;
 pub mod tls;
 pub mod zero_rtt;
 
@@ -0,0 +1,378 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Known-good values for validating QUIC crypto providers
+//!
+//! The vectors are taken from the examples in
+//! [RFC 9001 Appendix A](https://www.rfc-editor.org/rfc/rfc9001#appendix-A) and include the
+//! derived keys, the header protection sample and mask, and the final protected packet for each
+//! example. The `check_*` functions can be used by providers to validate their implementations
+//! against the same values used by s2n-quic. Each function panics if the implementation produces
+//! an unexpected result.
+
+use crate::crypto::{
+    initial::{
+        EXAMPLE_CLIENT_INITIAL_HEADER, EXAMPLE_CLIENT_INITIAL_PAYLOAD,
+        EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET, EXAMPLE_CLIENT_INITIAL_SECRET, EXAMPLE_DCID,
+        EXAMPLE_SERVER_INITIAL_HEADER, EXAMPLE_SERVER_INITIAL_PAYLOAD,
+        EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET, EXAMPLE_SERVER_INITIAL_SECRET,
+    },
+    retry, HeaderKey, HeaderProtectionMask, InitialKey, Key, RetryKey,
+};
+use hex_literal::hex;
+use s2n_codec::{encoder::scatter, EncoderBuffer};
+
+/// The largest protected packet included in the test vectors
+const MAX_PACKET_LEN: usize = 1200;
+
+/// Packet protection keys derived from a traffic secret
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct Keys {
+    /// The traffic secret from which the keys are derived
+    pub secret: &'static [u8],
+    /// The AEAD key, derived with the "quic key" label
+    pub key: &'static [u8],
+    /// The AEAD IV, derived with the "quic iv" label
+    pub iv: &'static [u8],
+    /// The header protection key, derived with the "quic hp" label
+    pub hp: &'static [u8],
+    /// The secret used after a key update, derived with the "quic ku" label, if provided
+    pub ku: Option<&'static [u8]>,
+}
+
+/// A packet protected with a set of [`Keys`], along with the intermediate values
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct Packet {
+    /// The keys used to protect the packet
+    pub keys: Keys,
+    /// The full, non-truncated packet number
+    pub packet_number: u64,
+    /// The packet header, including the packet number, before header protection is applied
+    pub unprotected_header: &'static [u8],
+    /// The cleartext payload of the packet
+    ///
+    /// Any trailing PADDING frames are omitted.
+    pub payload: &'static [u8],
+    /// The ciphertext sample used for header protection
+    pub sample: &'static [u8],
+    /// The header protection mask derived from the sample
+    pub mask: HeaderProtectionMask,
+    /// The final protected packet
+    pub protected_packet: &'static [u8],
+}
+
+/// A Retry packet and the values used to compute its integrity tag
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct Retry {
+    /// The Destination Connection ID of the client's first Initial packet
+    pub original_destination_connection_id: &'static [u8],
+    /// The Retry Pseudo-Packet over which the integrity tag is computed
+    pub pseudo_packet: &'static [u8],
+    /// The expected integrity tag
+    pub tag: retry::IntegrityTag,
+    /// The final Retry packet
+    pub packet: &'static [u8],
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.1
+//# initial_secret = HKDF-Extract(initial_salt, cid)
+//#     = 7db5df06e7a69e432496adedb0085192
+//#       3595221596ae2ae9fb8115c1e9ed0a44
+
+/// The initial secret extracted from the example Destination Connection ID
+pub const INITIAL_SECRET: [u8; 32] =
+    hex!("7db5df06e7a69e432496adedb00851923595221596ae2ae9fb8115c1e9ed0a44");
+
+/// The Destination Connection ID from which the example Initial keys are derived
+pub const INITIAL_DESTINATION_CONNECTION_ID: [u8; 8] = EXAMPLE_DCID;
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.1
+//# key = HKDF-Expand-Label(client_initial_secret, "quic key", "", 16)
+//#     = 1f369613dd76d5467730efcbe3b1a22d
+//#
+//# iv  = HKDF-Expand-Label(client_initial_secret, "quic iv", "", 12)
+//#     = fa044b2f42a3fd3b46fb255c
+//#
+//# hp  = HKDF-Expand-Label(client_initial_secret, "quic hp", "", 16)
+//#     = 9f50449e04a0e810283a1e9933adedd2
+
+/// The keys used to protect the client's Initial packets
+pub const CLIENT_INITIAL_KEYS: Keys = Keys {
+    secret: &EXAMPLE_CLIENT_INITIAL_SECRET,
+    key: &hex!("1f369613dd76d5467730efcbe3b1a22d"),
+    iv: &hex!("fa044b2f42a3fd3b46fb255c"),
+    hp: &hex!("9f50449e04a0e810283a1e9933adedd2"),
+    ku: None,
+};
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.1
+//# key = HKDF-Expand-Label(server_initial_secret, "quic key", "", 16)
+//#     = cf3a5331653c364c88f0f379b6067e37
+//#
+//# iv  = HKDF-Expand-Label(server_initial_secret, "quic iv", "", 12)
+//#     = 0ac1493ca1905853b0bba03e
+//#
+//# hp  = HKDF-Expand-Label(server_initial_secret, "quic hp", "", 16)
+//#     = c206b8d9b9f0f37644430b490eeaa314
+
+/// The keys used to protect the server's Initial packets
+pub const SERVER_INITIAL_KEYS: Keys = Keys {
+    secret: &EXAMPLE_SERVER_INITIAL_SECRET,
+    key: &hex!("cf3a5331653c364c88f0f379b6067e37"),
+    iv: &hex!("0ac1493ca1905853b0bba03e"),
+    hp: &hex!("c206b8d9b9f0f37644430b490eeaa314"),
+    ku: None,
+};
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.2
+//# sample = d1b1c98dd7689fb8ec11d242b123dc9b
+//#
+//# mask = AES-ECB(hp, sample)[0..4]
+//#      = 437b9aec36
+
+/// The client's first Initial packet
+pub const CLIENT_INITIAL: Packet = Packet {
+    keys: CLIENT_INITIAL_KEYS,
+    packet_number: 2,
+    unprotected_header: &EXAMPLE_CLIENT_INITIAL_HEADER,
+    payload: &EXAMPLE_CLIENT_INITIAL_PAYLOAD,
+    sample: &hex!("d1b1c98dd7689fb8ec11d242b123dc9b"),
+    mask: hex!("437b9aec36"),
+    protected_packet: &EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET,
+};
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.3
+//# sample = 2cd0991cd25b0aac406a5816b6394100
+//# mask   = 2ec0d8356a
+
+/// The server's first Initial packet
+pub const SERVER_INITIAL: Packet = Packet {
+    keys: SERVER_INITIAL_KEYS,
+    packet_number: 1,
+    unprotected_header: &EXAMPLE_SERVER_INITIAL_HEADER,
+    payload: &EXAMPLE_SERVER_INITIAL_PAYLOAD,
+    sample: &hex!("2cd0991cd25b0aac406a5816b6394100"),
+    mask: hex!("2ec0d8356a"),
+    protected_packet: &EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
+};
+
+/// The Retry packet sent in response to [`CLIENT_INITIAL`]
+pub const RETRY: Retry = Retry {
+    original_destination_connection_id: &retry::example::ODCID,
+    pseudo_packet: &retry::example::PSEUDO_PACKET,
+    tag: retry::example::EXPECTED_TAG,
+    packet: &retry::example::PACKET,
+};
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
+//# secret
+//#     = 9ac312a7f877468ebe69422748ad00a1
+//#       5443f18203a07d6060f688f30f21632b
+//#
+//# key = HKDF-Expand-Label(secret, "quic key", "", 32)
+//#     = c6d98ff3441c3fe1b2182094f69caa2e
+//#       d4b716b65488960a7a984979fb23e1c8
+//#
+//# iv  = HKDF-Expand-Label(secret, "quic iv", "", 12)
+//#     = e0459b3474bdd0e44a41c144
+//#
+//# hp  = HKDF-Expand-Label(secret, "quic hp", "", 32)
+//#     = 25a282b9e82f06f21f488917a4fc8f1b
+//#       73573685608597d0efcb076b0ab7a7a4
+//#
+//# ku  = HKDF-Expand-Label(secret, "quic ku", "", 32)
+//#     = 1223504755036d556342ee9361d25342
+//#       1a826c9ecdf3c7148684b36b714881f9
+
+/// The 1-RTT keys for the TLS_CHACHA20_POLY1305_SHA256 cipher suite
+pub const CHACHA20_POLY1305_KEYS: Keys = Keys {
+    secret: &hex!("9ac312a7f877468ebe69422748ad00a15443f18203a07d6060f688f30f21632b"),
+    key: &hex!("c6d98ff3441c3fe1b2182094f69caa2ed4b716b65488960a7a984979fb23e1c8"),
+    iv: &hex!("e0459b3474bdd0e44a41c144"),
+    hp: &hex!("25a282b9e82f06f21f488917a4fc8f1b73573685608597d0efcb076b0ab7a7a4"),
+    ku: Some(&CHACHA20_POLY1305_KU),
+};
+
+const CHACHA20_POLY1305_KU: [u8; 32] =
+    hex!("1223504755036d556342ee9361d253421a826c9ecdf3c7148684b36b714881f9");
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
+//# pn                 = 654360564 (decimal)
+//# nonce              = e0459b3474bdd0e46d417eb0
+//# unprotected header = 4200bff4
+//# payload plaintext  = 01
+//# payload ciphertext = 655e5cd55c41f69080575d7999c25a5bfb
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
+//# sample = 5e5cd55c41f69080575d7999c25a5bfb
+//# mask   = aefefe7d03
+//# header = 4cfe4189
+
+//= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
+//# packet = 4cfe4189655e5cd55c41f69080575d7999c25a5bfb
+
+/// A short header packet protected with [`CHACHA20_POLY1305_KEYS`]
+pub const CHACHA20_POLY1305_SHORT_HEADER: Packet = Packet {
+    keys: CHACHA20_POLY1305_KEYS,
+    packet_number: 654360564,
+    unprotected_header: &hex!("4200bff4"),
+    payload: &hex!("01"),
+    sample: &hex!("5e5cd55c41f69080575d7999c25a5bfb"),
+    mask: hex!("aefefe7d03"),
+    protected_packet: &hex!("4cfe4189655e5cd55c41f69080575d7999c25a5bfb"),
+};
+
+/// Checks that `key` opens the payload of `packet`
+///
+/// The key must have been derived from the secret used to seal the packet.
+#[track_caller]
+pub fn check_opening_key<K: Key>(key: &K, packet: &Packet) {
+    let mut buffer = [0u8; MAX_PACKET_LEN];
+    let buffer = &mut buffer[..packet.protected_packet.len()];
+    buffer.copy_from_slice(packet.protected_packet);
+
+    let (header, payload) = buffer.split_at_mut(packet.unprotected_header.len());
+    header.copy_from_slice(packet.unprotected_header);
+
+    key.decrypt(packet.packet_number, header, payload)
+        .expect("the packet payload should be opened");
+
+    let payload_len = payload.len() - key.tag_len();
+    let (payload, padding) = payload[..payload_len].split_at(packet.payload.len());
+    assert_eq!(payload, packet.payload, "unexpected cleartext payload");
+    assert!(
+        padding.iter().all(|byte| *byte == 0),
+        "the payload should only be followed by PADDING frames"
+    );
+}
+
+/// Checks that `key` seals the payload of `packet` to the expected ciphertext
+///
+/// The key must have been derived from the secret used to seal the packet.
+#[track_caller]
+pub fn check_sealing_key<K: Key>(key: &K, packet: &Packet) {
+    let header_len = packet.unprotected_header.len();
+    let mut buffer = [0u8; MAX_PACKET_LEN];
+    let buffer = &mut buffer[..packet.protected_packet.len()];
+
+    let (header, payload) = buffer.split_at_mut(header_len);
+    header.copy_from_slice(packet.unprotected_header);
+    payload[..packet.payload.len()].copy_from_slice(packet.payload);
+
+    let mut payload = EncoderBuffer::new(payload);
+    // the cleartext payload includes any padding up to the tag
+    payload.advance_position(packet.protected_packet.len() - header_len - key.tag_len());
+    let mut payload = scatter::Buffer::new(payload);
+    key.encrypt(packet.packet_number, header, &mut payload)
+        .expect("the packet payload should be sealed");
+
+    assert_eq!(
+        &buffer[header_len..],
+        &packet.protected_packet[header_len..],
+        "unexpected ciphertext"
+    );
+}
+
+/// Checks that `key` derives the expected mask when opening `packet`
+#[track_caller]
+pub fn check_opening_header_key<K: HeaderKey>(key: &K, packet: &Packet) {
+    assert_eq!(key.opening_sample_len(), packet.sample.len());
+    assert_eq!(
+        key.opening_header_protection_mask(packet.sample),
+        packet.mask,
+        "unexpected header protection mask"
+    );
+}
+
+/// Checks that `key` derives the expected mask when sealing `packet`
+#[track_caller]
+pub fn check_sealing_header_key<K: HeaderKey>(key: &K, packet: &Packet) {
+    assert_eq!(key.sealing_sample_len(), packet.sample.len());
+    assert_eq!(
+        key.sealing_header_protection_mask(packet.sample),
+        packet.mask,
+        "unexpected header protection mask"
+    );
+}
+
+/// Checks that the Initial keys derived by `K` protect the example Initial packets
+#[track_caller]
+pub fn check_initial_key<K: InitialKey>() {
+    let (client_key, client_header_key) = K::new_client(&INITIAL_DESTINATION_CONNECTION_ID);
+    let (server_key, server_header_key) = K::new_server(&INITIAL_DESTINATION_CONNECTION_ID);
+
+    // the client seals its own packets and opens the server's packets
+    check_sealing_key(&client_key, &CLIENT_INITIAL);
+    check_sealing_header_key(&client_header_key, &CLIENT_INITIAL);
+    check_opening_key(&client_key, &SERVER_INITIAL);
+    check_opening_header_key(&client_header_key, &SERVER_INITIAL);
+
+    // and the server does the inverse
+    check_sealing_key(&server_key, &SERVER_INITIAL);
+    check_sealing_header_key(&server_header_key, &SERVER_INITIAL);
+    check_opening_key(&server_key, &CLIENT_INITIAL);
+    check_opening_header_key(&server_header_key, &CLIENT_INITIAL);
+}
+
+/// Checks that `K` computes and validates the example Retry integrity tag
+#[track_caller]
+pub fn check_retry_key<K: RetryKey>() {
+    assert_eq!(
+        K::generate_tag(RETRY.pseudo_packet),
+        RETRY.tag,
+        "unexpected integrity tag"
+    );
+    assert!(K::validate(RETRY.pseudo_packet, RETRY.tag).is_ok());
+
+    let mut invalid_tag = RETRY.tag;
+    invalid_tag[0] ^= 1;
+    assert!(K::validate(RETRY.pseudo_packet, invalid_tag).is_err());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::{remove_header_protection, ProtectedPayload},
+        packet::number::PacketNumberSpace,
+    };
+
+    /// Checks that each vector is consistent with the packet it describes
+    #[test]
+    fn packet_consistency_test() {
+        for (packet, space) in [
+            (CLIENT_INITIAL, PacketNumberSpace::Initial),
+            (SERVER_INITIAL, PacketNumberSpace::Initial),
+            (
+                CHACHA20_POLY1305_SHORT_HEADER,
+                PacketNumberSpace::ApplicationData,
+            ),
+        ] {
+            let header = packet.unprotected_header;
+            let packet_number_len = (header[0] & 0b11) as usize + 1;
+            let packet_number_offset = header.len() - packet_number_len;
+
+            // the sample is taken 4 bytes after the start of the packet number
+            let sample_offset = packet_number_offset + 4;
+            assert_eq!(
+                &packet.protected_packet[sample_offset..][..packet.sample.len()],
+                packet.sample
+            );
+
+            let mut buffer = packet.protected_packet.to_vec();
+            remove_header_protection(
+                space,
+                packet.mask,
+                ProtectedPayload::new(packet_number_offset, &mut buffer),
+            )
+            .unwrap();
+            assert_eq!(&buffer[..header.len()], header);
+
+            assert!(packet.protected_packet.len() <= MAX_PACKET_LEN);
+            assert_eq!(packet.keys.iv.len(), 12);
+        }
+    }
+}
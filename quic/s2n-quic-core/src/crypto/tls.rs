@@ -9,10 +9,19 @@ use zerocopy::{AsBytes, FromBytes, FromZeroes, Unaligned};
 mod error;
 pub use error::Error;
 
+#[cfg(feature = "alloc")]
+pub mod pin;
+
+#[cfg(feature = "alloc")]
+pub mod resumption;
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
-#[cfg(all(feature = "alloc", any(test, feature = "testing")))]
+#[cfg(all(
+    feature = "alloc",
+    any(test, feature = "testing", feature = "dangerous-null-crypto")
+))]
 pub mod null;
 
 /// Holds all application parameters which are exchanged within the TLS handshake.
@@ -85,6 +94,12 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_protocol: Bytes,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called when a client offers a session ticket or learns whether the server accepted it
+    fn on_resumption_status(
+        &mut self,
+        status: ResumptionStatus,
+    ) -> Result<(), crate::transport::Error>;
+
     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.1.1
     //# The TLS handshake is considered complete when the
     //# TLS stack has reported that the handshake is complete.  This happens
@@ -127,6 +142,17 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
     fn waker(&self) -> &core::task::Waker;
 }
 
+/// The state of a client's attempt to resume a previous TLS session
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResumptionStatus {
+    /// A session ticket was offered to the server
+    Attempted,
+    /// The server accepted the offered session ticket
+    Accepted,
+    /// The server rejected the offered session ticket and performed a full handshake
+    Rejected,
+}
+
 #[cfg(feature = "alloc")]
 pub trait Endpoint: 'static + Sized + Send {
     type Session: Session;
@@ -144,6 +170,13 @@ pub trait Endpoint: 'static + Sized + Send {
 
     /// The maximum length of a tag for any algorithm that may be negotiated
     fn max_tag_length(&self) -> usize;
+
+    /// Returns `true` if the endpoint only negotiates FIPS-approved algorithms using a
+    /// FIPS-validated crypto backend
+    #[inline]
+    fn is_fips_compliant(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -164,6 +197,14 @@ pub trait Session: crate::crypto::CryptoSuite + Sized + Send + Debug {
         true
     }
 
+    /// Requires the peer's certificate chain to contain one of the pinned public keys
+    ///
+    /// This is checked in addition to the normal certificate verification. Providers which
+    /// don't support pinning return an error so the connection isn't established without it.
+    fn pin_spki(&mut self, _pins: pin::SpkiPins) -> Result<(), Error> {
+        Err(pin::UNSUPPORTED)
+    }
+
     /// Parses a hello message of the provided type
     ///
     /// The default implementation of this function assumes TLS messages are being exchanged.
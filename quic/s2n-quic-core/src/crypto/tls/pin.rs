@@ -0,0 +1,225 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Server certificate pinning by subject public key info (SPKI)
+//!
+//! The pins are checked by the TLS providers in addition to the normal certificate verification.
+//! A handshake succeeds only if the peer's end-entity certificate has a public key matching one
+//! of the pinned hashes.
+//!
+//! The remaining certificates in the chain are sent by the peer and aren't necessarily part of
+//! the path that was verified, so they are never matched against the pins.
+
+use super::Error;
+use alloc::sync::Arc;
+use s2n_codec::{DecoderBuffer, DecoderError};
+
+/// The length of a SHA-256 hash of a DER-encoded subject public key info
+pub const SPKI_HASH_LEN: usize = 32;
+
+/// A SHA-256 hash of a DER-encoded subject public key info
+pub type SpkiHash = [u8; SPKI_HASH_LEN];
+
+/// The error returned when the peer's certificate doesn't match a pinned public key
+pub const MISMATCH: Error =
+    Error::BAD_CERTIFICATE.with_reason("peer certificate did not match any pinned public key");
+
+/// The error returned when the TLS provider does not support certificate pinning
+pub const UNSUPPORTED: Error =
+    Error::INTERNAL_ERROR.with_reason("the TLS provider does not support certificate pinning");
+
+/// A set of pinned public key hashes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpkiPins {
+    hashes: Arc<[SpkiHash]>,
+}
+
+impl SpkiPins {
+    pub fn new<I: IntoIterator<Item = SpkiHash>>(hashes: I) -> Self {
+        Self {
+            hashes: hashes.into_iter().collect(),
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, hash: &SpkiHash) -> bool {
+        self.hashes.iter().any(|pin| pin == hash)
+    }
+
+    /// Checks that the peer's end-entity certificate has a pinned public key
+    ///
+    /// `end_entity` is the first certificate in the peer's chain, or `None` if the peer didn't
+    /// send one. `hash` is called with its DER-encoded subject public key info and should return
+    /// its SHA-256 hash.
+    pub fn verify<H>(&self, end_entity: Option<&[u8]>, hash: H) -> Result<(), Error>
+    where
+        H: FnOnce(&[u8]) -> SpkiHash,
+    {
+        let certificate = end_entity.ok_or(MISMATCH)?;
+        let spki = subject_public_key_info(certificate).map_err(|_| Error::BAD_CERTIFICATE)?;
+
+        if self.contains(&hash(spki)) {
+            Ok(())
+        } else {
+            Err(MISMATCH)
+        }
+    }
+}
+
+const SEQUENCE: u8 = 0x30;
+const INTEGER: u8 = 0x02;
+const VERSION: u8 = 0xa0;
+
+/// Returns the DER-encoded subject public key info of an X.509 certificate
+pub fn subject_public_key_info(certificate: &[u8]) -> Result<&[u8], DecoderError> {
+    //= https://www.rfc-editor.org/rfc/rfc5280#section-4.1
+    //# Certificate  ::=  SEQUENCE  {
+    //#      tbsCertificate       TBSCertificate,
+    //#      signatureAlgorithm   AlgorithmIdentifier,
+    //#      signatureValue       BIT STRING  }
+    let (certificate, _) = read_tlv(DecoderBuffer::new(certificate), SEQUENCE)?;
+    let (tbs, _) = read_tlv(certificate, SEQUENCE)?;
+
+    //= https://www.rfc-editor.org/rfc/rfc5280#section-4.1
+    //# TBSCertificate  ::=  SEQUENCE  {
+    //#      version         [0]  EXPLICIT Version DEFAULT v1,
+    //#      serialNumber         CertificateSerialNumber,
+    //#      signature            AlgorithmIdentifier,
+    //#      issuer               Name,
+    //#      validity             Validity,
+    //#      subject              Name,
+    //#      subjectPublicKeyInfo SubjectPublicKeyInfo,
+    let tbs = match tbs.peek_byte(0) {
+        Ok(VERSION) => read_tlv(tbs, VERSION)?.1,
+        _ => tbs,
+    };
+    let (_serial_number, tbs) = read_tlv(tbs, INTEGER)?;
+    let (_signature, tbs) = read_tlv(tbs, SEQUENCE)?;
+    let (_issuer, tbs) = read_tlv(tbs, SEQUENCE)?;
+    let (_validity, tbs) = read_tlv(tbs, SEQUENCE)?;
+    let (_subject, tbs) = read_tlv(tbs, SEQUENCE)?;
+
+    // the hash covers the entire encoding, including the tag and length
+    let encoded_len = tbs.len();
+    let (_spki, remaining) = read_tlv(tbs, SEQUENCE)?;
+    let spki_len = encoded_len - remaining.len();
+    let (spki, _) = tbs.decode_slice(spki_len)?;
+
+    Ok(spki.into_less_safe_slice())
+}
+
+/// Reads a DER value with the expected tag and returns its contents and the remaining buffer
+fn read_tlv(
+    buffer: DecoderBuffer,
+    tag: u8,
+) -> Result<(DecoderBuffer, DecoderBuffer), DecoderError> {
+    let (actual, buffer) = buffer.decode::<u8>()?;
+    if actual != tag {
+        return Err(DecoderError::InvariantViolation("unexpected DER tag"));
+    }
+
+    let (len, buffer) = buffer.decode::<u8>()?;
+    let (len, buffer) = if len & 0x80 == 0 {
+        (len as usize, buffer)
+    } else {
+        // long form lengths encode the number of length bytes in the lower bits
+        let count = (len & 0x7f) as usize;
+        if count == 0 || count > core::mem::size_of::<u32>() {
+            return Err(DecoderError::InvariantViolation("invalid DER length"));
+        }
+        let (bytes, buffer) = buffer.decode_slice(count)?;
+        let len = bytes
+            .into_less_safe_slice()
+            .iter()
+            .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+        (len, buffer)
+    };
+
+    buffer.decode_slice(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    /// A minimal certificate with a version, serial number, and empty names
+    const CERTIFICATE: &[u8] = &hex!(
+        "30 1d"             // Certificate
+        "30 16"             // TBSCertificate
+        "a0 03 02 01 02"    // version
+        "02 01 01"          // serialNumber
+        "30 00"             // signature
+        "30 00"             // issuer
+        "30 00"             // validity
+        "30 00"             // subject
+        "30 04 01 02 03 04" // subjectPublicKeyInfo
+        "30 00"             // signatureAlgorithm
+        "03 01 00"          // signatureValue
+    );
+
+    #[test]
+    fn spki_test() {
+        assert_eq!(
+            subject_public_key_info(CERTIFICATE).unwrap(),
+            &hex!("30 04 01 02 03 04")[..]
+        );
+
+        // truncated certificates are rejected
+        for len in 0..CERTIFICATE.len() {
+            assert!(subject_public_key_info(&CERTIFICATE[..len]).is_err());
+        }
+    }
+
+    /// The same certificate as `CERTIFICATE` with a different public key
+    const OTHER_CERTIFICATE: &[u8] = &hex!(
+        "30 1d"             // Certificate
+        "30 16"             // TBSCertificate
+        "a0 03 02 01 02"    // version
+        "02 01 02"          // serialNumber
+        "30 00"             // signature
+        "30 00"             // issuer
+        "30 00"             // validity
+        "30 00"             // subject
+        "30 04 05 06 07 08" // subjectPublicKeyInfo
+        "30 00"             // signatureAlgorithm
+        "03 01 00"          // signatureValue
+    );
+
+    // use the first byte of the spki contents as the "hash"
+    fn hash(spki: &[u8]) -> SpkiHash {
+        [spki[2]; SPKI_HASH_LEN]
+    }
+
+    #[test]
+    fn verify_test() {
+        let pins = SpkiPins::new([[1; SPKI_HASH_LEN]]);
+        assert_eq!(pins.verify(Some(CERTIFICATE), hash), Ok(()));
+
+        let pins = SpkiPins::new([[2; SPKI_HASH_LEN]]);
+        assert_eq!(pins.verify(Some(CERTIFICATE), hash), Err(MISMATCH));
+        assert_eq!(pins.verify(None, hash), Err(MISMATCH));
+        assert_eq!(
+            pins.verify(Some(&[0u8; 4][..]), hash),
+            Err(Error::BAD_CERTIFICATE)
+        );
+    }
+
+    /// Peers can append arbitrary certificates to their chain, so a matching certificate after
+    /// the end-entity certificate must not satisfy the pins
+    #[test]
+    fn appended_certificate_test() {
+        let pins = SpkiPins::new([[1; SPKI_HASH_LEN]]);
+        let chain = [OTHER_CERTIFICATE, CERTIFICATE];
+
+        assert_eq!(
+            pins.verify(chain.first().copied(), hash),
+            Err(MISMATCH),
+            "only the end-entity certificate can match a pin"
+        );
+
+        // the same certificate is accepted when it's the end-entity certificate
+        let chain = [CERTIFICATE, OTHER_CERTIFICATE];
+        assert_eq!(pins.verify(chain.first().copied(), hash), Ok(()));
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client-side session resumption policy
+//!
+//! The policy is shared by the TLS providers so that the same builder settings result in the
+//! same resumption behavior regardless of the provider in use.
+
+use crate::application::matches_server_name;
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+/// The default number of session tickets a client will store
+pub const DEFAULT_MAX_TICKETS: usize = 256;
+
+/// The hosts and limits a client applies to session resumption
+///
+/// The default policy disables resumption. Providers which resumed sessions before the policy
+/// was introduced start from an enabled policy instead, so existing clients keep resuming.
+#[derive(Clone, Debug)]
+pub struct Policy {
+    enabled: bool,
+    max_tickets: usize,
+    max_ticket_lifetime: Option<Duration>,
+    hosts: Vec<(String, bool)>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl Policy {
+    /// Creates a policy which enables or disables resumption for all hosts by default
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            max_tickets: DEFAULT_MAX_TICKETS,
+            max_ticket_lifetime: None,
+            hosts: Vec::new(),
+        }
+    }
+
+    /// Enables or disables resumption for hosts that don't match any host pattern
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Sets the maximum number of session tickets stored across all hosts
+    pub fn set_max_tickets(&mut self, max_tickets: usize) -> &mut Self {
+        self.max_tickets = max_tickets;
+        self
+    }
+
+    /// Sets an upper bound on how long a session ticket will be used after it is received
+    ///
+    /// Tickets are still discarded earlier if the server advertised a shorter lifetime.
+    pub fn set_max_ticket_lifetime(&mut self, lifetime: Duration) -> &mut Self {
+        self.max_ticket_lifetime = Some(lifetime);
+        self
+    }
+
+    /// Enables or disables resumption for hosts matching `pattern`
+    ///
+    /// A pattern is either an exact host name or a wildcard of the form `*.example.com`, which
+    /// matches any subdomain of `example.com`. Patterns are compared case-insensitively and
+    /// patterns added later take precedence over earlier ones.
+    pub fn set_host<P: Into<String>>(&mut self, pattern: P, enabled: bool) -> &mut Self {
+        self.hosts.push((pattern.into(), enabled));
+        self
+    }
+
+    /// Returns the maximum number of session tickets stored across all hosts
+    #[inline]
+    pub fn max_tickets(&self) -> usize {
+        self.max_tickets
+    }
+
+    /// Returns the configured upper bound on ticket lifetimes, if any
+    #[inline]
+    pub fn max_ticket_lifetime(&self) -> Option<Duration> {
+        self.max_ticket_lifetime
+    }
+
+    /// Clamps the lifetime advertised by the server to the configured maximum
+    #[inline]
+    pub fn clamp_lifetime(&self, lifetime: Duration) -> Duration {
+        match self.max_ticket_lifetime {
+            Some(max) => lifetime.min(max),
+            None => lifetime,
+        }
+    }
+
+    /// Returns `true` if resumption is enabled for the given host
+    pub fn is_enabled(&self, host: &str) -> bool {
+        if self.max_tickets == 0 {
+            return false;
+        }
+
+        self.hosts
+            .iter()
+            .rev()
+            .find(|(pattern, _)| matches_server_name(pattern, host))
+            .map_or(self.enabled, |(_, enabled)| *enabled)
+    }
+
+    /// Returns `true` if resumption is disabled for every host
+    pub fn is_disabled(&self) -> bool {
+        self.max_tickets == 0 || (!self.enabled && self.hosts.iter().all(|(_, enabled)| !enabled))
+    }
+
+    /// Returns `true` if resumption may be enabled for some hosts and disabled for others
+    pub fn is_host_dependent(&self) -> bool {
+        !self.is_disabled()
+            && self
+                .hosts
+                .iter()
+                .any(|(_, enabled)| *enabled != self.enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_policy() {
+        let mut policy = Policy::new(true);
+        policy
+            .set_host("*.internal", false)
+            .set_host("cache.internal", true);
+
+        assert!(policy.is_enabled("example.com"));
+        assert!(!policy.is_enabled("db.internal"));
+        assert!(policy.is_enabled("cache.internal"));
+        assert!(policy.is_host_dependent());
+        assert!(!policy.is_disabled());
+
+        policy.set_max_tickets(0);
+        assert!(!policy.is_enabled("example.com"));
+        assert!(policy.is_disabled());
+    }
+
+    #[test]
+    fn default_disabled() {
+        let mut policy = Policy::default();
+        assert!(policy.is_disabled());
+        assert!(!policy.is_enabled("example.com"));
+
+        policy.set_host("example.com", true);
+        assert!(!policy.is_disabled());
+        assert!(policy.is_enabled("example.com"));
+        assert!(!policy.is_enabled("other.com"));
+    }
+
+    #[test]
+    fn lifetime_clamp() {
+        let mut policy = Policy::default();
+        assert_eq!(
+            policy.clamp_lifetime(Duration::from_secs(60)),
+            Duration::from_secs(60)
+        );
+
+        policy.set_max_ticket_lifetime(Duration::from_secs(10));
+        assert_eq!(
+            policy.clamp_lifetime(Duration::from_secs(60)),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            policy.clamp_lifetime(Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+    }
+}
@@ -704,6 +704,14 @@ where
         Ok(())
     }
 
+    fn on_resumption_status(
+        &mut self,
+        status: tls::ResumptionStatus,
+    ) -> Result<(), transport::Error> {
+        self.log(&format!("resumption status: {status:?}"));
+        Ok(())
+    }
+
     fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
         assert!(
             !self.handshake_complete,
@@ -6,6 +6,7 @@
 use crate::{
     connection,
     datagram::{ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
+    query,
     transport::parameters::MaxDatagramFrameSize,
 };
 use alloc::collections::VecDeque;
@@ -274,6 +275,8 @@ pub enum DatagramError {
     ExceedsPeerTransportLimits,
     #[non_exhaustive]
     ConnectionError { error: connection::Error },
+    #[non_exhaustive]
+    Unsupported,
 }
 
 impl fmt::Display for DatagramError {
@@ -291,6 +294,21 @@ impl fmt::Display for DatagramError {
             Self::ConnectionError { .. } => {
                 write!(f, "Connection-level error occurred.")
             }
+            Self::Unsupported { .. } => {
+                write!(f, "Connection is not using the default datagram provider.")
+            }
+        }
+    }
+}
+
+impl From<query::Error> for DatagramError {
+    #[inline]
+    fn from(error: query::Error) -> Self {
+        match error {
+            query::Error::ConnectionLockPoisoned => Self::ConnectionError {
+                error: connection::Error::unspecified(),
+            },
+            query::Error::ContextTypeMismatch => Self::Unsupported,
         }
     }
 }
@@ -317,26 +335,47 @@ impl Sender {
         data: &mut bytes::Bytes,
         cx: &mut Context,
     ) -> Poll<Result<(), DatagramError>> {
+        match self.try_send_datagram(data) {
+            Err(DatagramError::QueueAtCapacity) => {
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            result => Poll::Ready(result),
+        }
+    }
+
+    /// Enqueues a datagram for sending it towards the peer without registering a waker.
+    ///
+    /// Unlike [`Self::send_datagram`], the datagram is left in `data` if it could not be
+    /// enqueued, so the caller can retry it later.
+    ///
+    /// # Return value
+    ///
+    /// - `Ok(())` if the datagram was enqueued for sending. `data` will be replaced with an
+    ///   empty [`Bytes`].
+    /// - `Err(DatagramError::QueueAtCapacity)` if the datagram's send buffer capacity is
+    ///   currently exhausted and the datagram was not added to the queue.
+    /// - `Err(DatagramError)` if any other error occurred while trying to send the datagram.
+    pub fn try_send_datagram(&mut self, data: &mut bytes::Bytes) -> Result<(), DatagramError> {
         if data.len() as u64 > self.max_datagram_payload {
-            return Poll::Ready(Err(DatagramError::ExceedsPeerTransportLimits));
+            return Err(DatagramError::ExceedsPeerTransportLimits);
         }
 
         // If there was some connection-level error the user is not allowed to add
         // datagrams to the queue as they will never be sent.
         if let Some(err) = self.error {
-            return Poll::Ready(Err(DatagramError::ConnectionError { error: err }));
+            return Err(DatagramError::ConnectionError { error: err });
         }
 
         if self.queue.len() == self.capacity {
-            self.waker = Some(cx.waker().clone());
-            return Poll::Pending;
+            return Err(DatagramError::QueueAtCapacity);
         }
 
         let datagram = Datagram {
             data: core::mem::replace(data, bytes::Bytes::new()),
         };
         self.queue.push_back(datagram);
-        Poll::Ready(Ok(()))
+        Ok(())
     }
 
     /// Adds datagrams on the queue to be sent
@@ -706,6 +745,44 @@ mod tests {
         assert!(default_sender.queue.is_empty());
     }
 
+    #[test]
+    fn try_send_datagram() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_capacity(1)
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let mut datagram_0 = bytes::Bytes::from_static(&[1, 2, 3]);
+        let mut datagram_1 = bytes::Bytes::from_static(&[4, 5, 6]);
+
+        assert_eq!(default_sender.try_send_datagram(&mut datagram_0), Ok(()));
+        assert!(datagram_0.is_empty());
+
+        // Queue is at capacity so the datagram is handed back to the caller
+        assert_eq!(
+            default_sender.try_send_datagram(&mut datagram_1),
+            Err(DatagramError::QueueAtCapacity)
+        );
+        assert_eq!(datagram_1[..], [4, 5, 6]);
+
+        // No waker is registered
+        assert!(default_sender.waker.is_none());
+
+        let first = default_sender.queue.pop_front().unwrap();
+        assert_eq!(first.data[..], [1, 2, 3]);
+        assert_eq!(default_sender.try_send_datagram(&mut datagram_1), Ok(()));
+
+        // Datagrams larger than the peer's limit are rejected
+        let mut datagram_2 = bytes::Bytes::from(vec![0; 101]);
+        default_sender.queue.clear();
+        assert_eq!(
+            default_sender.try_send_datagram(&mut datagram_2),
+            Err(DatagramError::ExceedsPeerTransportLimits)
+        );
+        assert_eq!(datagram_2.len(), 101);
+    }
+
     #[test]
     fn retain_datagrams() {
         let conn_info = ConnectionInfo {
@@ -913,6 +990,10 @@ mod tests {
             self.remaining_capacity
         }
 
+        fn packet_number(&self) -> u64 {
+            0
+        }
+
         fn write_datagram(&mut self, data: &[u8]) -> Result<(), WriteError> {
             self.write_datagram_vectored(&[data])
         }
@@ -0,0 +1,628 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Forwards unreliable datagrams received on one connection to another connection
+//!
+//! This is intended for building relays and tunnels. Received datagrams are copied once into a
+//! buffer drawn from a [`Pool`], which can be shared across endpoints, and are then written
+//! directly into the packets of the outbound connection without any further allocations.
+//!
+//! Connections are linked with a [`Route`]: the inbound connection's [`Receiver`] pushes
+//! datagrams onto the route and the outbound connection's [`Sender`] drains it as the outbound
+//! connection is able to transmit.
+//!
+//! The route is bounded. When the outbound connection is limited by congestion control the route
+//! fills up, and the datagrams received while it's full are dropped instead of being buffered
+//! without limit. The dropped datagrams are counted in the [`RouteStatistics`].
+//!
+//! QUIC has no flow control for datagrams, so the inbound peer can only be slowed down by the
+//! application that produces them. [`Route::poll_ready`] couples the two connections: it is
+//! pending while the route is full and wakes the inbound side once the outbound connection has
+//! transmitted enough to free up space, so a relay can stop reading from its source (or signal
+//! the peer to slow down) until the route is ready again.
+//!
+//! Each connection is linked by querying its datagram endpoint with `datagram_mut`:
+//!
+//! ```no_run
+//! use s2n_quic_core::datagram::forward;
+//!
+//! let route = forward::Route::new(100);
+//!
+//! // passed to `datagram_mut` on the inbound connection
+//! let link_inbound = |receiver: &mut forward::Receiver| receiver.forward_to(route.clone());
+//!
+//! // passed to `datagram_mut` on the outbound connection
+//! let link_outbound = |sender: &mut forward::Sender| sender.forward_from(route.clone());
+//! # let _ = (link_inbound, link_outbound);
+//! ```
+
+use crate::{
+    connection,
+    datagram::{ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
+    transport::parameters::MaxDatagramFrameSize,
+};
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use bytes::{Bytes, BytesMut};
+use core::task::{Context, Poll, Waker};
+use std::sync::Mutex;
+
+const DEFAULT_SLAB_LEN: usize = 64 * 1024;
+
+/// Buffer space used to hold datagrams while they are being forwarded
+///
+/// Datagrams are carved out of a shared slab. Once all of the datagrams in a slab have been
+/// transmitted and released, the slab's allocation is reused for subsequent datagrams.
+#[derive(Clone, Debug)]
+pub struct Pool {
+    slab: Arc<Mutex<BytesMut>>,
+    slab_len: usize,
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Self::new(DEFAULT_SLAB_LEN)
+    }
+}
+
+impl Pool {
+    /// Creates a pool which allocates slabs of `slab_len` bytes
+    pub fn new(slab_len: usize) -> Self {
+        Self {
+            slab: Arc::new(Mutex::new(BytesMut::with_capacity(slab_len))),
+            slab_len,
+        }
+    }
+
+    /// Copies the datagram into the pool
+    #[inline]
+    fn copy(&self, data: &[u8]) -> Bytes {
+        let mut slab = self.slab.lock().unwrap();
+
+        if slab.capacity() < data.len() {
+            // this reclaims the current allocation if all of the datagrams carved out of it have
+            // been released, otherwise a new slab is allocated
+            slab.reserve(self.slab_len.max(data.len()));
+        }
+
+        slab.extend_from_slice(data);
+        slab.split().freeze()
+    }
+}
+
+/// Counters describing the datagrams which passed through a [`Route`]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RouteStatistics {
+    /// The number of datagrams which were written to the outbound connection
+    pub forwarded: u64,
+
+    /// The number of datagrams which were dropped before reaching the outbound connection
+    ///
+    /// Datagrams are dropped when the route is full, when they exceed the outbound peer's
+    /// limits, or when the outbound connection was closed.
+    pub dropped: u64,
+}
+
+/// A bounded queue of datagrams flowing from one or more connections to an outbound connection
+#[derive(Clone, Debug)]
+pub struct Route(Arc<Mutex<RouteState>>);
+
+#[derive(Debug)]
+struct RouteState {
+    queue: VecDeque<Bytes>,
+    capacity: usize,
+    outbound: Option<Outbound>,
+    /// The inbound tasks waiting for the route to have capacity
+    ingress_wakers: Vec<Waker>,
+    closed: Option<connection::Error>,
+    statistics: RouteStatistics,
+}
+
+impl RouteState {
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+}
+
+#[derive(Debug)]
+struct Outbound {
+    waker: Waker,
+    max_datagram_payload: u64,
+}
+
+impl Route {
+    /// Creates a route which holds at most `capacity` datagrams
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(Mutex::new(RouteState {
+            queue: VecDeque::with_capacity(capacity),
+            capacity,
+            outbound: None,
+            ingress_wakers: Vec::new(),
+            closed: None,
+            statistics: RouteStatistics::default(),
+        })))
+    }
+
+    /// Returns the number of datagrams waiting to be transmitted on the outbound connection
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().queue.len()
+    }
+
+    /// Returns `true` if no datagrams are waiting to be transmitted
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the route is full and newly received datagrams are being dropped
+    ///
+    /// This indicates the outbound connection is unable to keep up with the inbound rate.
+    pub fn is_congested(&self) -> bool {
+        self.0.lock().unwrap().is_full()
+    }
+
+    /// Polls the route for capacity to accept more datagrams
+    ///
+    /// Returns `Poll::Pending` while the route is full, in which case the waker is notified once
+    /// the outbound connection has transmitted at least one of the queued datagrams. An error is
+    /// returned once the outbound connection is closed, since any further datagrams are dropped.
+    pub fn poll_ready(&self, cx: &mut Context) -> Poll<Result<(), connection::Error>> {
+        let mut state = self.0.lock().unwrap();
+
+        if let Some(error) = state.closed {
+            return Poll::Ready(Err(error));
+        }
+
+        if !state.is_full() {
+            return Poll::Ready(Ok(()));
+        }
+
+        if !state
+            .ingress_wakers
+            .iter()
+            .any(|waker| waker.will_wake(cx.waker()))
+        {
+            state.ingress_wakers.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+
+    /// Returns the counters for the route
+    pub fn statistics(&self) -> RouteStatistics {
+        self.0.lock().unwrap().statistics
+    }
+
+    #[inline]
+    fn push(&self, pool: &Pool, data: &[u8]) {
+        let mut state = self.0.lock().unwrap();
+
+        let max_datagram_payload = state
+            .outbound
+            .as_ref()
+            .map_or(u64::MAX, |outbound| outbound.max_datagram_payload);
+
+        if state.closed.is_some() || state.is_full() || data.len() as u64 > max_datagram_payload {
+            state.statistics.dropped += 1;
+            return;
+        }
+
+        state.queue.push_back(pool.copy(data));
+
+        // notify the outbound connection that it has datagrams to send
+        let waker = state
+            .outbound
+            .as_ref()
+            .map(|outbound| outbound.waker.clone());
+        drop(state);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// A datagram endpoint which forwards datagrams between connections
+///
+/// Connections do not forward any datagrams until they are linked with a [`Route`].
+#[derive(Debug, Default)]
+pub struct Endpoint {
+    pool: Pool,
+}
+
+impl Endpoint {
+    /// Creates an endpoint which copies received datagrams into the given pool
+    ///
+    /// The same pool can be used by multiple endpoints.
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+impl super::Endpoint for Endpoint {
+    type Sender = Sender;
+    type Receiver = Receiver;
+
+    fn create_connection(&mut self, info: &ConnectionInfo) -> (Self::Sender, Self::Receiver) {
+        (
+            Sender {
+                route: None,
+                waker: info.waker.clone(),
+                max_datagram_payload: info.max_datagram_payload,
+            },
+            Receiver {
+                route: None,
+                pool: self.pool.clone(),
+            },
+        )
+    }
+
+    fn max_datagram_frame_size(&self, _info: &PreConnectionInfo) -> u64 {
+        MaxDatagramFrameSize::RECOMMENDED
+    }
+}
+
+/// Pushes the datagrams received on a connection onto a [`Route`]
+#[derive(Debug)]
+pub struct Receiver {
+    route: Option<Route>,
+    pool: Pool,
+}
+
+impl Receiver {
+    /// Forwards all subsequently received datagrams to the given route
+    pub fn forward_to(&mut self, route: Route) {
+        self.route = Some(route);
+    }
+
+    /// Stops forwarding received datagrams, returning the previous route
+    pub fn stop_forwarding(&mut self) -> Option<Route> {
+        self.route.take()
+    }
+}
+
+impl super::Receiver for Receiver {
+    #[inline]
+    fn on_datagram(&mut self, _: &ReceiveContext, datagram: &[u8]) {
+        if let Some(route) = self.route.as_ref() {
+            route.push(&self.pool, datagram);
+        }
+    }
+
+    fn on_connection_error(&mut self, _error: connection::Error) {
+        // any datagrams which are already on the route are still transmitted
+        self.route = None;
+    }
+}
+
+/// Transmits the datagrams queued on a [`Route`]
+#[derive(Debug)]
+pub struct Sender {
+    route: Option<Route>,
+    waker: Waker,
+    max_datagram_payload: u64,
+}
+
+impl Sender {
+    /// Transmits the datagrams queued on the given route on this connection
+    ///
+    /// A route can only have a single outbound connection; any previous outbound connection
+    /// stops transmitting datagrams from the route.
+    pub fn forward_from(&mut self, route: Route) {
+        {
+            let mut state = route.0.lock().unwrap();
+            state.outbound = Some(Outbound {
+                waker: self.waker.clone(),
+                max_datagram_payload: self.max_datagram_payload,
+            });
+
+            // drop anything that was queued for a peer with larger limits
+            let max_datagram_payload = self.max_datagram_payload;
+            let len = state.queue.len();
+            state
+                .queue
+                .retain(|datagram| datagram.len() as u64 <= max_datagram_payload);
+            state.statistics.dropped += (len - state.queue.len()) as u64;
+        }
+
+        if !route.is_empty() {
+            self.waker.wake_by_ref();
+        }
+
+        self.route = Some(route);
+    }
+
+    /// Stops transmitting datagrams from the route, returning the previous route
+    pub fn stop_forwarding(&mut self) -> Option<Route> {
+        let route = self.route.take()?;
+        route.0.lock().unwrap().outbound = None;
+        Some(route)
+    }
+}
+
+impl super::Sender for Sender {
+    fn on_transmit<P: Packet>(&mut self, packet: &mut P) {
+        // Cede space to stream data when datagrams are not prioritized
+        if packet.has_pending_streams() && !packet.datagrams_prioritized() {
+            return;
+        }
+
+        let Some(route) = self.route.as_ref() else {
+            return;
+        };
+
+        let mut state = route.0.lock().unwrap();
+        let was_full = state.is_full();
+        let mut has_written = false;
+
+        while let Some(datagram) = state.queue.front() {
+            if packet.remaining_capacity() < datagram.len() {
+                // Leave the datagram for the next packet if this one already has datagrams in it.
+                // Otherwise the datagram can't fit in a packet on this path so it's dropped,
+                // rather than blocking the rest of the route.
+                if has_written {
+                    break;
+                }
+                state.queue.pop_front();
+                state.statistics.dropped += 1;
+                continue;
+            }
+
+            let datagram = state.queue.pop_front().unwrap();
+            match packet.write_datagram(&datagram) {
+                Ok(()) => {
+                    state.statistics.forwarded += 1;
+                    has_written = true;
+                }
+                Err(_) => state.statistics.dropped += 1,
+            }
+        }
+
+        // let the inbound side know the route has capacity again
+        if was_full && !state.is_full() {
+            let wakers = core::mem::take(&mut state.ingress_wakers);
+            drop(state);
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+    }
+
+    #[inline]
+    fn has_transmission_interest(&self) -> bool {
+        self.route.as_ref().map_or(false, |route| !route.is_empty())
+    }
+
+    fn on_connection_error(&mut self, error: connection::Error) {
+        if let Some(route) = self.route.take() {
+            let mut state = route.0.lock().unwrap();
+            state.outbound = None;
+            state.closed = Some(error);
+            state.statistics.dropped += state.queue.len() as u64;
+            state.queue.clear();
+
+            // the inbound side shouldn't wait on a route which will never drain
+            let wakers = core::mem::take(&mut state.ingress_wakers);
+            drop(state);
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datagram::{Endpoint as _, Receiver as _, Sender as _, WriteError};
+    use futures_test::task::{new_count_waker, noop_waker};
+
+    fn fake_receive_context() -> ReceiveContext<'static> {
+        ReceiveContext {
+            path: crate::event::api::Path {
+                local_addr: crate::event::api::SocketAddress::IpV4 {
+                    ip: &[0; 4],
+                    port: 0,
+                },
+                local_cid: crate::event::api::ConnectionId { bytes: &[] },
+                remote_addr: crate::event::api::SocketAddress::IpV4 {
+                    ip: &[0; 4],
+                    port: 0,
+                },
+                remote_cid: crate::event::api::ConnectionId { bytes: &[] },
+                id: 0,
+                is_active: true,
+            },
+        }
+    }
+
+    #[derive(Default)]
+    struct MockPacket {
+        remaining_capacity: usize,
+        written: Vec<Vec<u8>>,
+    }
+
+    impl Packet for MockPacket {
+        fn remaining_capacity(&self) -> usize {
+            self.remaining_capacity
+        }
+
+        fn packet_number(&self) -> u64 {
+            0
+        }
+
+        fn write_datagram(&mut self, data: &[u8]) -> Result<(), WriteError> {
+            self.write_datagram_vectored(&[data])
+        }
+
+        fn write_datagram_vectored(&mut self, data: &[&[u8]]) -> Result<(), WriteError> {
+            let data = data.concat();
+            if data.len() > self.remaining_capacity {
+                return Err(WriteError::ExceedsPacketCapacity);
+            }
+            self.remaining_capacity -= data.len();
+            self.written.push(data);
+            Ok(())
+        }
+
+        fn has_pending_streams(&self) -> bool {
+            false
+        }
+
+        fn datagrams_prioritized(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn forward_test() {
+        let pool = Pool::new(16);
+        let mut inbound = Endpoint::new(pool.clone());
+        let mut outbound = Endpoint::new(pool);
+
+        let (_, mut receiver) = inbound.create_connection(&ConnectionInfo::new(100, noop_waker()));
+        let (waker, wake_count) = new_count_waker();
+        let (mut sender, _) = outbound.create_connection(&ConnectionInfo::new(4, waker));
+
+        let ctx = fake_receive_context();
+
+        // datagrams aren't forwarded until a route is configured
+        receiver.on_datagram(&ctx, &[1, 2, 3]);
+
+        let route = Route::new(2);
+        receiver.forward_to(route.clone());
+        sender.forward_from(route.clone());
+        assert!(!sender.has_transmission_interest());
+
+        receiver.on_datagram(&ctx, &[1, 2, 3]);
+        assert_eq!(wake_count.get(), 1);
+        // the datagram exceeds the outbound peer's limits
+        receiver.on_datagram(&ctx, &[1, 2, 3, 4, 5]);
+        receiver.on_datagram(&ctx, &[4, 5, 6]);
+        assert!(route.is_congested());
+        // the route is full
+        receiver.on_datagram(&ctx, &[7, 8, 9]);
+
+        assert!(sender.has_transmission_interest());
+
+        let mut packet = MockPacket {
+            remaining_capacity: 5,
+            ..Default::default()
+        };
+        sender.on_transmit(&mut packet);
+        assert_eq!(packet.written, [vec![1, 2, 3]]);
+        assert!(sender.has_transmission_interest());
+
+        let mut packet = MockPacket {
+            remaining_capacity: 5,
+            ..Default::default()
+        };
+        sender.on_transmit(&mut packet);
+        assert_eq!(packet.written, [vec![4, 5, 6]]);
+        assert!(!sender.has_transmission_interest());
+
+        assert_eq!(
+            route.statistics(),
+            RouteStatistics {
+                forwarded: 2,
+                dropped: 2,
+            }
+        );
+
+        // once the outbound connection is closed, nothing else is queued
+        sender.on_connection_error(connection::Error::closed(crate::endpoint::Location::Remote));
+        receiver.on_datagram(&ctx, &[1, 2, 3]);
+        assert!(route.is_empty());
+        assert_eq!(route.statistics().dropped, 3);
+    }
+
+    #[test]
+    fn oversized_datagram_test() {
+        let mut endpoint = Endpoint::default();
+        let (_, mut receiver) = endpoint.create_connection(&ConnectionInfo::new(100, noop_waker()));
+        let (mut sender, _) = endpoint.create_connection(&ConnectionInfo::new(100, noop_waker()));
+        let ctx = fake_receive_context();
+
+        let route = Route::new(4);
+        receiver.forward_to(route.clone());
+        sender.forward_from(route.clone());
+
+        // the first datagram is within the peer's limits but can't fit in a packet
+        receiver.on_datagram(&ctx, &[1; 10]);
+        receiver.on_datagram(&ctx, &[2; 4]);
+
+        let mut packet = MockPacket {
+            remaining_capacity: 8,
+            ..Default::default()
+        };
+        sender.on_transmit(&mut packet);
+        assert_eq!(packet.written, [vec![2; 4]]);
+        assert!(!sender.has_transmission_interest());
+        assert_eq!(
+            route.statistics(),
+            RouteStatistics {
+                forwarded: 1,
+                dropped: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn poll_ready_test() {
+        let mut endpoint = Endpoint::default();
+        let (_, mut receiver) = endpoint.create_connection(&ConnectionInfo::new(100, noop_waker()));
+        let (mut sender, _) = endpoint.create_connection(&ConnectionInfo::new(100, noop_waker()));
+        let ctx = fake_receive_context();
+
+        let route = Route::new(1);
+        receiver.forward_to(route.clone());
+        sender.forward_from(route.clone());
+
+        let (waker, wake_count) = new_count_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(matches!(route.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+
+        receiver.on_datagram(&ctx, &[1, 2, 3]);
+        assert!(route.poll_ready(&mut cx).is_pending());
+        // polling again doesn't register the same waker twice
+        assert!(route.poll_ready(&mut cx).is_pending());
+
+        // transmitting the queued datagram wakes the inbound side
+        let mut packet = MockPacket {
+            remaining_capacity: 5,
+            ..Default::default()
+        };
+        sender.on_transmit(&mut packet);
+        assert_eq!(wake_count.get(), 1);
+        assert!(matches!(route.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+
+        // closing the outbound connection wakes the inbound side with an error
+        receiver.on_datagram(&ctx, &[4, 5, 6]);
+        assert!(route.poll_ready(&mut cx).is_pending());
+        sender.on_connection_error(connection::Error::closed(crate::endpoint::Location::Remote));
+        assert_eq!(wake_count.get(), 2);
+        assert!(matches!(route.poll_ready(&mut cx), Poll::Ready(Err(_))));
+    }
+
+    #[test]
+    fn pool_reuse_test() {
+        let pool = Pool::new(8);
+
+        let a = pool.copy(&[1; 4]);
+        let b = pool.copy(&[2; 4]);
+        assert_eq!(&a[..], &[1; 4]);
+        assert_eq!(&b[..], &[2; 4]);
+        let ptr = a.as_ptr();
+        drop(a);
+        drop(b);
+
+        // the slab is reclaimed once all of the datagrams are released
+        let c = pool.copy(&[3; 4]);
+        assert_eq!(&c[..], &[3; 4]);
+        assert_eq!(c.as_ptr(), ptr);
+
+        // datagrams larger than the slab are still supported
+        let d = pool.copy(&[4; 16]);
+        assert_eq!(&d[..], &[4; 16]);
+    }
+}
@@ -95,6 +95,27 @@ pub trait Sender: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// A callback used to notify the application that a packet containing datagrams was
+    /// acknowledged by the peer
+    ///
+    /// The `packet_number` is the value returned by [`Packet::packet_number`] when the datagrams
+    /// were written in [`Sender::on_transmit`]. Each packet is reported as either acknowledged or
+    /// lost at most once.
+    #[inline]
+    fn on_packet_ack(&mut self, packet_number: u64) {
+        let _ = packet_number;
+    }
+
+    /// A callback used to notify the application that a packet containing datagrams was
+    /// declared lost
+    ///
+    /// Datagrams are never retransmitted by the transport. Applications can use this signal to
+    /// drive their own recovery or rate adaptation, e.g. by adjusting forward error correction.
+    #[inline]
+    fn on_packet_loss(&mut self, packet_number: u64) {
+        let _ = packet_number;
+    }
 }
 
 /// A packet will be available during the on_transmit callback. Use the methods
@@ -103,6 +124,12 @@ pub trait Packet {
     /// Returns the remaining space in the packet left to write datagrams
     fn remaining_capacity(&self) -> usize;
 
+    /// Returns the number of the packet being written
+    ///
+    /// If any datagrams are written to the packet, the same value will be passed to either
+    /// [`Sender::on_packet_ack`] or [`Sender::on_packet_loss`] once the outcome is known.
+    fn packet_number(&self) -> u64;
+
     /// Writes a single datagram to a packet. This function should be called
     /// per datagram.
     fn write_datagram(&mut self, data: &[u8]) -> Result<(), WriteError>;
@@ -0,0 +1,265 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Application-defined challenges which a client must solve before a server allocates any
+//! connection state
+//!
+//! When the endpoint limits return [`Outcome::challenge()`](super::limits::Outcome::challenge),
+//! the server generates a challenge with [`Limiter::generate_challenge`] and delivers it to the
+//! client in the token of a Retry packet. A client configured with a [`Solver`] responds with the
+//! solution in the token of its next Initial packet, which is checked with
+//! [`Limiter::verify_challenge`] before the connection is created. This can be used to require
+//! clients to perform a proof-of-work while the server is under load.
+//!
+//! Every Retry token issued by the server starts with a tag byte which describes how the rest of
+//! the token is framed:
+//!
+//! * `0x00` - the address token follows the tag
+//! * `0x01` - a length-prefixed challenge follows the tag, and then the address token
+//!
+//! Clients respond to a challenge with a token tagged with `0x02`, followed by the length-prefixed
+//! challenge, the length-prefixed solution and then the address token. Since the address token
+//! is always preceded by the tag, tokens generated by the address token provider can contain any
+//! bytes without being confused with a challenge.
+//!
+//! [`Limiter::generate_challenge`]: super::limits::Limiter::generate_challenge
+//! [`Limiter::verify_challenge`]: super::limits::Limiter::verify_challenge
+
+use s2n_codec::{DecoderBuffer, Encoder, EncoderBuffer};
+
+/// The maximum length of a challenge or a solution
+pub const MAX_LEN: usize = u8::MAX as usize;
+
+const ADDRESS_TOKEN_TAG: u8 = 0x00;
+const CHALLENGE_TAG: u8 = 0x01;
+const RESPONSE_TAG: u8 = 0x02;
+
+/// Solves the challenges issued by servers
+///
+/// Returning `None` leaves the challenge unsolved, in which case the server will drop the
+/// connection attempt.
+///
+/// Retry tokens which start with the challenge tag are passed to the solver, so a solver should
+/// only be configured for servers which are known to issue challenges.
+#[cfg(feature = "alloc")]
+pub trait Solver: 'static + Send + Sync {
+    fn solve(&self, challenge: &[u8]) -> Option<alloc::vec::Vec<u8>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<F> Solver for F
+where
+    F: 'static + Send + Sync + Fn(&[u8]) -> Option<alloc::vec::Vec<u8>>,
+{
+    #[inline]
+    fn solve(&self, challenge: &[u8]) -> Option<alloc::vec::Vec<u8>> {
+        (self)(challenge)
+    }
+}
+
+/// Returns the encoded length of a Retry token prefix for a challenge of `len` bytes
+///
+/// An empty challenge is encoded as the prefix of a plain address token.
+#[inline]
+pub fn encoding_size(len: usize) -> usize {
+    if len == 0 {
+        1
+    } else {
+        2 + len
+    }
+}
+
+/// Writes the prefix of a Retry token carrying the `challenge`
+///
+/// An empty challenge writes the prefix of a plain address token. The address token should be
+/// written immediately after the prefix.
+#[inline]
+pub fn encode_prefix(challenge: &[u8], buffer: &mut EncoderBuffer) {
+    debug_assert!(challenge.len() <= MAX_LEN);
+
+    if challenge.is_empty() {
+        buffer.encode(&ADDRESS_TOKEN_TAG);
+        return;
+    }
+
+    buffer.encode(&CHALLENGE_TAG);
+    buffer.encode(&(challenge.len() as u8));
+    buffer.write_slice(challenge);
+}
+
+/// A token received by a server in an Initial packet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// An address token which was issued without a challenge
+    Address(&'a [u8]),
+    /// A challenge which the client echoed back without solving it
+    Unsolved(Challenge<'a>),
+    /// A client's response to a challenge
+    Response(Response<'a>),
+}
+
+impl<'a> Token<'a> {
+    /// Decodes a token, returning `None` if it wasn't framed by the endpoint
+    #[inline]
+    pub fn decode(token: &'a [u8]) -> Option<Self> {
+        let (tag, address_token) = token.split_first()?;
+
+        match *tag {
+            ADDRESS_TOKEN_TAG => Some(Self::Address(address_token)),
+            CHALLENGE_TAG => Challenge::decode(token).map(Self::Unsolved),
+            RESPONSE_TAG => Response::decode(token).map(Self::Response),
+            _ => None,
+        }
+    }
+
+    /// Returns the address token which was framed in the token
+    #[inline]
+    pub fn address_token(&self) -> &'a [u8] {
+        match self {
+            Self::Address(token) => token,
+            Self::Unsolved(challenge) => challenge.token,
+            Self::Response(response) => response.token,
+        }
+    }
+}
+
+/// A challenge which was delivered in a Retry token
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Challenge<'a> {
+    pub challenge: &'a [u8],
+    /// The address token following the challenge
+    pub token: &'a [u8],
+}
+
+impl<'a> Challenge<'a> {
+    /// Decodes a Retry token, returning `None` if it doesn't carry a challenge
+    #[inline]
+    pub fn decode(token: &'a [u8]) -> Option<Self> {
+        let buffer = DecoderBuffer::new(token);
+        let (tag, buffer) = buffer.decode::<u8>().ok()?;
+        if tag != CHALLENGE_TAG {
+            return None;
+        }
+        let (challenge, buffer) = buffer.decode_slice_with_len_prefix::<u8>().ok()?;
+
+        Some(Self {
+            challenge: challenge.into_less_safe_slice(),
+            token: buffer.into_less_safe_slice(),
+        })
+    }
+
+    /// Solves the challenge and returns the token to send in the next Initial packet
+    ///
+    /// Returns `None` if the solver didn't produce a solution.
+    #[cfg(feature = "alloc")]
+    pub fn respond<S: Solver + ?Sized>(&self, solver: &S) -> Option<alloc::vec::Vec<u8>> {
+        let solution = solver.solve(self.challenge)?;
+        if solution.len() > MAX_LEN {
+            return None;
+        }
+
+        let len = 1 + (1 + self.challenge.len()) + (1 + solution.len()) + self.token.len();
+        let mut response = alloc::vec![0; len];
+        let mut buffer = EncoderBuffer::new(&mut response);
+        buffer.encode(&RESPONSE_TAG);
+        buffer.encode(&(self.challenge.len() as u8));
+        buffer.write_slice(self.challenge);
+        buffer.encode(&(solution.len() as u8));
+        buffer.write_slice(&solution);
+        buffer.write_slice(self.token);
+
+        Some(response)
+    }
+}
+
+/// A client's response to a challenge, delivered in the token of an Initial packet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Response<'a> {
+    pub challenge: &'a [u8],
+    pub solution: &'a [u8],
+    /// The address token which was issued with the challenge
+    pub token: &'a [u8],
+}
+
+impl<'a> Response<'a> {
+    /// Decodes an Initial token, returning `None` if it doesn't carry a response
+    #[inline]
+    pub fn decode(token: &'a [u8]) -> Option<Self> {
+        let buffer = DecoderBuffer::new(token);
+        let (tag, buffer) = buffer.decode::<u8>().ok()?;
+        if tag != RESPONSE_TAG {
+            return None;
+        }
+        let (challenge, buffer) = buffer.decode_slice_with_len_prefix::<u8>().ok()?;
+        let (solution, buffer) = buffer.decode_slice_with_len_prefix::<u8>().ok()?;
+
+        Some(Self {
+            challenge: challenge.into_less_safe_slice(),
+            solution: solution.into_less_safe_slice(),
+            token: buffer.into_less_safe_slice(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(challenge: &[u8], address_token: &[u8]) -> Vec<u8> {
+        let mut token = vec![0u8; encoding_size(challenge.len()) + address_token.len()];
+        let mut buffer = EncoderBuffer::new(&mut token);
+        encode_prefix(challenge, &mut buffer);
+        assert_eq!(buffer.len(), encoding_size(challenge.len()));
+        buffer.write_slice(address_token);
+        token
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let token = encode(b"nonce", b"address");
+
+        let challenge = Challenge::decode(&token).unwrap();
+        assert_eq!(challenge.challenge, b"nonce");
+        assert_eq!(challenge.token, b"address");
+        assert_eq!(Token::decode(&token), Some(Token::Unsolved(challenge)));
+
+        // the tokens can't be confused with each other
+        assert_eq!(Response::decode(&token), None);
+
+        let solver = |challenge: &[u8]| Some(challenge.iter().rev().copied().collect());
+        let response = challenge.respond(&solver).unwrap();
+        assert_eq!(Challenge::decode(&response), None);
+
+        let decoded = Response::decode(&response).unwrap();
+        assert_eq!(decoded.challenge, b"nonce");
+        assert_eq!(decoded.solution, b"ecnon");
+        assert_eq!(decoded.token, b"address");
+        assert_eq!(Token::decode(&response), Some(Token::Response(decoded)));
+
+        // a solver can refuse to solve a challenge
+        let solver = |_: &[u8]| None;
+        assert_eq!(challenge.respond(&solver), None);
+    }
+
+    #[test]
+    fn address_token_test() {
+        // address tokens can start with any byte, including the challenge and response tags
+        for first in [ADDRESS_TOKEN_TAG, CHALLENGE_TAG, RESPONSE_TAG, 0xfe, 0xff] {
+            let address_token = [first, 1, 2, 3];
+            let token = encode(&[], &address_token);
+            assert_eq!(token.len(), encoding_size(0) + address_token.len());
+
+            let decoded = Token::decode(&token).unwrap();
+            assert_eq!(decoded, Token::Address(&address_token[..]));
+            assert_eq!(decoded.address_token(), &address_token[..]);
+
+            // plain address tokens don't carry a challenge
+            assert_eq!(Challenge::decode(&token), None);
+            assert_eq!(Response::decode(&token), None);
+        }
+
+        // tokens which weren't framed by the endpoint are rejected
+        assert_eq!(Token::decode(&[]), None);
+        assert_eq!(Token::decode(&[0xff, 1, 2, 3]), None);
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
 /// use information from the ConnectionAttempt object to determine how the library should handle
 /// the connection attempt
 #[non_exhaustive]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Outcome {
     /// Allow the connection to continue
     ///
@@ -22,19 +22,25 @@ pub enum Outcome {
     ///
     /// Use `Outcome::retry()` to construct this variant
     #[non_exhaustive]
-    Retry,
+    Retry { reason: Option<&'static str> },
 
     /// Silently drop the connection attempt
     ///
     /// Use `Outcome::drop()` to construct this variant
     #[non_exhaustive]
-    Drop,
+    Drop { reason: Option<&'static str> },
 
     /// Cleanly close the connection
     ///
     /// Use `Outcome::close()` to construct this variant
     #[non_exhaustive]
-    Close,
+    Close { reason: Option<&'static str> },
+
+    /// Defer the connection until the peer solves a challenge
+    ///
+    /// Use `Outcome::challenge()` to construct this variant
+    #[non_exhaustive]
+    Challenge { reason: Option<&'static str> },
 }
 
 impl Outcome {
@@ -45,17 +51,55 @@ impl Outcome {
 
     /// Defer the connection by sending a Retry packet
     pub fn retry() -> Self {
-        Self::Retry
+        Self::Retry { reason: None }
     }
 
     /// Silently drop the connection attempt
     pub fn drop() -> Self {
-        Self::Drop
+        Self::Drop { reason: None }
     }
 
     /// Cleanly close the connection
     pub fn close() -> Self {
-        Self::Close
+        Self::Close { reason: None }
+    }
+
+    /// Defer the connection by sending a Retry packet carrying a challenge for the peer to solve
+    ///
+    /// The challenge is generated with [`Limiter::generate_challenge`]. If the limiter doesn't
+    /// generate one, a regular Retry packet is sent instead. Attempts which were already
+    /// validated can't be sent another Retry packet, so they're allowed to continue if they
+    /// solved a challenge and dropped otherwise.
+    pub fn challenge() -> Self {
+        Self::Challenge { reason: None }
+    }
+
+    /// Attaches a reason to an outcome which doesn't allow the connection
+    ///
+    /// The reason is reported in the `EndpointConnectionAttemptRejected` event. It has no effect
+    /// on `Outcome::allow()`.
+    pub fn with_reason(mut self, reason: &'static str) -> Self {
+        match &mut self {
+            Self::Retry { reason: r }
+            | Self::Drop { reason: r }
+            | Self::Close { reason: r }
+            | Self::Challenge { reason: r } => {
+                *r = Some(reason);
+            }
+            Self::Allow => {}
+        }
+        self
+    }
+
+    /// Returns the reason attached with [`Self::with_reason`], if any
+    pub fn reason(&self) -> Option<&'static str> {
+        match self {
+            Self::Retry { reason }
+            | Self::Drop { reason }
+            | Self::Close { reason }
+            | Self::Challenge { reason } => *reason,
+            Self::Allow => None,
+        }
     }
 }
 
@@ -75,6 +119,19 @@ pub struct ConnectionAttempt<'a> {
     /// This address comes from the datagram
     pub remote_address: SocketAddress<'a>,
     pub timestamp: Timestamp,
+
+    /// Whether the peer's address was validated with a token, such as one from a Retry packet
+    ///
+    /// A validated address can't be validated again, so returning `Outcome::retry()` for a
+    /// validated attempt allows the connection to continue.
+    ///
+    /// An attempt which was sent a Retry packet is passed to the limiter again when the peer
+    /// comes back with the token. Limiters which count attempts should skip counting them on
+    /// this second pass so each connection is only charged once.
+    pub is_address_validated: bool,
+
+    /// Whether the peer solved a challenge issued with `Outcome::challenge()`
+    pub is_challenge_solved: bool,
 }
 
 impl<'a> ConnectionAttempt<'a> {
@@ -90,6 +147,133 @@ impl<'a> ConnectionAttempt<'a> {
             connection_count,
             remote_address: remote_address.into_event(),
             timestamp,
+            is_address_validated: false,
+            is_challenge_solved: false,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn with_address_validated(mut self, is_address_validated: bool) -> Self {
+        self.is_address_validated = is_address_validated;
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn with_challenge_solved(mut self, is_challenge_solved: bool) -> Self {
+        self.is_challenge_solved = is_challenge_solved;
+        self
+    }
+}
+
+/// A ChallengeResponse holds a peer's solution to a challenge generated by
+/// [`Limiter::generate_challenge`]
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ChallengeResponse<'a> {
+    /// The unverified address of the peer
+    /// This address comes from the datagram
+    pub remote_address: SocketAddress<'a>,
+
+    /// The challenge which the peer claims to have been issued
+    ///
+    /// The server doesn't store the challenges it issues, so this must be authenticated by the
+    /// limiter before the solution is checked.
+    pub challenge: &'a [u8],
+
+    /// The peer's solution to the challenge
+    pub solution: &'a [u8],
+
+    pub timestamp: Timestamp,
+}
+
+impl<'a> ChallengeResponse<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: &'a inet::SocketAddress,
+        challenge: &'a [u8],
+        solution: &'a [u8],
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            challenge,
+            solution,
+            timestamp,
+        }
+    }
+}
+
+/// A DatagramAttempt holds information about a datagram received by the endpoint before it is
+/// parsed. This can be used to cheaply drop datagrams from abusive peers.
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct DatagramAttempt<'a> {
+    /// The unverified address of the peer
+    /// This address comes from the datagram
+    pub remote_address: SocketAddress<'a>,
+
+    /// The length of the datagram payload
+    pub payload_len: usize,
+
+    pub timestamp: Timestamp,
+}
+
+impl<'a> DatagramAttempt<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: &'a inet::SocketAddress,
+        payload_len: usize,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            payload_len,
+            timestamp,
+        }
+    }
+}
+
+/// A ConnectionOpened holds information about a connection which was allowed by the endpoint
+/// and is now holding connection state
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ConnectionOpened<'a> {
+    /// The address of the peer at the time of the connection attempt
+    pub remote_address: SocketAddress<'a>,
+
+    pub timestamp: Timestamp,
+}
+
+impl<'a> ConnectionOpened<'a> {
+    #[doc(hidden)]
+    pub fn new(remote_address: &'a inet::SocketAddress, timestamp: Timestamp) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            timestamp,
+        }
+    }
+}
+
+/// A ConnectionClosed holds information about a previously opened connection which was removed
+/// from the endpoint
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ConnectionClosed<'a> {
+    /// The address of the peer at the time of the connection attempt
+    ///
+    /// This is the same address which was reported in the corresponding `ConnectionOpened`, even
+    /// if the peer migrated to another address afterwards.
+    pub remote_address: SocketAddress<'a>,
+
+    pub timestamp: Timestamp,
+}
+
+impl<'a> ConnectionClosed<'a> {
+    #[doc(hidden)]
+    pub fn new(remote_address: &'a inet::SocketAddress, timestamp: Timestamp) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            timestamp,
         }
     }
 }
@@ -99,6 +283,9 @@ pub trait Limiter: 'static + Send {
     /// implementor returns an Outcome based on the ConnectionAttempt, or other information that the
     /// implementor may have.
     ///
+    /// This is called for each new connection attempt, including attempts which carry a token
+    /// validating the peer's address.
+    ///
     /// ```rust
     /// # mod s2n_quic { pub mod provider { pub mod endpoint_limits { pub use s2n_quic_core::endpoint::limits::*; } } }
     /// use s2n_quic::provider::endpoint_limits::{Limiter, ConnectionAttempt, Outcome};
@@ -119,4 +306,52 @@ pub trait Limiter: 'static + Send {
     /// }
     /// ```
     fn on_connection_attempt(&mut self, info: &ConnectionAttempt) -> Outcome;
+
+    /// Called for every datagram received by the endpoint before it is parsed
+    ///
+    /// Returning `false` will drop the datagram. Since this is called in the receive fast path,
+    /// implementations should be inexpensive.
+    #[inline]
+    fn on_datagram(&mut self, info: &DatagramAttempt) -> bool {
+        let _ = info;
+        true
+    }
+
+    /// Called when a server connection is created after the connection attempt was allowed
+    ///
+    /// Every call is eventually followed by a call to `on_connection_closed` with the same remote
+    /// address, which allows implementations to track the number of open connections per peer.
+    #[inline]
+    fn on_connection_opened(&mut self, info: &ConnectionOpened) {
+        let _ = info;
+    }
+
+    /// Called when a server connection which was previously opened is removed from the endpoint
+    #[inline]
+    fn on_connection_closed(&mut self, info: &ConnectionClosed) {
+        let _ = info;
+    }
+
+    /// Called to generate the challenge for an attempt which returned `Outcome::challenge()`
+    ///
+    /// The challenge is written to `output`, which holds up to
+    /// [`challenge::MAX_LEN`](super::challenge::MAX_LEN) bytes, and its length is returned. The
+    /// server doesn't keep any state for challenged attempts, so the challenge should be
+    /// authenticated, e.g. with a MAC, and include an expiration time. Returning `None` sends a
+    /// regular Retry packet instead.
+    #[inline]
+    fn generate_challenge(&mut self, info: &ConnectionAttempt, output: &mut [u8]) -> Option<usize> {
+        let _ = (info, output);
+        None
+    }
+
+    /// Called when a peer responds to a challenge
+    ///
+    /// Returning `false` drops the connection attempt. Otherwise, the attempt is passed to
+    /// `on_connection_attempt` with `is_challenge_solved` set.
+    #[inline]
+    fn verify_challenge(&mut self, info: &ChallengeResponse) -> bool {
+        let _ = info;
+        false
+    }
 }
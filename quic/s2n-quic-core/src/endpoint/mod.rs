@@ -12,6 +12,7 @@ use core::{
     task::{Context, Poll},
 };
 
+pub mod challenge;
 pub mod limits;
 pub use limits::Limiter;
 
@@ -132,6 +133,23 @@ pub trait Endpoint: 'static + Send + Sized {
     /// Sets configuration for the maximum transmission unit (MTU) that can be sent on a path
     fn set_mtu_config(&mut self, mtu_config: mtu::Config);
 
+    /// Sets the MTU clamps applied to paths with matching peer addresses
+    ///
+    /// The most specific matching clamp bounds the MTU configuration of each new path.
+    #[inline]
+    fn set_mtu_clamps(&mut self, clamps: &[mtu::Clamp]) {
+        let _ = clamps;
+    }
+
+    /// Sets the local ports which client connections can be assigned to
+    ///
+    /// If more than one port is provided, each new client connection is assigned to a random port
+    /// in the set. Endpoints which don't open client connections can ignore the value.
+    #[inline]
+    fn set_local_ports(&mut self, ports: &[u16]) {
+        let _ = ports;
+    }
+
     /// Returns the endpoint's event subscriber
     fn subscriber(&mut self) -> &mut Self::Subscriber;
 }
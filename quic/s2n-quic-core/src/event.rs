@@ -5,6 +5,7 @@ use crate::{connection, endpoint};
 use core::{ops::RangeInclusive, time::Duration};
 
 mod generated;
+pub mod profile;
 pub use generated::*;
 
 /// All event types which can be emitted from this library.
@@ -103,7 +104,9 @@ impl Timestamp {
     /// # let meta: event::api::ConnectionMeta = event::builder::ConnectionMeta {
     /// #     endpoint_type: endpoint::Type::Server,
     /// #     id: 0,
+    /// #     attempt_id: 0,
     /// #     timestamp: unsafe { Timestamp::from_duration(Duration::from_secs(1) )},
+    /// #     application_context: Default::default(),
     /// # }.into_event();
     /// let event_time = start_time + meta.timestamp.duration_since_start();
     /// ```
@@ -165,3 +168,47 @@ impl core::fmt::Debug for TlsSession<'_> {
         f.debug_struct("TlsSession").finish_non_exhaustive()
     }
 }
+
+/// The context attached to a connection by the application
+///
+/// This allows subscribers to correlate connection events with application state, e.g. a
+/// request identifier, without maintaining a separate map keyed by the connection id.
+#[derive(Clone, Copy, Default)]
+pub struct ApplicationContext<'a> {
+    context: Option<&'a (dyn core::any::Any + Send)>,
+}
+
+impl<'a> ApplicationContext<'a> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(context: Option<&'a (dyn core::any::Any + Send)>) -> ApplicationContext<'a> {
+        ApplicationContext { context }
+    }
+
+    /// Returns the context if it has been set by the application and is of type `T`
+    #[inline]
+    pub fn get<T: core::any::Any>(&self) -> Option<&'a T> {
+        self.context?.downcast_ref()
+    }
+
+    /// Returns `true` if the application has set a context on the connection
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        self.context.is_some()
+    }
+}
+
+impl<'a> crate::event::IntoEvent<ApplicationContext<'a>> for ApplicationContext<'a> {
+    #[inline]
+    fn into_event(self) -> Self {
+        self
+    }
+}
+
+impl core::fmt::Debug for ApplicationContext<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ApplicationContext")
+            .field("is_set", &self.is_set())
+            .finish()
+    }
+}
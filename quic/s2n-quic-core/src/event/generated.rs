@@ -12,20 +12,40 @@ pub mod api {
     pub use traits::Subscriber;
     #[derive(Clone, Debug)]
     #[non_exhaustive]
-    pub struct ConnectionMeta {
+    pub struct ConnectionMeta<'a> {
         pub endpoint_type: EndpointType,
         pub id: u64,
+        #[doc = " Identifies the connection attempt the connection was created for"]
+        #[doc = ""]
+        #[doc = " The value is derived from the original destination connection ID, so it also matches the"]
+        #[doc = " endpoint events which were published for the attempt before the connection existed, e.g."]
+        #[doc = " when sending a Retry or Version Negotiation packet."]
+        pub attempt_id: u64,
         pub timestamp: crate::event::Timestamp,
+        #[doc = " The context attached to the connection by the application, if any"]
+        pub application_context: crate::event::ApplicationContext<'a>,
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
     pub struct EndpointMeta {
         pub endpoint_type: EndpointType,
         pub timestamp: crate::event::Timestamp,
+        #[doc = " Identifies the connection attempt the event is associated with, if any"]
+        #[doc = ""]
+        #[doc = " See [`ConnectionMeta::attempt_id`]."]
+        pub attempt_id: Option<u64>,
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
-    pub struct ConnectionInfo {}
+    pub struct ConnectionInfo<'a> {
+        pub remote_address: SocketAddress<'a>,
+        #[doc = " The server name requested by the application"]
+        #[doc = ""]
+        #[doc = " Servers don't learn the name until the ClientHello is processed, so it's not available"]
+        #[doc = " when the connection context is created. It's provided to subscribers afterwards with"]
+        #[doc = " `on_connection_info_updated`."]
+        pub server_name: Option<&'a str>,
+    }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
     pub struct TransportParameters<'a> {
@@ -202,6 +222,8 @@ pub mod api {
         HandshakeDone {},
         #[non_exhaustive]
         Datagram { len: u16 },
+        #[non_exhaustive]
+        ObservedAddress { sequence_number: u64 },
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -323,6 +345,17 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds {},
+        #[non_exhaustive]
+        #[doc = " The datagram was rejected by the endpoint limits before it was parsed."]
+        #[doc = ""]
+        #[doc = " This occurs when the peer's address has been blocked."]
+        Blocked {},
+        #[non_exhaustive]
+        #[doc = " The datagram payload exceeded the `max_udp_payload_size` advertised to the peer."]
+        ExceedsMaxUdpPayloadSize {},
+        #[non_exhaustive]
+        #[doc = " The peer's solution to a challenge issued by the endpoint limits was rejected."]
+        InvalidChallengeResponse {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -454,6 +487,8 @@ pub mod api {
         IpScopeChange {},
         #[non_exhaustive]
         ConnectionMigrationDisabled {},
+        #[non_exhaustive]
+        Application {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -498,6 +533,20 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The state of a client's attempt to resume a previous TLS session"]
+    pub enum SessionResumptionStatus {
+        #[non_exhaustive]
+        #[doc = " A session ticket was offered to the server"]
+        Attempted {},
+        #[non_exhaustive]
+        #[doc = " The server accepted the offered session ticket"]
+        Accepted {},
+        #[non_exhaustive]
+        #[doc = " The server rejected the offered session ticket"]
+        Rejected {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The source that caused a congestion event"]
     pub enum CongestionSource {
         #[non_exhaustive]
@@ -551,6 +600,23 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The policy which determined the contents of a PTO probe packet"]
+    pub enum ProbePolicy {
+        #[non_exhaustive]
+        #[doc = " New data, followed by any unacknowledged data which fit in the remaining space"]
+        Default {},
+        #[non_exhaustive]
+        #[doc = " Only data which was still unacknowledged"]
+        RetransmitUnacknowledged {},
+        #[non_exhaustive]
+        #[doc = " Only data which hadn't been transmitted yet, along with any data which was declared lost"]
+        NewData {},
+        #[non_exhaustive]
+        #[doc = " Only a PING frame, along with any pending ACK frame"]
+        PingOnly {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The reason the MTU was updated"]
     pub enum MtuUpdatedCause {
         #[non_exhaustive]
@@ -586,6 +652,78 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The congestion control algorithm used on a path, along with its parameters"]
+    pub enum CongestionControlAlgorithm {
+        #[non_exhaustive]
+        #[doc = " CUBIC, as specified in RFC 8312"]
+        Cubic {
+            #[doc = " The multiplicative window decrease factor"]
+            beta: f32,
+            #[doc = " The scaling constant which determines the aggressiveness of window growth"]
+            c: f32,
+        },
+        #[non_exhaustive]
+        #[doc = " NewReno, as specified in RFC 9002"]
+        NewReno {
+            #[doc = " The multiplicative window decrease factor"]
+            beta: f32,
+        },
+        #[non_exhaustive]
+        #[doc = " BBRv2"]
+        Bbr {},
+        #[non_exhaustive]
+        #[doc = " A congestion controller provided by the application"]
+        Other {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " How packets from multiple packet number spaces were combined into a datagram"]
+    pub enum CoalescingDecision {
+        #[non_exhaustive]
+        #[doc = " The packets were coalesced up to the maximum datagram size of the path"]
+        Coalesced {},
+        #[non_exhaustive]
+        #[doc = " The packets were coalesced into a datagram limited to `max_len` bytes"]
+        Limited { max_len: u16 },
+        #[non_exhaustive]
+        #[doc = " Coalescing is disabled, so only the first packet was written and the rest were deferred to"]
+        #[doc = " later datagrams"]
+        Disabled {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " How the endpoint responded to a connection attempt which wasn't allowed to continue"]
+    pub enum ConnectionAttemptAction {
+        #[non_exhaustive]
+        #[doc = " A Retry packet was sent to validate the peer's address"]
+        Retry {},
+        #[non_exhaustive]
+        #[doc = " The attempt was silently dropped"]
+        Drop {},
+        #[non_exhaustive]
+        #[doc = " The attempt was refused"]
+        Close {},
+        #[non_exhaustive]
+        #[doc = " A Retry packet was sent carrying a challenge for the peer to solve"]
+        Challenge {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The reason data in the Initial or Handshake packet number space is retransmitted"]
+    pub enum HandshakeRetransmissionReason {
+        #[non_exhaustive]
+        #[doc = " A packet was declared lost"]
+        PacketLost { packet_number: u64, bytes_lost: u16 },
+        #[non_exhaustive]
+        #[doc = " The PTO timer expired while ack-eliciting packets were in flight"]
+        ProbeTimeout { pto_backoff: u32 },
+        #[non_exhaustive]
+        #[doc = " The PTO timer expired with nothing in flight, so a probe is sent to keep the peer from"]
+        #[doc = " being blocked by its anti-amplification limit"]
+        AntiDeadlock { pto_backoff: u32 },
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Application level protocol"]
     pub struct ApplicationProtocolInformation<'a> {
         pub chosen_application_protocol: &'a [u8],
@@ -706,6 +844,29 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " An RTT sample was generated from an acknowledgement"]
+    #[doc = ""]
+    #[doc = " Samples are emitted in every packet number space, so the RTT is available as soon as the"]
+    #[doc = " first Initial packet is acknowledged, rather than only after the handshake completes."]
+    pub struct RttSample<'a> {
+        pub path: Path<'a>,
+        #[doc = " The header of the largest acknowledged packet, which generated the sample"]
+        pub packet_header: PacketHeader,
+        #[doc = " The time between sending the packet and receiving its acknowledgement"]
+        pub latest_rtt: Duration,
+        #[doc = " The time the peer reported delaying the acknowledgement"]
+        #[doc = ""]
+        #[doc = " During the handshake this includes the time the peer spent processing the packet, so"]
+        #[doc = " subtracting it from `latest_rtt` separates the network RTT from the peer's processing time."]
+        pub ack_delay: Duration,
+        pub min_rtt: Duration,
+        pub smoothed_rtt: Duration,
+    }
+    impl<'a> Event for RttSample<'a> {
+        const NAME: &'static str = "recovery:rtt_sample";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Congestion (ECN or packet loss) has occurred"]
     pub struct Congestion<'a> {
         pub path: Path<'a>,
@@ -857,6 +1018,10 @@ pub mod api {
     #[doc = " Datagram received by a connection"]
     pub struct DatagramReceived {
         pub len: u16,
+        #[doc = " The index of the IO provider's receive queue the datagram was read from, if known"]
+        #[doc = ""]
+        #[doc = " This is not the NIC's RSS queue or the CPU which received the datagram."]
+        pub queue_id: Option<u32>,
     }
     impl Event for DatagramReceived {
         const NAME: &'static str = "transport:datagram_received";
@@ -930,6 +1095,15 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " Emitted when a client offers a session ticket and when the server responds to it"]
+    pub struct SessionResumption {
+        pub status: SessionResumptionStatus,
+    }
+    impl Event for SessionResumption {
+        const NAME: &'static str = "security:session_resumption";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub struct TlsClientHello<'a> {
         pub payload: &'a [&'a [u8]],
     }
@@ -946,6 +1120,33 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The client's ClientHello did not fit in a single datagram and was split across multiple"]
+    #[doc = " Initial packets"]
+    pub struct ClientHelloFragmented {
+        #[doc = " The length of the ClientHello message"]
+        pub len: u64,
+        #[doc = " The number of datagrams used to transmit the ClientHello"]
+        pub datagram_count: u16,
+    }
+    impl Event for ClientHelloFragmented {
+        const NAME: &'static str = "transport:client_hello_fragmented";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " Packets from more than one packet number space were ready to be sent in the same datagram"]
+    pub struct DatagramCoalesced {
+        #[doc = " The number of packets which were written into the datagram"]
+        pub packet_count: u8,
+        #[doc = " The length of the datagram"]
+        pub len: u16,
+        #[doc = " How the connection decided to coalesce the packets"]
+        pub decision: CoalescingDecision,
+    }
+    impl Event for DatagramCoalesced {
+        const NAME: &'static str = "transport:datagram_coalesced";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub struct RxStreamProgress {
         pub bytes: usize,
     }
@@ -962,6 +1163,52 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " A packet carrying bytes from a marked range of a stream was sent"]
+    pub struct StreamMarkerSent {
+        pub stream_id: u64,
+        pub marker: u64,
+        pub packet_header: PacketHeader,
+        #[doc = " `true` if the bytes had been declared lost and are being retransmitted"]
+        pub is_retransmission: bool,
+    }
+    impl Event for StreamMarkerSent {
+        const NAME: &'static str = "transport:stream_marker_sent";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " Bytes from a marked range of a stream were declared lost"]
+    pub struct StreamMarkerLost {
+        pub stream_id: u64,
+        pub marker: u64,
+    }
+    impl Event for StreamMarkerLost {
+        const NAME: &'static str = "transport:stream_marker_lost";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " All of the bytes in a marked range of a stream have been acknowledged by the peer"]
+    pub struct StreamMarkerAcknowledged {
+        pub stream_id: u64,
+        pub marker: u64,
+    }
+    impl Event for StreamMarkerAcknowledged {
+        const NAME: &'static str = "transport:stream_marker_acknowledged";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The peer opened a stream"]
+    #[doc = ""]
+    #[doc = " Opening a stream implicitly opens all lower stream IDs of the same type, so an event is"]
+    #[doc = " emitted for each of those streams as well."]
+    pub struct PeerStreamOpened {
+        pub id: u64,
+        pub stream_type: StreamType,
+    }
+    impl Event for PeerStreamOpened {
+        const NAME: &'static str = "transport:peer_stream_opened";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub struct KeepAliveTimerExpired {
         pub timeout: Duration,
     }
@@ -970,6 +1217,27 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The connection released its transient buffers after being idle for the hibernation timeout"]
+    pub struct ConnectionHibernated {
+        pub timeout: Duration,
+    }
+    impl Event for ConnectionHibernated {
+        const NAME: &'static str = "connectivity::connection_hibernated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The connection stopped making progress and is being closed"]
+    #[doc = ""]
+    #[doc = " This is only checked in debug builds. The report describes the timers and transmission"]
+    #[doc = " interest of each of the connection's components."]
+    pub struct ConnectionStalled<'a> {
+        pub report: &'a str,
+    }
+    impl<'a> Event for ConnectionStalled<'a> {
+        const NAME: &'static str = "connectivity::connection_stalled";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The maximum transmission unit (MTU) for the path has changed"]
     pub struct MtuUpdated {
         pub path_id: u64,
@@ -993,6 +1261,36 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " A probe packet was sent after the PTO timer expired"]
+    pub struct PtoProbeSent {
+        pub packet_header: PacketHeader,
+        #[doc = " The policy which determined the contents of the probe"]
+        pub policy: ProbePolicy,
+        #[doc = " The number of stream bytes which were transmitted for the first time"]
+        pub new_stream_bytes: u64,
+        #[doc = " The number of stream bytes which were retransmitted"]
+        pub retransmitted_stream_bytes: u64,
+    }
+    impl Event for PtoProbeSent {
+        const NAME: &'static str = "recovery:pto_probe_sent";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " Data in the Initial or Handshake packet number space needs to be retransmitted"]
+    #[doc = ""]
+    #[doc = " These spaces are only used before the handshake is confirmed, so the event makes it possible"]
+    #[doc = " to tell why a handshake is taking longer than expected."]
+    pub struct HandshakeRetransmission {
+        #[doc = " The key space of the data being retransmitted"]
+        pub space: KeySpace,
+        #[doc = " The reason the data needs to be retransmitted"]
+        pub reason: HandshakeRetransmissionReason,
+    }
+    impl Event for HandshakeRetransmission {
+        const NAME: &'static str = "recovery:handshake_retransmission";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " A new delivery rate sample has been generated"]
     #[doc = " Note: This event is only recorded for congestion controllers that support"]
     #[doc = "       bandwidth estimates, such as BBR"]
@@ -1027,6 +1325,16 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " A congestion controller has been initialized for a path"]
+    pub struct CongestionControllerInitialized {
+        pub path_id: u64,
+        pub algorithm: CongestionControlAlgorithm,
+    }
+    impl Event for CongestionControllerInitialized {
+        const NAME: &'static str = "recovery:congestion_controller_initialized";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -1099,6 +1407,32 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " A connection attempt was not allowed to continue by the endpoint limits"]
+    pub struct EndpointConnectionAttemptRejected<'a> {
+        #[doc = " How the endpoint responded to the attempt"]
+        pub action: ConnectionAttemptAction,
+        #[doc = " The reason provided by the endpoint limits, if any"]
+        pub reason: Option<&'a str>,
+        #[doc = " Whether the peer's address was validated with a token"]
+        pub is_address_validated: bool,
+    }
+    impl<'a> Event for EndpointConnectionAttemptRejected<'a> {
+        const NAME: &'static str = "transport:connection_attempt_rejected";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " A client connection attempt failed and will be retried after the backoff"]
+    pub struct EndpointConnectionAttemptRetried {
+        #[doc = " The number of attempts which have been made, including the failed one"]
+        pub attempts: u32,
+        pub backoff: Duration,
+        pub error: crate::connection::Error,
+    }
+    impl Event for EndpointConnectionAttemptRetried {
+        const NAME: &'static str = "transport:connection_attempt_retried";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Emitted when the platform sends at least one packet"]
     pub struct PlatformTx {
         #[doc = " The number of packets sent"]
@@ -1564,6 +1898,14 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::ObservedAddress {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ObservedAddress {
+                sequence_number: self.sequence_number.as_u64(),
+            }
+        }
+    }
     impl IntoEvent<builder::StreamType> for &crate::stream::StreamType {
         #[inline]
         fn into_event(self) -> builder::StreamType {
@@ -1678,7 +2020,7 @@ pub mod tracing {
                 api::EndpointType::Client {} => self.client.id(),
                 api::EndpointType::Server {} => self.server.id(),
             };
-            tracing :: span ! (target : "s2n_quic" , parent : parent , tracing :: Level :: DEBUG , "conn" , id = meta . id)
+            tracing :: span ! (target : "s2n_quic" , parent : parent , tracing :: Level :: DEBUG , "conn" , id = meta . id , attempt_id = meta . attempt_id)
         }
         #[inline]
         fn on_application_protocol_information(
@@ -1835,6 +2177,24 @@ pub mod tracing {
             tracing :: event ! (target : "recovery_metrics" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , min_rtt = tracing :: field :: debug (min_rtt) , smoothed_rtt = tracing :: field :: debug (smoothed_rtt) , latest_rtt = tracing :: field :: debug (latest_rtt) , rtt_variance = tracing :: field :: debug (rtt_variance) , max_ack_delay = tracing :: field :: debug (max_ack_delay) , pto_count = tracing :: field :: debug (pto_count) , congestion_window = tracing :: field :: debug (congestion_window) , bytes_in_flight = tracing :: field :: debug (bytes_in_flight) , congestion_limited = tracing :: field :: debug (congestion_limited));
         }
         #[inline]
+        fn on_rtt_sample(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::RttSample,
+        ) {
+            let id = context.id();
+            let api::RttSample {
+                path,
+                packet_header,
+                latest_rtt,
+                ack_delay,
+                min_rtt,
+                smoothed_rtt,
+            } = event;
+            tracing :: event ! (target : "rtt_sample" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , packet_header = tracing :: field :: debug (packet_header) , latest_rtt = tracing :: field :: debug (latest_rtt) , ack_delay = tracing :: field :: debug (ack_delay) , min_rtt = tracing :: field :: debug (min_rtt) , smoothed_rtt = tracing :: field :: debug (smoothed_rtt));
+        }
+        #[inline]
         fn on_congestion(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2008,8 +2368,8 @@ pub mod tracing {
             event: &api::DatagramReceived,
         ) {
             let id = context.id();
-            let api::DatagramReceived { len } = event;
-            tracing :: event ! (target : "datagram_received" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len));
+            let api::DatagramReceived { len, queue_id } = event;
+            tracing :: event ! (target : "datagram_received" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , queue_id = tracing :: field :: debug (queue_id));
         }
         #[inline]
         fn on_datagram_dropped(
@@ -2098,6 +2458,17 @@ pub mod tracing {
             tracing :: event ! (target : "path_challenge_updated" , parent : id , tracing :: Level :: DEBUG , path_challenge_status = tracing :: field :: debug (path_challenge_status) , path = tracing :: field :: debug (path) , challenge_data = tracing :: field :: debug (challenge_data));
         }
         #[inline]
+        fn on_session_resumption(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::SessionResumption,
+        ) {
+            let id = context.id();
+            let api::SessionResumption { status } = event;
+            tracing :: event ! (target : "session_resumption" , parent : id , tracing :: Level :: DEBUG , status = tracing :: field :: debug (status));
+        }
+        #[inline]
         fn on_tls_client_hello(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2120,6 +2491,35 @@ pub mod tracing {
             tracing :: event ! (target : "tls_server_hello" , parent : id , tracing :: Level :: DEBUG , payload = tracing :: field :: debug (payload));
         }
         #[inline]
+        fn on_client_hello_fragmented(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ClientHelloFragmented,
+        ) {
+            let id = context.id();
+            let api::ClientHelloFragmented {
+                len,
+                datagram_count,
+            } = event;
+            tracing :: event ! (target : "client_hello_fragmented" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , datagram_count = tracing :: field :: debug (datagram_count));
+        }
+        #[inline]
+        fn on_datagram_coalesced(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::DatagramCoalesced,
+        ) {
+            let id = context.id();
+            let api::DatagramCoalesced {
+                packet_count,
+                len,
+                decision,
+            } = event;
+            tracing :: event ! (target : "datagram_coalesced" , parent : id , tracing :: Level :: DEBUG , packet_count = tracing :: field :: debug (packet_count) , len = tracing :: field :: debug (len) , decision = tracing :: field :: debug (decision));
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2142,6 +2542,55 @@ pub mod tracing {
             tracing :: event ! (target : "tx_stream_progress" , parent : id , tracing :: Level :: DEBUG , bytes = tracing :: field :: debug (bytes));
         }
         #[inline]
+        fn on_stream_marker_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::StreamMarkerSent,
+        ) {
+            let id = context.id();
+            let api::StreamMarkerSent {
+                stream_id,
+                marker,
+                packet_header,
+                is_retransmission,
+            } = event;
+            tracing :: event ! (target : "stream_marker_sent" , parent : id , tracing :: Level :: DEBUG , stream_id = tracing :: field :: debug (stream_id) , marker = tracing :: field :: debug (marker) , packet_header = tracing :: field :: debug (packet_header) , is_retransmission = tracing :: field :: debug (is_retransmission));
+        }
+        #[inline]
+        fn on_stream_marker_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::StreamMarkerLost,
+        ) {
+            let id = context.id();
+            let api::StreamMarkerLost { stream_id, marker } = event;
+            tracing :: event ! (target : "stream_marker_lost" , parent : id , tracing :: Level :: DEBUG , stream_id = tracing :: field :: debug (stream_id) , marker = tracing :: field :: debug (marker));
+        }
+        #[inline]
+        fn on_stream_marker_acknowledged(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::StreamMarkerAcknowledged,
+        ) {
+            let id = context.id();
+            let api::StreamMarkerAcknowledged { stream_id, marker } = event;
+            tracing :: event ! (target : "stream_marker_acknowledged" , parent : id , tracing :: Level :: DEBUG , stream_id = tracing :: field :: debug (stream_id) , marker = tracing :: field :: debug (marker));
+        }
+        #[inline]
+        fn on_peer_stream_opened(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PeerStreamOpened,
+        ) {
+            let id = context.id();
+            let api::PeerStreamOpened { id, stream_type } = event;
+            tracing :: event ! (target : "peer_stream_opened" , parent : id , tracing :: Level :: DEBUG , id = tracing :: field :: debug (id) , stream_type = tracing :: field :: debug (stream_type));
+        }
+        #[inline]
         fn on_keep_alive_timer_expired(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2153,6 +2602,28 @@ pub mod tracing {
             tracing :: event ! (target : "keep_alive_timer_expired" , parent : id , tracing :: Level :: DEBUG , timeout = tracing :: field :: debug (timeout));
         }
         #[inline]
+        fn on_connection_hibernated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ConnectionHibernated,
+        ) {
+            let id = context.id();
+            let api::ConnectionHibernated { timeout } = event;
+            tracing :: event ! (target : "connection_hibernated" , parent : id , tracing :: Level :: DEBUG , timeout = tracing :: field :: debug (timeout));
+        }
+        #[inline]
+        fn on_connection_stalled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ConnectionStalled,
+        ) {
+            let id = context.id();
+            let api::ConnectionStalled { report } = event;
+            tracing :: event ! (target : "connection_stalled" , parent : id , tracing :: Level :: DEBUG , report = tracing :: field :: debug (report));
+        }
+        #[inline]
         fn on_mtu_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2183,6 +2654,33 @@ pub mod tracing {
             tracing :: event ! (target : "slow_start_exited" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , cause = tracing :: field :: debug (cause) , congestion_window = tracing :: field :: debug (congestion_window));
         }
         #[inline]
+        fn on_pto_probe_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PtoProbeSent,
+        ) {
+            let id = context.id();
+            let api::PtoProbeSent {
+                packet_header,
+                policy,
+                new_stream_bytes,
+                retransmitted_stream_bytes,
+            } = event;
+            tracing :: event ! (target : "pto_probe_sent" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , policy = tracing :: field :: debug (policy) , new_stream_bytes = tracing :: field :: debug (new_stream_bytes) , retransmitted_stream_bytes = tracing :: field :: debug (retransmitted_stream_bytes));
+        }
+        #[inline]
+        fn on_handshake_retransmission(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::HandshakeRetransmission,
+        ) {
+            let id = context.id();
+            let api::HandshakeRetransmission { space, reason } = event;
+            tracing :: event ! (target : "handshake_retransmission" , parent : id , tracing :: Level :: DEBUG , space = tracing :: field :: debug (space) , reason = tracing :: field :: debug (reason));
+        }
+        #[inline]
         fn on_delivery_rate_sampled(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2224,6 +2722,17 @@ pub mod tracing {
             tracing :: event ! (target : "bbr_state_changed" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , state = tracing :: field :: debug (state));
         }
         #[inline]
+        fn on_congestion_controller_initialized(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::CongestionControllerInitialized,
+        ) {
+            let id = context.id();
+            let api::CongestionControllerInitialized { path_id, algorithm } = event;
+            tracing :: event ! (target : "congestion_controller_initialized" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , algorithm = tracing :: field :: debug (algorithm));
+        }
+        #[inline]
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -2238,7 +2747,7 @@ pub mod tracing {
                 client_versions,
                 chosen_version,
             } = event;
-            tracing :: event ! (target : "version_information" , parent : parent , tracing :: Level :: DEBUG , server_versions = tracing :: field :: debug (server_versions) , client_versions = tracing :: field :: debug (client_versions) , chosen_version = tracing :: field :: debug (chosen_version));
+            tracing :: event ! (target : "version_information" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , server_versions = tracing :: field :: debug (server_versions) , client_versions = tracing :: field :: debug (client_versions) , chosen_version = tracing :: field :: debug (chosen_version));
         }
         #[inline]
         fn on_endpoint_packet_sent(
@@ -2251,7 +2760,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointPacketSent { packet_header } = event;
-            tracing :: event ! (target : "endpoint_packet_sent" , parent : parent , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header));
+            tracing :: event ! (target : "endpoint_packet_sent" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , packet_header = tracing :: field :: debug (packet_header));
         }
         #[inline]
         fn on_endpoint_packet_received(
@@ -2264,7 +2773,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointPacketReceived { packet_header } = event;
-            tracing :: event ! (target : "endpoint_packet_received" , parent : parent , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header));
+            tracing :: event ! (target : "endpoint_packet_received" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , packet_header = tracing :: field :: debug (packet_header));
         }
         #[inline]
         fn on_endpoint_datagram_sent(
@@ -2277,7 +2786,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointDatagramSent { len, gso_offset } = event;
-            tracing :: event ! (target : "endpoint_datagram_sent" , parent : parent , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , gso_offset = tracing :: field :: debug (gso_offset));
+            tracing :: event ! (target : "endpoint_datagram_sent" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , len = tracing :: field :: debug (len) , gso_offset = tracing :: field :: debug (gso_offset));
         }
         #[inline]
         fn on_endpoint_datagram_received(
@@ -2290,7 +2799,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointDatagramReceived { len } = event;
-            tracing :: event ! (target : "endpoint_datagram_received" , parent : parent , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len));
+            tracing :: event ! (target : "endpoint_datagram_received" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , len = tracing :: field :: debug (len));
         }
         #[inline]
         fn on_endpoint_datagram_dropped(
@@ -2303,7 +2812,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointDatagramDropped { len, reason } = event;
-            tracing :: event ! (target : "endpoint_datagram_dropped" , parent : parent , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , reason = tracing :: field :: debug (reason));
+            tracing :: event ! (target : "endpoint_datagram_dropped" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , len = tracing :: field :: debug (len) , reason = tracing :: field :: debug (reason));
         }
         #[inline]
         fn on_endpoint_connection_attempt_failed(
@@ -2316,25 +2825,59 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointConnectionAttemptFailed { error } = event;
-            tracing :: event ! (target : "endpoint_connection_attempt_failed" , parent : parent , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error));
+            tracing :: event ! (target : "endpoint_connection_attempt_failed" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , error = tracing :: field :: debug (error));
         }
         #[inline]
-        fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptRejected,
+        ) {
             let parent = match meta.endpoint_type {
                 api::EndpointType::Client {} => self.client.id(),
                 api::EndpointType::Server {} => self.server.id(),
             };
-            let api::PlatformTx { count } = event;
-            tracing :: event ! (target : "platform_tx" , parent : parent , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
+            let api::EndpointConnectionAttemptRejected {
+                action,
+                reason,
+                is_address_validated,
+            } = event;
+            tracing :: event ! (target : "endpoint_connection_attempt_rejected" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , action = tracing :: field :: debug (action) , reason = tracing :: field :: debug (reason) , is_address_validated = tracing :: field :: debug (is_address_validated));
         }
         #[inline]
-        fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptRetried,
+        ) {
             let parent = match meta.endpoint_type {
                 api::EndpointType::Client {} => self.client.id(),
                 api::EndpointType::Server {} => self.server.id(),
             };
-            let api::PlatformTxError { errno } = event;
-            tracing :: event ! (target : "platform_tx_error" , parent : parent , tracing :: Level :: DEBUG , errno = tracing :: field :: debug (errno));
+            let api::EndpointConnectionAttemptRetried {
+                attempts,
+                backoff,
+                error,
+            } = event;
+            tracing :: event ! (target : "endpoint_connection_attempt_retried" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , attempts = tracing :: field :: debug (attempts) , backoff = tracing :: field :: debug (backoff) , error = tracing :: field :: debug (error));
+        }
+        #[inline]
+        fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::PlatformTx { count } = event;
+            tracing :: event ! (target : "platform_tx" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , count = tracing :: field :: debug (count));
+        }
+        #[inline]
+        fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::PlatformTxError { errno } = event;
+            tracing :: event ! (target : "platform_tx_error" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , errno = tracing :: field :: debug (errno));
         }
         #[inline]
         fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
@@ -2343,7 +2886,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformRx { count } = event;
-            tracing :: event ! (target : "platform_rx" , parent : parent , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
+            tracing :: event ! (target : "platform_rx" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , count = tracing :: field :: debug (count));
         }
         #[inline]
         fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
@@ -2352,7 +2895,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformRxError { errno } = event;
-            tracing :: event ! (target : "platform_rx_error" , parent : parent , tracing :: Level :: DEBUG , errno = tracing :: field :: debug (errno));
+            tracing :: event ! (target : "platform_rx_error" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , errno = tracing :: field :: debug (errno));
         }
         #[inline]
         fn on_platform_feature_configured(
@@ -2365,7 +2908,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformFeatureConfigured { configuration } = event;
-            tracing :: event ! (target : "platform_feature_configured" , parent : parent , tracing :: Level :: DEBUG , configuration = tracing :: field :: debug (configuration));
+            tracing :: event ! (target : "platform_feature_configured" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , configuration = tracing :: field :: debug (configuration));
         }
         #[inline]
         fn on_platform_event_loop_wakeup(
@@ -2383,7 +2926,7 @@ pub mod tracing {
                 tx_ready,
                 application_wakeup,
             } = event;
-            tracing :: event ! (target : "platform_event_loop_wakeup" , parent : parent , tracing :: Level :: DEBUG , timeout_expired = tracing :: field :: debug (timeout_expired) , rx_ready = tracing :: field :: debug (rx_ready) , tx_ready = tracing :: field :: debug (tx_ready) , application_wakeup = tracing :: field :: debug (application_wakeup));
+            tracing :: event ! (target : "platform_event_loop_wakeup" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , timeout_expired = tracing :: field :: debug (timeout_expired) , rx_ready = tracing :: field :: debug (rx_ready) , tx_ready = tracing :: field :: debug (tx_ready) , application_wakeup = tracing :: field :: debug (application_wakeup));
         }
         #[inline]
         fn on_platform_event_loop_sleep(
@@ -2399,30 +2942,42 @@ pub mod tracing {
                 timeout,
                 processing_duration,
             } = event;
-            tracing :: event ! (target : "platform_event_loop_sleep" , parent : parent , tracing :: Level :: DEBUG , timeout = tracing :: field :: debug (timeout) , processing_duration = tracing :: field :: debug (processing_duration));
+            tracing :: event ! (target : "platform_event_loop_sleep" , parent : parent , tracing :: Level :: DEBUG , attempt_id = tracing :: field :: debug (meta . attempt_id) , timeout = tracing :: field :: debug (timeout) , processing_duration = tracing :: field :: debug (processing_duration));
         }
     }
 }
 pub mod builder {
     use super::*;
     #[derive(Clone, Debug)]
-    pub struct ConnectionMeta {
+    pub struct ConnectionMeta<'a> {
         pub endpoint_type: crate::endpoint::Type,
         pub id: u64,
+        #[doc = " Identifies the connection attempt the connection was created for"]
+        #[doc = ""]
+        #[doc = " The value is derived from the original destination connection ID, so it also matches the"]
+        #[doc = " endpoint events which were published for the attempt before the connection existed, e.g."]
+        #[doc = " when sending a Retry or Version Negotiation packet."]
+        pub attempt_id: u64,
         pub timestamp: crate::time::Timestamp,
+        #[doc = " The context attached to the connection by the application, if any"]
+        pub application_context: crate::event::ApplicationContext<'a>,
     }
-    impl IntoEvent<api::ConnectionMeta> for ConnectionMeta {
+    impl<'a> IntoEvent<api::ConnectionMeta<'a>> for ConnectionMeta<'a> {
         #[inline]
-        fn into_event(self) -> api::ConnectionMeta {
+        fn into_event(self) -> api::ConnectionMeta<'a> {
             let ConnectionMeta {
                 endpoint_type,
                 id,
+                attempt_id,
                 timestamp,
+                application_context,
             } = self;
             api::ConnectionMeta {
                 endpoint_type: endpoint_type.into_event(),
                 id: id.into_event(),
+                attempt_id: attempt_id.into_event(),
                 timestamp: timestamp.into_event(),
+                application_context: application_context.into_event(),
             }
         }
     }
@@ -2430,6 +2985,10 @@ pub mod builder {
     pub struct EndpointMeta {
         pub endpoint_type: crate::endpoint::Type,
         pub timestamp: crate::time::Timestamp,
+        #[doc = " Identifies the connection attempt the event is associated with, if any"]
+        #[doc = ""]
+        #[doc = " See [`ConnectionMeta::attempt_id`]."]
+        pub attempt_id: Option<u64>,
     }
     impl IntoEvent<api::EndpointMeta> for EndpointMeta {
         #[inline]
@@ -2437,20 +2996,36 @@ pub mod builder {
             let EndpointMeta {
                 endpoint_type,
                 timestamp,
+                attempt_id,
             } = self;
             api::EndpointMeta {
                 endpoint_type: endpoint_type.into_event(),
                 timestamp: timestamp.into_event(),
+                attempt_id: attempt_id.into_event(),
             }
         }
     }
     #[derive(Clone, Debug)]
-    pub struct ConnectionInfo {}
-    impl IntoEvent<api::ConnectionInfo> for ConnectionInfo {
+    pub struct ConnectionInfo<'a> {
+        pub remote_address: SocketAddress<'a>,
+        #[doc = " The server name requested by the application"]
+        #[doc = ""]
+        #[doc = " Servers don't learn the name until the ClientHello is processed, so it's not available"]
+        #[doc = " when the connection context is created. It's provided to subscribers afterwards with"]
+        #[doc = " `on_connection_info_updated`."]
+        pub server_name: Option<&'a str>,
+    }
+    impl<'a> IntoEvent<api::ConnectionInfo<'a>> for ConnectionInfo<'a> {
         #[inline]
-        fn into_event(self) -> api::ConnectionInfo {
-            let ConnectionInfo {} = self;
-            api::ConnectionInfo {}
+        fn into_event(self) -> api::ConnectionInfo<'a> {
+            let ConnectionInfo {
+                remote_address,
+                server_name,
+            } = self;
+            api::ConnectionInfo {
+                remote_address: remote_address.into_event(),
+                server_name: server_name.into_event(),
+            }
         }
     }
     #[derive(Clone, Debug)]
@@ -2771,6 +3346,9 @@ pub mod builder {
         Datagram {
             len: u16,
         },
+        ObservedAddress {
+            sequence_number: u64,
+        },
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -2865,6 +3443,9 @@ pub mod builder {
                 Self::Datagram { len } => Datagram {
                     len: len.into_event(),
                 },
+                Self::ObservedAddress { sequence_number } => ObservedAddress {
+                    sequence_number: sequence_number.into_event(),
+                },
             }
         }
     }
@@ -3037,6 +3618,14 @@ pub mod builder {
         PathLimitExceeded,
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds,
+        #[doc = " The datagram was rejected by the endpoint limits before it was parsed."]
+        #[doc = ""]
+        #[doc = " This occurs when the peer's address has been blocked."]
+        Blocked,
+        #[doc = " The datagram payload exceeded the `max_udp_payload_size` advertised to the peer."]
+        ExceedsMaxUdpPayloadSize,
+        #[doc = " The peer's solution to a challenge issued by the endpoint limits was rejected."]
+        InvalidChallengeResponse,
     }
     impl IntoEvent<api::DatagramDropReason> for DatagramDropReason {
         #[inline]
@@ -3055,6 +3644,9 @@ pub mod builder {
                 Self::RejectedConnectionMigration => RejectedConnectionMigration {},
                 Self::PathLimitExceeded => PathLimitExceeded {},
                 Self::InsufficientConnectionIds => InsufficientConnectionIds {},
+                Self::Blocked => Blocked {},
+                Self::ExceedsMaxUdpPayloadSize => ExceedsMaxUdpPayloadSize {},
+                Self::InvalidChallengeResponse => InvalidChallengeResponse {},
             }
         }
     }
@@ -3261,6 +3853,7 @@ pub mod builder {
         PortScopeChanged,
         IpScopeChange,
         ConnectionMigrationDisabled,
+        Application,
     }
     impl IntoEvent<api::MigrationDenyReason> for MigrationDenyReason {
         #[inline]
@@ -3271,6 +3864,7 @@ pub mod builder {
                 Self::PortScopeChanged => PortScopeChanged {},
                 Self::IpScopeChange => IpScopeChange {},
                 Self::ConnectionMigrationDisabled => ConnectionMigrationDisabled {},
+                Self::Application => Application {},
             }
         }
     }
@@ -3330,6 +3924,27 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The state of a client's attempt to resume a previous TLS session"]
+    pub enum SessionResumptionStatus {
+        #[doc = " A session ticket was offered to the server"]
+        Attempted,
+        #[doc = " The server accepted the offered session ticket"]
+        Accepted,
+        #[doc = " The server rejected the offered session ticket"]
+        Rejected,
+    }
+    impl IntoEvent<api::SessionResumptionStatus> for SessionResumptionStatus {
+        #[inline]
+        fn into_event(self) -> api::SessionResumptionStatus {
+            use api::SessionResumptionStatus::*;
+            match self {
+                Self::Attempted => Attempted {},
+                Self::Accepted => Accepted {},
+                Self::Rejected => Rejected {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The source that caused a congestion event"]
     pub enum CongestionSource {
         #[doc = " Explicit Congestion Notification"]
@@ -3411,6 +4026,30 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The policy which determined the contents of a PTO probe packet"]
+    pub enum ProbePolicy {
+        #[doc = " New data, followed by any unacknowledged data which fit in the remaining space"]
+        Default,
+        #[doc = " Only data which was still unacknowledged"]
+        RetransmitUnacknowledged,
+        #[doc = " Only data which hadn't been transmitted yet, along with any data which was declared lost"]
+        NewData,
+        #[doc = " Only a PING frame, along with any pending ACK frame"]
+        PingOnly,
+    }
+    impl IntoEvent<api::ProbePolicy> for ProbePolicy {
+        #[inline]
+        fn into_event(self) -> api::ProbePolicy {
+            use api::ProbePolicy::*;
+            match self {
+                Self::Default => Default {},
+                Self::RetransmitUnacknowledged => RetransmitUnacknowledged {},
+                Self::NewData => NewData {},
+                Self::PingOnly => PingOnly {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The reason the MTU was updated"]
     pub enum MtuUpdatedCause {
         #[doc = " The MTU was initialized with the default value"]
@@ -3460,6 +4099,123 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The congestion control algorithm used on a path, along with its parameters"]
+    pub enum CongestionControlAlgorithm {
+        #[doc = " CUBIC, as specified in RFC 8312"]
+        Cubic {
+            #[doc = " The multiplicative window decrease factor"]
+            beta: f32,
+            #[doc = " The scaling constant which determines the aggressiveness of window growth"]
+            c: f32,
+        },
+        #[doc = " NewReno, as specified in RFC 9002"]
+        NewReno {
+            #[doc = " The multiplicative window decrease factor"]
+            beta: f32,
+        },
+        #[doc = " BBRv2"]
+        Bbr,
+        #[doc = " A congestion controller provided by the application"]
+        Other,
+    }
+    impl IntoEvent<api::CongestionControlAlgorithm> for CongestionControlAlgorithm {
+        #[inline]
+        fn into_event(self) -> api::CongestionControlAlgorithm {
+            use api::CongestionControlAlgorithm::*;
+            match self {
+                Self::Cubic { beta, c } => Cubic {
+                    beta: beta.into_event(),
+                    c: c.into_event(),
+                },
+                Self::NewReno { beta } => NewReno {
+                    beta: beta.into_event(),
+                },
+                Self::Bbr => Bbr {},
+                Self::Other => Other {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " How packets from multiple packet number spaces were combined into a datagram"]
+    pub enum CoalescingDecision {
+        #[doc = " The packets were coalesced up to the maximum datagram size of the path"]
+        Coalesced,
+        #[doc = " The packets were coalesced into a datagram limited to `max_len` bytes"]
+        Limited { max_len: u16 },
+        #[doc = " Coalescing is disabled, so only the first packet was written and the rest were deferred to"]
+        #[doc = " later datagrams"]
+        Disabled,
+    }
+    impl IntoEvent<api::CoalescingDecision> for CoalescingDecision {
+        #[inline]
+        fn into_event(self) -> api::CoalescingDecision {
+            use api::CoalescingDecision::*;
+            match self {
+                Self::Coalesced => Coalesced {},
+                Self::Limited { max_len } => Limited {
+                    max_len: max_len.into_event(),
+                },
+                Self::Disabled => Disabled {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " How the endpoint responded to a connection attempt which wasn't allowed to continue"]
+    pub enum ConnectionAttemptAction {
+        #[doc = " A Retry packet was sent to validate the peer's address"]
+        Retry,
+        #[doc = " The attempt was silently dropped"]
+        Drop,
+        #[doc = " The attempt was refused"]
+        Close,
+        #[doc = " A Retry packet was sent carrying a challenge for the peer to solve"]
+        Challenge,
+    }
+    impl IntoEvent<api::ConnectionAttemptAction> for ConnectionAttemptAction {
+        #[inline]
+        fn into_event(self) -> api::ConnectionAttemptAction {
+            use api::ConnectionAttemptAction::*;
+            match self {
+                Self::Retry => Retry {},
+                Self::Drop => Drop {},
+                Self::Close => Close {},
+                Self::Challenge => Challenge {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The reason data in the Initial or Handshake packet number space is retransmitted"]
+    pub enum HandshakeRetransmissionReason {
+        #[doc = " A packet was declared lost"]
+        PacketLost { packet_number: u64, bytes_lost: u16 },
+        #[doc = " The PTO timer expired while ack-eliciting packets were in flight"]
+        ProbeTimeout { pto_backoff: u32 },
+        #[doc = " The PTO timer expired with nothing in flight, so a probe is sent to keep the peer from"]
+        #[doc = " being blocked by its anti-amplification limit"]
+        AntiDeadlock { pto_backoff: u32 },
+    }
+    impl IntoEvent<api::HandshakeRetransmissionReason> for HandshakeRetransmissionReason {
+        #[inline]
+        fn into_event(self) -> api::HandshakeRetransmissionReason {
+            use api::HandshakeRetransmissionReason::*;
+            match self {
+                Self::PacketLost {
+                    packet_number,
+                    bytes_lost,
+                } => PacketLost {
+                    packet_number: packet_number.into_event(),
+                    bytes_lost: bytes_lost.into_event(),
+                },
+                Self::ProbeTimeout { pto_backoff } => ProbeTimeout {
+                    pto_backoff: pto_backoff.into_event(),
+                },
+                Self::AntiDeadlock { pto_backoff } => AntiDeadlock {
+                    pto_backoff: pto_backoff.into_event(),
+                },
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Application level protocol"]
     pub struct ApplicationProtocolInformation<'a> {
         pub chosen_application_protocol: &'a [u8],
@@ -3689,6 +4445,46 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " An RTT sample was generated from an acknowledgement"]
+    #[doc = ""]
+    #[doc = " Samples are emitted in every packet number space, so the RTT is available as soon as the"]
+    #[doc = " first Initial packet is acknowledged, rather than only after the handshake completes."]
+    pub struct RttSample<'a> {
+        pub path: Path<'a>,
+        #[doc = " The header of the largest acknowledged packet, which generated the sample"]
+        pub packet_header: PacketHeader,
+        #[doc = " The time between sending the packet and receiving its acknowledgement"]
+        pub latest_rtt: Duration,
+        #[doc = " The time the peer reported delaying the acknowledgement"]
+        #[doc = ""]
+        #[doc = " During the handshake this includes the time the peer spent processing the packet, so"]
+        #[doc = " subtracting it from `latest_rtt` separates the network RTT from the peer's processing time."]
+        pub ack_delay: Duration,
+        pub min_rtt: Duration,
+        pub smoothed_rtt: Duration,
+    }
+    impl<'a> IntoEvent<api::RttSample<'a>> for RttSample<'a> {
+        #[inline]
+        fn into_event(self) -> api::RttSample<'a> {
+            let RttSample {
+                path,
+                packet_header,
+                latest_rtt,
+                ack_delay,
+                min_rtt,
+                smoothed_rtt,
+            } = self;
+            api::RttSample {
+                path: path.into_event(),
+                packet_header: packet_header.into_event(),
+                latest_rtt: latest_rtt.into_event(),
+                ack_delay: ack_delay.into_event(),
+                min_rtt: min_rtt.into_event(),
+                smoothed_rtt: smoothed_rtt.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Congestion (ECN or packet loss) has occurred"]
     pub struct Congestion<'a> {
         pub path: Path<'a>,
@@ -3938,13 +4734,18 @@ pub mod builder {
     #[doc = " Datagram received by a connection"]
     pub struct DatagramReceived {
         pub len: u16,
+        #[doc = " The index of the IO provider's receive queue the datagram was read from, if known"]
+        #[doc = ""]
+        #[doc = " This is not the NIC's RSS queue or the CPU which received the datagram."]
+        pub queue_id: Option<u32>,
     }
     impl IntoEvent<api::DatagramReceived> for DatagramReceived {
         #[inline]
         fn into_event(self) -> api::DatagramReceived {
-            let DatagramReceived { len } = self;
+            let DatagramReceived { len, queue_id } = self;
             api::DatagramReceived {
                 len: len.into_event(),
+                queue_id: queue_id.into_event(),
             }
         }
     }
@@ -4067,6 +4868,20 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " Emitted when a client offers a session ticket and when the server responds to it"]
+    pub struct SessionResumption {
+        pub status: SessionResumptionStatus,
+    }
+    impl IntoEvent<api::SessionResumption> for SessionResumption {
+        #[inline]
+        fn into_event(self) -> api::SessionResumption {
+            let SessionResumption { status } = self;
+            api::SessionResumption {
+                status: status.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct TlsClientHello<'a> {
         pub payload: &'a [&'a [u8]],
     }
@@ -4093,6 +4908,53 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The client's ClientHello did not fit in a single datagram and was split across multiple"]
+    #[doc = " Initial packets"]
+    pub struct ClientHelloFragmented {
+        #[doc = " The length of the ClientHello message"]
+        pub len: u64,
+        #[doc = " The number of datagrams used to transmit the ClientHello"]
+        pub datagram_count: u16,
+    }
+    impl IntoEvent<api::ClientHelloFragmented> for ClientHelloFragmented {
+        #[inline]
+        fn into_event(self) -> api::ClientHelloFragmented {
+            let ClientHelloFragmented {
+                len,
+                datagram_count,
+            } = self;
+            api::ClientHelloFragmented {
+                len: len.into_event(),
+                datagram_count: datagram_count.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " Packets from more than one packet number space were ready to be sent in the same datagram"]
+    pub struct DatagramCoalesced {
+        #[doc = " The number of packets which were written into the datagram"]
+        pub packet_count: u8,
+        #[doc = " The length of the datagram"]
+        pub len: u16,
+        #[doc = " How the connection decided to coalesce the packets"]
+        pub decision: CoalescingDecision,
+    }
+    impl IntoEvent<api::DatagramCoalesced> for DatagramCoalesced {
+        #[inline]
+        fn into_event(self) -> api::DatagramCoalesced {
+            let DatagramCoalesced {
+                packet_count,
+                len,
+                decision,
+            } = self;
+            api::DatagramCoalesced {
+                packet_count: packet_count.into_event(),
+                len: len.into_event(),
+                decision: decision.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct RxStreamProgress {
         pub bytes: usize,
     }
@@ -4119,6 +4981,83 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A packet carrying bytes from a marked range of a stream was sent"]
+    pub struct StreamMarkerSent {
+        pub stream_id: u64,
+        pub marker: u64,
+        pub packet_header: PacketHeader,
+        #[doc = " `true` if the bytes had been declared lost and are being retransmitted"]
+        pub is_retransmission: bool,
+    }
+    impl IntoEvent<api::StreamMarkerSent> for StreamMarkerSent {
+        #[inline]
+        fn into_event(self) -> api::StreamMarkerSent {
+            let StreamMarkerSent {
+                stream_id,
+                marker,
+                packet_header,
+                is_retransmission,
+            } = self;
+            api::StreamMarkerSent {
+                stream_id: stream_id.into_event(),
+                marker: marker.into_event(),
+                packet_header: packet_header.into_event(),
+                is_retransmission: is_retransmission.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " Bytes from a marked range of a stream were declared lost"]
+    pub struct StreamMarkerLost {
+        pub stream_id: u64,
+        pub marker: u64,
+    }
+    impl IntoEvent<api::StreamMarkerLost> for StreamMarkerLost {
+        #[inline]
+        fn into_event(self) -> api::StreamMarkerLost {
+            let StreamMarkerLost { stream_id, marker } = self;
+            api::StreamMarkerLost {
+                stream_id: stream_id.into_event(),
+                marker: marker.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " All of the bytes in a marked range of a stream have been acknowledged by the peer"]
+    pub struct StreamMarkerAcknowledged {
+        pub stream_id: u64,
+        pub marker: u64,
+    }
+    impl IntoEvent<api::StreamMarkerAcknowledged> for StreamMarkerAcknowledged {
+        #[inline]
+        fn into_event(self) -> api::StreamMarkerAcknowledged {
+            let StreamMarkerAcknowledged { stream_id, marker } = self;
+            api::StreamMarkerAcknowledged {
+                stream_id: stream_id.into_event(),
+                marker: marker.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The peer opened a stream"]
+    #[doc = ""]
+    #[doc = " Opening a stream implicitly opens all lower stream IDs of the same type, so an event is"]
+    #[doc = " emitted for each of those streams as well."]
+    pub struct PeerStreamOpened {
+        pub id: u64,
+        pub stream_type: StreamType,
+    }
+    impl IntoEvent<api::PeerStreamOpened> for PeerStreamOpened {
+        #[inline]
+        fn into_event(self) -> api::PeerStreamOpened {
+            let PeerStreamOpened { id, stream_type } = self;
+            api::PeerStreamOpened {
+                id: id.into_event(),
+                stream_type: stream_type.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct KeepAliveTimerExpired {
         pub timeout: Duration,
     }
@@ -4132,6 +5071,37 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The connection released its transient buffers after being idle for the hibernation timeout"]
+    pub struct ConnectionHibernated {
+        pub timeout: Duration,
+    }
+    impl IntoEvent<api::ConnectionHibernated> for ConnectionHibernated {
+        #[inline]
+        fn into_event(self) -> api::ConnectionHibernated {
+            let ConnectionHibernated { timeout } = self;
+            api::ConnectionHibernated {
+                timeout: timeout.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The connection stopped making progress and is being closed"]
+    #[doc = ""]
+    #[doc = " This is only checked in debug builds. The report describes the timers and transmission"]
+    #[doc = " interest of each of the connection's components."]
+    pub struct ConnectionStalled<'a> {
+        pub report: &'a str,
+    }
+    impl<'a> IntoEvent<api::ConnectionStalled<'a>> for ConnectionStalled<'a> {
+        #[inline]
+        fn into_event(self) -> api::ConnectionStalled<'a> {
+            let ConnectionStalled { report } = self;
+            api::ConnectionStalled {
+                report: report.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The maximum transmission unit (MTU) for the path has changed"]
     pub struct MtuUpdated {
         pub path_id: u64,
@@ -4177,6 +5147,55 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A probe packet was sent after the PTO timer expired"]
+    pub struct PtoProbeSent {
+        pub packet_header: PacketHeader,
+        #[doc = " The policy which determined the contents of the probe"]
+        pub policy: ProbePolicy,
+        #[doc = " The number of stream bytes which were transmitted for the first time"]
+        pub new_stream_bytes: u64,
+        #[doc = " The number of stream bytes which were retransmitted"]
+        pub retransmitted_stream_bytes: u64,
+    }
+    impl IntoEvent<api::PtoProbeSent> for PtoProbeSent {
+        #[inline]
+        fn into_event(self) -> api::PtoProbeSent {
+            let PtoProbeSent {
+                packet_header,
+                policy,
+                new_stream_bytes,
+                retransmitted_stream_bytes,
+            } = self;
+            api::PtoProbeSent {
+                packet_header: packet_header.into_event(),
+                policy: policy.into_event(),
+                new_stream_bytes: new_stream_bytes.into_event(),
+                retransmitted_stream_bytes: retransmitted_stream_bytes.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " Data in the Initial or Handshake packet number space needs to be retransmitted"]
+    #[doc = ""]
+    #[doc = " These spaces are only used before the handshake is confirmed, so the event makes it possible"]
+    #[doc = " to tell why a handshake is taking longer than expected."]
+    pub struct HandshakeRetransmission {
+        #[doc = " The key space of the data being retransmitted"]
+        pub space: KeySpace,
+        #[doc = " The reason the data needs to be retransmitted"]
+        pub reason: HandshakeRetransmissionReason,
+    }
+    impl IntoEvent<api::HandshakeRetransmission> for HandshakeRetransmission {
+        #[inline]
+        fn into_event(self) -> api::HandshakeRetransmission {
+            let HandshakeRetransmission { space, reason } = self;
+            api::HandshakeRetransmission {
+                space: space.into_event(),
+                reason: reason.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " A new delivery rate sample has been generated"]
     #[doc = " Note: This event is only recorded for congestion controllers that support"]
     #[doc = "       bandwidth estimates, such as BBR"]
@@ -4239,6 +5258,22 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A congestion controller has been initialized for a path"]
+    pub struct CongestionControllerInitialized {
+        pub path_id: u64,
+        pub algorithm: CongestionControlAlgorithm,
+    }
+    impl IntoEvent<api::CongestionControllerInitialized> for CongestionControllerInitialized {
+        #[inline]
+        fn into_event(self) -> api::CongestionControllerInitialized {
+            let CongestionControllerInitialized { path_id, algorithm } = self;
+            api::CongestionControllerInitialized {
+                path_id: path_id.into_event(),
+                algorithm: algorithm.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -4354,6 +5389,56 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A connection attempt was not allowed to continue by the endpoint limits"]
+    pub struct EndpointConnectionAttemptRejected<'a> {
+        #[doc = " How the endpoint responded to the attempt"]
+        pub action: ConnectionAttemptAction,
+        #[doc = " The reason provided by the endpoint limits, if any"]
+        pub reason: Option<&'a str>,
+        #[doc = " Whether the peer's address was validated with a token"]
+        pub is_address_validated: bool,
+    }
+    impl<'a> IntoEvent<api::EndpointConnectionAttemptRejected<'a>>
+        for EndpointConnectionAttemptRejected<'a>
+    {
+        #[inline]
+        fn into_event(self) -> api::EndpointConnectionAttemptRejected<'a> {
+            let EndpointConnectionAttemptRejected {
+                action,
+                reason,
+                is_address_validated,
+            } = self;
+            api::EndpointConnectionAttemptRejected {
+                action: action.into_event(),
+                reason: reason.into_event(),
+                is_address_validated: is_address_validated.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A client connection attempt failed and will be retried after the backoff"]
+    pub struct EndpointConnectionAttemptRetried {
+        #[doc = " The number of attempts which have been made, including the failed one"]
+        pub attempts: u32,
+        pub backoff: Duration,
+        pub error: crate::connection::Error,
+    }
+    impl IntoEvent<api::EndpointConnectionAttemptRetried> for EndpointConnectionAttemptRetried {
+        #[inline]
+        fn into_event(self) -> api::EndpointConnectionAttemptRetried {
+            let EndpointConnectionAttemptRetried {
+                attempts,
+                backoff,
+                error,
+            } = self;
+            api::EndpointConnectionAttemptRetried {
+                attempts: attempts.into_event(),
+                backoff: backoff.into_event(),
+                error: error.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Emitted when the platform sends at least one packet"]
     pub struct PlatformTx {
         #[doc = " The number of packets sent"]
@@ -4587,7 +5672,7 @@ mod traits {
         #[doc = r" The time the event occurred"]
         fn timestamp(&self) -> &crate::event::Timestamp;
     }
-    impl Meta for ConnectionMeta {
+    impl<'a> Meta for ConnectionMeta<'a> {
         fn endpoint_type(&self) -> &EndpointType {
             &self.endpoint_type
         }
@@ -4660,6 +5745,16 @@ mod traits {
             meta: &ConnectionMeta,
             info: &ConnectionInfo,
         ) -> Self::ConnectionContext;
+        #[doc = r" Returns `false` if connection events should not be published for the connection"]
+        #[doc = r""]
+        #[doc = r" The transport checks this before publishing each connection event, which allows"]
+        #[doc = r" subscribers to opt out of a connection in `create_connection_context` without"]
+        #[doc = r" paying the cost of event dispatch. See [`Sampled`] for an implementation."]
+        #[inline]
+        fn is_connection_sampled(context: &Self::ConnectionContext) -> bool {
+            let _ = context;
+            true
+        }
         #[doc = r" The period at which `on_supervisor_timeout` is called"]
         #[doc = r""]
         #[doc = r" If multiple `event::Subscriber`s are composed together, the minimum `supervisor_timeout`"]
@@ -4694,19 +5789,72 @@ mod traits {
         ) -> supervisor::Outcome {
             supervisor::Outcome::default()
         }
-        #[doc = "Called when the `ApplicationProtocolInformation` event is triggered"]
+        #[doc = r" Called after events are published for a connection to determine if the connection should be closed"]
+        #[doc = r""]
+        #[doc = r" This allows subscribers to enforce policies based on the events they observe, e.g. closing"]
+        #[doc = r" a connection which sends an excessive number of ACK frames or resets too many streams. Event"]
+        #[doc = r" callbacks typically record the decision in the `ConnectionContext`, which is then returned here."]
+        #[doc = r""]
+        #[doc = r" The transport checks the verdict after processing each received datagram, timeout, and wakeup"]
+        #[doc = r" for the connection."]
+        #[doc = r""]
+        #[doc = r" If multiple `event::Subscriber`s are composed together, the most severe `supervisor::Outcome`"]
+        #[doc = r" across all `event::Subscriber`s will be used."]
+        #[allow(unused_variables)]
+        fn connection_verdict(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+        ) -> supervisor::Outcome {
+            supervisor::Outcome::default()
+        }
+        #[doc = r" Called when the transport enters one of the hot paths listed in [`profile::Scope`]"]
+        #[doc = r""]
+        #[doc = r" Each call is followed by a call to `on_profile_scope_exited` with the same scope"]
+        #[doc = r" once the work completes. Scopes of the same kind never overlap, so subscribers can"]
+        #[doc = r" measure the time spent in each scope by recording the time it was entered. Both calls"]
+        #[doc = r" are made for every connection, regardless of `is_connection_sampled`."]
+        #[doc = r""]
+        #[doc = r" [`profile::Scope`]: crate::event::profile::Scope"]
         #[inline]
-        fn on_application_protocol_information(
+        fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+            let _ = scope;
+        }
+        #[doc = r" Called when the transport exits a hot path entered with `on_profile_scope_entered`"]
+        #[inline]
+        fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+            let _ = scope;
+        }
+        #[doc = r" Called when the information passed to `create_connection_context` is updated"]
+        #[doc = r""]
+        #[doc = r" Servers only learn the server name requested by the client once the ClientHello is"]
+        #[doc = r" processed, at which point this is called with the server name filled in. Unlike"]
+        #[doc = r" connection events, this is called regardless of `is_connection_sampled`, which allows"]
+        #[doc = r" [`Sampled`] subscribers to select the connection once the server name is known."]
+        #[inline]
+        fn on_connection_info_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
             meta: &ConnectionMeta,
-            event: &ApplicationProtocolInformation,
+            info: &ConnectionInfo,
         ) {
             let _ = context;
             let _ = meta;
-            let _ = event;
+            let _ = info;
         }
-        #[doc = "Called when the `ServerNameInformation` event is triggered"]
+        #[doc = "Called when the `ApplicationProtocolInformation` event is triggered"]
+        #[inline]
+        fn on_application_protocol_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ApplicationProtocolInformation,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ServerNameInformation` event is triggered"]
         #[inline]
         fn on_server_name_information(
             &mut self,
@@ -4826,6 +5974,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `RttSample` event is triggered"]
+        #[inline]
+        fn on_rtt_sample(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &RttSample,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `Congestion` event is triggered"]
         #[inline]
         fn on_congestion(
@@ -5080,6 +6240,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `SessionResumption` event is triggered"]
+        #[inline]
+        fn on_session_resumption(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SessionResumption,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `TlsClientHello` event is triggered"]
         #[inline]
         fn on_tls_client_hello(
@@ -5104,6 +6276,30 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `ClientHelloFragmented` event is triggered"]
+        #[inline]
+        fn on_client_hello_fragmented(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ClientHelloFragmented,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DatagramCoalesced` event is triggered"]
+        #[inline]
+        fn on_datagram_coalesced(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramCoalesced,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `RxStreamProgress` event is triggered"]
         #[inline]
         fn on_rx_stream_progress(
@@ -5128,6 +6324,54 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `StreamMarkerSent` event is triggered"]
+        #[inline]
+        fn on_stream_marker_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerSent,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `StreamMarkerLost` event is triggered"]
+        #[inline]
+        fn on_stream_marker_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerLost,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `StreamMarkerAcknowledged` event is triggered"]
+        #[inline]
+        fn on_stream_marker_acknowledged(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerAcknowledged,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PeerStreamOpened` event is triggered"]
+        #[inline]
+        fn on_peer_stream_opened(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PeerStreamOpened,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `KeepAliveTimerExpired` event is triggered"]
         #[inline]
         fn on_keep_alive_timer_expired(
@@ -5140,6 +6384,30 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `ConnectionHibernated` event is triggered"]
+        #[inline]
+        fn on_connection_hibernated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionHibernated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionStalled` event is triggered"]
+        #[inline]
+        fn on_connection_stalled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionStalled,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `MtuUpdated` event is triggered"]
         #[inline]
         fn on_mtu_updated(
@@ -5164,6 +6432,30 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PtoProbeSent` event is triggered"]
+        #[inline]
+        fn on_pto_probe_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PtoProbeSent,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `HandshakeRetransmission` event is triggered"]
+        #[inline]
+        fn on_handshake_retransmission(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeRetransmission,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `DeliveryRateSampled` event is triggered"]
         #[inline]
         fn on_delivery_rate_sampled(
@@ -5200,6 +6492,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `CongestionControllerInitialized` event is triggered"]
+        #[inline]
+        fn on_congestion_controller_initialized(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &CongestionControllerInitialized,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `VersionInformation` event is triggered"]
         #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
@@ -5258,6 +6562,26 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `EndpointConnectionAttemptRejected` event is triggered"]
+        #[inline]
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRejected,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `EndpointConnectionAttemptRetried` event is triggered"]
+        #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRetried,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `PlatformTx` event is triggered"]
         #[inline]
         fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
@@ -5367,6 +6691,10 @@ mod traits {
             )
         }
         #[inline]
+        fn is_connection_sampled(context: &Self::ConnectionContext) -> bool {
+            A::is_connection_sampled(&context.0) || B::is_connection_sampled(&context.1)
+        }
+        #[inline]
         fn supervisor_timeout(
             &mut self,
             conn_context: &mut Self::ConnectionContext,
@@ -5411,6 +6739,48 @@ mod traits {
             }
         }
         #[inline]
+        fn connection_verdict(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+        ) -> supervisor::Outcome {
+            let outcome_a = self.0.connection_verdict(&mut conn_context.0, meta);
+            let outcome_b = self.1.connection_verdict(&mut conn_context.1, meta);
+            match (outcome_a, outcome_b) {
+                (supervisor::Outcome::ImmediateClose { reason }, _)
+                | (_, supervisor::Outcome::ImmediateClose { reason }) => {
+                    supervisor::Outcome::ImmediateClose { reason }
+                }
+                (supervisor::Outcome::Close { error_code }, _)
+                | (_, supervisor::Outcome::Close { error_code }) => {
+                    supervisor::Outcome::Close { error_code }
+                }
+                _ => supervisor::Outcome::Continue,
+            }
+        }
+        #[inline]
+        fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+            self.0.on_profile_scope_entered(scope);
+            self.1.on_profile_scope_entered(scope);
+        }
+        #[inline]
+        fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+            self.1.on_profile_scope_exited(scope);
+            self.0.on_profile_scope_exited(scope);
+        }
+        #[inline]
+        fn on_connection_info_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            info: &ConnectionInfo,
+        ) {
+            self.0
+                .on_connection_info_updated(&mut context.0, meta, info);
+            self.1
+                .on_connection_info_updated(&mut context.1, meta, info);
+        }
+        #[inline]
         fn on_application_protocol_information(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5521,6 +6891,16 @@ mod traits {
             (self.1).on_recovery_metrics(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_rtt_sample(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &RttSample,
+        ) {
+            (self.0).on_rtt_sample(&mut context.0, meta, event);
+            (self.1).on_rtt_sample(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_congestion(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5732,6 +7112,16 @@ mod traits {
             (self.1).on_path_challenge_updated(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_session_resumption(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SessionResumption,
+        ) {
+            (self.0).on_session_resumption(&mut context.0, meta, event);
+            (self.1).on_session_resumption(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_tls_client_hello(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5752,6 +7142,26 @@ mod traits {
             (self.1).on_tls_server_hello(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_client_hello_fragmented(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ClientHelloFragmented,
+        ) {
+            (self.0).on_client_hello_fragmented(&mut context.0, meta, event);
+            (self.1).on_client_hello_fragmented(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_datagram_coalesced(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramCoalesced,
+        ) {
+            (self.0).on_datagram_coalesced(&mut context.0, meta, event);
+            (self.1).on_datagram_coalesced(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5772,6 +7182,46 @@ mod traits {
             (self.1).on_tx_stream_progress(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_stream_marker_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerSent,
+        ) {
+            (self.0).on_stream_marker_sent(&mut context.0, meta, event);
+            (self.1).on_stream_marker_sent(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_stream_marker_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerLost,
+        ) {
+            (self.0).on_stream_marker_lost(&mut context.0, meta, event);
+            (self.1).on_stream_marker_lost(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_stream_marker_acknowledged(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerAcknowledged,
+        ) {
+            (self.0).on_stream_marker_acknowledged(&mut context.0, meta, event);
+            (self.1).on_stream_marker_acknowledged(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_peer_stream_opened(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PeerStreamOpened,
+        ) {
+            (self.0).on_peer_stream_opened(&mut context.0, meta, event);
+            (self.1).on_peer_stream_opened(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_keep_alive_timer_expired(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5782,6 +7232,26 @@ mod traits {
             (self.1).on_keep_alive_timer_expired(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_connection_hibernated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionHibernated,
+        ) {
+            (self.0).on_connection_hibernated(&mut context.0, meta, event);
+            (self.1).on_connection_hibernated(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_connection_stalled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionStalled,
+        ) {
+            (self.0).on_connection_stalled(&mut context.0, meta, event);
+            (self.1).on_connection_stalled(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_mtu_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5802,6 +7272,26 @@ mod traits {
             (self.1).on_slow_start_exited(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_pto_probe_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PtoProbeSent,
+        ) {
+            (self.0).on_pto_probe_sent(&mut context.0, meta, event);
+            (self.1).on_pto_probe_sent(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_handshake_retransmission(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeRetransmission,
+        ) {
+            (self.0).on_handshake_retransmission(&mut context.0, meta, event);
+            (self.1).on_handshake_retransmission(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_delivery_rate_sampled(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5832,6 +7322,16 @@ mod traits {
             (self.1).on_bbr_state_changed(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_congestion_controller_initialized(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &CongestionControllerInitialized,
+        ) {
+            (self.0).on_congestion_controller_initialized(&mut context.0, meta, event);
+            (self.1).on_congestion_controller_initialized(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
             (self.0).on_version_information(meta, event);
             (self.1).on_version_information(meta, event);
@@ -5883,24 +7383,943 @@ mod traits {
             (self.1).on_endpoint_connection_attempt_failed(meta, event);
         }
         #[inline]
-        fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
-            (self.0).on_platform_tx(meta, event);
-            (self.1).on_platform_tx(meta, event);
-        }
-        #[inline]
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRejected,
+        ) {
+            (self.0).on_endpoint_connection_attempt_rejected(meta, event);
+            (self.1).on_endpoint_connection_attempt_rejected(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRetried,
+        ) {
+            (self.0).on_endpoint_connection_attempt_retried(meta, event);
+            (self.1).on_endpoint_connection_attempt_retried(meta, event);
+        }
+        #[inline]
+        fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
+            (self.0).on_platform_tx(meta, event);
+            (self.1).on_platform_tx(meta, event);
+        }
+        #[inline]
+        fn on_platform_tx_error(&mut self, meta: &EndpointMeta, event: &PlatformTxError) {
+            (self.0).on_platform_tx_error(meta, event);
+            (self.1).on_platform_tx_error(meta, event);
+        }
+        #[inline]
+        fn on_platform_rx(&mut self, meta: &EndpointMeta, event: &PlatformRx) {
+            (self.0).on_platform_rx(meta, event);
+            (self.1).on_platform_rx(meta, event);
+        }
+        #[inline]
+        fn on_platform_rx_error(&mut self, meta: &EndpointMeta, event: &PlatformRxError) {
+            (self.0).on_platform_rx_error(meta, event);
+            (self.1).on_platform_rx_error(meta, event);
+        }
+        #[inline]
+        fn on_platform_feature_configured(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &PlatformFeatureConfigured,
+        ) {
+            (self.0).on_platform_feature_configured(meta, event);
+            (self.1).on_platform_feature_configured(meta, event);
+        }
+        #[inline]
+        fn on_platform_event_loop_wakeup(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &PlatformEventLoopWakeup,
+        ) {
+            (self.0).on_platform_event_loop_wakeup(meta, event);
+            (self.1).on_platform_event_loop_wakeup(meta, event);
+        }
+        #[inline]
+        fn on_platform_event_loop_sleep(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &PlatformEventLoopSleep,
+        ) {
+            (self.0).on_platform_event_loop_sleep(meta, event);
+            (self.1).on_platform_event_loop_sleep(meta, event);
+        }
+        #[inline]
+        fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
+            self.0.on_event(meta, event);
+            self.1.on_event(meta, event);
+        }
+        #[inline]
+        fn on_connection_event<E: Event>(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &E,
+        ) {
+            self.0.on_connection_event(&mut context.0, meta, event);
+            self.1.on_connection_event(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            query
+                .execute(context)
+                .and_then(|| A::query(&context.0, query))
+                .and_then(|| B::query(&context.1, query))
+        }
+        #[inline]
+        fn query_mut(
+            context: &mut Self::ConnectionContext,
+            query: &mut dyn query::QueryMut,
+        ) -> query::ControlFlow {
+            query
+                .execute_mut(context)
+                .and_then(|| A::query_mut(&mut context.0, query))
+                .and_then(|| B::query_mut(&mut context.1, query))
+        }
+    }
+    #[doc = r" Selects the connections observed by a [`Sampled`] subscriber"]
+    pub trait Sampler: 'static + Send {
+        #[doc = r" Returns `true` if the connection should be observed"]
+        fn sample(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> bool;
+        #[doc = r" Returns `true` if a connection which wasn't selected should be observed after its"]
+        #[doc = r" information was updated"]
+        #[doc = r""]
+        #[doc = r" This is called when a server learns the server name requested by the client. The"]
+        #[doc = r" subscriber only observes the events published after that point. Returns `false` by"]
+        #[doc = r" default, which keeps the decision made by `sample`."]
+        #[inline]
+        fn sample_updated(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> bool {
+            let _ = meta;
+            let _ = info;
+            false
+        }
+    }
+    impl<F> Sampler for F
+    where
+        F: 'static + Send + FnMut(&ConnectionMeta, &ConnectionInfo) -> bool,
+    {
+        #[inline]
+        fn sample(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> bool {
+            (self)(meta, info)
+        }
+    }
+    #[doc = r" A subscriber which only observes the connections selected by a [`Sampler`]"]
+    #[doc = r""]
+    #[doc = r" The sampling decision is made once per connection, in `create_connection_context`."]
+    #[doc = r" Connection events for connections that were not sampled are not passed to the"]
+    #[doc = r" wrapped subscriber. Endpoint events are always forwarded, as are calls to"]
+    #[doc = r" [`Subscriber::on_event`] if another composed subscriber observes the connection."]
+    #[derive(Clone, Debug, Default)]
+    pub struct Sampled<S, P> {
+        subscriber: S,
+        sampler: P,
+    }
+    impl<S, P> Sampled<S, P> {
+        #[doc = r" Wraps `subscriber` so it only observes connections selected by `sampler`"]
+        #[inline]
+        pub fn new(subscriber: S, sampler: P) -> Self {
+            Self {
+                subscriber,
+                sampler,
+            }
+        }
+        #[doc = r" Returns a reference to the wrapped subscriber"]
+        #[inline]
+        pub fn subscriber(&self) -> &S {
+            &self.subscriber
+        }
+        #[doc = r" Returns a mutable reference to the wrapped subscriber"]
+        #[inline]
+        pub fn subscriber_mut(&mut self) -> &mut S {
+            &mut self.subscriber
+        }
+    }
+    impl<S, P> Subscriber for Sampled<S, P>
+    where
+        S: Subscriber,
+        P: Sampler,
+    {
+        type ConnectionContext = Option<S::ConnectionContext>;
+        #[inline]
+        fn create_connection_context(
+            &mut self,
+            meta: &ConnectionMeta,
+            info: &ConnectionInfo,
+        ) -> Self::ConnectionContext {
+            if self.sampler.sample(meta, info) {
+                Some(self.subscriber.create_connection_context(meta, info))
+            } else {
+                None
+            }
+        }
+        #[inline]
+        fn is_connection_sampled(context: &Self::ConnectionContext) -> bool {
+            context.as_ref().map_or(false, S::is_connection_sampled)
+        }
+        #[inline]
+        fn supervisor_timeout(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            context: &supervisor::Context,
+        ) -> Option<Duration> {
+            let conn_context = conn_context.as_mut()?;
+            self.subscriber
+                .supervisor_timeout(conn_context, meta, context)
+        }
+        #[inline]
+        fn on_supervisor_timeout(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            context: &supervisor::Context,
+        ) -> supervisor::Outcome {
+            if let Some(conn_context) = conn_context.as_mut() {
+                self.subscriber
+                    .on_supervisor_timeout(conn_context, meta, context)
+            } else {
+                supervisor::Outcome::default()
+            }
+        }
+        #[inline]
+        fn connection_verdict(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+        ) -> supervisor::Outcome {
+            if let Some(conn_context) = conn_context.as_mut() {
+                self.subscriber.connection_verdict(conn_context, meta)
+            } else {
+                supervisor::Outcome::default()
+            }
+        }
+        #[inline]
+        fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+            self.subscriber.on_profile_scope_entered(scope);
+        }
+        #[inline]
+        fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+            self.subscriber.on_profile_scope_exited(scope);
+        }
+        #[inline]
+        fn on_connection_info_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            info: &ConnectionInfo,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_connection_info_updated(context, meta, info);
+            } else if self.sampler.sample_updated(meta, info) {
+                *context = Some(self.subscriber.create_connection_context(meta, info));
+            }
+        }
+        #[inline]
+        fn on_application_protocol_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ApplicationProtocolInformation,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_application_protocol_information(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_server_name_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ServerNameInformation,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_server_name_information(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_skipped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketSkipped,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_packet_skipped(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketSent,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_packet_sent(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketReceived,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_packet_received(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_active_path_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ActivePathUpdated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_active_path_updated(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_created(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PathCreated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_path_created(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &FrameSent,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_frame_sent(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &FrameReceived,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_frame_received(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketLost,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_packet_lost(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_recovery_metrics(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &RecoveryMetrics,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_recovery_metrics(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_rtt_sample(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &RttSample,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_rtt_sample(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &Congestion,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_congestion(context, meta, event);
+            }
+        }
+        #[inline]
+        #[allow(deprecated)]
+        fn on_ack_processed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &AckProcessed,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_ack_processed(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_ack_range_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &RxAckRangeDropped,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_rx_ack_range_dropped(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &AckRangeReceived,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_ack_range_received(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &AckRangeSent,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_ack_range_sent(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacketDropped,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_packet_dropped(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_update(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &KeyUpdate,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_key_update(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_space_discarded(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &KeySpaceDiscarded,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_key_space_discarded(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_started(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionStarted,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_connection_started(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_closed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionClosed,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_connection_closed(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_duplicate_packet(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DuplicatePacket,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_duplicate_packet(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_transport_parameters_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TransportParametersReceived,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_transport_parameters_received(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramSent,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_datagram_sent(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramReceived,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_datagram_received(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramDropped,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_datagram_dropped(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_id_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionIdUpdated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_connection_id_updated(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_ecn_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &EcnStateChanged,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_ecn_state_changed(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_migration_denied(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionMigrationDenied,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_connection_migration_denied(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_handshake_status_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeStatusUpdated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_handshake_status_updated(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_exporter_ready(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TlsExporterReady,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_tls_exporter_ready(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_challenge_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PathChallengeUpdated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_path_challenge_updated(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_session_resumption(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SessionResumption,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_session_resumption(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_client_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TlsClientHello,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_tls_client_hello(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_server_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TlsServerHello,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_tls_server_hello(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_client_hello_fragmented(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ClientHelloFragmented,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_client_hello_fragmented(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_coalesced(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramCoalesced,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_datagram_coalesced(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &RxStreamProgress,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_rx_stream_progress(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_tx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TxStreamProgress,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_tx_stream_progress(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_stream_marker_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerSent,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_stream_marker_sent(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_stream_marker_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerLost,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_stream_marker_lost(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_stream_marker_acknowledged(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &StreamMarkerAcknowledged,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_stream_marker_acknowledged(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_peer_stream_opened(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PeerStreamOpened,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_peer_stream_opened(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_keep_alive_timer_expired(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &KeepAliveTimerExpired,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_keep_alive_timer_expired(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_hibernated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionHibernated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_connection_hibernated(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_stalled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionStalled,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_connection_stalled(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_mtu_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &MtuUpdated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_mtu_updated(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_slow_start_exited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SlowStartExited,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_slow_start_exited(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_pto_probe_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PtoProbeSent,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_pto_probe_sent(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_handshake_retransmission(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeRetransmission,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_handshake_retransmission(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_delivery_rate_sampled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DeliveryRateSampled,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_delivery_rate_sampled(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_pacing_rate_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PacingRateUpdated,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_pacing_rate_updated(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_bbr_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &BbrStateChanged,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_bbr_state_changed(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_controller_initialized(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &CongestionControllerInitialized,
+        ) {
+            if let Some(context) = context.as_mut() {
+                self.subscriber
+                    .on_congestion_controller_initialized(context, meta, event);
+            }
+        }
+        #[inline]
+        fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
+            self.subscriber.on_version_information(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_packet_sent(&mut self, meta: &EndpointMeta, event: &EndpointPacketSent) {
+            self.subscriber.on_endpoint_packet_sent(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_packet_received(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointPacketReceived,
+        ) {
+            self.subscriber.on_endpoint_packet_received(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_datagram_sent(&mut self, meta: &EndpointMeta, event: &EndpointDatagramSent) {
+            self.subscriber.on_endpoint_datagram_sent(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_datagram_received(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointDatagramReceived,
+        ) {
+            self.subscriber.on_endpoint_datagram_received(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_datagram_dropped(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointDatagramDropped,
+        ) {
+            self.subscriber.on_endpoint_datagram_dropped(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_failed(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptFailed,
+        ) {
+            self.subscriber
+                .on_endpoint_connection_attempt_failed(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRejected,
+        ) {
+            self.subscriber
+                .on_endpoint_connection_attempt_rejected(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRetried,
+        ) {
+            self.subscriber
+                .on_endpoint_connection_attempt_retried(meta, event);
+        }
+        #[inline]
+        fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
+            self.subscriber.on_platform_tx(meta, event);
+        }
+        #[inline]
         fn on_platform_tx_error(&mut self, meta: &EndpointMeta, event: &PlatformTxError) {
-            (self.0).on_platform_tx_error(meta, event);
-            (self.1).on_platform_tx_error(meta, event);
+            self.subscriber.on_platform_tx_error(meta, event);
         }
         #[inline]
         fn on_platform_rx(&mut self, meta: &EndpointMeta, event: &PlatformRx) {
-            (self.0).on_platform_rx(meta, event);
-            (self.1).on_platform_rx(meta, event);
+            self.subscriber.on_platform_rx(meta, event);
         }
         #[inline]
         fn on_platform_rx_error(&mut self, meta: &EndpointMeta, event: &PlatformRxError) {
-            (self.0).on_platform_rx_error(meta, event);
-            (self.1).on_platform_rx_error(meta, event);
+            self.subscriber.on_platform_rx_error(meta, event);
         }
         #[inline]
         fn on_platform_feature_configured(
@@ -5908,8 +8327,7 @@ mod traits {
             meta: &EndpointMeta,
             event: &PlatformFeatureConfigured,
         ) {
-            (self.0).on_platform_feature_configured(meta, event);
-            (self.1).on_platform_feature_configured(meta, event);
+            self.subscriber.on_platform_feature_configured(meta, event);
         }
         #[inline]
         fn on_platform_event_loop_wakeup(
@@ -5917,8 +8335,7 @@ mod traits {
             meta: &EndpointMeta,
             event: &PlatformEventLoopWakeup,
         ) {
-            (self.0).on_platform_event_loop_wakeup(meta, event);
-            (self.1).on_platform_event_loop_wakeup(meta, event);
+            self.subscriber.on_platform_event_loop_wakeup(meta, event);
         }
         #[inline]
         fn on_platform_event_loop_sleep(
@@ -5926,13 +8343,11 @@ mod traits {
             meta: &EndpointMeta,
             event: &PlatformEventLoopSleep,
         ) {
-            (self.0).on_platform_event_loop_sleep(meta, event);
-            (self.1).on_platform_event_loop_sleep(meta, event);
+            self.subscriber.on_platform_event_loop_sleep(meta, event);
         }
         #[inline]
         fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
-            self.0.on_event(meta, event);
-            self.1.on_event(meta, event);
+            self.subscriber.on_event(meta, event);
         }
         #[inline]
         fn on_connection_event<E: Event>(
@@ -5941,28 +8356,29 @@ mod traits {
             meta: &ConnectionMeta,
             event: &E,
         ) {
-            self.0.on_connection_event(&mut context.0, meta, event);
-            self.1.on_connection_event(&mut context.1, meta, event);
+            if let Some(context) = context.as_mut() {
+                self.subscriber.on_connection_event(context, meta, event);
+            }
         }
         #[inline]
         fn query(
             context: &Self::ConnectionContext,
             query: &mut dyn query::Query,
         ) -> query::ControlFlow {
-            query
-                .execute(context)
-                .and_then(|| A::query(&context.0, query))
-                .and_then(|| B::query(&context.1, query))
+            query.execute(context).and_then(|| match context {
+                Some(context) => S::query(context, query),
+                None => query::ControlFlow::Continue,
+            })
         }
         #[inline]
         fn query_mut(
             context: &mut Self::ConnectionContext,
             query: &mut dyn query::QueryMut,
         ) -> query::ControlFlow {
-            query
-                .execute_mut(context)
-                .and_then(|| A::query_mut(&mut context.0, query))
-                .and_then(|| B::query_mut(&mut context.1, query))
+            query.execute_mut(context).and_then(|| match context {
+                Some(context) => S::query_mut(context, query),
+                None => query::ControlFlow::Continue,
+            })
         }
     }
     pub trait EndpointPublisher {
@@ -5983,6 +8399,16 @@ mod traits {
             &mut self,
             event: builder::EndpointConnectionAttemptFailed,
         );
+        #[doc = "Publishes a `EndpointConnectionAttemptRejected` event to the publisher's subscriber"]
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRejected,
+        );
+        #[doc = "Publishes a `EndpointConnectionAttemptRetried` event to the publisher's subscriber"]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRetried,
+        );
         #[doc = "Publishes a `PlatformTx` event to the publisher's subscriber"]
         fn on_platform_tx(&mut self, event: builder::PlatformTx);
         #[doc = "Publishes a `PlatformTxError` event to the publisher's subscriber"]
@@ -5999,6 +8425,10 @@ mod traits {
         fn on_platform_event_loop_sleep(&mut self, event: builder::PlatformEventLoopSleep);
         #[doc = r" Returns the QUIC version, if any"]
         fn quic_version(&self) -> Option<u32>;
+        #[doc = r" Returns the connection attempt which published events are associated with, if any"]
+        fn attempt_id(&self) -> Option<u64>;
+        #[doc = r" Associates the events published after this call with a connection attempt"]
+        fn set_attempt_id(&mut self, attempt_id: Option<u64>);
     }
     pub struct EndpointPublisherSubscriber<'a, Sub: Subscriber> {
         meta: EndpointMeta,
@@ -6079,6 +8509,26 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRejected,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_endpoint_connection_attempt_rejected(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRetried,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_endpoint_connection_attempt_retried(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_platform_tx(&mut self, event: builder::PlatformTx) {
             let event = event.into_event();
             self.subscriber.on_platform_tx(&self.meta, &event);
@@ -6127,6 +8577,14 @@ mod traits {
         fn quic_version(&self) -> Option<u32> {
             self.quic_version
         }
+        #[inline]
+        fn attempt_id(&self) -> Option<u64> {
+            self.meta.attempt_id
+        }
+        #[inline]
+        fn set_attempt_id(&mut self, attempt_id: Option<u64>) {
+            self.meta.attempt_id = attempt_id;
+        }
     }
     pub trait ConnectionPublisher {
         #[doc = "Publishes a `ApplicationProtocolInformation` event to the publisher's subscriber"]
@@ -6154,6 +8612,8 @@ mod traits {
         fn on_packet_lost(&mut self, event: builder::PacketLost);
         #[doc = "Publishes a `RecoveryMetrics` event to the publisher's subscriber"]
         fn on_recovery_metrics(&mut self, event: builder::RecoveryMetrics);
+        #[doc = "Publishes a `RttSample` event to the publisher's subscriber"]
+        fn on_rtt_sample(&mut self, event: builder::RttSample);
         #[doc = "Publishes a `Congestion` event to the publisher's subscriber"]
         fn on_congestion(&mut self, event: builder::Congestion);
         #[doc = "Publishes a `AckProcessed` event to the publisher's subscriber"]
@@ -6196,33 +8656,66 @@ mod traits {
         fn on_tls_exporter_ready(&mut self, event: builder::TlsExporterReady);
         #[doc = "Publishes a `PathChallengeUpdated` event to the publisher's subscriber"]
         fn on_path_challenge_updated(&mut self, event: builder::PathChallengeUpdated);
+        #[doc = "Publishes a `SessionResumption` event to the publisher's subscriber"]
+        fn on_session_resumption(&mut self, event: builder::SessionResumption);
         #[doc = "Publishes a `TlsClientHello` event to the publisher's subscriber"]
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello);
         #[doc = "Publishes a `TlsServerHello` event to the publisher's subscriber"]
         fn on_tls_server_hello(&mut self, event: builder::TlsServerHello);
+        #[doc = "Publishes a `ClientHelloFragmented` event to the publisher's subscriber"]
+        fn on_client_hello_fragmented(&mut self, event: builder::ClientHelloFragmented);
+        #[doc = "Publishes a `DatagramCoalesced` event to the publisher's subscriber"]
+        fn on_datagram_coalesced(&mut self, event: builder::DatagramCoalesced);
         #[doc = "Publishes a `RxStreamProgress` event to the publisher's subscriber"]
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress);
         #[doc = "Publishes a `TxStreamProgress` event to the publisher's subscriber"]
         fn on_tx_stream_progress(&mut self, event: builder::TxStreamProgress);
+        #[doc = "Publishes a `StreamMarkerSent` event to the publisher's subscriber"]
+        fn on_stream_marker_sent(&mut self, event: builder::StreamMarkerSent);
+        #[doc = "Publishes a `StreamMarkerLost` event to the publisher's subscriber"]
+        fn on_stream_marker_lost(&mut self, event: builder::StreamMarkerLost);
+        #[doc = "Publishes a `StreamMarkerAcknowledged` event to the publisher's subscriber"]
+        fn on_stream_marker_acknowledged(&mut self, event: builder::StreamMarkerAcknowledged);
+        #[doc = "Publishes a `PeerStreamOpened` event to the publisher's subscriber"]
+        fn on_peer_stream_opened(&mut self, event: builder::PeerStreamOpened);
         #[doc = "Publishes a `KeepAliveTimerExpired` event to the publisher's subscriber"]
         fn on_keep_alive_timer_expired(&mut self, event: builder::KeepAliveTimerExpired);
+        #[doc = "Publishes a `ConnectionHibernated` event to the publisher's subscriber"]
+        fn on_connection_hibernated(&mut self, event: builder::ConnectionHibernated);
+        #[doc = "Publishes a `ConnectionStalled` event to the publisher's subscriber"]
+        fn on_connection_stalled(&mut self, event: builder::ConnectionStalled);
         #[doc = "Publishes a `MtuUpdated` event to the publisher's subscriber"]
         fn on_mtu_updated(&mut self, event: builder::MtuUpdated);
         #[doc = "Publishes a `SlowStartExited` event to the publisher's subscriber"]
         fn on_slow_start_exited(&mut self, event: builder::SlowStartExited);
+        #[doc = "Publishes a `PtoProbeSent` event to the publisher's subscriber"]
+        fn on_pto_probe_sent(&mut self, event: builder::PtoProbeSent);
+        #[doc = "Publishes a `HandshakeRetransmission` event to the publisher's subscriber"]
+        fn on_handshake_retransmission(&mut self, event: builder::HandshakeRetransmission);
         #[doc = "Publishes a `DeliveryRateSampled` event to the publisher's subscriber"]
         fn on_delivery_rate_sampled(&mut self, event: builder::DeliveryRateSampled);
         #[doc = "Publishes a `PacingRateUpdated` event to the publisher's subscriber"]
         fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated);
         #[doc = "Publishes a `BbrStateChanged` event to the publisher's subscriber"]
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged);
+        #[doc = "Publishes a `CongestionControllerInitialized` event to the publisher's subscriber"]
+        fn on_congestion_controller_initialized(
+            &mut self,
+            event: builder::CongestionControllerInitialized,
+        );
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
         fn quic_version(&self) -> u32;
         #[doc = r" Returns the [`Subject`] for the current publisher"]
         fn subject(&self) -> Subject;
+        #[doc = r" Notifies the subscriber that a profiled hot path was entered"]
+        fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope);
+        #[doc = r" Notifies the subscriber that a profiled hot path was exited"]
+        fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope);
+        #[doc = r" Notifies the subscriber that the connection information was updated"]
+        fn on_connection_info_updated(&mut self, info: builder::ConnectionInfo);
     }
     pub struct ConnectionPublisherSubscriber<'a, Sub: Subscriber> {
-        meta: ConnectionMeta,
+        meta: ConnectionMeta<'a>,
         quic_version: u32,
         subscriber: &'a mut Sub,
         context: &'a mut Sub::ConnectionContext,
@@ -6238,7 +8731,7 @@ mod traits {
     impl<'a, Sub: Subscriber> ConnectionPublisherSubscriber<'a, Sub> {
         #[inline]
         pub fn new(
-            meta: builder::ConnectionMeta,
+            meta: builder::ConnectionMeta<'a>,
             quic_version: u32,
             subscriber: &'a mut Sub,
             context: &'a mut Sub::ConnectionContext,
@@ -6257,6 +8750,9 @@ mod traits {
             &mut self,
             event: builder::ApplicationProtocolInformation,
         ) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_application_protocol_information(self.context, &self.meta, &event);
@@ -6266,6 +8762,9 @@ mod traits {
         }
         #[inline]
         fn on_server_name_information(&mut self, event: builder::ServerNameInformation) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_server_name_information(self.context, &self.meta, &event);
@@ -6275,6 +8774,9 @@ mod traits {
         }
         #[inline]
         fn on_packet_skipped(&mut self, event: builder::PacketSkipped) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_packet_skipped(self.context, &self.meta, &event);
@@ -6284,6 +8786,9 @@ mod traits {
         }
         #[inline]
         fn on_packet_sent(&mut self, event: builder::PacketSent) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_packet_sent(self.context, &self.meta, &event);
@@ -6293,6 +8798,9 @@ mod traits {
         }
         #[inline]
         fn on_packet_received(&mut self, event: builder::PacketReceived) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_packet_received(self.context, &self.meta, &event);
@@ -6302,6 +8810,9 @@ mod traits {
         }
         #[inline]
         fn on_active_path_updated(&mut self, event: builder::ActivePathUpdated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_active_path_updated(self.context, &self.meta, &event);
@@ -6311,6 +8822,9 @@ mod traits {
         }
         #[inline]
         fn on_path_created(&mut self, event: builder::PathCreated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_path_created(self.context, &self.meta, &event);
@@ -6320,6 +8834,9 @@ mod traits {
         }
         #[inline]
         fn on_frame_sent(&mut self, event: builder::FrameSent) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_frame_sent(self.context, &self.meta, &event);
@@ -6329,6 +8846,9 @@ mod traits {
         }
         #[inline]
         fn on_frame_received(&mut self, event: builder::FrameReceived) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_frame_received(self.context, &self.meta, &event);
@@ -6338,6 +8858,9 @@ mod traits {
         }
         #[inline]
         fn on_packet_lost(&mut self, event: builder::PacketLost) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_packet_lost(self.context, &self.meta, &event);
@@ -6347,6 +8870,9 @@ mod traits {
         }
         #[inline]
         fn on_recovery_metrics(&mut self, event: builder::RecoveryMetrics) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_recovery_metrics(self.context, &self.meta, &event);
@@ -6355,7 +8881,22 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_rtt_sample(&mut self, event: builder::RttSample) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_rtt_sample(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_congestion(&mut self, event: builder::Congestion) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_congestion(self.context, &self.meta, &event);
@@ -6366,6 +8907,9 @@ mod traits {
         #[inline]
         #[allow(deprecated)]
         fn on_ack_processed(&mut self, event: builder::AckProcessed) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_ack_processed(self.context, &self.meta, &event);
@@ -6375,6 +8919,9 @@ mod traits {
         }
         #[inline]
         fn on_rx_ack_range_dropped(&mut self, event: builder::RxAckRangeDropped) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_rx_ack_range_dropped(self.context, &self.meta, &event);
@@ -6384,6 +8931,9 @@ mod traits {
         }
         #[inline]
         fn on_ack_range_received(&mut self, event: builder::AckRangeReceived) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_ack_range_received(self.context, &self.meta, &event);
@@ -6393,6 +8943,9 @@ mod traits {
         }
         #[inline]
         fn on_ack_range_sent(&mut self, event: builder::AckRangeSent) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_ack_range_sent(self.context, &self.meta, &event);
@@ -6402,6 +8955,9 @@ mod traits {
         }
         #[inline]
         fn on_packet_dropped(&mut self, event: builder::PacketDropped) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_packet_dropped(self.context, &self.meta, &event);
@@ -6411,6 +8967,9 @@ mod traits {
         }
         #[inline]
         fn on_key_update(&mut self, event: builder::KeyUpdate) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_key_update(self.context, &self.meta, &event);
@@ -6420,6 +8979,9 @@ mod traits {
         }
         #[inline]
         fn on_key_space_discarded(&mut self, event: builder::KeySpaceDiscarded) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_key_space_discarded(self.context, &self.meta, &event);
@@ -6429,6 +8991,9 @@ mod traits {
         }
         #[inline]
         fn on_connection_started(&mut self, event: builder::ConnectionStarted) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_connection_started(self.context, &self.meta, &event);
@@ -6438,6 +9003,9 @@ mod traits {
         }
         #[inline]
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_connection_closed(self.context, &self.meta, &event);
@@ -6447,6 +9015,9 @@ mod traits {
         }
         #[inline]
         fn on_duplicate_packet(&mut self, event: builder::DuplicatePacket) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_duplicate_packet(self.context, &self.meta, &event);
@@ -6459,6 +9030,9 @@ mod traits {
             &mut self,
             event: builder::TransportParametersReceived,
         ) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_transport_parameters_received(self.context, &self.meta, &event);
@@ -6468,6 +9042,9 @@ mod traits {
         }
         #[inline]
         fn on_datagram_sent(&mut self, event: builder::DatagramSent) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_datagram_sent(self.context, &self.meta, &event);
@@ -6477,6 +9054,9 @@ mod traits {
         }
         #[inline]
         fn on_datagram_received(&mut self, event: builder::DatagramReceived) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_datagram_received(self.context, &self.meta, &event);
@@ -6486,6 +9066,9 @@ mod traits {
         }
         #[inline]
         fn on_datagram_dropped(&mut self, event: builder::DatagramDropped) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_datagram_dropped(self.context, &self.meta, &event);
@@ -6495,6 +9078,9 @@ mod traits {
         }
         #[inline]
         fn on_connection_id_updated(&mut self, event: builder::ConnectionIdUpdated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_connection_id_updated(self.context, &self.meta, &event);
@@ -6504,6 +9090,9 @@ mod traits {
         }
         #[inline]
         fn on_ecn_state_changed(&mut self, event: builder::EcnStateChanged) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_ecn_state_changed(self.context, &self.meta, &event);
@@ -6513,6 +9102,9 @@ mod traits {
         }
         #[inline]
         fn on_connection_migration_denied(&mut self, event: builder::ConnectionMigrationDenied) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_connection_migration_denied(self.context, &self.meta, &event);
@@ -6522,6 +9114,9 @@ mod traits {
         }
         #[inline]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_handshake_status_updated(self.context, &self.meta, &event);
@@ -6531,87 +9126,249 @@ mod traits {
         }
         #[inline]
         fn on_tls_exporter_ready(&mut self, event: builder::TlsExporterReady) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_tls_exporter_ready(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_path_challenge_updated(&mut self, event: builder::PathChallengeUpdated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_path_challenge_updated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_session_resumption(&mut self, event: builder::SessionResumption) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_session_resumption(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_tls_client_hello(&mut self, event: builder::TlsClientHello) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_tls_client_hello(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_tls_server_hello(&mut self, event: builder::TlsServerHello) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_tls_server_hello(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_client_hello_fragmented(&mut self, event: builder::ClientHelloFragmented) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_client_hello_fragmented(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_datagram_coalesced(&mut self, event: builder::DatagramCoalesced) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_datagram_coalesced(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_rx_stream_progress(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_tx_stream_progress(&mut self, event: builder::TxStreamProgress) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_tx_stream_progress(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_stream_marker_sent(&mut self, event: builder::StreamMarkerSent) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_stream_marker_sent(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_stream_marker_lost(&mut self, event: builder::StreamMarkerLost) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_stream_marker_lost(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_stream_marker_acknowledged(&mut self, event: builder::StreamMarkerAcknowledged) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_tls_exporter_ready(self.context, &self.meta, &event);
+                .on_stream_marker_acknowledged(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_path_challenge_updated(&mut self, event: builder::PathChallengeUpdated) {
+        fn on_peer_stream_opened(&mut self, event: builder::PeerStreamOpened) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_path_challenge_updated(self.context, &self.meta, &event);
+                .on_peer_stream_opened(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_tls_client_hello(&mut self, event: builder::TlsClientHello) {
+        fn on_keep_alive_timer_expired(&mut self, event: builder::KeepAliveTimerExpired) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_tls_client_hello(self.context, &self.meta, &event);
+                .on_keep_alive_timer_expired(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_tls_server_hello(&mut self, event: builder::TlsServerHello) {
+        fn on_connection_hibernated(&mut self, event: builder::ConnectionHibernated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_tls_server_hello(self.context, &self.meta, &event);
+                .on_connection_hibernated(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
+        fn on_connection_stalled(&mut self, event: builder::ConnectionStalled) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_rx_stream_progress(self.context, &self.meta, &event);
+                .on_connection_stalled(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_tx_stream_progress(&mut self, event: builder::TxStreamProgress) {
+        fn on_mtu_updated(&mut self, event: builder::MtuUpdated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_tx_stream_progress(self.context, &self.meta, &event);
+                .on_mtu_updated(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_keep_alive_timer_expired(&mut self, event: builder::KeepAliveTimerExpired) {
+        fn on_slow_start_exited(&mut self, event: builder::SlowStartExited) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_keep_alive_timer_expired(self.context, &self.meta, &event);
+                .on_slow_start_exited(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_mtu_updated(&mut self, event: builder::MtuUpdated) {
+        fn on_pto_probe_sent(&mut self, event: builder::PtoProbeSent) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_mtu_updated(self.context, &self.meta, &event);
+                .on_pto_probe_sent(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
-        fn on_slow_start_exited(&mut self, event: builder::SlowStartExited) {
+        fn on_handshake_retransmission(&mut self, event: builder::HandshakeRetransmission) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
-                .on_slow_start_exited(self.context, &self.meta, &event);
+                .on_handshake_retransmission(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
         fn on_delivery_rate_sampled(&mut self, event: builder::DeliveryRateSampled) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_delivery_rate_sampled(self.context, &self.meta, &event);
@@ -6621,6 +9378,9 @@ mod traits {
         }
         #[inline]
         fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_pacing_rate_updated(self.context, &self.meta, &event);
@@ -6630,6 +9390,9 @@ mod traits {
         }
         #[inline]
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_bbr_state_changed(self.context, &self.meta, &event);
@@ -6638,6 +9401,21 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_congestion_controller_initialized(
+            &mut self,
+            event: builder::CongestionControllerInitialized,
+        ) {
+            if !Sub::is_connection_sampled(self.context) {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_congestion_controller_initialized(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> u32 {
             self.quic_version
         }
@@ -6645,6 +9423,20 @@ mod traits {
         fn subject(&self) -> api::Subject {
             self.meta.subject()
         }
+        #[inline]
+        fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+            self.subscriber.on_profile_scope_entered(scope);
+        }
+        #[inline]
+        fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+            self.subscriber.on_profile_scope_exited(scope);
+        }
+        #[inline]
+        fn on_connection_info_updated(&mut self, info: builder::ConnectionInfo) {
+            let info = info.into_event();
+            self.subscriber
+                .on_connection_info_updated(self.context, &self.meta, &info);
+        }
     }
 }
 #[cfg(any(test, feature = "testing"))]
@@ -6665,6 +9457,7 @@ pub mod testing {
         pub frame_received: u32,
         pub packet_lost: u32,
         pub recovery_metrics: u32,
+        pub rtt_sample: u32,
         pub congestion: u32,
         pub ack_processed: u32,
         pub rx_ack_range_dropped: u32,
@@ -6686,16 +9479,28 @@ pub mod testing {
         pub handshake_status_updated: u32,
         pub tls_exporter_ready: u32,
         pub path_challenge_updated: u32,
+        pub session_resumption: u32,
         pub tls_client_hello: u32,
         pub tls_server_hello: u32,
+        pub client_hello_fragmented: u32,
+        pub datagram_coalesced: u32,
         pub rx_stream_progress: u32,
         pub tx_stream_progress: u32,
+        pub stream_marker_sent: u32,
+        pub stream_marker_lost: u32,
+        pub stream_marker_acknowledged: u32,
+        pub peer_stream_opened: u32,
         pub keep_alive_timer_expired: u32,
+        pub connection_hibernated: u32,
+        pub connection_stalled: u32,
         pub mtu_updated: u32,
         pub slow_start_exited: u32,
+        pub pto_probe_sent: u32,
+        pub handshake_retransmission: u32,
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub congestion_controller_initialized: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -6703,6 +9508,8 @@ pub mod testing {
         pub endpoint_datagram_received: u32,
         pub endpoint_datagram_dropped: u32,
         pub endpoint_connection_attempt_failed: u32,
+        pub endpoint_connection_attempt_rejected: u32,
+        pub endpoint_connection_attempt_retried: u32,
         pub platform_tx: u32,
         pub platform_tx_error: u32,
         pub platform_rx: u32,
@@ -6745,6 +9552,7 @@ pub mod testing {
                 frame_received: 0,
                 packet_lost: 0,
                 recovery_metrics: 0,
+                rtt_sample: 0,
                 congestion: 0,
                 ack_processed: 0,
                 rx_ack_range_dropped: 0,
@@ -6766,16 +9574,28 @@ pub mod testing {
                 handshake_status_updated: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
+                session_resumption: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                client_hello_fragmented: 0,
+                datagram_coalesced: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
+                stream_marker_sent: 0,
+                stream_marker_lost: 0,
+                stream_marker_acknowledged: 0,
+                peer_stream_opened: 0,
                 keep_alive_timer_expired: 0,
+                connection_hibernated: 0,
+                connection_stalled: 0,
                 mtu_updated: 0,
                 slow_start_exited: 0,
+                pto_probe_sent: 0,
+                handshake_retransmission: 0,
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                congestion_controller_initialized: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -6783,6 +9603,8 @@ pub mod testing {
                 endpoint_datagram_received: 0,
                 endpoint_datagram_dropped: 0,
                 endpoint_connection_attempt_failed: 0,
+                endpoint_connection_attempt_rejected: 0,
+                endpoint_connection_attempt_retried: 0,
                 platform_tx: 0,
                 platform_tx_error: 0,
                 platform_rx: 0,
@@ -6922,6 +9744,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_rtt_sample(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RttSample,
+        ) {
+            self.rtt_sample += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_congestion(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7154,6 +9987,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_session_resumption(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::SessionResumption,
+        ) {
+            self.session_resumption += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_tls_client_hello(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7176,6 +10020,28 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_client_hello_fragmented(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ClientHelloFragmented,
+        ) {
+            self.client_hello_fragmented += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_datagram_coalesced(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramCoalesced,
+        ) {
+            self.datagram_coalesced += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_rx_stream_progress(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7198,6 +10064,50 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_stream_marker_sent(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamMarkerSent,
+        ) {
+            self.stream_marker_sent += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_stream_marker_lost(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamMarkerLost,
+        ) {
+            self.stream_marker_lost += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_stream_marker_acknowledged(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamMarkerAcknowledged,
+        ) {
+            self.stream_marker_acknowledged += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_peer_stream_opened(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PeerStreamOpened,
+        ) {
+            self.peer_stream_opened += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_keep_alive_timer_expired(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7209,6 +10119,28 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_connection_hibernated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionHibernated,
+        ) {
+            self.connection_hibernated += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_connection_stalled(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionStalled,
+        ) {
+            self.connection_stalled += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_mtu_updated(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7231,6 +10163,28 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_pto_probe_sent(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PtoProbeSent,
+        ) {
+            self.pto_probe_sent += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_handshake_retransmission(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::HandshakeRetransmission,
+        ) {
+            self.handshake_retransmission += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_delivery_rate_sampled(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7264,6 +10218,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_congestion_controller_initialized(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::CongestionControllerInitialized,
+        ) {
+            self.congestion_controller_initialized += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -7320,6 +10285,22 @@ pub mod testing {
             self.endpoint_connection_attempt_failed += 1;
             self.output.push(format!("{meta:?} {event:?}"));
         }
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptRejected,
+        ) {
+            self.endpoint_connection_attempt_rejected += 1;
+            self.output.push(format!("{meta:?} {event:?}"));
+        }
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptRetried,
+        ) {
+            self.endpoint_connection_attempt_retried += 1;
+            self.output.push(format!("{meta:?} {event:?}"));
+        }
         fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
             self.platform_tx += 1;
             self.output.push(format!("{meta:?} {event:?}"));
@@ -7365,6 +10346,7 @@ pub mod testing {
     pub struct Publisher {
         location: Option<Location>,
         output: Vec<String>,
+        attempt_id: Option<u64>,
         pub application_protocol_information: u32,
         pub server_name_information: u32,
         pub packet_skipped: u32,
@@ -7376,6 +10358,7 @@ pub mod testing {
         pub frame_received: u32,
         pub packet_lost: u32,
         pub recovery_metrics: u32,
+        pub rtt_sample: u32,
         pub congestion: u32,
         pub ack_processed: u32,
         pub rx_ack_range_dropped: u32,
@@ -7397,16 +10380,28 @@ pub mod testing {
         pub handshake_status_updated: u32,
         pub tls_exporter_ready: u32,
         pub path_challenge_updated: u32,
+        pub session_resumption: u32,
         pub tls_client_hello: u32,
         pub tls_server_hello: u32,
+        pub client_hello_fragmented: u32,
+        pub datagram_coalesced: u32,
         pub rx_stream_progress: u32,
         pub tx_stream_progress: u32,
+        pub stream_marker_sent: u32,
+        pub stream_marker_lost: u32,
+        pub stream_marker_acknowledged: u32,
+        pub peer_stream_opened: u32,
         pub keep_alive_timer_expired: u32,
+        pub connection_hibernated: u32,
+        pub connection_stalled: u32,
         pub mtu_updated: u32,
         pub slow_start_exited: u32,
+        pub pto_probe_sent: u32,
+        pub handshake_retransmission: u32,
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub congestion_controller_initialized: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -7414,6 +10409,8 @@ pub mod testing {
         pub endpoint_datagram_received: u32,
         pub endpoint_datagram_dropped: u32,
         pub endpoint_connection_attempt_failed: u32,
+        pub endpoint_connection_attempt_rejected: u32,
+        pub endpoint_connection_attempt_retried: u32,
         pub platform_tx: u32,
         pub platform_tx_error: u32,
         pub platform_rx: u32,
@@ -7435,6 +10432,7 @@ pub mod testing {
             Self {
                 location: None,
                 output: Default::default(),
+                attempt_id: None,
                 application_protocol_information: 0,
                 server_name_information: 0,
                 packet_skipped: 0,
@@ -7446,6 +10444,7 @@ pub mod testing {
                 frame_received: 0,
                 packet_lost: 0,
                 recovery_metrics: 0,
+                rtt_sample: 0,
                 congestion: 0,
                 ack_processed: 0,
                 rx_ack_range_dropped: 0,
@@ -7467,16 +10466,28 @@ pub mod testing {
                 handshake_status_updated: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
+                session_resumption: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                client_hello_fragmented: 0,
+                datagram_coalesced: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
+                stream_marker_sent: 0,
+                stream_marker_lost: 0,
+                stream_marker_acknowledged: 0,
+                peer_stream_opened: 0,
                 keep_alive_timer_expired: 0,
+                connection_hibernated: 0,
+                connection_stalled: 0,
                 mtu_updated: 0,
                 slow_start_exited: 0,
+                pto_probe_sent: 0,
+                handshake_retransmission: 0,
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                congestion_controller_initialized: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -7484,6 +10495,8 @@ pub mod testing {
                 endpoint_datagram_received: 0,
                 endpoint_datagram_dropped: 0,
                 endpoint_connection_attempt_failed: 0,
+                endpoint_connection_attempt_rejected: 0,
+                endpoint_connection_attempt_retried: 0,
                 platform_tx: 0,
                 platform_tx_error: 0,
                 platform_rx: 0,
@@ -7533,6 +10546,22 @@ pub mod testing {
             let event = event.into_event();
             self.output.push(format!("{event:?}"));
         }
+        fn on_endpoint_connection_attempt_rejected(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRejected,
+        ) {
+            self.endpoint_connection_attempt_rejected += 1;
+            let event = event.into_event();
+            self.output.push(format!("{event:?}"));
+        }
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRetried,
+        ) {
+            self.endpoint_connection_attempt_retried += 1;
+            let event = event.into_event();
+            self.output.push(format!("{event:?}"));
+        }
         fn on_platform_tx(&mut self, event: builder::PlatformTx) {
             self.platform_tx += 1;
             let event = event.into_event();
@@ -7571,6 +10600,12 @@ pub mod testing {
         fn quic_version(&self) -> Option<u32> {
             Some(1)
         }
+        fn attempt_id(&self) -> Option<u64> {
+            self.attempt_id
+        }
+        fn set_attempt_id(&mut self, attempt_id: Option<u64>) {
+            self.attempt_id = attempt_id;
+        }
     }
     impl super::ConnectionPublisher for Publisher {
         fn on_application_protocol_information(
@@ -7653,6 +10688,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_rtt_sample(&mut self, event: builder::RttSample) {
+            self.rtt_sample += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_congestion(&mut self, event: builder::Congestion) {
             self.congestion += 1;
             let event = event.into_event();
@@ -7804,6 +10846,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_session_resumption(&mut self, event: builder::SessionResumption) {
+            self.session_resumption += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello) {
             self.tls_client_hello += 1;
             let event = event.into_event();
@@ -7818,6 +10867,20 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_client_hello_fragmented(&mut self, event: builder::ClientHelloFragmented) {
+            self.client_hello_fragmented += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_datagram_coalesced(&mut self, event: builder::DatagramCoalesced) {
+            self.datagram_coalesced += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
             self.rx_stream_progress += 1;
             let event = event.into_event();
@@ -7832,6 +10895,34 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_stream_marker_sent(&mut self, event: builder::StreamMarkerSent) {
+            self.stream_marker_sent += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_stream_marker_lost(&mut self, event: builder::StreamMarkerLost) {
+            self.stream_marker_lost += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_stream_marker_acknowledged(&mut self, event: builder::StreamMarkerAcknowledged) {
+            self.stream_marker_acknowledged += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_peer_stream_opened(&mut self, event: builder::PeerStreamOpened) {
+            self.peer_stream_opened += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_keep_alive_timer_expired(&mut self, event: builder::KeepAliveTimerExpired) {
             self.keep_alive_timer_expired += 1;
             let event = event.into_event();
@@ -7839,6 +10930,20 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_connection_hibernated(&mut self, event: builder::ConnectionHibernated) {
+            self.connection_hibernated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_connection_stalled(&mut self, event: builder::ConnectionStalled) {
+            self.connection_stalled += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_mtu_updated(&mut self, event: builder::MtuUpdated) {
             self.mtu_updated += 1;
             let event = event.into_event();
@@ -7853,6 +10958,20 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_pto_probe_sent(&mut self, event: builder::PtoProbeSent) {
+            self.pto_probe_sent += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_handshake_retransmission(&mut self, event: builder::HandshakeRetransmission) {
+            self.handshake_retransmission += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_delivery_rate_sampled(&mut self, event: builder::DeliveryRateSampled) {
             self.delivery_rate_sampled += 1;
             let event = event.into_event();
@@ -7874,12 +10993,25 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_congestion_controller_initialized(
+            &mut self,
+            event: builder::CongestionControllerInitialized,
+        ) {
+            self.congestion_controller_initialized += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn quic_version(&self) -> u32 {
             1
         }
         fn subject(&self) -> api::Subject {
             api::Subject::Connection { id: 0 }
         }
+        fn on_profile_scope_entered(&mut self, _scope: crate::event::profile::Scope) {}
+        fn on_profile_scope_exited(&mut self, _scope: crate::event::profile::Scope) {}
+        fn on_connection_info_updated(&mut self, _info: builder::ConnectionInfo) {}
     }
     impl Drop for Publisher {
         fn drop(&mut self) {
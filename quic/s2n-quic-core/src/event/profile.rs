@@ -0,0 +1,62 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hot paths of the transport which can be profiled by an event subscriber
+//!
+//! The transport calls [`Subscriber::on_profile_scope_entered`](crate::event::Subscriber::on_profile_scope_entered)
+//! when it enters one of the [`Scope`]s and
+//! [`Subscriber::on_profile_scope_exited`](crate::event::Subscriber::on_profile_scope_exited) once
+//! it leaves it. The hooks don't carry any timing information, since the transport doesn't have
+//! access to a clock with enough precision, so subscribers are expected to read their own.
+
+/// A hot path of the transport
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Scope {
+    /// Removing header protection from and decrypting a received packet
+    PacketDecrypt,
+    /// Processing the frames of a decrypted packet
+    FrameProcessing,
+    /// Writing a connection's packets into the transmit queue
+    Transmit,
+    /// Handling a connection's expired timers
+    Timers,
+}
+
+impl Scope {
+    /// All of the scopes, ordered by [`Self::index`]
+    pub const ALL: &'static [Self] = &[
+        Self::PacketDecrypt,
+        Self::FrameProcessing,
+        Self::Transmit,
+        Self::Timers,
+    ];
+
+    /// Returns the position of the scope in [`Self::ALL`]
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PacketDecrypt => "packet_decrypt",
+            Self::FrameProcessing => "frame_processing",
+            Self::Transmit => "transmit",
+            Self::Timers => "timers",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_test() {
+        for (index, scope) in Scope::ALL.iter().enumerate() {
+            assert_eq!(scope.index(), index);
+        }
+    }
+}
@@ -85,6 +85,7 @@ impl AckElicitable for crate::frame::MaxStreamData {}
 impl AckElicitable for crate::frame::MaxStreams {}
 impl AckElicitable for crate::frame::NewConnectionId<'_> {}
 impl AckElicitable for crate::frame::NewToken<'_> {}
+impl AckElicitable for crate::frame::ObservedAddress {}
 impl AckElicitable for crate::frame::Padding {
     #[inline]
     fn ack_elicitation(&self) -> AckElicitation {
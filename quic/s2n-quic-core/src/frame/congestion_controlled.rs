@@ -31,6 +31,7 @@ impl CongestionControlled for crate::frame::MaxStreamData {}
 impl CongestionControlled for crate::frame::MaxStreams {}
 impl CongestionControlled for crate::frame::NewConnectionId<'_> {}
 impl CongestionControlled for crate::frame::NewToken<'_> {}
+impl CongestionControlled for crate::frame::ObservedAddress {}
 impl CongestionControlled for crate::frame::Padding {
     //= https://www.rfc-editor.org/rfc/rfc9002#section-2
     //= type=exception
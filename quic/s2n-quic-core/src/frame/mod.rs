@@ -6,6 +6,7 @@
 use crate::{
     event,
     frame::{ack_elicitation::AckElicitable, congestion_controlled::CongestionControlled},
+    varint::VarInt,
 };
 use core::fmt;
 use s2n_codec::{
@@ -27,6 +28,10 @@ mod tests;
 
 pub(crate) type Tag = u8;
 
+/// Frame types defined by extensions are encoded as variable-length integers that don't
+/// fit in a single byte
+pub(crate) type ExtensionTag = VarInt;
+
 pub type FrameRef<'a> = Frame<'a, ack::AckRangesDecoder<'a>, DecoderBuffer<'a>>;
 pub type FrameMut<'a> = Frame<'a, ack::AckRangesDecoder<'a>, DecoderBufferMut<'a>>;
 
@@ -36,12 +41,17 @@ pub trait FrameTrait: AckElicitable + CongestionControlled + path_validation::Pr
 impl<T: AckElicitable + CongestionControlled + path_validation::Probing> FrameTrait for T {}
 
 macro_rules! frames {
-    ($ack:ident, $data:ident | $($tag_macro:ident => $module:ident, $handler:ident, $ty:ident $([$($generics:tt)+])?;)*) => {
+    ($ack:ident, $data:ident | $($tag_macro:ident => $module:ident, $handler:ident, $ty:ident $([$($generics:tt)+])?;)* | $($ext_tag_macro:ident => $ext_module:ident, $ext_handler:ident, $ext_ty:ident;)*) => {
         $(
             #[macro_use]
             pub mod $module;
             pub use $module::$ty;
         )*
+        $(
+            #[macro_use]
+            pub mod $ext_module;
+            pub use $ext_module::$ext_ty;
+        )*
 
         pub type RemainingBuffer<'a> = Option<DecoderBufferMut<'a>>;
 
@@ -50,14 +60,21 @@ macro_rules! frames {
             $(
                 $ty($module::$ty $(<$($generics)*>)?),
             )*
+            $(
+                $ext_ty($ext_module::$ext_ty),
+            )*
         }
 
         impl<'a, $ack, $data> Frame<'a, $ack, $data> {
+            /// Returns the frame type
             #[inline]
-            pub fn tag(&self) -> Tag {
+            pub fn tag(&self) -> ExtensionTag {
                 match self {
                     $(
-                        Frame::$ty(frame) => frame.tag(),
+                        Frame::$ty(frame) => frame.tag().into(),
+                    )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.tag(),
                     )*
                 }
             }
@@ -74,6 +91,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(inner) => inner.into_event(),
                     )*
+                    $(
+                        Frame::$ext_ty(inner) => inner.into_event(),
+                    )*
                 }
             }
         }
@@ -85,6 +105,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.ack_elicitation(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.ack_elicitation(),
+                    )*
                 }
             }
         }
@@ -96,6 +119,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.path_validation(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.path_validation(),
+                    )*
                 }
             }
         }
@@ -109,6 +135,15 @@ macro_rules! frames {
             }
         )*
 
+        $(
+            impl<'a, $ack, $data> From<$ext_module::$ext_ty> for Frame<'a, $ack, $data> {
+                #[inline]
+                fn from(v: $ext_module::$ext_ty)  -> Frame<'a, $ack, $data> {
+                    Frame::$ext_ty(v)
+                }
+            }
+        )*
+
         impl<'a, $ack, $data: DecoderValueMut<'a>> DecoderValueMut<'a> for Frame<'a, $ack, $data>
         where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
             #[inline]
@@ -124,6 +159,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => buffer.encode(frame),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => buffer.encode(frame),
+                    )*
                 }
             }
         }
@@ -140,6 +178,13 @@ macro_rules! frames {
                     Ok(Frame::$ty(frame))
                 }
             )*
+
+            $(
+                #[inline]
+                fn $ext_handler(&mut self, frame: $ext_module::$ext_ty) -> Result<Self::Output, DecoderError> {
+                    Ok(Frame::$ext_ty(frame))
+                }
+            )*
         }
 
         pub trait FrameDecoder<'a, $ack, $data: DecoderValueMut<'a>>
@@ -150,6 +195,10 @@ macro_rules! frames {
                 fn $handler(&mut self, frame: $module::$ty $(<$($generics)*>)?) -> Result<Self::Output, DecoderError>;
             )*
 
+            $(
+                fn $ext_handler(&mut self, frame: $ext_module::$ext_ty) -> Result<Self::Output, DecoderError>;
+            )*
+
             #[inline]
             fn handle_extension_frame(&mut self, buffer: DecoderBufferMut<'a>) -> DecoderBufferMutResult<'a, Self::Output> {
                 let _ = buffer;
@@ -166,7 +215,19 @@ macro_rules! frames {
                 match tag {
                     // Make sure the single byte frame tags fit into a small variable-integer
                     // otherwise fallback to extension selection
-                    0b0100_0000..=0xff => self.handle_extension_frame(buffer),
+                    0b0100_0000..=0xff => {
+                        match peek_extension_tag(&buffer)? {
+                            $(
+                                $ext_tag_macro!() => {
+                                    let (tag, buffer) = buffer.decode::<ExtensionTag>()?;
+                                    let (frame, buffer) = buffer.decode_parameterized(tag)?;
+                                    let output = self.$ext_handler(frame)?;
+                                    Ok((output, buffer))
+                                },
+                            )*
+                            _ => self.handle_extension_frame(buffer),
+                        }
+                    }
                     $(
                         $tag_macro!() => {
                             let buffer = buffer.skip(core::mem::size_of::<Tag>())?;
@@ -195,10 +256,34 @@ macro_rules! frames {
                     ));
                 }
             )*
+
+            $(
+                #[test]
+                fn $ext_module() {
+                    assert_codec_round_trip_sample_file!(FrameMut, concat!(
+                        "src/frame/test_samples/",
+                        stringify!($ext_module),
+                        ".bin"
+                    ));
+                }
+            )*
         }
     };
 }
 
+/// Reads the frame type of an extension frame without consuming it from the buffer
+#[inline]
+fn peek_extension_tag(buffer: &DecoderBufferMut) -> Result<u64, DecoderError> {
+    let first = buffer.peek_byte(0)?;
+    // The two most significant bits of the first byte encode the length of the integer
+    let len = 1usize << (first >> 6);
+    let mut tag = (first & 0b0011_1111) as u64;
+    for index in 1..len {
+        tag = (tag << 8) | buffer.peek_byte(index)? as u64;
+    }
+    Ok(tag)
+}
+
 // This implements a codec for a frame that contains simple
 // values that don't vary based on the tag
 macro_rules! simple_frame_codec {
@@ -256,6 +341,8 @@ frames! {
     connection_close_tag => connection_close, handle_connection_close_frame, ConnectionClose['a];
     handshake_done_tag => handshake_done, handle_handshake_done_frame, HandshakeDone;
     datagram_tag => datagram, handle_datagram_frame, Datagram[Data];
+    |
+    observed_address_tag => observed_address, handle_observed_address_frame, ObservedAddress;
 }
 
 #[derive(Clone, Copy, Debug, Default)]
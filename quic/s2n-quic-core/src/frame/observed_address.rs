@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    frame::ExtensionTag,
+    inet::{SocketAddress, SocketAddressV4, SocketAddressV6},
+    varint::VarInt,
+};
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// https://datatracker.ietf.org/doc/draft-ietf-quic-address-discovery/
+//
+// The OBSERVED_ADDRESS frame is used by an endpoint to inform its peer of the source
+// address that was observed on packets received from that peer. There is one frame
+// type for each address family.
+
+macro_rules! observed_address_tag {
+    () => {
+        0x9f81a6u64..=0x9f81a7u64
+    };
+}
+
+const IPV4_TAG: ExtensionTag = VarInt::from_u32(0x9f81a6);
+const IPV6_TAG: ExtensionTag = VarInt::from_u32(0x9f81a7);
+
+// OBSERVED_ADDRESS Frame {
+//   Type (i) = 0x9f81a6..0x9f81a7,
+//   Sequence Number (i),
+//   IPv4 (32) / IPv6 (128),
+//   Port (16),
+// }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObservedAddress {
+    /// A monotonically increasing number that allows the peer to discard
+    /// reordered observations
+    pub sequence_number: VarInt,
+
+    /// The address that was observed by the sender of the frame
+    pub address: SocketAddress,
+}
+
+impl ObservedAddress {
+    #[inline]
+    pub const fn tag(&self) -> ExtensionTag {
+        match self.address {
+            SocketAddress::IpV4(_) => IPV4_TAG,
+            SocketAddress::IpV6(_) => IPV6_TAG,
+        }
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> ObservedAddress {
+        fn decode(tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (sequence_number, buffer) = buffer.decode()?;
+
+            let (address, buffer) = if tag == IPV4_TAG {
+                let (address, buffer) = buffer.decode::<SocketAddressV4>()?;
+                (address.into(), buffer)
+            } else {
+                let (address, buffer) = buffer.decode::<SocketAddressV6>()?;
+                (address.into(), buffer)
+            };
+
+            let frame = ObservedAddress {
+                sequence_number,
+                address,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for ObservedAddress {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.tag());
+        buffer.encode(&self.sequence_number);
+        match &self.address {
+            SocketAddress::IpV4(address) => buffer.encode(address),
+            SocketAddress::IpV6(address) => buffer.encode(address),
+        }
+    }
+}
@@ -80,6 +80,7 @@ impl Probing for crate::frame::NewConnectionId<'_> {
     }
 }
 impl Probing for crate::frame::NewToken<'_> {}
+impl Probing for crate::frame::ObservedAddress {}
 impl Probing for crate::frame::Padding {
     #[inline]
     fn path_validation(&self) -> Probe {
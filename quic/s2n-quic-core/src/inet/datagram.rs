@@ -7,13 +7,33 @@ use crate::{
 
 /// Header information for a datagram sent/received over the network
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
 pub struct Header<Path> {
     pub path: Path,
     pub ecn: ExplicitCongestionNotification,
+    /// The index of the IO provider's receive queue the datagram was read from, if known
+    ///
+    /// For the socket providers, this is the index of the ring buffer the datagram was read from
+    /// (one per rx socket reader). It is not the NIC's RSS queue or the CPU which received the
+    /// datagram.
+    pub queue_id: Option<u32>,
+}
+
+impl<Path> Header<Path> {
+    /// Creates a header for a datagram which wasn't read from a known receive queue
+    #[inline]
+    pub fn new(path: Path, ecn: ExplicitCongestionNotification) -> Self {
+        Self {
+            path,
+            ecn,
+            queue_id: None,
+        }
+    }
 }
 
 /// Metadata for a datagram sent/received over the network
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
 pub struct DatagramInfo {
     pub timestamp: Timestamp,
     pub payload_len: usize,
@@ -21,6 +41,31 @@ pub struct DatagramInfo {
     pub destination_connection_id: connection::LocalId,
     pub destination_connection_id_classification: connection::id::Classification,
     pub source_connection_id: Option<connection::PeerId>,
+    /// The index of the IO provider's receive queue the datagram was read from, if known
+    pub queue_id: Option<u32>,
+}
+
+impl DatagramInfo {
+    /// Creates the metadata for a datagram which wasn't read from a known receive queue
+    #[inline]
+    pub fn new(
+        timestamp: Timestamp,
+        payload_len: usize,
+        ecn: ExplicitCongestionNotification,
+        destination_connection_id: connection::LocalId,
+        destination_connection_id_classification: connection::id::Classification,
+        source_connection_id: Option<connection::PeerId>,
+    ) -> Self {
+        Self {
+            timestamp,
+            payload_len,
+            ecn,
+            destination_connection_id,
+            destination_connection_id_classification,
+            source_connection_id,
+            queue_id: None,
+        }
+    }
 }
 
 /// Additional metadata for a datagram sent/received over the network
@@ -83,6 +83,24 @@ impl IpV6Address {
         }
     }
 
+    /// Converts the IP address into IPv4 only if it is an IPv4-mapped address (`::ffff:0:0/96`)
+    ///
+    /// Unlike [`Self::unmap`], IPv4-compatible and NAT64 addresses are not converted, since these
+    /// can't be reached by transmitting to the IPv4 address directly.
+    #[inline]
+    pub const fn to_ipv4_mapped(self) -> Option<IpV4Address> {
+        match self.segments() {
+            [0, 0, 0, 0, 0, 0xffff, ab, cd] => {
+                let [a, b] = u16::to_be_bytes(ab);
+                let [c, d] = u16::to_be_bytes(cd);
+                Some(IpV4Address {
+                    octets: [a, b, c, d],
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the [`ip::UnicastScope`] for the given address
     ///
     /// See the [IANA Registry](https://www.iana.org/assignments/ipv6-address-space/ipv6-address-space.xhtml)
@@ -315,6 +333,16 @@ impl SocketAddressV6 {
         }
     }
 
+    /// Converts the address into IPv4 only if it is an IPv4-mapped address (`::ffff:0:0/96`)
+    #[inline]
+    pub fn to_ipv4_mapped(self) -> Option<SocketAddressV4> {
+        let ip = self.ip.to_ipv4_mapped()?;
+        Some(SocketAddressV4 {
+            ip,
+            port: self.port,
+        })
+    }
+
     #[inline]
     pub const fn unicast_scope(&self) -> Option<ip::UnicastScope> {
         self.ip.unicast_scope()
@@ -713,6 +741,28 @@ mod tests {
         })
     }
 
+    /// Asserts the IPv4-mapped conversion matches the standard library
+    #[test]
+    fn to_ipv4_mapped_test() {
+        let g = gen::<([u8; 12], [u8; 4])>().map_gen(|(prefix, ipv4)| {
+            // make sure we generate a good amount of mapped addresses
+            let prefix = if prefix[0] % 2 == 0 {
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff]
+            } else {
+                prefix
+            };
+            let mut octets = [0; IPV6_LEN];
+            octets[..12].copy_from_slice(&prefix);
+            octets[12..].copy_from_slice(&ipv4);
+            IpV6Address::from(octets)
+        });
+        check!().with_generator(g).cloned().for_each(|subject| {
+            let expected = std::net::Ipv6Addr::from(subject).to_ipv4_mapped();
+            let actual = subject.to_ipv4_mapped().map(std::net::Ipv4Addr::from);
+            assert_eq!(expected, actual);
+        });
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn snapshot_test() {
@@ -235,6 +235,22 @@ impl<T> IntervalSet<T> {
         self.intervals.clear()
     }
 
+    /// Shrinks the capacity of the `IntervalSet` as much as possible
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// # use s2n_quic_transport::interval_set::IntervalSet;
+    /// let mut set = IntervalSet::with_capacity(16);
+    /// assert!(set.insert(0..4).is_ok());
+    /// set.shrink_to_fit();
+    /// assert!(set.capacity() >= 1);
+    /// ```
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.intervals.shrink_to_fit()
+    }
+
     /// Removes the lowest `Interval` in the set, if any
     ///
     /// # Examples
@@ -47,6 +47,7 @@ where
                     event::builder::EndpointMeta {
                         endpoint_type: E::ENDPOINT_TYPE,
                         timestamp,
+                        attempt_id: None,
                     },
                     None,
                     subscriber,
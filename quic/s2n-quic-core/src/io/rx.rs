@@ -4,6 +4,7 @@
 use crate::{event, inet::datagram, path};
 use core::task::{Context, Poll};
 
+pub mod handle_map;
 pub mod pair;
 
 /// Handle to a receive IO provider
@@ -42,6 +43,21 @@ pub trait RxExt: Rx {
     {
         pair::Channel { a: self, b: other }
     }
+
+    /// Maps one type of handle to another with a mapping function
+    ///
+    /// If the mapping function returns `None`, the datagram is dropped.
+    #[inline]
+    fn with_handle_map<Map, Handle>(self, map: Map) -> handle_map::Channel<Map, Self, Handle>
+    where
+        Map: Fn(&Self::PathHandle) -> Option<Handle>,
+    {
+        handle_map::Channel {
+            map,
+            rx: self,
+            handle: Default::default(),
+        }
+    }
 }
 
 /// Implement the extension traits for all Rx queues
@@ -0,0 +1,149 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{event, inet::datagram, io::rx, path};
+use core::{
+    marker::PhantomData,
+    task::{Context, Poll},
+};
+
+pub struct Channel<Map, Rx, U> {
+    pub(super) map: Map,
+    pub(super) rx: Rx,
+    pub(super) handle: PhantomData<U>,
+}
+
+impl<Map, Rx, U> rx::Rx for Channel<Map, Rx, U>
+where
+    Map: 'static + Fn(&Rx::PathHandle) -> Option<U>,
+    Rx: rx::Rx,
+    Rx::Queue: 'static,
+    U: path::Handle,
+{
+    type PathHandle = U;
+    type Queue = Queue<'static, Map, Rx::Queue, U>;
+    type Error = Rx::Error;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.rx.poll_ready(cx)
+    }
+
+    #[inline]
+    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+        let map = &mut self.map;
+        let rx = &mut self.rx;
+        rx.queue(|rx| {
+            let (map, rx): (&'static mut _, &'static mut _) = unsafe {
+                // Safety: As noted in the [transmute examples](https://doc.rust-lang.org/std/mem/fn.transmute.html#examples)
+                // it can be used to temporarily extend the lifetime of a reference. In this case, we
+                // don't want to use GATs until the MSRV is >=1.65.0, which means `Self::Queue` is not
+                // allowed to take generic lifetimes.
+                //
+                // We are left with using a `'static` lifetime here and encapsulating it in a private
+                // field. The `Self::Queue` struct is then borrowed for the lifetime of the `F`
+                // function. This will prevent the value from escaping beyond the lifetime of `&mut
+                // self`.
+                //
+                // See https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=9a32abe85c666f36fb2ec86496cc41b4
+                //
+                // Once https://github.com/aws/s2n-quic/issues/1742 is resolved this code can go away
+                (core::mem::transmute(map), core::mem::transmute(rx))
+            };
+
+            let mut queue = Queue {
+                map,
+                rx,
+                handle: PhantomData,
+            };
+            f(&mut queue);
+        });
+    }
+
+    #[inline]
+    fn handle_error<E: event::EndpointPublisher>(self, error: Self::Error, events: &mut E) {
+        self.rx.handle_error(error, events)
+    }
+}
+
+pub struct Queue<'a, Map, Rx, U>
+where
+    Map: Fn(&Rx::Handle) -> Option<U>,
+    Rx: rx::Queue,
+{
+    map: &'a Map,
+    rx: &'a mut Rx,
+    handle: PhantomData<U>,
+}
+
+impl<'a, Map, Rx, U> rx::Queue for Queue<'a, Map, Rx, U>
+where
+    Map: Fn(&Rx::Handle) -> Option<U>,
+    Rx: rx::Queue,
+    U: path::Handle,
+{
+    type Handle = U;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        let map = self.map;
+        self.rx.for_each(|header, payload| {
+            // datagrams that can't be mapped are dropped
+            if let Some(path) = map(&header.path) {
+                let header = datagram::Header {
+                    path,
+                    ecn: header.ecn,
+                    queue_id: header.queue_id,
+                };
+                on_packet(header, payload);
+            }
+        });
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.rx.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        io::{
+            rx::{Queue as _, Rx as _, RxExt as _},
+            testing,
+        },
+        path::{Handle as _, RemoteAddress},
+    };
+
+    #[test]
+    fn handle_map_test() {
+        let channel = testing::Channel::default();
+        let mut mapped = channel.clone().with_handle_map(|handle: &testing::Handle| {
+            // drop any datagrams from port 456
+            if handle.remote_address.port() == 456 {
+                return None;
+            }
+
+            Some(handle.remote_address())
+        });
+
+        for port in [123, 456] {
+            let mut message = testing::Message::default();
+            message.header.path.remote_address.set_port(port);
+            message.payload = vec![1, 2, 3];
+            channel.push(message);
+        }
+
+        let mut received = vec![];
+        mapped.queue(|queue| {
+            queue.for_each(|header, payload| {
+                received.push((header.path, payload.to_vec()));
+            });
+        });
+
+        let mut expected = RemoteAddress::default();
+        expected.set_port(123);
+        assert_eq!(received, vec![(expected, vec![1, 2, 3])]);
+    }
+}
@@ -62,13 +62,13 @@ pub struct Message {
 impl Default for Message {
     fn default() -> Self {
         Self {
-            header: datagram::Header {
-                ecn: Default::default(),
-                path: Tuple {
+            header: datagram::Header::new(
+                Tuple {
                     local_address: Default::default(),
                     remote_address: Default::default(),
                 },
-            },
+                Default::default(),
+            ),
             payload: Default::default(),
         }
     }
@@ -48,6 +48,9 @@ const PACKET_TYPE_OFFSET: u8 = 4;
 
 pub(crate) type Version = u32;
 
+/// The version number of QUIC version 1
+pub const VERSION_1: u32 = 0x0000_0001;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
 //# Destination Connection ID Length:  The byte following the version
 //#    contains the length in bytes of the Destination Connection ID
@@ -249,6 +249,16 @@ impl<V> Map<V> {
         self.index = self.values.len();
     }
 
+    /// Releases any capacity beyond the default if the map is empty
+    ///
+    /// The capacity will grow again as more packet number entries are added.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        if self.is_empty() && self.values.len() > DEFAULT_CAPACITY {
+            *self = Self::default();
+        }
+    }
+
     #[inline]
     fn pn_index(&self, packet_number: PacketNumber) -> Option<usize> {
         // the map is empty so there are no valid entries
@@ -763,6 +773,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn shrink_to_fit_test() {
+        let mut map = TestMap::default();
+        let space = PacketNumberSpace::ApplicationData;
+
+        for pn in 0..64u8 {
+            map.insert(space.new_packet_number(VarInt::from_u8(pn)), pn as u64);
+        }
+        assert_eq!(map.values.len(), 64);
+
+        // entries are retained while the map is occupied
+        map.shrink_to_fit();
+        assert_eq!(map.values.len(), 64);
+
+        map.remove_range(PacketNumberRange::new(
+            space.new_packet_number(VarInt::from_u8(0)),
+            space.new_packet_number(VarInt::from_u8(63)),
+        ))
+        .for_each(drop);
+        assert!(map.is_empty());
+
+        map.shrink_to_fit();
+        assert_eq!(map.values.len(), DEFAULT_CAPACITY);
+
+        // the map continues to work after shrinking
+        let pn = space.new_packet_number(VarInt::from_u8(64));
+        map.insert(pn, 64);
+        assert_eq!(map.get(pn), Some(&64));
+    }
+
     #[test]
     fn differential_test() {
         check!()
@@ -90,6 +90,34 @@ impl fmt::Debug for PacketNumber {
     }
 }
 
+/// The serialized form of a [`PacketNumber`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "PacketNumber")]
+struct SerdePacketNumber {
+    space: PacketNumberSpace,
+    value: VarInt,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PacketNumber {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = SerdePacketNumber {
+            space: self.space(),
+            value: PacketNumber::as_varint(*self),
+        };
+        serde::Serialize::serialize(&value, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PacketNumber {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SerdePacketNumber { space, value } = serde::Deserialize::deserialize(deserializer)?;
+        Ok(space.new_packet_number(value))
+    }
+}
+
 impl fmt::Display for PacketNumber {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.as_u64().fmt(f)
@@ -12,6 +12,7 @@ use bolero_generator::*;
 /// Contains all of the available packet spaces for QUIC packets
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(any(test, feature = "generator"), derive(TypeGenerator))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum PacketNumberSpace {
     // This MUST start with 1 to enable optimized memory layout
@@ -232,3 +232,15 @@ fn size_of_snapshots() {
     assert_debug_snapshot!("ProtectedPacketNumber", size_of::<ProtectedPacketNumber>());
     assert_debug_snapshot!("TruncatedPacketNumber", size_of::<TruncatedPacketNumber>());
 }
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_test() {
+    let packet_number = PacketNumberSpace::Handshake.new_packet_number(VarInt::from_u8(42));
+    let json = serde_json::to_string(&packet_number).unwrap();
+    assert_eq!(json, r#"{"space":"Handshake","value":42}"#);
+
+    let decoded: PacketNumber = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.space(), PacketNumberSpace::Handshake);
+    assert_eq!(decoded, packet_number);
+}
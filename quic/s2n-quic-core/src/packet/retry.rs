@@ -7,6 +7,7 @@ use crate::{
         packet_protection, retry,
         retry::{IntegrityTag, RetryKey},
     },
+    endpoint,
     inet::SocketAddress,
     packet::{
         decoding::HeaderDecoder,
@@ -122,6 +123,29 @@ impl<'a> Retry<'a> {
         random: &mut dyn random::Generator,
         token_format: &mut T,
         packet_buf: &mut [u8],
+    ) -> Option<Range<usize>> {
+        Self::encode_packet_with_challenge::<T, C>(
+            remote_address,
+            packet,
+            local_connection_id,
+            random,
+            token_format,
+            &[],
+            packet_buf,
+        )
+    }
+
+    /// Encodes a Retry packet with the `challenge` prefixed to the token
+    ///
+    /// An empty challenge encodes a regular Retry packet.
+    pub fn encode_packet_with_challenge<T: token::Format, C: RetryKey>(
+        remote_address: &SocketAddress,
+        packet: &ProtectedInitial,
+        local_connection_id: &connection::LocalId,
+        random: &mut dyn random::Generator,
+        token_format: &mut T,
+        challenge: &[u8],
+        packet_buf: &mut [u8],
     ) -> Option<Range<usize>> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.1
         //# This value MUST NOT be equal to the Destination
@@ -144,6 +168,15 @@ impl<'a> Retry<'a> {
             &connection::PeerId::try_from_bytes(retry_packet.destination_connection_id).unwrap();
         let mut context = token::Context::new(remote_address, destination_connection_id, random);
 
+        // the address token is framed so it can't be confused with a challenge
+        let len = endpoint::challenge::encoding_size(challenge.len());
+        if challenge.len() > endpoint::challenge::MAX_LEN
+            || buffer.remaining_capacity() < len + T::TOKEN_LEN + INTEGRITY_TAG_LEN
+        {
+            return None;
+        }
+        endpoint::challenge::encode_prefix(challenge, &mut buffer);
+
         let mut outcome = None;
 
         buffer.write_sized(T::TOKEN_LEN, |token_buf| {
@@ -0,0 +1,267 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A cache of path characteristics shared between connections to the same destination
+//!
+//! Every new connection starts with a conservative view of the path: a default initial RTT, the
+//! minimum MTU and an initial congestion window of a few packets. Workloads which open a
+//! connection per request pay the cost of rediscovering the path each time. The cache records the
+//! characteristics of paths when their connections close and seeds new connections to the same
+//! remote prefix with them.
+
+use crate::{
+    connection::limits::ValidationError,
+    inet::{IpAddress, SocketAddress},
+    recovery::bandwidth::Bandwidth,
+    time::Timestamp,
+};
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+
+const DEFAULT_CAPACITY: usize = 1024;
+const DEFAULT_IPV4_PREFIX_LEN: u8 = 24;
+const DEFAULT_IPV6_PREFIX_LEN: u8 = 48;
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// The characteristics of a path observed by a previous connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Characteristics {
+    /// The smoothed RTT of the path
+    pub smoothed_rtt: Duration,
+    /// The minimum RTT of the path
+    pub min_rtt: Duration,
+    /// The estimated bottleneck bandwidth of the path
+    pub bandwidth: Bandwidth,
+    /// The largest QUIC datagram which was validated on the path
+    ///
+    /// This does not include the size of UDP and IP headers.
+    pub max_datagram_size: u16,
+}
+
+impl Characteristics {
+    /// Creates a new set of path characteristics
+    #[inline]
+    pub fn new(
+        smoothed_rtt: Duration,
+        min_rtt: Duration,
+        bandwidth: Bandwidth,
+        max_datagram_size: u16,
+    ) -> Self {
+        Self {
+            smoothed_rtt,
+            min_rtt,
+            bandwidth,
+            max_datagram_size,
+        }
+    }
+
+    /// Returns the bandwidth-delay product of the path, in bytes
+    #[inline]
+    pub fn bandwidth_delay_product(&self) -> u64 {
+        self.bandwidth * self.min_rtt
+    }
+}
+
+/// Configuration for a [`Cache`]
+///
+/// Remote addresses are grouped by prefix, so connections to hosts in the same network share
+/// their path characteristics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    capacity: usize,
+    ipv4_prefix_len: u8,
+    ipv6_prefix_len: u8,
+    max_age: Duration,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    /// Creates a configuration with the default values
+    pub const fn new() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            ipv4_prefix_len: DEFAULT_IPV4_PREFIX_LEN,
+            ipv6_prefix_len: DEFAULT_IPV6_PREFIX_LEN,
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+
+    /// Sets the maximum number of prefixes which are cached
+    ///
+    /// Once the cache is full, the least recently updated prefix is evicted. Defaults to `1024`.
+    pub fn with_capacity(mut self, capacity: usize) -> Result<Self, ValidationError> {
+        if capacity == 0 {
+            return Err(ValidationError("capacity must be greater than 0"));
+        }
+        self.capacity = capacity;
+        Ok(self)
+    }
+
+    /// Sets the prefix lengths used to group remote addresses
+    ///
+    /// IPv4-mapped IPv6 addresses are grouped as IPv4 addresses. The lengths must not be larger
+    /// than `32` and `128`, respectively. Defaults to `24` and `48`.
+    pub fn with_prefix_len(mut self, ipv4: u8, ipv6: u8) -> Result<Self, ValidationError> {
+        if ipv4 > 32 {
+            return Err(ValidationError(
+                "ipv4 prefix length must not be greater than 32",
+            ));
+        }
+        if ipv6 > 128 {
+            return Err(ValidationError(
+                "ipv6 prefix length must not be greater than 128",
+            ));
+        }
+        self.ipv4_prefix_len = ipv4;
+        self.ipv6_prefix_len = ipv6;
+        Ok(self)
+    }
+
+    /// Sets how long cached characteristics are used to seed new connections
+    ///
+    /// Networks change over time, so older observations are discarded. Defaults to 10 minutes.
+    pub fn with_max_age(mut self, max_age: Duration) -> Result<Self, ValidationError> {
+        if max_age.is_zero() {
+            return Err(ValidationError("max_age must be greater than 0"));
+        }
+        self.max_age = max_age;
+        Ok(self)
+    }
+
+    /// Returns the maximum number of prefixes which are cached
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns how long cached characteristics are used to seed new connections
+    #[inline]
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Returns the prefix of the remote address that the characteristics are cached under
+    #[inline]
+    pub fn prefix(&self, remote_address: &SocketAddress) -> IpAddress {
+        let (mut ip, prefix_len) = match remote_address.ip().unmap() {
+            ip @ IpAddress::Ipv4(_) => (ip, self.ipv4_prefix_len),
+            ip => (ip, self.ipv6_prefix_len),
+        };
+
+        let octets = match &mut ip {
+            IpAddress::Ipv4(ip) => ip.as_bytes_mut(),
+            IpAddress::Ipv6(ip) => ip.as_bytes_mut(),
+        };
+
+        let bytes = prefix_len as usize / 8;
+        let bits = prefix_len % 8;
+        if let Some((partial, rest)) = octets[bytes..].split_first_mut() {
+            *partial &= !(0xffu8 >> bits);
+            rest.fill(0);
+        }
+
+        ip
+    }
+}
+
+/// A size-bounded map of remote prefixes to the characteristics of their paths
+///
+/// Entries are keyed by the prefix of the remote address, as configured by [`Config`], and are
+/// discarded once they are older than [`Config::max_age`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct Cache {
+    config: Config,
+    entries: BTreeMap<IpAddress, (Characteristics, Timestamp)>,
+}
+
+#[cfg(feature = "alloc")]
+impl Cache {
+    /// Creates an empty cache with the given configuration
+    #[inline]
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of cached prefixes
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no prefixes are cached
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the characteristics cached for the prefix of the remote address, if they are
+    /// still fresh
+    #[inline]
+    pub fn get(
+        &mut self,
+        remote_address: &SocketAddress,
+        now: Timestamp,
+    ) -> Option<Characteristics> {
+        let prefix = self.config.prefix(remote_address);
+        let (characteristics, updated) = *self.entries.get(&prefix)?;
+
+        if now.saturating_duration_since(updated) >= self.config.max_age {
+            self.entries.remove(&prefix);
+            return None;
+        }
+
+        Some(characteristics)
+    }
+
+    /// Records the characteristics of a path to the remote address
+    ///
+    /// The most recent observation replaces any previous one for the same prefix.
+    #[inline]
+    pub fn insert(
+        &mut self,
+        remote_address: &SocketAddress,
+        characteristics: Characteristics,
+        now: Timestamp,
+    ) {
+        let prefix = self.config.prefix(remote_address);
+
+        if !self.entries.contains_key(&prefix) && self.entries.len() >= self.config.capacity {
+            self.evict();
+        }
+
+        self.entries.insert(prefix, (characteristics, now));
+    }
+
+    /// Removes the least recently updated entry
+    ///
+    /// Evictions only happen when a new prefix is inserted into a full cache, so a linear scan is
+    /// preferred over maintaining a separate ordering of the entries.
+    #[inline]
+    fn evict(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, (_, updated))| *updated)
+            .map(|(prefix, _)| *prefix);
+
+        if let Some(prefix) = oldest {
+            self.entries.remove(&prefix);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests;
@@ -0,0 +1,138 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::time::clock::testing::now;
+use std::net::SocketAddr;
+
+fn addr(addr: &str) -> SocketAddress {
+    addr.parse::<SocketAddr>().unwrap().into()
+}
+
+fn characteristics(rtt_ms: u64) -> Characteristics {
+    let rtt = Duration::from_millis(rtt_ms);
+    Characteristics::new(rtt, rtt, Bandwidth::new(100_000, rtt), 1400)
+}
+
+#[test]
+fn prefix_test() {
+    let config = Config::new();
+    assert_eq!(
+        config.prefix(&addr("192.0.2.123:443")),
+        config.prefix(&addr("192.0.2.1:1234"))
+    );
+    assert_ne!(
+        config.prefix(&addr("192.0.2.1:443")),
+        config.prefix(&addr("192.0.3.1:443"))
+    );
+
+    // mapped addresses are grouped with their IPv4 form
+    assert_eq!(
+        config.prefix(&addr("[::ffff:192.0.2.1]:443")),
+        config.prefix(&addr("192.0.2.1:443"))
+    );
+
+    assert_eq!(
+        config.prefix(&addr("[2001:db8:1:2::1]:443")),
+        config.prefix(&addr("[2001:db8:1:3::1]:443"))
+    );
+    assert_ne!(
+        config.prefix(&addr("[2001:db8:1::1]:443")),
+        config.prefix(&addr("[2001:db8:2::1]:443"))
+    );
+
+    // prefixes which don't end on a byte boundary
+    let config = Config::new().with_prefix_len(20, 44).unwrap();
+    assert_eq!(
+        config.prefix(&addr("192.0.31.1:443")),
+        config.prefix(&addr("192.0.16.1:443"))
+    );
+    assert_ne!(
+        config.prefix(&addr("192.0.31.1:443")),
+        config.prefix(&addr("192.0.32.1:443"))
+    );
+
+    // lengths longer than the address are rejected
+    assert!(Config::new().with_prefix_len(33, 48).is_err());
+    assert!(Config::new().with_prefix_len(24, 129).is_err());
+
+    // the full address is used when the length covers it
+    let config = Config::new().with_prefix_len(32, 128).unwrap();
+    assert_ne!(
+        config.prefix(&addr("192.0.2.1:443")),
+        config.prefix(&addr("192.0.2.2:443"))
+    );
+    assert_ne!(
+        config.prefix(&addr("[2001:db8::1]:443")),
+        config.prefix(&addr("[2001:db8::2]:443"))
+    );
+}
+
+#[test]
+fn insert_get_test() {
+    let now = now();
+    let mut cache = Cache::new(Config::new());
+    let peer = addr("192.0.2.1:443");
+
+    assert!(cache.get(&peer, now).is_none());
+
+    cache.insert(&peer, characteristics(10), now);
+    assert_eq!(cache.get(&peer, now), Some(characteristics(10)));
+    // other addresses in the prefix share the entry
+    assert_eq!(
+        cache.get(&addr("192.0.2.2:8443"), now),
+        Some(characteristics(10))
+    );
+    assert!(cache.get(&addr("192.0.3.1:443"), now).is_none());
+
+    // the latest observation replaces the previous one
+    cache.insert(&peer, characteristics(20), now);
+    assert_eq!(cache.get(&peer, now), Some(characteristics(20)));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn max_age_test() {
+    let now = now();
+    let max_age = Duration::from_secs(5);
+    let mut cache = Cache::new(Config::new().with_max_age(max_age).unwrap());
+    let peer = addr("192.0.2.1:443");
+
+    cache.insert(&peer, characteristics(10), now);
+    assert!(cache
+        .get(&peer, now + max_age - Duration::from_millis(1))
+        .is_some());
+
+    // stale entries are removed
+    assert!(cache.get(&peer, now + max_age).is_none());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn capacity_test() {
+    let now = now();
+    let mut cache = Cache::new(Config::new().with_capacity(2).unwrap());
+    let a = addr("192.0.2.1:443");
+    let b = addr("192.0.3.1:443");
+    let c = addr("192.0.4.1:443");
+
+    cache.insert(&a, characteristics(10), now);
+    cache.insert(&b, characteristics(20), now + Duration::from_secs(1));
+    // updating an existing prefix doesn't evict anything
+    cache.insert(&a, characteristics(30), now + Duration::from_secs(2));
+    assert_eq!(cache.len(), 2);
+
+    // the least recently updated prefix is evicted
+    let now = now + Duration::from_secs(3);
+    cache.insert(&c, characteristics(40), now);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&b, now).is_none());
+    assert_eq!(cache.get(&a, now), Some(characteristics(30)));
+    assert_eq!(cache.get(&c, now), Some(characteristics(40)));
+}
+
+#[test]
+fn config_validation_test() {
+    assert!(Config::new().with_capacity(0).is_err());
+    assert!(Config::new().with_max_age(Duration::ZERO).is_err());
+}
@@ -17,6 +17,12 @@ pub struct Attempt<'a> {
     pub active_path: Path<'a>,
     /// Information about the packet triggering the migration attempt
     pub packet: PacketInfo<'a>,
+    /// The number of times the peer has previously moved the active path of the connection
+    ///
+    /// Every change of the active path is counted, including moving back to a path which was
+    /// used before. Denied attempts are not counted. This can be used to limit the number of path
+    /// changes over the lifetime of a connection.
+    pub migration_count: u32,
 }
 
 #[derive(Debug)]
@@ -25,6 +31,8 @@ pub struct AttemptBuilder<'a> {
     pub active_path: Path<'a>,
     /// Information about the packet triggering the migration attempt
     pub packet: PacketInfo<'a>,
+    /// The number of times the peer has previously moved the active path of the connection
+    pub migration_count: u32,
 }
 
 impl<'a> From<AttemptBuilder<'a>> for Attempt<'a> {
@@ -33,6 +41,7 @@ impl<'a> From<AttemptBuilder<'a>> for Attempt<'a> {
         Self {
             active_path: builder.active_path,
             packet: builder.packet,
+            migration_count: builder.migration_count,
         }
     }
 }
@@ -89,6 +98,8 @@ pub enum DenyReason {
     IpScopeChanged,
     // All connection migrations are disabled
     ConnectionMigrationDisabled,
+    // The migration was denied by an application-provided validator
+    Application,
 }
 
 impl IntoEvent<event::builder::ConnectionMigrationDenied> for DenyReason {
@@ -101,14 +112,21 @@ impl IntoEvent<event::builder::ConnectionMigrationDenied> for DenyReason {
             DenyReason::ConnectionMigrationDisabled => {
                 event::builder::MigrationDenyReason::ConnectionMigrationDisabled
             }
+            DenyReason::Application => event::builder::MigrationDenyReason::Application,
         };
         event::builder::ConnectionMigrationDenied { reason }
     }
 }
 
 /// Validates a path migration attempt from an active path to another
+///
+/// A single validator is shared by all of the connections on an endpoint. Connections can be
+/// told apart by the connection IDs of the [`Attempt::active_path`].
 pub trait Validator: 'static + Send {
     /// Called on each connection migration attempt for a connection
+    ///
+    /// Denied attempts don't reserve any state, so the peer may trigger another attempt with
+    /// each packet it sends from the new address.
     fn on_migration_attempt(&mut self, attempt: &Attempt) -> Outcome;
 }
 
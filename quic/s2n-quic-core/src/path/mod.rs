@@ -3,13 +3,16 @@
 
 use crate::{
     event,
-    inet::{IpV4Address, IpV6Address, SocketAddress, SocketAddressV4, SocketAddressV6},
+    inet::{
+        IpV4Address, IpV6Address, SocketAddress, SocketAddressV4, SocketAddressV6, Unspecified,
+    },
 };
 use core::fmt;
 
 #[cfg(any(test, feature = "generator"))]
 use bolero_generator::*;
 
+pub mod cache;
 pub mod ecn;
 pub mod migration;
 pub mod mtu;
@@ -62,6 +65,9 @@ pub trait Handle: 'static + Copy + Send + fmt::Debug {
     /// Updates the remote port to the given value
     fn set_remote_port(&mut self, port: u16);
 
+    /// Updates the local port to the given value
+    fn set_local_port(&mut self, port: u16);
+
     /// Returns the local address for the given handle
     fn local_address(&self) -> LocalAddress;
 
@@ -166,6 +172,11 @@ impl Handle for RemoteAddress {
         self.0.set_port(port)
     }
 
+    #[inline]
+    fn set_local_port(&mut self, _port: u16) {
+        // the local address isn't tracked
+    }
+
     #[inline]
     fn local_address(&self) -> LocalAddress {
         SocketAddressV4::UNSPECIFIED.into()
@@ -222,6 +233,11 @@ impl Handle for Tuple {
         self.remote_address.set_port(port)
     }
 
+    #[inline]
+    fn set_local_port(&mut self, port: u16) {
+        self.local_address.set_port(port)
+    }
+
     #[inline]
     fn local_address(&self) -> LocalAddress {
         self.local_address
@@ -245,7 +261,10 @@ impl Handle for Tuple {
         }
 
         // once we discover our path, or the port changes, update the address with the new information
-        if self.local_address.port() != other.local_address.port() {
+        if self.local_address.port() != other.local_address.port()
+            || (self.local_address.ip().is_unspecified()
+                && !other.local_address.ip().is_unspecified())
+        {
             self.local_address = other.local_address;
         }
     }
@@ -6,7 +6,7 @@ use crate::{
     event,
     event::{builder::MtuUpdatedCause, IntoEvent},
     frame,
-    inet::SocketAddress,
+    inet::{IpAddress, SocketAddress},
     packet::number::PacketNumber,
     path,
     recovery::{congestion_controller, CongestionController},
@@ -257,6 +257,135 @@ impl Config {
     pub fn is_valid(&self) -> bool {
         self.base_mtu.0 <= self.initial_mtu.0 && self.initial_mtu.0 <= self.max_mtu.0
     }
+
+    /// Returns the configuration with the `initial_mtu` raised to support datagrams of the given
+    /// `max_datagram_size`, as previously validated on a path to the same peer
+    ///
+    /// The `initial_mtu` is kept within the `base_mtu` and `max_mtu` bounds and is never lowered.
+    #[inline]
+    #[must_use]
+    pub fn with_validated_datagram_size(
+        self,
+        max_datagram_size: u16,
+        peer_socket_address: &SocketAddress,
+    ) -> Self {
+        let min_ip_header_len = match peer_socket_address {
+            SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
+            SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
+        };
+        let mtu = max_datagram_size
+            .saturating_add(UDP_HEADER_LEN + min_ip_header_len)
+            .min(self.max_mtu.0.get());
+
+        match NonZeroU16::new(mtu) {
+            Some(mtu) if mtu > self.initial_mtu.0 => Self {
+                initial_mtu: InitialMtu(mtu),
+                ..self
+            },
+            _ => self,
+        }
+    }
+
+    /// Returns the configuration with each MTU bounded by the given `max_mtu`
+    #[inline]
+    #[must_use]
+    pub fn clamp(self, max_mtu: MaxMtu) -> Self {
+        let max = max_mtu.0;
+        Self {
+            initial_mtu: InitialMtu(self.initial_mtu.0.min(max)),
+            base_mtu: BaseMtu(self.base_mtu.0.min(max)),
+            max_mtu: MaxMtu(self.max_mtu.0.min(max)),
+        }
+    }
+}
+
+/// Bounds the MTU of paths to peers within an address prefix
+///
+/// Some networks are known to silently drop datagrams above a certain size. Clamping the MTU for
+/// these peers prevents the MTU controller from repeatedly probing for sizes the network can't
+/// support and then detecting the resulting black hole.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Clamp {
+    prefix: IpAddress,
+    prefix_len: u8,
+    max_mtu: MaxMtu,
+}
+
+impl Clamp {
+    /// Creates a clamp which bounds the MTU of peers within `prefix/prefix_len` to `max_mtu`
+    ///
+    /// IPv4-mapped IPv6 prefixes are converted to their IPv4 form. The `prefix_len` is limited to
+    /// the length of the address.
+    pub fn new(prefix: IpAddress, prefix_len: u8, max_mtu: u16) -> Result<Self, MtuError> {
+        let (prefix, prefix_len) = match (prefix, prefix.unmap()) {
+            // the leading 96 bits of a mapped prefix are dropped in the IPv4 form
+            (IpAddress::Ipv6(_), ip @ IpAddress::Ipv4(_)) => {
+                (ip, prefix_len.min(128).saturating_sub(96))
+            }
+            (_, ip @ IpAddress::Ipv4(_)) => (ip, prefix_len.min(32)),
+            (_, ip) => (ip, prefix_len.min(128)),
+        };
+
+        Ok(Self {
+            prefix,
+            prefix_len,
+            max_mtu: max_mtu.try_into()?,
+        })
+    }
+
+    /// Returns the length of the prefix, in bits
+    #[inline]
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the MTU that matching paths are bounded by
+    #[inline]
+    pub fn max_mtu(&self) -> MaxMtu {
+        self.max_mtu
+    }
+
+    /// Returns true if the peer address is within the prefix
+    #[inline]
+    pub fn matches(&self, peer_socket_address: &SocketAddress) -> bool {
+        match (self.prefix, peer_socket_address.ip().unmap()) {
+            (IpAddress::Ipv4(prefix), IpAddress::Ipv4(ip)) => {
+                prefix_matches(&*prefix, &*ip, self.prefix_len)
+            }
+            (IpAddress::Ipv6(prefix), IpAddress::Ipv6(ip)) => {
+                prefix_matches(&*prefix, &*ip, self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+
+    /// Applies the most specific clamp matching the peer address to the configuration
+    #[inline]
+    pub fn apply(clamps: &[Self], config: Config, peer_socket_address: &SocketAddress) -> Config {
+        clamps
+            .iter()
+            .filter(|clamp| clamp.matches(peer_socket_address))
+            .max_by_key(|clamp| clamp.prefix_len)
+            .map_or(config, |clamp| config.clamp(clamp.max_mtu))
+    }
+}
+
+/// Returns true if the first `len` bits of `prefix` and `ip` are equal
+#[inline]
+fn prefix_matches(prefix: &[u8], ip: &[u8], len: u8) -> bool {
+    let bytes = len as usize / 8;
+    let bits = len % 8;
+
+    if prefix[..bytes] != ip[..bytes] {
+        return false;
+    }
+
+    if bits == 0 {
+        return true;
+    }
+
+    let mask = 0xffu8 << (8 - bits);
+    (prefix[bytes] ^ ip[bytes]) & mask == 0
 }
 
 #[derive(Debug, Default)]
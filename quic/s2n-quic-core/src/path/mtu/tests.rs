@@ -121,6 +121,133 @@ fn mtu_config_builder() {
     assert_eq!(Some(MtuError), result.err());
 }
 
+#[test]
+fn mtu_config_clamp() {
+    let config = Config {
+        initial_mtu: 1500.try_into().unwrap(),
+        base_mtu: 1400.try_into().unwrap(),
+        max_mtu: 9000.try_into().unwrap(),
+    };
+
+    let clamped = config.clamp(1350.try_into().unwrap());
+    assert!(clamped.is_valid());
+    assert_eq!(1350_u16, u16::from(clamped.initial_mtu));
+    assert_eq!(1350_u16, u16::from(clamped.base_mtu));
+    assert_eq!(1350_u16, u16::from(clamped.max_mtu));
+
+    // a clamp above the configured values has no effect
+    let clamped = config.clamp(9001.try_into().unwrap());
+    assert_eq!(config.initial_mtu, clamped.initial_mtu);
+    assert_eq!(config.base_mtu, clamped.base_mtu);
+    assert_eq!(config.max_mtu, clamped.max_mtu);
+}
+
+#[test]
+fn mtu_config_with_validated_datagram_size() {
+    let addr = |addr: &str| -> SocketAddress { addr.parse::<SocketAddr>().unwrap().into() };
+    let ipv4 = addr("192.0.2.1:443");
+    let ipv6 = addr("[2001:db8::1]:443");
+    let config = Config {
+        initial_mtu: 1300.try_into().unwrap(),
+        base_mtu: 1300.try_into().unwrap(),
+        max_mtu: 1500.try_into().unwrap(),
+    };
+
+    // the IP and UDP headers are added to the datagram size
+    let seeded = config.with_validated_datagram_size(1400, &ipv4);
+    assert!(seeded.is_valid());
+    assert_eq!(1428_u16, u16::from(seeded.initial_mtu));
+    assert_eq!(config.base_mtu, seeded.base_mtu);
+    assert_eq!(config.max_mtu, seeded.max_mtu);
+    let seeded = config.with_validated_datagram_size(1400, &ipv6);
+    assert_eq!(1448_u16, u16::from(seeded.initial_mtu));
+
+    // the initial MTU is bounded by the max MTU
+    let seeded = config.with_validated_datagram_size(8972, &ipv4);
+    assert_eq!(1500_u16, u16::from(seeded.initial_mtu));
+
+    // the initial MTU is never lowered
+    let seeded = config.with_validated_datagram_size(1200, &ipv4);
+    assert_eq!(config.initial_mtu, seeded.initial_mtu);
+}
+
+#[test]
+fn mtu_clamp_matches() {
+    let addr = |addr: &str| -> SocketAddress { addr.parse::<SocketAddr>().unwrap().into() };
+    let ip = |addr: &str| {
+        addr.parse::<SocketAddr>()
+            .map(SocketAddress::from)
+            .unwrap()
+            .ip()
+    };
+
+    let clamp = Clamp::new(ip("192.0.2.0:0"), 24, 1350).unwrap();
+    assert!(clamp.matches(&addr("192.0.2.1:443")));
+    assert!(clamp.matches(&addr("[::ffff:192.0.2.255]:443")));
+    assert!(!clamp.matches(&addr("192.0.3.1:443")));
+    assert!(!clamp.matches(&addr("[2001:db8::1]:443")));
+
+    // prefixes that don't end on a byte boundary
+    let clamp = Clamp::new(ip("198.51.100.128:0"), 25, 1350).unwrap();
+    assert!(clamp.matches(&addr("198.51.100.200:443")));
+    assert!(!clamp.matches(&addr("198.51.100.127:443")));
+
+    // mapped prefixes are converted to IPv4
+    let clamp = Clamp::new(ip("[::ffff:192.0.2.0]:0"), 120, 1350).unwrap();
+    assert_eq!(clamp.prefix_len(), 24);
+    assert!(clamp.matches(&addr("192.0.2.1:443")));
+
+    let clamp = Clamp::new(ip("[2001:db8::]:0"), 32, 1350).unwrap();
+    assert!(clamp.matches(&addr("[2001:db8:1::1]:443")));
+    assert!(!clamp.matches(&addr("[2001:db9::1]:443")));
+    assert!(!clamp.matches(&addr("192.0.2.1:443")));
+
+    // prefix lengths are limited to the length of the address
+    assert_eq!(
+        Clamp::new(ip("192.0.2.1:0"), 64, 1350)
+            .unwrap()
+            .prefix_len(),
+        32
+    );
+
+    // the MTU must be valid
+    assert_eq!(
+        Some(MtuError),
+        Clamp::new(ip("192.0.2.0:0"), 24, 1000).err()
+    );
+}
+
+#[test]
+fn mtu_clamp_apply() {
+    let addr = |addr: &str| -> SocketAddress { addr.parse::<SocketAddr>().unwrap().into() };
+    let ip = |addr: &str| {
+        addr.parse::<SocketAddr>()
+            .map(SocketAddress::from)
+            .unwrap()
+            .ip()
+    };
+
+    let config = Config {
+        max_mtu: 9000.try_into().unwrap(),
+        ..Default::default()
+    };
+    let clamps = [
+        Clamp::new(ip("10.0.0.0:0"), 8, 1500).unwrap(),
+        Clamp::new(ip("10.1.0.0:0"), 16, 1350).unwrap(),
+    ];
+
+    // the most specific prefix is applied
+    let clamped = Clamp::apply(&clamps, config, &addr("10.1.2.3:443"));
+    assert_eq!(1350_u16, u16::from(clamped.max_mtu));
+
+    let clamped = Clamp::apply(&clamps, config, &addr("10.2.2.3:443"));
+    assert_eq!(1500_u16, u16::from(clamped.max_mtu));
+
+    // paths without a matching prefix are unaffected
+    let clamped = Clamp::apply(&clamps, config, &addr("192.0.2.1:443"));
+    assert_eq!(9000_u16, u16::from(clamped.max_mtu));
+}
+
 #[test]
 fn base_plpmtu_is_1200() {
     //= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.2
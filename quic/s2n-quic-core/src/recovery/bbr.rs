@@ -528,10 +528,20 @@ impl CongestionController for BbrCongestionController {
         self.pacer.earliest_departure_time()
     }
 
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        Some(self.pacer.pacing_rate())
+    }
+
     #[inline]
     fn send_quantum(&self) -> Option<usize> {
         Some(self.pacer.send_quantum())
     }
+
+    #[inline]
+    fn algorithm(&self) -> event::builder::CongestionControlAlgorithm {
+        event::builder::CongestionControlAlgorithm::Bbr
+    }
 }
 
 impl BbrCongestionController {
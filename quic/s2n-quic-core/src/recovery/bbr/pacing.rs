@@ -180,7 +180,7 @@ impl Pacer {
         self.send_quantum = send_quantum
     }
 
-    #[cfg(test)]
+    #[inline]
     pub fn pacing_rate(&self) -> Bandwidth {
         self.pacing_rate
     }
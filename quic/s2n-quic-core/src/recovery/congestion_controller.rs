@@ -5,7 +5,7 @@ use crate::{
     event,
     event::{
         api::SocketAddress,
-        builder::{BbrState, SlowStartExitCause},
+        builder::{BbrState, CongestionControlAlgorithm, SlowStartExitCause},
         IntoEvent,
     },
     inet, path,
@@ -21,6 +21,8 @@ use core::fmt::Debug;
 use num_rational::Ratio;
 use num_traits::ToPrimitive;
 
+pub mod select;
+
 pub trait Endpoint: 'static + Debug + Send {
     type CongestionController: CongestionController;
 
@@ -33,6 +35,11 @@ pub struct PathInfo<'a> {
     pub remote_address: SocketAddress<'a>,
     pub application_protocol: Option<&'a [u8]>,
     pub max_datagram_size: u16,
+    /// The characteristics observed by a previous connection to the same remote prefix, if the
+    /// endpoint has a path cache
+    ///
+    /// Congestion controllers may use these to ramp up faster than from the initial window.
+    pub cached: Option<path::cache::Characteristics>,
 }
 
 impl<'a> PathInfo<'a> {
@@ -42,8 +49,17 @@ impl<'a> PathInfo<'a> {
             remote_address: remote_address.into_event(),
             application_protocol: None,
             max_datagram_size: initial_mtu.max_datagram_size(remote_address),
+            cached: None,
         }
     }
+
+    /// Sets the characteristics observed by a previous connection to the same remote prefix
+    #[inline]
+    #[must_use]
+    pub fn with_cached(mut self, cached: Option<path::cache::Characteristics>) -> Self {
+        self.cached = cached;
+        self
+    }
 }
 
 pub trait Publisher {
@@ -245,6 +261,20 @@ pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed
     fn send_quantum(&self) -> Option<usize> {
         None
     }
+
+    /// Returns the rate at which the congestion controller currently paces packets
+    ///
+    /// If the value is `None`, packets are not being paced.
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        None
+    }
+
+    /// Returns the algorithm implemented by the congestion controller and its parameters
+    ///
+    /// This is reported in the `CongestionControllerInitialized` event for each new path.
+    fn algorithm(&self) -> CongestionControlAlgorithm {
+        CongestionControlAlgorithm::Other
+    }
 }
 
 // Prevent implementation of the `CongestionController` trait if the
@@ -260,9 +290,13 @@ mod private {
             // otherwise implements `CongestionController`
             impl<T: crate::recovery::CongestionController> Sealed for T {}
         } else {
-            // Otherwise only allow the included CUBIC and BBRv2 congestion controllers
+            // Otherwise only allow the included CUBIC and BBRv2 congestion controllers, as well
+            // as the controllers created by the `Selector`
             impl Sealed for crate::recovery::CubicCongestionController {}
             impl Sealed for crate::recovery::bbr::BbrCongestionController {}
+            impl Sealed for core::convert::Infallible {}
+            impl<C: crate::recovery::CongestionController> Sealed
+                for super::select::Selected<C> {}
         }
     );
 }
@@ -0,0 +1,511 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Selects the congestion control algorithm for each new path
+//!
+//! The [`Selector`] endpoint is called with the [`PathInfo`] of each path and returns which
+//! [`Algorithm`] should be used to create its congestion controller. This allows, for example,
+//! BBR to be used for peers on high bandwidth-delay product networks while CUBIC is used
+//! for everything else.
+
+use super::{CongestionController, Endpoint, PathInfo, Publisher};
+use crate::{
+    event::builder::CongestionControlAlgorithm,
+    random,
+    recovery::{
+        bandwidth::Bandwidth,
+        bbr::{self, BbrCongestionController},
+        cubic::{self, CubicCongestionController},
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+use core::{convert::Infallible, fmt};
+
+/// The congestion control algorithm chosen for a path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// CUBIC, as specified in RFC 8312
+    Cubic,
+    /// BBRv2, as implemented by [`BbrCongestionController`]
+    Bbr,
+    /// The custom endpoint configured with [`Selector::with_custom`]
+    ///
+    /// If no custom endpoint was configured, CUBIC is used instead.
+    Custom,
+}
+
+/// A congestion controller endpoint which selects the algorithm for each path
+pub struct Selector<F, C = Infallible> {
+    select: F,
+    cubic: cubic::Endpoint,
+    bbr: bbr::Endpoint,
+    custom: Option<C>,
+}
+
+impl<F> Selector<F>
+where
+    F: 'static + Send + FnMut(&PathInfo) -> Algorithm,
+{
+    /// Creates a selector which calls `select` for each new path
+    pub fn new(select: F) -> Self {
+        Self {
+            select,
+            cubic: Default::default(),
+            bbr: Default::default(),
+            custom: None,
+        }
+    }
+}
+
+impl<F, C> Selector<F, C> {
+    /// Sets the endpoint used to create CUBIC congestion controllers
+    #[must_use]
+    pub fn with_cubic(mut self, cubic: cubic::Endpoint) -> Self {
+        self.cubic = cubic;
+        self
+    }
+
+    /// Sets the endpoint used to create BBR congestion controllers
+    #[must_use]
+    pub fn with_bbr(mut self, bbr: bbr::Endpoint) -> Self {
+        self.bbr = bbr;
+        self
+    }
+
+    /// Sets the endpoint used to create congestion controllers for [`Algorithm::Custom`]
+    #[must_use]
+    pub fn with_custom<E: Endpoint>(self, custom: E) -> Selector<F, E> {
+        Selector {
+            select: self.select,
+            cubic: self.cubic,
+            bbr: self.bbr,
+            custom: Some(custom),
+        }
+    }
+}
+
+impl<F, C: fmt::Debug> fmt::Debug for Selector<F, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Selector")
+            .field("cubic", &self.cubic)
+            .field("bbr", &self.bbr)
+            .field("custom", &self.custom)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, C> Endpoint for Selector<F, C>
+where
+    F: 'static + Send + FnMut(&PathInfo) -> Algorithm,
+    C: Endpoint,
+{
+    type CongestionController = Selected<C::CongestionController>;
+
+    #[inline]
+    fn new_congestion_controller(&mut self, path_info: PathInfo) -> Self::CongestionController {
+        match (self.select)(&path_info) {
+            Algorithm::Bbr => Selected::Bbr(self.bbr.new_congestion_controller(path_info)),
+            Algorithm::Custom if self.custom.is_some() => {
+                let custom = self.custom.as_mut().expect("custom endpoint is configured");
+                Selected::Custom(custom.new_congestion_controller(path_info))
+            }
+            Algorithm::Cubic | Algorithm::Custom => {
+                Selected::Cubic(self.cubic.new_congestion_controller(path_info))
+            }
+        }
+    }
+}
+
+/// A congestion controller created by a [`Selector`]
+#[derive(Clone, Debug)]
+pub enum Selected<C> {
+    Cubic(CubicCongestionController),
+    Bbr(BbrCongestionController),
+    Custom(C),
+}
+
+/// The packet info of a congestion controller created by a [`Selector`]
+#[derive(Clone, Copy, Debug)]
+pub enum SelectedPacketInfo<I> {
+    Cubic(<CubicCongestionController as CongestionController>::PacketInfo),
+    Bbr(<BbrCongestionController as CongestionController>::PacketInfo),
+    Custom(I),
+}
+
+/// Calls the same expression on whichever congestion controller was selected
+macro_rules! dispatch {
+    ($self:expr, $cc:ident => $expr:expr) => {
+        match $self {
+            Selected::Cubic($cc) => $expr,
+            Selected::Bbr($cc) => $expr,
+            Selected::Custom($cc) => $expr,
+        }
+    };
+}
+
+/// Calls the same expression on the selected congestion controller with its packet info
+macro_rules! dispatch_info {
+    ($self:expr, $info:expr,($cc:ident, $packet_info:ident) => $expr:expr) => {
+        match ($self, $info) {
+            (Selected::Cubic($cc), SelectedPacketInfo::Cubic($packet_info)) => $expr,
+            (Selected::Bbr($cc), SelectedPacketInfo::Bbr($packet_info)) => $expr,
+            (Selected::Custom($cc), SelectedPacketInfo::Custom($packet_info)) => $expr,
+            _ => debug_assert!(
+                false,
+                "packet info was created by a different congestion controller"
+            ),
+        }
+    };
+}
+
+impl<C: CongestionController> CongestionController for Selected<C> {
+    type PacketInfo = SelectedPacketInfo<C::PacketInfo>;
+
+    #[inline]
+    fn congestion_window(&self) -> u32 {
+        dispatch!(self, cc => cc.congestion_window())
+    }
+
+    #[inline]
+    fn bytes_in_flight(&self) -> u32 {
+        dispatch!(self, cc => cc.bytes_in_flight())
+    }
+
+    #[inline]
+    fn is_congestion_limited(&self) -> bool {
+        dispatch!(self, cc => cc.is_congestion_limited())
+    }
+
+    #[inline]
+    fn requires_fast_retransmission(&self) -> bool {
+        dispatch!(self, cc => cc.requires_fast_retransmission())
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        sent_bytes: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) -> Self::PacketInfo {
+        match self {
+            Self::Cubic(cc) => SelectedPacketInfo::Cubic(cc.on_packet_sent(
+                time_sent,
+                sent_bytes,
+                app_limited,
+                rtt_estimator,
+                publisher,
+            )),
+            Self::Bbr(cc) => SelectedPacketInfo::Bbr(cc.on_packet_sent(
+                time_sent,
+                sent_bytes,
+                app_limited,
+                rtt_estimator,
+                publisher,
+            )),
+            Self::Custom(cc) => SelectedPacketInfo::Custom(cc.on_packet_sent(
+                time_sent,
+                sent_bytes,
+                app_limited,
+                rtt_estimator,
+                publisher,
+            )),
+        }
+    }
+
+    #[inline]
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        now: Timestamp,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        dispatch!(self, cc => cc.on_rtt_update(time_sent, now, rtt_estimator, publisher))
+    }
+
+    #[inline]
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        newest_acked_packet_info: Self::PacketInfo,
+        rtt_estimator: &RttEstimator,
+        random_generator: &mut dyn random::Generator,
+        ack_receive_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        dispatch_info!(self, newest_acked_packet_info, (cc, packet_info) => cc.on_ack(
+            newest_acked_time_sent,
+            bytes_acknowledged,
+            packet_info,
+            rtt_estimator,
+            random_generator,
+            ack_receive_time,
+            publisher,
+        ))
+    }
+
+    #[inline]
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        lost_bytes: u32,
+        packet_info: Self::PacketInfo,
+        persistent_congestion: bool,
+        new_loss_burst: bool,
+        random_generator: &mut dyn random::Generator,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        dispatch_info!(self, packet_info, (cc, packet_info) => cc.on_packet_lost(
+            lost_bytes,
+            packet_info,
+            persistent_congestion,
+            new_loss_burst,
+            random_generator,
+            timestamp,
+            publisher,
+        ))
+    }
+
+    #[inline]
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        ce_count: u64,
+        event_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        dispatch!(self, cc => cc.on_explicit_congestion(ce_count, event_time, publisher))
+    }
+
+    #[inline]
+    fn on_mtu_update<Pub: Publisher>(&mut self, max_data_size: u16, publisher: &mut Pub) {
+        dispatch!(self, cc => cc.on_mtu_update(max_data_size, publisher))
+    }
+
+    #[inline]
+    fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, publisher: &mut Pub) {
+        dispatch!(self, cc => cc.on_packet_discarded(bytes_sent, publisher))
+    }
+
+    #[inline]
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        dispatch!(self, cc => cc.earliest_departure_time())
+    }
+
+    #[inline]
+    fn send_quantum(&self) -> Option<usize> {
+        dispatch!(self, cc => cc.send_quantum())
+    }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        dispatch!(self, cc => cc.pacing_rate())
+    }
+
+    #[inline]
+    fn algorithm(&self) -> CongestionControlAlgorithm {
+        dispatch!(self, cc => cc.algorithm())
+    }
+}
+
+/// Used as the custom endpoint of a [`Selector`] when none is configured
+impl Endpoint for Infallible {
+    type CongestionController = Infallible;
+
+    fn new_congestion_controller(&mut self, _path_info: PathInfo) -> Self::CongestionController {
+        match *self {}
+    }
+}
+
+impl CongestionController for Infallible {
+    type PacketInfo = Infallible;
+
+    fn congestion_window(&self) -> u32 {
+        match *self {}
+    }
+
+    fn bytes_in_flight(&self) -> u32 {
+        match *self {}
+    }
+
+    fn is_congestion_limited(&self) -> bool {
+        match *self {}
+    }
+
+    fn requires_fast_retransmission(&self) -> bool {
+        match *self {}
+    }
+
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        _time_sent: Timestamp,
+        _sent_bytes: usize,
+        _app_limited: Option<bool>,
+        _rtt_estimator: &RttEstimator,
+        _publisher: &mut Pub,
+    ) -> Self::PacketInfo {
+        match *self {}
+    }
+
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        _time_sent: Timestamp,
+        _now: Timestamp,
+        _rtt_estimator: &RttEstimator,
+        _publisher: &mut Pub,
+    ) {
+        match *self {}
+    }
+
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        _newest_acked_time_sent: Timestamp,
+        _bytes_acknowledged: usize,
+        _newest_acked_packet_info: Self::PacketInfo,
+        _rtt_estimator: &RttEstimator,
+        _random_generator: &mut dyn random::Generator,
+        _ack_receive_time: Timestamp,
+        _publisher: &mut Pub,
+    ) {
+        match *self {}
+    }
+
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        _lost_bytes: u32,
+        _packet_info: Self::PacketInfo,
+        _persistent_congestion: bool,
+        _new_loss_burst: bool,
+        _random_generator: &mut dyn random::Generator,
+        _timestamp: Timestamp,
+        _publisher: &mut Pub,
+    ) {
+        match *self {}
+    }
+
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        _ce_count: u64,
+        _event_time: Timestamp,
+        _publisher: &mut Pub,
+    ) {
+        match *self {}
+    }
+
+    fn on_mtu_update<Pub: Publisher>(&mut self, _max_data_size: u16, _publisher: &mut Pub) {
+        match *self {}
+    }
+
+    fn on_packet_discarded<Pub: Publisher>(&mut self, _bytes_sent: usize, _publisher: &mut Pub) {
+        match *self {}
+    }
+
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        match *self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event,
+        inet::SocketAddress,
+        path,
+        path::InitialMtu,
+        recovery::congestion_controller::{testing::mock, PathPublisher},
+        time::{Clock, NoopClock},
+    };
+
+    /// Uses BBR for port 443, the custom controller for port 8443 and CUBIC otherwise
+    fn select(path_info: &PathInfo) -> Algorithm {
+        match path_info.remote_address.port() {
+            443 => Algorithm::Bbr,
+            8443 => Algorithm::Custom,
+            _ => Algorithm::Cubic,
+        }
+    }
+
+    fn new_controller<E: Endpoint>(endpoint: &mut E, port: u16) -> E::CongestionController {
+        let mut remote_address = SocketAddress::default();
+        remote_address.set_port(port);
+        let path_info = PathInfo::new(InitialMtu::default(), &remote_address);
+        endpoint.new_congestion_controller(path_info)
+    }
+
+    #[test]
+    fn select_test() {
+        let mut selector = Selector::new(select);
+
+        assert!(matches!(
+            new_controller(&mut selector, 443),
+            Selected::Bbr(_)
+        ));
+        assert!(matches!(
+            new_controller(&mut selector, 80),
+            Selected::Cubic(_)
+        ));
+        // custom falls back to CUBIC if not configured
+        assert!(matches!(
+            new_controller(&mut selector, 8443),
+            Selected::Cubic(_)
+        ));
+
+        let mut selector = selector.with_custom(mock::Endpoint::default());
+        assert!(matches!(
+            new_controller(&mut selector, 8443),
+            Selected::Custom(_)
+        ));
+    }
+
+    #[test]
+    fn dispatch_test() {
+        let mut selector = Selector::new(select).with_custom(mock::Endpoint::default());
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+        let rtt_estimator = RttEstimator::default();
+        let now = NoopClock.get_time();
+
+        for port in [80, 443] {
+            let mut cc = new_controller(&mut selector, port);
+            let info = cc.on_packet_sent(now, 1200, Some(false), &rtt_estimator, &mut publisher);
+            assert_eq!(cc.bytes_in_flight(), 1200);
+
+            cc.on_ack(
+                now,
+                1200,
+                info,
+                &rtt_estimator,
+                &mut random::testing::Generator::default(),
+                now,
+                &mut publisher,
+            );
+            assert_eq!(cc.bytes_in_flight(), 0);
+        }
+
+        let mut cc = new_controller(&mut selector, 8443);
+        let info = cc.on_packet_sent(now, 1200, Some(false), &rtt_estimator, &mut publisher);
+        cc.on_ack(
+            now,
+            1200,
+            info,
+            &rtt_estimator,
+            &mut random::testing::Generator::default(),
+            now,
+            &mut publisher,
+        );
+
+        match cc {
+            Selected::Custom(cc) => {
+                assert_eq!(cc.bytes_in_flight, 1200);
+                assert_eq!(cc.on_packet_ack, 1);
+            }
+            _ => panic!("expected the custom congestion controller"),
+        }
+    }
+}
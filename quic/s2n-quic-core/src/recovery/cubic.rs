@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    connection::limits::ValidationError,
     counter::Counter,
-    event::builder::SlowStartExitCause,
+    event::builder::{CongestionControlAlgorithm, SlowStartExitCause},
     random,
     recovery::{
+        bandwidth::Bandwidth,
         congestion_controller::{self, CongestionController, Publisher},
         cubic::{FastRetransmission::*, State::*},
         hybrid_slow_start::HybridSlowStart,
@@ -135,6 +137,9 @@ impl CongestionAvoidanceTiming {
 /// A congestion controller that implements "CUBIC for Fast Long-Distance Networks"
 /// as specified in <https://tools.ietf.org/html/rfc8312>. The Hybrid Slow Start algorithm
 /// is used for determining the slow start threshold.
+///
+/// The controller can alternatively be configured to only use the NewReno window increase
+/// function, as specified in <https://www.rfc-editor.org/rfc/rfc9002#section-7.3>.
 #[derive(Clone, Debug)]
 pub struct CubicCongestionController {
     cubic: Cubic,
@@ -491,14 +496,29 @@ impl CongestionController for CubicCongestionController {
     fn earliest_departure_time(&self) -> Option<Timestamp> {
         self.pacer.earliest_departure_time()
     }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        self.pacer.pacing_rate()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> CongestionControlAlgorithm {
+        self.cubic.config.algorithm()
+    }
 }
 
 impl CubicCongestionController {
     // max_datagram_size is the current max_datagram_size, and is
     // expected to be 1200 when the congestion controller is created.
     pub fn new(max_datagram_size: u16) -> Self {
+        Self::with_config(max_datagram_size, Config::default())
+    }
+
+    /// Creates a congestion controller with the given parameters
+    pub fn with_config(max_datagram_size: u16, config: Config) -> Self {
         Self {
-            cubic: Cubic::new(max_datagram_size),
+            cubic: Cubic::with_config(max_datagram_size, config),
             slow_start: HybridSlowStart::new(max_datagram_size),
             pacer: Pacer::default(),
             max_datagram_size,
@@ -511,6 +531,18 @@ impl CubicCongestionController {
         }
     }
 
+    /// Raises the congestion window to half of the bandwidth-delay product observed by a previous
+    /// connection to the same peer
+    ///
+    /// Only jumping to half of the previous capacity, as done by Careful Resume, limits the
+    /// impact on a path whose capacity has since decreased. The window is never lowered below
+    /// the initial window.
+    #[inline]
+    pub fn resume(&mut self, bandwidth_delay_product: u64) {
+        let window = (bandwidth_delay_product / 2).min(u32::MAX as u64) as f32;
+        self.congestion_window = self.congestion_window.max(window);
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
     //# Endpoints SHOULD use an initial congestion
     //# window of ten times the maximum datagram size (max_datagram_size),
@@ -543,6 +575,16 @@ impl CubicCongestionController {
         // as the Linux implementation of Cubic does.
         let max_cwnd = (self.congestion_window + sent_bytes as f32 / 2.0).min(max_cwnd);
 
+        if self.cubic.config.new_reno {
+            // A NewReno sender in congestion avoidance increases the congestion window by at
+            // most one maximum datagram size for each congestion window that is acknowledged.
+            // See https://www.rfc-editor.org/rfc/rfc9002#section-7.3.3
+            let window_increment =
+                self.max_datagram_size as f32 * sent_bytes as f32 / self.congestion_window;
+            self.congestion_window = (self.congestion_window + window_increment).min(max_cwnd);
+            return;
+        }
+
         if w_cubic < w_est {
             // TCP-Friendly Region
             //= https://www.rfc-editor.org/rfc/rfc8312#section-4.2
@@ -695,6 +737,7 @@ struct Cubic {
     // k is the time until we expect to reach w_max
     k: Duration,
     max_datagram_size: u16,
+    config: Config,
 }
 
 //= https://www.rfc-editor.org/rfc/rfc8312#section-5.1
@@ -707,13 +750,23 @@ const C: f32 = 0.4;
 //# Parameter beta_cubic SHOULD be set to 0.7.
 const BETA_CUBIC: f32 = 0.7;
 
+// The NewReno loss reduction factor (kLossReductionFactor) recommended by
+// https://www.rfc-editor.org/rfc/rfc9002#appendix-B.2
+const BETA_NEW_RENO: f32 = 0.5;
+
 impl Cubic {
+    #[cfg(test)]
     pub fn new(max_datagram_size: u16) -> Self {
+        Self::with_config(max_datagram_size, Config::default())
+    }
+
+    pub fn with_config(max_datagram_size: u16, config: Config) -> Self {
         Cubic {
             w_max: 0.0,
             w_last_max: 0.0,
             k: Duration::ZERO,
             max_datagram_size,
+            config,
         }
     }
 
@@ -742,7 +795,7 @@ impl Cubic {
     //# where beta_cubic is the CUBIC multiplication decrease factor
     #[inline]
     fn w_cubic(&self, t: Duration) -> f32 {
-        C * (t.as_secs_f32() - self.k.as_secs_f32()).powi(3) + self.w_max
+        self.config.c * (t.as_secs_f32() - self.k.as_secs_f32()).powi(3) + self.w_max
     }
 
     //= https://www.rfc-editor.org/rfc/rfc8312#section-4.2
@@ -750,9 +803,10 @@ impl Cubic {
     //               [3*(1-beta_cubic)/(1+beta_cubic)] * (t/RTT) (Eq. 4)
     #[inline]
     fn w_est(&self, t: Duration, rtt: Duration) -> f32 {
+        let beta = self.config.beta;
         self.w_max.mul_add(
-            BETA_CUBIC,
-            (3.0 * (1.0 - BETA_CUBIC) / (1.0 + BETA_CUBIC)) * (t.as_secs_f32() / rtt.as_secs_f32()),
+            beta,
+            (3.0 * (1.0 - beta) / (1.0 + beta)) * (t.as_secs_f32() / rtt.as_secs_f32()),
         )
     }
 
@@ -769,6 +823,13 @@ impl Cubic {
     // This does not change the units of the congestion window
     #[inline]
     fn multiplicative_decrease(&mut self, cwnd: f32) -> f32 {
+        let beta = self.config.beta;
+
+        if self.config.new_reno {
+            // NewReno reduces the window by the loss reduction factor without tracking W_max
+            return (cwnd * beta).max(self.minimum_window());
+        }
+
         self.w_max = self.bytes_to_packets(cwnd);
 
         //= https://www.rfc-editor.org/rfc/rfc8312#section-4.6
@@ -799,12 +860,12 @@ impl Cubic {
         //# time for the new flow to catch up to its congestion window size.
         let w_max = self.w_max;
         if w_max < self.w_last_max {
-            self.w_max = (w_max * (1.0 + BETA_CUBIC) / 2.0)
-                .max(self.bytes_to_packets(self.minimum_window()));
+            self.w_max =
+                (w_max * (1.0 + beta) / 2.0).max(self.bytes_to_packets(self.minimum_window()));
         }
         self.w_last_max = w_max;
 
-        let cwnd_start = (cwnd * BETA_CUBIC).max(self.minimum_window());
+        let cwnd_start = (cwnd * beta).max(self.minimum_window());
 
         //= https://tools.ietf.org/id/draft-eggert-tcpm-rfc8312bis-01#4.2
         //# _K_ is the time period that the above
@@ -823,8 +884,9 @@ impl Cubic {
         //#
         //# where _cwnd_(start)_ is the congestion window at the beginning of the
         //# current congestion avoidance stage.
-        self.k =
-            Duration::from_secs_f32(((self.w_max - self.bytes_to_packets(cwnd_start)) / C).cbrt());
+        self.k = Duration::from_secs_f32(
+            ((self.w_max - self.bytes_to_packets(cwnd_start)) / self.config.c).cbrt(),
+        );
 
         cwnd_start
     }
@@ -862,9 +924,74 @@ impl Cubic {
     }
 }
 
+/// Parameters for the CUBIC congestion controller
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    beta: f32,
+    c: f32,
+    new_reno: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            beta: BETA_CUBIC,
+            c: C,
+            new_reno: false,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the multiplicative window decrease factor
+    #[inline]
+    pub fn beta(&self) -> f32 {
+        self.beta
+    }
+
+    /// Returns the scaling constant which determines the aggressiveness of window growth
+    #[inline]
+    pub fn c(&self) -> f32 {
+        self.c
+    }
+
+    /// Returns true if only the NewReno window increase function is used
+    #[inline]
+    pub fn is_new_reno(&self) -> bool {
+        self.new_reno
+    }
+
+    #[inline]
+    fn algorithm(&self) -> CongestionControlAlgorithm {
+        if self.new_reno {
+            CongestionControlAlgorithm::NewReno { beta: self.beta }
+        } else {
+            CongestionControlAlgorithm::Cubic {
+                beta: self.beta,
+                c: self.c,
+            }
+        }
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Default)]
-pub struct Endpoint {}
+pub struct Endpoint {
+    config: Config,
+}
+
+impl Endpoint {
+    /// Returns a builder for configuring the CUBIC parameters
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the parameters used for new congestion controllers
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+}
 
 impl congestion_controller::Endpoint for Endpoint {
     type CongestionController = CubicCongestionController;
@@ -873,7 +1000,77 @@ impl congestion_controller::Endpoint for Endpoint {
         &mut self,
         path_info: congestion_controller::PathInfo,
     ) -> Self::CongestionController {
-        CubicCongestionController::new(path_info.max_datagram_size)
+        let mut controller =
+            CubicCongestionController::with_config(path_info.max_datagram_size, self.config);
+        if let Some(cached) = path_info.cached {
+            controller.resume(cached.bandwidth_delay_product());
+        }
+        controller
+    }
+}
+
+/// Builds a CUBIC congestion controller endpoint with specific parameters
+///
+/// The defaults follow the values recommended by RFC 8312 and are appropriate for most
+/// networks. Changing them is intended for research and controlled environments.
+#[derive(Debug, Default)]
+pub struct Builder {
+    beta: Option<f32>,
+    c: Option<f32>,
+    new_reno: bool,
+}
+
+impl Builder {
+    /// Sets the multiplicative window decrease factor applied on congestion events
+    ///
+    /// Defaults to 0.7 for CUBIC and 0.5 for NewReno. Must be greater than 0 and less than 1.
+    pub fn with_beta(mut self, beta: f32) -> Result<Self, ValidationError> {
+        ensure!(
+            beta > 0.0 && beta < 1.0,
+            Err(ValidationError(
+                "beta must be greater than 0 and less than 1"
+            ))
+        );
+        self.beta = Some(beta);
+        Ok(self)
+    }
+
+    /// Sets the scaling constant which determines the aggressiveness of the CUBIC window
+    /// growth (default: 0.4)
+    ///
+    /// Must be a positive, finite number.
+    pub fn with_c(mut self, c: f32) -> Result<Self, ValidationError> {
+        ensure!(
+            c > 0.0 && c.is_finite(),
+            Err(ValidationError("c must be a positive, finite number"))
+        );
+        self.c = Some(c);
+        Ok(self)
+    }
+
+    /// Only uses the NewReno window increase function in congestion avoidance (default: false)
+    ///
+    /// The congestion window grows by at most one datagram per round trip after leaving slow
+    /// start, as specified in RFC 9002. The `c` parameter has no effect in this mode.
+    pub fn with_new_reno(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.new_reno = enabled;
+        Ok(self)
+    }
+
+    pub fn build(self) -> Endpoint {
+        let default_beta = if self.new_reno {
+            BETA_NEW_RENO
+        } else {
+            BETA_CUBIC
+        };
+
+        Endpoint {
+            config: Config {
+                beta: self.beta.unwrap_or(default_beta),
+                c: self.c.unwrap_or(C),
+                new_reno: self.new_reno,
+            },
+        }
     }
 }
 
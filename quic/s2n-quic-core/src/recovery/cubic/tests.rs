@@ -194,6 +194,22 @@ fn initial_window() {
     );
 }
 
+#[test]
+fn resume() {
+    let max_datagram_size = 1200;
+    let initial_window = CubicCongestionController::initial_window(max_datagram_size) as f32;
+    let mut cc = CubicCongestionController::new(max_datagram_size);
+
+    // the window is never lowered below the initial window
+    cc.resume(1000);
+    assert_eq!(initial_window, cc.congestion_window);
+
+    // the window jumps to half of the previous bandwidth-delay product
+    cc.resume(100_000);
+    assert_eq!(50_000.0, cc.congestion_window);
+    assert_eq!(SlowStart, cc.state);
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
 //= type=test
 //# The RECOMMENDED
@@ -1228,3 +1244,131 @@ fn on_packet_ack_congestion_avoidance_too_large_increase() {
     assert!(cc.cubic.w_cubic(t) > cc.cubic.w_est(t, rtt));
     assert_delta!(cc.congestion_window, 3_600_000.0 + 1000.0 / 2.0, 0.001);
 }
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn builder_validation() {
+    assert!(Endpoint::builder().with_beta(0.0).is_err());
+    assert!(Endpoint::builder().with_beta(1.0).is_err());
+    assert!(Endpoint::builder().with_beta(f32::NAN).is_err());
+    assert!(Endpoint::builder().with_c(0.0).is_err());
+    assert!(Endpoint::builder().with_c(-0.4).is_err());
+    assert!(Endpoint::builder().with_c(f32::INFINITY).is_err());
+
+    let endpoint = Endpoint::builder().build();
+    assert_eq!(*endpoint.config(), Config::default());
+
+    let endpoint = Endpoint::builder()
+        .with_beta(0.8)
+        .unwrap()
+        .with_c(0.6)
+        .unwrap()
+        .build();
+    assert_eq!(endpoint.config().beta(), 0.8);
+    assert_eq!(endpoint.config().c(), 0.6);
+    assert!(!endpoint.config().is_new_reno());
+
+    // NewReno uses a different default beta
+    let endpoint = Endpoint::builder().with_new_reno(true).unwrap().build();
+    assert_eq!(endpoint.config().beta(), BETA_NEW_RENO);
+    assert!(endpoint.config().is_new_reno());
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn algorithm() {
+    let cc = CubicCongestionController::new(1200);
+    assert!(matches!(
+        cc.algorithm(),
+        CongestionControlAlgorithm::Cubic { beta, c } if beta == BETA_CUBIC && c == C
+    ));
+
+    let endpoint = Endpoint::builder().with_new_reno(true).unwrap().build();
+    let cc = CubicCongestionController::with_config(1200, *endpoint.config());
+    assert!(matches!(
+        cc.algorithm(),
+        CongestionControlAlgorithm::NewReno { beta } if beta == BETA_NEW_RENO
+    ));
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn w_cubic_with_config() {
+    let max_datagram_size = 1200;
+    let config = Endpoint::builder()
+        .with_beta(0.5)
+        .unwrap()
+        .with_c(1.152)
+        .unwrap()
+        .build()
+        .config;
+    let mut cubic = Cubic::with_config(max_datagram_size, config);
+
+    assert_eq!(
+        cubic.multiplicative_decrease(2_764_800.0),
+        2_764_800.0 * 0.5
+    );
+
+    // K = cubic_root(W_max*(1-beta)/C)
+    // K = cubic_root(2304 * 0.5 / 1.152) = 10
+    assert_delta!(cubic.k.as_secs_f32(), 10.0, 0.001);
+
+    // W_cubic(t) = C*(t-K)^3 + W_max
+    // W_cubic(15) = 1.152*125 + 2304 = 2448
+    assert_delta!(cubic.w_cubic(Duration::from_secs(15)), 2448.0, 0.01);
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn new_reno_multiplicative_decrease() {
+    let max_datagram_size = 1200;
+    let config = Endpoint::builder()
+        .with_new_reno(true)
+        .unwrap()
+        .build()
+        .config;
+    let mut cubic = Cubic::with_config(max_datagram_size, config);
+
+    assert_eq!(cubic.multiplicative_decrease(100_000.0), 50_000.0);
+    // W_max is not tracked in NewReno mode
+    assert_eq!(cubic.w_max, 0.0);
+    assert_eq!(cubic.k, Duration::ZERO);
+
+    assert_eq!(
+        cubic.multiplicative_decrease(3000.0),
+        cubic.minimum_window()
+    );
+}
+
+#[test]
+fn new_reno_congestion_avoidance() {
+    let max_datagram_size = 1000;
+    let config = Endpoint::builder()
+        .with_new_reno(true)
+        .unwrap()
+        .build()
+        .config;
+    let mut cc = CubicCongestionController::with_config(max_datagram_size, config);
+
+    cc.congestion_window = 10_000.0;
+
+    // the window grows by max_datagram_size * sent_bytes / congestion_window
+    cc.congestion_avoidance(
+        Duration::from_secs(10),
+        Duration::from_millis(100),
+        1000,
+        f32::MAX,
+    );
+    assert_delta!(cc.congestion_window, 10_100.0, 0.001);
+
+    // acknowledging a full window grows the window by roughly one datagram
+    for _ in 0..10 {
+        cc.congestion_avoidance(
+            Duration::from_secs(10),
+            Duration::from_millis(100),
+            1010,
+            f32::MAX,
+        );
+    }
+    assert!(cc.congestion_window < 11_200.0);
+}
@@ -3,7 +3,7 @@
 
 pub use congestion_controller::CongestionController;
 pub use cubic::CubicCongestionController;
-pub use pto::Pto;
+pub use pto::{ProbePolicy, Pto};
 pub use rtt_estimator::*;
 pub use sent_packets::*;
 
@@ -39,6 +39,8 @@ pub struct Pacer {
     capacity: Counter<u32, Saturating>,
     // The time the next packet should be transmitted
     next_packet_departure_time: Option<Timestamp>,
+    // The rate used to compute the last interval, if packets are being paced
+    pacing_rate: Option<Bandwidth>,
 }
 
 impl Pacer {
@@ -56,11 +58,17 @@ impl Pacer {
         publisher: &mut Pub,
     ) {
         if rtt_estimator.smoothed_rtt() < MINIMUM_PACING_RTT {
+            self.pacing_rate = None;
             return;
         }
 
         if self.capacity == 0 {
             if let Some(next_packet_departure_time) = self.next_packet_departure_time {
+                self.pacing_rate = Some(Self::rate(
+                    rtt_estimator.smoothed_rtt(),
+                    congestion_window,
+                    slow_start,
+                ));
                 let interval = Self::interval(
                     rtt_estimator.smoothed_rtt(),
                     congestion_window,
@@ -86,6 +94,15 @@ impl Pacer {
         self.next_packet_departure_time
     }
 
+    /// Returns the rate at which packets are currently paced
+    ///
+    /// This is `None` until the first interval has been computed, or if the RTT is too low for
+    /// pacing to be used.
+    #[inline]
+    pub fn pacing_rate(&self) -> Option<Bandwidth> {
+        self.pacing_rate
+    }
+
     // Recalculate the interval between bursts of paced packets
     #[inline]
     fn interval<Pub: Publisher>(
@@ -95,6 +112,21 @@ impl Pacer {
         slow_start: bool,
         publisher: &mut Pub,
     ) -> Duration {
+        let n = if slow_start { SLOW_START_N } else { N };
+        let pacing_rate = Self::rate(rtt, congestion_window, slow_start);
+
+        // `MAX_BURST_PACKETS` is incorporated into the formula since we are trying to spread
+        // bursts of packets evenly over time.
+        let packet_size = MAX_BURST_PACKETS * max_datagram_size as u32;
+
+        publisher.on_pacing_rate_updated(pacing_rate, packet_size, n);
+
+        packet_size as u64 / pacing_rate
+    }
+
+    // Calculate the rate at which packets are spread over time
+    #[inline]
+    fn rate(rtt: Duration, congestion_window: u32, slow_start: bool) -> Bandwidth {
         debug_assert_ne!(congestion_window, 0);
 
         let n = if slow_start { SLOW_START_N } else { N };
@@ -107,15 +139,7 @@ impl Pacer {
         //# where congestion_window is in bytes:
         //#
         //# rate = N * congestion_window / smoothed_rtt
-        let pacing_rate = Bandwidth::new(congestion_window as u64, rtt) * n;
-
-        // `MAX_BURST_PACKETS` is incorporated into the formula since we are trying to spread
-        // bursts of packets evenly over time.
-        let packet_size = MAX_BURST_PACKETS * max_datagram_size as u32;
-
-        publisher.on_pacing_rate_updated(pacing_rate, packet_size, n);
-
-        packet_size as u64 / pacing_rate
+        Bandwidth::new(congestion_window as u64, rtt) * n
     }
 }
 
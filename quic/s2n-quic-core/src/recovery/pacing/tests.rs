@@ -7,6 +7,7 @@ use crate::{
     path,
     path::MINIMUM_MAX_DATAGRAM_SIZE,
     recovery::{
+        bandwidth::Bandwidth,
         congestion_controller::PathPublisher,
         pacing::{Pacer, INITIAL_INTERVAL, N, SLOW_START_N},
         RttEstimator, MAX_BURST_PACKETS,
@@ -45,6 +46,50 @@ fn earliest_departure_time() {
     );
 }
 
+#[test]
+fn pacing_rate() {
+    let mut pacer = Pacer::default();
+    let now = NoopClock.get_time();
+    let rtt = RttEstimator::default();
+    let cwnd = 12000;
+    assert_eq!(None, pacer.pacing_rate());
+
+    let _ = get_interval(
+        now,
+        &mut pacer,
+        &rtt,
+        cwnd,
+        MINIMUM_MAX_DATAGRAM_SIZE,
+        false,
+    );
+
+    // The rate is the congestion window averaged over the RTT, scaled by N
+    let expected = Bandwidth::new(cwnd as u64, rtt.smoothed_rtt()) * N;
+    assert_eq!(Some(expected), pacer.pacing_rate());
+
+    // Pacing is disabled on low RTT networks
+    let mut rtt = RttEstimator::new(Duration::from_millis(1));
+    rtt.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(1),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    pacer.on_packet_sent(
+        now,
+        MINIMUM_MAX_DATAGRAM_SIZE as usize,
+        &rtt,
+        cwnd,
+        MINIMUM_MAX_DATAGRAM_SIZE,
+        false,
+        &mut publisher,
+    );
+    assert_eq!(None, pacer.pacing_rate());
+}
+
 #[test]
 fn on_packet_sent_large_bytes_sent() {
     let mut pacer = Pacer::default();
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    event::{self, IntoEvent},
     frame,
     time::{timer, Timer, Timestamp},
     transmission::{self, interest::Provider as _},
@@ -176,6 +177,61 @@ impl State {
     }
 }
 
+/// Controls which data is included in the probe packets sent when the PTO timer expires
+///
+/// Regardless of the policy, every probe is ack-eliciting; if the selected data doesn't make the
+/// packet ack-eliciting, a PING frame is added.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProbePolicy {
+    /// Sends new data, followed by any unacknowledged data which fits in the remaining space
+    #[default]
+    Default,
+    /// Only retransmits data which is still unacknowledged, starting with the oldest
+    ///
+    /// This is suited for bulk transfers, where repairing the oldest outstanding data unblocks
+    /// the receiver the quickest.
+    RetransmitUnacknowledged,
+    /// Only sends new data, without re-sending any data which is still in flight
+    ///
+    /// Data which was already declared lost is still retransmitted.
+    ///
+    /// This is suited for low-latency request/response workloads, where new requests shouldn't
+    /// wait behind retransmissions.
+    NewData,
+    /// Only sends a PING frame, along with any pending ACK frame
+    ///
+    /// This keeps probes as small as possible and leaves all retransmissions to loss detection.
+    PingOnly,
+}
+
+impl ProbePolicy {
+    /// Returns `true` if probes may include data which hasn't been transmitted yet
+    #[inline]
+    pub fn sends_new_data(self) -> bool {
+        matches!(self, Self::Default | Self::NewData)
+    }
+
+    /// Returns `true` if probes only contain a PING frame
+    #[inline]
+    pub fn is_ping_only(self) -> bool {
+        matches!(self, Self::PingOnly)
+    }
+}
+
+impl IntoEvent<event::builder::ProbePolicy> for ProbePolicy {
+    #[inline]
+    fn into_event(self) -> event::builder::ProbePolicy {
+        use event::builder::ProbePolicy::*;
+        match self {
+            Self::Default => Default {},
+            Self::RetransmitUnacknowledged => RetransmitUnacknowledged {},
+            Self::NewData => NewData {},
+            Self::PingOnly => PingOnly {},
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,6 +332,17 @@ mod tests {
         assert_eq!(pto.state, State::RequiresTransmission(2));
     }
 
+    #[test]
+    fn probe_policy() {
+        assert!(ProbePolicy::Default.sends_new_data());
+        assert!(ProbePolicy::NewData.sends_new_data());
+        assert!(!ProbePolicy::RetransmitUnacknowledged.sends_new_data());
+        assert!(!ProbePolicy::PingOnly.sends_new_data());
+
+        assert!(ProbePolicy::PingOnly.is_ping_only());
+        assert!(!ProbePolicy::Default.is_ping_only());
+    }
+
     #[test]
     fn transmission_interest() {
         let mut pto = Pto::default();
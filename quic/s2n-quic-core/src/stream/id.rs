@@ -13,6 +13,11 @@ use bolero_generator::*;
 /// on a connection.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Hash)]
 #[cfg_attr(any(feature = "generator", test), derive(TypeGenerator))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct StreamId(VarInt);
 
 // Stream IDs can be converted into `VarInt` and `u64`
@@ -226,4 +231,13 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_test() {
+        let id = StreamId::initial(endpoint::Type::Server, StreamType::Unidirectional);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "3");
+        assert_eq!(serde_json::from_str::<StreamId>(&json).unwrap(), id);
+    }
 }
@@ -66,6 +66,48 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Overrides the maximum number of bytes buffered on the tx stream
+    pub fn with_send_buffer_size(&mut self, size: u32) -> &mut Self {
+        self.tx_mut().buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the transmission priority of the tx stream
+    pub fn with_send_priority(&mut self, priority: u8) -> &mut Self {
+        self.tx_mut().priority = Some(priority);
+        self
+    }
+
+    /// Requests the current usage of the tx stream's send buffer
+    pub fn send_buffer_stats(&mut self) -> &mut Self {
+        self.tx_mut().buffer_stats = true;
+        self
+    }
+
+    /// Requests the current offsets of the tx stream
+    pub fn send_offsets(&mut self) -> &mut Self {
+        self.tx_mut().offsets = true;
+        self
+    }
+
+    /// Requests the cumulative statistics of the tx stream
+    pub fn send_stats(&mut self) -> &mut Self {
+        self.tx_mut().stats = true;
+        self
+    }
+
+    /// Waits until all of the data on the tx stream up to `offset` has been acknowledged
+    pub fn wait_for_ack(&mut self, offset: u64) -> &mut Self {
+        self.tx_mut().ack_offset = Some(offset);
+        self
+    }
+
+    /// Tags the chunks sent by this request with a marker
+    pub fn with_send_marker(&mut self, marker: u64) -> &mut Self {
+        self.tx_mut().marker = Some(marker);
+        self
+    }
+
     /// Requests data on the rx stream to be received into the provided slice of chunks
     pub fn receive(&mut self, chunks: &'a mut [bytes::Bytes]) -> &mut Self {
         self.rx_mut().chunks = Some(chunks);
@@ -78,6 +120,12 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Requests the current offsets of the rx stream
+    pub fn receive_offsets(&mut self) -> &mut Self {
+        self.rx_mut().offsets = true;
+        self
+    }
+
     /// Sets the watermarks for the rx stream
     pub fn with_watermark(&mut self, low: usize, high: usize) -> &mut Self {
         let rx = self.rx_mut();
@@ -173,6 +221,7 @@ impl Response {
 /// Request and response related to transmitting on a stream
 pub mod tx {
     use super::*;
+    use core::time::Duration;
 
     /// A request on a `tx` stream
     #[derive(Default, Debug)]
@@ -196,6 +245,117 @@ pub mod tx {
         /// Marks the tx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
+
+        /// Optionally overrides the maximum number of bytes buffered on the stream
+        ///
+        /// This replaces the connection-wide default for this stream only.
+        pub buffer_size: Option<u32>,
+
+        /// Optionally sets the transmission priority of the stream
+        ///
+        /// Streams with a higher priority are scheduled ahead of streams with a lower priority and
+        /// are given more consecutive packets before yielding to the other streams.
+        pub priority: Option<u8>,
+
+        /// Includes the current usage of the send buffer in the response
+        pub buffer_stats: bool,
+
+        /// Includes the current [`Offsets`] of the stream in the response
+        pub offsets: bool,
+
+        /// Includes the cumulative [`Stats`] of the stream in the response
+        pub stats: bool,
+
+        /// Optionally tags the chunks consumed by this request with a marker
+        ///
+        /// Events are emitted for each packet which carries, retransmits, or acknowledges the
+        /// tagged bytes.
+        pub marker: Option<u64>,
+
+        /// Optionally waits until all of the data up to the offset has been acknowledged by the
+        /// peer
+        ///
+        /// The context is woken once the acknowledged offset reaches the requested offset.
+        pub ack_offset: Option<u64>,
+    }
+
+    /// The progress of the data sent on a tx stream
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Offsets {
+        /// The number of bytes which have been enqueued on the stream by the application
+        pub enqueued: u64,
+
+        /// The offset up to which all of the data has been acknowledged by the peer
+        ///
+        /// Note that acknowledged data has been received by the peer's transport but may not have
+        /// been read by the peer's application yet.
+        pub acknowledged: u64,
+    }
+
+    impl Offsets {
+        /// Merges the offsets from a later observation of the same stream
+        ///
+        /// Offsets only ever increase, so this keeps the highest value of each field.
+        #[inline]
+        pub fn merge(&mut self, other: &Self) {
+            self.enqueued = self.enqueued.max(other.enqueued);
+            self.acknowledged = self.acknowledged.max(other.acknowledged);
+        }
+    }
+
+    /// The cumulative statistics of a tx stream
+    ///
+    /// The stall durations and the age are updated each time the stream is given an opportunity to
+    /// transmit. A stall which is still ongoing is included once the stream transmits again.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Stats {
+        /// The number of bytes which have been transmitted at least once
+        pub bytes_sent: u64,
+
+        /// The number of bytes which have been acknowledged by the peer
+        pub bytes_acknowledged: u64,
+
+        /// The number of bytes which were transmitted again after being declared lost
+        pub bytes_retransmitted: u64,
+
+        /// The amount of time the stream had data ready to send but was waiting on the connection
+        /// to transmit it
+        ///
+        /// This is mostly caused by the congestion window and pacing, but also includes the time
+        /// spent waiting behind other streams.
+        pub congestion_blocked: Duration,
+
+        /// The amount of time the stream was blocked by the peer's stream flow control limit
+        pub stream_flow_control_blocked: Duration,
+
+        /// The amount of time the stream was blocked by the peer's connection flow control limit
+        pub connection_flow_control_blocked: Duration,
+
+        /// The amount of time since the stream first had data to transmit
+        pub age: Duration,
+    }
+
+    /// The usage of a tx stream's send buffer
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct BufferStats {
+        /// The maximum number of bytes which can be buffered on the stream
+        pub capacity: u64,
+
+        /// The number of bytes which have been enqueued but not yet transmitted
+        pub unsent: u64,
+
+        /// The number of bytes which have been transmitted but not yet acknowledged by the peer
+        ///
+        /// This includes data which was declared lost and is waiting to be retransmitted.
+        pub unacknowledged: u64,
+    }
+
+    impl BufferStats {
+        /// Returns the number of bytes which have not been acknowledged by the peer
+        #[inline]
+        pub fn remaining(&self) -> u64 {
+            self.unsent.saturating_add(self.unacknowledged)
+        }
     }
 
     /// The result of a tx request
@@ -213,6 +373,15 @@ pub mod tx {
 
         /// The current status of the stream
         pub status: Status,
+
+        /// The usage of the send buffer, if requested with [`Request::buffer_stats`]
+        pub buffer: Option<BufferStats>,
+
+        /// The offsets of the stream, if requested with [`Request::offsets`]
+        pub offsets: Option<Offsets>,
+
+        /// The statistics of the stream, if requested with [`Request::stats`]
+        pub stats: Option<Stats>,
     }
 
     impl Default for Response {
@@ -222,6 +391,9 @@ pub mod tx {
                 chunks: Chunks::default(),
                 will_wake: false,
                 status: Status::Open,
+                buffer: None,
+                offsets: None,
+                stats: None,
             }
         }
     }
@@ -274,6 +446,9 @@ pub mod rx {
         /// Marks the rx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
+
+        /// Includes the current [`Offsets`] of the stream in the response
+        pub offsets: bool,
     }
 
     impl<'a> Default for Request<'a> {
@@ -284,10 +459,35 @@ pub mod rx {
                 high_watermark: core::usize::MAX,
                 stop_sending: None,
                 detached: false,
+                offsets: false,
             }
         }
     }
 
+    /// The progress of the data received on a rx stream
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Offsets {
+        /// The number of bytes which have been read by the application
+        ///
+        /// An interrupted transfer can be resumed from this offset, since all of the data before
+        /// it has been delivered.
+        pub consumed: u64,
+
+        /// The offset up to which all of the data has been received from the peer
+        pub received: u64,
+    }
+
+    impl Offsets {
+        /// Merges the offsets from a later observation of the same stream
+        ///
+        /// Offsets only ever increase, so this keeps the highest value of each field.
+        #[inline]
+        pub fn merge(&mut self, other: &Self) {
+            self.consumed = self.consumed.max(other.consumed);
+            self.received = self.received.max(other.received);
+        }
+    }
+
     /// The result of a pop operation
     #[derive(Debug, PartialEq, Eq)]
     pub struct Response {
@@ -303,6 +503,9 @@ pub mod rx {
 
         /// The current status of the stream
         pub status: Status,
+
+        /// The offsets of the stream, if requested with [`Request::offsets`]
+        pub offsets: Option<Offsets>,
     }
 
     impl Default for Response {
@@ -312,6 +515,7 @@ pub mod rx {
                 chunks: Chunks::default(),
                 will_wake: false,
                 status: Status::Open,
+                offsets: None,
             }
         }
     }
@@ -498,6 +702,9 @@ mod tests {
             .send(&mut send_chunks)
             .finish()
             .flush()
+            .with_send_buffer_size(1024)
+            .with_send_priority(3)
+            .send_buffer_stats()
             .reset(application::Error::new(1).unwrap())
             .receive(&mut receive_chunks)
             .with_watermark(5, 10)
@@ -512,6 +719,13 @@ mod tests {
                     flush: true,
                     reset: Some(reset),
                     detached: false,
+                    buffer_size: Some(1024),
+                    priority: Some(3),
+                    buffer_stats: true,
+                    offsets: false,
+                    stats: false,
+                    marker: None,
+                    ack_offset: None,
                 }),
                 rx: Some(rx::Request {
                     chunks: Some(rx_chunks),
@@ -519,6 +733,7 @@ mod tests {
                     high_watermark: 10,
                     stop_sending: Some(stop_sending),
                     detached: false,
+                    offsets: false,
                 })
             } if reset == application::Error::new(1).unwrap()
               && stop_sending == application::Error::new(2).unwrap()
@@ -4,6 +4,7 @@
 /// The Stream Type defines whether data can be transmitted in both directions
 /// or only in a single direction.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StreamType {
     /// Data can be transmitted on the Stream in both directions
     Bidirectional,
@@ -13,4 +13,6 @@ pub mod cursor;
 #[cfg(feature = "alloc")]
 pub mod spsc;
 #[cfg(feature = "alloc")]
+pub mod wait_queue;
+#[cfg(feature = "alloc")]
 pub mod worker;
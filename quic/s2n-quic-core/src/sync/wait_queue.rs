@@ -0,0 +1,272 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A FIFO queue of tasks waiting on a shared resource
+//!
+//! Each waiting task holds a [`Ticket`] which identifies its slot in the queue. Polling again
+//! with the same ticket replaces the stored waker in place rather than enqueueing a duplicate,
+//! and waking a batch of tasks pops them off the front of the queue without shifting the
+//! remaining waiters. This keeps the cost of each operation independent of the number of
+//! tasks which are waiting.
+
+use alloc::collections::VecDeque;
+use core::{num::NonZeroU64, task::Waker};
+
+/// Identifies a task's position in a [`WaitQueue`]
+///
+/// A ticket is issued the first time a task is registered and should be presented each time the
+/// task registers again. Once the task has been woken, the ticket expires and the task will be
+/// placed at the back of the queue if it registers again.
+#[derive(Debug, Default)]
+pub struct Ticket(Option<NonZeroU64>);
+
+impl Ticket {
+    #[inline]
+    pub const fn new() -> Self {
+        Self(None)
+    }
+
+    /// Resets the ticket so it no longer refers to a slot
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Self(None);
+    }
+}
+
+/// A FIFO queue of wakers which are deduplicated by [`Ticket`]
+#[derive(Debug)]
+pub struct WaitQueue {
+    /// The wakers of the waiting tasks in the order they were registered
+    ///
+    /// Slots of tasks which were cancelled are set to `None` and skipped when waking.
+    wakers: VecDeque<Option<Waker>>,
+    /// The ticket id of the slot at the front of `wakers`
+    front: NonZeroU64,
+    /// The number of slots which are still occupied by a waker
+    len: usize,
+}
+
+impl Default for WaitQueue {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaitQueue {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            wakers: VecDeque::new(),
+            front: NonZeroU64::MIN,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of tasks which are waiting
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no tasks are waiting
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Registers the `waker` of the task holding the `ticket`
+    ///
+    /// If the ticket still refers to a slot in the queue, the task keeps its position and the
+    /// waker is only replaced if it changed. Otherwise, the task is added to the back of the queue
+    /// and issued a new ticket.
+    #[inline]
+    pub fn register(&mut self, ticket: &mut Ticket, waker: &Waker) {
+        if let Some(slot) = self.slot_mut(ticket) {
+            match slot {
+                Some(prev) if prev.will_wake(waker) => {}
+                Some(prev) => *prev = waker.clone(),
+                None => {
+                    *slot = Some(waker.clone());
+                    self.len += 1;
+                }
+            }
+            return;
+        }
+
+        let id = self.front.get() + self.wakers.len() as u64;
+        // Safety: the front id is non-zero so adding the queue length is always non-zero
+        *ticket = Ticket(Some(unsafe { NonZeroU64::new_unchecked(id) }));
+        self.wakers.push_back(Some(waker.clone()));
+        self.len += 1;
+    }
+
+    /// Removes the task holding the `ticket` from the queue, if it's still waiting
+    ///
+    /// This should be called when a task no longer needs to be woken, for example if it acquired
+    /// the resource without being woken, so its slot isn't counted against the next wake up.
+    #[inline]
+    pub fn cancel(&mut self, ticket: &mut Ticket) {
+        if let Some(slot) = self.slot_mut(ticket) {
+            if slot.take().is_some() {
+                self.len -= 1;
+            }
+            self.trim_front();
+        }
+
+        ticket.clear();
+    }
+
+    /// Wakes up to `count` tasks in the order they were registered
+    ///
+    /// Returns the number of tasks which were woken.
+    #[inline]
+    pub fn wake(&mut self, count: usize) -> usize {
+        let mut woken = 0;
+
+        while woken < count {
+            let Some(slot) = self.pop_front() else {
+                break;
+            };
+
+            if let Some(waker) = slot {
+                waker.wake();
+                woken += 1;
+            }
+        }
+
+        self.trim_front();
+        woken
+    }
+
+    /// Wakes all of the tasks in the queue
+    #[inline]
+    pub fn wake_all(&mut self) -> usize {
+        self.wake(usize::MAX)
+    }
+
+    #[inline]
+    fn slot_mut(&mut self, ticket: &Ticket) -> Option<&mut Option<Waker>> {
+        let id = ticket.0?.get();
+        let index = id.checked_sub(self.front.get())?;
+        self.wakers.get_mut(index as usize)
+    }
+
+    #[inline]
+    fn pop_front(&mut self) -> Option<Option<Waker>> {
+        let slot = self.wakers.pop_front()?;
+
+        // Safety: incrementing a non-zero id is always non-zero
+        self.front = unsafe { NonZeroU64::new_unchecked(self.front.get() + 1) };
+
+        if slot.is_some() {
+            self.len -= 1;
+        }
+
+        Some(slot)
+    }
+
+    /// Removes any cancelled slots from the front of the queue
+    #[inline]
+    fn trim_front(&mut self) {
+        while matches!(self.wakers.front(), Some(None)) {
+            let _ = self.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_test::task::new_count_waker;
+
+    #[test]
+    fn fifo_test() {
+        let mut queue = WaitQueue::new();
+        let mut tickets = [Ticket::new(), Ticket::new(), Ticket::new()];
+        let wakers = [new_count_waker(), new_count_waker(), new_count_waker()];
+
+        for (ticket, (waker, _)) in tickets.iter_mut().zip(&wakers) {
+            queue.register(ticket, waker);
+        }
+        assert_eq!(queue.len(), 3);
+
+        assert_eq!(queue.wake(1), 1);
+        assert_eq!(wakers[0].1, 1);
+        assert_eq!(wakers[1].1, 0);
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.wake(5), 2);
+        assert_eq!(wakers[1].1, 1);
+        assert_eq!(wakers[2].1, 1);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn dedup_test() {
+        let mut queue = WaitQueue::new();
+        let mut first = Ticket::new();
+        let mut second = Ticket::new();
+        let (waker, wake_count) = new_count_waker();
+        let (other_waker, other_wake_count) = new_count_waker();
+
+        queue.register(&mut first, &waker);
+        queue.register(&mut second, &other_waker);
+
+        // registering again with the same ticket keeps the position in the queue
+        for _ in 0..10 {
+            queue.register(&mut first, &waker);
+        }
+        assert_eq!(queue.len(), 2);
+
+        // a changed waker replaces the previous one in place
+        let (new_waker, new_wake_count) = new_count_waker();
+        queue.register(&mut first, &new_waker);
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.wake(1), 1);
+        assert_eq!(wake_count, 0);
+        assert_eq!(new_wake_count, 1);
+        assert_eq!(other_wake_count, 0);
+
+        // the ticket expired so registering again goes to the back of the queue
+        queue.register(&mut first, &new_waker);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.wake(1), 1);
+        assert_eq!(other_wake_count, 1);
+        assert_eq!(new_wake_count, 1);
+        assert_eq!(queue.wake(1), 1);
+        assert_eq!(new_wake_count, 2);
+    }
+
+    #[test]
+    fn cancel_test() {
+        let mut queue = WaitQueue::new();
+        let mut tickets = [Ticket::new(), Ticket::new(), Ticket::new()];
+        let wakers = [new_count_waker(), new_count_waker(), new_count_waker()];
+
+        for (ticket, (waker, _)) in tickets.iter_mut().zip(&wakers) {
+            queue.register(ticket, waker);
+        }
+
+        // cancelled tasks don't use up any of the wake ups
+        queue.cancel(&mut tickets[0]);
+        queue.cancel(&mut tickets[1]);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.wake(1), 1);
+        assert_eq!(wakers[0].1, 0);
+        assert_eq!(wakers[1].1, 0);
+        assert_eq!(wakers[2].1, 1);
+
+        // cancelling an expired ticket is a no-op
+        queue.cancel(&mut tickets[2]);
+        assert!(queue.is_empty());
+
+        // a cancelled ticket registers at the back of the queue
+        queue.register(&mut tickets[1], &wakers[1].0);
+        queue.register(&mut tickets[0], &wakers[0].0);
+        assert_eq!(queue.wake_all(), 2);
+        assert_eq!(wakers[0].1, 1);
+        assert_eq!(wakers[1].1, 1);
+    }
+}
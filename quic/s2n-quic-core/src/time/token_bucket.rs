@@ -105,6 +105,18 @@ impl TokenBucket {
         self.invariants();
     }
 
+    /// Raises the maximum number of tokens and the refill amount by `amount`
+    ///
+    /// The additional tokens are available immediately.
+    #[inline]
+    pub fn grow(&mut self, amount: u64) {
+        self.max = self.max.saturating_add(amount);
+        self.current = self.current.saturating_add(amount);
+        self.refill_amount = self.refill_amount.saturating_add(amount);
+
+        self.invariants();
+    }
+
     #[inline]
     pub fn cancel(&mut self) {
         self.refill_timer.cancel();
@@ -215,4 +227,22 @@ mod tests {
         assert_eq!(bucket.take(100, clock.get_time()), 15);
         assert!(bucket.refill_timer.is_armed());
     }
+
+    #[test]
+    fn grow_test() {
+        let mut bucket = TokenBucket::default();
+
+        let mut clock = Clock::default();
+
+        assert_eq!(bucket.take(98, clock.get_time()), 98);
+
+        // the additional tokens are available right away
+        bucket.grow(10);
+        assert_eq!(bucket.take(100, clock.get_time()), 12);
+        assert!(bucket.refill_timer.is_armed());
+
+        // and the refill amount grows along with the maximum
+        clock.inc_by(Duration::from_secs(1));
+        assert_eq!(bucket.take(100, clock.get_time()), 15);
+    }
 }
@@ -30,6 +30,11 @@ impl<'a> Context<'a> {
     }
 }
 
+/// Generates and validates the tokens used to validate a client's address
+///
+/// The tokens are opaque to the endpoint and may contain any bytes. Retry tokens are framed by
+/// the endpoint before being sent so they can't be confused with the challenges issued by the
+/// endpoint limits, and the framing is removed before the token is passed to `validate_token`.
 pub trait Format: 'static + Send {
     const TOKEN_LEN: usize;
 
@@ -576,6 +576,10 @@ impl TransportParameterValidator for stateless_reset::Token {}
 
 transport_parameter!(MaxUdpPayloadSize(VarInt), 0x03, VarInt::from_u16(65527));
 
+impl MaxUdpPayloadSize {
+    pub const RECOMMENDED: Self = Self(VarInt::from_u16(65527));
+}
+
 impl TransportParameterValidator for MaxUdpPayloadSize {
     fn validate(self) -> Result<Self, DecoderError> {
         decoder_invariant!(
@@ -1059,6 +1063,224 @@ impl From<connection::id::LocalId> for InitialSourceConnectionId {
 connection_id_parameter!(RetrySourceConnectionId, LocalId, 0x10);
 optional_transport_parameter!(RetrySourceConnectionId);
 
+// https://datatracker.ietf.org/doc/draft-ietf-quic-address-discovery/
+//
+// address_discovery (0x9f81a176): Indicates that the endpoint supports the address
+//    discovery extension. The value indicates whether the endpoint is willing to report
+//    the addresses it observes to the peer, wants to receive reports of its own address,
+//    or both. If this transport parameter is absent, the extension is disabled.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressDiscovery {
+    /// The endpoint reports observed addresses but doesn't want to receive them
+    ProvideOnly,
+    /// The endpoint wants to receive observed addresses but doesn't report them
+    ReceiveOnly,
+    /// The endpoint reports and receives observed addresses
+    ProvideAndReceive,
+}
+
+impl AddressDiscovery {
+    /// Returns `true` if the endpoint is willing to report observed addresses
+    #[inline]
+    pub const fn provides(self) -> bool {
+        !matches!(self, Self::ReceiveOnly)
+    }
+
+    /// Returns `true` if the endpoint wants to receive its observed address
+    #[inline]
+    pub const fn receives(self) -> bool {
+        !matches!(self, Self::ProvideOnly)
+    }
+}
+
+impl TransportParameter for AddressDiscovery {
+    type CodecValue = Self;
+
+    const ID: TransportParameterId = TransportParameterId::from_u32(0x9f81a176);
+
+    fn from_codec_value(value: Self) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!(
+            "AddressDiscovery is an optional transport parameter, so the default is None"
+        )
+    }
+}
+
+impl TransportParameterValidator for AddressDiscovery {}
+
+decoder_value!(
+    impl<'a> AddressDiscovery {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (value, buffer) = buffer.decode::<VarInt>()?;
+            let value = match value.as_u64() {
+                0 => Self::ProvideOnly,
+                1 => Self::ReceiveOnly,
+                2 => Self::ProvideAndReceive,
+                _ => {
+                    return Err(DecoderError::InvariantViolation(
+                        "invalid address_discovery value",
+                    ))
+                }
+            };
+            Ok((value, buffer))
+        }
+    }
+);
+
+impl EncoderValue for AddressDiscovery {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        let value = match self {
+            Self::ProvideOnly => 0u8,
+            Self::ReceiveOnly => 1,
+            Self::ProvideAndReceive => 2,
+        };
+        buffer.encode(&VarInt::from_u8(value));
+    }
+}
+
+optional_transport_parameter!(AddressDiscovery);
+
+// https://www.rfc-editor.org/rfc/rfc9368#section-3
+//
+// version_information (0x11): Contains the version the endpoint used to send the handshake,
+//    followed by the versions it supports in order of preference. Both fields are encoded as
+//    32-bit integers and neither may contain a version of 0.
+
+/// The versions of QUIC an endpoint supports and the version it chose for the connection
+///
+/// Only the first [`VersionInformation::MAX_AVAILABLE_VERSIONS`] available versions received
+/// from a peer are retained.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionInformation {
+    chosen_version: u32,
+    available_versions: [u32; VersionInformation::MAX_AVAILABLE_VERSIONS],
+    available_versions_len: u8,
+}
+
+impl VersionInformation {
+    /// The maximum number of available versions which are stored
+    pub const MAX_AVAILABLE_VERSIONS: usize = 8;
+
+    /// Creates version information for the `chosen_version` and the `available_versions`
+    ///
+    /// Returns `None` if any of the versions is `0` or too many versions are available.
+    pub fn new(chosen_version: u32, available_versions: &[u32]) -> Option<Self> {
+        if chosen_version == 0 || available_versions.len() > Self::MAX_AVAILABLE_VERSIONS {
+            return None;
+        }
+
+        let mut value = Self {
+            chosen_version,
+            available_versions: [0; Self::MAX_AVAILABLE_VERSIONS],
+            available_versions_len: 0,
+        };
+
+        for version in available_versions.iter().copied() {
+            if version == 0 {
+                return None;
+            }
+            value.push(version);
+        }
+
+        Some(value)
+    }
+
+    /// Returns the version which was used to send the handshake
+    #[inline]
+    pub fn chosen_version(&self) -> u32 {
+        self.chosen_version
+    }
+
+    /// Returns the versions which are supported by the endpoint, in order of preference
+    #[inline]
+    pub fn available_versions(&self) -> &[u32] {
+        &self.available_versions[..self.available_versions_len as usize]
+    }
+
+    /// Returns `true` if the endpoint listed `version` as available
+    #[inline]
+    pub fn is_available(&self, version: u32) -> bool {
+        self.available_versions().contains(&version)
+    }
+
+    #[inline]
+    fn push(&mut self, version: u32) {
+        let len = self.available_versions_len as usize;
+        if let Some(slot) = self.available_versions.get_mut(len) {
+            *slot = version;
+            self.available_versions_len += 1;
+        }
+    }
+}
+
+impl TransportParameter for VersionInformation {
+    type CodecValue = Self;
+
+    const ID: TransportParameterId = TransportParameterId::from_u8(0x11);
+
+    fn from_codec_value(value: Self) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!(
+            "VersionInformation is an optional transport parameter, so the default is None"
+        )
+    }
+}
+
+impl TransportParameterValidator for VersionInformation {}
+
+decoder_value!(
+    impl<'a> VersionInformation {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (chosen_version, mut buffer) = buffer.decode::<u32>()?;
+
+            decoder_invariant!(chosen_version != 0, "invalid chosen version");
+
+            let mut value = Self {
+                chosen_version,
+                available_versions: [0; Self::MAX_AVAILABLE_VERSIONS],
+                available_versions_len: 0,
+            };
+
+            while !buffer.is_empty() {
+                let (version, remaining) = buffer.decode::<u32>()?;
+
+                decoder_invariant!(version != 0, "invalid available version");
+
+                value.push(version);
+                buffer = remaining;
+            }
+
+            Ok((value, buffer))
+        }
+    }
+);
+
+impl EncoderValue for VersionInformation {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.chosen_version);
+        for version in self.available_versions() {
+            buffer.encode(version);
+        }
+    }
+}
+
+optional_transport_parameter!(VersionInformation);
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# If present, transport parameters that set initial per-stream flow
 //# control limits (initial_max_stream_data_bidi_local,
@@ -1415,6 +1637,8 @@ impl_transport_parameters!(
         preferred_address: PreferredAddress,
         initial_source_connection_id: Option<InitialSourceConnectionId>,
         retry_source_connection_id: RetrySourceConnectionId,
+        address_discovery: Option<AddressDiscovery>,
+        version_information: Option<VersionInformation>,
     }
 );
 
@@ -1462,5 +1686,7 @@ impl<
         load!(ack_delay_exponent, ack_delay_exponent);
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
+        load!(max_udp_payload_size, max_udp_payload_size);
+        load!(address_discovery, address_discovery);
     }
 }
@@ -80,6 +80,8 @@ fn server_transport_parameters() -> ServerTransportParameters {
         }),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
+        address_discovery: Some(AddressDiscovery::ProvideAndReceive),
+        version_information: None,
     }
 }
 
@@ -117,6 +119,8 @@ fn client_transport_parameters() -> ClientTransportParameters {
         preferred_address: Default::default(),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Default::default(),
+        address_discovery: Some(AddressDiscovery::ReceiveOnly),
+        version_information: None,
     }
 }
 
@@ -180,6 +184,42 @@ fn ignore_unknown_parameter() {
     assert_eq!(0, remaining.len());
 }
 
+#[test]
+fn version_information_test() {
+    let value = VersionInformation::new(1, &[0x6b33_43cf, 1]).unwrap();
+    assert_eq!(value.chosen_version(), 1);
+    assert_eq!(value.available_versions(), &[0x6b33_43cf, 1]);
+    assert!(value.is_available(0x6b33_43cf));
+    assert!(!value.is_available(2));
+
+    let mut params = client_transport_parameters();
+    params.version_information = Some(value);
+    assert_codec_round_trip_value!(ClientTransportParameters, params);
+
+    // versions of 0 are not allowed
+    assert!(VersionInformation::new(0, &[1]).is_none());
+    assert!(VersionInformation::new(1, &[0]).is_none());
+    for bytes in [
+        &[0u8, 0, 0, 0][..],
+        &[0, 0, 0, 1, 0, 0, 0, 0],
+        &[0, 0, 0, 1, 0, 0],
+    ] {
+        assert!(VersionInformation::decode(DecoderBuffer::new(bytes)).is_err());
+    }
+
+    // only the first available versions are retained
+    let mut bytes = vec![0, 0, 0, 1];
+    for version in 1..=(VersionInformation::MAX_AVAILABLE_VERSIONS as u32 + 2) {
+        bytes.extend_from_slice(&version.to_be_bytes());
+    }
+    let (value, remaining) = VersionInformation::decode(DecoderBuffer::new(&bytes)).unwrap();
+    assert!(remaining.is_empty());
+    assert_eq!(
+        value.available_versions().len(),
+        VersionInformation::MAX_AVAILABLE_VERSIONS
+    );
+}
+
 #[test]
 fn compute_data_window_test() {
     assert_eq!(
@@ -306,3 +306,19 @@ fn checked_ops_test() {
         }
     })
 }
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_test() {
+    let value = VarInt::from_u32(1234);
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, "1234");
+    assert_eq!(serde_json::from_str::<VarInt>(&json).unwrap(), value);
+
+    let max = serde_json::to_string(&VarInt::MAX).unwrap();
+    assert_eq!(serde_json::from_str::<VarInt>(&max).unwrap(), VarInt::MAX);
+
+    // values outside of the varint range are rejected
+    let too_large = (MAX_VARINT_VALUE + 1).to_string();
+    assert!(serde_json::from_str::<VarInt>(&too_large).is_err());
+}
@@ -105,10 +105,7 @@ impl<P: EventHandler> EventHandler for datagram::Header<P> {
 pub fn decode_packet<'a, D: Decoder<'a>>(
     buffer: D,
 ) -> core::result::Result<Option<(datagram::Header<path::Tuple>, D)>, DecoderError> {
-    let mut header = datagram::Header {
-        path: path::Tuple::UNSPECIFIED,
-        ecn: Default::default(),
-    };
+    let mut header = datagram::Header::new(path::Tuple::UNSPECIFIED, Default::default());
     match decode_packet_with_event(buffer, &mut header)? {
         Some(buffer) => Ok(Some((header, buffer))),
         None => Ok(None),
@@ -101,6 +101,11 @@ impl Handle for Tuple {
         self.remote_address.port = port;
     }
 
+    #[inline]
+    fn set_local_port(&mut self, port: u16) {
+        self.local_address.port = port;
+    }
+
     #[inline]
     fn local_address(&self) -> path::LocalAddress {
         self.local_address.into()
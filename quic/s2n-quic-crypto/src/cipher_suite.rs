@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{aead::Aead, header_key::HeaderKey, hkdf, iv, ring_aead as aead};
+use crate::{aead::Aead, header_key::HeaderKey, hkdf, iv, offload, ring_aead as aead};
 use core::fmt;
 use s2n_quic_core::{
     assume,
@@ -28,6 +28,7 @@ macro_rules! impl_cipher_suite {
         $cipher:path,
         $cipher_key_len:expr,
         $header_protection:path,
+        $offload_cipher:expr,
         $key_label:expr,
         $iv_label:expr,
         $hp_label:expr,
@@ -51,18 +52,27 @@ macro_rules! impl_cipher_suite {
                 secret: hkdf::Prk,
                 iv: iv::Iv,
                 key: Key,
+                offload: offload::Offload,
+                offload_key: Option<Box<dyn offload::PacketProtectionKey>>,
             }
 
             impl $name {
-                pub fn new(secret: hkdf::Prk) -> (Self, HeaderKey) {
+                /// Creates a key which uses the given offload implementations, if any
+                pub fn new(secret: hkdf::Prk, offload: &offload::Offload) -> (Self, HeaderKey) {
                     let iv = Self::new_iv(&secret);
-                    let key = {
+                    let (key, offload_key) = {
                         let secret = Self::new_key_secret(&secret);
-                        Key::new(&*secret)
+                        (Key::new(&*secret), Self::new_offload_key(offload, &*secret))
+                    };
+                    let header_key = Self::new_header_key(&secret, offload);
+
+                    let key = Self {
+                        secret,
+                        iv,
+                        key,
+                        offload: offload.clone(),
+                        offload_key,
                     };
-                    let header_key = Self::new_header_key(&secret);
-
-                    let key = Self { secret, iv, key };
 
                     (key, header_key)
                 }
@@ -78,13 +88,22 @@ macro_rules! impl_cipher_suite {
                         .into();
 
                     let iv = Self::new_iv(&secret);
-                    let key = {
+                    let (key, offload_key) = {
                         let key = Self::new_key_secret(&secret);
                         // ask the existing key to derive the next one so it can persist any
                         // configuration
-                        self.key.update(&*key)
+                        (
+                            self.key.update(&*key),
+                            Self::new_offload_key(&self.offload, &*key),
+                        )
                     };
-                    Self { secret, iv, key }
+                    Self {
+                        secret,
+                        iv,
+                        key,
+                        offload: self.offload.clone(),
+                        offload_key,
+                    }
                 }
 
                 #[inline]
@@ -111,8 +130,21 @@ macro_rules! impl_cipher_suite {
                     iv::Iv::new(secret, &$iv_label)
                 }
 
-                fn new_header_key(secret: &hkdf::Prk) -> HeaderKey {
-                    HeaderKey::new::<{ KEY_LEN }>(secret, &$hp_label, &$header_protection)
+                fn new_header_key(secret: &hkdf::Prk, offload: &offload::Offload) -> HeaderKey {
+                    HeaderKey::new::<{ KEY_LEN }>(
+                        secret,
+                        &$hp_label,
+                        &$header_protection,
+                        $offload_cipher,
+                        offload.header_protection(),
+                    )
+                }
+
+                fn new_offload_key(
+                    offload: &offload::Offload,
+                    key: &[u8; KEY_LEN],
+                ) -> Option<Box<dyn offload::PacketProtectionKey>> {
+                    offload.packet_protection()?.new_key($offload_cipher, key)
                 }
             }
 
@@ -148,7 +180,11 @@ macro_rules! impl_cipher_suite {
                         res.unwrap()
                     };
 
-                    self.key.decrypt(&nonce, header, payload, tag)?;
+                    if let Some(offload_key) = &self.offload_key {
+                        offload_key.decrypt(&nonce, header, payload, tag)?;
+                    } else {
+                        self.key.decrypt(&nonce, header, payload, tag)?;
+                    }
 
                     Ok(())
                 }
@@ -161,7 +197,11 @@ macro_rules! impl_cipher_suite {
                     payload: &mut scatter::Buffer,
                 ) -> Result<(), packet_protection::Error> {
                     let nonce = self.iv.nonce(packet_number);
-                    self.key.encrypt(&nonce, header, payload)?;
+                    if let Some(offload_key) = &self.offload_key {
+                        offload_key.encrypt(&nonce, header, payload)?;
+                    } else {
+                        self.key.encrypt(&nonce, header, payload)?;
+                    }
                     Ok(())
                 }
 
@@ -264,6 +304,7 @@ impl_cipher_suite!(
     aead::AES_256_GCM,
     256 / 8, // 256-bit key
     aead::quic::AES_256,
+    offload::Cipher::Aes256,
     label::QUIC_KEY_32,
     label::QUIC_IV_12,
     label::QUIC_HP_32,
@@ -284,6 +325,7 @@ impl_cipher_suite!(
     aead::CHACHA20_POLY1305,
     256 / 8, // 256-bit key
     aead::quic::CHACHA20,
+    offload::Cipher::ChaCha20,
     label::QUIC_KEY_32,
     label::QUIC_IV_12,
     label::QUIC_HP_32,
@@ -301,6 +343,7 @@ impl_cipher_suite!(
     aead::AES_128_GCM,
     128 / 8, // 128-bit key
     aead::quic::AES_128,
+    offload::Cipher::Aes128,
     label::QUIC_KEY_16,
     label::QUIC_IV_12,
     label::QUIC_HP_16,
@@ -0,0 +1,20 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports whether the crypto backend is operating in FIPS mode
+
+/// Returns `true` if the crypto backend is a FIPS-validated module operating in FIPS mode
+///
+/// This requires the `fips` feature to be enabled.
+#[cfg(not(target_os = "windows"))]
+pub fn is_enabled() -> bool {
+    aws_lc_rs::try_fips_mode().is_ok()
+}
+
+/// Returns `true` if the crypto backend is a FIPS-validated module operating in FIPS mode
+///
+/// The backend used on Windows does not provide a FIPS mode.
+#[cfg(target_os = "windows")]
+pub fn is_enabled() -> bool {
+    false
+}
@@ -1,11 +1,16 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{hkdf, ring_aead as aead};
+use crate::{hkdf, offload, ring_aead as aead};
 use core::fmt;
 use s2n_quic_core::crypto::{self, HeaderProtectionMask};
 
-pub struct HeaderKey(pub(crate) aead::quic::HeaderProtectionKey);
+pub struct HeaderKey(Inner);
+
+enum Inner {
+    Software(aead::quic::HeaderProtectionKey),
+    Offload(Box<dyn offload::HeaderProtectionKey>),
+}
 
 impl crypto::HeaderKey for HeaderKey {
     #[inline]
@@ -15,7 +20,7 @@ impl crypto::HeaderKey for HeaderKey {
 
     #[inline]
     fn opening_sample_len(&self) -> usize {
-        self.0.algorithm().sample_len()
+        self.sample_len()
     }
 
     #[inline]
@@ -25,7 +30,25 @@ impl crypto::HeaderKey for HeaderKey {
 
     #[inline]
     fn sealing_sample_len(&self) -> usize {
-        self.0.algorithm().sample_len()
+        self.sample_len()
+    }
+
+    #[inline]
+    fn opening_header_protection_masks(
+        &self,
+        samples: &[&[u8]],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        self.header_protection_masks(samples, masks)
+    }
+
+    #[inline]
+    fn sealing_header_protection_masks(
+        &self,
+        samples: &[&[u8]],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        self.header_protection_masks(samples, masks)
     }
 }
 
@@ -34,6 +57,8 @@ impl HeaderKey {
         secret: &hkdf::Prk,
         label: &[u8],
         alg: &'static aead::quic::Algorithm,
+        cipher: offload::Cipher,
+        offload: Option<&dyn offload::HeaderProtection>,
     ) -> Self {
         let mut bytes = zeroize::Zeroizing::new([0u8; KEY_LEN]);
 
@@ -43,28 +68,62 @@ impl HeaderKey {
             .fill(bytes.as_mut())
             .expect("fill size verified");
 
+        if let Some(key) = offload.and_then(|offload| offload.new_key(cipher, bytes.as_ref())) {
+            return Self(Inner::Offload(key));
+        }
+
         let key = aead::quic::HeaderProtectionKey::new(alg, bytes.as_ref())
             .expect("header secret length already checked");
-        Self(key)
+        Self(Inner::Software(key))
+    }
+
+    #[inline]
+    fn sample_len(&self) -> usize {
+        match &self.0 {
+            Inner::Software(key) => key.algorithm().sample_len(),
+            Inner::Offload(key) => key.sample_len(),
+        }
     }
 
     #[inline]
     fn header_protection_mask(&self, sample: &[u8]) -> HeaderProtectionMask {
-        self.0
-            .new_mask(sample)
-            .expect("sample length already checked")
+        match &self.0 {
+            Inner::Software(key) => key.new_mask(sample).expect("sample length already checked"),
+            Inner::Offload(key) => key.mask(sample),
+        }
+    }
+
+    #[inline]
+    fn header_protection_masks(&self, samples: &[&[u8]], masks: &mut [HeaderProtectionMask]) {
+        match &self.0 {
+            Inner::Software(_) => {
+                for (sample, mask) in samples.iter().zip(masks.iter_mut()) {
+                    *mask = self.header_protection_mask(sample);
+                }
+            }
+            Inner::Offload(key) => key.masks(samples, masks),
+        }
     }
 }
 
 impl fmt::Debug for HeaderKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("HeaderKey").finish()
+        let offloaded = matches!(self.0, Inner::Offload(_));
+        f.debug_struct("HeaderKey")
+            .field("offloaded", &offloaded)
+            .finish()
     }
 }
 
 impl From<aead::quic::HeaderProtectionKey> for HeaderKey {
     fn from(key: aead::quic::HeaderProtectionKey) -> Self {
-        Self(key)
+        Self(Inner::Software(key))
+    }
+}
+
+impl From<Box<dyn offload::HeaderProtectionKey>> for HeaderKey {
+    fn from(key: Box<dyn offload::HeaderProtectionKey>) -> Self {
+        Self(Inner::Offload(key))
     }
 }
 
@@ -94,6 +153,24 @@ impl crypto::HeaderKey for HeaderKeyPair {
     fn sealing_sample_len(&self) -> usize {
         self.sealer.sealing_sample_len()
     }
+
+    #[inline]
+    fn opening_header_protection_masks(
+        &self,
+        samples: &[&[u8]],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        self.opener.opening_header_protection_masks(samples, masks)
+    }
+
+    #[inline]
+    fn sealing_header_protection_masks(
+        &self,
+        samples: &[&[u8]],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        self.sealer.sealing_header_protection_masks(samples, masks)
+    }
 }
 
 macro_rules! header_key {
@@ -127,6 +204,24 @@ macro_rules! header_key {
             fn sealing_sample_len(&self) -> usize {
                 self.0.sealing_sample_len()
             }
+
+            #[inline]
+            fn opening_header_protection_masks(
+                &self,
+                samples: &[&[u8]],
+                masks: &mut [s2n_quic_core::crypto::HeaderProtectionMask],
+            ) {
+                self.0.opening_header_protection_masks(samples, masks)
+            }
+
+            #[inline]
+            fn sealing_header_protection_masks(
+                &self,
+                samples: &[&[u8]],
+                masks: &mut [s2n_quic_core::crypto::HeaderProtectionMask],
+            ) {
+                self.0.sealing_header_protection_masks(samples, masks)
+            }
         }
 
         impl From<crate::header_key::HeaderKeyPair> for $name {
@@ -133,7 +133,7 @@ mod tests {
                 EXAMPLE_DCID, EXAMPLE_SERVER_INITIAL_PAYLOAD,
                 EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
             },
-            InitialKey as _,
+            test_vectors, InitialKey as _,
         },
         inet::SocketAddress,
         packet::{encoding::PacketEncoder, initial::CleartextInitial, ProtectedPacket},
@@ -159,6 +159,11 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vectors_test() {
+        test_vectors::check_initial_key::<InitialKey>();
+    }
+
     fn test_round_trip(
         sealer: &(InitialKey, InitialHeaderKey),
         opener: &(InitialKey, InitialHeaderKey),
@@ -25,7 +25,7 @@ pub use ring::{
     aead::{Algorithm, MAX_TAG_LEN},
     constant_time, digest, hkdf,
     hkdf::Prk,
-    hmac,
+    hmac, rand,
 };
 
 #[derive(Clone)]
@@ -34,8 +34,10 @@ pub struct SecretPair {
     pub client: Prk,
 }
 
+pub mod fips;
 pub mod handshake;
 pub mod initial;
+pub mod offload;
 pub mod one_rtt;
 pub mod retry;
 pub mod zero_rtt;
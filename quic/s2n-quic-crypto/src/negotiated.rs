@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    cipher_suite::NegotiatedCipherSuite as CipherSuite, header_key::HeaderKeyPair, Algorithm,
-    SecretPair,
+    cipher_suite::NegotiatedCipherSuite as CipherSuite, header_key::HeaderKeyPair, offload,
+    Algorithm, SecretPair,
 };
 use s2n_quic_core::{
     crypto::{packet_protection, scatter, Key},
@@ -21,14 +21,15 @@ impl KeyPair {
         endpoint: endpoint::Type,
         algorithm: &Algorithm,
         secrets: SecretPair,
+        offload: &offload::Offload,
     ) -> Option<(Self, HeaderKeyPair)> {
         let (sealer_secret, opener_secret) = match endpoint {
             endpoint::Type::Client => (secrets.client, secrets.server),
             endpoint::Type::Server => (secrets.server, secrets.client),
         };
 
-        let (sealer, header_sealer) = CipherSuite::new(algorithm, sealer_secret)?;
-        let (opener, header_opener) = CipherSuite::new(algorithm, opener_secret)?;
+        let (sealer, header_sealer) = CipherSuite::new(algorithm, sealer_secret, offload)?;
+        let (opener, header_opener) = CipherSuite::new(algorithm, opener_secret, offload)?;
 
         let key = Self { sealer, opener };
         let header_key = HeaderKeyPair {
@@ -119,9 +120,20 @@ macro_rules! negotiated_crypto {
                 endpoint: s2n_quic_core::endpoint::Type,
                 algorithm: &$crate::Algorithm,
                 secrets: $crate::SecretPair,
+            ) -> Option<(Self, $header_key)> {
+                Self::new_with_offload(endpoint, algorithm, secrets, &Default::default())
+            }
+
+            /// Create a cipher_suite for an endpoint type which uses the given offload
+            /// implementations, if any
+            pub fn new_with_offload(
+                endpoint: s2n_quic_core::endpoint::Type,
+                algorithm: &$crate::Algorithm,
+                secrets: $crate::SecretPair,
+                offload: &$crate::offload::Offload,
             ) -> Option<(Self, $header_key)> {
                 let (key, header_key) =
-                    crate::negotiated::KeyPair::new(endpoint, algorithm, secrets)?;
+                    crate::negotiated::KeyPair::new(endpoint, algorithm, secrets, offload)?;
 
                 let key = Self(key);
                 let header_key = $header_key::from(header_key);
@@ -0,0 +1,293 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offloads parts of packet protection to an external implementation
+//!
+//! Header protection and payload protection can be offloaded independently of each other. For
+//! example, a deployment may generate header protection masks in batches using SIMD instructions
+//! while keeping the AEAD in software, or hand the AEAD to an accelerator while keeping header
+//! protection in software.
+//!
+//! Keys are always derived in software. Only the derived key material is passed to the offload
+//! implementation, which can decline a key by returning `None`, in which case the software
+//! implementation is used.
+//!
+//! The implementations are configured with an [`Offload`], which is passed to the TLS provider
+//! that derives the Handshake and 1-RTT keys.
+
+use crate::iv::NONCE_LEN;
+use s2n_quic_core::crypto::{packet_protection, scatter, HeaderProtectionMask};
+use std::sync::Arc;
+
+/// The length of the authentication tag for all of the supported ciphers
+pub const TAG_LEN: usize = 16;
+
+/// The cipher used by an offloaded key
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Cipher {
+    Aes128,
+    Aes256,
+    ChaCha20,
+}
+
+/// Creates offloaded header protection keys
+pub trait HeaderProtection: 'static + Send + Sync {
+    /// Creates a header protection key from the derived `key` material
+    ///
+    /// Returning `None` uses the software implementation for the key.
+    fn new_key(&self, cipher: Cipher, key: &[u8]) -> Option<Box<dyn HeaderProtectionKey>>;
+}
+
+/// An offloaded header protection key
+pub trait HeaderProtectionKey: Send {
+    /// Returns the sample size needed to derive a mask
+    fn sample_len(&self) -> usize;
+
+    /// Derives a header protection mask from a sample
+    fn mask(&self, sample: &[u8]) -> HeaderProtectionMask;
+
+    /// Derives a header protection mask for each of the samples
+    ///
+    /// Implementations which are able to process multiple samples at once should override this
+    /// method.
+    #[inline]
+    fn masks(&self, samples: &[&[u8]], masks: &mut [HeaderProtectionMask]) {
+        for (sample, mask) in samples.iter().zip(masks.iter_mut()) {
+            *mask = self.mask(sample);
+        }
+    }
+}
+
+/// Creates offloaded packet protection keys
+pub trait PacketProtection: 'static + Send + Sync {
+    /// Creates a packet protection key from the derived `key` material
+    ///
+    /// Returning `None` uses the software implementation for the key.
+    fn new_key(&self, cipher: Cipher, key: &[u8]) -> Option<Box<dyn PacketProtectionKey>>;
+}
+
+/// An offloaded packet protection key
+///
+/// The nonce is computed in software from the packet number and IV.
+pub trait PacketProtectionKey: Send {
+    /// Encrypts the payload in place, appending the tag
+    fn encrypt(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), packet_protection::Error>;
+
+    /// Decrypts the payload in place, verifying the tag
+    fn decrypt(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        header: &[u8],
+        payload: &mut [u8],
+        tag: &[u8; TAG_LEN],
+    ) -> Result<(), packet_protection::Error>;
+}
+
+/// The offload implementations used when deriving keys
+///
+/// By default, nothing is offloaded and all of the keys use the software implementation.
+#[derive(Clone, Default)]
+pub struct Offload {
+    header_protection: Option<Arc<dyn HeaderProtection>>,
+    packet_protection: Option<Arc<dyn PacketProtection>>,
+}
+
+impl Offload {
+    /// Offloads header protection to the given implementation
+    pub fn with_header_protection<H: HeaderProtection>(mut self, offload: H) -> Self {
+        self.header_protection = Some(Arc::new(offload));
+        self
+    }
+
+    /// Offloads packet protection to the given implementation
+    pub fn with_packet_protection<P: PacketProtection>(mut self, offload: P) -> Self {
+        self.packet_protection = Some(Arc::new(offload));
+        self
+    }
+
+    /// Returns `true` if any part of packet protection is offloaded
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.header_protection.is_some() || self.packet_protection.is_some()
+    }
+
+    #[inline]
+    pub(crate) fn header_protection(&self) -> Option<&dyn HeaderProtection> {
+        self.header_protection.as_deref()
+    }
+
+    #[inline]
+    pub(crate) fn packet_protection(&self) -> Option<&dyn PacketProtection> {
+        self.packet_protection.as_deref()
+    }
+}
+
+impl core::fmt::Debug for Offload {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Offload")
+            .field("header_protection", &self.header_protection.is_some())
+            .field("packet_protection", &self.packet_protection.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aead::Aead, cipher_suite::TLS_CHACHA20_POLY1305_SHA256, hkdf, ring_aead as aead};
+    use s2n_codec::EncoderBuffer;
+    use s2n_quic_core::crypto::{test_vectors, HeaderKey as _, Key as _};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counter(AtomicUsize);
+
+    impl Counter {
+        fn increment(&self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn get(&self) -> usize {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+
+    static HEADER_PROTECTION_MASKS: Counter = Counter(AtomicUsize::new(0));
+    static PACKET_PROTECTION_CALLS: Counter = Counter(AtomicUsize::new(0));
+
+    /// Offloads keys to the software implementation while counting the calls
+    struct TestOffload {
+        accept: bool,
+    }
+
+    impl HeaderProtection for TestOffload {
+        fn new_key(&self, cipher: Cipher, key: &[u8]) -> Option<Box<dyn HeaderProtectionKey>> {
+            assert_eq!(cipher, Cipher::ChaCha20);
+            if !self.accept {
+                return None;
+            }
+            let key = aead::quic::HeaderProtectionKey::new(&aead::quic::CHACHA20, key).unwrap();
+            Some(Box::new(TestHeaderKey(key)))
+        }
+    }
+
+    struct TestHeaderKey(aead::quic::HeaderProtectionKey);
+
+    impl HeaderProtectionKey for TestHeaderKey {
+        fn sample_len(&self) -> usize {
+            self.0.algorithm().sample_len()
+        }
+
+        fn mask(&self, sample: &[u8]) -> HeaderProtectionMask {
+            HEADER_PROTECTION_MASKS.increment();
+            self.0.new_mask(sample).unwrap()
+        }
+    }
+
+    impl PacketProtection for TestOffload {
+        fn new_key(&self, cipher: Cipher, key: &[u8]) -> Option<Box<dyn PacketProtectionKey>> {
+            assert_eq!(cipher, Cipher::ChaCha20);
+            if !self.accept {
+                return None;
+            }
+            let key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, key).unwrap();
+            Some(Box::new(TestPacketKey(aead::LessSafeKey::new(key))))
+        }
+    }
+
+    struct TestPacketKey(aead::LessSafeKey);
+
+    impl PacketProtectionKey for TestPacketKey {
+        fn encrypt(
+            &self,
+            nonce: &[u8; NONCE_LEN],
+            header: &[u8],
+            payload: &mut scatter::Buffer,
+        ) -> Result<(), packet_protection::Error> {
+            PACKET_PROTECTION_CALLS.increment();
+            self.0.encrypt(nonce, header, payload)
+        }
+
+        fn decrypt(
+            &self,
+            nonce: &[u8; NONCE_LEN],
+            header: &[u8],
+            payload: &mut [u8],
+            tag: &[u8; TAG_LEN],
+        ) -> Result<(), packet_protection::Error> {
+            PACKET_PROTECTION_CALLS.increment();
+            self.0.decrypt(nonce, header, payload, tag)
+        }
+    }
+
+    const ACCEPT: TestOffload = TestOffload { accept: true };
+    const DECLINE: TestOffload = TestOffload { accept: false };
+
+    fn new_cipher_suite(
+        offload: &Offload,
+    ) -> (TLS_CHACHA20_POLY1305_SHA256, crate::header_key::HeaderKey) {
+        let vector = test_vectors::CHACHA20_POLY1305_SHORT_HEADER;
+        let secret = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, vector.keys.secret);
+        TLS_CHACHA20_POLY1305_SHA256::new(secret, offload)
+    }
+
+    fn check(key: &TLS_CHACHA20_POLY1305_SHA256, header_key: &crate::header_key::HeaderKey) {
+        let vector = test_vectors::CHACHA20_POLY1305_SHORT_HEADER;
+        test_vectors::check_sealing_key(key, &vector);
+        test_vectors::check_opening_key(key, &vector);
+        test_vectors::check_sealing_header_key(header_key, &vector);
+        test_vectors::check_opening_header_key(header_key, &vector);
+    }
+
+    #[test]
+    fn offload_test() {
+        let vector = test_vectors::CHACHA20_POLY1305_SHORT_HEADER;
+
+        // nothing is offloaded by default
+        let (key, header_key) = new_cipher_suite(&Offload::default());
+        check(&key, &header_key);
+        assert_eq!(HEADER_PROTECTION_MASKS.get(), 0);
+        assert_eq!(PACKET_PROTECTION_CALLS.get(), 0);
+
+        // only offload header protection
+        let (key, header_key) =
+            new_cipher_suite(&Offload::default().with_header_protection(ACCEPT));
+        check(&key, &header_key);
+        assert_eq!(HEADER_PROTECTION_MASKS.get(), 2);
+        assert_eq!(PACKET_PROTECTION_CALLS.get(), 0);
+
+        let mut masks = [HeaderProtectionMask::default(); 3];
+        header_key.opening_header_protection_masks(&[vector.sample; 3], &mut masks);
+        assert_eq!(masks, [vector.mask; 3]);
+        assert_eq!(HEADER_PROTECTION_MASKS.get(), 5);
+
+        // only offload packet protection
+        let (key, header_key) =
+            new_cipher_suite(&Offload::default().with_packet_protection(ACCEPT));
+        check(&key, &header_key);
+        assert_eq!(HEADER_PROTECTION_MASKS.get(), 5);
+        assert_eq!(PACKET_PROTECTION_CALLS.get(), 2);
+
+        // keys derived from a key update continue to use the offload
+        let key = key.update();
+        let mut payload = [0; 32];
+        let mut payload = scatter::Buffer::new(EncoderBuffer::new(&mut payload));
+        key.encrypt(0, &[], &mut payload).unwrap();
+        assert_eq!(PACKET_PROTECTION_CALLS.get(), 3);
+
+        // declined keys fall back to the software implementation
+        let (key, header_key) = new_cipher_suite(
+            &Offload::default()
+                .with_header_protection(DECLINE)
+                .with_packet_protection(DECLINE),
+        );
+        check(&key, &header_key);
+        assert_eq!(HEADER_PROTECTION_MASKS.get(), 5);
+        assert_eq!(PACKET_PROTECTION_CALLS.get(), 3);
+    }
+}
@@ -31,7 +31,7 @@ mod tests {
     use crate::{cipher_suite::TLS_CHACHA20_POLY1305_SHA256, hkdf};
     use hex_literal::hex;
     use s2n_codec::{encoder::scatter, EncoderBuffer};
-    use s2n_quic_core::crypto::Key;
+    use s2n_quic_core::crypto::{test_vectors, Key};
 
     //= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
     //# In this example, TLS produces an application write secret from which
@@ -75,18 +75,30 @@ mod tests {
     ) -> (TLS_CHACHA20_POLY1305_SHA256, TLS_CHACHA20_POLY1305_SHA256) {
         // Create a cipher based on the initial secret
         let key = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, secret);
-        let cipher = TLS_CHACHA20_POLY1305_SHA256::new(key);
+        let cipher = TLS_CHACHA20_POLY1305_SHA256::new(key, &Default::default());
 
         // Create the cipher after a Key Update has occurred
         let next_cipher = cipher.0.update();
 
         // Create a cipher based on the expected post-update secret
         let next_key = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, next_secret);
-        let expected_next_cipher = TLS_CHACHA20_POLY1305_SHA256::new(next_key);
+        let expected_next_cipher = TLS_CHACHA20_POLY1305_SHA256::new(next_key, &Default::default());
 
         (next_cipher, expected_next_cipher.0)
     }
 
+    #[test]
+    fn test_vectors_test() {
+        let vector = test_vectors::CHACHA20_POLY1305_SHORT_HEADER;
+        let secret = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, vector.keys.secret);
+        let (key, header_key) = TLS_CHACHA20_POLY1305_SHA256::new(secret, &Default::default());
+
+        test_vectors::check_sealing_key(&key, &vector);
+        test_vectors::check_opening_key(&key, &vector);
+        test_vectors::check_sealing_header_key(&header_key, &vector);
+        test_vectors::check_opening_header_key(&header_key, &vector);
+    }
+
     #[test]
     fn test_key_update() {
         let tests = [
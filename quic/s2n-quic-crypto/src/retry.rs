@@ -46,7 +46,7 @@ mod tests {
     use s2n_quic_core::{
         connection,
         connection::id::ConnectionInfo,
-        crypto::{retry, RetryKey as _},
+        crypto::{retry, test_vectors, RetryKey as _},
         inet, packet,
         packet::number::{PacketNumberSpace, TruncatedPacketNumber},
         random, token,
@@ -64,6 +64,11 @@ mod tests {
         assert!(RetryKey::validate(&retry::example::PSEUDO_PACKET, invalid_tag).is_err());
     }
 
+    #[test]
+    fn test_vectors_test() {
+        test_vectors::check_retry_key::<RetryKey>();
+    }
+
     fn pn(space: PacketNumberSpace) -> TruncatedPacketNumber {
         let pn = space.new_packet_number(VarInt::new(0x1).unwrap());
         pn.truncate(pn).unwrap()
@@ -1,14 +1,24 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-struct ConnectionMeta {
+struct ConnectionMeta<'a> {
     #[builder(crate::endpoint::Type)]
     endpoint_type: EndpointType,
 
     id: u64,
 
+    /// Identifies the connection attempt the connection was created for
+    ///
+    /// The value is derived from the original destination connection ID, so it also matches the
+    /// endpoint events which were published for the attempt before the connection existed, e.g.
+    /// when sending a Retry or Version Negotiation packet.
+    attempt_id: u64,
+
     #[builder(crate::time::Timestamp)]
     timestamp: crate::event::Timestamp,
+
+    /// The context attached to the connection by the application, if any
+    application_context: crate::event::ApplicationContext<'a>,
 }
 
 struct EndpointMeta {
@@ -17,9 +27,22 @@ struct EndpointMeta {
 
     #[builder(crate::time::Timestamp)]
     timestamp: crate::event::Timestamp,
+
+    /// Identifies the connection attempt the event is associated with, if any
+    ///
+    /// See [`ConnectionMeta::attempt_id`].
+    attempt_id: Option<u64>,
 }
 
-struct ConnectionInfo {}
+struct ConnectionInfo<'a> {
+    remote_address: SocketAddress<'a>,
+    /// The server name requested by the application
+    ///
+    /// Servers don't learn the name until the ClientHello is processed, so it's not available
+    /// when the connection context is created. It's provided to subscribers afterwards with
+    /// `on_connection_info_updated`.
+    server_name: Option<&'a str>,
+}
 
 // https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.3
 struct TransportParameters<'a> {
@@ -369,6 +392,9 @@ enum Frame {
     Datagram {
         len: u16,
     },
+    ObservedAddress {
+        sequence_number: u64,
+    },
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -572,6 +598,15 @@ where
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::ObservedAddress {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ObservedAddress {
+            sequence_number: self.sequence_number.as_u64(),
+        }
+    }
+}
+
 enum StreamType {
     Bidirectional,
     Unidirectional,
@@ -725,6 +760,14 @@ enum DatagramDropReason {
     PathLimitExceeded,
     /// The peer initiated a connection migration without supplying enough connection IDs to use.
     InsufficientConnectionIds,
+    /// The datagram was rejected by the endpoint limits before it was parsed.
+    ///
+    /// This occurs when the peer's address has been blocked.
+    Blocked,
+    /// The datagram payload exceeded the `max_udp_payload_size` advertised to the peer.
+    ExceedsMaxUdpPayloadSize,
+    /// The peer's solution to a challenge issued by the endpoint limits was rejected.
+    InvalidChallengeResponse,
 }
 
 enum KeySpace {
@@ -833,6 +876,7 @@ enum MigrationDenyReason {
     PortScopeChanged,
     IpScopeChange,
     ConnectionMigrationDisabled,
+    Application,
 }
 
 /// The current state of the ECN controller for the path
@@ -867,6 +911,16 @@ enum HandshakeStatus {
     HandshakeDoneLost,
 }
 
+/// The state of a client's attempt to resume a previous TLS session
+enum SessionResumptionStatus {
+    /// A session ticket was offered to the server
+    Attempted,
+    /// The server accepted the offered session ticket
+    Accepted,
+    /// The server rejected the offered session ticket
+    Rejected,
+}
+
 /// The source that caused a congestion event
 enum CongestionSource {
     /// Explicit Congestion Notification
@@ -917,6 +971,18 @@ enum SlowStartExitCause {
     Other,
 }
 
+/// The policy which determined the contents of a PTO probe packet
+enum ProbePolicy {
+    /// New data, followed by any unacknowledged data which fit in the remaining space
+    Default,
+    /// Only data which was still unacknowledged
+    RetransmitUnacknowledged,
+    /// Only data which hadn't been transmitted yet, along with any data which was declared lost
+    NewData,
+    /// Only a PING frame, along with any pending ACK frame
+    PingOnly,
+}
+
 /// The reason the MTU was updated
 enum MtuUpdatedCause {
     /// The MTU was initialized with the default value
@@ -963,3 +1029,60 @@ enum BbrState {
     ProbeBwUp,
     ProbeRtt,
 }
+
+/// The congestion control algorithm used on a path, along with its parameters
+enum CongestionControlAlgorithm {
+    /// CUBIC, as specified in RFC 8312
+    Cubic {
+        /// The multiplicative window decrease factor
+        beta: f32,
+        /// The scaling constant which determines the aggressiveness of window growth
+        c: f32,
+    },
+    /// NewReno, as specified in RFC 9002
+    NewReno {
+        /// The multiplicative window decrease factor
+        beta: f32,
+    },
+    /// BBRv2
+    Bbr,
+    /// A congestion controller provided by the application
+    Other,
+}
+
+/// How packets from multiple packet number spaces were combined into a datagram
+enum CoalescingDecision {
+    /// The packets were coalesced up to the maximum datagram size of the path
+    Coalesced,
+    /// The packets were coalesced into a datagram limited to `max_len` bytes
+    Limited { max_len: u16 },
+    /// Coalescing is disabled, so only the first packet was written and the rest were deferred to
+    /// later datagrams
+    Disabled,
+}
+
+/// How the endpoint responded to a connection attempt which wasn't allowed to continue
+enum ConnectionAttemptAction {
+    /// A Retry packet was sent to validate the peer's address
+    Retry,
+    /// The attempt was silently dropped
+    Drop,
+    /// The attempt was refused
+    Close,
+    /// A Retry packet was sent carrying a challenge for the peer to solve
+    Challenge,
+}
+
+/// The reason data in the Initial or Handshake packet number space is retransmitted
+enum HandshakeRetransmissionReason {
+    /// A packet was declared lost
+    PacketLost { packet_number: u64, bytes_lost: u16 },
+    /// The PTO timer expired while ack-eliciting packets were in flight
+    ProbeTimeout { pto_backoff: u32 },
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.2.1
+    //# it is the client's responsibility to send packets to unblock the server
+    //# until it is certain that the server has finished its address validation
+    /// The PTO timer expired with nothing in flight, so a probe is sent to keep the peer from
+    /// being blocked by its anti-amplification limit
+    AntiDeadlock { pto_backoff: u32 },
+}
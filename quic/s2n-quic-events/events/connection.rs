@@ -105,6 +105,26 @@ struct RecoveryMetrics<'a> {
     congestion_limited: bool,
 }
 
+#[event("recovery:rtt_sample")]
+/// An RTT sample was generated from an acknowledgement
+///
+/// Samples are emitted in every packet number space, so the RTT is available as soon as the
+/// first Initial packet is acknowledged, rather than only after the handshake completes.
+struct RttSample<'a> {
+    path: Path<'a>,
+    /// The header of the largest acknowledged packet, which generated the sample
+    packet_header: PacketHeader,
+    /// The time between sending the packet and receiving its acknowledgement
+    latest_rtt: Duration,
+    /// The time the peer reported delaying the acknowledgement
+    ///
+    /// During the handshake this includes the time the peer spent processing the packet, so
+    /// subtracting it from `latest_rtt` separates the network RTT from the peer's processing time.
+    ack_delay: Duration,
+    min_rtt: Duration,
+    smoothed_rtt: Duration,
+}
+
 #[event("recovery:congestion")]
 /// Congestion (ECN or packet loss) has occurred
 struct Congestion<'a> {
@@ -221,6 +241,10 @@ struct DatagramSent {
 /// Datagram received by a connection
 struct DatagramReceived {
     len: u16,
+    /// The index of the IO provider's receive queue the datagram was read from, if known
+    ///
+    /// This is not the NIC's RSS queue or the CPU which received the datagram.
+    queue_id: Option<u32>,
 }
 
 #[event("transport:datagram_dropped")]
@@ -271,6 +295,12 @@ struct PathChallengeUpdated<'a> {
     challenge_data: &'a [u8],
 }
 
+#[event("security:session_resumption")]
+/// Emitted when a client offers a session ticket and when the server responds to it
+struct SessionResumption {
+    status: SessionResumptionStatus,
+}
+
 #[event("tls:client_hello")]
 struct TlsClientHello<'a> {
     payload: &'a [&'a [u8]],
@@ -281,6 +311,27 @@ struct TlsServerHello<'a> {
     payload: &'a [&'a [u8]],
 }
 
+#[event("transport:client_hello_fragmented")]
+/// The client's ClientHello did not fit in a single datagram and was split across multiple
+/// Initial packets
+struct ClientHelloFragmented {
+    /// The length of the ClientHello message
+    len: u64,
+    /// The number of datagrams used to transmit the ClientHello
+    datagram_count: u16,
+}
+
+#[event("transport:datagram_coalesced")]
+/// Packets from more than one packet number space were ready to be sent in the same datagram
+struct DatagramCoalesced {
+    /// The number of packets which were written into the datagram
+    packet_count: u8,
+    /// The length of the datagram
+    len: u16,
+    /// How the connection decided to coalesce the packets
+    decision: CoalescingDecision,
+}
+
 #[event("transport:rx_stream_progress")]
 struct RxStreamProgress {
     bytes: usize,
@@ -291,11 +342,60 @@ struct TxStreamProgress {
     bytes: usize,
 }
 
+#[event("transport:stream_marker_sent")]
+/// A packet carrying bytes from a marked range of a stream was sent
+struct StreamMarkerSent {
+    stream_id: u64,
+    marker: u64,
+    packet_header: PacketHeader,
+    /// `true` if the bytes had been declared lost and are being retransmitted
+    is_retransmission: bool,
+}
+
+#[event("transport:stream_marker_lost")]
+/// Bytes from a marked range of a stream were declared lost
+struct StreamMarkerLost {
+    stream_id: u64,
+    marker: u64,
+}
+
+#[event("transport:stream_marker_acknowledged")]
+/// All of the bytes in a marked range of a stream have been acknowledged by the peer
+struct StreamMarkerAcknowledged {
+    stream_id: u64,
+    marker: u64,
+}
+
+#[event("transport:peer_stream_opened")]
+/// The peer opened a stream
+///
+/// Opening a stream implicitly opens all lower stream IDs of the same type, so an event is
+/// emitted for each of those streams as well.
+struct PeerStreamOpened {
+    id: u64,
+    stream_type: StreamType,
+}
+
 #[event("connectivity::keep_alive_timer_expired")]
 pub struct KeepAliveTimerExpired {
     timeout: Duration,
 }
 
+#[event("connectivity::connection_hibernated")]
+/// The connection released its transient buffers after being idle for the hibernation timeout
+struct ConnectionHibernated {
+    timeout: Duration,
+}
+
+#[event("connectivity::connection_stalled")]
+/// The connection stopped making progress and is being closed
+///
+/// This is only checked in debug builds. The report describes the timers and transmission
+/// interest of each of the connection's components.
+struct ConnectionStalled<'a> {
+    report: &'a str,
+}
+
 #[event("connectivity:mtu_updated")]
 /// The maximum transmission unit (MTU) for the path has changed
 struct MtuUpdated {
@@ -313,6 +413,30 @@ struct SlowStartExited {
     congestion_window: u32,
 }
 
+#[event("recovery:pto_probe_sent")]
+/// A probe packet was sent after the PTO timer expired
+struct PtoProbeSent {
+    packet_header: PacketHeader,
+    /// The policy which determined the contents of the probe
+    policy: ProbePolicy,
+    /// The number of stream bytes which were transmitted for the first time
+    new_stream_bytes: u64,
+    /// The number of stream bytes which were retransmitted
+    retransmitted_stream_bytes: u64,
+}
+
+#[event("recovery:handshake_retransmission")]
+/// Data in the Initial or Handshake packet number space needs to be retransmitted
+///
+/// These spaces are only used before the handshake is confirmed, so the event makes it possible
+/// to tell why a handshake is taking longer than expected.
+struct HandshakeRetransmission {
+    /// The key space of the data being retransmitted
+    space: KeySpace,
+    /// The reason the data needs to be retransmitted
+    reason: HandshakeRetransmissionReason,
+}
+
 #[event("recovery:delivery_rate_sampled")]
 /// A new delivery rate sample has been generated
 /// Note: This event is only recorded for congestion controllers that support
@@ -337,3 +461,10 @@ struct BbrStateChanged {
     path_id: u64,
     state: BbrState,
 }
+
+#[event("recovery:congestion_controller_initialized")]
+/// A congestion controller has been initialized for a path
+struct CongestionControllerInitialized {
+    path_id: u64,
+    algorithm: CongestionControlAlgorithm,
+}
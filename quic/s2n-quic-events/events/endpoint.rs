@@ -66,3 +66,25 @@ struct EndpointDatagramDropped {
 struct EndpointConnectionAttemptFailed {
     error: crate::connection::Error,
 }
+
+#[event("transport:connection_attempt_rejected")]
+#[subject(endpoint)]
+/// A connection attempt was not allowed to continue by the endpoint limits
+struct EndpointConnectionAttemptRejected<'a> {
+    /// How the endpoint responded to the attempt
+    action: ConnectionAttemptAction,
+    /// The reason provided by the endpoint limits, if any
+    reason: Option<&'a str>,
+    /// Whether the peer's address was validated with a token
+    is_address_validated: bool,
+}
+
+#[event("transport:connection_attempt_retried")]
+#[subject(endpoint)]
+/// A client connection attempt failed and will be retried after the backoff
+struct EndpointConnectionAttemptRetried {
+    /// The number of attempts which have been made, including the failed one
+    attempts: u32,
+    backoff: Duration,
+    error: crate::connection::Error,
+}
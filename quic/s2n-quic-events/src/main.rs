@@ -17,6 +17,7 @@ struct Output {
     pub connection_publisher: TokenStream,
     pub connection_publisher_subscriber: TokenStream,
     pub tuple_subscriber: TokenStream,
+    pub sampled_subscriber: TokenStream,
     pub tracing_subscriber: TokenStream,
     pub builders: TokenStream,
     pub api: TokenStream,
@@ -37,6 +38,7 @@ impl ToTokens for Output {
             connection_publisher,
             connection_publisher_subscriber,
             tuple_subscriber,
+            sampled_subscriber,
             tracing_subscriber,
             builders,
             api,
@@ -99,7 +101,7 @@ impl ToTokens for Output {
                                 self.server.id()
                             }
                         };
-                        tracing::span!(target: "s2n_quic", parent: parent, tracing::Level::DEBUG, "conn", id = meta.id)
+                        tracing::span!(target: "s2n_quic", parent: parent, tracing::Level::DEBUG, "conn", id = meta.id, attempt_id = meta.attempt_id)
                     }
 
                     #tracing_subscriber
@@ -196,7 +198,7 @@ impl ToTokens for Output {
                     fn timestamp(&self) -> &crate::event::Timestamp;
                 }
 
-                impl Meta for ConnectionMeta {
+                impl<'a> Meta for ConnectionMeta<'a> {
                     fn endpoint_type(&self) -> &EndpointType {
                         &self.endpoint_type
                     }
@@ -274,6 +276,17 @@ impl ToTokens for Output {
                     /// Creates a context to be passed to each connection-related event
                     fn create_connection_context(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> Self::ConnectionContext;
 
+                    /// Returns `false` if connection events should not be published for the connection
+                    ///
+                    /// The transport checks this before publishing each connection event, which allows
+                    /// subscribers to opt out of a connection in `create_connection_context` without
+                    /// paying the cost of event dispatch. See [`Sampled`] for an implementation.
+                    #[inline]
+                    fn is_connection_sampled(context: &Self::ConnectionContext) -> bool {
+                        let _ = context;
+                        true
+                    }
+
                     /// The period at which `on_supervisor_timeout` is called
                     ///
                     /// If multiple `event::Subscriber`s are composed together, the minimum `supervisor_timeout`
@@ -300,6 +313,54 @@ impl ToTokens for Output {
                         supervisor::Outcome::default()
                     }
 
+                    /// Called after events are published for a connection to determine if the connection should be closed
+                    ///
+                    /// This allows subscribers to enforce policies based on the events they observe, e.g. closing
+                    /// a connection which sends an excessive number of ACK frames or resets too many streams. Event
+                    /// callbacks typically record the decision in the `ConnectionContext`, which is then returned here.
+                    ///
+                    /// The transport checks the verdict after processing each received datagram, timeout, and wakeup
+                    /// for the connection.
+                    ///
+                    /// If multiple `event::Subscriber`s are composed together, the most severe `supervisor::Outcome`
+                    /// across all `event::Subscriber`s will be used.
+                    #[allow(unused_variables)]
+                    fn connection_verdict(&mut self, conn_context: &mut Self::ConnectionContext, meta: &ConnectionMeta) -> supervisor::Outcome {
+                        supervisor::Outcome::default()
+                    }
+
+                    /// Called when the transport enters one of the hot paths listed in [`profile::Scope`]
+                    ///
+                    /// Each call is followed by a call to `on_profile_scope_exited` with the same scope
+                    /// once the work completes. Scopes of the same kind never overlap, so subscribers can
+                    /// measure the time spent in each scope by recording the time it was entered. Both calls
+                    /// are made for every connection, regardless of `is_connection_sampled`.
+                    ///
+                    /// [`profile::Scope`]: crate::event::profile::Scope
+                    #[inline]
+                    fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+                        let _ = scope;
+                    }
+
+                    /// Called when the transport exits a hot path entered with `on_profile_scope_entered`
+                    #[inline]
+                    fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+                        let _ = scope;
+                    }
+
+                    /// Called when the information passed to `create_connection_context` is updated
+                    ///
+                    /// Servers only learn the server name requested by the client once the ClientHello is
+                    /// processed, at which point this is called with the server name filled in. Unlike
+                    /// connection events, this is called regardless of `is_connection_sampled`, which allows
+                    /// [`Sampled`] subscribers to select the connection once the server name is known.
+                    #[inline]
+                    fn on_connection_info_updated(&mut self, context: &mut Self::ConnectionContext, meta: &ConnectionMeta, info: &ConnectionInfo) {
+                        let _ = context;
+                        let _ = meta;
+                        let _ = info;
+                    }
+
                     #subscriber
 
                     /// Called for each event that relates to the endpoint and all connections
@@ -344,6 +405,11 @@ impl ToTokens for Output {
                         (self.0.create_connection_context(meta, info), self.1.create_connection_context(meta, info))
                     }
 
+                    #[inline]
+                    fn is_connection_sampled(context: &Self::ConnectionContext) -> bool {
+                        A::is_connection_sampled(&context.0) || B::is_connection_sampled(&context.1)
+                    }
+
                     #[inline]
                     fn supervisor_timeout(&mut self, conn_context: &mut Self::ConnectionContext, meta: &ConnectionMeta, context: &supervisor::Context) -> Option<Duration> {
                         let timeout_a = self.0.supervisor_timeout(&mut conn_context.0, meta, context);
@@ -366,6 +432,36 @@ impl ToTokens for Output {
                         }
                     }
 
+                    #[inline]
+                    fn connection_verdict(&mut self, conn_context: &mut Self::ConnectionContext, meta: &ConnectionMeta) -> supervisor::Outcome {
+                        let outcome_a = self.0.connection_verdict(&mut conn_context.0, meta);
+                        let outcome_b = self.1.connection_verdict(&mut conn_context.1, meta);
+                        match (outcome_a, outcome_b) {
+                            (supervisor::Outcome::ImmediateClose { reason }, _) | (_, supervisor::Outcome::ImmediateClose { reason }) => supervisor::Outcome::ImmediateClose { reason },
+                            (supervisor::Outcome::Close { error_code }, _) | (_, supervisor::Outcome::Close { error_code }) => supervisor::Outcome::Close { error_code },
+                            _ => supervisor::Outcome::Continue,
+                        }
+                    }
+
+                    #[inline]
+                    fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+                        self.0.on_profile_scope_entered(scope);
+                        self.1.on_profile_scope_entered(scope);
+                    }
+
+                    #[inline]
+                    fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+                        // exit in the reverse order so each subscriber's measurement excludes the other
+                        self.1.on_profile_scope_exited(scope);
+                        self.0.on_profile_scope_exited(scope);
+                    }
+
+                    #[inline]
+                    fn on_connection_info_updated(&mut self, context: &mut Self::ConnectionContext, meta: &ConnectionMeta, info: &ConnectionInfo) {
+                        self.0.on_connection_info_updated(&mut context.0, meta, info);
+                        self.1.on_connection_info_updated(&mut context.1, meta, info);
+                    }
+
                     #tuple_subscriber
 
                     #[inline]
@@ -395,11 +491,175 @@ impl ToTokens for Output {
                     }
                 }
 
+                /// Selects the connections observed by a [`Sampled`] subscriber
+                pub trait Sampler: 'static + Send {
+                    /// Returns `true` if the connection should be observed
+                    fn sample(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> bool;
+
+                    /// Returns `true` if a connection which wasn't selected should be observed after its
+                    /// information was updated
+                    ///
+                    /// This is called when a server learns the server name requested by the client. The
+                    /// subscriber only observes the events published after that point. Returns `false` by
+                    /// default, which keeps the decision made by `sample`.
+                    #[inline]
+                    fn sample_updated(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> bool {
+                        let _ = meta;
+                        let _ = info;
+                        false
+                    }
+                }
+
+                impl<F> Sampler for F
+                    where
+                        F: 'static + Send + FnMut(&ConnectionMeta, &ConnectionInfo) -> bool,
+                {
+                    #[inline]
+                    fn sample(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> bool {
+                        (self)(meta, info)
+                    }
+                }
+
+                /// A subscriber which only observes the connections selected by a [`Sampler`]
+                ///
+                /// The sampling decision is made once per connection, in `create_connection_context`.
+                /// Connection events for connections that were not sampled are not passed to the
+                /// wrapped subscriber. Endpoint events are always forwarded, as are calls to
+                /// [`Subscriber::on_event`] if another composed subscriber observes the connection.
+                #[derive(Clone, Debug, Default)]
+                pub struct Sampled<S, P> {
+                    subscriber: S,
+                    sampler: P,
+                }
+
+                impl<S, P> Sampled<S, P> {
+                    /// Wraps `subscriber` so it only observes connections selected by `sampler`
+                    #[inline]
+                    pub fn new(subscriber: S, sampler: P) -> Self {
+                        Self { subscriber, sampler }
+                    }
+
+                    /// Returns a reference to the wrapped subscriber
+                    #[inline]
+                    pub fn subscriber(&self) -> &S {
+                        &self.subscriber
+                    }
+
+                    /// Returns a mutable reference to the wrapped subscriber
+                    #[inline]
+                    pub fn subscriber_mut(&mut self) -> &mut S {
+                        &mut self.subscriber
+                    }
+                }
+
+                impl<S, P> Subscriber for Sampled<S, P>
+                    where
+                        S: Subscriber,
+                        P: Sampler,
+                {
+                    type ConnectionContext = Option<S::ConnectionContext>;
+
+                    #[inline]
+                    fn create_connection_context(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> Self::ConnectionContext {
+                        if self.sampler.sample(meta, info) {
+                            Some(self.subscriber.create_connection_context(meta, info))
+                        } else {
+                            None
+                        }
+                    }
+
+                    #[inline]
+                    fn is_connection_sampled(context: &Self::ConnectionContext) -> bool {
+                        context.as_ref().map_or(false, S::is_connection_sampled)
+                    }
+
+                    #[inline]
+                    fn supervisor_timeout(&mut self, conn_context: &mut Self::ConnectionContext, meta: &ConnectionMeta, context: &supervisor::Context) -> Option<Duration> {
+                        let conn_context = conn_context.as_mut()?;
+                        self.subscriber.supervisor_timeout(conn_context, meta, context)
+                    }
+
+                    #[inline]
+                    fn on_supervisor_timeout(&mut self, conn_context: &mut Self::ConnectionContext, meta: &ConnectionMeta, context: &supervisor::Context) -> supervisor::Outcome {
+                        if let Some(conn_context) = conn_context.as_mut() {
+                            self.subscriber.on_supervisor_timeout(conn_context, meta, context)
+                        } else {
+                            supervisor::Outcome::default()
+                        }
+                    }
+
+                    #[inline]
+                    fn connection_verdict(&mut self, conn_context: &mut Self::ConnectionContext, meta: &ConnectionMeta) -> supervisor::Outcome {
+                        if let Some(conn_context) = conn_context.as_mut() {
+                            self.subscriber.connection_verdict(conn_context, meta)
+                        } else {
+                            supervisor::Outcome::default()
+                        }
+                    }
+
+                    #[inline]
+                    fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+                        self.subscriber.on_profile_scope_entered(scope);
+                    }
+
+                    #[inline]
+                    fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+                        self.subscriber.on_profile_scope_exited(scope);
+                    }
+
+                    #[inline]
+                    fn on_connection_info_updated(&mut self, context: &mut Self::ConnectionContext, meta: &ConnectionMeta, info: &ConnectionInfo) {
+                        if let Some(context) = context.as_mut() {
+                            self.subscriber.on_connection_info_updated(context, meta, info);
+                        } else if self.sampler.sample_updated(meta, info) {
+                            *context = Some(self.subscriber.create_connection_context(meta, info));
+                        }
+                    }
+
+                    #sampled_subscriber
+
+                    #[inline]
+                    fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
+                        self.subscriber.on_event(meta, event);
+                    }
+
+                    #[inline]
+                    fn on_connection_event<E: Event>(&mut self, context: &mut Self::ConnectionContext, meta: &ConnectionMeta, event: &E) {
+                        if let Some(context) = context.as_mut() {
+                            self.subscriber.on_connection_event(context, meta, event);
+                        }
+                    }
+
+                    #[inline]
+                    fn query(context: &Self::ConnectionContext, query: &mut dyn query::Query) -> query::ControlFlow {
+                        query.execute(context)
+                            .and_then(|| match context {
+                                Some(context) => S::query(context, query),
+                                None => query::ControlFlow::Continue,
+                            })
+                    }
+
+                    #[inline]
+                    fn query_mut(context: &mut Self::ConnectionContext, query: &mut dyn query::QueryMut) -> query::ControlFlow {
+                        query.execute_mut(context)
+                            .and_then(|| match context {
+                                Some(context) => S::query_mut(context, query),
+                                None => query::ControlFlow::Continue,
+                            })
+                    }
+                }
+
                 pub trait EndpointPublisher {
                     #endpoint_publisher
 
                     /// Returns the QUIC version, if any
                     fn quic_version(&self) -> Option<u32>;
+
+                    /// Returns the connection attempt which published events are associated with, if any
+                    fn attempt_id(&self) -> Option<u64>;
+
+                    /// Associates the events published after this call with a connection attempt
+                    fn set_attempt_id(&mut self, attempt_id: Option<u64>);
                 }
 
                 pub struct EndpointPublisherSubscriber<'a, Sub: Subscriber> {
@@ -439,6 +699,16 @@ impl ToTokens for Output {
                     fn quic_version(&self) -> Option<u32> {
                         self.quic_version
                     }
+
+                    #[inline]
+                    fn attempt_id(&self) -> Option<u64> {
+                        self.meta.attempt_id
+                    }
+
+                    #[inline]
+                    fn set_attempt_id(&mut self, attempt_id: Option<u64>) {
+                        self.meta.attempt_id = attempt_id;
+                    }
                 }
 
                 pub trait ConnectionPublisher {
@@ -449,10 +719,19 @@ impl ToTokens for Output {
 
                     /// Returns the [`Subject`] for the current publisher
                     fn subject(&self) -> Subject;
+
+                    /// Notifies the subscriber that a profiled hot path was entered
+                    fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope);
+
+                    /// Notifies the subscriber that a profiled hot path was exited
+                    fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope);
+
+                    /// Notifies the subscriber that the connection information was updated
+                    fn on_connection_info_updated(&mut self, info: builder::ConnectionInfo);
                 }
 
                 pub struct ConnectionPublisherSubscriber<'a, Sub: Subscriber> {
-                    meta: ConnectionMeta,
+                    meta: ConnectionMeta<'a>,
                     quic_version: u32,
                     subscriber: &'a mut Sub,
                     context: &'a mut Sub::ConnectionContext,
@@ -470,7 +749,7 @@ impl ToTokens for Output {
                 impl<'a, Sub: Subscriber> ConnectionPublisherSubscriber<'a, Sub> {
                     #[inline]
                     pub fn new(
-                        meta: builder::ConnectionMeta,
+                        meta: builder::ConnectionMeta<'a>,
                         quic_version: u32,
                         subscriber: &'a mut Sub,
                         context: &'a mut Sub::ConnectionContext
@@ -496,6 +775,22 @@ impl ToTokens for Output {
                     fn subject(&self) -> api::Subject {
                         self.meta.subject()
                     }
+
+                    #[inline]
+                    fn on_profile_scope_entered(&mut self, scope: crate::event::profile::Scope) {
+                        self.subscriber.on_profile_scope_entered(scope);
+                    }
+
+                    #[inline]
+                    fn on_profile_scope_exited(&mut self, scope: crate::event::profile::Scope) {
+                        self.subscriber.on_profile_scope_exited(scope);
+                    }
+
+                    #[inline]
+                    fn on_connection_info_updated(&mut self, info: builder::ConnectionInfo) {
+                        let info = info.into_event();
+                        self.subscriber.on_connection_info_updated(self.context, &self.meta, &info);
+                    }
                 }
             }
 
@@ -554,6 +849,7 @@ impl ToTokens for Output {
                 pub struct Publisher {
                     location: Option<Location>,
                     output: Vec<String>,
+                    attempt_id: Option<u64>,
                     #testing_fields
                 }
 
@@ -571,6 +867,7 @@ impl ToTokens for Output {
                         Self {
                             location: None,
                             output: Default::default(),
+                            attempt_id: None,
                             #testing_fields_init
                         }
                     }
@@ -582,6 +879,14 @@ impl ToTokens for Output {
                     fn quic_version(&self) -> Option<u32> {
                         Some(1)
                     }
+
+                    fn attempt_id(&self) -> Option<u64> {
+                        self.attempt_id
+                    }
+
+                    fn set_attempt_id(&mut self, attempt_id: Option<u64>) {
+                        self.attempt_id = attempt_id;
+                    }
                 }
 
                 impl super::ConnectionPublisher for Publisher {
@@ -594,6 +899,12 @@ impl ToTokens for Output {
                     fn subject(&self) -> api::Subject {
                         api::Subject::Connection { id: 0 }
                     }
+
+                    fn on_profile_scope_entered(&mut self, _scope: crate::event::profile::Scope) {}
+
+                    fn on_profile_scope_exited(&mut self, _scope: crate::event::profile::Scope) {}
+
+                    fn on_connection_info_updated(&mut self, _info: builder::ConnectionInfo) {}
                 }
 
                 impl Drop for Publisher {
@@ -178,6 +178,14 @@ impl Struct {
                         }
                     ));
 
+                    output.sampled_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&mut self, meta: &EndpointMeta, event: &#ident) {
+                            self.subscriber.#function(meta, event);
+                        }
+                    ));
+
                     output.tracing_subscriber.extend(quote!(
                         #[inline]
                         #allow_deprecated
@@ -191,7 +199,7 @@ impl Struct {
                                 }
                             };
                             let api::#ident { #(#destructure_fields),* } = event;
-                            tracing::event!(target: #snake, parent: parent, tracing::Level::DEBUG, #(#destructure_fields = tracing::field::debug(#destructure_fields)),*);
+                            tracing::event!(target: #snake, parent: parent, tracing::Level::DEBUG, attempt_id = tracing::field::debug(meta.attempt_id), #(#destructure_fields = tracing::field::debug(#destructure_fields)),*);
                         }
                     ));
 
@@ -249,6 +257,16 @@ impl Struct {
                         }
                     ));
 
+                    output.sampled_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&mut self, context: &mut Self::ConnectionContext, meta: &ConnectionMeta, event: &#ident) {
+                            if let Some(context) = context.as_mut() {
+                                self.subscriber.#function(context, meta, event);
+                            }
+                        }
+                    ));
+
                     output.tracing_subscriber.extend(quote!(
                         #[inline]
                         #allow_deprecated
@@ -268,6 +286,10 @@ impl Struct {
                         #[inline]
                         #allow_deprecated
                         fn #function(&mut self, event: builder::#ident) {
+                            if !Sub::is_connection_sampled(self.context) {
+                                return;
+                            }
+
                             let event = event.into_event();
                             self.subscriber.#function(self.context, &self.meta, &event);
                             self.subscriber.on_connection_event(self.context, &self.meta, &event);
@@ -3,5 +3,16 @@
 
 mod s2n_quic;
 
+#[cfg(all(test, feature = "webtransport"))]
+mod tests;
+
 pub use self::s2n_quic::*;
 pub use h3;
+
+/// WebTransport sessions over HTTP/3
+///
+/// The [`Connection`] implements the datagram and unframed stream extensions required by
+/// [`h3_webtransport`](https://docs.rs/h3-webtransport), which handles session establishment
+/// with extended CONNECT and maps WebTransport streams and datagrams onto the QUIC connection.
+#[cfg(feature = "webtransport")]
+pub use h3_webtransport as webtransport;
@@ -244,6 +244,19 @@ where
     }
 }
 
+impl<B> quic::SendStreamUnframed<B> for BidiStream<B>
+where
+    B: Buf,
+{
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, Self::Error>> {
+        self.send.poll_send(cx, buf)
+    }
+}
+
 impl<B> From<BidirectionalStream> for BidiStream<B>
 where
     B: Buf,
@@ -445,6 +458,31 @@ where
     }
 }
 
+impl<B> quic::SendStreamUnframed<B> for SendStream<B>
+where
+    B: Buf,
+{
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, Self::Error>> {
+        // flush any framed data first so the unframed bytes are written after it
+        ready!(quic::SendStream::poll_ready(self, cx))?;
+
+        let len = buf.chunk().len();
+        if len == 0 {
+            return Poll::Ready(Ok(0));
+        }
+
+        let available = ready!(self.stream.poll_send_ready(cx))?;
+        let len = len.min(available);
+        self.stream.send_data(buf.copy_to_bytes(len))?;
+
+        Poll::Ready(Ok(len))
+    }
+}
+
 impl<B> From<s2n_quic::stream::SendStream> for SendStream<B>
 where
     B: Buf,
@@ -504,3 +542,109 @@ impl From<SendStreamError> for Arc<dyn Error> {
         Arc::new(e)
     }
 }
+
+#[cfg(feature = "webtransport")]
+mod datagram {
+    use super::*;
+    use h3::ext::Datagram;
+    use s2n_quic::provider::datagram::default;
+
+    /// Sends HTTP/3 datagrams with the connection's default datagram sender
+    ///
+    /// The server must be started with the default datagram endpoint from
+    /// `s2n_quic::provider::datagram::default` for datagrams to be negotiated.
+    impl<B> quic::SendDatagramExt<B> for Connection
+    where
+        B: Buf,
+    {
+        type Error = DatagramError;
+
+        fn send_datagram(&mut self, data: Datagram<B>) -> Result<(), Self::Error> {
+            let mut buf = bytes::BytesMut::new();
+            data.encode(&mut buf);
+            let buf = buf.freeze();
+
+            self.conn
+                .datagram_mut(|sender: &mut default::Sender| sender.send_datagram(buf))??;
+
+            Ok(())
+        }
+    }
+
+    impl quic::RecvDatagramExt for Connection {
+        type Buf = Bytes;
+        type Error = DatagramError;
+
+        fn poll_accept_datagram(
+            &mut self,
+            cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+            let datagram = self
+                .conn
+                .datagram_mut(|receiver: &mut default::Receiver| receiver.poll_recv_datagram(cx))?;
+            let datagram = ready!(datagram)?;
+            Poll::Ready(Ok(Some(datagram)))
+        }
+    }
+
+    #[derive(Debug)]
+    pub enum DatagramError {
+        /// The connection's datagram endpoint couldn't be queried
+        Query(s2n_quic_core::query::Error),
+        Datagram(default::DatagramError),
+    }
+
+    impl std::error::Error for DatagramError {}
+
+    impl Display for DatagramError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Query(error) => error.fmt(f),
+                Self::Datagram(error) => error.fmt(f),
+            }
+        }
+    }
+
+    impl From<s2n_quic_core::query::Error> for DatagramError {
+        fn from(e: s2n_quic_core::query::Error) -> Self {
+            Self::Query(e)
+        }
+    }
+
+    impl From<default::DatagramError> for DatagramError {
+        fn from(e: default::DatagramError) -> Self {
+            Self::Datagram(e)
+        }
+    }
+
+    impl Error for DatagramError {
+        fn is_timeout(&self) -> bool {
+            matches!(
+                self,
+                Self::Datagram(default::DatagramError::ConnectionError {
+                    error: s2n_quic::connection::Error::IdleTimerExpired { .. },
+                    ..
+                })
+            )
+        }
+
+        fn err_code(&self) -> Option<u64> {
+            match self {
+                Self::Datagram(default::DatagramError::ConnectionError {
+                    error: s2n_quic::connection::Error::Application { error, .. },
+                    ..
+                }) => Some((*error).into()),
+                _ => None,
+            }
+        }
+    }
+
+    impl From<DatagramError> for Arc<dyn Error> {
+        fn from(e: DatagramError) -> Self {
+            Arc::new(e)
+        }
+    }
+}
+
+#[cfg(feature = "webtransport")]
+pub use datagram::DatagramError;
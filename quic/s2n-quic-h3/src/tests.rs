@@ -0,0 +1,129 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    h3::{self, ext::Protocol, quic::BidiStream as _},
+    webtransport::server::{AcceptedBi, WebTransportSession},
+    Connection,
+};
+use bytes::Bytes;
+use futures::{future::poll_fn, AsyncReadExt as _, AsyncWriteExt as _};
+use s2n_quic::{client::Connect, provider::datagram::default::Endpoint, Client, Server};
+use s2n_quic_core::crypto::tls::testing::certificates;
+
+fn datagram_endpoint() -> Endpoint {
+    Endpoint::builder()
+        .with_send_capacity(200)
+        .unwrap()
+        .with_recv_capacity(200)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Opens a WebTransport session and echoes a bidirectional stream through it
+#[tokio::test]
+async fn webtransport_bidi_stream_test() {
+    let mut server = Server::builder()
+        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))
+        .unwrap()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_datagram(datagram_endpoint())
+        .unwrap()
+        .start()
+        .unwrap();
+    let addr = server.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let connection = server.accept().await.unwrap();
+        let mut h3: h3::server::Connection<Connection, Bytes> = h3::server::builder()
+            .enable_webtransport(true)
+            .enable_connect(true)
+            .enable_datagram(true)
+            .max_webtransport_sessions(1)
+            .build(Connection::new(connection))
+            .await
+            .unwrap();
+
+        let (request, stream) = h3.accept().await.unwrap().unwrap();
+        assert_eq!(request.method(), http::Method::CONNECT);
+        assert_eq!(
+            request.extensions().get::<Protocol>(),
+            Some(&Protocol::WEB_TRANSPORT)
+        );
+
+        let session = WebTransportSession::accept(request, stream, h3)
+            .await
+            .unwrap();
+
+        let stream = match session.accept_bi().await.unwrap() {
+            Some(AcceptedBi::BidiStream(_session_id, stream)) => stream,
+            _ => panic!("expected a WebTransport bidirectional stream"),
+        };
+
+        // echo the stream back to the client
+        let (mut send, mut recv) = stream.split();
+        let mut message = vec![];
+        recv.read_to_end(&mut message).await.unwrap();
+        send.write_all(&message).await.unwrap();
+        send.close().await.unwrap();
+
+        // keep the session open until the client closes the connection
+        let _ = session.accept_bi().await;
+    });
+
+    let client = Client::builder()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .with_io("0.0.0.0:0")
+        .unwrap()
+        .with_datagram(datagram_endpoint())
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let connect = Connect::new(addr).with_server_name("localhost");
+    let connection = client.connect(connect).await.unwrap();
+    let mut handle = connection.handle();
+
+    let (mut driver, mut send_request) = h3::client::builder()
+        .enable_webtransport(true)
+        .enable_extended_connect(true)
+        .enable_datagram(true)
+        .max_webtransport_sessions(1)
+        .build::<_, _, Bytes>(Connection::new(connection))
+        .await
+        .unwrap();
+    tokio::spawn(async move { poll_fn(|cx| driver.poll_close(cx)).await });
+
+    let request = http::Request::builder()
+        .method(http::Method::CONNECT)
+        .uri("https://localhost/session")
+        .extension(Protocol::WEB_TRANSPORT)
+        .body(())
+        .unwrap();
+    let mut session = send_request.send_request(request).await.unwrap();
+    let response = session.recv_response().await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+
+    // The h3 client doesn't implement WebTransport streams so the stream header is written
+    // directly to the QUIC stream. The header is the WT_STREAM signal value (0x41) followed by
+    // the session ID, which is the ID of the CONNECT request stream (0).
+    let mut stream = handle.open_bidirectional_stream().await.unwrap();
+    stream
+        .send(Bytes::from_static(&[0x40, 0x41, 0x00]))
+        .await
+        .unwrap();
+    stream.send(Bytes::from_static(b"hello")).await.unwrap();
+    stream.finish().unwrap();
+
+    let mut echoed = vec![];
+    while let Some(chunk) = stream.receive().await.unwrap() {
+        echoed.extend_from_slice(&chunk);
+    }
+    assert_eq!(echoed, b"hello");
+
+    handle.close(0u8.into());
+    server.await.unwrap();
+}
@@ -1,6 +1,12 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(any(test, feature = "tx-fuzz"))]
+pub mod fuzz;
+
+#[cfg(feature = "std")]
+pub mod mirror;
+
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
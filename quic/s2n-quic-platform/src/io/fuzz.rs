@@ -0,0 +1,366 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mutates outgoing datagrams after they've been encrypted
+//!
+//! This is intended for validating the robustness of peers, as well as the endpoint's own loss
+//! recovery, in integration tests. It must never be enabled in production deployments.
+
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
+use s2n_quic_core::{
+    event,
+    inet::{ExplicitCongestionNotification, SocketAddress},
+    io::tx,
+    path::{Handle as _, MaxMtu},
+};
+
+/// The action applied to an outgoing datagram
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Action {
+    /// Transmits the datagram without any modifications
+    #[default]
+    Forward,
+    /// Discards the datagram without transmitting it
+    Drop,
+    /// Shortens the datagram to at most `len` bytes
+    ///
+    /// Truncating a datagram to zero bytes is equivalent to dropping it.
+    Truncate { len: usize },
+    /// Flips the bits set in `mask` for the byte at `index`
+    ///
+    /// The index wraps around the length of the datagram.
+    FlipBits { index: usize, mask: u8 },
+}
+
+impl Action {
+    /// Applies the action to the payload and returns the new length, or `None` if the datagram
+    /// should be dropped
+    #[inline]
+    fn apply(self, payload: &mut [u8]) -> Option<usize> {
+        let len = payload.len();
+        match self {
+            Self::Forward => Some(len),
+            Self::Drop => None,
+            Self::Truncate { len: max_len } => Some(max_len.min(len)).filter(|len| *len > 0),
+            Self::FlipBits { index, mask } => {
+                if let Some(byte) = payload.get_mut(index % len.max(1)) {
+                    *byte ^= mask;
+                }
+                Some(len)
+            }
+        }
+    }
+}
+
+/// Selects the action to apply to each outgoing datagram
+pub trait Policy: 'static + Send {
+    /// Called with the encrypted payload of each datagram before it's transmitted
+    ///
+    /// Coalesced packets are passed as a single datagram.
+    fn on_datagram(&mut self, remote_address: &SocketAddress, payload: &[u8]) -> Action;
+}
+
+impl<F> Policy for F
+where
+    F: 'static + Send + FnMut(&SocketAddress, &[u8]) -> Action,
+{
+    #[inline]
+    fn on_datagram(&mut self, remote_address: &SocketAddress, payload: &[u8]) -> Action {
+        (self)(remote_address, payload)
+    }
+}
+
+/// Wraps a TX channel and applies the policy to each datagram pushed into it
+///
+/// If no policy is configured, messages are passed through without any additional copies.
+pub struct Tx<T> {
+    tx: T,
+    policy: Option<Box<dyn Policy>>,
+    buffer: Box<[u8]>,
+}
+
+impl<T> Tx<T> {
+    #[inline]
+    pub fn new(tx: T, policy: Option<Box<dyn Policy>>, max_mtu: MaxMtu) -> Self {
+        // only allocate a buffer if we're going to use it
+        let buffer_len = if policy.is_some() { max_mtu.into() } else { 0 };
+
+        Self {
+            tx,
+            policy,
+            buffer: vec![0; buffer_len].into_boxed_slice(),
+        }
+    }
+}
+
+impl<T> tx::Tx for Tx<T>
+where
+    T: tx::Tx,
+    T::Queue: 'static,
+{
+    type PathHandle = T::PathHandle;
+    type Queue = Queue<'static, T::Queue>;
+    type Error = T::Error;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.tx.poll_ready(cx)
+    }
+
+    #[inline]
+    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+        let policy = &mut self.policy;
+        let buffer = &mut self.buffer;
+        self.tx.queue(|tx| {
+            let (policy, buffer, tx): (&'static mut _, &'static mut _, &'static mut _) = unsafe {
+                // Safety: As noted in the [transmute examples](https://doc.rust-lang.org/std/mem/fn.transmute.html#examples)
+                // it can be used to temporarily extend the lifetime of a reference. In this case, we
+                // don't want to use GATs until the MSRV is >=1.65.0, which means `Self::Queue` is not
+                // allowed to take generic lifetimes.
+                //
+                // We are left with using a `'static` lifetime here and encapsulating it in a private
+                // field. The `Self::Queue` struct is then borrowed for the lifetime of the `F`
+                // function. This will prevent the value from escaping beyond the lifetime of `&mut
+                // self`.
+                //
+                // See https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=9a32abe85c666f36fb2ec86496cc41b4
+                //
+                // Once https://github.com/aws/s2n-quic/issues/1742 is resolved this code can go away
+                (
+                    core::mem::transmute(policy),
+                    core::mem::transmute(buffer),
+                    core::mem::transmute(tx),
+                )
+            };
+
+            let mut queue = Queue { tx, policy, buffer };
+            f(&mut queue);
+        });
+    }
+
+    #[inline]
+    fn handle_error<E: event::EndpointPublisher>(self, error: Self::Error, events: &mut E) {
+        self.tx.handle_error(error, events)
+    }
+}
+
+pub struct Queue<'a, Q> {
+    tx: &'a mut Q,
+    policy: &'a mut Option<Box<dyn Policy>>,
+    buffer: &'a mut Box<[u8]>,
+}
+
+impl<'a, Q: tx::Queue> tx::Queue for Queue<'a, Q> {
+    type Handle = Q::Handle;
+
+    const SUPPORTS_ECN: bool = Q::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Q::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Q::SUPPORTS_FLOW_LABELS;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        let policy = if let Some(policy) = self.policy.as_mut() {
+            policy
+        } else {
+            return self.tx.push(message);
+        };
+
+        // writing the payload consumes the packet so make sure there's room for it first
+        if !self.tx.has_capacity() {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        // write the encrypted payload into the scratch buffer so the policy can inspect and
+        // modify it before it's queued
+        let len = message.write_payload(tx::PayloadBuffer::new(&mut self.buffer[..]), 0)?;
+
+        if len == 0 {
+            return Err(tx::Error::EmptyPayload);
+        }
+
+        let remote_address = message.path_handle().remote_address();
+        let payload = &mut self.buffer[..len];
+        let action = policy.on_datagram(&remote_address, payload);
+
+        let len = match action.apply(payload) {
+            Some(len) => len,
+            // report the datagram as sent so the endpoint treats it as lost in the network
+            None => return Ok(tx::Outcome { len, index: 0 }),
+        };
+
+        self.tx.push(Datagram {
+            message,
+            payload: &self.buffer[..len],
+        })
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.tx.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.tx.capacity()
+    }
+
+    #[inline]
+    fn has_capacity(&self) -> bool {
+        self.tx.has_capacity()
+    }
+}
+
+/// A datagram which has already been written by the endpoint
+struct Datagram<'a, M> {
+    message: M,
+    payload: &'a [u8],
+}
+
+impl<'a, M: tx::Message> tx::Message for Datagram<'a, M> {
+    type Handle = M::Handle;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        self.message.path_handle()
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        self.message.ecn()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> Duration {
+        self.message.delay()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        self.message.ipv6_flow_label()
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool {
+        segment_len >= self.payload.len() && self.message.can_gso(segment_len, segment_count)
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(self.payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        io::tx::{Queue as _, Tx as _},
+        path::RemoteAddress,
+    };
+
+    /// Records the payload of each transmitted message
+    #[derive(Default)]
+    struct Recorder(Vec<Vec<u8>>);
+
+    impl tx::Tx for Recorder {
+        type PathHandle = RemoteAddress;
+        type Queue = Recorder;
+        type Error = ();
+
+        fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Pending
+        }
+
+        fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+            f(self)
+        }
+
+        fn handle_error<E: event::EndpointPublisher>(self, _error: Self::Error, _events: &mut E) {}
+    }
+
+    impl tx::Queue for Recorder {
+        type Handle = RemoteAddress;
+
+        fn push<M: tx::Message<Handle = Self::Handle>>(
+            &mut self,
+            mut message: M,
+        ) -> Result<tx::Outcome, tx::Error> {
+            let mut payload = vec![0; 1500];
+            let len = message.write_payload(tx::PayloadBuffer::new(&mut payload), 0)?;
+            payload.truncate(len);
+            self.0.push(payload);
+            Ok(tx::Outcome {
+                len,
+                index: self.0.len() - 1,
+            })
+        }
+
+        fn capacity(&self) -> usize {
+            usize::MAX
+        }
+    }
+
+    fn transmit(action: Action, payload: &[u8]) -> Option<Vec<u8>> {
+        let policy = move |_: &SocketAddress, _: &[u8]| action;
+        let mut tx = Tx::new(
+            Recorder::default(),
+            Some(Box::new(policy)),
+            MaxMtu::default(),
+        );
+
+        tx.queue(|queue| {
+            let outcome = queue.push((RemoteAddress::default(), payload)).unwrap();
+            // the endpoint should observe the original length, regardless of the action
+            assert_eq!(outcome.len, payload.len());
+        });
+
+        tx.tx.0.pop()
+    }
+
+    #[test]
+    fn action_test() {
+        let payload = [1u8, 2, 3, 4];
+
+        assert_eq!(transmit(Action::Forward, &payload), Some(payload.to_vec()));
+        assert_eq!(transmit(Action::Drop, &payload), None);
+        assert_eq!(
+            transmit(Action::Truncate { len: 2 }, &payload),
+            Some(vec![1, 2])
+        );
+        assert_eq!(transmit(Action::Truncate { len: 0 }, &payload), None);
+        assert_eq!(
+            transmit(
+                Action::FlipBits {
+                    index: 5,
+                    mask: 0b1000_0001
+                },
+                &payload
+            ),
+            Some(vec![1, 0b1000_0011, 3, 4])
+        );
+    }
+
+    #[test]
+    fn passthrough_test() {
+        let mut tx = Tx::new(Recorder::default(), None, MaxMtu::default());
+
+        tx.queue(|queue| {
+            queue
+                .push((RemoteAddress::default(), &[1, 2, 3][..]))
+                .unwrap();
+        });
+
+        assert_eq!(tx.tx.0.pop().unwrap(), vec![1, 2, 3]);
+    }
+}
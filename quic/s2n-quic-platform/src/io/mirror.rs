@@ -0,0 +1,638 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Copies encrypted datagrams to an application-provided channel
+//!
+//! This allows security appliances, such as intrusion detection systems, to observe the traffic
+//! of an endpoint without running a separate packet capture stack. Records are delivered with
+//! [`SyncSender::try_send`] so a slow consumer never blocks the endpoint. Any records that don't
+//! fit in the channel are discarded and counted by [`Tap::dropped`].
+
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
+use s2n_quic_core::{
+    event,
+    inet::{datagram, ExplicitCongestionNotification, SocketAddress},
+    io::{rx, tx},
+    path::{self, MaxMtu},
+};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{sync_channel, Receiver, SyncSender},
+        Arc,
+    },
+    time::SystemTime,
+};
+
+/// The direction a mirrored datagram was traveling
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The datagram was received from the peer
+    Ingress,
+    /// The datagram was transmitted to the peer
+    Egress,
+}
+
+/// A copy of a datagram sent or received by the endpoint
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Record {
+    pub direction: Direction,
+    /// The time the datagram was copied
+    pub timestamp: SystemTime,
+    pub local_address: SocketAddress,
+    pub remote_address: SocketAddress,
+    pub ecn: ExplicitCongestionNotification,
+    /// The length of the datagram, before truncation
+    pub len: usize,
+    /// The encrypted contents of the datagram, truncated to the tap's snap length
+    pub payload: Vec<u8>,
+}
+
+/// Creates a tap and the receiving end of its channel
+///
+/// At most `bound` records are buffered in the channel before new records are dropped.
+pub fn channel(bound: usize) -> (Tap, Receiver<Record>) {
+    let (sender, receiver) = sync_channel(bound);
+    (Tap::new(sender), receiver)
+}
+
+/// Configures which datagrams are copied and where they are sent
+///
+/// By default, every datagram in both directions is copied in full.
+#[derive(Clone, Debug)]
+pub struct Tap {
+    sender: SyncSender<Record>,
+    sample_interval: u32,
+    snap_len: usize,
+    ingress: bool,
+    egress: bool,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Tap {
+    pub fn new(sender: SyncSender<Record>) -> Self {
+        Self {
+            sender,
+            sample_interval: 1,
+            snap_len: usize::MAX,
+            ingress: true,
+            egress: true,
+            dropped: Default::default(),
+        }
+    }
+
+    /// Copies one out of every `interval` datagrams in each direction
+    ///
+    /// An interval of `0` or `1` copies every datagram.
+    #[must_use]
+    pub fn with_sample_interval(mut self, interval: u32) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+
+    /// Truncates each copy to at most `len` bytes
+    ///
+    /// The original length of the datagram is still reported in [`Record::len`].
+    #[must_use]
+    pub fn with_snap_len(mut self, len: usize) -> Self {
+        self.snap_len = len;
+        self
+    }
+
+    /// Sets whether datagrams received from peers are copied
+    #[must_use]
+    pub fn with_ingress(mut self, enabled: bool) -> Self {
+        self.ingress = enabled;
+        self
+    }
+
+    /// Sets whether datagrams transmitted to peers are copied
+    #[must_use]
+    pub fn with_egress(mut self, enabled: bool) -> Self {
+        self.egress = enabled;
+        self
+    }
+
+    /// Returns the number of records which were discarded because the channel was full or closed
+    #[inline]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn is_enabled(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Ingress => self.ingress,
+            Direction::Egress => self.egress,
+        }
+    }
+}
+
+/// The per-direction sampling state of a tap
+struct State {
+    tap: Tap,
+    direction: Direction,
+    count: u32,
+}
+
+impl State {
+    #[inline]
+    fn new(tap: Option<Tap>, direction: Direction) -> Option<Self> {
+        let tap = tap.filter(|tap| tap.is_enabled(direction))?;
+        Some(Self {
+            tap,
+            direction,
+            count: 0,
+        })
+    }
+
+    /// Returns `true` if the next datagram should be copied
+    #[inline]
+    fn sample(&mut self) -> bool {
+        self.count += 1;
+        if self.count < self.tap.sample_interval {
+            return false;
+        }
+        self.count = 0;
+        true
+    }
+
+    #[inline]
+    fn mirror<H: path::Handle>(
+        &self,
+        handle: &H,
+        ecn: ExplicitCongestionNotification,
+        payload: &[u8],
+    ) {
+        let len = payload.len();
+        let record = Record {
+            direction: self.direction,
+            timestamp: SystemTime::now(),
+            local_address: *handle.local_address(),
+            remote_address: *handle.remote_address(),
+            ecn,
+            len,
+            payload: payload[..len.min(self.tap.snap_len)].to_vec(),
+        };
+
+        // never block the endpoint on the consumer
+        if self.tap.sender.try_send(record).is_err() {
+            self.tap.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Wraps an RX channel and copies the received datagrams to the tap
+///
+/// If no tap is configured, datagrams are passed through without any additional copies.
+pub struct Rx<R> {
+    rx: R,
+    state: Option<State>,
+}
+
+impl<R> Rx<R> {
+    #[inline]
+    pub fn new(rx: R, tap: Option<Tap>) -> Self {
+        Self {
+            rx,
+            state: State::new(tap, Direction::Ingress),
+        }
+    }
+}
+
+impl<R> rx::Rx for Rx<R>
+where
+    R: rx::Rx,
+    R::Queue: 'static,
+{
+    type PathHandle = R::PathHandle;
+    type Queue = RxQueue<'static, R::Queue>;
+    type Error = R::Error;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.rx.poll_ready(cx)
+    }
+
+    #[inline]
+    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+        let state = &mut self.state;
+        self.rx.queue(|rx| {
+            let (state, rx): (&'static mut _, &'static mut _) = unsafe {
+                // Safety: As noted in the [transmute examples](https://doc.rust-lang.org/std/mem/fn.transmute.html#examples)
+                // it can be used to temporarily extend the lifetime of a reference. In this case, we
+                // don't want to use GATs until the MSRV is >=1.65.0, which means `Self::Queue` is not
+                // allowed to take generic lifetimes.
+                //
+                // We are left with using a `'static` lifetime here and encapsulating it in a private
+                // field. The `Self::Queue` struct is then borrowed for the lifetime of the `F`
+                // function. This will prevent the value from escaping beyond the lifetime of `&mut
+                // self`.
+                //
+                // See https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=9a32abe85c666f36fb2ec86496cc41b4
+                //
+                // Once https://github.com/aws/s2n-quic/issues/1742 is resolved this code can go away
+                (core::mem::transmute(state), core::mem::transmute(rx))
+            };
+
+            let mut queue = RxQueue { rx, state };
+            f(&mut queue);
+        });
+    }
+
+    #[inline]
+    fn handle_error<E: event::EndpointPublisher>(self, error: Self::Error, events: &mut E) {
+        self.rx.handle_error(error, events)
+    }
+}
+
+pub struct RxQueue<'a, Q> {
+    rx: &'a mut Q,
+    state: &'a mut Option<State>,
+}
+
+impl<'a, Q: rx::Queue> rx::Queue for RxQueue<'a, Q> {
+    type Handle = Q::Handle;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        let state = if let Some(state) = self.state.as_mut() {
+            state
+        } else {
+            return self.rx.for_each(on_packet);
+        };
+
+        self.rx.for_each(|header, payload| {
+            // copy the datagram before the endpoint decrypts it in place
+            if state.sample() {
+                state.mirror(&header.path, header.ecn, payload);
+            }
+            on_packet(header, payload)
+        });
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.rx.is_empty()
+    }
+}
+
+/// Wraps a TX channel and copies the transmitted datagrams to the tap
+///
+/// If no tap is configured, messages are passed through without any additional copies.
+pub struct Tx<T> {
+    tx: T,
+    state: Option<State>,
+    buffer: Box<[u8]>,
+}
+
+impl<T> Tx<T> {
+    #[inline]
+    pub fn new(tx: T, tap: Option<Tap>, max_mtu: MaxMtu) -> Self {
+        let state = State::new(tap, Direction::Egress);
+
+        // only allocate a buffer if we're going to use it
+        let buffer_len = if state.is_some() { max_mtu.into() } else { 0 };
+
+        Self {
+            tx,
+            state,
+            buffer: vec![0; buffer_len].into_boxed_slice(),
+        }
+    }
+}
+
+impl<T> tx::Tx for Tx<T>
+where
+    T: tx::Tx,
+    T::Queue: 'static,
+{
+    type PathHandle = T::PathHandle;
+    type Queue = TxQueue<'static, T::Queue>;
+    type Error = T::Error;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.tx.poll_ready(cx)
+    }
+
+    #[inline]
+    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+        let state = &mut self.state;
+        let buffer = &mut self.buffer;
+        self.tx.queue(|tx| {
+            let (state, buffer, tx): (&'static mut _, &'static mut _, &'static mut _) = unsafe {
+                // Safety: As noted in the [transmute examples](https://doc.rust-lang.org/std/mem/fn.transmute.html#examples)
+                // it can be used to temporarily extend the lifetime of a reference. In this case, we
+                // don't want to use GATs until the MSRV is >=1.65.0, which means `Self::Queue` is not
+                // allowed to take generic lifetimes.
+                //
+                // We are left with using a `'static` lifetime here and encapsulating it in a private
+                // field. The `Self::Queue` struct is then borrowed for the lifetime of the `F`
+                // function. This will prevent the value from escaping beyond the lifetime of `&mut
+                // self`.
+                //
+                // See https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=9a32abe85c666f36fb2ec86496cc41b4
+                //
+                // Once https://github.com/aws/s2n-quic/issues/1742 is resolved this code can go away
+                (
+                    core::mem::transmute(state),
+                    core::mem::transmute(buffer),
+                    core::mem::transmute(tx),
+                )
+            };
+
+            let mut queue = TxQueue { tx, state, buffer };
+            f(&mut queue);
+        });
+    }
+
+    #[inline]
+    fn handle_error<E: event::EndpointPublisher>(self, error: Self::Error, events: &mut E) {
+        self.tx.handle_error(error, events)
+    }
+}
+
+pub struct TxQueue<'a, Q> {
+    tx: &'a mut Q,
+    state: &'a mut Option<State>,
+    buffer: &'a mut Box<[u8]>,
+}
+
+impl<'a, Q: tx::Queue> tx::Queue for TxQueue<'a, Q> {
+    type Handle = Q::Handle;
+
+    const SUPPORTS_ECN: bool = Q::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Q::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Q::SUPPORTS_FLOW_LABELS;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        let state = if let Some(state) = self.state.as_mut() {
+            state
+        } else {
+            return self.tx.push(message);
+        };
+
+        // writing the payload consumes the packet so make sure there's room for it first
+        if !self.tx.has_capacity() {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        if !state.sample() {
+            return self.tx.push(message);
+        }
+
+        // write the encrypted payload into the scratch buffer so it can be copied to the tap
+        // before it's queued
+        let len = message.write_payload(tx::PayloadBuffer::new(&mut self.buffer[..]), 0)?;
+
+        if len == 0 {
+            return Err(tx::Error::EmptyPayload);
+        }
+
+        let payload = &self.buffer[..len];
+        let ecn = message.ecn();
+        state.mirror(message.path_handle(), ecn, payload);
+
+        self.tx.push(Datagram {
+            message,
+            ecn,
+            payload,
+        })
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.tx.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.tx.capacity()
+    }
+
+    #[inline]
+    fn has_capacity(&self) -> bool {
+        self.tx.has_capacity()
+    }
+}
+
+/// A datagram which has already been written by the endpoint
+struct Datagram<'a, M> {
+    message: M,
+    ecn: ExplicitCongestionNotification,
+    payload: &'a [u8],
+}
+
+impl<'a, M: tx::Message> tx::Message for Datagram<'a, M> {
+    type Handle = M::Handle;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        self.message.path_handle()
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        // the ECN marking was already read when the datagram was copied
+        self.ecn
+    }
+
+    #[inline]
+    fn delay(&mut self) -> Duration {
+        self.message.delay()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        self.message.ipv6_flow_label()
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool {
+        segment_len >= self.payload.len() && self.message.can_gso(segment_len, segment_count)
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(self.payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        io::{
+            rx::{Queue as _, Rx as _},
+            tx::{Queue as _, Tx as _},
+        },
+        path::RemoteAddress,
+    };
+
+    /// Records the payload of each transmitted message and replays them on receive
+    #[derive(Default)]
+    struct Recorder(Vec<Vec<u8>>);
+
+    impl tx::Tx for Recorder {
+        type PathHandle = RemoteAddress;
+        type Queue = Recorder;
+        type Error = ();
+
+        fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Pending
+        }
+
+        fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+            f(self)
+        }
+
+        fn handle_error<E: event::EndpointPublisher>(self, _error: Self::Error, _events: &mut E) {}
+    }
+
+    impl tx::Queue for Recorder {
+        type Handle = RemoteAddress;
+
+        fn push<M: tx::Message<Handle = Self::Handle>>(
+            &mut self,
+            mut message: M,
+        ) -> Result<tx::Outcome, tx::Error> {
+            let mut payload = vec![0; 1500];
+            let len = message.write_payload(tx::PayloadBuffer::new(&mut payload), 0)?;
+            payload.truncate(len);
+            self.0.push(payload);
+            Ok(tx::Outcome {
+                len,
+                index: self.0.len() - 1,
+            })
+        }
+
+        fn capacity(&self) -> usize {
+            usize::MAX
+        }
+    }
+
+    impl rx::Rx for Recorder {
+        type PathHandle = RemoteAddress;
+        type Queue = Recorder;
+        type Error = ();
+
+        fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Pending
+        }
+
+        fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+            f(self)
+        }
+
+        fn handle_error<E: event::EndpointPublisher>(self, _error: Self::Error, _events: &mut E) {}
+    }
+
+    impl rx::Queue for Recorder {
+        type Handle = RemoteAddress;
+
+        fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(
+            &mut self,
+            mut on_packet: F,
+        ) {
+            for mut payload in self.0.drain(..) {
+                let header = datagram::Header::new(RemoteAddress::default(), Default::default());
+                on_packet(header, &mut payload);
+            }
+        }
+
+        fn is_empty(&self) -> bool {
+            self.0.is_empty()
+        }
+    }
+
+    fn transmit(tx: &mut Tx<Recorder>, payloads: &[&[u8]]) {
+        tx.queue(|queue| {
+            for payload in payloads {
+                queue.push((RemoteAddress::default(), *payload)).unwrap();
+            }
+        });
+    }
+
+    #[test]
+    fn egress_test() {
+        let (tap, records) = channel(16);
+        let tap = tap.with_sample_interval(2).with_snap_len(2);
+        let mut tx = Tx::new(Recorder::default(), Some(tap), MaxMtu::default());
+
+        transmit(&mut tx, &[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9, 10]]);
+
+        // the transmitted datagrams are unmodified
+        assert_eq!(
+            tx.tx.0,
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9, 10]]
+        );
+
+        // only the second datagram was sampled
+        let record = records.try_recv().unwrap();
+        assert_eq!(record.direction, Direction::Egress);
+        assert_eq!(record.len, 3);
+        assert_eq!(record.payload, vec![4, 5]);
+        assert!(records.try_recv().is_err());
+    }
+
+    #[test]
+    fn ingress_test() {
+        let (tap, records) = channel(16);
+        let tap = tap.with_egress(false);
+        let mut rx = Rx::new(Recorder(vec![vec![1, 2, 3], vec![4, 5]]), Some(tap));
+
+        let mut received = vec![];
+        rx.queue(|queue| {
+            queue.for_each(|_header, payload| received.push(payload.to_vec()));
+        });
+
+        assert_eq!(received, vec![vec![1, 2, 3], vec![4, 5]]);
+
+        let payloads: Vec<_> = records.try_iter().map(|record| record.payload).collect();
+        assert_eq!(payloads, received);
+    }
+
+    #[test]
+    fn full_channel_test() {
+        let (tap, records) = channel(1);
+        let mut tx = Tx::new(Recorder::default(), Some(tap.clone()), MaxMtu::default());
+
+        transmit(&mut tx, &[&[1], &[2], &[3]]);
+
+        // datagrams are still transmitted when the channel is full
+        assert_eq!(tx.tx.0.len(), 3);
+        assert_eq!(tap.dropped(), 2);
+        assert_eq!(records.try_recv().unwrap().payload, vec![1]);
+
+        // closing the receiver also drops records
+        drop(records);
+        transmit(&mut tx, &[&[4]]);
+        assert_eq!(tap.dropped(), 3);
+    }
+
+    #[test]
+    fn disabled_direction_test() {
+        let (tap, records) = channel(16);
+        let tap = tap.with_egress(false);
+        let mut tx = Tx::new(Recorder::default(), Some(tap), MaxMtu::default());
+
+        transmit(&mut tx, &[&[1, 2, 3]]);
+
+        assert_eq!(tx.tx.0, vec![vec![1, 2, 3]]);
+        assert!(tx.buffer.is_empty());
+        assert!(records.try_recv().is_err());
+    }
+}
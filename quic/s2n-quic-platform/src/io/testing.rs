@@ -4,7 +4,10 @@
 use bach::time::scheduler;
 use core::task::Poll;
 use s2n_quic_core::{
-    endpoint::Endpoint, inet::SocketAddress, io::event_loop::EventLoop, path::mtu,
+    endpoint::Endpoint,
+    inet::{IpAddress, SocketAddress},
+    io::event_loop::EventLoop,
+    path::mtu,
 };
 
 type Error = std::io::Error;
@@ -229,8 +232,12 @@ impl Handle {
             address: None,
             on_socket: None,
             mtu_config_builder: mtu::Config::builder(),
+            mtu_clamps: Vec::new(),
             queue_recv_buffer_size: None,
             queue_send_buffer_size: None,
+            #[cfg(feature = "tx-fuzz")]
+            tx_fuzz: None,
+            mirror: None,
         }
     }
 }
@@ -240,8 +247,12 @@ pub struct Builder {
     address: Option<SocketAddress>,
     on_socket: Option<Box<dyn FnOnce(socket::Socket)>>,
     mtu_config_builder: mtu::Builder,
+    mtu_clamps: Vec<mtu::Clamp>,
     queue_recv_buffer_size: Option<u32>,
     queue_send_buffer_size: Option<u32>,
+    #[cfg(feature = "tx-fuzz")]
+    tx_fuzz: Option<Box<dyn super::fuzz::Policy>>,
+    mirror: Option<super::mirror::Tap>,
 }
 
 impl Builder {
@@ -267,6 +278,28 @@ impl Builder {
         self
     }
 
+    pub fn with_mtu_clamp(mut self, prefix: IpAddress, prefix_len: u8, max_mtu: u16) -> Self {
+        self.mtu_clamps
+            .push(mtu::Clamp::new(prefix, prefix_len, max_mtu).unwrap());
+        self
+    }
+
+    /// Applies the policy to each outgoing datagram after it has been encrypted
+    ///
+    /// This can be used to drop, truncate or corrupt specific datagrams, rather than relying on
+    /// the random behavior of the network model.
+    #[cfg(feature = "tx-fuzz")]
+    pub fn with_tx_fuzz(mut self, policy: impl super::fuzz::Policy) -> Self {
+        self.tx_fuzz = Some(Box::new(policy));
+        self
+    }
+
+    /// Copies encrypted datagrams sent and received by the endpoint to the tap
+    pub fn with_mirror(mut self, tap: super::mirror::Tap) -> Self {
+        self.mirror = Some(tap);
+        self
+    }
+
     pub fn on_socket(mut self, f: impl FnOnce(socket::Socket) + 'static) -> Self {
         self.on_socket = Some(Box::new(f));
         self
@@ -310,8 +343,12 @@ impl Io {
             address,
             on_socket,
             mtu_config_builder,
+            mtu_clamps: _,
             queue_recv_buffer_size: _,
             queue_send_buffer_size: _,
+            #[cfg(feature = "tx-fuzz")]
+                tx_fuzz: _,
+            mirror: _,
         } = self.builder;
 
         let handle = address.unwrap_or_else(|| buffers.generate_addr());
@@ -334,17 +371,26 @@ impl Io {
             address,
             on_socket,
             mtu_config_builder,
+            mtu_clamps,
             queue_recv_buffer_size,
             queue_send_buffer_size,
+            #[cfg(feature = "tx-fuzz")]
+            tx_fuzz,
+            mirror,
         } = self.builder;
         let mtu_config = mtu_config_builder.build().unwrap();
         endpoint.set_mtu_config(mtu_config);
+        endpoint.set_mtu_clamps(&mtu_clamps);
 
         let handle = address.unwrap_or_else(|| buffers.generate_addr());
 
         let socket = buffers.register(handle, mtu_config.max_mtu);
         let tx = socket.tx_task(mtu_config.max_mtu, queue_send_buffer_size);
+        let tx = super::mirror::Tx::new(tx, mirror.clone(), mtu_config.max_mtu);
+        #[cfg(feature = "tx-fuzz")]
+        let tx = super::fuzz::Tx::new(tx, tx_fuzz, mtu_config.max_mtu);
         let rx = socket.rx_task(mtu_config.max_mtu, queue_recv_buffer_size);
+        let rx = super::mirror::Rx::new(rx, mirror);
 
         if let Some(on_socket) = on_socket {
             on_socket(socket);
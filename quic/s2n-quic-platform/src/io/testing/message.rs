@@ -92,10 +92,7 @@ impl MessageTrait for Message {
         _local_address: &path::LocalAddress,
     ) -> Option<message::RxMessage<Self::Handle>> {
         let path = self.handle;
-        let header = datagram::Header {
-            path,
-            ecn: Default::default(),
-        };
+        let header = datagram::Header::new(path, Default::default());
         let payload = self.payload_mut();
 
         let message = message::RxMessage {
@@ -6,7 +6,7 @@ use s2n_quic_core::{
     endpoint::Endpoint,
     event::{self, EndpointPublisher as _},
     inet::{self, SocketAddress},
-    io::event_loop::EventLoop,
+    io::{event_loop::EventLoop, rx::RxExt as _, tx::TxExt as _},
     path::{mtu, MaxMtu},
     task::cooldown::Cooldown,
     time::Clock as ClockTrait,
@@ -16,6 +16,8 @@ use tokio::runtime::Handle;
 
 mod builder;
 mod clock;
+mod mapped;
+mod port;
 pub(crate) mod task;
 #[cfg(test)]
 mod tests;
@@ -23,6 +25,8 @@ mod tests;
 pub type PathHandle = message::Handle;
 pub use builder::Builder;
 pub(crate) use clock::Clock;
+pub use mapped::MappedAddressPolicy;
+pub use port::SourcePortPolicy;
 
 #[derive(Debug, Default)]
 pub struct Io {
@@ -49,16 +53,30 @@ impl Io {
             rx_socket,
             tx_socket,
             recv_addr,
+            additional_rx_sockets,
+            additional_recv_addrs,
             send_addr,
             socket_recv_buffer_size,
             socket_send_buffer_size,
             queue_recv_buffer_size,
             queue_send_buffer_size,
             mtu_config_builder,
+            mtu_clamps,
             max_segments,
             gro_enabled,
             reuse_address,
             reuse_port,
+            dual_stack,
+            mapped_address_policy,
+            socket_configurator,
+            tx_socket_count,
+            packet_socket_interface,
+            port_range,
+            source_port_policy,
+            #[cfg(feature = "tx-fuzz")]
+            tx_fuzz,
+            mirror,
+            socket_stats,
         } = self.builder;
 
         let clock = Clock::default();
@@ -67,6 +85,7 @@ impl Io {
             event::builder::EndpointMeta {
                 endpoint_type: E::ENDPOINT_TYPE,
                 timestamp: clock.get_time(),
+                attempt_id: None,
             },
             None,
             endpoint.subscriber(),
@@ -88,10 +107,39 @@ impl Io {
 
         let guard = handle.enter();
 
+        // sockets provided by the application may have been created in blocking mode
+        for socket in rx_socket
+            .iter()
+            .chain(tx_socket.iter())
+            .chain(additional_rx_sockets.iter())
+        {
+            socket.set_nonblocking(true)?;
+
+            // Linux accepts an explicit destination for each datagram sent on a connected socket
+            // but other platforms fail the send with `EISCONN`
+            #[cfg(not(target_os = "linux"))]
+            if socket.peer_addr().is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "connected sockets are only supported on linux",
+                ));
+            }
+        }
+
+        let tx_socket_count = tx_socket_count.unwrap_or(1);
+
+        let bind_options = syscall::BindOptions {
+            reuse_address,
+            // additional tx sockets require the port to be shared
+            reuse_port: reuse_port || tx_socket_count > 1,
+            dual_stack,
+            configurator: socket_configurator.as_ref().map(|c| &*c.0),
+        };
+
         let rx_socket = if let Some(rx_socket) = rx_socket {
             rx_socket
         } else if let Some(recv_addr) = recv_addr {
-            syscall::bind_udp(recv_addr, reuse_address, reuse_port)?
+            port::bind(recv_addr, port_range.as_ref(), &bind_options)?
         } else {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -101,22 +149,117 @@ impl Io {
 
         let rx_addr = convert_addr_to_std(rx_socket.local_addr()?)?;
 
+        let mut is_shared_socket = false;
         let tx_socket = if let Some(tx_socket) = tx_socket {
             tx_socket
         } else if let Some(send_addr) = send_addr {
-            syscall::bind_udp(send_addr, reuse_address, reuse_port)?
+            port::bind(send_addr, port_range.as_ref(), &bind_options)?
         } else {
             // No tx_socket or send address was specified, so the tx socket
             // will be a handle to the rx socket.
+            is_shared_socket = true;
             rx_socket.try_clone()?
         };
 
+        let tx_addr = convert_addr_to_std(tx_socket.local_addr()?)?;
+        let is_ipv6_tx_socket = tx_addr.is_ipv6();
+
+        let mut rx_sockets = vec![rx_socket];
+        let mut tx_sockets = vec![tx_socket];
+
+        // bind the additional tx sockets to the same address as the first so peers observe a
+        // consistent source port
+        for _ in 1..tx_socket_count {
+            let socket = syscall::bind_udp_with_options(tx_addr, &bind_options)?;
+
+            // the OS will distribute incoming packets for the address to all of the sockets so
+            // we need to read from them as well
+            if is_shared_socket {
+                rx_sockets.push(socket.try_clone()?);
+            }
+
+            tx_sockets.push(socket);
+        }
+
+        // bind the sockets for the per-connection source ports
+        //
+        // Server connections are still replied to from the port the peer sent to, since the
+        // transmissions are steered by the local port of each path.
+        let source_port_count = source_port_policy.pool_size();
+
+        if source_port_count > 1 {
+            if !is_shared_socket || packet_socket_interface.is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "per-connection source ports require a shared rx and tx socket",
+                ));
+            }
+
+            let mut addr = rx_addr;
+            addr.set_port(0);
+
+            for _ in 1..source_port_count {
+                let socket = port::bind(addr, port_range.as_ref(), &bind_options)?;
+                rx_sockets.push(socket.try_clone()?);
+                tx_sockets.push(socket);
+            }
+        }
+
+        // bind the additional addresses the endpoint listens on
+        //
+        // Each socket is also used for transmitting so peers are replied to from the address they
+        // sent to.
+        let listen_socket_index = tx_sockets.len();
+        let mut additional_rx_sockets = additional_rx_sockets;
+        for addr in additional_recv_addrs {
+            additional_rx_sockets.push(port::bind(addr, port_range.as_ref(), &bind_options)?);
+        }
+        let has_additional_addresses = !additional_rx_sockets.is_empty();
+
+        if has_additional_addresses && (!is_shared_socket || packet_socket_interface.is_some()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "additional receive addresses require a shared rx and tx socket",
+            ));
+        }
+
+        for socket in additional_rx_sockets {
+            rx_sockets.push(socket.try_clone()?);
+            tx_sockets.push(socket);
+        }
+
+        let rx_socket_addrs = rx_sockets
+            .iter()
+            .map(|socket| convert_addr_to_std(socket.local_addr()?))
+            .collect::<io::Result<Vec<_>>>()?;
+        let tx_socket_ports = tx_sockets
+            .iter()
+            .map(|socket| Ok(convert_addr_to_std(socket.local_addr()?)?.port()))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        // transmissions are steered by the local port so each listening address needs its own
+        if has_additional_addresses {
+            let (ports, listen_ports) = tx_socket_ports.split_at(listen_socket_index);
+            for (idx, port) in listen_ports.iter().enumerate() {
+                if ports.contains(port) || listen_ports[..idx].contains(port) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "additional receive addresses must use distinct ports",
+                    ));
+                }
+            }
+        }
+
         if let Some(size) = socket_send_buffer_size {
-            tx_socket.set_send_buffer_size(size)?;
+            for tx_socket in &tx_sockets {
+                tx_socket.set_send_buffer_size(size)?;
+            }
         }
 
         if let Some(size) = socket_recv_buffer_size {
-            rx_socket.set_recv_buffer_size(size)?;
+            for rx_socket in &rx_sockets {
+                rx_socket.set_recv_buffer_size(size)?;
+            }
         }
 
         let mut mtu_config = mtu_config_builder
@@ -125,7 +268,7 @@ impl Io {
         let original_max_mtu = mtu_config.max_mtu;
 
         // Configure MTU discovery
-        if !syscall::configure_mtu_disc(&tx_socket) {
+        if !configure_all(&tx_sockets, syscall::configure_mtu_disc) {
             // disable MTU probing if we can't prevent fragmentation
             mtu_config = mtu::Config::MIN;
         }
@@ -149,7 +292,11 @@ impl Io {
         });
 
         // Configure the socket with GRO
-        let gro_enabled = gro_enabled.unwrap_or(true) && syscall::configure_gro(&rx_socket);
+        //
+        // Packet sockets read the frames from the interface so GRO can't be used to coalesce them
+        let gro_enabled = packet_socket_interface.is_none()
+            && gro_enabled.unwrap_or(true)
+            && configure_all(&rx_sockets, syscall::configure_gro);
 
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
             configuration: event::builder::PlatformFeatureConfiguration::Gro {
@@ -158,10 +305,10 @@ impl Io {
         });
 
         // Configure packet info CMSG
-        syscall::configure_pktinfo(&rx_socket);
+        configure_all(&rx_sockets, syscall::configure_pktinfo);
 
         // Configure TOS/ECN
-        let tos_enabled = syscall::configure_tos(&rx_socket);
+        let tos_enabled = configure_all(&rx_sockets, syscall::configure_tos);
 
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
             configuration: event::builder::PlatformFeatureConfiguration::Ecn {
@@ -189,6 +336,7 @@ impl Io {
             };
 
             let mut consumers = vec![];
+            let mut local_addresses = vec![];
 
             let rx_socket_count = parse_env("S2N_QUIC_UNSTABLE_RX_SOCKET_COUNT").unwrap_or(1);
 
@@ -196,17 +344,46 @@ impl Io {
             // complete
             let rx_cooldown = cooldown("RX");
 
-            for idx in 0usize..rx_socket_count {
+            if let Some(interface) = packet_socket_interface {
                 let (producer, consumer) = socket::ring::pair(entries, payload_len);
                 consumers.push(consumer);
-
-                // spawn a task that actually reads from the socket into the ring buffer
-                if idx + 1 == rx_socket_count {
-                    handle.spawn(task::rx(rx_socket, producer, rx_cooldown));
-                    break;
-                } else {
-                    let rx_socket = rx_socket.try_clone()?;
-                    handle.spawn(task::rx(rx_socket, producer, rx_cooldown.clone()));
+                local_addresses.push(inet::SocketAddress::from(rx_addr).into());
+
+                spawn_packet_rx(
+                    &handle,
+                    &interface,
+                    rx_addr,
+                    rx_sockets,
+                    producer,
+                    rx_cooldown,
+                    socket_stats.clone(),
+                )?;
+            } else {
+                for (rx_socket, rx_socket_addr) in rx_sockets.into_iter().zip(rx_socket_addrs) {
+                    for idx in 0usize..rx_socket_count {
+                        let (producer, consumer) = socket::ring::pair(entries, payload_len);
+                        consumers.push(consumer);
+                        local_addresses.push(inet::SocketAddress::from(rx_socket_addr).into());
+
+                        // spawn a task that actually reads from the socket into the ring buffer
+                        if idx + 1 == rx_socket_count {
+                            handle.spawn(task::rx(
+                                rx_socket,
+                                producer,
+                                rx_cooldown.clone(),
+                                socket_stats.clone(),
+                            ));
+                            break;
+                        } else {
+                            let rx_socket = rx_socket.try_clone()?;
+                            handle.spawn(task::rx(
+                                rx_socket,
+                                producer,
+                                rx_cooldown.clone(),
+                                socket_stats.clone(),
+                            ));
+                        }
+                    }
                 }
             }
 
@@ -214,8 +391,15 @@ impl Io {
             let max_mtu = MaxMtu::try_from(payload_len as u16).unwrap();
             let addr: inet::SocketAddress = rx_addr.into();
             socket::io::rx::Rx::new(consumers, max_mtu, addr.into())
+                // report the address of the socket that each datagram was received on
+                .with_local_addresses(local_addresses)
+                .with_stats(socket_stats.clone())
+                .with_handle_map(move |handle: &PathHandle| mapped_address_policy.on_rx(handle))
         };
 
+        // copy received datagrams to the tap, if configured
+        let rx = crate::io::mirror::Rx::new(rx, mirror.clone());
+
         let tx = {
             let gso = crate::features::Gso::from(max_segments);
 
@@ -236,38 +420,77 @@ impl Io {
             };
 
             let mut producers = vec![];
+            let mut channel_ports = vec![];
 
-            let tx_socket_count = parse_env("S2N_QUIC_UNSTABLE_TX_SOCKET_COUNT").unwrap_or(1);
+            let tx_task_count = parse_env("S2N_QUIC_UNSTABLE_TX_SOCKET_COUNT").unwrap_or(1);
 
             // configure the number of self-wakes before "cooling down" and waiting for epoll to
             // complete
             let tx_cooldown = cooldown("TX");
 
-            for idx in 0usize..tx_socket_count {
-                let (producer, consumer) = socket::ring::pair(entries, payload_len);
-                producers.push(producer);
-
-                // spawn a task that actually flushes the ring buffer to the socket
-                if idx + 1 == tx_socket_count {
-                    handle.spawn(task::tx(tx_socket, consumer, gso.clone(), tx_cooldown));
-                    break;
-                } else {
-                    let tx_socket = tx_socket.try_clone()?;
-                    handle.spawn(task::tx(
-                        tx_socket,
-                        consumer,
-                        gso.clone(),
-                        tx_cooldown.clone(),
-                    ));
+            for (tx_socket, tx_socket_port) in tx_sockets.into_iter().zip(&tx_socket_ports) {
+                for idx in 0usize..tx_task_count {
+                    let (producer, consumer) = socket::ring::pair(entries, payload_len);
+                    producers.push(producer);
+                    channel_ports.push(*tx_socket_port);
+
+                    // spawn a task that actually flushes the ring buffer to the socket
+                    if idx + 1 == tx_task_count {
+                        handle.spawn(task::tx(
+                            tx_socket,
+                            consumer,
+                            gso.clone(),
+                            tx_cooldown.clone(),
+                            socket_stats.clone(),
+                        ));
+                        break;
+                    } else {
+                        let tx_socket = tx_socket.try_clone()?;
+                        handle.spawn(task::tx(
+                            tx_socket,
+                            consumer,
+                            gso.clone(),
+                            tx_cooldown.clone(),
+                            socket_stats.clone(),
+                        ));
+                    }
                 }
             }
 
             // construct the TX side for the endpoint event loop
-            socket::io::tx::Tx::new(producers, gso, mtu_config.max_mtu)
+            let mut tx = socket::io::tx::Tx::new(producers, gso, mtu_config.max_mtu)
+                // steer each peer to a single socket if we have more than one
+                .with_flow_steering(tx_socket_count > 1)
+                .with_stats(socket_stats);
+
+            // transmit each connection from the port it was assigned
+            if source_port_count > 1 || has_additional_addresses {
+                tx = tx.with_port_steering(channel_ports);
+            }
+
+            tx.with_handle_map(move |handle: &PathHandle| {
+                mapped_address_policy.on_tx(handle, is_ipv6_tx_socket)
+            })
         };
 
+        // copy transmitted datagrams to the tap, if configured
+        let tx = crate::io::mirror::Tx::new(tx, mirror, mtu_config.max_mtu);
+
+        // mutate datagrams after they've been encrypted, if configured
+        #[cfg(feature = "tx-fuzz")]
+        let tx = crate::io::fuzz::Tx::new(tx, tx_fuzz.map(|policy| policy.0), mtu_config.max_mtu);
+
         // Notify the endpoint of the MTU that we chose
         endpoint.set_mtu_config(mtu_config);
+        endpoint.set_mtu_clamps(&mtu_clamps);
+
+        // Notify the endpoint of the ports that connections can be assigned
+        if source_port_count > 1 {
+            // client connections are only assigned the ports from the source port pool
+            let mut ports = tx_socket_ports[..listen_socket_index].to_vec();
+            ports.dedup();
+            endpoint.set_local_ports(&ports);
+        }
 
         let task = handle.spawn(
             EventLoop {
@@ -286,6 +509,68 @@ impl Io {
     }
 }
 
+/// Spawns a task which reads the datagrams for the rx address from a packet socket
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn spawn_packet_rx(
+    handle: &Handle,
+    interface: &std::ffi::CStr,
+    rx_addr: std::net::SocketAddr,
+    rx_sockets: Vec<socket2::Socket>,
+    producer: socket::ring::Producer<message::Message>,
+    cooldown: Cooldown,
+    stats: socket::stats::Stats,
+) -> io::Result<()> {
+    // the packet socket receives its own copy of each frame so have the OS discard the datagrams
+    // for the UDP sockets rather than queueing them
+    for rx_socket in &rx_sockets {
+        syscall::attach_drop_filter(rx_socket)?;
+    }
+
+    // the packet socket needs to accept the same datagrams the kernel would have delivered to the
+    // UDP sockets, which depends on how they were bound
+    let dual_stack = rx_addr.is_ipv6() && !rx_sockets[0].only_v6()?;
+
+    let socket = socket::packet::Socket::open(
+        interface,
+        rx_addr.into(),
+        dual_stack,
+        socket::packet::Config::default(),
+    )?;
+
+    handle.spawn(task::packet::rx(
+        socket, rx_sockets, producer, cooldown, stats,
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn spawn_packet_rx(
+    handle: &Handle,
+    interface: &std::ffi::CStr,
+    rx_addr: std::net::SocketAddr,
+    rx_sockets: Vec<socket2::Socket>,
+    producer: socket::ring::Producer<message::Message>,
+    cooldown: Cooldown,
+    stats: socket::stats::Stats,
+) -> io::Result<()> {
+    let _ = (
+        handle, interface, rx_addr, rx_sockets, producer, cooldown, stats,
+    );
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "packet sockets are not supported on the current platform",
+    ))
+}
+
+/// Applies the socket configuration function to all of the sockets, returning `true` if it
+/// succeeded for every socket
+fn configure_all(sockets: &[socket2::Socket], f: fn(&socket2::Socket) -> bool) -> bool {
+    sockets
+        .iter()
+        .fold(true, |is_success, socket| f(socket) & is_success)
+}
+
 fn convert_addr_to_std(addr: socket2::SockAddr) -> io::Result<std::net::SocketAddr> {
     addr.as_socket()
         .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid domain for socket"))
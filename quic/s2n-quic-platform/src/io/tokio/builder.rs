@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use core::{fmt, ops::RangeInclusive};
+use std::{ffi::CString, sync::Arc};
 
 #[derive(Debug, Default)]
 pub struct Builder {
@@ -9,16 +11,49 @@ pub struct Builder {
     pub(super) rx_socket: Option<socket2::Socket>,
     pub(super) tx_socket: Option<socket2::Socket>,
     pub(super) recv_addr: Option<std::net::SocketAddr>,
+    pub(super) additional_rx_sockets: Vec<socket2::Socket>,
+    pub(super) additional_recv_addrs: Vec<std::net::SocketAddr>,
     pub(super) send_addr: Option<std::net::SocketAddr>,
     pub(super) socket_recv_buffer_size: Option<usize>,
     pub(super) socket_send_buffer_size: Option<usize>,
     pub(super) queue_recv_buffer_size: Option<u32>,
     pub(super) queue_send_buffer_size: Option<u32>,
     pub(super) mtu_config_builder: mtu::Builder,
+    pub(super) mtu_clamps: Vec<mtu::Clamp>,
     pub(super) max_segments: gso::MaxSegments,
     pub(super) gro_enabled: Option<bool>,
     pub(super) reuse_address: bool,
     pub(super) reuse_port: bool,
+    pub(super) dual_stack: Option<bool>,
+    pub(super) mapped_address_policy: MappedAddressPolicy,
+    pub(super) socket_configurator: Option<SocketConfigurator>,
+    pub(super) tx_socket_count: Option<usize>,
+    pub(super) packet_socket_interface: Option<CString>,
+    pub(super) port_range: Option<RangeInclusive<u16>>,
+    pub(super) source_port_policy: SourcePortPolicy,
+    #[cfg(feature = "tx-fuzz")]
+    pub(super) tx_fuzz: Option<TxFuzzPolicy>,
+    pub(super) mirror: Option<crate::io::mirror::Tap>,
+    pub(super) socket_stats: socket::stats::Stats,
+}
+
+#[derive(Clone)]
+pub(super) struct SocketConfigurator(pub(super) Arc<syscall::Configurator>);
+
+impl fmt::Debug for SocketConfigurator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SocketConfigurator").finish()
+    }
+}
+
+#[cfg(feature = "tx-fuzz")]
+pub(super) struct TxFuzzPolicy(pub(super) Box<dyn crate::io::fuzz::Policy>);
+
+#[cfg(feature = "tx-fuzz")]
+impl fmt::Debug for TxFuzzPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TxFuzzPolicy").finish()
+    }
 }
 
 impl Builder {
@@ -51,6 +86,11 @@ impl Builder {
     /// Sets the socket used for receiving for the runtime. If no tx_socket or send address is
     /// specified, this socket will be used for transmitting.
     ///
+    /// The socket is switched to non-blocking mode when the runtime starts and receives the same
+    /// platform configuration, like GSO and GRO, as sockets bound by the runtime. Connected
+    /// sockets are supported on Linux; other platforms reject sending datagrams with an explicit
+    /// destination on a connected socket.
+    ///
     /// NOTE: this method is mutually exclusive with `with_receive_address`
     pub fn with_rx_socket(mut self, socket: std::net::UdpSocket) -> io::Result<Self> {
         debug_assert!(
@@ -61,6 +101,43 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the socket used for receiving for the runtime from a socket created with tokio
+    ///
+    /// This is useful for sockets which were created by the application with special options
+    /// or inherited from a supervisor, like systemd socket activation. The socket may also have
+    /// been connected to a single peer, in which case it only exchanges datagrams with that peer.
+    ///
+    /// See [`Self::with_rx_socket`] for more details.
+    pub fn with_tokio_rx_socket(self, socket: tokio::net::UdpSocket) -> io::Result<Self> {
+        self.with_rx_socket(socket.into_std()?)
+    }
+
+    /// Adds an address for the runtime to listen on, in addition to the receive address
+    ///
+    /// Connections are served from whichever address the peer sent to, which allows a single
+    /// endpoint to accept connections on several ports (e.g. `443` and `8443`). The local address
+    /// of each connection is reported in its path information and events. Each additional
+    /// address must use a different port from the receive address and the other additional
+    /// addresses, as well as the same IP version as the receive address.
+    ///
+    /// NOTE: additional addresses require the rx socket to also be used for transmitting and are
+    /// not supported with packet socket receive
+    pub fn with_additional_receive_address(
+        mut self,
+        addr: std::net::SocketAddr,
+    ) -> io::Result<Self> {
+        self.additional_recv_addrs.push(addr);
+        Ok(self)
+    }
+
+    /// Adds a socket for the runtime to listen on, in addition to the rx socket
+    ///
+    /// See [`Self::with_additional_receive_address`] for more details.
+    pub fn with_additional_rx_socket(mut self, socket: std::net::UdpSocket) -> io::Result<Self> {
+        self.additional_rx_sockets.push(socket.into());
+        Ok(self)
+    }
+
     /// Sets the socket used for transmitting on for the runtime. If no tx_socket or send address is
     /// specified, the rx_socket will be used for transmitting.
     ///
@@ -176,6 +253,76 @@ impl Builder {
         Ok(self)
     }
 
+    /// Bounds the maximum transmission unit (MTU) of paths to peers within an address prefix
+    ///
+    /// Some networks, such as certain mobile carriers, are known to drop datagrams larger than the
+    /// MTU they can support without any indication to the sender. Paths to peers within
+    /// `prefix/prefix_len` will not probe beyond `max_mtu`, avoiding repeated black hole detection
+    /// for these networks. The `initial_mtu` and `base_mtu` for these paths are also limited to
+    /// `max_mtu`. If multiple prefixes match a peer, the longest prefix is used.
+    ///
+    /// Note: `max_mtu` must be >= 1228
+    pub fn with_mtu_clamp(
+        mut self,
+        prefix: std::net::IpAddr,
+        prefix_len: u8,
+        max_mtu: u16,
+    ) -> io::Result<Self> {
+        let prefix = inet::SocketAddress::from((prefix, 0)).ip();
+        let clamp = mtu::Clamp::new(prefix, prefix_len, max_mtu)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        self.mtu_clamps.push(clamp);
+        Ok(self)
+    }
+
+    /// Applies the policy to each outgoing datagram after it has been encrypted
+    ///
+    /// The policy can drop, truncate or flip bits in datagrams to validate how peers, and the
+    /// endpoint's own loss recovery, handle damaged packets. This must never be used in
+    /// production deployments.
+    #[cfg(feature = "tx-fuzz")]
+    pub fn with_tx_fuzz(mut self, policy: impl crate::io::fuzz::Policy) -> io::Result<Self> {
+        self.tx_fuzz = Some(TxFuzzPolicy(Box::new(policy)));
+        Ok(self)
+    }
+
+    /// Copies encrypted datagrams sent and received by the endpoint to the tap
+    ///
+    /// Copies are delivered without blocking the endpoint, so records are dropped if the tap's
+    /// channel is full. See [`mirror`](crate::io::mirror) for more details.
+    pub fn with_mirror(mut self, tap: crate::io::mirror::Tap) -> io::Result<Self> {
+        self.mirror = Some(tap);
+        Ok(self)
+    }
+
+    /// Records the socket statistics of the endpoint, like syscalls and GSO segments, into the
+    /// provided handle
+    ///
+    /// The application keeps a clone of the handle and queries it with
+    /// [`Stats::snapshot`](crate::socket::stats::Stats::snapshot) to monitor the health of the
+    /// sockets. Nothing is recorded by default.
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// use s2n_quic_platform::{io::tokio::Io, socket::stats::Stats};
+    ///
+    /// let stats = Stats::new();
+    /// let io = Io::builder()
+    ///     .with_receive_address("0.0.0.0:443".parse().unwrap())?
+    ///     .with_socket_stats(stats.clone())?
+    ///     .build()?;
+    ///
+    /// // later on
+    /// let snapshot = stats.snapshot();
+    /// println!("send syscalls: {}", snapshot.tx_syscalls);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_socket_stats(mut self, stats: socket::stats::Stats) -> io::Result<Self> {
+        self.socket_stats = stats;
+        Ok(self)
+    }
+
     /// Disables Generic Segmentation Offload (GSO)
     ///
     /// By default, GSO will be used unless the platform does not support it or an attempt to use
@@ -236,6 +383,161 @@ impl Builder {
         Ok(self)
     }
 
+    /// Configures the dual-stack (IPv4 and IPv6) mode of sockets bound by the runtime
+    ///
+    /// When enabled, a single IPv6 socket is used to communicate with both IPv4 and IPv6 peers.
+    /// IPv4 peers are then reported by the operating system as IPv4-mapped IPv6 addresses
+    /// (`::ffff:a.b.c.d`), which are handled according to the configured
+    /// [`MappedAddressPolicy`]. When disabled, IPv6 sockets will only accept IPv6 traffic.
+    ///
+    /// By default, IPv6 sockets will attempt to enable dual-stack mode, ignoring any errors. Setting
+    /// this option explicitly will cause the endpoint to fail to start if the mode cannot be
+    /// applied, including if dual-stack mode is enabled with an IPv4 bind address.
+    ///
+    /// NOTE: this option has no effect on sockets provided with `with_rx_socket` or `with_tx_socket`
+    pub fn with_dual_stack(mut self, enabled: bool) -> io::Result<Self> {
+        self.dual_stack = Some(enabled);
+        Ok(self)
+    }
+
+    /// Configures how IPv4-mapped IPv6 addresses are handled (default: [`MappedAddressPolicy::Normalize`])
+    ///
+    /// By default, IPv4-mapped addresses are normalized to their IPv4 form so path tracking,
+    /// connection routing, limits and events observe a single form of each peer's address,
+    /// regardless of the platform.
+    pub fn with_mapped_address_policy(mut self, policy: MappedAddressPolicy) -> io::Result<Self> {
+        self.mapped_address_policy = policy;
+        Ok(self)
+    }
+
+    /// Sets a callback to configure each socket created by the runtime
+    ///
+    /// The callback is invoked with both the rx and tx sockets, if separate, after the runtime has
+    /// applied its own options but before the socket is bound. This allows applications to set
+    /// additional socket options (e.g. `SO_MARK`, `IP_FREEBIND` or `SO_BINDTODEVICE`) without
+    /// needing to create the sockets themselves. Returning an error will cause the endpoint to fail
+    /// to start.
+    ///
+    /// The socket is passed as a `std::os::fd::BorrowedFd` on unix platforms and a
+    /// `std::os::windows::io::BorrowedSocket` on windows, so the callback doesn't depend on a
+    /// specific version of a socket library. Libraries like `socket2` can wrap the
+    /// borrowed socket to set options, e.g. `socket2::SockRef::from(&socket)`.
+    ///
+    /// NOTE: the callback is not invoked for sockets provided with `with_rx_socket` or
+    /// `with_tx_socket`
+    pub fn with_socket_configurator<F>(mut self, configurator: F) -> io::Result<Self>
+    where
+        F: 'static + Fn(syscall::BorrowedSocket) -> io::Result<()> + Send + Sync,
+    {
+        self.socket_configurator = Some(SocketConfigurator(Arc::new(configurator)));
+        Ok(self)
+    }
+
+    /// Sets the number of sockets used for transmitting (default: 1)
+    ///
+    /// A single socket can be limited by the throughput of its send syscalls. Configuring multiple
+    /// sockets allows the transmissions to be spread across several sockets, each with its own
+    /// task. Each socket is bound to the same local address using the port reuse
+    /// (SO_REUSEPORT) socket option, so peers observe a consistent source port. The transmissions
+    /// for each peer are steered to a single socket based on the remote address.
+    ///
+    /// If the tx socket is shared with the rx socket, the additional sockets will receive a
+    /// portion of the incoming traffic for the address and are read from as well.
+    pub fn with_tx_socket_count(mut self, count: usize) -> io::Result<Self> {
+        if count == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "tx_socket_count must be at least 1",
+            ));
+        }
+
+        if count > 1 && !cfg!(unix) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "multiple tx sockets are not supported on the current platform",
+            ));
+        }
+
+        self.tx_socket_count = Some(count);
+        Ok(self)
+    }
+
+    /// Receives datagrams from a packet socket on the given interface
+    ///
+    /// Instead of reading from the UDP socket, datagrams are read from a raw packet socket
+    /// (`AF_PACKET`) with a ring buffer shared with the kernel (`TPACKET_V3`). This avoids
+    /// performing a syscall for each batch of datagrams, which can reduce the receive overhead
+    /// when Generic Receive Offload (GRO) is unavailable. The UDP socket is still bound to reserve
+    /// the port, but the datagrams it receives are discarded by the OS. GRO is disabled when this
+    /// option is used.
+    ///
+    /// **WARNING**: packet sockets read the frames before the host's network stack processes
+    /// them, so this path bypasses host firewall rules (netfilter/iptables/nftables). Datagrams
+    /// which the firewall would drop for the UDP socket are still delivered to the endpoint.
+    /// The frames are limited to UDP datagrams for the bound port which are addressed to the host
+    /// and carry a valid checksum, but any additional filtering must be performed by the
+    /// application (e.g. with the endpoint limits provider).
+    ///
+    /// The interface must use Ethernet framing (including the loopback interface) and opening a
+    /// packet socket requires the `CAP_NET_RAW` capability.
+    ///
+    /// This option is only supported on Linux.
+    pub fn with_packet_socket_rx(mut self, interface: &str) -> io::Result<Self> {
+        if !cfg!(any(target_os = "android", target_os = "linux")) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "packet sockets are not supported on the current platform",
+            ));
+        }
+
+        let interface = CString::new(interface)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        self.packet_socket_interface = Some(interface);
+        Ok(self)
+    }
+
+    /// Sets the range of ports to choose from when binding to an address without a port
+    ///
+    /// By default, the OS assigns an ephemeral port to sockets bound with a port of `0`. When a
+    /// range is configured, a random port within the range is chosen instead, which allows the
+    /// ports used by the endpoint to be constrained to the ones permitted by a firewall or NAT.
+    /// Binding fails if none of the ports that were tried are available.
+    ///
+    /// NOTE: this option has no effect on sockets provided with `with_rx_socket` or `with_tx_socket`
+    pub fn with_port_range(mut self, range: RangeInclusive<u16>) -> io::Result<Self> {
+        if range.is_empty() || *range.start() == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "port_range must be non-empty and cannot include port 0",
+            ));
+        }
+
+        self.port_range = Some(range);
+        Ok(self)
+    }
+
+    /// Configures how source ports are assigned to client connections (default: [`SourcePortPolicy::Shared`])
+    ///
+    /// With [`SourcePortPolicy::PerConnection`], additional sockets are bound to ephemeral ports
+    /// when the endpoint starts and each new connection is assigned one of the ports at random.
+    /// The additional sockets are bound to the same IP as the receive address, using the
+    /// configured port range, if any. Connections accepted by a server are unaffected and
+    /// continue to use the port the peer connected to.
+    ///
+    /// NOTE: per-connection ports require the rx socket to also be used for transmitting and are
+    /// not supported with packet socket receive
+    pub fn with_source_port_policy(mut self, policy: SourcePortPolicy) -> io::Result<Self> {
+        if policy.pool_size() == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "source port pool_size must be at least 1",
+            ));
+        }
+
+        self.source_port_policy = policy;
+        Ok(self)
+    }
+
     pub fn build(self) -> io::Result<Io> {
         Ok(Io { builder: self })
     }
@@ -0,0 +1,137 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::PathHandle;
+use s2n_quic_core::inet::SocketAddress;
+
+/// Configures how IPv4-mapped IPv6 addresses (`::ffff:0:0/96`) are handled by the IO provider
+///
+/// Dual-stack sockets report IPv4 peers using their mapped IPv6 form on some platforms and their
+/// plain IPv4 form on others. Without normalization, address-based policies (limits, events, path
+/// tracking) can observe the same peer under two different addresses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MappedAddressPolicy {
+    /// IPv4-mapped addresses are converted to their IPv4 form before being passed to the endpoint
+    ///
+    /// Transmissions to IPv4 peers are mapped back to the IPv6 form if the socket is an IPv6
+    /// socket.
+    #[default]
+    Normalize,
+    /// Addresses are passed to the endpoint exactly as reported by the operating system
+    Preserve,
+    /// Datagrams received from IPv4-mapped addresses are dropped
+    Reject,
+}
+
+impl MappedAddressPolicy {
+    /// Applies the policy to a received datagram's path handle
+    ///
+    /// Returns `None` if the datagram should be dropped.
+    #[inline]
+    pub(super) fn on_rx(self, handle: &PathHandle) -> Option<PathHandle> {
+        let mut handle = *handle;
+
+        match self {
+            Self::Normalize => {
+                if let Some(addr) = to_ipv4_mapped(&handle.remote_address.0) {
+                    handle.remote_address = addr.into();
+                }
+                if let Some(addr) = to_ipv4_mapped(&handle.local_address.0) {
+                    handle.local_address = addr.into();
+                }
+            }
+            Self::Preserve => {}
+            Self::Reject => {
+                if to_ipv4_mapped(&handle.remote_address.0).is_some() {
+                    return None;
+                }
+            }
+        }
+
+        Some(handle)
+    }
+
+    /// Applies the policy to a transmitted datagram's path handle
+    #[inline]
+    pub(super) fn on_tx(self, handle: &PathHandle, is_ipv6_socket: bool) -> PathHandle {
+        let mut handle = *handle;
+
+        // only map the address back if we normalized it on the way in
+        if self == Self::Normalize && is_ipv6_socket {
+            if let SocketAddress::IpV4(addr) = handle.remote_address.0 {
+                handle.remote_address = addr.to_ipv6_mapped().into();
+            }
+        }
+
+        handle
+    }
+}
+
+#[inline]
+fn to_ipv4_mapped(addr: &SocketAddress) -> Option<SocketAddress> {
+    match addr {
+        SocketAddress::IpV4(_) => None,
+        SocketAddress::IpV6(addr) => addr.to_ipv4_mapped().map(SocketAddress::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::path::Handle as _;
+
+    fn handle(addr: &str) -> PathHandle {
+        let addr: std::net::SocketAddr = addr.parse().unwrap();
+        let addr: SocketAddress = addr.into();
+        PathHandle::from_remote_address(addr.into())
+    }
+
+    #[test]
+    fn normalize_test() {
+        let policy = MappedAddressPolicy::Normalize;
+
+        let mapped = handle("[::ffff:192.0.2.1]:443");
+        let actual = policy.on_rx(&mapped).unwrap();
+        assert_eq!(
+            actual.remote_address,
+            handle("192.0.2.1:443").remote_address
+        );
+
+        // the address should be mapped back when sending on an IPv6 socket
+        let actual = policy.on_tx(&actual, true);
+        assert_eq!(actual.remote_address, mapped.remote_address);
+
+        // NAT64 addresses aren't reachable over IPv4 so they should be left alone
+        let nat64 = handle("[64:ff9b::192.0.2.1]:443");
+        assert_eq!(policy.on_rx(&nat64).unwrap(), nat64);
+
+        // IPv4 addresses are left alone for IPv4 sockets
+        let ipv4 = handle("192.0.2.1:443");
+        assert_eq!(policy.on_tx(&ipv4, false), ipv4);
+    }
+
+    #[test]
+    fn preserve_test() {
+        let policy = MappedAddressPolicy::Preserve;
+
+        let mapped = handle("[::ffff:192.0.2.1]:443");
+        assert_eq!(policy.on_rx(&mapped).unwrap(), mapped);
+
+        let ipv4 = handle("192.0.2.1:443");
+        assert_eq!(policy.on_tx(&ipv4, true), ipv4);
+    }
+
+    #[test]
+    fn reject_test() {
+        let policy = MappedAddressPolicy::Reject;
+
+        assert!(policy.on_rx(&handle("[::ffff:192.0.2.1]:443")).is_none());
+
+        let ipv4 = handle("192.0.2.1:443");
+        assert_eq!(policy.on_rx(&ipv4).unwrap(), ipv4);
+
+        let ipv6 = handle("[2001:db8::1]:443");
+        assert_eq!(policy.on_rx(&ipv6).unwrap(), ipv6);
+    }
+}
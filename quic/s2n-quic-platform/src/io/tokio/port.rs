@@ -0,0 +1,88 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::syscall;
+use core::{
+    hash::{BuildHasher, Hasher},
+    ops::RangeInclusive,
+};
+use std::{collections::hash_map::RandomState, io, net::SocketAddr};
+
+/// The number of ports tried before giving up on binding a socket within the port range
+const MAX_BIND_ATTEMPTS: usize = 64;
+
+/// Configures how source ports are assigned to connections initiated by a client endpoint
+///
+/// By default, all connections share the port the endpoint is bound to. Assigning connections
+/// different ports makes it harder for observers to link connections to the same client and
+/// spreads the connections across more NAT bindings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SourcePortPolicy {
+    /// All connections are initiated from the port the endpoint is bound to
+    #[default]
+    Shared,
+    /// Each connection is initiated from a port randomly selected from a pool of sockets
+    ///
+    /// The pool includes the socket the endpoint is bound to, along with `pool_size - 1`
+    /// additional sockets bound to ephemeral ports. The sockets are opened when the endpoint
+    /// starts, so the number of NAT bindings used by the endpoint is bounded by the pool size.
+    PerConnection { pool_size: usize },
+}
+
+impl SourcePortPolicy {
+    /// Returns the number of sockets used by the policy
+    #[inline]
+    pub(super) fn pool_size(self) -> usize {
+        match self {
+            Self::Shared => 1,
+            Self::PerConnection { pool_size } => pool_size,
+        }
+    }
+}
+
+/// Binds a UDP socket to the address
+///
+/// If the address doesn't specify a port and a port range is configured, a random port within
+/// the range is chosen instead of letting the OS pick one.
+pub(super) fn bind(
+    addr: SocketAddr,
+    port_range: Option<&RangeInclusive<u16>>,
+    options: &syscall::BindOptions,
+) -> io::Result<socket2::Socket> {
+    let range = match port_range {
+        Some(range) if addr.port() == 0 => range,
+        _ => return syscall::bind_udp_with_options(addr, options),
+    };
+
+    let len = (*range.end() as u32 - *range.start() as u32) + 1;
+
+    // start at a random position in the range and walk it from there so small ranges are
+    // exhausted before giving up
+    let offset = random() % len;
+
+    for attempt in 0..len.min(MAX_BIND_ATTEMPTS as u32) {
+        let port = *range.start() as u32 + (offset + attempt) % len;
+        let mut addr = addr;
+        addr.set_port(port as u16);
+
+        match syscall::bind_udp_with_options(addr, options) {
+            Ok(socket) => return Ok(socket),
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AddrInUse,
+        "could not find an available port in the configured range",
+    ))
+}
+
+/// Returns a random value, seeded by the standard library's per-process hash keys
+#[inline]
+fn random() -> u32 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u8(0);
+    hasher.finish() as u32
+}
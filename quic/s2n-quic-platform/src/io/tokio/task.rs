@@ -4,6 +4,8 @@
 // depending on the platform, some of these implementations aren't used
 #![allow(dead_code)]
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub mod packet;
 mod simple;
 #[cfg(unix)]
 mod unix;
@@ -23,15 +25,20 @@ macro_rules! libc_msg {
         #[cfg($cfg)]
         mod $message {
             use super::unix;
-            use crate::{features::Gso, message::$message::Message, socket::ring};
+            use crate::{
+                features::Gso,
+                message::$message::Message,
+                socket::{ring, stats::Stats},
+            };
             use s2n_quic_core::task::cooldown::Cooldown;
 
             pub async fn rx<S: Into<std::net::UdpSocket>>(
                 socket: S,
                 producer: ring::Producer<Message>,
                 cooldown: Cooldown,
+                stats: Stats,
             ) -> std::io::Result<()> {
-                unix::rx(socket, producer, cooldown).await
+                unix::rx(socket, producer, cooldown, stats).await
             }
 
             pub async fn tx<S: Into<std::net::UdpSocket>>(
@@ -39,8 +46,9 @@ macro_rules! libc_msg {
                 consumer: ring::Consumer<Message>,
                 gso: Gso,
                 cooldown: Cooldown,
+                stats: Stats,
             ) -> std::io::Result<()> {
-                unix::tx(socket, consumer, gso, cooldown).await
+                unix::tx(socket, consumer, gso, cooldown, stats).await
             }
         }
     };
@@ -0,0 +1,79 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::socket::{
+    packet::{self, Message},
+    ring,
+    stats::Stats,
+    task::rx,
+};
+use core::task::{Context, Poll};
+use s2n_quic_core::task::cooldown::Cooldown;
+use std::io;
+use tokio::io::unix::AsyncFd;
+
+pub async fn rx<M: Message + Unpin>(
+    socket: packet::Socket,
+    udp_sockets: Vec<socket2::Socket>,
+    producer: ring::Producer<M>,
+    cooldown: Cooldown,
+    stats: Stats,
+) -> io::Result<()> {
+    // the UDP sockets need to stay open so the OS doesn't reject the datagrams for the port
+    let _udp_sockets = udp_sockets;
+
+    let socket = Socket(AsyncFd::new(socket)?);
+    let result = rx::Receiver::new(producer, socket, cooldown)
+        .with_stats(stats)
+        .await;
+    if let Some(err) = result {
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+struct Socket(AsyncFd<packet::Socket>);
+
+impl<M: Message> rx::Socket<M> for Socket {
+    type Error = io::Error;
+
+    #[inline]
+    fn recv(
+        &mut self,
+        cx: &mut Context,
+        entries: &mut [M],
+        events: &mut rx::Events,
+    ) -> io::Result<()> {
+        // read from the ring first, since it doesn't require a syscall
+        self.0.get_mut().recv(entries, events);
+
+        // yield back if we weren't blocked
+        if !events.is_blocked() {
+            return Ok(());
+        }
+
+        // * First iteration we need to clear socket readiness since the ring was empty.
+        // * Second iteration we need to register the waker, assuming the socket readiness was
+        // cleared.
+        //   * If we got a `Ready` anyway, then clear the blocked status and have the caller try
+        //   again.
+        for i in 0..2 {
+            match self.0.poll_read_ready(cx) {
+                Poll::Ready(guard) => {
+                    let mut guard = guard?;
+                    if i == 0 {
+                        guard.clear_ready();
+                    } else {
+                        events.take_blocked();
+                    }
+                }
+                Poll::Pending => {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -5,7 +5,9 @@ use crate::{
     features::Gso,
     message::{simple::Message, Message as _},
     socket::{
-        ring, task,
+        ring,
+        stats::Stats,
+        task,
         task::{rx, tx},
     },
     syscall::SocketEvents,
@@ -18,12 +20,15 @@ pub async fn rx<S: Into<std::net::UdpSocket>>(
     socket: S,
     producer: ring::Producer<Message>,
     cooldown: Cooldown,
+    stats: Stats,
 ) -> io::Result<()> {
     let socket = socket.into();
     socket.set_nonblocking(true).unwrap();
 
     let socket = UdpSocket::from_std(socket).unwrap();
-    let result = task::Receiver::new(producer, socket, cooldown).await;
+    let result = task::Receiver::new(producer, socket, cooldown)
+        .with_stats(stats)
+        .await;
     if let Some(err) = result {
         Err(err)
     } else {
@@ -36,12 +41,15 @@ pub async fn tx<S: Into<std::net::UdpSocket>>(
     consumer: ring::Consumer<Message>,
     gso: Gso,
     cooldown: Cooldown,
+    stats: Stats,
 ) -> io::Result<()> {
     let socket = socket.into();
     socket.set_nonblocking(true).unwrap();
 
     let socket = UdpSocket::from_std(socket).unwrap();
-    let result = task::Sender::new(consumer, socket, gso, cooldown).await;
+    let result = task::Sender::new(consumer, socket, gso, cooldown)
+        .with_stats(stats)
+        .await;
     if let Some(err) = result {
         Err(err)
     } else {
@@ -5,6 +5,7 @@ use crate::{
     features::Gso,
     socket::{
         ring,
+        stats::Stats,
         task::{rx, tx},
     },
     syscall::{SocketType, UnixMessage},
@@ -18,12 +19,15 @@ pub async fn rx<S: Into<std::net::UdpSocket>, M: UnixMessage + Unpin>(
     socket: S,
     producer: ring::Producer<M>,
     cooldown: Cooldown,
+    stats: Stats,
 ) -> io::Result<()> {
     let socket = socket.into();
     socket.set_nonblocking(true).unwrap();
 
     let socket = AsyncFd::new(socket).unwrap();
-    let result = rx::Receiver::new(producer, socket, cooldown).await;
+    let result = rx::Receiver::new(producer, socket, cooldown)
+        .with_stats(stats)
+        .await;
     if let Some(err) = result {
         Err(err)
     } else {
@@ -36,12 +40,15 @@ pub async fn tx<S: Into<std::net::UdpSocket>, M: UnixMessage + Unpin>(
     consumer: ring::Consumer<M>,
     gso: Gso,
     cooldown: Cooldown,
+    stats: Stats,
 ) -> io::Result<()> {
     let socket = socket.into();
     socket.set_nonblocking(true).unwrap();
 
     let socket = AsyncFd::new(socket).unwrap();
-    let result = tx::Sender::new(consumer, socket, gso, cooldown).await;
+    let result = tx::Sender::new(consumer, socket, gso, cooldown)
+        .with_stats(stats)
+        .await;
     if let Some(err) = result {
         Err(err)
     } else {
@@ -178,17 +178,34 @@ async fn test<A: ToSocketAddrs>(
     client_rx_addr: A,
     client_tx_addr: Option<A>,
 ) -> io::Result<()> {
-    let (server_io, server_addr) = runtime(server_rx_addr, server_tx_addr).await?;
-    let (client_io, client_addr) = runtime(client_rx_addr, client_tx_addr).await?;
+    let server = runtime(server_rx_addr, server_tx_addr).await?;
+    let client = runtime(client_rx_addr, client_tx_addr).await?;
+    run(server, client).await
+}
+
+/// Runs the server and client endpoints until the client receives all of its messages
+async fn run(
+    server: (super::Io, SocketAddress),
+    client: (super::Io, SocketAddress),
+) -> io::Result<()> {
+    let server_addr = server.1;
+    run_with_address(server, client, server_addr).await
+}
 
+/// Runs the server and client endpoints with the client sending to `connect_addr`
+async fn run_with_address(
+    (server_io, server_addr): (super::Io, SocketAddress),
+    (client_io, client_addr): (super::Io, SocketAddress),
+    connect_addr: SocketAddress,
+) -> io::Result<()> {
     let server_endpoint = {
         let mut handle = PathHandle::from_remote_address(client_addr.into());
-        handle.local_address = server_addr.into();
+        handle.local_address = connect_addr.into();
         TestEndpoint::<true>::new(handle)
     };
 
     let client_endpoint = {
-        let mut handle = PathHandle::from_remote_address(server_addr.into());
+        let mut handle = PathHandle::from_remote_address(connect_addr.into());
         handle.local_address = client_addr.into();
         TestEndpoint::<false>::new(handle)
     };
@@ -227,6 +244,200 @@ async fn ipv4_two_socket_test() -> io::Result<()> {
     .await
 }
 
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+#[cfg(unix)]
+async fn ipv4_multi_tx_socket_test() -> io::Result<()> {
+    // the provided socket needs port reuse enabled so the additional sockets can share the address
+    let rx_socket = syscall::bind_udp(IPV4_LOCALHOST, false, true)?;
+    rx_socket.set_nonblocking(true)?;
+    let rx_socket: std::net::UdpSocket = rx_socket.into();
+    let server_addr = rx_socket.local_addr()?.into();
+
+    let server_io = Io::builder()
+        .with_rx_socket(rx_socket)?
+        .with_tx_socket_count(4)?
+        .build()?;
+
+    let client = runtime(IPV4_LOCALHOST, None).await?;
+
+    run((server_io, server_addr), client).await
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn socket_stats_test() -> io::Result<()> {
+    use crate::socket::stats::Stats;
+
+    let server = runtime(IPV4_LOCALHOST, None).await?;
+
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let stats = Stats::new();
+    let client_io = Io {
+        builder: client_io.builder.with_socket_stats(stats.clone())?,
+    };
+
+    run(server, (client_io, client_addr)).await?;
+
+    let snapshot = stats.snapshot();
+    // the client sends 30 messages and receives them back from the server
+    assert!(snapshot.tx_syscalls > 0, "{snapshot:?}");
+    assert!(snapshot.tx_segments >= 30, "{snapshot:?}");
+    assert!(snapshot.tx_messages > 0, "{snapshot:?}");
+    assert!(snapshot.rx_syscalls > 0, "{snapshot:?}");
+    assert!(snapshot.rx_segments >= 30, "{snapshot:?}");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn ipv4_source_port_pool_test() -> io::Result<()> {
+    let server = runtime(IPV4_LOCALHOST, None).await?;
+
+    let port_range = 40000..=40999;
+
+    let client_socket = port::bind(
+        IPV4_LOCALHOST.parse().unwrap(),
+        Some(&port_range),
+        &Default::default(),
+    )?;
+    client_socket.set_nonblocking(true)?;
+    let client_socket: std::net::UdpSocket = client_socket.into();
+    let client_addr = client_socket.local_addr()?;
+    assert!(port_range.contains(&client_addr.port()));
+
+    let client_io = Io::builder()
+        .with_rx_socket(client_socket)?
+        .with_port_range(port_range)?
+        .with_source_port_policy(SourcePortPolicy::PerConnection { pool_size: 4 })?
+        .build()?;
+
+    run(server, (client_io, client_addr.into())).await
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn ipv4_additional_receive_address_test() -> io::Result<()> {
+    let bind = || -> io::Result<std::net::UdpSocket> {
+        let socket = syscall::bind_udp(IPV4_LOCALHOST, false, false)?;
+        socket.set_nonblocking(true)?;
+        Ok(socket.into())
+    };
+
+    let rx_socket = bind()?;
+    let server_addr = rx_socket.local_addr()?.into();
+    let additional_socket = bind()?;
+    let additional_addr = additional_socket.local_addr()?.into();
+
+    let server_io = Io::builder()
+        .with_rx_socket(rx_socket)?
+        .with_additional_rx_socket(additional_socket)?
+        .build()?;
+
+    let client = runtime(IPV4_LOCALHOST, None).await?;
+
+    // the client only talks to the additional address
+    run_with_address((server_io, server_addr), client, additional_addr).await
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+// the other loopback addresses are only available by default on linux
+#[cfg(target_os = "linux")]
+async fn additional_receive_address_validation_test() -> io::Result<()> {
+    let addr = IPV4_LOCALHOST.parse().unwrap();
+    let endpoint = || {
+        let handle = PathHandle::from_remote_address(SocketAddress::default().into());
+        TestEndpoint::<true>::new(handle)
+    };
+
+    // a separate tx socket can't reply from the additional address
+    let io = Io::builder()
+        .with_receive_address(addr)?
+        .with_send_address(addr)?
+        .with_additional_receive_address(addr)?
+        .build()?;
+    assert!(io.start(endpoint()).is_err());
+
+    // the additional addresses must have distinct ports
+    let rx_socket = syscall::bind_udp(IPV4_LOCALHOST, false, false)?;
+    rx_socket.set_nonblocking(true)?;
+    let rx_socket: std::net::UdpSocket = rx_socket.into();
+    let mut duplicate = rx_socket.local_addr()?;
+    duplicate.set_ip("127.0.0.2".parse().unwrap());
+    let io = Io::builder()
+        .with_rx_socket(rx_socket)?
+        .with_additional_receive_address(duplicate)?
+        .build()?;
+    assert!(io.start(endpoint()).is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn ipv4_application_socket_test() -> io::Result<()> {
+    let server_socket = tokio::net::UdpSocket::bind(IPV4_LOCALHOST).await?;
+    let server_addr = server_socket.local_addr()?.into();
+    let server_io = Io::builder().with_tokio_rx_socket(server_socket)?.build()?;
+
+    // the socket is left in blocking mode, which the runtime switches when starting
+    let client_socket = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+    let client_addr = client_socket.local_addr()?.into();
+    let client_io = Io::builder().with_rx_socket(client_socket)?.build()?;
+
+    run((server_io, server_addr), (client_io, client_addr)).await
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+#[cfg(target_os = "linux")]
+async fn ipv4_connected_socket_test() -> io::Result<()> {
+    let server = runtime(IPV4_LOCALHOST, None).await?;
+
+    let client_socket = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+    client_socket.connect(std::net::SocketAddr::from(server.1))?;
+    let client_addr = client_socket.local_addr()?.into();
+    let client_io = Io::builder().with_rx_socket(client_socket)?.build()?;
+
+    run(server, (client_io, client_addr)).await
+}
+
+#[test]
+fn source_port_policy_validation_test() {
+    assert!(Io::builder().with_port_range(1000..=999).is_err());
+    assert!(Io::builder().with_port_range(0..=1000).is_err());
+    assert!(Io::builder()
+        .with_source_port_policy(SourcePortPolicy::PerConnection { pool_size: 0 })
+        .is_err());
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+#[cfg(target_os = "linux")]
+async fn ipv4_packet_socket_rx_test() -> io::Result<()> {
+    let rx_socket = syscall::bind_udp(IPV4_LOCALHOST, false, false)?;
+    rx_socket.set_nonblocking(true)?;
+    let rx_socket: std::net::UdpSocket = rx_socket.into();
+    let server_addr = rx_socket.local_addr()?.into();
+
+    let server_io = Io::builder()
+        .with_rx_socket(rx_socket)?
+        .with_packet_socket_rx("lo")?
+        .build()?;
+
+    let client = runtime(IPV4_LOCALHOST, None).await?;
+
+    match run((server_io, server_addr), client).await {
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+            eprintln!("The current environment does not allow packet sockets; skipping");
+            Ok(())
+        }
+        other => other,
+    }
+}
+
 #[tokio::test]
 #[cfg_attr(miri, ignore)]
 async fn ipv6_test() -> io::Result<()> {
@@ -260,3 +471,44 @@ async fn ipv6_two_socket_test() -> io::Result<()> {
         other => other,
     }
 }
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn socket_configurator_test() -> io::Result<()> {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let addr: std::net::SocketAddr = IPV4_LOCALHOST.parse().unwrap();
+    let handle = PathHandle::from_remote_address(SocketAddress::default().into());
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let io = Io::builder()
+        .with_receive_address(addr)?
+        .with_send_address(addr)?
+        .with_socket_configurator({
+            let calls = calls.clone();
+            move |socket| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                socket2::SockRef::from(&socket).set_recv_buffer_size(1 << 16)
+            }
+        })?
+        .build()?;
+
+    let (task, _addr) = io.start(TestEndpoint::<true>::new(handle))?;
+    task.abort();
+
+    // the configurator should be called for both the rx and tx sockets
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+    // any errors from the configurator should prevent the endpoint from starting
+    let io = Io::builder()
+        .with_receive_address(addr)?
+        .with_socket_configurator(|_socket| Err(io::Error::new(io::ErrorKind::Other, "failed")))?
+        .build()?;
+
+    assert!(io.start(TestEndpoint::<true>::new(handle)).is_err());
+
+    Ok(())
+}
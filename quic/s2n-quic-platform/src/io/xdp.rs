@@ -77,7 +77,8 @@ pub mod tx {
 
         // spawn a task that actually flushes the ring buffer to the socket
         let cooldown = s2n_quic_core::task::cooldown::Cooldown::default();
-        let task = crate::io::tokio::task::tx(socket, consumer, gso.clone(), cooldown);
+        let task =
+            crate::io::tokio::task::tx(socket, consumer, gso.clone(), cooldown, Default::default());
 
         // construct the TX side for the endpoint event loop
         let io = crate::socket::io::tx::Tx::new(producers, gso, max_mtu);
@@ -27,7 +27,7 @@ impl Ext for msghdr {
 
         path.with_ancillary_data(ancillary_data);
 
-        let header = datagram::Header { path, ecn };
+        let header = datagram::Header::new(path, ecn);
 
         Some((header, ancillary_data))
     }
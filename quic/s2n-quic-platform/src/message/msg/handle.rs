@@ -5,7 +5,7 @@ use super::ext::Ext as _;
 use crate::{features, message::cmsg::Encoder};
 use libc::msghdr;
 use s2n_quic_core::{
-    inet::{AncillaryData, SocketAddressV4},
+    inet::{AncillaryData, SocketAddressV4, Unspecified},
     path::{self, LocalAddress, RemoteAddress},
 };
 
@@ -58,6 +58,11 @@ impl path::Handle for Handle {
         self.remote_address.0.set_port(port);
     }
 
+    #[inline]
+    fn set_local_port(&mut self, port: u16) {
+        self.local_address.0.set_port(port);
+    }
+
     #[inline]
     fn local_address(&self) -> LocalAddress {
         self.local_address
@@ -87,7 +92,10 @@ impl path::Handle for Handle {
         }
 
         // once we discover our path, or the port changes, update the address with the new information
-        if self.local_address.port() != other.local_address.port() {
+        if self.local_address.port() != other.local_address.port()
+            || (self.local_address.ip().is_unspecified()
+                && !other.local_address.ip().is_unspecified())
+        {
             self.local_address = other.local_address;
         }
     }
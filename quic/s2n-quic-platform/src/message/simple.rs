@@ -81,10 +81,7 @@ impl MessageTrait for Message {
             remote_address: self.address.into(),
             local_address: *local_address,
         };
-        let header = datagram::Header {
-            path,
-            ecn: Default::default(),
-        };
+        let header = datagram::Header::new(path, Default::default());
         let payload = self.payload_mut();
 
         let message = super::RxMessage {
@@ -2,5 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod io;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub mod packet;
 pub mod ring;
+pub mod stats;
 pub mod task;
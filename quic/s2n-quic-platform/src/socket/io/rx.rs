@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{message::Message, socket::ring::Consumer};
+use crate::{
+    message::Message,
+    socket::{ring::Consumer, stats::Stats},
+};
 use core::task::{Context, Poll};
 use s2n_quic_core::{
     event,
@@ -14,18 +17,43 @@ use s2n_quic_core::{
 pub struct Rx<T: Message> {
     channels: Vec<Consumer<T>>,
     max_mtu: MaxMtu,
-    local_address: LocalAddress,
+    /// The local address of the socket for each channel
+    local_addresses: Vec<LocalAddress>,
+    stats: Stats,
 }
 
 impl<T: Message> Rx<T> {
     #[inline]
     pub fn new(channels: Vec<Consumer<T>>, max_mtu: MaxMtu, local_address: LocalAddress) -> Self {
+        let local_addresses = vec![local_address; channels.len()];
         Self {
             channels,
             max_mtu,
-            local_address,
+            local_addresses,
+            stats: Stats::default(),
         }
     }
+
+    /// Records the datagrams read from the channels into the provided statistics
+    #[inline]
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Sets the local address of the socket for each channel
+    ///
+    /// This is required when the channels are read from sockets bound to different ports.
+    #[inline]
+    pub fn with_local_addresses(mut self, local_addresses: Vec<LocalAddress>) -> Self {
+        assert_eq!(
+            local_addresses.len(),
+            self.channels.len(),
+            "a local address is required for each channel"
+        );
+        self.local_addresses = local_addresses;
+        self
+    }
 }
 
 impl<T: Message> rx::Rx for Rx<T> {
@@ -86,7 +114,8 @@ impl<T: Message> rx::Rx for Rx<T> {
         let mut queue = RxQueue {
             channels: &mut this.channels,
             max_mtu: this.max_mtu,
-            local_address: &this.local_address,
+            local_addresses: &this.local_addresses,
+            stats: &this.stats,
         };
 
         f(&mut queue);
@@ -103,7 +132,8 @@ impl<T: Message> rx::Rx for Rx<T> {
 pub struct RxQueue<'a, T: Message> {
     channels: &'a mut [Consumer<T>],
     max_mtu: MaxMtu,
-    local_address: &'a LocalAddress,
+    local_addresses: &'a [LocalAddress],
+    stats: &'a Stats,
 }
 
 impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
@@ -111,7 +141,14 @@ impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
 
     #[inline]
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
-        for channel in self.channels.iter_mut() {
+        for (queue_id, (channel, local_address)) in self
+            .channels
+            .iter_mut()
+            .zip(self.local_addresses)
+            .enumerate()
+        {
+            let queue_id = Some(queue_id as u32);
+
             // one last effort to acquire items if some were received since we last polled
             let len = channel.acquire(u32::MAX);
 
@@ -123,8 +160,20 @@ impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
                 //
                 // NOTE: it's important that we process all of the messages in the queue as the
                 //       channel is completely drained here.
-                if let Some(message) = message.rx_read(self.local_address) {
-                    message.for_each(&mut on_packet);
+                if let Some(message) = message.rx_read(local_address) {
+                    if message.segment_size > 0 {
+                        let segments = message.payload.chunks(message.segment_size).len();
+                        self.stats.on_rx_segments(segments);
+                        if segments > 1 {
+                            self.stats.on_rx_gro_batch(1);
+                        }
+                    }
+
+                    message.for_each(|mut header, payload| {
+                        // annotate the datagram with the ring buffer it was read from
+                        header.queue_id = queue_id;
+                        on_packet(header, payload)
+                    });
                 }
 
                 unsafe {
@@ -143,3 +192,51 @@ impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{message::simple, socket::ring};
+    use s2n_quic_core::{
+        inet::{ExplicitCongestionNotification, SocketAddress},
+        io::rx::{Queue as _, Rx as _},
+        path::{Handle as _, RemoteAddress},
+    };
+
+    #[test]
+    fn queue_id_test() {
+        let max_mtu = MaxMtu::default();
+        let payload_len: usize = max_mtu.into();
+
+        let mut producers = vec![];
+        let mut consumers = vec![];
+        for _ in 0..2 {
+            let (producer, consumer) = ring::pair::<simple::Message>(4, payload_len as _);
+            producers.push(producer);
+            consumers.push(consumer);
+        }
+
+        // send a datagram on each channel, tagged with the index of the channel
+        for (idx, producer) in producers.iter_mut().enumerate() {
+            assert_eq!(producer.acquire(1), 1);
+            let entry = &mut producer.data()[0];
+            let handle = RemoteAddress::from(SocketAddress::default());
+            let handle = <simple::Message as Message>::Handle::from_remote_address(handle);
+            let payload = [idx as u8];
+            entry
+                .tx_write((handle, ExplicitCongestionNotification::Ect0, &payload[..]))
+                .unwrap();
+            producer.release(1);
+        }
+
+        let local_address = LocalAddress::from(SocketAddress::default());
+        let mut rx = Rx::new(consumers, max_mtu, local_address);
+
+        let mut received = vec![];
+        rx.queue(|queue| {
+            queue.for_each(|header, payload| received.push((payload[0], header.queue_id)));
+        });
+
+        assert_eq!(received, [(0, Some(0)), (1, Some(1))]);
+    }
+}
@@ -1,8 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{features::Gso, message::Message, socket::ring::Producer};
-use core::task::{Context, Poll};
+use crate::{
+    features::Gso,
+    message::Message,
+    socket::{ring::Producer, stats::Stats},
+};
+use core::{
+    hash::{Hash, Hasher},
+    task::{Context, Poll},
+};
 use s2n_quic_core::{
     event,
     inet::ExplicitCongestionNotification,
@@ -16,6 +23,10 @@ pub struct Tx<T: Message> {
     gso: Gso,
     max_mtu: usize,
     is_full: bool,
+    flow_steering: bool,
+    /// The local port of the socket for each channel, if the sockets are bound to different ports
+    channel_ports: Vec<u16>,
+    stats: Stats,
 }
 
 impl<T: Message> Tx<T> {
@@ -26,8 +37,48 @@ impl<T: Message> Tx<T> {
             gso,
             max_mtu: max_mtu.into(),
             is_full: true,
+            flow_steering: false,
+            channel_ports: Vec::new(),
+            stats: Stats::default(),
         }
     }
+
+    /// Records the datagrams written to the channels into the provided statistics
+    #[inline]
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Configures the queue to steer each flow to a single channel
+    ///
+    /// When enabled, messages are assigned to a channel based on their remote address, rather
+    /// than filling channels in order. This keeps each peer's transmissions on a single socket,
+    /// which avoids reordering between sockets and spreads the syscall load across the channels.
+    /// If the assigned channel is full, the message will be written to the next channel with
+    /// capacity.
+    #[inline]
+    pub fn with_flow_steering(mut self, enabled: bool) -> Self {
+        self.flow_steering = enabled;
+        self
+    }
+
+    /// Configures the queue to steer messages to the channel bound to their local port
+    ///
+    /// Each entry is the local port of the socket for the channel at the same index. Messages with
+    /// a local port that matches one of the channels are only written to channels with that port,
+    /// so they're transmitted from the port the path was established on. Other messages may be
+    /// written to any channel.
+    #[inline]
+    pub fn with_port_steering(mut self, channel_ports: Vec<u16>) -> Self {
+        assert_eq!(
+            channel_ports.len(),
+            self.channels.len(),
+            "a port is required for each channel"
+        );
+        self.channel_ports = channel_ports;
+        self
+    }
 }
 
 impl<T: Message> tx::Tx for Tx<T> {
@@ -128,6 +179,9 @@ impl<T: Message> tx::Tx for Tx<T> {
             max_mtu: this.max_mtu,
             capacity,
             is_full: &mut this.is_full,
+            flow_steering: this.flow_steering,
+            channel_ports: &this.channel_ports,
+            stats: &this.stats,
         };
 
         f(&mut queue);
@@ -141,6 +195,34 @@ impl<T: Message> tx::Tx for Tx<T> {
     }
 }
 
+/// A simple FNV-1a hasher used to assign flows to channels
+///
+/// The assignment only needs to be stable for the lifetime of the endpoint so we avoid the cost of
+/// a keyed hasher.
+struct FlowHasher(u64);
+
+impl Default for FlowHasher {
+    #[inline]
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FlowHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 /// Tracks the current state of a GSO message
 #[derive(Debug, Default)]
 pub struct GsoSegment<Handle> {
@@ -189,6 +271,11 @@ pub struct TxQueue<'a, T: Message> {
     /// Used to track if we have filled up the producer queue and waiting on free slots to be
     /// released by the consumer.
     is_full: &'a mut bool,
+    /// If set, messages are assigned to channels based on their remote address
+    flow_steering: bool,
+    /// The local port of the socket for each channel, if steering by port is enabled
+    channel_ports: &'a [u16],
+    stats: &'a Stats,
 }
 
 impl<'a, T: Message> TxQueue<'a, T> {
@@ -320,6 +407,8 @@ impl<'a, T: Message> TxQueue<'a, T> {
                 message.set_segment_size(gso.size);
             }
 
+            self.stats.on_tx_segments(gso.count);
+
             // clear out the current state and release the message
             self.gso_segment = None;
             self.release_message();
@@ -364,6 +453,39 @@ impl<'a, T: Message> TxQueue<'a, T> {
         self.pending_release += 1;
     }
 
+    /// Moves the queue to the channel assigned to the handle's flow
+    ///
+    /// If the assigned channel is full, the next channel with free slots is chosen instead. When
+    /// steering by port, only channels bound to the handle's local port are considered.
+    #[inline]
+    fn steer(&mut self, handle: &T::Handle) {
+        let len = self.channels.len();
+
+        // this shouldn't be called with a pending GSO segment since it would be written to
+        // another channel
+        debug_assert!(self.gso_segment.is_none());
+
+        let mut hasher = FlowHasher::default();
+        handle.remote_address().hash(&mut hasher);
+        let preferred = (hasher.finish() % len.max(1) as u64) as usize;
+
+        let channel_ports = self.channel_ports;
+        let local_port = handle.local_address().port();
+        let is_port_steered = channel_ports.contains(&local_port);
+
+        let target = (0..len)
+            .map(|offset| (preferred + offset) % len)
+            .filter(|idx| !is_port_steered || channel_ports[*idx] == local_port)
+            .find(|idx| !self.channels[*idx].data().is_empty())
+            // if all of the channels are full, move past the end to return an AtCapacity error
+            .unwrap_or(len);
+
+        if target != self.channel_index {
+            self.flush_channel();
+            self.channel_index = target;
+        }
+    }
+
     /// Flushes the current channel and releases any pending messages
     #[inline]
     fn flush_channel(&mut self) {
@@ -394,12 +516,16 @@ impl<'a, T: Message> tx::Queue for TxQueue<'a, T> {
             Err(message) => message,
         };
 
+        if self.flow_steering || !self.channel_ports.is_empty() {
+            self.steer(message.path_handle());
+        }
+
         // find the next free entry, if any
         let entry = loop {
-            let channel = self
-                .channels
-                .get_mut(self.channel_index)
-                .ok_or(tx::Error::AtCapacity)?;
+            let Some(channel) = self.channels.get_mut(self.channel_index) else {
+                self.stats.on_tx_ring_full(1);
+                return Err(tx::Error::AtCapacity);
+            };
 
             if let Some(entry) = channel.data().get_mut(self.message_index) {
                 break entry;
@@ -435,6 +561,7 @@ impl<'a, T: Message> tx::Queue for TxQueue<'a, T> {
             });
         } else {
             // otherwise, release the message to the consumer
+            self.stats.on_tx_segments(1);
             self.release_message();
         }
 
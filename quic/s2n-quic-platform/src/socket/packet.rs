@@ -0,0 +1,890 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Receives datagrams from a raw packet socket (`AF_PACKET`) with a `TPACKET_V3` ring
+//!
+//! The kernel writes frames from the interface directly into a ring of blocks which is shared
+//! with the process, so a burst of datagrams can be read without performing a syscall for each
+//! batch. This is mostly beneficial when Generic Receive Offload (GRO) is unavailable, in which
+//! case each `recvmmsg` call is limited to reading a single datagram per message.
+//!
+//! Packet sockets see the frames on the interface before the host's network stack processes
+//! them, which means firewall rules (netfilter/iptables) are NOT applied to the datagrams read
+//! from the ring. To limit what's read, a classic BPF filter is attached to the socket which only
+//! accepts UDP datagrams for the bound port. The frames are then checked in user space to only
+//! accept datagrams which the kernel would have delivered to the bound UDP socket:
+//!
+//! * The frame must be addressed to the host (`PACKET_HOST`).
+//! * The destination address must match the bound address, unless the socket is bound to the
+//!   unspecified address. IPv4 datagrams are only accepted by IPv6 sockets in dual-stack mode.
+//! * The UDP checksum must be valid, unless the kernel already verified it.
+//!
+//! Only Ethernet interfaces (including the loopback interface) are supported.
+//!
+//! See <https://docs.kernel.org/networking/packet_mmap.html>
+
+use crate::{
+    message::Message as MessageTrait,
+    syscall::{SocketEvents, UnixMessage},
+};
+use core::{
+    hash::Hasher,
+    ptr::NonNull,
+    sync::atomic::{fence, Ordering},
+    time::Duration,
+};
+use s2n_codec::DecoderBuffer;
+use s2n_quic_core::{
+    inet::{
+        checksum::Checksum, datagram, ethernet, ip, ipv4, ipv6, udp, IpAddress, SocketAddress,
+        Unspecified,
+    },
+    xdp::{
+        decoder::{self, EventHandler},
+        path::Tuple,
+    },
+};
+use std::{
+    ffi::CStr,
+    io,
+    os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+};
+
+const PACKET_RX_RING: libc::c_int = 5;
+const PACKET_VERSION: libc::c_int = 10;
+const PACKET_IGNORE_OUTGOING: libc::c_int = 23;
+const TPACKET_V3: libc::c_int = 2;
+const TP_STATUS_KERNEL: u32 = 0;
+const TP_STATUS_USER: u32 = 1;
+const TP_STATUS_CSUMNOTREADY: u32 = 1 << 3;
+const TP_STATUS_CSUM_VALID: u32 = 1 << 7;
+const ETH_P_ALL: u16 = 0x0003;
+
+/// The offset of the `sockaddr_ll` following each `tpacket3_hdr`
+///
+/// This is `TPACKET_ALIGN(sizeof(struct tpacket3_hdr))`.
+const FRAME_ADDRESS_OFFSET: usize = 48;
+
+/// The size of each frame slot used to compute the number of frames in the ring
+///
+/// With `TPACKET_V3`, frames are variable length and packed into each block so this only bounds
+/// the number of frames the kernel accounts for.
+const FRAME_SIZE: u32 = 1 << 11;
+
+/// `struct tpacket_req3` from `linux/if_packet.h`
+#[repr(C)]
+#[allow(dead_code)] // the fields are only read by the kernel
+#[derive(Clone, Copy, Debug, Default)]
+struct Request {
+    block_size: u32,
+    block_nr: u32,
+    frame_size: u32,
+    frame_nr: u32,
+    retire_blk_tov: u32,
+    sizeof_priv: u32,
+    feature_req_word: u32,
+}
+
+/// The leading fields of `struct tpacket_block_desc` with a `tpacket_hdr_v1` header
+#[repr(C)]
+#[allow(dead_code)] // some of the fields are only used by the kernel
+struct BlockHeader {
+    version: u32,
+    offset_to_priv: u32,
+    block_status: u32,
+    num_pkts: u32,
+    offset_to_first_pkt: u32,
+}
+
+/// The leading fields of `struct tpacket3_hdr`
+#[repr(C)]
+#[allow(dead_code)] // some of the fields are only used by the kernel
+struct FrameHeader {
+    next_offset: u32,
+    sec: u32,
+    nsec: u32,
+    snaplen: u32,
+    len: u32,
+    status: u32,
+    mac: u16,
+    net: u16,
+}
+
+/// Configures the ring shared with the kernel
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// The size of each block in the ring, which must be a power of two multiple of the page size
+    pub block_size: u32,
+    /// The number of blocks in the ring
+    pub block_count: u32,
+    /// The time after which the kernel hands a partially-filled block to the process
+    ///
+    /// This bounds the latency of datagrams received at low rates, since blocks are otherwise
+    /// only handed over once they're full.
+    pub retire_timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            block_size: 1 << 18,
+            block_count: 64,
+            retire_timeout: Duration::from_millis(1),
+        }
+    }
+}
+
+/// Writes a datagram read from the ring into a message
+pub trait Message: UnixMessage {
+    /// Writes the datagram into the message, returning `false` if it doesn't fit
+    fn write_datagram(&mut self, header: &datagram::Header<Tuple>, payload: &[u8]) -> bool;
+}
+
+#[cfg(s2n_quic_platform_socket_msg)]
+impl Message for libc::msghdr {
+    #[inline]
+    fn write_datagram(&mut self, header: &datagram::Header<Tuple>, payload: &[u8]) -> bool {
+        use crate::message::{cmsg::Encoder, msg::Ext};
+
+        let Some(buffer) = self.payload_mut().get_mut(..payload.len()) else {
+            return false;
+        };
+        buffer.copy_from_slice(payload);
+
+        unsafe {
+            // Safety: the payload was copied into the message buffer
+            self.set_payload_len(payload.len());
+        }
+
+        let remote_address = header
+            .path
+            .remote_address
+            .ip
+            .with_port(header.path.remote_address.port);
+        let local_address = header
+            .path
+            .local_address
+            .ip
+            .with_port(header.path.local_address.port);
+
+        self.set_remote_address(&remote_address);
+
+        // the ancillary data is encoded the same way the OS reports it for UDP sockets
+        self.msg_controllen = 0;
+        let mut encoder = self.cmsg_encoder();
+        encoder.encode_local_address(&local_address).is_ok()
+            && encoder.encode_ecn(header.ecn, &remote_address).is_ok()
+    }
+}
+
+#[cfg(s2n_quic_platform_socket_mmsg)]
+impl Message for libc::mmsghdr {
+    #[inline]
+    fn write_datagram(&mut self, header: &datagram::Header<Tuple>, payload: &[u8]) -> bool {
+        if !self.msg_hdr.write_datagram(header, payload) {
+            return false;
+        }
+
+        self.msg_len = payload.len() as _;
+        true
+    }
+}
+
+/// A packet socket with a mapped receive ring
+pub struct Socket {
+    fd: OwnedFd,
+    ring: NonNull<u8>,
+    block_size: usize,
+    block_count: usize,
+    block_index: usize,
+    cursor: Option<Cursor>,
+    filter: Filter,
+}
+
+/// Safety: the ring is only accessed through `&mut self`
+unsafe impl Send for Socket {}
+
+/// The position of the next frame in the current block
+#[derive(Clone, Copy, Debug)]
+struct Cursor {
+    offset: usize,
+    remaining: u32,
+}
+
+impl Socket {
+    /// Opens a packet socket on the interface which receives the datagrams for `local_address`
+    ///
+    /// `local_address` and `dual_stack` must reflect the UDP socket actually bound for the port,
+    /// since they decide which datagrams the kernel would have delivered to it. An unspecified
+    /// `local_address` accepts datagrams for any destination address on the interface.
+    ///
+    /// Opening a packet socket requires the `CAP_NET_RAW` capability.
+    pub fn open(
+        interface: &CStr,
+        local_address: SocketAddress,
+        dual_stack: bool,
+        config: Config,
+    ) -> io::Result<Self> {
+        let block_size = config.block_size;
+        let block_count = config.block_count;
+
+        if !block_size.is_power_of_two() || block_size < FRAME_SIZE || block_count == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid packet socket ring configuration",
+            ));
+        }
+
+        let ifindex = unsafe { libc::if_nametoindex(interface.as_ptr()) };
+        if ifindex == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // the protocol is set when binding so no frames are queued before the ring is configured
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_PACKET,
+                libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                0,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // the filter is attached before binding so no other frames are queued for the socket
+        attach_port_filter(&fd, local_address.port())?;
+
+        setsockopt(&fd, PACKET_VERSION, &TPACKET_V3)?;
+
+        // frames transmitted by the host are also delivered to packet sockets, which would
+        // otherwise be indistinguishable from received frames on the loopback interface
+        let enabled: libc::c_int = 1;
+        setsockopt(&fd, PACKET_IGNORE_OUTGOING, &enabled)?;
+
+        let request = Request {
+            block_size,
+            block_nr: block_count,
+            frame_size: FRAME_SIZE,
+            frame_nr: block_size / FRAME_SIZE * block_count,
+            retire_blk_tov: config.retire_timeout.as_millis().clamp(1, u32::MAX as _) as _,
+            ..Default::default()
+        };
+        setsockopt(&fd, PACKET_RX_RING, &request)?;
+
+        let len = block_size as usize * block_count as usize;
+        let ring = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if ring == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        let socket = Self {
+            fd,
+            ring: NonNull::new(ring as *mut u8).expect("mmap returned a null pointer"),
+            block_size: block_size as _,
+            block_count: block_count as _,
+            block_index: 0,
+            cursor: None,
+            filter: Filter::new(local_address, dual_stack),
+        };
+
+        let mut addr = unsafe { core::mem::zeroed::<libc::sockaddr_ll>() };
+        addr.sll_family = libc::AF_PACKET as _;
+        addr.sll_protocol = ETH_P_ALL.to_be();
+        addr.sll_ifindex = ifindex as _;
+
+        let res = unsafe {
+            libc::bind(
+                socket.fd.as_raw_fd(),
+                &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+                core::mem::size_of_val(&addr) as _,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(socket)
+    }
+
+    /// Reads the datagrams for the local address from the ring into the messages
+    ///
+    /// If the ring is empty, a `WouldBlock` error is reported to the events.
+    pub fn recv<M: Message, E: SocketEvents>(&mut self, entries: &mut [M], events: &mut E) {
+        if entries.is_empty() {
+            return;
+        }
+
+        let filter = self.filter;
+        let mut count = 0;
+
+        while count < entries.len() {
+            let Some((frame, status)) = self.next_frame() else {
+                break;
+            };
+
+            // the kernel reports if it already verified the checksum, or if the frame was sent by
+            // the host and the checksum hasn't been computed yet
+            let verify_checksum = status & (TP_STATUS_CSUM_VALID | TP_STATUS_CSUMNOTREADY) == 0;
+
+            let Some((header, payload)) = decode(frame, &filter, verify_checksum) else {
+                continue;
+            };
+
+            if entries[count].write_datagram(&header, payload) {
+                count += 1;
+            }
+        }
+
+        if count > 0 {
+            let _ = events.on_complete(count);
+        } else {
+            let _ = events.on_error(io::ErrorKind::WouldBlock.into());
+        }
+    }
+
+    /// Returns the next frame in the ring which was addressed to the host, along with its status
+    ///
+    /// Blocks are handed back to the kernel once all of their frames have been read.
+    #[inline]
+    fn next_frame(&mut self) -> Option<(&[u8], u32)> {
+        loop {
+            let block = unsafe { self.ring.as_ptr().add(self.block_index * self.block_size) };
+            let block_header = block as *mut BlockHeader;
+
+            if let Some(cursor) = self.cursor.as_mut() {
+                if cursor.remaining == 0 {
+                    // make sure all of the reads from the block complete before handing it back
+                    fence(Ordering::Release);
+                    unsafe {
+                        core::ptr::addr_of_mut!((*block_header).block_status)
+                            .write_volatile(TP_STATUS_KERNEL);
+                    }
+
+                    self.cursor = None;
+                    self.block_index = (self.block_index + 1) % self.block_count;
+                    continue;
+                }
+
+                let offset = cursor.offset;
+                let frame = unsafe { &*(block.add(offset) as *const FrameHeader) };
+
+                cursor.remaining -= 1;
+                cursor.offset += frame.next_offset as usize;
+
+                // skip any frames which were truncated to fit in the block
+                if frame.snaplen != frame.len {
+                    continue;
+                }
+
+                let start = offset + frame.mac as usize;
+                let end = start + frame.snaplen as usize;
+                if end > self.block_size
+                    || offset + FRAME_ADDRESS_OFFSET + core::mem::size_of::<libc::sockaddr_ll>()
+                        > self.block_size
+                {
+                    continue;
+                }
+
+                // skip any frames which weren't addressed to the host, e.g. frames for other
+                // hosts seen on a shared segment or broadcasts
+                let address = unsafe {
+                    (block.add(offset + FRAME_ADDRESS_OFFSET) as *const libc::sockaddr_ll)
+                        .read_unaligned()
+                };
+                if address.sll_pkttype != libc::PACKET_HOST {
+                    continue;
+                }
+
+                let data =
+                    unsafe { core::slice::from_raw_parts(block.add(start), frame.snaplen as _) };
+                return Some((data, frame.status));
+            }
+
+            let status =
+                unsafe { core::ptr::addr_of!((*block_header).block_status).read_volatile() };
+
+            if status & TP_STATUS_USER == 0 {
+                return None;
+            }
+
+            // synchronize with the kernel's writes to the block
+            fence(Ordering::Acquire);
+
+            let block_header = unsafe { &*block_header };
+            self.cursor = Some(Cursor {
+                offset: block_header.offset_to_first_pkt as _,
+                remaining: block_header.num_pkts,
+            });
+        }
+    }
+}
+
+impl AsRawFd for Socket {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(
+                self.ring.as_ptr() as *mut _,
+                self.block_size * self.block_count,
+            );
+        }
+    }
+}
+
+/// Attaches a classic BPF program which only accepts UDP datagrams for the port
+///
+/// The frames are filtered in the kernel so the ring isn't filled with the rest of the traffic on
+/// the interface. Fragmented IPv4 datagrams and IPv6 datagrams with extension headers are
+/// rejected, since the UDP header can't be located with a fixed offset.
+fn attach_port_filter(fd: &OwnedFd, port: u16) -> io::Result<()> {
+    const LDH_ABS: u16 = 0x28; // BPF_LD | BPF_H | BPF_ABS
+    const LDB_ABS: u16 = 0x30; // BPF_LD | BPF_B | BPF_ABS
+    const LDH_IND: u16 = 0x48; // BPF_LD | BPF_H | BPF_IND
+    const LDXB_MSH: u16 = 0xb1; // BPF_LDX | BPF_B | BPF_MSH
+    const JEQ: u16 = 0x15; // BPF_JMP | BPF_JEQ | BPF_K
+    const JSET: u16 = 0x45; // BPF_JMP | BPF_JSET | BPF_K
+    const RET: u16 = 0x06; // BPF_RET | BPF_K
+
+    const fn op(code: u16, jt: u8, jf: u8, k: u32) -> libc::sock_filter {
+        libc::sock_filter { code, jt, jf, k }
+    }
+
+    let udp = ip::Protocol::UDP.id as u32;
+    let port = port as u32;
+
+    // offsets are relative to the start of the Ethernet header
+    let program = [
+        // 0: load the ethertype
+        op(LDH_ABS, 0, 0, 12),
+        // 1: IPv4 continues to 2, otherwise check for IPv6 at 9
+        op(JEQ, 0, 7, 0x0800),
+        // 2: load the IPv4 protocol
+        op(LDB_ABS, 0, 0, 14 + 9),
+        // 3: drop anything other than UDP
+        op(JEQ, 0, 11, udp),
+        // 4: load the IPv4 flags and fragment offset
+        op(LDH_ABS, 0, 0, 14 + 6),
+        // 5: drop fragments other than the first one
+        op(JSET, 9, 0, 0x1fff),
+        // 6: load the IPv4 header length into X
+        op(LDXB_MSH, 0, 0, 14),
+        // 7: load the UDP destination port
+        op(LDH_IND, 0, 0, 14 + 2),
+        // 8: accept the port at 14, drop anything else at 15
+        op(JEQ, 5, 6, port),
+        // 9: drop anything other than IPv6
+        op(JEQ, 0, 5, 0x86dd),
+        // 10: load the IPv6 next header
+        op(LDB_ABS, 0, 0, 14 + 6),
+        // 11: drop anything other than UDP
+        op(JEQ, 0, 3, udp),
+        // 12: load the UDP destination port
+        op(LDH_ABS, 0, 0, 14 + 40 + 2),
+        // 13: accept the port at 14, drop anything else at 15
+        op(JEQ, 0, 1, port),
+        // 14: accept the entire frame
+        op(RET, 0, 0, u32::MAX),
+        // 15: drop the frame
+        op(RET, 0, 0, 0),
+    ];
+
+    let program = libc::sock_fprog {
+        len: program.len() as _,
+        filter: program.as_ptr() as *mut _,
+    };
+
+    let res = unsafe {
+        libc::setsockopt(
+            fd.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_ATTACH_FILTER,
+            &program as *const libc::sock_fprog as *const _,
+            core::mem::size_of_val(&program) as _,
+        )
+    };
+    if res < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[inline]
+fn setsockopt<T>(fd: &OwnedFd, name: libc::c_int, value: &T) -> io::Result<()> {
+    let res = unsafe {
+        libc::setsockopt(
+            fd.as_raw_fd(),
+            libc::SOL_PACKET,
+            name,
+            value as *const T as *const _,
+            core::mem::size_of::<T>() as _,
+        )
+    };
+    if res < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Decides which datagrams the kernel would have delivered to the bound UDP socket
+#[derive(Clone, Copy, Debug)]
+struct Filter {
+    local_address: SocketAddress,
+    dual_stack: bool,
+    // stores a copy of Checksum so we don't have to probe the platform function for every frame
+    checksum: Checksum,
+}
+
+impl Filter {
+    #[inline]
+    fn new(local_address: SocketAddress, dual_stack: bool) -> Self {
+        Self {
+            local_address,
+            dual_stack,
+            checksum: Checksum::default(),
+        }
+    }
+
+    /// Returns `true` if the datagram is addressed to the local address
+    #[inline]
+    fn is_match(&self, path: &Tuple) -> bool {
+        if path.local_address.port != self.local_address.port() {
+            return false;
+        }
+
+        match (self.local_address, path.local_address.ip) {
+            (SocketAddress::IpV4(addr), IpAddress::Ipv4(ip)) => {
+                addr.ip().is_unspecified() || *addr.ip() == ip
+            }
+            // IPv4 sockets don't receive IPv6 datagrams
+            (SocketAddress::IpV4(_), IpAddress::Ipv6(_)) => false,
+            (SocketAddress::IpV6(addr), IpAddress::Ipv6(ip)) => {
+                addr.ip().is_unspecified() || *addr.ip() == ip
+            }
+            // IPv6 sockets only receive IPv4 datagrams in dual-stack mode
+            (SocketAddress::IpV6(_), IpAddress::Ipv4(_)) if !self.dual_stack => false,
+            (SocketAddress::IpV6(addr), ip) => {
+                addr.ip().is_unspecified() || IpAddress::Ipv6(*addr.ip()).unmap() == ip
+            }
+        }
+    }
+}
+
+/// Decodes the UDP datagram in the Ethernet frame, if it's addressed to the local address
+///
+/// If `verify_checksum` is set, datagrams with an invalid UDP checksum are discarded.
+#[inline]
+fn decode<'a>(
+    frame: &'a [u8],
+    filter: &Filter,
+    verify_checksum: bool,
+) -> Option<(datagram::Header<Tuple>, &'a [u8])> {
+    let mut fields = Fields {
+        header: datagram::Header::new(Tuple::UNSPECIFIED, Default::default()),
+        checksum: filter.checksum,
+        expected_checksum: 0,
+        is_ipv6: false,
+    };
+
+    let payload =
+        decoder::decode_packet_with_event(DecoderBuffer::new(frame), &mut fields).ok()??;
+    let payload = payload.into_less_safe_slice();
+
+    if !filter.is_match(&fields.header.path) {
+        return None;
+    }
+
+    if verify_checksum && !fields.is_checksum_valid(payload) {
+        return None;
+    }
+
+    Some((fields.header, payload))
+}
+
+/// The fields of a frame needed to deliver its UDP datagram
+struct Fields {
+    header: datagram::Header<Tuple>,
+    /// The checksum of the pseudo-header and the UDP header
+    checksum: Checksum,
+    /// The checksum transmitted in the UDP header
+    expected_checksum: u16,
+    is_ipv6: bool,
+}
+
+impl Fields {
+    /// Returns `true` if the UDP checksum matches the payload
+    #[inline]
+    fn is_checksum_valid(&self, payload: &[u8]) -> bool {
+        // NOTE: duvet doesn't know how to parse this RFC since it doesn't follow more modern formatting
+        //# https://www.rfc-editor.org/rfc/rfc768#Fields
+        //# An all zero  transmitted
+        //# checksum  value means that the transmitter  generated  no checksum  (for
+        //# debugging or for higher level protocols that don't care).
+        if self.expected_checksum == 0 {
+            //= https://www.rfc-editor.org/rfc/rfc8200#section-8.1
+            //# IPv6 receivers must discard UDP packets containing a zero checksum
+            //# and should log the error.
+            return !self.is_ipv6;
+        }
+
+        let mut checksum = self.checksum;
+        checksum.write_padded(payload);
+        checksum.finish() == self.expected_checksum
+    }
+}
+
+impl EventHandler for Fields {
+    #[inline(always)]
+    fn on_ethernet_header(&mut self, header: &ethernet::Header) -> decoder::Result {
+        self.header.on_ethernet_header(header)
+    }
+
+    #[inline(always)]
+    fn on_ipv4_header(&mut self, header: &ipv4::Header) -> decoder::Result {
+        // the UDP-Lite checksum covers a different range of the datagram
+        if *header.protocol() != ip::Protocol::UDP {
+            return Ok(None);
+        }
+
+        self.header.on_ipv4_header(header)?;
+
+        // the addresses start at byte offset 12 in the header
+        self.checksum.write(&header.as_bytes()[12..]);
+
+        Ok(Some(()))
+    }
+
+    #[inline(always)]
+    fn on_ipv6_header(&mut self, header: &ipv6::Header) -> decoder::Result {
+        // the UDP-Lite checksum covers a different range of the datagram
+        if *header.next_header() != ip::Protocol::UDP {
+            return Ok(None);
+        }
+
+        self.header.on_ipv6_header(header)?;
+        self.is_ipv6 = true;
+
+        // the addresses start at byte offset 8 in the header
+        self.checksum.write(&header.as_bytes()[8..]);
+
+        Ok(Some(()))
+    }
+
+    #[inline(always)]
+    fn on_udp_header(&mut self, header: &udp::Header) -> decoder::Result {
+        self.header.on_udp_header(header)?;
+
+        let len = header.len().get();
+
+        // finish the pseudo-header with the protocol and UDP length
+        if self.is_ipv6 {
+            let mut parts = [0; 8];
+            parts[..4].copy_from_slice(&(len as u32).to_be_bytes());
+            parts[7] = ip::Protocol::UDP.id;
+            self.checksum.write(&parts);
+        } else {
+            let len = len.to_be_bytes();
+            self.checksum
+                .write(&[0, ip::Protocol::UDP.id, len[0], len[1]]);
+        }
+
+        // the checksum is computed with the checksum field set to zero
+        let mut header = *header;
+        self.expected_checksum = header.checksum().get();
+        header.checksum_mut().set(0);
+        self.checksum.write(header.as_bytes());
+
+        Ok(Some(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::inet::{ExplicitCongestionNotification, IpV4Address, SocketAddressV4};
+
+    /// Builds an Ethernet frame containing an IPv4 UDP datagram with a valid checksum
+    fn frame(source: [u8; 4], destination: [u8; 4], port: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![];
+
+        // ethernet header
+        frame.extend_from_slice(&[0; 12]);
+        frame.extend_from_slice(&0x0800u16.to_be_bytes());
+
+        // ipv4 header
+        let total_len = 20 + 8 + payload.len() as u16;
+        frame.extend_from_slice(&[0x45, ExplicitCongestionNotification::Ect0 as u8]);
+        frame.extend_from_slice(&total_len.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0, 0, 64, 17, 0, 0]);
+        frame.extend_from_slice(&source);
+        frame.extend_from_slice(&destination);
+
+        let mut pseudo_header = vec![];
+        pseudo_header.extend_from_slice(&source);
+        pseudo_header.extend_from_slice(&destination);
+        pseudo_header.extend_from_slice(&[0, 17]);
+        pseudo_header.extend_from_slice(&(8 + payload.len() as u16).to_be_bytes());
+
+        udp(&mut frame, &pseudo_header, port, payload);
+        frame
+    }
+
+    /// Builds an Ethernet frame containing an IPv6 UDP datagram with a valid checksum
+    fn frame_v6(source: [u8; 16], destination: [u8; 16], port: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![];
+
+        // ethernet header
+        frame.extend_from_slice(&[0; 12]);
+        frame.extend_from_slice(&0x86ddu16.to_be_bytes());
+
+        // ipv6 header
+        let payload_len = 8 + payload.len() as u16;
+        frame.extend_from_slice(&[0x60, 0, 0, 0]);
+        frame.extend_from_slice(&payload_len.to_be_bytes());
+        frame.extend_from_slice(&[17, 64]);
+        frame.extend_from_slice(&source);
+        frame.extend_from_slice(&destination);
+
+        let mut pseudo_header = vec![];
+        pseudo_header.extend_from_slice(&source);
+        pseudo_header.extend_from_slice(&destination);
+        pseudo_header.extend_from_slice(&(payload_len as u32).to_be_bytes());
+        pseudo_header.extend_from_slice(&[0, 0, 0, 17]);
+
+        udp(&mut frame, &pseudo_header, port, payload);
+        frame
+    }
+
+    /// Appends the UDP header and payload to the frame
+    fn udp(frame: &mut Vec<u8>, pseudo_header: &[u8], port: u16, payload: &[u8]) {
+        let mut datagram = vec![];
+        datagram.extend_from_slice(&4433u16.to_be_bytes());
+        datagram.extend_from_slice(&port.to_be_bytes());
+        datagram.extend_from_slice(&(8 + payload.len() as u16).to_be_bytes());
+        datagram.extend_from_slice(&[0, 0]);
+        datagram.extend_from_slice(payload);
+
+        let mut checksum = Checksum::default();
+        checksum.write(pseudo_header);
+        checksum.write_padded(&datagram);
+        datagram[6..8].copy_from_slice(&checksum.finish().to_be_bytes());
+
+        frame.extend_from_slice(&datagram);
+    }
+
+    fn local(ip: [u8; 4], port: u16) -> Filter {
+        Filter::new(
+            SocketAddressV4::new(IpV4Address::new(ip), port).into(),
+            false,
+        )
+    }
+
+    fn local_v6(addr: &str, dual_stack: bool) -> Filter {
+        let addr: std::net::SocketAddr = addr.parse().unwrap();
+        Filter::new(addr.into(), dual_stack)
+    }
+
+    #[test]
+    fn decode_test() {
+        let frame = frame([10, 0, 0, 1], [10, 0, 0, 2], 443, b"hello");
+
+        let (header, payload) = decode(&frame, &local([10, 0, 0, 2], 443), true).unwrap();
+        assert_eq!(payload, b"hello");
+        assert_eq!(header.ecn, ExplicitCongestionNotification::Ect0);
+        assert_eq!(
+            header.path.remote_address.ip,
+            IpAddress::Ipv4(IpV4Address::new([10, 0, 0, 1]))
+        );
+        assert_eq!(header.path.remote_address.port, 4433);
+
+        // unspecified addresses match any destination with the same port
+        assert!(decode(&frame, &local([0, 0, 0, 0], 443), true).is_some());
+
+        // datagrams for other ports or addresses are ignored
+        assert!(decode(&frame, &local([10, 0, 0, 2], 444), true).is_none());
+        assert!(decode(&frame, &local([10, 0, 0, 3], 443), true).is_none());
+        assert!(decode(&frame, &local_v6("[::1]:443", true), true).is_none());
+    }
+
+    #[test]
+    fn dual_stack_test() {
+        let frame = frame([10, 0, 0, 1], [10, 0, 0, 2], 443, b"hello");
+
+        // IPv4 datagrams are only delivered to IPv6 sockets in dual-stack mode
+        assert!(decode(&frame, &local_v6("[::]:443", true), true).is_some());
+        assert!(decode(&frame, &local_v6("[::]:443", false), true).is_none());
+
+        // IPv4-mapped addresses match the IPv4 destination
+        assert!(decode(&frame, &local_v6("[::ffff:10.0.0.2]:443", true), true).is_some());
+        assert!(decode(&frame, &local_v6("[::ffff:10.0.0.3]:443", true), true).is_none());
+    }
+
+    #[test]
+    fn ipv6_test() {
+        let mut source = [0; 16];
+        source[15] = 1;
+        let mut destination = [0; 16];
+        destination[15] = 2;
+        let frame = frame_v6(source, destination, 443, b"hello");
+
+        let (_header, payload) = decode(&frame, &local_v6("[::2]:443", false), true).unwrap();
+        assert_eq!(payload, b"hello");
+        assert!(decode(&frame, &local_v6("[::]:443", false), true).is_some());
+        assert!(decode(&frame, &local_v6("[::3]:443", true), true).is_none());
+        assert!(decode(&frame, &local([0, 0, 0, 0], 443), true).is_none());
+    }
+
+    #[test]
+    fn checksum_test() {
+        let filter = local([10, 0, 0, 2], 443);
+        let valid = frame([10, 0, 0, 1], [10, 0, 0, 2], 443, b"hello");
+
+        // corrupt a byte of the payload
+        let mut invalid = valid.clone();
+        *invalid.last_mut().unwrap() ^= 1;
+        assert!(decode(&invalid, &filter, true).is_none());
+        // the checksum isn't checked if the kernel already verified it
+        assert!(decode(&invalid, &filter, false).is_some());
+
+        // a zero checksum means the IPv4 sender didn't compute one
+        let mut no_checksum = invalid.clone();
+        no_checksum[40..42].copy_from_slice(&[0, 0]);
+        assert!(decode(&no_checksum, &filter, true).is_some());
+
+        // odd-length payloads are padded
+        let odd = frame([10, 0, 0, 1], [10, 0, 0, 2], 443, b"hello!!");
+        assert!(decode(&odd, &filter, true).is_some());
+
+        // IPv6 datagrams require a checksum
+        let mut frame = frame_v6([1; 16], [2; 16], 443, b"hello");
+        let filter = local_v6("[::]:443", false);
+        assert!(decode(&frame, &filter, true).is_some());
+        frame[60..62].copy_from_slice(&[0, 0]);
+        assert!(decode(&frame, &filter, true).is_none());
+    }
+
+    #[test]
+    fn truncated_frame_test() {
+        let frame = frame([10, 0, 0, 1], [10, 0, 0, 2], 443, b"hello");
+
+        for len in 0..frame.len() {
+            assert!(decode(&frame[..len], &local([10, 0, 0, 2], 443), true).is_none());
+        }
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Counters for the interactions between an endpoint and the kernel
+//!
+//! The socket tasks and the rings which connect them to the endpoint record what they observe
+//! into a shared [`Stats`] handle, which the application can query at any time to monitor the
+//! health of the IO provider.
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A handle to the socket statistics of an endpoint
+///
+/// The handle can be cheaply cloned and queried from any thread. The default handle is disabled:
+/// it doesn't record anything and all of its counters read as zero.
+#[derive(Clone, Debug, Default)]
+pub struct Stats(Option<Arc<Counters>>);
+
+#[derive(Debug, Default)]
+struct Counters {
+    tx_syscalls: AtomicU64,
+    rx_syscalls: AtomicU64,
+    tx_messages: AtomicU64,
+    rx_messages: AtomicU64,
+    tx_segments: AtomicU64,
+    rx_segments: AtomicU64,
+    rx_gro_batches: AtomicU64,
+    tx_would_block: AtomicU64,
+    rx_would_block: AtomicU64,
+    tx_errors: AtomicU64,
+    tx_ring_full: AtomicU64,
+    rx_ring_full: AtomicU64,
+}
+
+/// The values of the socket statistics at a point in time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Snapshot {
+    /// The number of send syscalls performed
+    pub tx_syscalls: u64,
+    /// The number of receive syscalls performed
+    pub rx_syscalls: u64,
+    /// The number of messages which were accepted by the kernel
+    pub tx_messages: u64,
+    /// The number of messages which were read from the sockets
+    pub rx_messages: u64,
+    /// The number of datagrams written to the transmit rings
+    ///
+    /// Each GSO segment is counted separately, so comparing this value to `tx_messages` shows how
+    /// many datagrams were sent per message.
+    pub tx_segments: u64,
+    /// The number of datagrams read from the receive rings, with each GRO segment counted
+    /// separately
+    pub rx_segments: u64,
+    /// The number of received messages which contained more than one datagram
+    pub rx_gro_batches: u64,
+    /// The number of times a send syscall failed with `EAGAIN`
+    pub tx_would_block: u64,
+    /// The number of times a receive syscall failed with `EAGAIN`
+    pub rx_would_block: u64,
+    /// The number of messages which were discarded after the socket returned an error
+    pub tx_errors: u64,
+    /// The number of datagrams which couldn't be written because all of the transmit rings were
+    /// full
+    ///
+    /// The endpoint tries to transmit these datagrams again once the rings have capacity.
+    pub tx_ring_full: u64,
+    /// The number of times the receive tasks stopped reading because their ring was full
+    ///
+    /// Datagrams which arrive in the meantime are queued in the socket receive buffer and are
+    /// dropped by the kernel once it's full.
+    pub rx_ring_full: u64,
+}
+
+macro_rules! counters {
+    ($($name:ident => $field:ident),* $(,)?) => {
+        $(
+            #[inline]
+            pub(crate) fn $name(&self, count: usize) {
+                if let Some(counters) = self.0.as_ref() {
+                    counters.$field.fetch_add(count as u64, Ordering::Relaxed);
+                }
+            }
+        )*
+    };
+}
+
+impl Stats {
+    /// Creates a handle which records the socket statistics
+    pub fn new() -> Self {
+        Self(Some(Default::default()))
+    }
+
+    /// Returns `true` if the handle records the socket statistics
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Returns the current values of the counters
+    pub fn snapshot(&self) -> Snapshot {
+        let Some(counters) = self.0.as_ref() else {
+            return Snapshot::default();
+        };
+
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+
+        Snapshot {
+            tx_syscalls: load(&counters.tx_syscalls),
+            rx_syscalls: load(&counters.rx_syscalls),
+            tx_messages: load(&counters.tx_messages),
+            rx_messages: load(&counters.rx_messages),
+            tx_segments: load(&counters.tx_segments),
+            rx_segments: load(&counters.rx_segments),
+            rx_gro_batches: load(&counters.rx_gro_batches),
+            tx_would_block: load(&counters.tx_would_block),
+            rx_would_block: load(&counters.rx_would_block),
+            tx_errors: load(&counters.tx_errors),
+            tx_ring_full: load(&counters.tx_ring_full),
+            rx_ring_full: load(&counters.rx_ring_full),
+        }
+    }
+
+    counters!(
+        on_tx_syscall => tx_syscalls,
+        on_rx_syscall => rx_syscalls,
+        on_tx_messages => tx_messages,
+        on_rx_messages => rx_messages,
+        on_tx_segments => tx_segments,
+        on_rx_segments => rx_segments,
+        on_rx_gro_batch => rx_gro_batches,
+        on_tx_would_block => tx_would_block,
+        on_rx_would_block => rx_would_block,
+        on_tx_error => tx_errors,
+        on_tx_ring_full => tx_ring_full,
+        on_rx_ring_full => rx_ring_full,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_test() {
+        let stats = Stats::new();
+        let handle = stats.clone();
+
+        stats.on_tx_syscall(1);
+        stats.on_tx_messages(2);
+        stats.on_tx_segments(8);
+        stats.on_rx_would_block(1);
+        stats.on_rx_would_block(1);
+
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.tx_syscalls, 1);
+        assert_eq!(snapshot.tx_messages, 2);
+        assert_eq!(snapshot.tx_segments, 8);
+        assert_eq!(snapshot.rx_would_block, 2);
+        assert_eq!(snapshot.rx_syscalls, 0);
+    }
+
+    #[test]
+    fn disabled_test() {
+        let stats = Stats::default();
+        assert!(!stats.is_enabled());
+
+        stats.on_tx_syscall(1);
+        stats.on_rx_ring_full(1);
+        assert_eq!(stats.snapshot(), Snapshot::default());
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::features::Gso;
+use crate::{features::Gso, socket::stats::Stats};
 use core::ops::ControlFlow;
 
 #[derive(Debug)]
@@ -9,6 +9,7 @@ pub struct TxEvents {
     count: usize,
     is_blocked: bool,
     gso: Gso,
+    stats: Stats,
 }
 
 impl TxEvents {
@@ -18,9 +19,17 @@ impl TxEvents {
             count: 0,
             is_blocked: false,
             gso,
+            stats: Stats::default(),
         }
     }
 
+    /// Records the socket operations into the provided statistics
+    #[inline]
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.stats = stats;
+        self
+    }
+
     /// Returns if the task is blocked
     #[inline]
     pub fn is_blocked(&self) -> bool {
@@ -37,6 +46,7 @@ impl TxEvents {
     #[inline]
     pub fn blocked(&mut self) {
         self.is_blocked = true;
+        self.stats.on_tx_would_block(1);
     }
 
     /// Returns and resets the number of messages sent
@@ -49,6 +59,9 @@ impl TxEvents {
 impl crate::syscall::SocketEvents for TxEvents {
     #[inline]
     fn on_complete(&mut self, count: usize) -> ControlFlow<(), ()> {
+        self.stats.on_tx_syscall(1);
+        self.stats.on_tx_messages(count);
+
         // increment the total sent packets and reset our blocked status
         self.count += count;
         self.is_blocked = false;
@@ -59,10 +72,13 @@ impl crate::syscall::SocketEvents for TxEvents {
     fn on_error(&mut self, error: ::std::io::Error) -> ControlFlow<(), ()> {
         use std::io::ErrorKind::*;
 
+        self.stats.on_tx_syscall(1);
+
         match error.kind() {
             WouldBlock => {
                 // record that we're blocked
                 self.is_blocked = true;
+                self.stats.on_tx_would_block(1);
                 ControlFlow::Break(())
             }
             Interrupted => {
@@ -76,6 +92,7 @@ impl crate::syscall::SocketEvents for TxEvents {
 
                 // ignore all other errors and just consider the packet sent
                 self.count += 1;
+                self.stats.on_tx_error(1);
 
                 // We `continue` instead of break because it's very unlikely the message would be
                 // accepted at a later time, so we just discard the packet.
@@ -89,9 +106,17 @@ impl crate::syscall::SocketEvents for TxEvents {
 pub struct RxEvents {
     count: usize,
     is_blocked: bool,
+    stats: Stats,
 }
 
 impl RxEvents {
+    /// Records the socket operations into the provided statistics
+    #[inline]
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.stats = stats;
+        self
+    }
+
     /// Returns if the task is blocked
     #[inline]
     pub fn is_blocked(&self) -> bool {
@@ -108,6 +133,7 @@ impl RxEvents {
     #[inline]
     pub fn blocked(&mut self) {
         self.is_blocked = true;
+        self.stats.on_rx_would_block(1);
     }
 
     /// Returns and resets the number of messages sent
@@ -115,11 +141,20 @@ impl RxEvents {
     pub fn take_count(&mut self) -> usize {
         core::mem::take(&mut self.count)
     }
+
+    /// Records that the task stopped reading because the ring was full
+    #[inline]
+    pub fn ring_full(&self) {
+        self.stats.on_rx_ring_full(1);
+    }
 }
 
 impl crate::syscall::SocketEvents for RxEvents {
     #[inline]
     fn on_complete(&mut self, count: usize) -> ControlFlow<(), ()> {
+        self.stats.on_rx_syscall(1);
+        self.stats.on_rx_messages(count);
+
         // increment the total sent packets and reset our blocked status
         self.count += count;
         self.is_blocked = false;
@@ -130,10 +165,13 @@ impl crate::syscall::SocketEvents for RxEvents {
     fn on_error(&mut self, error: ::std::io::Error) -> ControlFlow<(), ()> {
         use std::io::ErrorKind::*;
 
+        self.stats.on_rx_syscall(1);
+
         match error.kind() {
             WouldBlock => {
                 // record that we're blocked
                 self.is_blocked = true;
+                self.stats.on_rx_would_block(1);
                 ControlFlow::Break(())
             }
             Interrupted => {
@@ -3,7 +3,7 @@
 
 use crate::{
     message::Message,
-    socket::{ring::Producer, task::events},
+    socket::{ring::Producer, stats::Stats, task::events},
 };
 use core::{
     future::Future,
@@ -29,6 +29,7 @@ pub struct Receiver<T: Message, S: Socket<T>> {
     ring: Producer<T>,
     /// Implementation of a socket that fills free slots in the ring buffer
     rx: S,
+    events: Events,
     ring_cooldown: Cooldown,
     io_cooldown: Cooldown,
 }
@@ -43,11 +44,19 @@ where
         Self {
             ring,
             rx,
+            events: Events::default(),
             ring_cooldown: cooldown.clone(),
             io_cooldown: cooldown,
         }
     }
 
+    /// Records the socket operations of the receiver into the provided statistics
+    #[inline]
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.events = self.events.with_stats(stats);
+        self
+    }
+
     #[inline]
     fn poll_ring(&mut self, watermark: u32, cx: &mut Context) -> Poll<Result<(), ()>> {
         loop {
@@ -73,6 +82,10 @@ where
                 continue;
             }
 
+            if self.ring.is_open() {
+                self.events.ring_full();
+            }
+
             return Poll::Pending;
         }
     }
@@ -89,11 +102,9 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
         let this = self.get_mut();
 
-        let mut events = Events::default();
-
         let mut pending_wake = false;
 
-        while !events.take_blocked() {
+        while !this.events.take_blocked() {
             match this.poll_ring(u32::MAX, cx) {
                 Poll::Ready(Ok(_)) => {}
                 Poll::Ready(Err(_)) => return None.into(),
@@ -108,10 +119,10 @@ where
             let entries = this.ring.data();
 
             // perform the recv syscall
-            match this.rx.recv(cx, entries, &mut events) {
+            match this.rx.recv(cx, entries, &mut this.events) {
                 Ok(_) => {
                     // increment the number of received messages
-                    let count = events.take_count() as u32;
+                    let count = this.events.take_count() as u32;
 
                     if count > 0 {
                         this.ring.release_no_wake(count);
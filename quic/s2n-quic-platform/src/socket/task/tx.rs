@@ -4,7 +4,7 @@
 use crate::{
     features::Gso,
     message::Message,
-    socket::{ring::Consumer, task::events},
+    socket::{ring::Consumer, stats::Stats, task::events},
 };
 use core::{
     future::Future,
@@ -51,6 +51,13 @@ where
         }
     }
 
+    /// Records the socket operations of the sender into the provided statistics
+    #[inline]
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.events = self.events.with_stats(stats);
+        self
+    }
+
     #[inline]
     fn poll_ring(&mut self, watermark: u32, cx: &mut Context) -> Poll<Result<(), ()>> {
         loop {
@@ -60,14 +60,36 @@ pub trait UnixMessage: crate::message::Message {
 }
 
 pub fn udp_socket(addr: std::net::SocketAddr) -> io::Result<Socket> {
+    udp_socket_with_dual_stack(addr, None)
+}
+
+/// Creates a UDP socket for the provided address with the dual-stack mode configured
+///
+/// If `dual_stack` is `None`, IPv6 sockets will attempt to also accept IPv4 traffic, ignoring any
+/// errors. Otherwise, the mode is applied explicitly and any failures are returned.
+pub fn udp_socket_with_dual_stack(
+    addr: std::net::SocketAddr,
+    dual_stack: Option<bool>,
+) -> io::Result<Socket> {
     let domain = Domain::for_address(addr);
     let socket_type = Type::DGRAM;
     let protocol = Some(Protocol::UDP);
 
     let socket = Socket::new(domain, socket_type, protocol)?;
 
-    // allow ipv4 to also connect - ignore the error if it fails
-    let _ = socket.set_only_v6(false);
+    match dual_stack {
+        Some(true) if addr.is_ipv4() => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "dual-stack mode requires an IPv6 bind address",
+            ));
+        }
+        Some(dual_stack) if addr.is_ipv6() => socket.set_only_v6(!dual_stack)?,
+        _ => {
+            // allow ipv4 to also connect - ignore the error if it fails
+            let _ = socket.set_only_v6(false);
+        }
+    }
 
     Ok(socket)
 }
@@ -77,6 +99,45 @@ pub fn bind_udp<A: std::net::ToSocketAddrs>(
     addr: A,
     reuse_address: bool,
     reuse_port: bool,
+) -> io::Result<Socket> {
+    let options = BindOptions {
+        reuse_address,
+        reuse_port,
+        ..Default::default()
+    };
+    bind_udp_with_options(addr, &options)
+}
+
+/// A borrowed view of a socket's file descriptor
+#[cfg(unix)]
+pub type BorrowedSocket<'a> = std::os::fd::BorrowedFd<'a>;
+
+/// A borrowed view of a socket's handle
+#[cfg(windows)]
+pub type BorrowedSocket<'a> = std::os::windows::io::BorrowedSocket<'a>;
+
+/// Callback for applying additional options to a socket before it is bound
+pub type Configurator = dyn Fn(BorrowedSocket) -> io::Result<()> + Send + Sync;
+
+/// Options applied to a UDP socket in [`bind_udp_with_options`]
+#[derive(Clone, Copy, Default)]
+pub struct BindOptions<'a> {
+    /// Enables the address reuse (SO_REUSEADDR) socket option
+    pub reuse_address: bool,
+    /// Enables the port reuse (SO_REUSEPORT) socket option
+    pub reuse_port: bool,
+    /// Configures the dual-stack mode of the socket
+    ///
+    /// See [`udp_socket_with_dual_stack`] for details.
+    pub dual_stack: Option<bool>,
+    /// Called with the socket after all other options are applied but before it is bound
+    pub configurator: Option<&'a Configurator>,
+}
+
+/// Creates a UDP socket bound to the provided address with the given options
+pub fn bind_udp_with_options<A: std::net::ToSocketAddrs>(
+    addr: A,
+    options: &BindOptions,
 ) -> io::Result<Socket> {
     let addr = addr.to_socket_addrs()?.next().ok_or_else(|| {
         std::io::Error::new(
@@ -84,15 +145,21 @@ pub fn bind_udp<A: std::net::ToSocketAddrs>(
             "the provided bind address was empty",
         )
     })?;
-    let socket = udp_socket(addr)?;
+    let socket = udp_socket_with_dual_stack(addr, options.dual_stack)?;
 
-    socket.set_reuse_address(reuse_address)?;
+    socket.set_reuse_address(options.reuse_address)?;
 
     #[cfg(unix)]
-    socket.set_reuse_port(reuse_port)?;
+    socket.set_reuse_port(options.reuse_port)?;
 
-    // mark the variable as "used" regardless of platform support
-    let _ = reuse_port;
+    if let Some(configurator) = options.configurator {
+        #[cfg(unix)]
+        let borrowed = std::os::fd::AsFd::as_fd(&socket);
+        #[cfg(windows)]
+        let borrowed = std::os::windows::io::AsSocket::as_socket(&socket);
+
+        configurator(borrowed)?;
+    }
 
     socket.bind(&addr.into())?;
 
@@ -238,6 +305,23 @@ pub fn configure_pktinfo(rx_socket: &Socket) -> bool {
     success
 }
 
+/// Attaches a socket filter which discards every incoming datagram
+///
+/// This is used when the datagrams are read from another source, e.g. a packet socket, so the
+/// OS doesn't need to queue them for the socket as well.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn attach_drop_filter(rx_socket: &Socket) -> io::Result<()> {
+    // a classic BPF program with a single `ret #0` (BPF_RET | BPF_K) instruction, which accepts
+    // 0 bytes of each packet
+    let filter = libc::sock_filter {
+        code: 0x06,
+        jt: 0,
+        jf: 0,
+        k: 0,
+    };
+    rx_socket.attach_filter(&[filter])
+}
+
 pub fn configure_gro(rx_socket: &Socket) -> bool {
     let mut success = false;
 
@@ -1,16 +1,22 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, session::Session, Error};
-use core::convert::TryFrom;
-use rustls::ClientConfig;
+use crate::{certificate, resumption::SessionStore, session::Session, Error};
+use core::{convert::TryFrom, time::Duration};
+use rustls::{client::Resumption, ClientConfig};
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::{tls, tls::resumption},
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct Client {
     config: Arc<ClientConfig>,
+    /// The config used for hosts that the resumption policy excludes
+    no_resumption: Option<(Arc<resumption::Policy>, Arc<ClientConfig>)>,
+    require_application_protocol: bool,
 }
 
 impl Client {
@@ -18,6 +24,8 @@ impl Client {
     pub fn new(config: ClientConfig) -> Self {
         Self {
             config: Arc::new(config),
+            no_resumption: None,
+            require_application_protocol: false,
         }
     }
 
@@ -45,7 +53,11 @@ impl From<ClientConfig> for Client {
 // TODO this should be removed after removing deprecated re-exports
 impl From<Arc<ClientConfig>> for Client {
     fn from(config: Arc<ClientConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            no_resumption: None,
+            require_application_protocol: false,
+        }
     }
 }
 
@@ -71,15 +83,24 @@ impl tls::Endpoint for Client {
         let rustls_server_name =
             rustls::ServerName::try_from(server_name.as_ref()).expect("invalid server name");
 
+        let config = match &self.no_resumption {
+            Some((policy, config)) if !policy.is_enabled(&server_name) => config.clone(),
+            _ => self.config.clone(),
+        };
+
         let session = rustls::quic::ClientConnection::new(
-            self.config.clone(),
+            config,
             crate::QUIC_VERSION,
             rustls_server_name,
             transport_parameters,
         )
         .expect("could not create rustls client session");
 
-        Session::new(session.into(), Some(server_name))
+        Session::new(
+            session.into(),
+            Some(server_name),
+            self.require_application_protocol,
+        )
     }
 
     fn max_tag_length(&self) -> usize {
@@ -91,6 +112,8 @@ pub struct Builder {
     cert_store: rustls::RootCertStore,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    resumption: resumption::Policy,
+    require_application_protocol: bool,
 }
 
 impl Default for Builder {
@@ -105,6 +128,9 @@ impl Builder {
             cert_store: rustls::RootCertStore::empty(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            // rustls resumes sessions by default so the policy is enabled to preserve that
+            resumption: resumption::Policy::new(true),
+            require_application_protocol: false,
         }
     }
 
@@ -136,11 +162,68 @@ impl Builder {
         Ok(self)
     }
 
+    /// If enabled, the connection is closed with a `no_application_protocol` alert if the
+    /// server doesn't select one of the configured application protocols (default: false)
+    pub fn with_require_application_protocol(mut self, enabled: bool) -> Result<Self, Error> {
+        self.require_application_protocol = enabled;
+        Ok(self)
+    }
+
+    /// Restricts the client to FIPS-approved algorithms
+    ///
+    /// rustls does not use a FIPS-validated crypto backend so enabling FIPS mode always
+    /// returns an error.
+    pub fn with_fips(self, enabled: bool) -> Result<Self, Error> {
+        if enabled {
+            return Err(
+                rustls::Error::General("rustls does not support FIPS mode".to_string()).into(),
+            );
+        }
+        Ok(self)
+    }
+
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         self.key_log = Some(Arc::new(rustls::KeyLogFile::new()));
         Ok(self)
     }
 
+    /// Enables or disables session resumption
+    ///
+    /// When enabled, the client stores session tickets issued by servers and offers them
+    /// on subsequent connections to the same host. Resumption is enabled by default.
+    pub fn with_session_resumption(mut self, enabled: bool) -> Result<Self, Error> {
+        self.resumption.set_enabled(enabled);
+        Ok(self)
+    }
+
+    /// Enables or disables session resumption for hosts matching `pattern`
+    ///
+    /// A pattern is either an exact host name or a wildcard of the form `*.example.com`.
+    /// Patterns configured later take precedence over earlier ones.
+    pub fn with_session_resumption_for_host<P: Into<String>>(
+        mut self,
+        pattern: P,
+        enabled: bool,
+    ) -> Result<Self, Error> {
+        self.resumption.set_host(pattern, enabled);
+        Ok(self)
+    }
+
+    /// Sets the maximum number of session tickets stored across all hosts
+    ///
+    /// The oldest tickets are evicted once the limit is reached.
+    pub fn with_max_session_tickets(mut self, max: usize) -> Result<Self, Error> {
+        self.resumption.set_max_tickets(max);
+        Ok(self)
+    }
+
+    /// Limits how long a session ticket is used after it is received, regardless of the
+    /// lifetime advertised by the server
+    pub fn with_max_session_ticket_lifetime(mut self, lifetime: Duration) -> Result<Self, Error> {
+        self.resumption.set_max_ticket_lifetime(lifetime);
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, Error> {
         // TODO load system root store?
         if self.cert_store.is_empty() {
@@ -165,7 +248,29 @@ impl Builder {
             config.key_log = key_log;
         }
 
-        #[allow(deprecated)]
-        Ok(Client::new(config))
+        if self.resumption.is_disabled() {
+            config.resumption = Resumption::disabled();
+            return Ok(Client {
+                config: Arc::new(config),
+                no_resumption: None,
+                require_application_protocol: self.require_application_protocol,
+            });
+        }
+
+        let no_resumption = if self.resumption.is_host_dependent() {
+            let mut config = config.clone();
+            config.resumption = Resumption::disabled();
+            Some((Arc::new(self.resumption.clone()), Arc::new(config)))
+        } else {
+            None
+        };
+
+        config.resumption = Resumption::store(Arc::new(SessionStore::new(self.resumption)));
+
+        Ok(Client {
+            config: Arc::new(config),
+            no_resumption,
+            require_application_protocol: self.require_application_protocol,
+        })
     }
 }
@@ -1,17 +1,90 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use rustls::Error;
+use rustls::{AlertDescription, CertificateError, Error};
+use s2n_quic_core::crypto::tls;
 
-pub fn reason(error: rustls::Error) -> &'static str {
+/// Converts a rustls error into a TLS alert
+///
+/// The alert emitted by rustls is used if there is one. Otherwise, the alert is derived from the
+/// error so the peer is able to distinguish the failure from a generic internal error.
+pub fn alert(error: &Error, emitted: Option<AlertDescription>) -> tls::Error {
+    let alert = emitted
+        .map(|alert| tls::Error::new(alert.get_u8()))
+        .unwrap_or_else(|| description(error));
+
+    alert.with_reason(reason(error))
+}
+
+fn description(error: &Error) -> tls::Error {
+    match error {
+        Error::InappropriateMessage { .. } | Error::InappropriateHandshakeMessage { .. } => {
+            tls::Error::UNEXPECTED_MESSAGE
+        }
+        Error::InvalidMessage(_) => tls::Error::DECODE_ERROR,
+        Error::NoCertificatesPresented => tls::Error::CERTIFICATE_REQUIRED,
+        Error::UnsupportedNameType => tls::Error::UNRECOGNIZED_NAME,
+        Error::DecryptError => tls::Error::DECRYPT_ERROR,
+        Error::PeerIncompatible(_) => tls::Error::HANDSHAKE_FAILURE,
+        Error::PeerMisbehaved(_) => tls::Error::ILLEGAL_PARAMETER,
+        Error::AlertReceived(alert) => tls::Error::new(alert.get_u8()),
+        Error::InvalidCertificate(error) => match error {
+            CertificateError::BadEncoding
+            | CertificateError::UnhandledCriticalExtension
+            | CertificateError::NotValidForName => tls::Error::BAD_CERTIFICATE,
+            CertificateError::Expired | CertificateError::NotValidYet => {
+                tls::Error::CERTIFICATE_EXPIRED
+            }
+            CertificateError::Revoked => tls::Error::CERTIFICATE_REVOKED,
+            CertificateError::UnknownIssuer => tls::Error::UNKNOWN_CA,
+            CertificateError::BadSignature => tls::Error::DECRYPT_ERROR,
+            CertificateError::InvalidPurpose => tls::Error::UNSUPPORTED_CERTIFICATE,
+            CertificateError::ApplicationVerificationFailure => tls::Error::ACCESS_DENIED,
+            // rustls may add a new variant in the future that breaks us so do a wildcard
+            #[allow(unreachable_patterns)]
+            _ => tls::Error::CERTIFICATE_UNKNOWN,
+        },
+        Error::PeerSentOversizedRecord => tls::Error::RECORD_OVERFLOW,
+        Error::NoApplicationProtocol => tls::Error::NO_APPLICATION_PROTOCOL,
+        // rustls may add a new variant in the future that breaks us so do a wildcard
+        #[allow(unreachable_patterns)]
+        _ => tls::Error::INTERNAL_ERROR,
+    }
+}
+
+pub fn reason(error: &Error) -> &'static str {
     match error {
         Error::InappropriateMessage { .. } => "received unexpected message",
         Error::InappropriateHandshakeMessage { .. } => "received unexpected handshake message",
+        Error::InvalidMessage(_) => "received invalid message",
         Error::NoCertificatesPresented => "peer sent no certificates",
         Error::UnsupportedNameType => "unsupported name type",
         Error::DecryptError => "cannot decrypt peer's message",
         Error::EncryptError => "cannot encrypt local message",
+        Error::PeerIncompatible(_) => "peer is incompatible",
+        Error::PeerMisbehaved(_) => "peer misbehaved",
         Error::AlertReceived(_) => "received fatal alert",
+        Error::InvalidCertificate(error) => match error {
+            CertificateError::BadEncoding => "peer sent a malformed certificate",
+            CertificateError::Expired => "peer certificate is expired",
+            CertificateError::NotValidYet => "peer certificate is not valid yet",
+            CertificateError::Revoked => "peer certificate is revoked",
+            CertificateError::UnhandledCriticalExtension => {
+                "peer certificate contains an unsupported critical extension"
+            }
+            CertificateError::UnknownIssuer => "peer certificate is issued by an unknown authority",
+            CertificateError::BadSignature => "peer certificate has an invalid signature",
+            CertificateError::NotValidForName => {
+                "peer certificate is not valid for the server name"
+            }
+            CertificateError::InvalidPurpose => "peer certificate is not valid for this purpose",
+            CertificateError::ApplicationVerificationFailure => {
+                "peer certificate was rejected by the application"
+            }
+            // rustls may add a new variant in the future that breaks us so do a wildcard
+            #[allow(unreachable_patterns)]
+            _ => "invalid peer certificate",
+        },
         Error::InvalidSct(_) => "invalid certificate timestamp",
         Error::FailedToGetCurrentTime => "failed to get current time",
         Error::FailedToGetRandomBytes => "failed to get random bytes",
@@ -25,3 +98,51 @@ pub fn reason(error: rustls::Error) -> &'static str {
         _ => "unexpected error",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_test() {
+        // alerts emitted by rustls take priority
+        let error = alert(
+            &Error::NoApplicationProtocol,
+            Some(AlertDescription::HandshakeFailure),
+        );
+        assert_eq!(error.code, tls::Error::HANDSHAKE_FAILURE.code);
+        assert_eq!(error.reason, "peer doesn't support any known protocol");
+
+        for (error, expected) in [
+            (
+                Error::NoApplicationProtocol,
+                tls::Error::NO_APPLICATION_PROTOCOL,
+            ),
+            (
+                Error::InvalidCertificate(CertificateError::UnknownIssuer),
+                tls::Error::UNKNOWN_CA,
+            ),
+            (
+                Error::InvalidCertificate(CertificateError::Expired),
+                tls::Error::CERTIFICATE_EXPIRED,
+            ),
+            (
+                Error::InvalidCertificate(CertificateError::InvalidPurpose),
+                tls::Error::UNSUPPORTED_CERTIFICATE,
+            ),
+            (
+                Error::NoCertificatesPresented,
+                tls::Error::CERTIFICATE_REQUIRED,
+            ),
+            (
+                Error::General("unexpected".to_string()),
+                tls::Error::INTERNAL_ERROR,
+            ),
+        ] {
+            let reason = reason(&error);
+            let error = alert(&error, None);
+            assert_eq!(error.code, expected.code);
+            assert_eq!(error.reason, reason);
+        }
+    }
+}
@@ -17,6 +17,7 @@ type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 mod cipher_suite;
 mod error;
+mod resumption;
 mod session;
 
 pub mod certificate;
@@ -40,7 +41,10 @@ const QUIC_VERSION: rustls::quic::Version = rustls::quic::Version::V1;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use s2n_quic_core::crypto::tls::{self, testing::certificates::*};
+    use s2n_quic_core::{
+        crypto::tls::{self, testing::certificates::*},
+        transport,
+    };
 
     #[test]
     fn client_server_test() {
@@ -64,4 +68,40 @@ mod tests {
 
         pair.finish();
     }
+
+    #[test]
+    fn require_application_protocol_test() {
+        for (client_required, server_required) in [(true, false), (false, true)] {
+            let mut client = client::Builder::new()
+                .with_certificate(CERT_PEM)
+                .unwrap()
+                .with_require_application_protocol(client_required)
+                .unwrap()
+                .build()
+                .unwrap();
+
+            // the server doesn't support any application protocols so none is negotiated
+            let mut server = server::Builder::new()
+                .with_certificate(CERT_PEM, KEY_PEM)
+                .unwrap()
+                .with_application_protocols(core::iter::empty::<&[u8]>())
+                .unwrap()
+                .with_require_application_protocol(server_required)
+                .unwrap()
+                .build()
+                .unwrap();
+
+            let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+            let error = loop {
+                assert!(pair.is_handshaking(), "the handshake should fail");
+                if let Err(error) = pair.poll(None) {
+                    break error;
+                }
+            };
+
+            let expected: transport::Error = tls::Error::NO_APPLICATION_PROTOCOL.into();
+            assert_eq!(error.code, expected.code);
+        }
+    }
 }
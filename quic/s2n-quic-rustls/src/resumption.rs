@@ -0,0 +1,273 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use rustls::client::StoresClientSessions;
+use s2n_codec::{u24, DecoderBuffer, DecoderError};
+use s2n_quic_core::crypto::tls::resumption::Policy;
+use std::{collections::VecDeque, sync::Mutex, time::Instant};
+
+/// A client session store which applies the limits of a resumption [`Policy`]
+///
+/// rustls tracks the lifetime advertised by the server itself; the store only enforces the
+/// configured upper bound on top of it.
+pub(crate) struct SessionStore {
+    policy: Policy,
+    entries: Mutex<VecDeque<Entry>>,
+}
+
+struct Entry {
+    key: Vec<u8>,
+    value: Vec<u8>,
+    inserted_at: Instant,
+}
+
+impl SessionStore {
+    pub fn new(policy: Policy) -> Self {
+        Self {
+            policy,
+            entries: Default::default(),
+        }
+    }
+}
+
+impl StoresClientSessions for SessionStore {
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+
+        entries.retain(|entry| entry.key != key);
+
+        // evict the oldest entries first
+        while entries.len() >= self.policy.max_tickets() {
+            if entries.pop_front().is_none() {
+                break;
+            }
+        }
+
+        entries.push_back(Entry {
+            key,
+            value,
+            inserted_at: Instant::now(),
+        });
+
+        true
+    }
+
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().unwrap();
+
+        if let Some(max) = self.policy.max_ticket_lifetime() {
+            entries.retain(|entry| entry.inserted_at.elapsed() <= max);
+        }
+
+        entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.clone())
+    }
+}
+
+const CLIENT_HELLO: u8 = 1;
+const SERVER_HELLO: u8 = 2;
+
+//= https://www.rfc-editor.org/rfc/rfc8446#section-4.2
+//#     pre_shared_key(41),                         /* RFC 8446 */
+const PRE_SHARED_KEY: u16 = 41;
+
+/// Tracks whether a session ticket was offered and accepted during the handshake
+///
+/// rustls doesn't report if a session was resumed, so the hello messages exchanged in the
+/// Initial space are inspected instead. A client offers a ticket with the `pre_shared_key`
+/// extension in its ClientHello and the server only echoes the extension in its ServerHello if
+/// it accepted the ticket.
+#[derive(Debug, Default)]
+pub(crate) struct Tracker {
+    /// Initial crypto data received from the peer which doesn't form a complete message yet
+    received: Vec<u8>,
+    offered: bool,
+    accepted: bool,
+}
+
+impl Tracker {
+    /// Returns `true` if a ClientHello offering a session ticket was sent
+    #[inline]
+    pub fn offered(&self) -> bool {
+        self.offered
+    }
+
+    /// Returns `true` if the ServerHello accepted the offered session ticket
+    #[inline]
+    pub fn accepted(&self) -> bool {
+        self.accepted
+    }
+
+    /// Called with the Initial crypto data written by the local endpoint
+    ///
+    /// Returns `true` the first time a ClientHello offering a session ticket is sent.
+    pub fn on_sent(&mut self, data: &[u8]) -> bool {
+        let offered = self.offered;
+        // rustls always writes complete handshake messages
+        let _ = self.on_messages(DecoderBuffer::new(data));
+        !offered && self.offered
+    }
+
+    /// Called with the Initial crypto data received from the peer
+    pub fn on_received(&mut self, data: &[u8]) {
+        self.received.extend_from_slice(data);
+
+        let buffer = core::mem::take(&mut self.received);
+        let remaining = self.on_messages(DecoderBuffer::new(&buffer));
+        self.received = remaining.into_less_safe_slice().to_vec();
+    }
+
+    /// Processes each complete handshake message and returns the remaining bytes
+    fn on_messages<'a>(&mut self, mut buffer: DecoderBuffer<'a>) -> DecoderBuffer<'a> {
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4
+        //# struct {
+        //#     HandshakeType msg_type;    /* handshake type */
+        //#     uint24 length;             /* remaining bytes in message */
+        //#     select (Handshake.msg_type) {
+        while let Ok((msg_type, remaining)) = buffer.decode::<u8>() {
+            let Ok((len, remaining)) = remaining.decode::<u24>() else {
+                break;
+            };
+            let Ok((body, remaining)) = remaining.decode_slice(u32::from(len) as usize) else {
+                break;
+            };
+            buffer = remaining;
+
+            let has_pre_shared_key = has_pre_shared_key(msg_type, body).unwrap_or(false);
+            match msg_type {
+                CLIENT_HELLO => self.offered |= has_pre_shared_key,
+                // a HelloRetryRequest never includes the extension and is followed by another
+                // ServerHello, so the last one determines the outcome
+                SERVER_HELLO => self.accepted = has_pre_shared_key,
+                _ => {}
+            }
+        }
+
+        buffer
+    }
+}
+
+/// Returns `true` if the hello message includes the `pre_shared_key` extension
+fn has_pre_shared_key(msg_type: u8, body: DecoderBuffer) -> Result<bool, DecoderError> {
+    //= https://www.rfc-editor.org/rfc/rfc8446#section-4.1.2
+    //# struct {
+    //#     ProtocolVersion legacy_version = 0x0303;    /* TLS v1.2 */
+    //#     Random random;
+    //#     opaque legacy_session_id<0..32>;
+    //#     CipherSuite cipher_suites<2..2^16-2>;
+    //#     opaque legacy_compression_methods<1..2^8-1>;
+    //#     Extension extensions<8..2^16-1>;
+    //# } ClientHello;
+    //
+    //= https://www.rfc-editor.org/rfc/rfc8446#section-4.1.3
+    //# struct {
+    //#     ProtocolVersion legacy_version = 0x0303;    /* TLS v1.2 */
+    //#     Random random;
+    //#     opaque legacy_session_id_echo<0..32>;
+    //#     CipherSuite cipher_suite;
+    //#     uint8 legacy_compression_method = 0;
+    //#     Extension extensions<6..2^16-1>;
+    //# } ServerHello;
+    let body = body.skip(2 + 32)?;
+    let body = body.skip_with_len_prefix::<u8>()?;
+    let body = match msg_type {
+        CLIENT_HELLO => body
+            .skip_with_len_prefix::<u16>()?
+            .skip_with_len_prefix::<u8>()?,
+        SERVER_HELLO => body.skip(2 + 1)?,
+        _ => return Ok(false),
+    };
+    let (mut extensions, _) = body.decode_slice_with_len_prefix::<u16>()?;
+
+    while !extensions.is_empty() {
+        let (extension_type, remaining) = extensions.decode::<u16>()?;
+        if extension_type == PRE_SHARED_KEY {
+            return Ok(true);
+        }
+        extensions = remaining.skip_with_len_prefix::<u16>()?;
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a hello message with the given extension types
+    fn hello(msg_type: u8, extensions: &[u16]) -> Vec<u8> {
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0; 32]);
+        // legacy_session_id
+        body.push(0);
+        if msg_type == CLIENT_HELLO {
+            body.extend_from_slice(&[0x00, 0x02, 0x13, 0x01]);
+            body.extend_from_slice(&[0x01, 0x00]);
+        } else {
+            body.extend_from_slice(&[0x13, 0x01, 0x00]);
+        }
+
+        let mut encoded_extensions = vec![];
+        for extension_type in extensions {
+            encoded_extensions.extend_from_slice(&extension_type.to_be_bytes());
+            encoded_extensions.extend_from_slice(&[0x00, 0x01, 0xff]);
+        }
+        body.extend_from_slice(&(encoded_extensions.len() as u16).to_be_bytes());
+        body.extend_from_slice(&encoded_extensions);
+
+        let mut message = vec![msg_type];
+        message.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        message.extend_from_slice(&body);
+        message
+    }
+
+    #[test]
+    fn client_test() {
+        let mut tracker = Tracker::default();
+        assert!(!tracker.on_sent(&hello(CLIENT_HELLO, &[43, 51])));
+        assert!(!tracker.offered());
+
+        // the attempt is only reported once
+        assert!(tracker.on_sent(&hello(CLIENT_HELLO, &[43, 51, PRE_SHARED_KEY])));
+        assert!(!tracker.on_sent(&hello(CLIENT_HELLO, &[43, PRE_SHARED_KEY])));
+        assert!(tracker.offered());
+
+        // the ServerHello may be split across several CRYPTO frames
+        let server_hello = hello(SERVER_HELLO, &[43, PRE_SHARED_KEY]);
+        let (first, second) = server_hello.split_at(10);
+        tracker.on_received(first);
+        assert!(!tracker.accepted());
+        tracker.on_received(second);
+        assert!(tracker.accepted());
+    }
+
+    #[test]
+    fn rejected_test() {
+        let mut tracker = Tracker::default();
+        assert!(tracker.on_sent(&hello(CLIENT_HELLO, &[PRE_SHARED_KEY])));
+        tracker.on_received(&hello(SERVER_HELLO, &[43, 51]));
+        assert!(tracker.offered());
+        assert!(!tracker.accepted());
+    }
+
+    #[test]
+    fn server_test() {
+        let mut tracker = Tracker::default();
+        tracker.on_received(&hello(CLIENT_HELLO, &[PRE_SHARED_KEY]));
+        assert!(!tracker.on_sent(&hello(SERVER_HELLO, &[PRE_SHARED_KEY])));
+        assert!(!tracker.offered());
+        assert!(tracker.accepted());
+    }
+
+    #[test]
+    fn malformed_test() {
+        let mut tracker = Tracker::default();
+        let mut message = hello(CLIENT_HELLO, &[PRE_SHARED_KEY]);
+        // truncate the extensions while keeping the message length intact
+        let len = message.len();
+        message[len - 6] = 0xff;
+        assert!(!tracker.on_sent(&message));
+    }
+}
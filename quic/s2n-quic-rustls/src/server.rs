@@ -10,6 +10,7 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct Server {
     config: Arc<ServerConfig>,
+    require_application_protocol: bool,
 }
 
 impl Server {
@@ -17,6 +18,7 @@ impl Server {
     pub fn new(config: ServerConfig) -> Self {
         Self {
             config: Arc::new(config),
+            require_application_protocol: false,
         }
     }
 
@@ -43,7 +45,10 @@ impl From<ServerConfig> for Server {
 // TODO this should be removed after removing deprecated re-exports
 impl From<Arc<ServerConfig>> for Server {
     fn from(config: Arc<ServerConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            require_application_protocol: false,
+        }
     }
 }
 
@@ -65,7 +70,7 @@ impl tls::Endpoint for Server {
         )
         .expect("could not create rustls server session");
 
-        Session::new(session.into(), None)
+        Session::new(session.into(), None, self.require_application_protocol)
     }
 
     fn new_client_session<Params: EncoderValue>(
@@ -86,6 +91,7 @@ pub struct Builder {
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
     prefer_server_cipher_suite_order: bool,
+    require_application_protocol: bool,
 }
 
 impl Default for Builder {
@@ -101,6 +107,7 @@ impl Builder {
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
             prefer_server_cipher_suite_order: true,
+            require_application_protocol: false,
         }
     }
 
@@ -134,6 +141,19 @@ impl Builder {
         Ok(self)
     }
 
+    /// Restricts the server to FIPS-approved algorithms
+    ///
+    /// rustls does not use a FIPS-validated crypto backend so enabling FIPS mode always
+    /// returns an error.
+    pub fn with_fips(self, enabled: bool) -> Result<Self, Error> {
+        if enabled {
+            return Err(
+                rustls::Error::General("rustls does not support FIPS mode".to_string()).into(),
+            );
+        }
+        Ok(self)
+    }
+
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         self.key_log = Some(Arc::new(rustls::KeyLogFile::new()));
         Ok(self)
@@ -146,6 +166,13 @@ impl Builder {
         Ok(self)
     }
 
+    /// If enabled, the connection is closed with a `no_application_protocol` alert if the
+    /// client doesn't offer any of the configured application protocols (default: false)
+    pub fn with_require_application_protocol(mut self, enabled: bool) -> Result<Self, Error> {
+        self.require_application_protocol = enabled;
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Server, Error> {
         let builder = ServerConfig::builder()
             .with_cipher_suites(crate::cipher_suite::DEFAULT_CIPHERSUITES)
@@ -170,8 +197,10 @@ impl Builder {
             config.key_log = key_log;
         }
 
-        #[allow(deprecated)]
-        Ok(Server::new(config))
+        Ok(Server {
+            config: Arc::new(config),
+            require_application_protocol: self.require_application_protocol,
+        })
     }
 }
 
@@ -1,8 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::cipher_suite::{
-    HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys,
+use crate::{
+    cipher_suite::{HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys},
+    resumption,
 };
 use bytes::Bytes;
 use core::{fmt, fmt::Debug, task::Poll};
@@ -11,7 +12,10 @@ use rustls::quic::{
 };
 use s2n_quic_core::{
     application::ServerName,
-    crypto::{self, tls, tls::CipherSuite},
+    crypto::{
+        self, tls,
+        tls::{pin, CipherSuite},
+    },
     transport,
 };
 
@@ -24,6 +28,9 @@ pub struct Session {
     emitted_server_name: bool,
     emitted_application_protocol: bool,
     server_name: Option<ServerName>,
+    require_application_protocol: bool,
+    pinned_spki: Option<pin::SpkiPins>,
+    resumption: resumption::Tracker,
 }
 
 impl tls::TlsSession for Session {
@@ -72,7 +79,11 @@ impl fmt::Debug for Session {
 }
 
 impl Session {
-    pub fn new(connection: Connection, server_name: Option<ServerName>) -> Self {
+    pub fn new(
+        connection: Connection,
+        server_name: Option<ServerName>,
+        require_application_protocol: bool,
+    ) -> Self {
         Self {
             connection,
             rx_phase: Default::default(),
@@ -82,34 +93,55 @@ impl Session {
             emitted_server_name: false,
             emitted_application_protocol: false,
             server_name,
+            require_application_protocol,
+            pinned_spki: None,
+            resumption: Default::default(),
         }
     }
 
+    /// Checks the peer's end-entity certificate against the pinned public keys, if any
+    fn verify_pinned_spki(&self) -> Result<(), tls::Error> {
+        let Some(pins) = self.pinned_spki.as_ref() else {
+            return Ok(());
+        };
+
+        // resumed sessions don't have a peer certificate chain
+        let certificates = self.connection.peer_certificates().unwrap_or_default();
+
+        // only the end-entity certificate is matched since the rest of the chain is unverified
+        pins.verify(
+            certificates
+                .first()
+                .map(|certificate| certificate.0.as_slice()),
+            |spki| {
+                use s2n_quic_crypto::digest;
+                let mut hash = [0; pin::SPKI_HASH_LEN];
+                hash.copy_from_slice(digest::digest(&digest::SHA256, spki).as_ref());
+                hash
+            },
+        )
+    }
+
     fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
-        self.connection
-            .read_hs(crypto_data)
-            .map_err(crate::error::reason)
-            .map_err(|reason| {
-                //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
-                //# QUIC is only able to convey an alert level of "fatal".  In TLS 1.3,
-                //# the only existing uses for the "warning" level are to signal
-                //# connection close; see Section 6.1 of [TLS13].  As QUIC provides
-                //# alternative mechanisms for connection termination and the TLS
-                //# connection is only closed if an error is encountered, a QUIC endpoint
-                //# MUST treat any alert from TLS as if it were at the "fatal" level.
-
-                // According to the rustls docs, `alert` only returns fatal alerts:
-                // > https://docs.rs/rustls/0.19.0/rustls/quic/trait.QuicExt#tymethod.get_alert
-                // > Emit the TLS description code of a fatal alert, if one has arisen.
-
-                self.connection
-                    .alert()
-                    .map(|alert| tls::Error {
-                        code: alert.get_u8(),
-                        reason,
-                    })
-                    .unwrap_or(tls::Error::INTERNAL_ERROR)
-            })?;
+        if self.rx_phase == HandshakePhase::Initial {
+            self.resumption.on_received(crypto_data);
+        }
+
+        self.connection.read_hs(crypto_data).map_err(|error| {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
+            //# QUIC is only able to convey an alert level of "fatal".  In TLS 1.3,
+            //# the only existing uses for the "warning" level are to signal
+            //# connection close; see Section 6.1 of [TLS13].  As QUIC provides
+            //# alternative mechanisms for connection termination and the TLS
+            //# connection is only closed if an error is encountered, a QUIC endpoint
+            //# MUST treat any alert from TLS as if it were at the "fatal" level.
+
+            // According to the rustls docs, `alert` only returns fatal alerts:
+            // > https://docs.rs/rustls/0.19.0/rustls/quic/trait.QuicExt#tymethod.get_alert
+            // > Emit the TLS description code of a fatal alert, if one has arisen.
+
+            crate::error::alert(&error, self.connection.alert())
+        })?;
         Ok(())
     }
 
@@ -130,6 +162,22 @@ impl Session {
         })
     }
 
+    /// Ensures an application protocol was negotiated, if required
+    fn check_application_protocol(&self) -> Result<(), transport::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-8.1
+        //# When using ALPN, endpoints MUST immediately close a connection (see
+        //# Section 10.2 of [QUIC-TRANSPORT]) with a no_application_protocol TLS
+        //# alert (QUIC error code 0x178; see Section 4.8) if an application
+        //# protocol is not negotiated.
+        if self.require_application_protocol && self.application_protocol().is_none() {
+            return Err(tls::Error::NO_APPLICATION_PROTOCOL
+                .with_reason("an application protocol was not negotiated")
+                .into());
+        }
+
+        Ok(())
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9001#section-8.1
     //# Unless
     //# another mechanism is used for agreeing on an application protocol,
@@ -183,6 +231,19 @@ impl Session {
 
             // the handshake is complete!
             if !self.emitted_handshake_complete {
+                self.verify_pinned_spki()?;
+
+                if self.resumption.offered() {
+                    context.on_resumption_status(if self.resumption.accepted() {
+                        tls::ResumptionStatus::Accepted
+                    } else {
+                        tls::ResumptionStatus::Rejected
+                    })?;
+                } else if self.resumption.accepted() {
+                    // servers don't offer tickets but still report the tickets they accepted
+                    context.on_resumption_status(tls::ResumptionStatus::Accepted)?;
+                }
+
                 self.rx_phase.transition();
                 context.on_handshake_complete()?;
                 context.on_tls_exporter_ready(self)?;
@@ -259,6 +320,12 @@ impl Session {
                     break;
                 }
 
+                if self.tx_phase == HandshakePhase::Initial
+                    && self.resumption.on_sent(&transmission_buffer)
+                {
+                    context.on_resumption_status(tls::ResumptionStatus::Attempted)?;
+                }
+
                 // fill the correct buffer according to the handshake phase
                 match self.tx_phase {
                     HandshakePhase::Initial => context.send_initial(transmission_buffer.into()),
@@ -287,6 +354,7 @@ impl Session {
                         quic::KeyChange::OneRtt { keys, next } => {
                             let (key, header_key) = OneRttKey::new(keys, next, cipher_suite);
 
+                            self.check_application_protocol()?;
                             let application_parameters = self.application_parameters()?;
 
                             context.on_one_rtt_keys(key, header_key, application_parameters)?;
@@ -346,6 +414,11 @@ impl tls::Session for Session {
         self.emit_events(context)?;
         result
     }
+
+    fn pin_spki(&mut self, pins: pin::SpkiPins) -> Result<(), tls::Error> {
+        self.pinned_spki = Some(pins);
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
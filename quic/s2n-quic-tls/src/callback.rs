@@ -9,7 +9,8 @@ use s2n_quic_core::{
     endpoint, transport,
 };
 use s2n_quic_crypto::{
-    handshake::HandshakeKey, hkdf, one_rtt::OneRttKey, ring_aead as aead, Prk, SecretPair, Suite,
+    handshake::HandshakeKey, hkdf, offload::Offload, one_rtt::OneRttKey, ring_aead as aead, Prk,
+    SecretPair, Suite,
 };
 use s2n_tls::{connection::Connection, error::Fallible, ffi::*};
 
@@ -29,6 +30,7 @@ pub struct Callback<'a, T, C> {
     pub send_buffer: &'a mut BytesMut,
     pub emitted_server_name: &'a mut bool,
     pub server_name: &'a Option<ServerName>,
+    pub offload: &'a Offload,
 }
 
 impl<'a, T, C> Callback<'a, T, C>
@@ -216,16 +218,26 @@ where
 
                 match self.state.tx_phase {
                     HandshakePhase::Initial => {
-                        let (key, header_key) = HandshakeKey::new(self.endpoint, aead_algo, pair)
-                            .expect("invalid cipher");
+                        let (key, header_key) = HandshakeKey::new_with_offload(
+                            self.endpoint,
+                            aead_algo,
+                            pair,
+                            self.offload,
+                        )
+                        .expect("invalid cipher");
 
                         self.context.on_handshake_keys(key, header_key)?;
                         self.state.tx_phase.transition();
                         self.state.rx_phase.transition();
                     }
                     _ => {
-                        let (key, header_key) =
-                            OneRttKey::new(self.endpoint, aead_algo, pair).expect("invalid cipher");
+                        let (key, header_key) = OneRttKey::new_with_offload(
+                            self.endpoint,
+                            aead_algo,
+                            pair,
+                            self.offload,
+                        )
+                        .expect("invalid cipher");
                         // At this point the server is done writing Handshake messages
                         if self.endpoint.is_server() {
                             self.state.tx_phase.transition();
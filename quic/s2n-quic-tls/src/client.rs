@@ -5,11 +5,17 @@ use crate::{
     certificate::{IntoCertificate, IntoPrivateKey},
     keylog::KeyLogHandle,
     params::Params,
+    resumption::SessionCache,
     session::Session,
     ConfigLoader,
 };
+use core::time::Duration;
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::{tls, tls::resumption},
+    endpoint,
+};
 use s2n_tls::{
     callbacks::VerifyHostNameCallback,
     config::{self, Config},
@@ -23,6 +29,8 @@ pub struct Client<L: ConfigLoader = Config> {
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    fips: bool,
+    offload: Offload,
 }
 
 impl Client {
@@ -44,6 +52,8 @@ impl<L: ConfigLoader> Client<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            fips: false,
+            offload: Default::default(),
         }
     }
 }
@@ -66,6 +76,9 @@ impl<L: ConfigLoader> ConfigLoader for Client<L> {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    fips: bool,
+    offload: Offload,
+    resumption: resumption::Policy,
 }
 
 impl Default for Builder {
@@ -79,11 +92,18 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            fips: false,
+            offload: Default::default(),
+            resumption: resumption::Policy::default(),
         }
     }
 }
 
 impl Builder {
+    /// Returns the underlying s2n-tls config
+    ///
+    /// If FIPS mode is enabled, the FIPS security policy is applied again when the client is built,
+    /// replacing any policy set here.
     pub fn config_mut(&mut self) -> &mut s2n_tls::config::Builder {
         &mut self.config
     }
@@ -165,6 +185,36 @@ impl Builder {
         Ok(self)
     }
 
+    /// Restricts the client to FIPS-approved algorithms
+    ///
+    /// This replaces the security policy with one that only allows FIPS-approved cipher suites,
+    /// key exchange groups, and signature schemes. An error is returned if the crypto backend
+    /// is not a FIPS-validated module operating in FIPS mode, which requires the `fips` feature.
+    ///
+    /// The policy is applied again in [`Self::build`], so it can't be replaced through
+    /// [`Self::config_mut`], and building fails if key logging was enabled with
+    /// [`Self::with_key_logging`].
+    ///
+    /// Disabling FIPS mode leaves the configured security policy unchanged.
+    pub fn with_fips(mut self, enabled: bool) -> Result<Self, Error> {
+        if enabled {
+            let policy = crate::fips_policy()?;
+            self.config.set_security_policy(&policy)?;
+        }
+        self.fips = enabled;
+        Ok(self)
+    }
+
+    /// Offloads the header and/or packet protection of the Handshake and 1-RTT keys
+    ///
+    /// Keys which are declined by the offload implementation use the software implementation.
+    /// Since the offload implementation isn't part of the FIPS-validated crypto backend, an
+    /// endpoint with an offload is never reported as FIPS compliant.
+    pub fn with_offload(mut self, offload: Offload) -> Result<Self, Error> {
+        self.offload = offload;
+        Ok(self)
+    }
+
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         use crate::keylog::KeyLog;
 
@@ -185,11 +235,65 @@ impl Builder {
         Ok(self)
     }
 
-    pub fn build(self) -> Result<Client, Error> {
+    /// Enables or disables session resumption
+    ///
+    /// When enabled, the client stores session tickets issued by servers and offers them
+    /// on subsequent connections to the same host. Resumption is disabled by default.
+    ///
+    /// Note that this replaces any session ticket callback or connection initializer
+    /// set through [`Self::config_mut`].
+    pub fn with_session_resumption(mut self, enabled: bool) -> Result<Self, Error> {
+        self.resumption.set_enabled(enabled);
+        Ok(self)
+    }
+
+    /// Enables or disables session resumption for hosts matching `pattern`
+    ///
+    /// A pattern is either an exact host name or a wildcard of the form `*.example.com`.
+    /// Patterns configured later take precedence over earlier ones.
+    pub fn with_session_resumption_for_host<P: Into<String>>(
+        mut self,
+        pattern: P,
+        enabled: bool,
+    ) -> Result<Self, Error> {
+        self.resumption.set_host(pattern, enabled);
+        Ok(self)
+    }
+
+    /// Sets the maximum number of session tickets stored across all hosts
+    ///
+    /// The oldest tickets are evicted once the limit is reached.
+    pub fn with_max_session_tickets(mut self, max: usize) -> Result<Self, Error> {
+        self.resumption.set_max_tickets(max);
+        Ok(self)
+    }
+
+    /// Limits how long a session ticket is used after it is received, regardless of the
+    /// lifetime advertised by the server
+    pub fn with_max_session_ticket_lifetime(mut self, lifetime: Duration) -> Result<Self, Error> {
+        self.resumption.set_max_ticket_lifetime(lifetime);
+        Ok(self)
+    }
+
+    pub fn build(mut self) -> Result<Client, Error> {
+        if !self.resumption.is_disabled() {
+            let cache = SessionCache::new(self.resumption);
+            self.config
+                .enable_session_tickets(true)?
+                .set_session_ticket_callback(cache.clone())?
+                .set_connection_initializer(cache)?;
+        }
+
+        if self.fips {
+            crate::enforce_fips(&mut self.config, &self.keylog)?;
+        }
+
         Ok(Client {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            fips: self.fips,
+            offload: self.offload,
         })
     }
 }
@@ -209,12 +313,24 @@ impl<L: ConfigLoader> tls::Endpoint for Client<L> {
         let config = self.loader.load(crate::ConnectionContext {
             server_name: Some(&server_name),
         });
+        let offload = self.offload.clone();
         self.params.with(params, |params| {
-            Session::new(endpoint::Type::Client, config, params, Some(server_name)).unwrap()
+            Session::new(
+                endpoint::Type::Client,
+                config,
+                params,
+                Some(server_name),
+                offload,
+            )
+            .unwrap()
         })
     }
 
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn is_fips_compliant(&self) -> bool {
+        self.fips && !self.offload.is_enabled()
+    }
 }
@@ -13,6 +13,39 @@ static DEFAULT_POLICY: &s2n_tls::security::Policy = &s2n_tls::security::TESTING_
 #[cfg(not(all(s2n_quic_unstable, s2n_quic_enable_pq_tls)))]
 static DEFAULT_POLICY: &s2n_tls::security::Policy = &s2n_tls::security::DEFAULT_TLS13;
 
+/// A security policy which only allows FIPS-approved algorithms and supports TLS 1.3
+static FIPS_POLICY: &str = "20230317";
+
+/// Returns the FIPS security policy, as long as the crypto backend is operating in FIPS mode
+fn fips_policy() -> Result<s2n_tls::security::Policy, s2n_tls::error::Error> {
+    if !s2n_quic_crypto::fips::is_enabled() {
+        return Err(s2n_tls::error::Error::application(
+            "the crypto backend is not operating in FIPS mode".into(),
+        ));
+    }
+
+    s2n_tls::security::Policy::from_version(FIPS_POLICY)
+}
+
+/// Enforces FIPS mode on a config right before it is built
+///
+/// The security policy is applied again since it may have been replaced through `config_mut`
+/// after FIPS mode was enabled. Key logging exports the traffic secrets, so it is refused.
+fn enforce_fips(
+    config: &mut s2n_tls::config::Builder,
+    keylog: &Option<keylog::KeyLogHandle>,
+) -> Result<(), s2n_tls::error::Error> {
+    if keylog.is_some() {
+        return Err(s2n_tls::error::Error::application(
+            "key logging can't be enabled in FIPS mode".into(),
+        ));
+    }
+
+    let policy = fips_policy()?;
+    config.set_security_policy(&policy)?;
+    Ok(())
+}
+
 #[non_exhaustive]
 pub struct ConnectionContext<'a> {
     pub server_name: Option<&'a ServerName>,
@@ -49,6 +82,7 @@ impl ConfigLoader for Box<dyn ConfigLoader> {
 mod callback;
 mod keylog;
 mod params;
+mod resumption;
 mod session;
 
 pub mod certificate;
@@ -56,6 +90,7 @@ pub mod client;
 pub mod server;
 
 pub use client::Client;
+pub use s2n_quic_crypto::offload;
 pub use s2n_tls::*;
 pub use server::Server;
 
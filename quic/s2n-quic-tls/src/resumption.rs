@@ -0,0 +1,155 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic_core::crypto::tls::resumption::Policy;
+use s2n_tls::{
+    callbacks::{ConnectionFuture, SessionTicket, SessionTicketCallback},
+    config::ConnectionInitializer,
+    connection::Connection,
+    error::Error,
+};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// Marker stored on the connection when the cache offers a session ticket
+///
+/// The session uses this to report whether the server accepted the ticket.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TicketOffered;
+
+/// A client session ticket cache which applies a resumption [`Policy`]
+#[derive(Clone)]
+pub(crate) struct SessionCache {
+    policy: Arc<Policy>,
+    tickets: Arc<Mutex<VecDeque<Ticket>>>,
+}
+
+struct Ticket {
+    host: String,
+    data: Vec<u8>,
+    expiration: Instant,
+}
+
+impl SessionCache {
+    pub fn new(policy: Policy) -> Self {
+        Self {
+            policy: Arc::new(policy),
+            tickets: Default::default(),
+        }
+    }
+
+    fn insert(&self, host: &str, data: Vec<u8>, expiration: Instant) {
+        let mut tickets = self.tickets.lock().unwrap();
+
+        // evict the oldest tickets first
+        while tickets.len() >= self.policy.max_tickets() {
+            if tickets.pop_front().is_none() {
+                break;
+            }
+        }
+
+        tickets.push_back(Ticket {
+            host: host.to_owned(),
+            data,
+            expiration,
+        });
+    }
+
+    fn take(&self, host: &str, now: Instant) -> Option<Vec<u8>> {
+        let mut tickets = self.tickets.lock().unwrap();
+
+        tickets.retain(|ticket| ticket.expiration > now);
+
+        // tickets are single-use so remove the most recent one for the host
+        let index = tickets.iter().rposition(|ticket| ticket.host == host)?;
+        tickets.remove(index).map(|ticket| ticket.data)
+    }
+}
+
+impl SessionTicketCallback for SessionCache {
+    fn on_session_ticket(&self, connection: &mut Connection, session_ticket: &SessionTicket) {
+        let Some(host) = connection.server_name() else {
+            return;
+        };
+
+        if !self.policy.is_enabled(host) {
+            return;
+        }
+
+        let Ok(lifetime) = session_ticket.lifetime() else {
+            return;
+        };
+        let lifetime = self.policy.clamp_lifetime(lifetime);
+
+        let Ok(len) = session_ticket.len() else {
+            return;
+        };
+        let mut data = vec![0; len];
+        if session_ticket.data(&mut data).is_err() {
+            return;
+        }
+
+        self.insert(host, data, Instant::now() + lifetime);
+    }
+}
+
+impl ConnectionInitializer for SessionCache {
+    fn initialize_connection(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        let Some(host) = connection.server_name() else {
+            return Ok(None);
+        };
+
+        if !self.policy.is_enabled(host) {
+            return Ok(None);
+        }
+
+        let host = host.to_owned();
+        if let Some(ticket) = self.take(&host, Instant::now()) {
+            connection.set_session_ticket(&ticket)?;
+            connection.set_application_context(TicketOffered);
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[test]
+    fn cache_limits() {
+        let mut policy = Policy::new(true);
+        policy.set_max_tickets(2);
+        let cache = SessionCache::new(policy);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(60);
+
+        cache.insert("a.com", vec![1], later);
+        cache.insert("a.com", vec![2], later);
+        cache.insert("b.com", vec![3], later);
+
+        // the oldest ticket was evicted
+        assert_eq!(cache.take("a.com", now), Some(vec![2]));
+        assert_eq!(cache.take("a.com", now), None);
+        assert_eq!(cache.take("b.com", now), Some(vec![3]));
+    }
+
+    #[test]
+    fn cache_expiration() {
+        let cache = SessionCache::new(Policy::new(true));
+        let now = Instant::now();
+
+        cache.insert("a.com", vec![1], now + Duration::from_secs(1));
+        assert_eq!(cache.take("a.com", now + Duration::from_secs(2)), None);
+        assert!(cache.tickets.lock().unwrap().is_empty());
+    }
+}
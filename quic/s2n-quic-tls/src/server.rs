@@ -27,6 +27,8 @@ pub struct Server<L: ConfigLoader = Config> {
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    fips: bool,
+    offload: Offload,
 }
 
 impl Server {
@@ -48,6 +50,8 @@ impl<L: ConfigLoader> Server<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            fips: false,
+            offload: Default::default(),
         }
     }
 }
@@ -76,6 +80,8 @@ impl<L: ConfigLoader> From<Server<L>> for Config {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    fips: bool,
+    offload: Offload,
 }
 
 impl Default for Builder {
@@ -89,11 +95,17 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            fips: false,
+            offload: Default::default(),
         }
     }
 }
 
 impl Builder {
+    /// Returns the underlying s2n-tls config
+    ///
+    /// If FIPS mode is enabled, the FIPS security policy is applied again when the server is built,
+    /// replacing any policy set here.
     pub fn config_mut(&mut self) -> &mut s2n_tls::config::Builder {
         &mut self.config
     }
@@ -198,6 +210,36 @@ impl Builder {
         Ok(self)
     }
 
+    /// Restricts the server to FIPS-approved algorithms
+    ///
+    /// This replaces the security policy with one that only allows FIPS-approved cipher suites,
+    /// key exchange groups, and signature schemes. An error is returned if the crypto backend
+    /// is not a FIPS-validated module operating in FIPS mode, which requires the `fips` feature.
+    ///
+    /// The policy is applied again in [`Self::build`], so it can't be replaced through
+    /// [`Self::config_mut`], and building fails if key logging was enabled with
+    /// [`Self::with_key_logging`].
+    ///
+    /// Disabling FIPS mode leaves the configured security policy unchanged.
+    pub fn with_fips(mut self, enabled: bool) -> Result<Self, Error> {
+        if enabled {
+            let policy = crate::fips_policy()?;
+            self.config.set_security_policy(&policy)?;
+        }
+        self.fips = enabled;
+        Ok(self)
+    }
+
+    /// Offloads the header and/or packet protection of the Handshake and 1-RTT keys
+    ///
+    /// Keys which are declined by the offload implementation use the software implementation.
+    /// Since the offload implementation isn't part of the FIPS-validated crypto backend, an
+    /// endpoint with an offload is never reported as FIPS compliant.
+    pub fn with_offload(mut self, offload: Offload) -> Result<Self, Error> {
+        self.offload = offload;
+        Ok(self)
+    }
+
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         use crate::keylog::KeyLog;
 
@@ -218,11 +260,17 @@ impl Builder {
         Ok(self)
     }
 
-    pub fn build(self) -> Result<Server, Error> {
+    pub fn build(mut self) -> Result<Server, Error> {
+        if self.fips {
+            crate::enforce_fips(&mut self.config, &self.keylog)?;
+        }
+
         Ok(Server {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            fips: self.fips,
+            offload: self.offload,
         })
     }
 }
@@ -234,8 +282,9 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
         let config = self
             .loader
             .load(crate::ConnectionContext { server_name: None });
+        let offload = self.offload.clone();
         self.params.with(params, |params| {
-            Session::new(endpoint::Type::Server, config, params, None).unwrap()
+            Session::new(endpoint::Type::Server, config, params, None, offload).unwrap()
         })
     }
 
@@ -250,4 +299,8 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn is_fips_compliant(&self) -> bool {
+        self.fips && !self.offload.is_enabled()
+    }
 }
@@ -1,15 +1,22 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::callback::{self, Callback};
+use crate::{
+    callback::{self, Callback},
+    resumption::TicketOffered,
+};
 use bytes::BytesMut;
 use core::{marker::PhantomData, task::Poll};
 use s2n_quic_core::{
     application::ServerName,
-    crypto::{tls, tls::CipherSuite, CryptoSuite},
+    crypto::{
+        tls,
+        tls::{pin, CipherSuite},
+        CryptoSuite,
+    },
     endpoint, ensure, transport,
 };
-use s2n_quic_crypto::Suite;
+use s2n_quic_crypto::{digest, offload::Offload, Suite};
 use s2n_tls::{
     config::Config,
     connection::Connection,
@@ -28,6 +35,9 @@ pub struct Session {
     // This is only set for the client to avoid an extra allocation
     server_name: Option<ServerName>,
     received_ticket: bool,
+    offered_ticket: bool,
+    pinned_spki: Option<pin::SpkiPins>,
+    offload: Offload,
 }
 
 impl Session {
@@ -36,6 +46,7 @@ impl Session {
         config: Config,
         params: &[u8],
         server_name: Option<ServerName>,
+        offload: Offload,
     ) -> Result<Self, Error> {
         let mut connection = Connection::new(match endpoint {
             endpoint::Type::Server => Mode::Server,
@@ -63,6 +74,40 @@ impl Session {
             emitted_server_name: false,
             server_name,
             received_ticket: false,
+            offered_ticket: false,
+            pinned_spki: None,
+            offload,
+        })
+    }
+
+    /// Checks the peer's end-entity certificate against the pinned public keys, if any
+    fn verify_pinned_spki(&self) -> Result<(), tls::Error> {
+        let Some(pins) = self.pinned_spki.as_ref() else {
+            return Ok(());
+        };
+
+        // resumed sessions don't have a peer certificate chain
+        let chain = self
+            .connection
+            .peer_cert_chain()
+            .map_err(|_| pin::MISMATCH)?;
+
+        // only the end-entity certificate is matched since the rest of the chain is unverified
+        let end_entity = chain
+            .iter()
+            .next()
+            .transpose()
+            .map_err(|_| tls::Error::BAD_CERTIFICATE)?;
+        let end_entity = end_entity
+            .as_ref()
+            .map(|certificate| certificate.der())
+            .transpose()
+            .map_err(|_| tls::Error::BAD_CERTIFICATE)?;
+
+        pins.verify(end_entity, |spki| {
+            let mut hash = [0; pin::SPKI_HASH_LEN];
+            hash.copy_from_slice(digest::digest(&digest::SHA256, spki).as_ref());
+            hash
         })
     }
 }
@@ -110,6 +155,7 @@ impl tls::Session for Session {
             send_buffer: &mut self.send_buffer,
             emitted_server_name: &mut self.emitted_server_name,
             server_name: &self.server_name,
+            offload: &self.offload,
         };
 
         unsafe {
@@ -122,10 +168,34 @@ impl tls::Session for Session {
 
         callback.unset(&mut self.connection)?;
 
+        // The session cache marks the connection once it has offered a ticket
+        if !self.offered_ticket
+            && self
+                .connection
+                .application_context::<TicketOffered>()
+                .is_some()
+        {
+            self.offered_ticket = true;
+            context.on_resumption_status(tls::ResumptionStatus::Attempted)?;
+        }
+
         match result {
             Poll::Ready(Ok(())) => {
                 // s2n-tls has indicated that the handshake is complete
                 if !self.handshake_complete {
+                    self.verify_pinned_spki()?;
+
+                    if self.offered_ticket {
+                        context.on_resumption_status(if self.connection.resumed() {
+                            tls::ResumptionStatus::Accepted
+                        } else {
+                            tls::ResumptionStatus::Rejected
+                        })?;
+                    } else if self.connection.resumed() {
+                        // servers don't offer tickets but still report the tickets they accepted
+                        context.on_resumption_status(tls::ResumptionStatus::Accepted)?;
+                    }
+
                     self.state.on_handshake_complete();
                     context.on_handshake_complete()?;
                     context.on_tls_exporter_ready(self)?;
@@ -155,6 +225,7 @@ impl tls::Session for Session {
             send_buffer: &mut self.send_buffer,
             emitted_server_name: &mut self.emitted_server_name,
             server_name: &self.server_name,
+            offload: &self.offload,
         };
 
         unsafe {
@@ -191,6 +262,11 @@ impl tls::Session for Session {
         }
     }
 
+    fn pin_spki(&mut self, pins: pin::SpkiPins) -> Result<(), tls::Error> {
+        self.pinned_spki = Some(pins);
+        Ok(())
+    }
+
     fn should_discard_session(&self) -> bool {
         // Only clients process post-handshake messages currently
         ensure!(self.endpoint.is_client(), true);
@@ -1,9 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, client, server};
+use crate::{certificate, client, offload, server};
 use core::{
-    sync::atomic::{AtomicBool, AtomicU8, Ordering},
+    sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
     task::Poll,
 };
 use openssl::{ec::EcKey, ecdsa::EcdsaSig};
@@ -338,6 +338,52 @@ fn s2n_client_s2n_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+/// Declines every header protection key while counting the keys it was offered
+#[derive(Clone, Default)]
+struct CountingOffload(Arc<AtomicUsize>);
+
+impl offload::HeaderProtection for CountingOffload {
+    fn new_key(
+        &self,
+        _cipher: offload::Cipher,
+        _key: &[u8],
+    ) -> Option<Box<dyn offload::HeaderProtectionKey>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        None
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_offload_test() {
+    let client_offload = CountingOffload::default();
+    let server_offload = CountingOffload::default();
+
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_offload(offload::Offload::default().with_header_protection(client_offload.clone()))
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_offload(offload::Offload::default().with_header_protection(server_offload.clone()))
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert!(!client_endpoint.is_fips_compliant());
+    assert!(!server_endpoint.is_fips_compliant());
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+
+    // the sealing and opening keys of the Handshake and 1-RTT spaces are offered
+    assert_eq!(client_offload.0.load(Ordering::SeqCst), 4);
+    assert_eq!(server_offload.0.load(Ordering::SeqCst), 4);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_s2n_server_resumption_test() {
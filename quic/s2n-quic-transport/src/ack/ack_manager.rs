@@ -5,6 +5,7 @@ use crate::{
     ack::ack_transmission_state::AckTransmissionState, contexts::WriteContext,
     processed_packet::ProcessedPacket, transmission,
 };
+use core::num::NonZeroUsize;
 use s2n_quic_core::{
     ack,
     counter::{Counter, Saturating},
@@ -66,6 +67,9 @@ pub struct AckManager {
 
     /// Explicit Congestion Notification counts from processed packets
     ecn_counts: EcnCounts,
+
+    /// Controls the transmission of ACK frames, as configured by the application
+    mode: ack::Mode,
 }
 
 impl AckManager {
@@ -82,11 +86,16 @@ impl AckManager {
             transmissions_since_elicitation: Counter::new(0),
             transmission_state: AckTransmissionState::default(),
             ecn_counts: EcnCounts::default(),
+            mode: ack::Mode::default(),
         }
     }
 
     /// Called when an outgoing packet is being assembled
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> bool {
+        if self.mode.is_withholding() {
+            return false;
+        }
+
         let constraint = context.transmission_constraint();
         let mode = context.transmission_mode();
         let has_ranges = !self.ack_ranges.is_empty();
@@ -325,6 +334,8 @@ impl AckManager {
             //# send a non-probing packet in response.
             should_activate |= processed_packet.path_challenge_on_active_path;
 
+            should_activate |= self.mode.is_immediate();
+
             if should_activate {
                 self.transmission_state.activate();
             } else if !self.ack_delay_timer.is_armed() {
@@ -355,6 +366,43 @@ impl AckManager {
         }
     }
 
+    /// Transmits any pending ACK ranges without waiting for the ack delay to expire
+    pub fn flush(&mut self) {
+        self.transmission_state.activate();
+    }
+
+    /// Sets the mode controlling the transmission of ACK frames
+    pub fn set_mode(&mut self, mode: ack::Mode) {
+        let was_withholding = core::mem::replace(&mut self.mode, mode).is_withholding();
+
+        // transmit any acknowledgements that were withheld or are currently delayed
+        if mode.is_immediate() || (was_withholding && !mode.is_withholding()) {
+            self.transmission_state.activate();
+        }
+    }
+
+    /// Releases any ACK range capacity which isn't currently in use
+    pub fn hibernate(&mut self) {
+        self.ack_ranges.shrink_to_fit();
+    }
+
+    /// Replaces the ACK ranges with a previously used set in order to reuse its allocation
+    ///
+    /// Any ranges in the provided set are cleared.
+    pub fn with_ack_ranges(mut self, mut ack_ranges: ack::Ranges) -> Self {
+        let limit = NonZeroUsize::new(self.ack_settings.ack_ranges_limit as usize)
+            .expect("limit should be nonzero");
+        ack_ranges.clear();
+        ack_ranges.set_limit(limit);
+        self.ack_ranges = ack_ranges;
+        self
+    }
+
+    /// Takes the ACK ranges out of the manager so their allocation can be reused
+    pub fn take_ack_ranges(&mut self) -> ack::Ranges {
+        core::mem::take(&mut self.ack_ranges)
+    }
+
     /// Returns the largest received packet number that has been ACKed at least once
     pub fn largest_received_packet_number_acked(&self) -> PacketNumber {
         self.largest_received_packet_number_acked
@@ -387,6 +435,10 @@ impl transmission::interest::Provider for AckManager {
         &self,
         query: &mut Q,
     ) -> transmission::interest::Result {
+        if self.mode.is_withholding() {
+            return Ok(());
+        }
+
         self.transmission_state.transmission_interest(query)
     }
 }
@@ -419,14 +471,14 @@ mod tests {
             AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
 
         let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
-        let datagram = DatagramInfo {
-            ecn: Default::default(),
-            payload_len: 1200,
-            timestamp: NoopClock {}.get_time(),
-            destination_connection_id: connection::LocalId::TEST_ID,
-            destination_connection_id_classification: connection::id::Classification::Local,
-            source_connection_id: None,
-        };
+        let datagram = DatagramInfo::new(
+            NoopClock {}.get_time(),
+            1200,
+            Default::default(),
+            connection::LocalId::TEST_ID,
+            connection::id::Classification::Local,
+            None,
+        );
         let mut processed_packet = ProcessedPacket::new(pn, &datagram);
         processed_packet.path_challenge_on_active_path = true;
         processed_packet.ack_elicitation = AckElicitation::Eliciting;
@@ -520,15 +572,74 @@ mod tests {
     }
 
     /// Helper function to construct `DatagramInfo` with the given `ExplicitCongestionNotification`
+    #[test]
+    fn mode_test() {
+        use crate::transmission::interest::{Interest, Provider as _};
+
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+        let mut publisher = Publisher::snapshot();
+
+        let mut process_packet = |manager: &mut AckManager, packet_number: u8| {
+            let pn = PacketNumberSpace::ApplicationData
+                .new_packet_number(VarInt::from_u8(packet_number));
+            let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+            processed_packet.ack_elicitation = AckElicitation::Eliciting;
+            manager.on_processed_packet(
+                &processed_packet,
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+        };
+
+        // an in-order packet is acknowledged after the ack delay by default
+        process_packet(&mut manager, 1);
+        assert!(!manager.transmission_state.is_active());
+
+        // immediate mode transmits the pending acknowledgements
+        manager.set_mode(ack::Mode::Immediate);
+        assert!(manager.transmission_state.is_active());
+
+        // withholding suppresses any interest in transmission
+        manager.set_mode(ack::Mode::Withhold);
+        process_packet(&mut manager, 2);
+        assert_eq!(manager.get_transmission_interest(), Interest::None);
+
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut write_context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+        assert!(!manager.on_transmit(&mut write_context));
+        assert!(write_context.frame_buffer.is_empty());
+
+        // the withheld acknowledgements are transmitted once the mode is changed
+        manager.set_mode(ack::Mode::Default);
+        assert_ne!(manager.get_transmission_interest(), Interest::None);
+        assert!(manager.on_transmit(&mut write_context));
+        manager.on_transmit_complete(&mut write_context);
+
+        // every ack-eliciting packet is acknowledged without delay in immediate mode
+        manager.set_mode(ack::Mode::Immediate);
+        process_packet(&mut manager, 3);
+        assert!(manager.transmission_state.is_active());
+    }
+
     fn helper_datagram_info(ecn: ExplicitCongestionNotification) -> DatagramInfo {
-        DatagramInfo {
+        DatagramInfo::new(
+            NoopClock {}.get_time(),
+            1200,
             ecn,
-            payload_len: 1200,
-            timestamp: NoopClock {}.get_time(),
-            destination_connection_id: connection::LocalId::TEST_ID,
-            destination_connection_id_classification: connection::id::Classification::Local,
-            source_connection_id: None,
-        }
+            connection::LocalId::TEST_ID,
+            connection::id::Classification::Local,
+            None,
+        )
     }
 
     #[test]
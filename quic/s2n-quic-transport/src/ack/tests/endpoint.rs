@@ -51,14 +51,14 @@ impl Endpoint {
 
         self.ack_manager.on_timeout(self.env.current_time);
 
-        let datagram = DatagramInfo {
-            ecn: packet.ecn,
-            payload_len: 1200,
-            timestamp: self.env.current_time,
-            destination_connection_id: connection::LocalId::TEST_ID,
-            destination_connection_id_classification: connection::id::Classification::Local,
-            source_connection_id: None,
-        };
+        let datagram = DatagramInfo::new(
+            self.env.current_time,
+            1200,
+            packet.ecn,
+            connection::LocalId::TEST_ID,
+            connection::id::Classification::Local,
+            None,
+        );
 
         if let Some(ack) = packet.ack {
             for ack_range in ack.ack_ranges {
@@ -9,16 +9,22 @@ use crate::{
 };
 use bytes::Bytes;
 use core::{
+    any::Any,
     fmt,
     sync::atomic::{self, Ordering},
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
-    application,
+    ack, application,
     application::ServerName,
+    connection::{ChannelBinding, Fingerprint, RecoveryStats, Statistics, TransmissionStatus},
     inet::SocketAddress,
+    packet::number::PacketNumberSpace,
     query::{Query, QueryMut},
+    recovery::ProbePolicy,
     stream::StreamType,
+    varint::VarInt,
 };
 
 /// A QUIC connection
@@ -147,6 +153,17 @@ impl Connection {
             .poll_open_stream(&self.api, stream_type, &mut self.open_token, context)
     }
 
+    /// Opens the lowest reserved stream of the given type
+    ///
+    /// Returns `None` if the connection limits did not reserve any more streams of the type.
+    #[inline]
+    pub fn open_reserved_stream(
+        &mut self,
+        stream_type: StreamType,
+    ) -> Result<Option<Stream>, connection::Error> {
+        self.api.open_reserved_stream(&self.api, stream_type)
+    }
+
     #[inline]
     pub fn poll_request(
         &self,
@@ -157,6 +174,36 @@ impl Connection {
         self.api.poll_request(stream_id, request, context)
     }
 
+    /// Resets the sending half and/or stops the receiving half of every stream for which
+    /// `filter` returns `true`
+    ///
+    /// Returns the number of streams which matched the filter.
+    #[inline]
+    pub fn close_streams(
+        &self,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        filter: &mut dyn FnMut(StreamId) -> bool,
+    ) -> Result<usize, connection::Error> {
+        self.api.close_streams(reset, stop_sending, filter)
+    }
+
+    /// Raises the flow control window of the receiving half of the stream to `window` bytes
+    #[inline]
+    pub fn set_max_stream_data(&self, stream_id: StreamId, window: u32) -> Result<(), StreamError> {
+        self.api.set_max_stream_data(stream_id, window)
+    }
+
+    /// Raises the number of streams of the given type which the peer may have open concurrently
+    #[inline]
+    pub fn set_max_streams(
+        &self,
+        stream_type: StreamType,
+        limit: VarInt,
+    ) -> Result<(), connection::Error> {
+        self.api.set_max_streams(stream_type, limit)
+    }
+
     /// Closes the Connection with the provided error code
     ///
     /// This will immediately terminate all outstanding streams.
@@ -175,6 +222,21 @@ impl Connection {
         self.api.application_protocol()
     }
 
+    #[inline]
+    pub fn is_resumed(&self) -> Result<bool, connection::Error> {
+        self.api.is_resumed()
+    }
+
+    #[inline]
+    pub fn fingerprint(&self) -> Result<Option<Fingerprint>, connection::Error> {
+        self.api.fingerprint()
+    }
+
+    #[inline]
+    pub fn channel_binding(&self) -> Result<Option<ChannelBinding>, connection::Error> {
+        self.api.channel_binding()
+    }
+
     #[inline]
     pub fn id(&self) -> u64 {
         self.api.id()
@@ -189,6 +251,26 @@ impl Connection {
         self.api.keep_alive(enabled)
     }
 
+    pub fn set_transmit_tick(&self, period: Option<Duration>) -> Result<(), connection::Error> {
+        self.api.set_transmit_tick(period)
+    }
+
+    pub fn set_tx_coalescing(&self, delay: Option<Duration>) -> Result<(), connection::Error> {
+        self.api.set_tx_coalescing(delay)
+    }
+
+    pub fn set_probe_policy(&self, policy: ProbePolicy) -> Result<(), connection::Error> {
+        self.api.set_probe_policy(policy)
+    }
+
+    pub fn set_ack_mode(
+        &self,
+        space: PacketNumberSpace,
+        mode: ack::Mode,
+    ) -> Result<(), connection::Error> {
+        self.api.set_ack_mode(space, mode)
+    }
+
     #[inline]
     pub fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.local_address()
@@ -199,6 +281,16 @@ impl Connection {
         self.api.remote_address()
     }
 
+    #[inline]
+    pub fn observed_address(&self) -> Result<Option<SocketAddress>, connection::Error> {
+        self.api.observed_address()
+    }
+
+    #[inline]
+    pub fn current_mtu(&self) -> Result<u16, connection::Error> {
+        self.api.current_mtu()
+    }
+
     #[inline]
     pub fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api.query_event_context(query)
@@ -216,4 +308,48 @@ impl Connection {
     pub fn datagram_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error> {
         self.api.datagram_mut(query)
     }
+
+    #[inline]
+    pub fn set_application_context(
+        &self,
+        context: Box<dyn Any + Send>,
+    ) -> Result<(), connection::Error> {
+        self.api.set_application_context(context)
+    }
+
+    #[inline]
+    pub fn query_application_context(
+        &self,
+        query: &mut dyn Query,
+    ) -> Result<(), connection::Error> {
+        self.api.query_application_context(query)
+    }
+
+    #[inline]
+    pub fn transmission_status(&self) -> Result<TransmissionStatus, connection::Error> {
+        self.api.transmission_status()
+    }
+
+    #[inline]
+    pub fn statistics(&self) -> Result<Statistics, connection::Error> {
+        self.api.statistics()
+    }
+
+    #[inline]
+    pub fn query_stats(&self) -> Result<RecoveryStats, connection::Error> {
+        self.api.query_stats()
+    }
+
+    #[inline]
+    pub fn quic_version(&self) -> Result<u32, connection::Error> {
+        self.api.quic_version()
+    }
+
+    #[inline]
+    pub fn poll_probe_path(
+        &self,
+        context: &Context,
+    ) -> Poll<Result<Option<Duration>, connection::Error>> {
+        self.api.poll_probe_path(context)
+    }
 }
@@ -11,15 +11,21 @@ use crate::{
 use alloc::sync::Arc;
 use bytes::Bytes;
 use core::{
+    any::Any,
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
-    application,
+    ack, application,
     application::ServerName,
+    connection::{ChannelBinding, Fingerprint, RecoveryStats, Statistics, TransmissionStatus},
     inet::SocketAddress,
+    packet::number::PacketNumberSpace,
     query::{Query, QueryMut},
+    recovery::ProbePolicy,
     stream::{ops, StreamId, StreamType},
+    varint::VarInt,
 };
 
 /// A dynamically dispatched connection API
@@ -37,6 +43,21 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError>;
 
+    fn close_streams(
+        &self,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        filter: &mut dyn FnMut(StreamId) -> bool,
+    ) -> Result<usize, connection::Error>;
+
+    fn set_max_stream_data(&self, stream_id: StreamId, window: u32) -> Result<(), StreamError>;
+
+    fn set_max_streams(
+        &self,
+        stream_type: StreamType,
+        limit: VarInt,
+    ) -> Result<(), connection::Error>;
+
     fn poll_accept(
         &self,
         arc_self: &Arc<dyn ConnectionApiProvider>,
@@ -52,25 +73,73 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
         context: &Context,
     ) -> Poll<Result<Stream, connection::Error>>;
 
+    fn open_reserved_stream(
+        &self,
+        arc_self: &Arc<dyn ConnectionApiProvider>,
+        stream_type: StreamType,
+    ) -> Result<Option<Stream>, connection::Error>;
+
     fn close_connection(&self, code: Option<application::Error>);
 
     fn server_name(&self) -> Result<Option<ServerName>, connection::Error>;
 
     fn application_protocol(&self) -> Result<Bytes, connection::Error>;
 
+    fn is_resumed(&self) -> Result<bool, connection::Error>;
+
+    fn fingerprint(&self) -> Result<Option<Fingerprint>, connection::Error>;
+
+    fn channel_binding(&self) -> Result<Option<ChannelBinding>, connection::Error>;
+
     fn id(&self) -> u64;
 
     fn ping(&self) -> Result<(), connection::Error>;
 
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn set_transmit_tick(&self, period: Option<Duration>) -> Result<(), connection::Error>;
+
+    fn set_tx_coalescing(&self, delay: Option<Duration>) -> Result<(), connection::Error>;
+
+    fn set_probe_policy(&self, policy: ProbePolicy) -> Result<(), connection::Error>;
+
+    fn set_ack_mode(
+        &self,
+        space: PacketNumberSpace,
+        mode: ack::Mode,
+    ) -> Result<(), connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn observed_address(&self) -> Result<Option<SocketAddress>, connection::Error>;
+
+    fn current_mtu(&self) -> Result<u16, connection::Error>;
+
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
 
     fn query_event_context_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
 
     fn datagram_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
+
+    fn set_application_context(
+        &self,
+        context: Box<dyn Any + Send>,
+    ) -> Result<(), connection::Error>;
+
+    fn query_application_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
+
+    fn transmission_status(&self) -> Result<TransmissionStatus, connection::Error>;
+
+    fn statistics(&self) -> Result<Statistics, connection::Error>;
+
+    fn query_stats(&self) -> Result<RecoveryStats, connection::Error>;
+
+    fn quic_version(&self) -> Result<u32, connection::Error>;
+
+    fn poll_probe_path(
+        &self,
+        context: &Context,
+    ) -> Poll<Result<Option<Duration>, connection::Error>>;
 }
@@ -14,33 +14,43 @@ use crate::{
         self,
         connect::{self, ConnectionSender},
         handle::{AcceptorSender, ConnectorReceiver},
+        lifecycle,
     },
     stream,
 };
-use alloc::{collections::BTreeMap, sync::Arc};
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
 use bytes::Bytes;
 use core::{
+    any::Any,
     cell::Cell,
     marker::PhantomData,
     ops::Deref,
     pin::Pin,
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
 };
 use s2n_quic_core::{
-    application,
+    ack, application,
     application::ServerName,
+    connection::{ChannelBinding, Fingerprint, RecoveryStats, Statistics, TransmissionStatus},
     event::supervisor,
     inet::SocketAddress,
+    packet::number::PacketNumberSpace,
+    path::cache as path_cache,
     query::{Query, QueryMut},
-    recovery::K_GRANULARITY,
+    recovery::{ProbePolicy, K_GRANULARITY},
     time::Timestamp,
     transport,
+    varint::VarInt,
 };
 
+/// The remote address of a connection's active path along with its observed characteristics
+pub type PathCharacteristics = (SocketAddress, path_cache::Characteristics);
+
 // Intrusive list adapter for managing the list of `done` connections
 intrusive_adapter!(DoneConnectionsAdapter<C, L> = Arc<ConnectionNode<C, L>>: ConnectionNode<C, L> {
     done_connections_link: LinkedListLink
@@ -249,6 +259,31 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.poll_stream_request(stream_id, request, context))
     }
 
+    fn close_streams(
+        &self,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        filter: &mut dyn FnMut(stream::StreamId) -> bool,
+    ) -> Result<usize, connection::Error> {
+        self.api_write_call(|conn| conn.close_streams(reset, stop_sending, filter))
+    }
+
+    fn set_max_stream_data(
+        &self,
+        stream_id: stream::StreamId,
+        window: u32,
+    ) -> Result<(), stream::StreamError> {
+        self.api_write_call(|conn| conn.set_max_stream_data(stream_id, window))
+    }
+
+    fn set_max_streams(
+        &self,
+        stream_type: stream::StreamType,
+        limit: VarInt,
+    ) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_max_streams(stream_type, limit))
+    }
+
     fn poll_accept(
         &self,
         arc_self: &ConnectionApi,
@@ -294,6 +329,19 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         }
     }
 
+    fn open_reserved_stream(
+        &self,
+        arc_self: &ConnectionApi,
+        stream_type: stream::StreamType,
+    ) -> Result<Option<stream::Stream>, connection::Error> {
+        let stream_id = self.api_write_call(|conn| conn.open_reserved_stream(stream_type))?;
+
+        Ok(stream_id.map(|stream_id| {
+            let connection = Connection::new(arc_self.clone());
+            stream::Stream::new(connection, stream_id)
+        }))
+    }
+
     fn close_connection(&self, error: Option<application::Error>) {
         let _: Result<(), connection::Error> = self.api_write_call(|conn| {
             conn.application_close(error);
@@ -309,6 +357,18 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| Ok(conn.application_protocol()))
     }
 
+    fn is_resumed(&self) -> Result<bool, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.is_resumed()))
+    }
+
+    fn fingerprint(&self) -> Result<Option<Fingerprint>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.fingerprint()))
+    }
+
+    fn channel_binding(&self) -> Result<Option<ChannelBinding>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.channel_binding()))
+    }
+
     fn id(&self) -> u64 {
         self.internal_connection_id.into()
     }
@@ -321,6 +381,26 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.keep_alive(enabled))
     }
 
+    fn set_transmit_tick(&self, period: Option<Duration>) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_transmit_tick(period))
+    }
+
+    fn set_tx_coalescing(&self, delay: Option<Duration>) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_tx_coalescing(delay))
+    }
+
+    fn set_probe_policy(&self, policy: ProbePolicy) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_probe_policy(policy))
+    }
+
+    fn set_ack_mode(
+        &self,
+        space: PacketNumberSpace,
+        mode: ack::Mode,
+    ) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_ack_mode(space, mode))
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.local_address())
     }
@@ -329,6 +409,14 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| conn.remote_address())
     }
 
+    fn observed_address(&self) -> Result<Option<SocketAddress>, connection::Error> {
+        self.api_read_call(|conn| conn.observed_address())
+    }
+
+    fn current_mtu(&self) -> Result<u16, connection::Error> {
+        self.api_read_call(|conn| conn.current_mtu())
+    }
+
     #[inline]
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api_read_call(|conn| {
@@ -352,6 +440,52 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
             Ok(())
         })
     }
+
+    #[inline]
+    fn set_application_context(
+        &self,
+        context: Box<dyn Any + Send>,
+    ) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| {
+            conn.set_application_context(context);
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn query_application_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
+        self.api_read_call(|conn| {
+            conn.query_application_context(query);
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn transmission_status(&self) -> Result<TransmissionStatus, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.transmission_status()))
+    }
+
+    #[inline]
+    fn statistics(&self) -> Result<Statistics, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.statistics()))
+    }
+
+    #[inline]
+    fn query_stats(&self) -> Result<RecoveryStats, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.recovery_stats()))
+    }
+
+    #[inline]
+    fn quic_version(&self) -> Result<u32, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.quic_version()))
+    }
+
+    fn poll_probe_path(
+        &self,
+        context: &Context,
+    ) -> Poll<Result<Option<Duration>, connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_probe_path(context))
+    }
 }
 
 /// Contains all secondary lists of Connections.
@@ -616,6 +750,11 @@ pub struct ConnectionContainer<C: connection::Trait, L: connection::Lock<C>> {
     ///
     /// This is only used by clients
     connector_receiver: ConnectorReceiver,
+    /// Notifies the application when connections are removed
+    closed_notifier: lifecycle::Notifier,
+    /// The IDs of connections which were removed since the last call to `take_removed`, along
+    /// with the final characteristics of their active paths
+    removed: Vec<(InternalConnectionId, Option<PathCharacteristics>)>,
 }
 
 macro_rules! iterate_interruptible {
@@ -669,12 +808,18 @@ macro_rules! iterate_interruptible {
 
 impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
     /// Creates a new `ConnectionContainer`
-    pub(crate) fn new(accept_queue: AcceptorSender, connector_receiver: ConnectorReceiver) -> Self {
+    pub(crate) fn new(
+        accept_queue: AcceptorSender,
+        connector_receiver: ConnectorReceiver,
+        closed_notifier: lifecycle::Notifier,
+    ) -> Self {
         Self {
             connection_map: RBTree::new(ConnectionTreeAdapter::new()),
             interest_lists: InterestLists::new(),
             accept_queue,
             connector_receiver,
+            closed_notifier,
+            removed: Vec::new(),
         }
     }
 
@@ -822,6 +967,17 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         self.interest_lists.connection_count
     }
 
+    /// Calls `f` with the ID and final path characteristics of each connection removed since the
+    /// last call
+    pub fn take_removed<F: FnMut(InternalConnectionId, Option<PathCharacteristics>)>(
+        &mut self,
+        mut f: F,
+    ) {
+        for (id, path) in self.removed.drain(..) {
+            f(id, path)
+        }
+    }
+
     /// Looks up the `Connection` with the given ID and executes the provided function
     /// on it.
     ///
@@ -882,6 +1038,22 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         Some((result, interests))
     }
 
+    /// Executes the given function on every `Connection` in the container
+    pub fn for_each<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut C),
+    {
+        let ids: Vec<_> = self
+            .connection_map
+            .iter()
+            .map(|node| node.internal_connection_id)
+            .collect();
+
+        for id in ids {
+            self.with_connection(id, |conn| func(conn));
+        }
+    }
+
     /// Removes all Connections in the `done` state from the `ConnectionContainer`.
     fn finalize_done_connections(&mut self) {
         debug_assert_eq!(
@@ -1057,6 +1229,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
 
         if let Some(connection) = remove_result {
             self.interest_lists.remove_node(&connection);
+            self.on_removed(&connection);
         }
     }
 
@@ -1078,6 +1251,27 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         debug_assert!(remove_result.is_some());
 
         self.interest_lists.remove_node(connection);
+        self.on_removed(connection);
+    }
+
+    /// Notifies any subscribers that the connection was removed
+    fn on_removed(&mut self, connection: &ConnectionNode<C, L>) {
+        let path = connection
+            .inner
+            .read(|conn| conn.path_characteristics())
+            .ok()
+            .flatten();
+        self.removed.push((connection.internal_connection_id, path));
+
+        self.closed_notifier.on_closed(|| {
+            let (error, statistics) = connection
+                .inner
+                .read(|conn| (conn.error(), conn.statistics()))
+                // the connection panicked so the final state isn't available
+                .unwrap_or_default();
+            let error = error.unwrap_or_else(connection::Error::unspecified);
+            lifecycle::Closed::new(connection.internal_connection_id.into(), error, statistics)
+        });
     }
 }
 
@@ -5,8 +5,8 @@ use super::*;
 use crate::{
     connection::{
         self, connection_impl::AcceptState, connection_interests::ConnectionInterests,
-        internal_connection_id::InternalConnectionId, InternalConnectionIdGenerator,
-        ProcessingError, Trait,
+        internal_connection_id::InternalConnectionId, ConnectionIdMapper,
+        InternalConnectionIdGenerator, ProcessingError, Trait,
     },
     endpoint, path, stream,
 };
@@ -17,21 +17,26 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    application, event,
+    ack, application,
+    connection::{ChannelBinding, Fingerprint, RecoveryStats, Statistics, TransmissionStatus},
+    event,
     event::builder::DatagramDropReason,
     inet::{DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
         initial::{CleartextInitial, ProtectedInitial},
+        number::PacketNumberSpace,
         retry::ProtectedRetry,
         short::ProtectedShort,
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::mtu,
-    query,
+    path::{cache as path_cache, mtu},
+    query, random,
+    recovery::ProbePolicy,
     time::{Timer, Timestamp},
+    varint::VarInt,
 };
 use std::sync::Mutex;
 
@@ -99,6 +104,7 @@ impl connection::Trait for TestConnection {
         &mut self,
         _connection_id_format: &mut <Self::Config as endpoint::Config>::ConnectionIdFormat,
         _stateless_reset_token_generator: &mut <Self::Config as endpoint::Config>::StatelessResetTokenGenerator,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _timestamp: Timestamp,
     ) -> Result<(), connection::local_id_registry::LocalIdRegistrationError> {
         Ok(())
@@ -133,6 +139,15 @@ impl connection::Trait for TestConnection {
         _timestamp: Timestamp,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+    ) -> Result<(), connection::Error> {
+        Ok(())
+    }
+
+    fn on_datagram_processed(
+        &mut self,
+        _timestamp: Timestamp,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
     ) -> Result<(), connection::Error> {
         Ok(())
     }
@@ -260,6 +275,31 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn close_streams(
+        &mut self,
+        _reset: Option<application::Error>,
+        _stop_sending: Option<application::Error>,
+        _filter: &mut dyn FnMut(stream::StreamId) -> bool,
+    ) -> Result<usize, connection::Error> {
+        todo!()
+    }
+
+    fn set_max_stream_data(
+        &mut self,
+        _stream_id: stream::StreamId,
+        _window: u32,
+    ) -> Result<(), stream::StreamError> {
+        todo!()
+    }
+
+    fn set_max_streams(
+        &mut self,
+        _stream_type: stream::StreamType,
+        _limit: VarInt,
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn poll_accept_stream(
         &mut self,
         _stream_type: Option<stream::StreamType>,
@@ -277,6 +317,13 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn open_reserved_stream(
+        &mut self,
+        _stream_type: stream::StreamType,
+    ) -> Result<Option<stream::StreamId>, connection::Error> {
+        todo!()
+    }
+
     fn application_close(&mut self, _error: Option<application::Error>) {
         // no-op
     }
@@ -289,6 +336,18 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn is_resumed(&self) -> bool {
+        todo!()
+    }
+
+    fn fingerprint(&self) -> Option<Fingerprint> {
+        todo!()
+    }
+
+    fn channel_binding(&self) -> Option<ChannelBinding> {
+        todo!()
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         todo!()
     }
@@ -297,6 +356,26 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn set_transmit_tick(&mut self, _period: Option<Duration>) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn set_tx_coalescing(&mut self, _delay: Option<Duration>) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn set_probe_policy(&mut self, _policy: ProbePolicy) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn set_ack_mode(
+        &mut self,
+        _space: PacketNumberSpace,
+        _mode: ack::Mode,
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         todo!()
     }
@@ -305,6 +384,14 @@ impl connection::Trait for TestConnection {
         Ok(SocketAddress::default())
     }
 
+    fn observed_address(&self) -> Result<Option<SocketAddress>, connection::Error> {
+        todo!()
+    }
+
+    fn current_mtu(&self) -> Result<u16, connection::Error> {
+        todo!()
+    }
+
     fn error(&self) -> Option<connection::Error> {
         None
     }
@@ -321,6 +408,37 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn set_application_context(&mut self, _context: Box<dyn Any + Send>) {
+        todo!()
+    }
+
+    fn query_application_context(&self, _query: &mut dyn query::Query) {
+        todo!()
+    }
+
+    fn transmission_status(&self) -> TransmissionStatus {
+        todo!()
+    }
+
+    fn statistics(&self) -> Statistics {
+        todo!()
+    }
+
+    fn recovery_stats(&self) -> RecoveryStats {
+        todo!()
+    }
+
+    fn path_characteristics(&self) -> Option<(SocketAddress, path_cache::Characteristics)> {
+        todo!()
+    }
+
+    fn poll_probe_path(
+        &mut self,
+        _context: &Context,
+    ) -> Poll<Result<Option<Duration>, connection::Error>> {
+        todo!()
+    }
+
     fn with_event_publisher<F>(
         &mut self,
         _timestamp: Timestamp,
@@ -413,13 +531,17 @@ fn container_test() {
     check!().with_type::<Vec<Operation>>().for_each(|ops| {
         let mut id_gen = InternalConnectionIdGenerator::new();
         let mut connections = vec![];
-        let (handle, acceptor, connector, _close_handle) = endpoint::handle::Handle::new(100);
+        let id_map_metrics =
+            ConnectionIdMapper::new(&mut random::testing::Generator(123), endpoint::Type::Server)
+                .metrics_handle();
+        let (handle, acceptor, connector, _close_handle, closed_notifier) =
+            endpoint::handle::Handle::new(100, Default::default(), id_map_metrics);
         let (waker, _wake_count) = futures_test::task::new_count_waker();
         let mut now = unsafe { Timestamp::from_duration(Duration::from_secs(0)) };
 
         let mut handle = Some(handle);
         let mut container: ConnectionContainer<TestConnection, TestLock> =
-            ConnectionContainer::new(acceptor, connector);
+            ConnectionContainer::new(acceptor, connector, closed_notifier);
 
         for op in ops.iter() {
             match op {
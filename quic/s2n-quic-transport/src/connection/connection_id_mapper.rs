@@ -2,13 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Maps from external connection IDs to internal connection IDs
+//!
+//! Each map is split into independently locked shards, which are selected with a separately keyed
+//! hash of the map key. This allows connections to register and retire IDs without contending with
+//! the endpoint's lookups of unrelated IDs, and lookups only ever acquire a shared lock.
 
 use crate::connection::{local_id_registry::LocalIdRegistry, InternalConnectionId, PeerIdRegistry};
-use core::{convert::TryFrom as _, hash::BuildHasher};
+use core::{
+    convert::TryFrom as _,
+    hash::{BuildHasher, Hash},
+    sync::atomic::{AtomicU64, Ordering},
+};
 use hashbrown::hash_map::{Entry, HashMap};
 use s2n_quic_core::{connection, endpoint, random, stateless_reset, time::Timestamp};
 use siphasher::sip::SipHasher13;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// The number of shards in each map
+///
+/// This should be large enough that IDs registered by different connections rarely share a shard.
+const SHARD_COUNT: usize = 32;
 
 // Since the input to the hash function (stateless reset token) come from the peer, we need to
 // ensure that maliciously crafted values do not result in poor bucketing and thus degraded
@@ -19,7 +32,7 @@ use std::sync::{Arc, Mutex};
 // to protect against such attacks. We implement this explicitly to ensure this map continues to
 // provide this protection even if future versions of `std::collections::HashMap` do not and to
 // make the hash algorithm used explicit.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct HashState {
     k0: u64,
     k1: u64,
@@ -50,69 +63,182 @@ impl BuildHasher for HashState {
     }
 }
 
+/// The occupancy of a single connection ID map
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Occupancy {
+    /// The number of entries in the map
+    pub len: usize,
+    /// The number of entries in the most occupied shard
+    ///
+    /// This should stay close to `len / shards`. A much larger value indicates the shards are
+    /// unevenly loaded.
+    pub max_shard_len: usize,
+    /// The number of shards in the map
+    pub shards: usize,
+    /// The number of inserts which found the key was already registered
+    pub collisions: u64,
+}
+
+/// A snapshot of the occupancy of the endpoint's connection ID maps
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Metrics {
+    /// The map from local connection IDs to connections
+    pub local_ids: Occupancy,
+    /// The map from client-chosen initial connection IDs to connections
+    ///
+    /// This is only used by servers.
+    pub initial_ids: Occupancy,
+    /// The map from peer stateless reset tokens to connections
+    pub stateless_reset_tokens: Occupancy,
+}
+
+/// A hash map which is split into independently locked shards
+#[derive(Debug)]
+struct ShardedMap<K, V> {
+    /// Selects the shard for each key
+    ///
+    /// This is keyed separately from the shards so the entries in a shard don't share hash bits.
+    shard_hash_state: HashState,
+    shards: Box<[RwLock<HashMap<K, V, HashState>>]>,
+    collisions: AtomicU64,
+}
+
+impl<K: Hash + Eq, V> ShardedMap<K, V> {
+    fn new(random_generator: &mut dyn random::Generator) -> Self {
+        let shard_hash_state = HashState::new(random_generator);
+        let hash_state = HashState::new(random_generator);
+        let shards = (0..SHARD_COUNT)
+            .map(|_| RwLock::new(HashMap::with_hasher(hash_state.clone())))
+            .collect();
+
+        Self {
+            shard_hash_state,
+            shards,
+            collisions: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V, HashState>> {
+        let hash = self.shard_hash_state.hash_one(key);
+        &self.shards[(hash % self.shards.len() as u64) as usize]
+    }
+
+    // The maps are only modified with single inserts and removals, which can't leave a shard in
+    // an inconsistent state. Poisoned locks are recovered so a panicking connection doesn't
+    // prevent the rest of the endpoint from routing packets or cleaning up.
+
+    #[inline]
+    fn read(&self, key: &K) -> RwLockReadGuard<HashMap<K, V, HashState>> {
+        self.shard(key)
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[inline]
+    fn write(&self, key: &K) -> RwLockWriteGuard<HashMap<K, V, HashState>> {
+        self.shard(key)
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[inline]
+    fn on_collision(&self) {
+        self.collisions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn occupancy(&self) -> Occupancy {
+        let mut occupancy = Occupancy {
+            shards: self.shards.len(),
+            collisions: self.collisions.load(Ordering::Relaxed),
+            ..Default::default()
+        };
+
+        for shard in self.shards.iter() {
+            let len = shard
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .len();
+            occupancy.len += len;
+            occupancy.max_shard_len = occupancy.max_shard_len.max(len);
+        }
+
+        occupancy
+    }
+}
+
+impl<K: Hash + Eq, V: Copy> ShardedMap<K, V> {
+    #[inline]
+    fn get(&self, key: &K) -> Option<V> {
+        self.read(key).get(key).copied()
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct StatelessResetMap {
     /// Maps from a hash of peer stateless reset token to internal connection IDs
-    map: HashMap<stateless_reset::Token, InternalConnectionId, HashState>,
+    map: ShardedMap<stateless_reset::Token, InternalConnectionId>,
 }
 
 impl StatelessResetMap {
     /// Constructs a new `StatelessResetMap`
-    fn new(hash_state: HashState) -> Self {
+    fn new(random_generator: &mut dyn random::Generator) -> Self {
         Self {
-            map: HashMap::with_hasher(hash_state),
+            map: ShardedMap::new(random_generator),
         }
     }
 
     /// Inserts the given stateless reset token and the given
     /// internal connection ID into the stateless reset map.
-    pub(crate) fn insert(
-        &mut self,
-        token: stateless_reset::Token,
-        internal_id: InternalConnectionId,
-    ) {
-        self.map.insert(token, internal_id);
+    pub(crate) fn insert(&self, token: stateless_reset::Token, internal_id: InternalConnectionId) {
+        let previous = self.map.write(&token).insert(token, internal_id);
+        // tokens may be registered more than once by the same connection
+        if previous.map_or(false, |previous| previous != internal_id) {
+            self.map.on_collision();
+        }
     }
 
     /// Removes the mapping for the given key, returning the
     /// `InternalConnection` if it was in the map.
-    pub(crate) fn remove(
-        &mut self,
-        token: &stateless_reset::Token,
-    ) -> Option<InternalConnectionId> {
-        self.map.remove(token)
+    pub(crate) fn remove(&self, token: &stateless_reset::Token) -> Option<InternalConnectionId> {
+        self.map.write(token).remove(token)
     }
 }
 
 #[derive(Debug)]
 pub(crate) struct LocalIdMap {
     /// Maps from external to internal connection IDs
-    map: HashMap<connection::LocalId, InternalConnectionId, HashState>,
+    map: ShardedMap<connection::LocalId, InternalConnectionId>,
 }
 
 impl LocalIdMap {
     /// Constructs a new `LocalIdMap`
-    fn new(hash_state: HashState) -> Self {
+    fn new(random_generator: &mut dyn random::Generator) -> Self {
         Self {
-            map: HashMap::with_hasher(hash_state),
+            map: ShardedMap::new(random_generator),
         }
     }
 
     /// Gets the `InternalConnectionId` (if any) associated with the given local id
     pub(crate) fn get(&self, local_id: &connection::LocalId) -> Option<InternalConnectionId> {
-        self.map.get(local_id).copied()
+        self.map.get(local_id)
     }
 
     /// Inserts the given `LocalId` into the map if it is not already in the map,
     /// otherwise returns an Err
     pub(crate) fn try_insert(
-        &mut self,
+        &self,
         local_id: &connection::LocalId,
         internal_id: InternalConnectionId,
     ) -> Result<(), ()> {
-        let entry = self.map.entry(*local_id);
-        match entry {
-            Entry::Occupied(_) => Err(()),
+        let mut shard = self.map.write(local_id);
+        match shard.entry(*local_id) {
+            Entry::Occupied(_) => {
+                self.map.on_collision();
+                Err(())
+            }
             Entry::Vacant(entry) => {
                 entry.insert(internal_id);
                 Ok(())
@@ -121,11 +247,8 @@ impl LocalIdMap {
     }
 
     /// Removes the given `LocalId` from the map
-    pub(crate) fn remove(
-        &mut self,
-        local_id: &connection::LocalId,
-    ) -> Option<InternalConnectionId> {
-        self.map.remove(local_id)
+    pub(crate) fn remove(&self, local_id: &connection::LocalId) -> Option<InternalConnectionId> {
+        self.map.write(local_id).remove(local_id)
     }
 }
 
@@ -133,40 +256,45 @@ impl LocalIdMap {
 #[derive(Debug)]
 pub(crate) struct InitialIdMap {
     /// Maps from initial id to internal connection ID
-    initial_to_internal_id_map: HashMap<connection::InitialId, InternalConnectionId, HashState>,
+    initial_to_internal_id_map: ShardedMap<connection::InitialId, InternalConnectionId>,
     /// Maps from internal connection ID to initial ID
-    internal_to_initial_id_map: HashMap<InternalConnectionId, connection::InitialId, HashState>,
+    internal_to_initial_id_map: ShardedMap<InternalConnectionId, connection::InitialId>,
 }
 
 impl InitialIdMap {
     /// Constructs a new `InitialIdMap`
-    fn new(
-        initial_to_internal_hash_state: HashState,
-        internal_to_initial_hash_state: HashState,
-    ) -> Self {
+    fn new(random_generator: &mut dyn random::Generator) -> Self {
         Self {
-            initial_to_internal_id_map: HashMap::with_hasher(initial_to_internal_hash_state),
-            internal_to_initial_id_map: HashMap::with_hasher(internal_to_initial_hash_state),
+            initial_to_internal_id_map: ShardedMap::new(random_generator),
+            internal_to_initial_id_map: ShardedMap::new(random_generator),
         }
     }
 
     /// Gets the `InternalConnectionId` (if any) associated with the given initial id
     fn get(&self, initial_id: &connection::InitialId) -> Option<InternalConnectionId> {
-        self.initial_to_internal_id_map.get(initial_id).copied()
+        self.initial_to_internal_id_map.get(initial_id)
     }
 
     /// Inserts the given `InitialId` into the map if it is not already in the map,
     /// otherwise returns an Err
     fn try_insert(
-        &mut self,
+        &self,
         initial_id: connection::InitialId,
         internal_id: InternalConnectionId,
     ) -> Result<(), ()> {
-        let initial_to_internal_id_entry = self.initial_to_internal_id_map.entry(initial_id);
-        let internal_to_initial_id_entry = self.internal_to_initial_id_map.entry(internal_id);
+        // The shards are always locked in the order of initial ID and then internal ID. `remove`
+        // never holds both locks so it can't deadlock with an insert.
+        let mut initial_to_internal_id_shard = self.initial_to_internal_id_map.write(&initial_id);
+        let mut internal_to_initial_id_shard = self.internal_to_initial_id_map.write(&internal_id);
+
+        let initial_to_internal_id_entry = initial_to_internal_id_shard.entry(initial_id);
+        let internal_to_initial_id_entry = internal_to_initial_id_shard.entry(internal_id);
 
         match (initial_to_internal_id_entry, internal_to_initial_id_entry) {
-            (Entry::Occupied(_), _) | (_, Entry::Occupied(_)) => Err(()),
+            (Entry::Occupied(_), _) | (_, Entry::Occupied(_)) => {
+                self.initial_to_internal_id_map.on_collision();
+                Err(())
+            }
             (Entry::Vacant(initial_entry), Entry::Vacant(internal_entry)) => {
                 initial_entry.insert(internal_id);
                 internal_entry.insert(initial_id);
@@ -177,11 +305,16 @@ impl InitialIdMap {
 
     /// Removes the `InitialId` associated with the given `InternalConnectionId` from the map
     pub(crate) fn remove(
-        &mut self,
+        &self,
         internal_id: &InternalConnectionId,
     ) -> Option<connection::InitialId> {
-        let initial_id = self.internal_to_initial_id_map.remove(internal_id)?;
-        self.initial_to_internal_id_map.remove(&initial_id);
+        let initial_id = self
+            .internal_to_initial_id_map
+            .write(internal_id)
+            .remove(internal_id)?;
+        self.initial_to_internal_id_map
+            .write(&initial_id)
+            .remove(&initial_id);
         Some(initial_id)
     }
 }
@@ -199,20 +332,39 @@ pub(crate) struct ConnectionIdMapperState {
 impl ConnectionIdMapperState {
     fn new(random_generator: &mut dyn random::Generator) -> Self {
         Self {
-            local_id_map: LocalIdMap::new(HashState::new(random_generator)),
-            stateless_reset_map: StatelessResetMap::new(HashState::new(random_generator)),
-            initial_id_map: InitialIdMap::new(
-                HashState::new(random_generator),
-                HashState::new(random_generator),
-            ),
+            local_id_map: LocalIdMap::new(random_generator),
+            stateless_reset_map: StatelessResetMap::new(random_generator),
+            initial_id_map: InitialIdMap::new(random_generator),
+        }
+    }
+
+    fn metrics(&self) -> Metrics {
+        Metrics {
+            local_ids: self.local_id_map.map.occupancy(),
+            initial_ids: self.initial_id_map.initial_to_internal_id_map.occupancy(),
+            stateless_reset_tokens: self.stateless_reset_map.map.occupancy(),
         }
     }
 }
 
+/// Reads the connection ID map metrics from outside of the endpoint
+#[derive(Clone, Debug)]
+pub struct MetricsHandle(Arc<ConnectionIdMapperState>);
+
+impl MetricsHandle {
+    /// Returns a snapshot of the current occupancy of the maps
+    ///
+    /// This briefly acquires a shared lock on each shard so it should not be called on every
+    /// packet.
+    pub fn snapshot(&self) -> Metrics {
+        self.0.metrics()
+    }
+}
+
 /// Maps from external connection IDs to internal connection IDs
 pub struct ConnectionIdMapper {
     /// The shared state between mapper and registration
-    state: Arc<Mutex<ConnectionIdMapperState>>,
+    state: Arc<ConnectionIdMapperState>,
     /// The endpoint type for the endpoint using this mapper
     endpoint_type: endpoint::Type,
 }
@@ -224,22 +376,24 @@ impl ConnectionIdMapper {
         endpoint_type: endpoint::Type,
     ) -> Self {
         Self {
-            state: Arc::new(Mutex::new(ConnectionIdMapperState::new(random_generator))),
+            state: Arc::new(ConnectionIdMapperState::new(random_generator)),
             endpoint_type,
         }
     }
 
+    /// Returns a handle for reading the occupancy of the maps
+    pub fn metrics_handle(&self) -> MetricsHandle {
+        MetricsHandle(self.state.clone())
+    }
+
     /// Looks up the internal Connection ID which is associated with an external
     /// connection ID.
     pub fn lookup_internal_connection_id(
         &self,
         connection_id: &connection::LocalId,
     ) -> Option<(InternalConnectionId, connection::id::Classification)> {
-        let guard = self
-            .state
-            .lock()
-            .expect("should succeed unless the lock is poisoned");
-        guard
+        let state = &self.state;
+        state
             .local_id_map
             .get(connection_id)
             .map(|id| (id, connection::id::Classification::Local))
@@ -249,7 +403,7 @@ impl ConnectionIdMapper {
                     // map in case this ID was from a duplicate initial packet
                     connection::InitialId::try_from(*connection_id)
                         .ok()
-                        .and_then(|initial_id| guard.initial_id_map.get(&initial_id))
+                        .and_then(|initial_id| state.initial_id_map.get(&initial_id))
                         .map(|id| (id, connection::id::Classification::Initial))
                 } else {
                     None
@@ -265,11 +419,9 @@ impl ConnectionIdMapper {
         internal_id: InternalConnectionId,
     ) -> Result<(), ()> {
         debug_assert!(self.endpoint_type.is_server());
-        let mut guard = self
-            .state
-            .lock()
-            .expect("should succeed unless the lock is poisoned");
-        guard.initial_id_map.try_insert(initial_id, internal_id)
+        self.state
+            .initial_id_map
+            .try_insert(initial_id, internal_id)
     }
 
     /// Looks up the internal Connection ID which is associated with a stateless
@@ -279,10 +431,6 @@ impl ConnectionIdMapper {
         &mut self,
         peer_stateless_reset_token: &stateless_reset::Token,
     ) -> Option<InternalConnectionId> {
-        let mut guard = self
-            .state
-            .lock()
-            .expect("should succeed unless the lock is poisoned");
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.1
         //# When comparing a datagram to stateless reset token values, endpoints
         //# MUST perform the comparison without leaking information about the
@@ -294,7 +442,9 @@ impl ConnectionIdMapper {
         // but no information about the reset token itself.) Actual equality checks for
         // stateless reset tokens are implemented in stateless_reset::Token in
         // a constant-time manner.
-        guard.stateless_reset_map.remove(peer_stateless_reset_token)
+        self.state
+            .stateless_reset_map
+            .remove(peer_stateless_reset_token)
     }
 
     /// Removes the initial id mapping associated with the given internal ID
@@ -303,11 +453,7 @@ impl ConnectionIdMapper {
         internal_id: &InternalConnectionId,
     ) -> Option<connection::InitialId> {
         debug_assert!(self.endpoint_type.is_server());
-        let mut guard = self
-            .state
-            .lock()
-            .expect("should succeed unless the lock is poisoned");
-        guard.initial_id_map.remove(internal_id)
+        self.state.initial_id_map.remove(internal_id)
     }
 
     /// Creates a `LocalIdRegistry` for a new internal connection ID, which allows that
@@ -404,12 +550,7 @@ mod tests {
         registry.register_initial_connection_id(peer_id);
         registry.register_initial_stateless_reset_token(TEST_TOKEN_3);
 
-        mapper
-            .state
-            .lock()
-            .expect("should succeed unless the lock is poisoned")
-            .stateless_reset_map
-            .remove(&TEST_TOKEN_3);
+        mapper.state.stateless_reset_map.remove(&TEST_TOKEN_3);
 
         assert_eq!(
             None,
@@ -417,6 +558,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn metrics_test() {
+        let mut random_generator = random::testing::Generator(123);
+        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+        let metrics = mapper.metrics_handle();
+        let mut id_generator = InternalConnectionIdGenerator::new();
+
+        assert_eq!(metrics.snapshot().local_ids.len, 0);
+        assert_eq!(metrics.snapshot().local_ids.shards, SHARD_COUNT);
+
+        let mut registries = vec![];
+        for i in 0..1000u32 {
+            let local_id = connection::LocalId::try_from_bytes(&i.to_be_bytes()).unwrap();
+            registries.push(mapper.create_local_id_registry(
+                id_generator.generate_id(),
+                &local_id,
+                None,
+                TEST_TOKEN_1,
+                true,
+            ));
+        }
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.local_ids.len, 1000);
+        assert_eq!(snapshot.local_ids.collisions, 0);
+        // the IDs should be spread across the shards
+        assert!(
+            snapshot.local_ids.max_shard_len < 1000 / SHARD_COUNT * 2,
+            "{snapshot:?}"
+        );
+
+        let local_id = connection::LocalId::try_from_bytes(b"id000001").unwrap();
+        let initial_id = connection::InitialId::try_from(local_id).unwrap();
+        let internal_id = id_generator.generate_id();
+        assert!(mapper
+            .try_insert_initial_id(initial_id, internal_id)
+            .is_ok());
+        assert!(mapper
+            .try_insert_initial_id(initial_id, internal_id)
+            .is_err());
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.initial_ids.len, 1);
+        assert_eq!(snapshot.initial_ids.collisions, 1);
+
+        // dropping the registries removes their IDs
+        registries.clear();
+        assert_eq!(metrics.snapshot().local_ids.len, 0);
+    }
+
     #[test]
     fn initial_id_map() {
         let mut random_generator = random::testing::Generator(123);
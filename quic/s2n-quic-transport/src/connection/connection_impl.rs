@@ -10,12 +10,12 @@ use crate::{
         id::{ConnectionInfo, Interest},
         limits::Limits,
         local_id_registry::LocalIdRegistrationError,
-        ConnectionIdMapper, ConnectionInterests, ConnectionTimers, ConnectionTransmission,
-        ConnectionTransmissionContext, InternalConnectionId, Parameters as ConnectionParameters,
-        ProcessingError,
+        padding, watchdog, ConnectionIdMapper, ConnectionInterests, ConnectionTimers,
+        ConnectionTransmission, ConnectionTransmissionContext, InternalConnectionId,
+        Parameters as ConnectionParameters, ProcessingError,
     },
     contexts::{ConnectionApiCallContext, ConnectionOnTransmitError},
-    endpoint,
+    endpoint::{self, statistics},
     path::{self, path_event},
     processed_packet::ProcessedPacket,
     recovery::{recovery_event, RttEstimator},
@@ -28,14 +28,18 @@ use crate::{
 use alloc::sync::Arc;
 use bytes::Bytes;
 use core::{
+    any::Any,
     fmt,
     task::{Context, Poll, Waker},
     time::Duration,
 };
 use s2n_quic_core::{
-    application,
+    ack, application,
     application::ServerName,
-    connection::{error::Error, id::Generator as _, InitialId, PeerId},
+    connection::{
+        error::Error, id::Generator as _, ChannelBinding, Fingerprint, InitialId, PeerId,
+        RecoveryStats, Statistics, TransmissionStatus,
+    },
     crypto::{tls, CryptoSuite},
     datagram::{Receiver, Sender},
     event::{
@@ -54,12 +58,13 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::{mtu, Handle as _},
+    path::{cache as path_cache, mtu, Handle as _},
     query,
-    recovery::CongestionController,
+    recovery::{bandwidth::Bandwidth, CongestionController, ProbePolicy},
     stateless_reset::token::Generator as _,
     time::{timer, Timestamp},
     transport,
+    varint::VarInt,
 };
 
 /// Possible states for handing over a connection from the endpoint to the
@@ -178,23 +183,34 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     /// A Waker to the connection.
     waker: Waker,
     event_context: EventContext<Config>,
+    /// Records the delivery statistics of the connection
+    statistics: statistics::Recorder,
+    /// Applies the padding policy to transmitted datagrams
+    padding: padding::Controller,
 }
 
 struct EventContext<Config: endpoint::Config> {
     /// The [`Connection`]s internal identifier
     internal_connection_id: InternalConnectionId,
 
+    /// Identifies the connection attempt in emitted events
+    attempt_id: u64,
+
     /// The QUIC protocol version which is used for this particular connection
     quic_version: u32,
 
     /// Holds the event context associated with the connection
     context: <Config::EventSubscriber as event::Subscriber>::ConnectionContext,
+
+    /// The context attached to the connection by the application
+    application_context: Option<Box<dyn Any + Send>>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for EventContext<Config> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("EventContext")
             .field("internal_connection_id", &self.internal_connection_id)
+            .field("attempt_id", &self.attempt_id)
             .field("quic_version", &self.quic_version)
             .finish()
     }
@@ -211,7 +227,11 @@ impl<Config: endpoint::Config> EventContext<Config> {
             event::builder::ConnectionMeta {
                 endpoint_type: Config::ENDPOINT_TYPE,
                 id: self.internal_connection_id.into(),
+                attempt_id: self.attempt_id,
                 timestamp,
+                application_context: event::ApplicationContext::new(
+                    self.application_context.as_deref(),
+                ),
             },
             self.quic_version,
             subscriber,
@@ -256,6 +276,9 @@ macro_rules! transmission_context {
             outcome: $outcome,
             ecn,
             min_packet_len: None,
+            initial_padding: $self.limits.initial_padding(),
+            packet_coalescing: $self.limits.packet_coalescing(),
+            padding: &mut $self.padding,
             transmission_mode: $transmission_mode,
             publisher: &mut $self.event_context.publisher($timestamp, $subscriber),
             packet_interceptor: $packet_interceptor,
@@ -311,6 +334,14 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         if matches!(self.state, ConnectionState::Handshaking)
             && space_manager.is_handshake_complete()
         {
+            // In handshake-only mode, the server closes the connection instead of handing it
+            // over to the application
+            if Config::ENDPOINT_TYPE.is_server() {
+                if let Some(error) = self.limits.handshake_only() {
+                    return Err(connection::Error::application(error));
+                }
+            }
+
             // Move into the HandshakeCompleted state. This will signal the
             // necessary interest to hand over the connection to the application.
             self.accept_state = AcceptState::HandshakeCompleted;
@@ -320,6 +351,8 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             // Cancel the max handshake duration timer as the handshake has completed in time
             self.timers.max_handshake_duration_timer.cancel();
 
+            self.restart_hibernation_timer(timestamp);
+
             // We don't expect any further initial packets on this connection, so start
             // a timer to remove the mapping from the initial ID to the internal connection ID
             // to give time for any delayed initial packets to arrive.
@@ -333,6 +366,59 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         Ok(())
     }
 
+    /// Returns `true` if the connection is unable to make progress
+    ///
+    /// Only the handshake and connections with an idle timeout are checked, since connections
+    /// which disabled the idle timeout can legitimately wait on the peer or application forever.
+    fn is_stalled(&self, interests: &ConnectionInterests) -> bool {
+        let is_bounded = match self.state {
+            ConnectionState::Handshaking => true,
+            ConnectionState::Active | ConnectionState::Flushing => {
+                self.get_idle_timer_duration().is_some()
+            }
+            ConnectionState::Closing | ConnectionState::Draining | ConnectionState::Finished => {
+                false
+            }
+        };
+
+        is_bounded && watchdog::is_stalled(interests)
+    }
+
+    /// Closes the connection with a report of the component state if it is unable to make progress
+    fn check_progress(
+        &mut self,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+    ) -> Result<(), connection::Error> {
+        let interests = self.interests();
+        if !self.is_stalled(&interests) {
+            return Ok(());
+        }
+
+        let path = self.path_manager.active_path();
+        let mut report = watchdog::Report::new(self.state, &interests);
+        report
+            .timers("connection", &self.timers)
+            .timers("close_sender", &self.close_sender)
+            .timers("local_id_registry", &self.local_id_registry)
+            .timers("path_manager", &self.path_manager)
+            .timers("space_manager", &self.space_manager)
+            .transmission_interest("path_manager", &self.path_manager)
+            .transmission_interest("space_manager", &self.space_manager)
+            .transmission_interest("local_id_registry", &self.local_id_registry)
+            .transmission_interest("mtu_controller", &path.mtu_controller);
+
+        self.event_context
+            .publisher(timestamp, subscriber)
+            .on_connection_stalled(event::builder::ConnectionStalled {
+                report: report.as_str(),
+            });
+
+        Err(transport::Error::INTERNAL_ERROR
+            .with_reason("connection is not making progress")
+            .into())
+    }
+
     /// Returns the idle timeout based on transport parameters of both peers
     fn get_idle_timer_duration(&self) -> Option<Duration> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
@@ -371,6 +457,10 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             self.timers.reset_peer_idle_timer_on_send = true;
         }
 
+        self.restart_hibernation_timer(packet.datagram.timestamp);
+
+        self.statistics.record(self.space_manager.take_statistics());
+
         let mut publisher = self
             .event_context
             .publisher(packet.datagram.timestamp, subscriber);
@@ -378,7 +468,10 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         if packet.bytes_progressed > 0 {
             publisher.on_rx_stream_progress(RxStreamProgress {
                 bytes: packet.bytes_progressed,
-            })
+            });
+
+            // the application is exchanging data so resume issuing connection IDs
+            self.local_id_registry.wake();
         }
 
         if self.space_manager.handshake().is_some() && self.space_manager.is_handshake_confirmed() {
@@ -410,6 +503,31 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                 self.timers.peer_idle_timer.set(timestamp + duration);
             }
         }
+
+        self.restart_hibernation_timer(timestamp);
+    }
+
+    /// Restarts the hibernation timer, if configured, since the connection is active
+    fn restart_hibernation_timer(&mut self, timestamp: Timestamp) {
+        if self.state != ConnectionState::Active {
+            return;
+        }
+
+        if let Some(timeout) = self.limits.hibernation_timeout() {
+            self.timers.hibernation_timer.set(timestamp + timeout);
+        }
+    }
+
+    /// Releases transient buffers once the connection has been idle for the hibernation timeout
+    ///
+    /// Spare local connection IDs are also retired. All of the released buffers are allocated
+    /// again, and the connection IDs replenished, as traffic resumes.
+    fn hibernate(&mut self, timestamp: Timestamp) {
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.hibernate();
+        }
+        self.local_id_registry.hibernate(timestamp);
+        self.path_manager.peer_id_registry.hibernate();
     }
 
     fn current_pto(&self) -> Duration {
@@ -459,6 +577,9 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                         local_id_registry: &mut self.local_id_registry,
                         outcome,
                         min_packet_len: None,
+                        initial_padding: self.limits.initial_padding(),
+                        packet_coalescing: self.limits.packet_coalescing(),
+                        padding: &mut self.padding,
                         ecn,
                         transmission_mode,
                         publisher: &mut self.event_context.publisher(timestamp, subscriber),
@@ -475,18 +596,26 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         count
     }
 
+    fn event_meta(&self, timestamp: Timestamp) -> event::api::ConnectionMeta {
+        event::builder::ConnectionMeta {
+            endpoint_type: Config::ENDPOINT_TYPE,
+            id: self.event_context.internal_connection_id.into(),
+            attempt_id: self.event_context.attempt_id,
+            timestamp,
+            application_context: event::ApplicationContext::new(
+                self.event_context.application_context.as_deref(),
+            ),
+        }
+        .into_event()
+    }
+
     fn on_supervisor_timeout(
         &mut self,
         timestamp: Timestamp,
         subscriber: &mut Config::EventSubscriber,
         supervisor_context: &supervisor::Context,
     ) -> Result<(), connection::Error> {
-        let meta = event::builder::ConnectionMeta {
-            endpoint_type: Config::ENDPOINT_TYPE,
-            id: self.event_context.internal_connection_id.into(),
-            timestamp,
-        }
-        .into_event();
+        let meta = self.event_meta(timestamp);
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-21.6
         //# QUIC deployments SHOULD provide mitigations for the Slowloris
@@ -506,22 +635,12 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         // Applications may implement the `on_supervisor_timeout` trait function to
         // close the connection based on data in the supervisor context and in the
         // connection and endpoint events.
-        match subscriber.on_supervisor_timeout(
+        let outcome = subscriber.on_supervisor_timeout(
             &mut self.event_context.context,
             &meta,
             supervisor_context,
-        ) {
-            supervisor::Outcome::Continue => {}
-            supervisor::Outcome::Close { error_code } => {
-                return Err(connection::Error::application(error_code))
-            }
-            supervisor::Outcome::ImmediateClose { reason } => {
-                return Err(connection::Error::immediate_close(reason))
-            }
-            _ => {
-                unreachable!()
-            }
-        }
+        );
+        Self::on_supervisor_outcome(outcome)?;
 
         if let Some(duration) = subscriber.supervisor_timeout(
             &mut self.event_context.context,
@@ -534,6 +653,41 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         Ok(())
     }
 
+    /// Closes the connection if a subscriber requested it based on the published events
+    fn check_connection_verdict(
+        &mut self,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+    ) -> Result<(), connection::Error> {
+        // the connection is already closing so there's nothing to enforce
+        if !matches!(
+            self.state,
+            ConnectionState::Handshaking | ConnectionState::Active | ConnectionState::Flushing
+        ) {
+            return Ok(());
+        }
+
+        let meta = self.event_meta(timestamp);
+        let outcome = subscriber.connection_verdict(&mut self.event_context.context, &meta);
+        Self::on_supervisor_outcome(outcome)
+    }
+
+    #[inline]
+    fn on_supervisor_outcome(outcome: supervisor::Outcome) -> Result<(), connection::Error> {
+        match outcome {
+            supervisor::Outcome::Continue => Ok(()),
+            supervisor::Outcome::Close { error_code } => {
+                Err(connection::Error::application(error_code))
+            }
+            supervisor::Outcome::ImmediateClose { reason } => {
+                Err(connection::Error::immediate_close(reason))
+            }
+            _ => {
+                unreachable!()
+            }
+        }
+    }
+
     /// Polls for the connection to flush all of the outstanding streams
     ///
     /// Once all of the streams are finished, `Poll::Ready` will be returned
@@ -579,7 +733,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         let mut event_context = EventContext {
             context: parameters.event_context,
             internal_connection_id: parameters.internal_connection_id,
+            attempt_id: parameters.attempt_id,
             quic_version: parameters.quic_version,
+            application_context: None,
         };
 
         let rtt_estimator = RttEstimator::new(parameters.limits.initial_round_trip_time());
@@ -622,6 +778,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             cause: MtuUpdatedCause::NewPath,
         });
 
+        publisher.on_congestion_controller_initialized(
+            event::builder::CongestionControllerInitialized {
+                path_id: path_manager.active_path_id().into_event(),
+                algorithm: path_manager.active_path().congestion_controller.algorithm(),
+            },
+        );
+
         let wakeup_handle = Arc::from(parameters.wakeup_handle);
         let waker = Waker::from(wakeup_handle.clone());
         let mut connection = Self {
@@ -637,6 +800,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             wakeup_handle,
             waker,
             event_context,
+            statistics: statistics::Recorder::new(parameters.endpoint_statistics),
+            padding: parameters.padding,
         };
 
         if Config::ENDPOINT_TYPE.is_client() {
@@ -663,7 +828,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Config::ENDPOINT_TYPE,
             id: connection.internal_connection_id().into(),
+            attempt_id: connection.event_context.attempt_id,
             timestamp: parameters.timestamp,
+            application_context: Default::default(),
         };
 
         if let Some(duration) = parameters.event_subscriber.supervisor_timeout(
@@ -784,6 +951,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             self.state = ConnectionState::Finished;
         }
 
+        // Notify any pending path probes that the connection has closed
+        self.path_manager.probe.on_connection_error();
+
         // Notify the datagram manager that the connection has closed
         if let Some((space, _)) = self.space_manager.application_mut() {
             space.datagram_manager.sender.on_connection_error(error);
@@ -809,6 +979,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         &mut self,
         connection_id_format: &mut Config::ConnectionIdFormat,
         stateless_reset_token_generator: &mut Config::StatelessResetTokenGenerator,
+        random_generator: &mut Config::RandomGenerator,
         timestamp: Timestamp,
     ) -> Result<(), LocalIdRegistrationError> {
         match self.local_id_registry.connection_id_interest() {
@@ -817,7 +988,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 let connection_info = ConnectionInfo::new(&remote_address);
 
                 while count > 0 {
-                    let id = connection_id_format.generate(&connection_info);
+                    let id = connection_id_format
+                        .generate_with_random(&connection_info, random_generator);
                     let expiration = connection_id_format
                         .lifetime()
                         .map(|duration| timestamp + duration);
@@ -931,7 +1103,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 let meta = event::builder::ConnectionMeta {
                     endpoint_type: Config::ENDPOINT_TYPE,
                     id: self.internal_connection_id().into(),
+                    attempt_id: self.event_context.attempt_id,
                     timestamp,
+                    application_context: event::ApplicationContext::new(
+                        self.event_context.application_context.as_deref(),
+                    ),
                 };
                 let path_id = self.path_manager.active_path_id().as_u8();
                 let path = self.path_manager.active_path();
@@ -990,6 +1166,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             }
         }
 
+        self.statistics.record(self.space_manager.take_statistics());
+        self.statistics
+            .on_padding_sent(self.padding.take_padding_bytes());
+
         if count == 0 {
             Err(ConnectionOnTransmitError::NoDatagram)
         } else {
@@ -1043,7 +1223,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             random_generator,
             timestamp,
             &mut publisher,
-        );
+        )?;
 
         if self
             .timers
@@ -1075,6 +1255,23 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             self.on_supervisor_timeout(timestamp, subscriber, supervisor_context)?;
         }
 
+        if self
+            .timers
+            .hibernation_timer
+            .poll_expiration(timestamp)
+            .is_ready()
+        {
+            self.hibernate(timestamp);
+
+            if let Some(timeout) = self.limits.hibernation_timeout() {
+                self.event_context
+                    .publisher(timestamp, subscriber)
+                    .on_connection_hibernated(event::builder::ConnectionHibernated { timeout });
+            }
+        }
+
+        self.check_connection_verdict(timestamp, subscriber)?;
+
         // check to see if we're flushing the connection
         if self.poll_flush().is_ready() {
             return self.error;
@@ -1109,7 +1306,14 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         timestamp: Timestamp,
         subscriber: &mut Config::EventSubscriber,
         datagram: &mut Config::DatagramEndpoint,
+        random_generator: &mut Config::RandomGenerator,
     ) -> Result<(), connection::Error> {
+        // close the connection if the watchdog woke it up. This is checked before the queued
+        // state is reset so querying the interests doesn't queue another wakeup.
+        if cfg!(debug_assertions) {
+            self.check_progress(timestamp, subscriber)?;
+        }
+
         // reset the queued state first so that new wakeup request are not missed
         self.wakeup_handle.wakeup_handled();
 
@@ -1119,7 +1323,25 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // return an error if the application set one
         self.error?;
 
-        Ok(())
+        // start any path probes requested by the application
+        self.path_manager.on_probe_wakeup(random_generator);
+
+        // the application is using the connection so resume issuing connection IDs
+        self.local_id_registry.wake();
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.on_wakeup(timestamp, self.path_manager.active_path());
+        }
+
+        self.check_connection_verdict(timestamp, subscriber)
+    }
+
+    fn on_datagram_processed(
+        &mut self,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+    ) -> Result<(), connection::Error> {
+        self.check_connection_verdict(timestamp, subscriber)
     }
 
     // Packet handling
@@ -1132,6 +1354,14 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         mtu_config: mtu::Config,
         subscriber: &mut Config::EventSubscriber,
     ) -> Result<path::Id, DatagramDropReason> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# UDP datagrams with payloads
+        //#    larger than this limit are not likely to be processed by the
+        //#    receiver.
+        if datagram.payload_len as u64 > self.limits.max_udp_payload_size() {
+            return Err(DatagramDropReason::ExceedsMaxUdpPayloadSize);
+        }
+
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-9
@@ -1160,6 +1390,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         publisher.on_datagram_received(event::builder::DatagramReceived {
             len: datagram.payload_len as u16,
+            queue_id: datagram.queue_id,
         });
 
         if amplification_outcome.is_active_path_unblocked() {
@@ -1215,15 +1446,28 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         //# subsequent Initial packets include a different Source Connection ID,
         //# they MUST be discarded.
 
+        // Initial packets which are still addressed to the original destination connection ID
+        // were looked up by the endpoint in the initial ID map. These are coalesced into this
+        // handshake rather than creating a new connection for each retransmission.
+        if datagram
+            .destination_connection_id_classification
+            .is_initial()
+        {
+            self.statistics.on_coalesced_initial_packet();
+        }
+
         if let Some((space, _status)) = self.space_manager.initial_mut() {
             let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
+            publisher.on_profile_scope_entered(event::profile::Scope::PacketDecrypt);
             let packet = space.validate_and_decrypt_packet(
                 packet,
                 path_id,
                 &self.path_manager[path_id],
                 &mut publisher,
-            )?;
+            );
+            publisher.on_profile_scope_exited(event::profile::Scope::PacketDecrypt);
+            let packet = packet.map_err(|error| self.statistics.on_processing_error(error))?;
 
             publisher.on_packet_received(event::builder::PacketReceived {
                 packet_header: event::builder::PacketHeader::new(
@@ -1298,6 +1542,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             // token is received in the first Initial Packet. If that value is set, it should be
             // verified in all subsequent packets.
 
+            publisher.on_profile_scope_entered(event::profile::Scope::FrameProcessing);
             let processed_packet = space.handle_cleartext_payload(
                 packet.packet_number,
                 packet.payload,
@@ -1309,7 +1554,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 random_generator,
                 &mut publisher,
                 packet_interceptor,
-            )?;
+            );
+            publisher.on_profile_scope_exited(event::profile::Scope::FrameProcessing);
+            let processed_packet = processed_packet?;
 
             // try to move the crypto state machine forward
             self.update_crypto_state(datagram.timestamp, subscriber, datagram_endpoint)?;
@@ -1367,12 +1614,15 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         }
 
         if let Some((space, handshake_status)) = self.space_manager.handshake_mut() {
+            publisher.on_profile_scope_entered(event::profile::Scope::PacketDecrypt);
             let packet = space.validate_and_decrypt_packet(
                 packet,
                 path_id,
                 &self.path_manager[path_id],
                 &mut publisher,
-            )?;
+            );
+            publisher.on_profile_scope_exited(event::profile::Scope::PacketDecrypt);
+            let packet = packet.map_err(|error| self.statistics.on_processing_error(error))?;
 
             publisher.on_packet_received(event::builder::PacketReceived {
                 packet_header: event::builder::PacketHeader::new(
@@ -1381,6 +1631,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 ),
             });
 
+            publisher.on_profile_scope_entered(event::profile::Scope::FrameProcessing);
             let processed_packet = space.handle_cleartext_payload(
                 packet.packet_number,
                 packet.payload,
@@ -1392,7 +1643,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 random_generator,
                 &mut publisher,
                 packet_interceptor,
-            )?;
+            );
+            publisher.on_profile_scope_exited(event::profile::Scope::FrameProcessing);
+            let processed_packet = processed_packet?;
 
             if Self::Config::ENDPOINT_TYPE.is_server() {
                 //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.1
@@ -1497,13 +1750,16 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         }
 
         if let Some((space, handshake_status)) = self.space_manager.application_mut() {
+            publisher.on_profile_scope_entered(event::profile::Scope::PacketDecrypt);
             let packet = space.validate_and_decrypt_packet(
                 packet,
                 datagram,
                 path_id,
                 &self.path_manager[path_id],
                 &mut publisher,
-            )?;
+            );
+            publisher.on_profile_scope_exited(event::profile::Scope::PacketDecrypt);
+            let packet = packet.map_err(|error| self.statistics.on_processing_error(error))?;
 
             publisher.on_packet_received(event::builder::PacketReceived {
                 packet_header: event::builder::PacketHeader::new(
@@ -1522,6 +1778,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 &mut publisher,
             );
 
+            publisher.on_profile_scope_entered(event::profile::Scope::FrameProcessing);
             let processed_packet = space.handle_cleartext_payload(
                 packet.packet_number,
                 packet.payload,
@@ -1533,7 +1790,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 random_generator,
                 &mut publisher,
                 packet_interceptor,
-            )?;
+            );
+            publisher.on_profile_scope_exited(event::profile::Scope::FrameProcessing);
+            let processed_packet = processed_packet?;
 
             // try to process any post-handshake messages
             if Config::ENDPOINT_TYPE.is_client() && processed_packet.contains_crypto {
@@ -1831,6 +2090,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             interests.timeout = self.next_expiration();
         }
 
+        // wake up the connection so it can be closed with a report of the stalled components
+        if cfg!(debug_assertions) && self.is_stalled(&interests) {
+            self.wakeup_handle.wakeup();
+        }
+
         interests
     }
 
@@ -1858,6 +2122,66 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             .poll_request(stream_id, &mut api_context, request, context)
     }
 
+    fn close_streams(
+        &mut self,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        filter: &mut dyn FnMut(stream::StreamId) -> bool,
+    ) -> Result<usize, connection::Error> {
+        self.error?;
+
+        let (space, _) = self
+            .space_manager
+            .application_mut()
+            .ok_or_else(connection::Error::unspecified)?;
+
+        let mut api_context = ConnectionApiCallContext::from_wakeup_handle(&self.wakeup_handle);
+
+        Ok(space
+            .stream_manager
+            .close_streams(&mut api_context, reset, stop_sending, filter))
+    }
+
+    fn set_max_stream_data(
+        &mut self,
+        stream_id: stream::StreamId,
+        window: u32,
+    ) -> Result<(), stream::StreamError> {
+        self.error?;
+
+        let (space, _) = self
+            .space_manager
+            .application_mut()
+            .ok_or_else(connection::Error::unspecified)?;
+
+        let mut api_context = ConnectionApiCallContext::from_wakeup_handle(&self.wakeup_handle);
+
+        space
+            .stream_manager
+            .set_max_stream_data(stream_id, &mut api_context, window)
+    }
+
+    fn set_max_streams(
+        &mut self,
+        stream_type: stream::StreamType,
+        limit: VarInt,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        let (space, _) = self
+            .space_manager
+            .application_mut()
+            .ok_or_else(connection::Error::unspecified)?;
+
+        let mut api_context = ConnectionApiCallContext::from_wakeup_handle(&self.wakeup_handle);
+
+        space
+            .stream_manager
+            .set_max_streams(&mut api_context, stream_type, limit);
+
+        Ok(())
+    }
+
     fn poll_accept_stream(
         &mut self,
         stream_type: Option<stream::StreamType>,
@@ -1901,6 +2225,20 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         )
     }
 
+    fn open_reserved_stream(
+        &mut self,
+        stream_type: stream::StreamType,
+    ) -> Result<Option<stream::StreamId>, connection::Error> {
+        self.error?;
+
+        let (space, _) = self
+            .space_manager
+            .application_mut()
+            .ok_or_else(connection::Error::unspecified)?;
+
+        space.stream_manager.open_reserved_stream(stream_type)
+    }
+
     fn application_close(&mut self, error: Option<application::Error>) {
         if self.error.is_err() {
             return;
@@ -1926,6 +2264,18 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.space_manager.application_protocol.clone()
     }
 
+    fn is_resumed(&self) -> bool {
+        self.space_manager.is_resumed
+    }
+
+    fn fingerprint(&self) -> Option<Fingerprint> {
+        self.space_manager.fingerprint
+    }
+
+    fn channel_binding(&self) -> Option<ChannelBinding> {
+        self.space_manager.channel_binding
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1962,6 +2312,94 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn set_transmit_tick(&mut self, period: Option<Duration>) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.set_transmit_tick(period);
+
+            // the tick is started on the next wakeup
+            self.wakeup_handle.wakeup();
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
+    fn set_tx_coalescing(&mut self, delay: Option<Duration>) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.set_tx_coalescing(delay);
+
+            // wake up the connection so any held writes are transmitted
+            self.wakeup_handle.wakeup();
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
+    fn set_probe_policy(&mut self, policy: ProbePolicy) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.set_probe_policy(policy);
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
+    fn set_ack_mode(
+        &mut self,
+        space: PacketNumberSpace,
+        mode: ack::Mode,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        let ack_manager = match space {
+            PacketNumberSpace::Initial => self
+                .space_manager
+                .initial_mut()
+                .map(|(space, _)| &mut space.ack_manager),
+            PacketNumberSpace::Handshake => self
+                .space_manager
+                .handshake_mut()
+                .map(|(space, _)| &mut space.ack_manager),
+            PacketNumberSpace::ApplicationData => self
+                .space_manager
+                .application_mut()
+                .map(|(space, _)| &mut space.ack_manager),
+        };
+
+        // the Initial and Handshake spaces are discarded as the handshake progresses, after which
+        // there aren't any acknowledgements left to control
+        if let Some(ack_manager) = ack_manager {
+            ack_manager.set_mode(mode);
+
+            // the connection needs to update its transmission interest
+            self.wakeup_handle.wakeup();
+        }
+
+        Ok(())
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
@@ -1970,6 +2408,15 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(*self.path_manager.active_path().handle.remote_address())
     }
 
+    fn observed_address(&self) -> Result<Option<SocketAddress>, connection::Error> {
+        Ok(self.path_manager.observed_address.address())
+    }
+
+    fn current_mtu(&self) -> Result<u16, connection::Error> {
+        let mtu_controller = &self.path_manager.active_path().mtu_controller;
+        Ok(mtu_controller.max_datagram_size() as u16)
+    }
+
     fn error(&self) -> Option<connection::Error> {
         self.error.err()
     }
@@ -1996,6 +2443,94 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         }
     }
 
+    #[inline]
+    fn set_application_context(&mut self, context: Box<dyn Any + Send>) {
+        self.event_context.application_context = Some(context);
+    }
+
+    #[inline]
+    fn query_application_context(&self, query: &mut dyn query::Query) {
+        if let Some(context) = self.event_context.application_context.as_deref() {
+            let _ = query.execute(context);
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.statistics.snapshot()
+    }
+
+    fn recovery_stats(&self) -> RecoveryStats {
+        let path = self.path_manager.active_path();
+        let rtt = &path.rtt_estimator;
+        let mut stats = RecoveryStats::default();
+        stats.smoothed_rtt = rtt.smoothed_rtt();
+        stats.rttvar = rtt.rttvar();
+        stats.min_rtt = rtt.min_rtt();
+        stats.latest_rtt = rtt.latest_rtt();
+        // the backoff is doubled on each consecutive PTO
+        stats.pto_count = path.pto_backoff.checked_ilog2().unwrap_or_default();
+        stats.congestion_window = path.congestion_controller.congestion_window();
+        stats.bytes_in_flight = path.congestion_controller.bytes_in_flight();
+        stats.pacing_rate = path
+            .congestion_controller
+            .pacing_rate()
+            .map(|rate| rate.as_bytes_per_second());
+        stats
+    }
+
+    fn path_characteristics(&self) -> Option<(SocketAddress, path_cache::Characteristics)> {
+        let path = self.path_manager.active_path();
+        let rtt = &path.rtt_estimator;
+        rtt.first_rtt_sample()?;
+
+        // the congestion window is delivered once per round trip
+        let bandwidth = Bandwidth::new(
+            path.congestion_controller.congestion_window() as u64,
+            rtt.smoothed_rtt(),
+        );
+        let characteristics = path_cache::Characteristics::new(
+            rtt.smoothed_rtt(),
+            rtt.min_rtt(),
+            bandwidth,
+            path.mtu_controller.max_datagram_size() as u16,
+        );
+
+        Some((*path.handle.remote_address(), characteristics))
+    }
+
+    fn poll_probe_path(
+        &mut self,
+        context: &Context,
+    ) -> Poll<Result<Option<Duration>, connection::Error>> {
+        self.error?;
+
+        let outcome = self.path_manager.probe.poll(context);
+
+        if self.path_manager.probe.is_requested() {
+            // the challenge data is generated by the endpoint
+            self.wakeup_handle.wakeup();
+        }
+
+        outcome.map(Ok)
+    }
+
+    fn transmission_status(&self) -> TransmissionStatus {
+        let path = self.path_manager.active_path();
+        let mut status = TransmissionStatus::default();
+        status.congestion_window = path.congestion_controller.congestion_window();
+        status.bytes_in_flight = path.congestion_controller.bytes_in_flight();
+        status.is_congestion_limited = path.congestion_controller.is_congestion_limited();
+        // the pacer arms the timer whenever the next packet has to wait
+        status.is_pacing_limited = self.timers.pacing_timer.is_armed();
+        status.is_amplification_limited = path.at_amplification_limit();
+
+        if let Some(space) = self.space_manager.application() {
+            space.stream_manager.update_transmission_status(&mut status);
+        }
+
+        status
+    }
+
     fn with_event_publisher<F>(
         &mut self,
         timestamp: Timestamp,
@@ -15,26 +15,34 @@ use crate::{
     stream,
 };
 use bytes::Bytes;
-use core::task::{Context, Poll};
+use core::{
+    any::Any,
+    task::{Context, Poll},
+    time::Duration,
+};
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
-    application,
+    ack, application,
     application::ServerName,
+    connection::{ChannelBinding, Fingerprint, RecoveryStats, Statistics, TransmissionStatus},
     event::{self, builder::DatagramDropReason, supervisor, ConnectionPublisher, IntoEvent},
     inet::{DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
         initial::{CleartextInitial, ProtectedInitial},
+        number::PacketNumberSpace,
         retry::ProtectedRetry,
         short::ProtectedShort,
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
         ProtectedPacket,
     },
-    path::{mtu, Handle as _},
+    path::{cache as path_cache, mtu, Handle as _},
     query,
+    recovery::ProbePolicy,
     time::Timestamp,
+    varint::VarInt,
 };
 
 /// A trait which represents an internally used `Connection`
@@ -78,6 +86,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         &mut self,
         connection_id_format: &mut <Self::Config as endpoint::Config>::ConnectionIdFormat,
         stateless_reset_token_generator: &mut <Self::Config as endpoint::Config>::StatelessResetTokenGenerator,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         timestamp: Timestamp,
     ) -> Result<(), LocalIdRegistrationError>;
 
@@ -110,6 +119,14 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         timestamp: Timestamp,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+    ) -> Result<(), connection::Error>;
+
+    /// Is called once all of the packets in a received datagram have been handled
+    fn on_datagram_processed(
+        &mut self,
+        timestamp: Timestamp,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
     ) -> Result<(), connection::Error>;
 
     // Packet handling
@@ -353,7 +370,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 //# associated with a connection, or cannot be decrypted.
                 *check_for_stateless_reset = true;
             }
-            Err(ProcessingError::Other) => {
+            Err(ProcessingError::DuplicatePacket) | Err(ProcessingError::Other) => {
                 // All other processing errors are handled by the connection implementation
             }
         };
@@ -453,6 +470,25 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         context: Option<&Context>,
     ) -> Result<stream::ops::Response, stream::StreamError>;
 
+    fn close_streams(
+        &mut self,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        filter: &mut dyn FnMut(stream::StreamId) -> bool,
+    ) -> Result<usize, connection::Error>;
+
+    fn set_max_stream_data(
+        &mut self,
+        stream_id: stream::StreamId,
+        window: u32,
+    ) -> Result<(), stream::StreamError>;
+
+    fn set_max_streams(
+        &mut self,
+        stream_type: stream::StreamType,
+        limit: VarInt,
+    ) -> Result<(), connection::Error>;
+
     fn poll_accept_stream(
         &mut self,
         stream_type: Option<stream::StreamType>,
@@ -466,20 +502,47 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         context: &Context,
     ) -> Poll<Result<stream::StreamId, connection::Error>>;
 
+    fn open_reserved_stream(
+        &mut self,
+        stream_type: stream::StreamType,
+    ) -> Result<Option<stream::StreamId>, connection::Error>;
+
     fn application_close(&mut self, error: Option<application::Error>);
 
     fn server_name(&self) -> Option<ServerName>;
 
     fn application_protocol(&self) -> Bytes;
 
+    fn is_resumed(&self) -> bool;
+
+    fn fingerprint(&self) -> Option<Fingerprint>;
+
+    fn channel_binding(&self) -> Option<ChannelBinding>;
+
     fn ping(&mut self) -> Result<(), connection::Error>;
 
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn set_transmit_tick(&mut self, period: Option<Duration>) -> Result<(), connection::Error>;
+
+    fn set_tx_coalescing(&mut self, delay: Option<Duration>) -> Result<(), connection::Error>;
+
+    fn set_probe_policy(&mut self, policy: ProbePolicy) -> Result<(), connection::Error>;
+
+    fn set_ack_mode(
+        &mut self,
+        space: PacketNumberSpace,
+        mode: ack::Mode,
+    ) -> Result<(), connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn observed_address(&self) -> Result<Option<SocketAddress>, connection::Error>;
+
+    fn current_mtu(&self) -> Result<u16, connection::Error>;
+
     fn error(&self) -> Option<connection::Error>;
 
     fn query_event_context(&self, query: &mut dyn query::Query);
@@ -488,6 +551,26 @@ pub trait ConnectionTrait: 'static + Send + Sized {
 
     fn datagram_mut(&mut self, query: &mut dyn query::QueryMut);
 
+    fn set_application_context(&mut self, context: Box<dyn Any + Send>);
+
+    fn query_application_context(&self, query: &mut dyn query::Query);
+
+    fn transmission_status(&self) -> TransmissionStatus;
+
+    fn statistics(&self) -> Statistics;
+
+    fn recovery_stats(&self) -> RecoveryStats;
+
+    /// Returns the remote address of the active path along with its observed characteristics
+    ///
+    /// Returns `None` if the path was never measured.
+    fn path_characteristics(&self) -> Option<(SocketAddress, path_cache::Characteristics)>;
+
+    fn poll_probe_path(
+        &mut self,
+        context: &Context,
+    ) -> Poll<Result<Option<Duration>, connection::Error>>;
+
     fn with_event_publisher<F>(
         &mut self,
         timestamp: Timestamp,
@@ -18,7 +18,7 @@ use s2n_quic_core::{
     time::{timer, Duration, Timer, Timestamp},
 };
 use smallvec::SmallVec;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 /// The amount of ConnectionIds we can register without dynamic memory allocation
 const NR_STATIC_REGISTRABLE_IDS: usize = 5;
@@ -69,7 +69,7 @@ pub struct LocalIdRegistry {
     /// The internal connection ID for this registration
     internal_id: InternalConnectionId,
     /// The shared state between mapper and registration
-    state: Arc<Mutex<ConnectionIdMapperState>>,
+    state: Arc<ConnectionIdMapperState>,
     /// The connection IDs which are currently registered at the ConnectionIdMapper
     registered_ids: RegisteredIds,
     /// The sequence number to use the next time a new connection ID is registered
@@ -89,6 +89,9 @@ pub struct LocalIdRegistry {
     /// If true, the connection ID used during the the handshake will be requested
     /// to be retired following confirmation of the handshake completing.
     rotate_handshake_connection_id: bool,
+    /// If true, the connection is hibernating and only a single connection ID
+    /// is kept active for the peer
+    is_hibernating: bool,
 }
 
 type RegisteredIds = SmallVec<[LocalIdInfo; NR_STATIC_REGISTRABLE_IDS]>;
@@ -220,15 +223,13 @@ impl LocalIdRegistrationError {
 
 impl Drop for LocalIdRegistry {
     fn drop(&mut self) {
-        if let Ok(mut guard) = self.state.lock() {
-            // Unregister all previously registered IDs
-            for id_info in &self.registered_ids {
-                guard.local_id_map.remove(&id_info.id);
-            }
-
-            // Also clean up the initial ID if it had not already been removed
-            guard.initial_id_map.remove(&self.internal_id);
+        // Unregister all previously registered IDs
+        for id_info in &self.registered_ids {
+            self.state.local_id_map.remove(&id_info.id);
         }
+
+        // Also clean up the initial ID if it had not already been removed
+        self.state.initial_id_map.remove(&self.internal_id);
     }
 }
 
@@ -236,7 +237,7 @@ impl LocalIdRegistry {
     /// Constructs a new `LocalIdRegistry` and registers the provided `handshake_connection_id`
     pub(crate) fn new(
         internal_id: InternalConnectionId,
-        state: Arc<Mutex<ConnectionIdMapperState>>,
+        state: Arc<ConnectionIdMapperState>,
         handshake_connection_id: &connection::LocalId,
         handshake_connection_id_expiration_time: Option<Timestamp>,
         stateless_reset_token: stateless_reset::Token,
@@ -284,6 +285,7 @@ impl LocalIdRegistry {
                 count
             }),
             rotate_handshake_connection_id,
+            is_hibernating: false,
         };
 
         let _ = registry.register_connection_id(
@@ -350,8 +352,6 @@ impl LocalIdRegistry {
 
         // Try to insert into the global map
         self.state
-            .lock()
-            .expect("should succeed unless the lock is poisoned")
             .local_id_map
             .try_insert(id, self.internal_id)
             .map_err(|_| LocalIdRegistrationError::ConnectionIdInUse)?;
@@ -388,10 +388,7 @@ impl LocalIdRegistry {
     /// Unregisters connection IDs that have expired
     fn unregister_expired_ids(&mut self, timestamp: Timestamp) {
         {
-            let mut mapper_state = self
-                .state
-                .lock()
-                .expect("should succeed unless the lock is poisoned");
+            let mapper_state = &self.state;
 
             self.registered_ids.retain(|id_info| {
                 if id_info.is_expired(timestamp) {
@@ -503,7 +500,9 @@ impl LocalIdRegistry {
         //# To ensure that migration is possible and packets sent on different
         //# paths cannot be correlated, endpoints SHOULD provide new connection
         //# IDs before peers migrate; see Section 5.1.1.
-        let new_connection_id_count = self.active_connection_id_limit - active_connection_id_count;
+        let new_connection_id_count = self
+            .effective_connection_id_limit()
+            .saturating_sub(active_connection_id_count);
 
         if new_connection_id_count > 0 {
             self.check_active_connection_id_limit(
@@ -542,6 +541,44 @@ impl LocalIdRegistry {
         self.check_consistency();
     }
 
+    /// Retires all but the minimum number of connection IDs while the connection is hibernating
+    ///
+    /// Every active connection ID is retired and a single replacement is requested. The
+    /// replacement is issued with a Retire Prior To value covering the retired IDs, which
+    /// instructs the peer to stop using them. Any storage held for retired connection IDs
+    /// is released.
+    pub fn hibernate(&mut self, timestamp: Timestamp) {
+        if !self.is_hibernating {
+            self.is_hibernating = true;
+
+            if self.active_id_count.get(&self.registered_ids) > 1 {
+                for id_info in self
+                    .registered_ids
+                    .iter_mut()
+                    .filter(|id_info| !id_info.is_retired())
+                {
+                    id_info.retire(Some(timestamp));
+                    self.retire_prior_to = self.retire_prior_to.max(id_info.sequence_number + 1);
+                }
+
+                // clear all of the memoized values
+                self.ack_interest.clear();
+                self.transmission_interest.clear();
+                self.active_id_count.clear();
+                self.next_expiration.clear();
+            }
+        }
+
+        self.registered_ids.shrink_to_fit();
+
+        self.check_consistency();
+    }
+
+    /// Resumes issuing connection IDs up to the active connection ID limit after hibernating
+    pub fn wake(&mut self) {
+        self.is_hibernating = false;
+    }
+
     /// Writes any NEW_CONNECTION_ID frames necessary to the given context
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
         let constraint = context.transmission_constraint();
@@ -691,11 +728,23 @@ impl LocalIdRegistry {
             //# by including a sufficiently large value in the Retire Prior To field.
             assert!(
                 (active_count + new_count).saturating_sub(retired_count)
-                    <= self.active_connection_id_limit
+                    <= self.effective_connection_id_limit()
             );
         }
     }
 
+    /// The number of connection IDs to keep active for the peer
+    ///
+    /// This is reduced to a single connection ID while the connection is hibernating.
+    #[inline]
+    fn effective_connection_id_limit(&self) -> u8 {
+        if self.is_hibernating {
+            self.active_connection_id_limit.min(1)
+        } else {
+            self.active_connection_id_limit
+        }
+    }
+
     #[inline]
     fn timer(&self) -> Timer {
         Timer::from(self.next_expiration.get(&self.registered_ids))
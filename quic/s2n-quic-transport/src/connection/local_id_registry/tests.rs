@@ -861,6 +861,91 @@ fn on_timeout() {
     );
 }
 
+#[test]
+fn hibernate() {
+    let ext_id_1 = id(b"id01");
+    let ext_id_2 = id(b"id02");
+    let ext_id_3 = id(b"id03");
+    let ext_id_4 = id(b"id04");
+
+    let now = time::now();
+
+    let (_, mut reg1) = mapper(ext_id_1, None, TEST_TOKEN_1);
+    reg1.set_active_connection_id_limit(3);
+
+    assert!(reg1
+        .register_connection_id(&ext_id_2, None, TEST_TOKEN_2)
+        .is_ok());
+    assert!(reg1
+        .register_connection_id(&ext_id_3, None, TEST_TOKEN_3)
+        .is_ok());
+    assert_eq!(
+        connection::id::Interest::None,
+        reg1.connection_id_interest()
+    );
+
+    reg1.hibernate(now);
+
+    // All of the connection IDs are retired, with a removal time set in case the peer
+    // never confirms the retirement
+    for ext_id in [ext_id_1, ext_id_2, ext_id_3] {
+        assert_eq!(
+            PendingRetirementConfirmation(Some(now + EXPIRATION_BUFFER)),
+            reg1.get_connection_id_info(&ext_id).unwrap().status
+        );
+    }
+    assert_eq!(Some(now + EXPIRATION_BUFFER), reg1.next_expiration());
+
+    // Only a single replacement connection ID is requested while hibernating
+    assert_eq!(
+        connection::id::Interest::New(1),
+        reg1.connection_id_interest()
+    );
+    assert!(reg1
+        .register_connection_id(&ext_id_4, None, TEST_TOKEN_4)
+        .is_ok());
+    assert_eq!(
+        connection::id::Interest::None,
+        reg1.connection_id_interest()
+    );
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        now,
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+    reg1.on_transmit(&mut write_context);
+
+    // The replacement instructs the peer to retire all of the previous connection IDs
+    let expected_frame = Frame::NewConnectionId(NewConnectionId {
+        sequence_number: VarInt::from_u32(3),
+        retire_prior_to: VarInt::from_u32(3),
+        connection_id: ext_id_4.as_bytes(),
+        stateless_reset_token: TEST_TOKEN_4.as_ref().try_into().unwrap(),
+    });
+    assert_eq!(
+        expected_frame,
+        write_context.frame_buffer.pop_front().unwrap().as_frame()
+    );
+
+    // Hibernating again does not retire the remaining connection ID
+    reg1.hibernate(now);
+    assert!(matches!(
+        reg1.get_connection_id_info(&ext_id_4).unwrap().status,
+        PendingAcknowledgement(_)
+    ));
+
+    // The remaining connection IDs are issued once the connection wakes up
+    reg1.wake();
+    assert_eq!(
+        connection::id::Interest::New(2),
+        reg1.connection_id_interest()
+    );
+}
+
 #[test]
 fn on_handshake_confirmed_rotate_handshake_connection_id_enabled() {
     let ext_id_1 = id(b"id01");
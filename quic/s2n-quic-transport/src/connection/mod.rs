@@ -13,7 +13,7 @@ mod api;
 mod api_provider;
 mod close_sender;
 mod connection_container;
-mod connection_id_mapper;
+pub(crate) mod connection_id_mapper;
 mod connection_impl;
 mod connection_interests;
 mod connection_timers;
@@ -22,8 +22,10 @@ pub(crate) mod finalization;
 mod internal_connection_id;
 pub(crate) mod local_id_registry;
 pub(crate) mod open_token;
+pub(crate) mod padding;
 pub(crate) mod peer_id_registry;
 pub(crate) mod transmission;
+mod watchdog;
 
 pub(crate) use api_provider::{ConnectionApi, ConnectionApiProvider};
 pub(crate) use connection_container::{ConnectionContainer, ConnectionContainerIterationResult};
@@ -37,6 +39,7 @@ pub(crate) use peer_id_registry::PeerIdRegistry;
 pub(crate) use transmission::{ConnectionTransmission, ConnectionTransmissionContext};
 
 pub use api::Connection;
+pub use connection_id_mapper::{Metrics as IdMapMetrics, Occupancy as IdMapOccupancy};
 pub use connection_impl::ConnectionImpl as Implementation;
 pub use connection_trait::Lock;
 pub use open_token::Pair as OpenToken;
@@ -48,6 +51,10 @@ pub use s2n_quic_core::connection::*;
 pub struct Parameters<'a, Cfg: endpoint::Config> {
     /// The [`Connection`]s internal identifier
     pub internal_connection_id: InternalConnectionId,
+    /// Identifies the connection attempt in emitted events
+    pub attempt_id: u64,
+    /// The statistics shared by all of the connections on the endpoint
+    pub endpoint_statistics: endpoint::statistics::Counters,
     /// The local ID registry which should be utilized by the connection
     pub local_id_registry: LocalIdRegistry,
     /// The peer ID registry which should be utilized by the connection
@@ -74,6 +81,8 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub limits: connection::Limits,
     /// Configuration for the maximum transmission unit (MTU) that can be sent on a path
     pub mtu_config: mtu::Config,
+    /// Applies the padding policy to the datagrams transmitted by the connection
+    pub padding: padding::Controller,
     /// The context that should be passed to all related connection events
     pub event_context: <Cfg::EventSubscriber as event::Subscriber>::ConnectionContext,
     /// The context passed to the connection supervisor
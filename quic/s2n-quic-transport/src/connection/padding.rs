@@ -0,0 +1,138 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Applies the padding policy of a connection to the datagrams it transmits
+
+use s2n_quic_core::{connection::limits::PaddingPolicy, random};
+
+/// Decides which datagrams are padded and counts the PADDING bytes that were written
+#[derive(Debug, Default)]
+pub struct Controller {
+    policy: PaddingPolicy,
+    /// The state of the generator used by the `Random` policy
+    ///
+    /// The decisions only need to be unpredictable to on-path observers, which can't see inside
+    /// of the encrypted packets, so a fast generator seeded from the endpoint is sufficient.
+    state: u64,
+    /// The number of PADDING bytes written since the last call to `take_padding_bytes`
+    padding_bytes: u64,
+}
+
+impl Controller {
+    pub fn new<R: random::Generator + ?Sized>(policy: PaddingPolicy, random: &mut R) -> Self {
+        let mut state = 0;
+
+        // only draw from the generator when it's needed so the other policies don't change the
+        // sequence of random values used by the endpoint
+        if matches!(policy, PaddingPolicy::Random(_)) {
+            let mut seed = [0; 8];
+            random.private_random_fill(&mut seed);
+            // the generator gets stuck on a zero state
+            state = u64::from_ne_bytes(seed) | 1;
+        }
+
+        Self {
+            policy,
+            state,
+            padding_bytes: 0,
+        }
+    }
+
+    /// Returns the length that all datagrams are limited to, if any
+    #[inline]
+    pub fn max_datagram_size(&self) -> Option<usize> {
+        if let PaddingPolicy::Fixed(len) = self.policy {
+            Some(len as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the next datagram should be padded up to its maximum size
+    #[inline]
+    pub fn should_pad(&mut self, has_initial: bool, has_handshake: bool) -> bool {
+        match self.policy {
+            PaddingPolicy::Full | PaddingPolicy::Fixed(_) => true,
+            PaddingPolicy::Random(percent) => self.next_random() % 100 < percent as u64,
+            PaddingPolicy::Handshake => has_initial || has_handshake,
+            _ => false,
+        }
+    }
+
+    /// Called when a packet was padded with the given number of bytes
+    #[inline]
+    pub fn on_padding(&mut self, bytes: usize) {
+        self.padding_bytes += bytes as u64;
+    }
+
+    /// Returns the number of PADDING bytes which were written since the last call
+    #[inline]
+    pub fn take_padding_bytes(&mut self) -> u64 {
+        core::mem::take(&mut self.padding_bytes)
+    }
+
+    /// Advances the xorshift generator
+    #[inline]
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::random::testing::Generator;
+
+    #[test]
+    fn should_pad_test() {
+        let mut random = Generator::default();
+
+        let mut disabled = Controller::new(PaddingPolicy::Disabled, &mut random);
+        assert!(!disabled.should_pad(true, true));
+        assert_eq!(disabled.max_datagram_size(), None);
+
+        let mut fixed = Controller::new(PaddingPolicy::Fixed(1300), &mut random);
+        assert!(fixed.should_pad(false, false));
+        assert_eq!(fixed.max_datagram_size(), Some(1300));
+
+        let mut handshake = Controller::new(PaddingPolicy::Handshake, &mut random);
+        assert!(handshake.should_pad(true, false));
+        assert!(handshake.should_pad(false, true));
+        assert!(!handshake.should_pad(false, false));
+
+        // only the random policy consumes values from the generator
+        assert_eq!(random.0, 0);
+    }
+
+    #[test]
+    fn random_test() {
+        let mut random = Generator::default();
+
+        let mut never = Controller::new(PaddingPolicy::Random(0), &mut random);
+        let mut always = Controller::new(PaddingPolicy::Random(100), &mut random);
+        let mut half = Controller::new(PaddingPolicy::Random(50), &mut random);
+
+        let mut padded = 0;
+        for _ in 0..1000 {
+            assert!(!never.should_pad(false, false));
+            assert!(always.should_pad(false, false));
+            padded += half.should_pad(false, false) as usize;
+        }
+
+        assert!((400..=600).contains(&padded), "{padded}");
+    }
+
+    #[test]
+    fn padding_bytes_test() {
+        let mut controller = Controller::default();
+        controller.on_padding(10);
+        controller.on_padding(5);
+        assert_eq!(controller.take_padding_bytes(), 15);
+        assert_eq!(controller.take_padding_bytes(), 0);
+    }
+}
@@ -28,7 +28,7 @@ use s2n_quic_core::{
     stateless_reset, transport,
 };
 use smallvec::SmallVec;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 /// The amount of ConnectionIds we can register without dynamic memory allocation
 const NR_STATIC_REGISTRABLE_IDS: usize = 5;
@@ -56,7 +56,7 @@ pub struct PeerIdRegistry {
     /// The internal connection ID for this registration
     internal_id: InternalConnectionId,
     /// The shared state between mapper and registration
-    state: Arc<Mutex<ConnectionIdMapperState>>,
+    state: Arc<ConnectionIdMapperState>,
     /// The connection IDs which are currently registered
     registered_ids: RegisteredIds,
     /// The largest retire prior to value that has been received from the peer
@@ -232,15 +232,13 @@ impl From<PeerIdRegistrationError> for transport::Error {
 
 impl Drop for PeerIdRegistry {
     fn drop(&mut self) {
-        if let Ok(mut guard) = self.state.lock() {
-            // Stop tracking all associated stateless reset tokens
-            for token in self
-                .registered_ids
-                .iter()
-                .flat_map(|id_info| id_info.stateless_reset_token)
-            {
-                guard.stateless_reset_map.remove(&token);
-            }
+        // Stop tracking all associated stateless reset tokens
+        for token in self
+            .registered_ids
+            .iter()
+            .flat_map(|id_info| id_info.stateless_reset_token)
+        {
+            self.state.stateless_reset_map.remove(&token);
         }
     }
 }
@@ -249,7 +247,7 @@ impl PeerIdRegistry {
     /// Constructs a new `PeerIdRegistry`.
     pub(crate) fn new(
         internal_id: InternalConnectionId,
-        state: Arc<Mutex<ConnectionIdMapperState>>,
+        state: Arc<ConnectionIdMapperState>,
         rotate_handshake_connection_id: bool,
     ) -> Self {
         Self {
@@ -327,8 +325,6 @@ impl PeerIdRegistry {
         }
 
         self.state
-            .lock()
-            .expect("should succeed unless the lock is poisoned")
             .stateless_reset_map
             .insert(stateless_reset_token, self.internal_id);
     }
@@ -477,10 +473,7 @@ impl PeerIdRegistry {
             return;
         }
 
-        let mut mapper_state = self
-            .state
-            .lock()
-            .expect("should succeed unless the lock is poisoned");
+        let mapper_state = &self.state;
 
         self.registered_ids.retain(|id_info| {
             if let PendingAcknowledgement(packet_number) = id_info.status {
@@ -530,6 +523,11 @@ impl PeerIdRegistry {
         self.check_consistency();
     }
 
+    /// Releases any storage for retired connection IDs while the connection is hibernating
+    pub fn hibernate(&mut self) {
+        self.registered_ids.shrink_to_fit();
+    }
+
     /// Checks if the peer_id exists and if it is active.
     pub fn is_active(&self, peer_id: &connection::PeerId) -> bool {
         self.registered_ids
@@ -550,8 +548,6 @@ impl PeerIdRegistry {
                 //# been retired.
                 if let Some(token) = id_info.stateless_reset_token {
                     self.state
-                        .lock()
-                        .expect("should succeed unless the lock is poisoned")
                         .stateless_reset_map
                         .insert(token, self.internal_id);
                 }
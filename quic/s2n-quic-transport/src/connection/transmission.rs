@@ -8,6 +8,7 @@ use crate::{
 use core::time::Duration;
 use s2n_codec::{Encoder, EncoderBuffer};
 use s2n_quic_core::{
+    connection::limits::{InitialPadding, PacketCoalescing},
     event::{self, ConnectionPublisher as _},
     frame::ack_elicitation::AckElicitable,
     inet::ExplicitCongestionNotification,
@@ -27,6 +28,9 @@ pub struct ConnectionTransmissionContext<'a, 'sub, Config: endpoint::Config> {
     pub outcome: &'a mut transmission::Outcome,
     pub ecn: ExplicitCongestionNotification,
     pub min_packet_len: Option<usize>,
+    pub initial_padding: InitialPadding,
+    pub packet_coalescing: PacketCoalescing,
+    pub padding: &'a mut connection::padding::Controller,
     pub transmission_mode: transmission::Mode,
     pub publisher: &'a mut event::ConnectionPublisherSubscriber<'sub, Config::EventSubscriber>,
     pub packet_interceptor: &'a mut Config::PacketInterceptor,
@@ -150,7 +154,46 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
 
         // limit the number of retries to the MAX_BURST_PACKETS
         for _ in 0..MAX_BURST_PACKETS {
-            let encoder = EncoderBuffer::new(&mut buffer[..max_datagram_size]);
+            let is_mtu_probing = self.context.transmission_mode.is_mtu_probing();
+            let has_initial = has_transmission(space_manager.initial(), transmission_constraint);
+            let has_handshake =
+                has_transmission(space_manager.handshake(), transmission_constraint);
+            let has_application =
+                has_transmission(space_manager.application(), transmission_constraint);
+
+            // MTU probes are only sent in the Application Space so they are never coalesced
+            let may_coalesce = !is_mtu_probing
+                && [has_initial, has_handshake, has_application]
+                    .iter()
+                    .filter(|has_transmission| **has_transmission)
+                    .count()
+                    > 1;
+            let packet_coalescing = self.context.packet_coalescing;
+
+            // datagrams carrying Initial packets can be limited to a fixed size, which splits
+            // large ClientHello messages across multiple datagrams
+            let mut datagram_size = match self.context.initial_padding {
+                InitialPadding::Fixed(len) if has_initial => max_datagram_size.min(len as usize),
+                _ => max_datagram_size,
+            };
+
+            if let PacketCoalescing::Limited(len) = packet_coalescing {
+                if may_coalesce {
+                    datagram_size = datagram_size.min(len as usize);
+                }
+            }
+
+            // MTU probes are already padded to the size being probed
+            let pad_datagram =
+                !is_mtu_probing && self.context.padding.should_pad(has_initial, has_handshake);
+
+            if pad_datagram {
+                if let Some(len) = self.context.padding.max_datagram_size() {
+                    datagram_size = datagram_size.min(len);
+                }
+            }
+
+            let encoder = EncoderBuffer::new(&mut buffer[..datagram_size]);
             let initial_capacity = encoder.capacity();
 
             //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
@@ -179,15 +222,15 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             // end, so we check that next. Finally, if there is no ApplicationData or Handshake packet
             // to transmit, the Initial packet itself will be padded.
             let mut pn_space_to_pad = {
-                let needs_padding =
-                    has_transmission(space_manager.initial(), transmission_constraint);
-
-                if !needs_padding {
+                if !has_initial {
                     // There is no Initial packet, so no padding is needed
                     None
-                } else if has_transmission(space_manager.application(), transmission_constraint) {
+                } else if packet_coalescing == PacketCoalescing::Disabled {
+                    // The Initial packet won't be followed by any other packets
+                    Some(PacketNumberSpace::Initial)
+                } else if has_application {
                     Some(PacketNumberSpace::ApplicationData)
-                } else if has_transmission(space_manager.handshake(), transmission_constraint) {
+                } else if has_handshake {
                     Some(PacketNumberSpace::Handshake)
                 } else {
                     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9
@@ -196,13 +239,37 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 }
             };
 
+            // The padding policy pads the last packet written to the datagram, or the only one if
+            // packets aren't coalesced
+            if pad_datagram && pn_space_to_pad.is_none() {
+                let spaces = [
+                    (has_initial, PacketNumberSpace::Initial),
+                    (has_handshake, PacketNumberSpace::Handshake),
+                    (has_application, PacketNumberSpace::ApplicationData),
+                ];
+                let mut spaces = spaces
+                    .into_iter()
+                    .filter(|(has_transmission, _)| *has_transmission)
+                    .map(|(_, space)| space);
+
+                pn_space_to_pad = if packet_coalescing == PacketCoalescing::Disabled {
+                    spaces.next()
+                } else {
+                    spaces.last()
+                };
+            }
+
             //= https://www.rfc-editor.org/rfc/rfc9001#section-4
             //# When packets of different types need to be sent,
             //# endpoints SHOULD use coalesced packets to send them in the same UDP
             //# datagram.
             // here we query all of the spaces to try and fill the current datagram
 
-            let is_mtu_probing = self.context.transmission_mode.is_mtu_probing();
+            // only the first packet is written when coalescing is disabled
+            let can_write = |encoder: &EncoderBuffer| {
+                packet_coalescing != PacketCoalescing::Disabled
+                    || encoder.capacity() == initial_capacity
+            };
 
             let encoder = if let Some((space, handshake_status)) = space_manager
                 .initial_mut()
@@ -221,6 +288,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 ) {
                     Ok((outcome, encoder)) => {
                         if Config::ENDPOINT_TYPE.is_server()
+                            && !pad_datagram
                             && !outcome.ack_elicitation().is_ack_eliciting()
                         {
                             //= https://www.rfc-editor.org/rfc/rfc9000#section-14.1
@@ -256,10 +324,13 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 encoder
             };
 
+            let packet_count = (encoder.capacity() < initial_capacity) as u8;
+            let capacity = encoder.capacity();
+
             let encoder = if let Some((space, handshake_status)) = space_manager
                 .handshake_mut()
                 // MTU probes are only sent in the Application Space
-                .filter(|_| !is_mtu_probing)
+                .filter(|_| !is_mtu_probing && can_write(&encoder))
             {
                 self.context.min_packet_len = pn_space_to_pad
                     .filter(|pn_space| pn_space.is_handshake())
@@ -323,7 +394,13 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             // frames are only allowed in the ApplicationData space, which will always be the highest
             // current-available encryption level.
 
-            let encoder = if let Some((space, handshake_status)) = space_manager.application_mut() {
+            let packet_count = packet_count + (encoder.capacity() < capacity) as u8;
+            let capacity = encoder.capacity();
+
+            let encoder = if let Some((space, handshake_status)) = space_manager
+                .application_mut()
+                .filter(|_| can_write(&encoder))
+            {
                 self.context.min_packet_len = pn_space_to_pad
                     .filter(|pn_space| pn_space.is_application_data())
                     .map(|_| encoder.capacity());
@@ -381,6 +458,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 encoder
             };
 
+            let packet_count = packet_count + (encoder.capacity() < capacity) as u8;
             let datagram_len = initial_capacity - encoder.capacity();
 
             // the spaces didn't write anything so we're done
@@ -388,6 +466,24 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 return Err(tx::Error::EmptyPayload);
             }
 
+            if may_coalesce {
+                let decision = match packet_coalescing {
+                    PacketCoalescing::Limited(max_len) => {
+                        event::builder::CoalescingDecision::Limited { max_len }
+                    }
+                    PacketCoalescing::Disabled => event::builder::CoalescingDecision::Disabled,
+                    _ => event::builder::CoalescingDecision::Coalesced,
+                };
+
+                self.context
+                    .publisher
+                    .on_datagram_coalesced(event::builder::DatagramCoalesced {
+                        packet_count,
+                        len: datagram_len as u16,
+                        decision,
+                    });
+            }
+
             // Emit the transmission event
             //
             // Even though the interceptor could alter the outgoing bytes, we're going to pretend
@@ -0,0 +1,164 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detects connections which are unable to make forward progress
+//!
+//! The endpoint only drives a connection when it receives a packet, when one of its timers
+//! expires, when it has something to transmit or when the application wakes it up. A connection
+//! which isn't closed but has no timer armed and nothing to transmit relies entirely on the peer
+//! to make progress. If the peer has gone away, the connection hangs forever without an error.
+//! This almost always means a component forgot to arm a timer or report its interest.
+//!
+//! In debug builds, the connection checks its interests each time they're queried. A stalled
+//! connection is woken up and closed with an internal error, after publishing a
+//! `ConnectionStalled` event with a [`Report`] of the timers and transmission interest of each of
+//! its components.
+
+use crate::connection::ConnectionInterests;
+use alloc::string::String;
+use core::fmt::{self, Write};
+use s2n_quic_core::{time::timer, transmission::interest};
+
+/// Returns `true` if the interests don't give the endpoint any reason to drive the connection
+#[inline]
+pub fn is_stalled(interests: &ConnectionInterests) -> bool {
+    // destructure the interests so new ones have to be considered here
+    let ConnectionInterests {
+        finalization,
+        closing,
+        accept,
+        transmission,
+        new_connection_id,
+        ack,
+        timeout,
+    } = *interests;
+
+    !(finalization
+        || closing
+        || accept
+        || transmission
+        || new_connection_id
+        || ack
+        || timeout.is_some())
+}
+
+/// A description of the state of a stalled connection
+#[derive(Debug, Default)]
+pub struct Report {
+    buffer: String,
+}
+
+impl Report {
+    pub fn new<S: fmt::Debug>(state: S, interests: &ConnectionInterests) -> Self {
+        let mut report = Self::default();
+        let _ = writeln!(report.buffer, "state: {state:?}");
+        let _ = writeln!(report.buffer, "interests: {interests:?}");
+        report
+    }
+
+    /// Records the timers owned by the component
+    pub fn timers<T: timer::Provider>(&mut self, name: &str, component: &T) -> &mut Self {
+        let mut count = 0;
+        let mut armed = String::new();
+        component.for_each_timer(|timer| {
+            count += 1;
+            if let Some(expiration) = timer::Provider::next_expiration(timer) {
+                let _ = write!(armed, " {expiration}");
+            }
+            Ok(())
+        });
+
+        let armed = if armed.is_empty() { " none" } else { &armed };
+        let _ = writeln!(self.buffer, "{name} timers ({count}): armed{armed}");
+        self
+    }
+
+    /// Records the transmission interest of the component
+    pub fn transmission_interest<T: interest::Provider>(
+        &mut self,
+        name: &str,
+        component: &T,
+    ) -> &mut Self {
+        let interest = component.get_transmission_interest();
+        let _ = writeln!(self.buffer, "{name} transmission interest: {interest:?}");
+        self
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{Clock, NoopClock, Timer};
+
+    struct Lost;
+
+    impl interest::Provider for Lost {
+        fn transmission_interest<Q: interest::Query>(&self, query: &mut Q) -> interest::Result {
+            query.on_lost_data()
+        }
+    }
+
+    #[test]
+    fn is_stalled_test() {
+        assert!(is_stalled(&ConnectionInterests::default()));
+
+        let now = NoopClock.get_time();
+        for interests in [
+            ConnectionInterests {
+                timeout: Some(now),
+                ..Default::default()
+            },
+            ConnectionInterests {
+                transmission: true,
+                ..Default::default()
+            },
+            ConnectionInterests {
+                finalization: true,
+                ..Default::default()
+            },
+            ConnectionInterests {
+                accept: true,
+                ..Default::default()
+            },
+        ] {
+            assert!(!is_stalled(&interests), "{interests:?}");
+        }
+    }
+
+    #[test]
+    fn report_test() {
+        let now = NoopClock.get_time();
+        let mut armed = Timer::default();
+        armed.set(now);
+        let idle = Timer::default();
+
+        let mut report = Report::new("Active", &ConnectionInterests::default());
+        report
+            .timers("idle", &idle)
+            .timers("both", &(&armed, &idle))
+            .transmission_interest("recovery", &Lost);
+
+        let report = report.to_string();
+        assert!(report.contains("state: \"Active\""), "{report}");
+        assert!(report.contains("idle timers (1): armed none"), "{report}");
+        assert!(
+            report.contains(&format!("both timers (2): armed {now}")),
+            "{report}"
+        );
+        assert!(
+            report.contains("recovery transmission interest: LostData"),
+            "{report}"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Derives identifiers for connection attempts
+//!
+//! Each connection attempt is identified by hashing the original destination connection ID
+//! chosen by the client. Since the ODCID is recovered from the Retry token and is echoed back
+//! in Version Negotiation, all of the events for a single attempt can be correlated, even
+//! before the final connection IDs are chosen.
+
+use core::hash::Hasher;
+use s2n_quic_core::random;
+use siphasher::sip::SipHasher13;
+
+#[derive(Debug)]
+pub struct Generator {
+    k0: u64,
+    k1: u64,
+}
+
+impl Generator {
+    /// Creates a generator with random keys so attempt IDs are unique to the endpoint
+    pub fn new(random_generator: &mut dyn random::Generator) -> Self {
+        let mut k0 = [0u8; core::mem::size_of::<u64>()];
+        let mut k1 = [0u8; core::mem::size_of::<u64>()];
+
+        random_generator.private_random_fill(&mut k0);
+        random_generator.private_random_fill(&mut k1);
+
+        Self {
+            k0: u64::from_be_bytes(k0),
+            k1: u64::from_be_bytes(k1),
+        }
+    }
+
+    /// Returns the attempt ID for the given original destination connection ID
+    #[inline]
+    pub fn attempt_id(&self, original_destination_connection_id: &[u8]) -> u64 {
+        let mut hasher = SipHasher13::new_with_keys(self.k0, self.k1);
+        hasher.write(original_destination_connection_id);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::random::testing::Generator as RandomGenerator;
+
+    #[test]
+    fn attempt_id_test() {
+        let mut random = RandomGenerator::default();
+        let generator = Generator::new(&mut random);
+
+        let a = generator.attempt_id(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let b = generator.attempt_id(&[8, 7, 6, 5, 4, 3, 2, 1]);
+
+        assert_eq!(a, generator.attempt_id(&[1, 2, 3, 4, 5, 6, 7, 8]));
+        assert_ne!(a, b);
+
+        // another endpoint produces different IDs for the same connection ID
+        let other = Generator::new(&mut random);
+        assert_ne!(a, other.attempt_id(&[1, 2, 3, 4, 5, 6, 7, 8]));
+    }
+}
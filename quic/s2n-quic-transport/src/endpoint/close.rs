@@ -5,15 +5,39 @@ use super::handle::CloseReceiver;
 use crate::{connection, endpoint::handle::CloseSender};
 use alloc::sync::Arc;
 use core::{
+    pin::Pin,
     sync::atomic::{AtomicBool, Ordering},
     task::{Context, Poll, Waker},
+    time::Duration,
 };
+use futures_core::Stream;
+use s2n_quic_core::application;
+
+/// Configures how the endpoint drains its connections before closing
+#[derive(Clone, Copy, Debug)]
+pub struct Drain {
+    /// The amount of time connections are given to close on their own
+    pub timeout: Duration,
+    /// The error used to close any connections which are still open after the timeout
+    pub error: application::Error,
+}
+
+/// A request from the application to close the endpoint
+#[derive(Debug)]
+pub(crate) struct Request {
+    /// Woken once the endpoint has closed
+    waker: Waker,
+    /// Set if the endpoint should stop accepting connections and close them after a timeout
+    drain: Option<Drain>,
+}
 
 /// Held by library. Used to receive close attempts and track close state.
 #[derive(Debug)]
 pub(crate) struct CloseHandle {
     /// Used to determine if the application has interest in closing the endpoint
     first_waker: Option<Waker>,
+    /// The drain configuration which has yet to be applied by the endpoint
+    drain: Option<Drain>,
     /// A channel which is used to receive connection close attempts
     close_receiver: CloseReceiver,
     /// Track the endpoint open state
@@ -24,19 +48,21 @@ impl CloseHandle {
     pub fn new(close_receiver: CloseReceiver, endpoint_state: EndpointState) -> Self {
         Self {
             first_waker: None,
+            drain: None,
             close_receiver,
             endpoint_state,
         }
     }
 
     /// Returns `Poll::Ready` if there is interest in closing the endpoint.
-    pub fn poll_interest(&mut self) -> Poll<()> {
+    pub fn poll_interest(&mut self, cx: &mut Context) -> Poll<()> {
         if self.first_waker.is_some() {
             Poll::Ready(())
         } else {
-            match self.close_receiver.try_next() {
-                Ok(Some(waker)) => {
-                    self.first_waker = Some(waker);
+            match Stream::poll_next(Pin::new(&mut self.close_receiver), cx) {
+                Poll::Ready(Some(request)) => {
+                    self.first_waker = Some(request.waker);
+                    self.drain = request.drain;
                     Poll::Ready(())
                 }
                 _ => Poll::Pending,
@@ -44,6 +70,13 @@ impl CloseHandle {
         }
     }
 
+    /// Returns the drain configuration if the application requested the endpoint to be drained
+    ///
+    /// The configuration is only returned once.
+    pub fn take_drain(&mut self) -> Option<Drain> {
+        self.drain.take()
+    }
+
     /// Marks that the endpoint has finished processing and accepting connections and is
     /// ready to be closed.
     pub fn close(&mut self) {
@@ -52,8 +85,8 @@ impl CloseHandle {
         if let Some(waker) = self.first_waker.take() {
             waker.wake();
         }
-        while let Ok(Some(waker)) = self.close_receiver.try_next() {
-            waker.wake_by_ref();
+        while let Ok(Some(request)) = self.close_receiver.try_next() {
+            request.waker.wake();
         }
     }
 }
@@ -101,6 +134,25 @@ impl Closer {
     pub(crate) fn poll_close(
         &mut self,
         context: &mut Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.poll_request(context, None)
+    }
+
+    /// Stops accepting connections and polls for the endpoint to close
+    ///
+    /// Only the configuration passed on the first call is used.
+    pub(crate) fn poll_drain(
+        &mut self,
+        context: &mut Context,
+        drain: Drain,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.poll_request(context, Some(drain))
+    }
+
+    fn poll_request(
+        &mut self,
+        context: &mut Context,
+        drain: Option<Drain>,
     ) -> Poll<Result<(), connection::Error>> {
         if !self.endpoint_state.is_open() {
             return Poll::Ready(Ok(()));
@@ -110,7 +162,11 @@ impl Closer {
             match self.close_sender.poll_ready(context) {
                 Poll::Ready(Ok(())) => {
                     // send a waker to the endpoint, which is woken once the endpoint has closed
-                    match self.close_sender.try_send(context.waker().clone()) {
+                    let request = Request {
+                        waker: context.waker().clone(),
+                        drain,
+                    };
+                    match self.close_sender.try_send(request) {
                         Ok(_) => {
                             self.request_sent = true;
                         }
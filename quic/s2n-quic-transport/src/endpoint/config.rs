@@ -50,6 +50,20 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
 
     /// Returns the context for the endpoint configuration
     fn context(&mut self) -> Context<Self>;
+
+    /// Returns the configuration of the cache which shares path characteristics between
+    /// connections to the same remote prefix, or `None` if it is disabled
+    #[inline]
+    fn path_cache(&self) -> Option<path::cache::Config> {
+        None
+    }
+
+    /// Returns the number of each kind of packet space buffer which is retained after its
+    /// connection discards it, to be reused by new connections. `0` disables the pool.
+    #[inline]
+    fn packet_space_pool_capacity(&self) -> usize {
+        0
+    }
 }
 
 #[derive(Debug)]
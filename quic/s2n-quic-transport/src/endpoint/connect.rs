@@ -5,14 +5,23 @@ use crate::{
     connection::{self, Connection},
     endpoint::handle::ConnectorSender,
 };
+use alloc::{sync::Arc, vec::Vec};
 use core::{
     fmt,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 use futures_channel::oneshot;
-use s2n_quic_core::{application::ServerName, inet::SocketAddress, path::RemoteAddress};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::pin::{SpkiHash, SpkiPins},
+    endpoint::challenge,
+    inet::SocketAddress,
+    path::RemoteAddress,
+    transport,
+};
 
 /// Held by connection Attempt future. Used to receive the actual connection.
 pub(crate) type ConnectionReceiver = oneshot::Receiver<Result<Connection, connection::Error>>;
@@ -25,6 +34,11 @@ pub(crate) type ConnectionSender = oneshot::Sender<Result<Connection, connection
 pub struct Connect {
     pub(crate) remote_address: RemoteAddress,
     pub(crate) server_name: Option<ServerName>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) candidate_addresses: Vec<RemoteAddress>,
+    pub(crate) candidate_delay: Duration,
+    pub(crate) pinned_spki: Option<SpkiPins>,
+    pub(crate) challenge_solver: Option<ChallengeSolver>,
 }
 
 impl fmt::Display for Connect {
@@ -44,11 +58,23 @@ impl fmt::Display for Connect {
 }
 
 impl Connect {
+    //= https://www.rfc-editor.org/rfc/rfc8305#section-5
+    //# A simple implementation can have a fixed delay for how long to wait
+    //# before starting the next connection attempt.  This delay is referred
+    //# to as the "Connection Attempt Delay".  One recommended value for a
+    //# default delay is 250 milliseconds.
+    const DEFAULT_CANDIDATE_DELAY: Duration = Duration::from_millis(250);
+
     /// Creates a connection attempt with the specified remote address
     pub fn new<Addr: Into<SocketAddress>>(addr: Addr) -> Self {
         Self {
             remote_address: addr.into().into(),
             server_name: None,
+            retry_policy: None,
+            candidate_addresses: Vec::new(),
+            candidate_delay: Self::DEFAULT_CANDIDATE_DELAY,
+            pinned_spki: None,
+            challenge_solver: None,
         }
     }
 
@@ -60,6 +86,222 @@ impl Connect {
             ..self
         }
     }
+
+    /// Specifies the policy for retrying failed connection attempts
+    ///
+    /// By default, a failed connection attempt is returned to the application without any
+    /// retries.
+    #[must_use]
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy: Some(retry_policy),
+            ..self
+        }
+    }
+
+    /// Specifies additional remote addresses at which the peer may be reachable
+    ///
+    /// A connection attempt is made to each candidate address, in order, after the attempt to the
+    /// primary address. The first attempt to complete the handshake is returned to the
+    /// application and the remaining attempts are abandoned. This allows applications to race the
+    /// addresses gathered by a rendezvous protocol, which also opens any NAT bindings along the
+    /// way.
+    #[must_use]
+    pub fn with_candidate_addresses<Addrs>(self, addrs: Addrs) -> Self
+    where
+        Addrs: IntoIterator,
+        Addrs::Item: Into<SocketAddress>,
+    {
+        Self {
+            candidate_addresses: addrs.into_iter().map(|addr| addr.into().into()).collect(),
+            ..self
+        }
+    }
+
+    /// Specifies how long to wait before starting the attempt to each candidate address
+    ///
+    /// Defaults to 250ms. A delay of zero starts all of the attempts at the same time, which is
+    /// useful when both peers are connecting to each other simultaneously.
+    #[must_use]
+    pub fn with_candidate_delay(self, delay: Duration) -> Self {
+        Self {
+            candidate_delay: delay,
+            ..self
+        }
+    }
+
+    /// Requires the server's end-entity certificate to have one of the pinned public keys
+    ///
+    /// Each pin is the SHA-256 hash of a DER-encoded subject public key info. The pins are
+    /// checked in addition to the TLS provider's normal certificate verification. Only the first
+    /// certificate the server sends is matched; intermediate and root certificates are not. If it
+    /// doesn't match, the handshake fails with a
+    /// [`pin::MISMATCH`](s2n_quic_core::crypto::tls::pin::MISMATCH) crypto error.
+    ///
+    /// Resumed sessions don't include the server's certificates, so they never match a pin.
+    #[must_use]
+    pub fn with_pinned_spki<Hashes>(self, hashes: Hashes) -> Self
+    where
+        Hashes: IntoIterator<Item = SpkiHash>,
+    {
+        Self {
+            pinned_spki: Some(SpkiPins::new(hashes)),
+            ..self
+        }
+    }
+
+    /// Solves the challenges a server may issue before allocating any connection state
+    ///
+    /// Servers under load can require clients to solve an application-defined challenge, such as
+    /// a proof-of-work, which is delivered in the token of a Retry packet. Attempts which receive
+    /// a challenge without a solver, or which the solver can't solve, are dropped by the server.
+    #[must_use]
+    pub fn with_challenge_solver<S: challenge::Solver>(self, solver: S) -> Self {
+        Self {
+            challenge_solver: Some(ChallengeSolver(Arc::new(solver))),
+            ..self
+        }
+    }
+
+    /// Splits the attempt into one attempt per remote address
+    fn into_candidates(mut self) -> impl Iterator<Item = Self> {
+        let candidates = core::mem::take(&mut self.candidate_addresses);
+        let primary = self.clone();
+        core::iter::once(primary).chain(candidates.into_iter().map(move |remote_address| Self {
+            remote_address,
+            ..self.clone()
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ChallengeSolver(pub(crate) Arc<dyn challenge::Solver>);
+
+impl fmt::Debug for ChallengeSolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChallengeSolver").finish_non_exhaustive()
+    }
+}
+
+/// Controls how failed connection attempts are retried
+///
+/// Only failures matching one of the enabled classifications are retried. Each retry waits for
+/// an exponentially increasing backoff, starting with the initial backoff and capped at the
+/// maximum.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::time::Duration;
+/// use s2n_quic_transport::endpoint::connect::RetryPolicy;
+///
+/// let policy = RetryPolicy::new(5)
+///     .with_backoff(Duration::from_millis(50), Duration::from_secs(2))
+///     .with_retry_on_version_negotiation(true);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_on_timeout: bool,
+    retry_on_version_negotiation: bool,
+    retry_on_refused: bool,
+}
+
+impl RetryPolicy {
+    const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+    const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+    /// Creates a policy which makes at most `max_attempts` attempts, including the first one
+    ///
+    /// Timeouts and refused connections are retried by default.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: Self::DEFAULT_INITIAL_BACKOFF,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            retry_on_timeout: true,
+            retry_on_version_negotiation: false,
+            retry_on_refused: true,
+        }
+    }
+
+    /// Sets the backoff before the first retry and the maximum backoff between retries
+    #[must_use]
+    pub fn with_backoff(self, initial: Duration, max: Duration) -> Self {
+        Self {
+            initial_backoff: initial,
+            max_backoff: max.max(initial),
+            ..self
+        }
+    }
+
+    /// Retries attempts which failed because the handshake or idle timer expired, or the server
+    /// didn't respond to the first flight
+    #[must_use]
+    pub fn with_retry_on_timeout(self, enabled: bool) -> Self {
+        Self {
+            retry_on_timeout: enabled,
+            ..self
+        }
+    }
+
+    /// Retries attempts which failed because the endpoints could not agree on a version
+    #[must_use]
+    pub fn with_retry_on_version_negotiation(self, enabled: bool) -> Self {
+        Self {
+            retry_on_version_negotiation: enabled,
+            ..self
+        }
+    }
+
+    /// Retries attempts which were refused by the server
+    #[must_use]
+    pub fn with_retry_on_refused(self, enabled: bool) -> Self {
+        Self {
+            retry_on_refused: enabled,
+            ..self
+        }
+    }
+
+    /// Returns the backoff before the next attempt if the failed attempt should be retried
+    ///
+    /// `attempts` is the number of attempts which have been made, including the failed one.
+    pub fn backoff(&self, attempts: u32, error: &connection::Error) -> Option<Duration> {
+        if attempts >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+
+        let multiplier = 1u32
+            .checked_shl(attempts.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let backoff = self
+            .initial_backoff
+            .checked_mul(multiplier)
+            .unwrap_or(self.max_backoff);
+
+        Some(backoff.min(self.max_backoff))
+    }
+
+    fn is_retryable(&self, error: &connection::Error) -> bool {
+        match error {
+            connection::Error::IdleTimerExpired { .. }
+            | connection::Error::MaxHandshakeDurationExceeded { .. }
+            | connection::Error::NoServerResponse { .. } => self.retry_on_timeout,
+            connection::Error::Transport { code, .. }
+                if *code == transport::Error::VERSION_NEGOTIATION_ERROR.code =>
+            {
+                self.retry_on_version_negotiation
+            }
+            connection::Error::Transport { code, .. }
+                if *code == transport::Error::CONNECTION_REFUSED.code =>
+            {
+                self.retry_on_refused
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Make it easy for applications to create a connection attempt without importing the `Connect` struct
@@ -74,11 +316,27 @@ impl<T: Into<SocketAddress>> From<T> for Connect {
 pub(crate) struct Request {
     pub connect: Connect,
     pub sender: ConnectionSender,
+    /// Set when the request retries a previously failed attempt
+    pub retry: Option<Retry>,
+    /// Set when the request should be delayed before opening the connection
+    pub delay: Option<Duration>,
+}
+
+/// Describes the failed attempt which is being retried by a `Request`
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Retry {
+    /// The number of attempts which have been made, including the failed one
+    pub attempts: u32,
+    pub backoff: Duration,
+    pub error: connection::Error,
 }
 
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct Attempt {
-    state: AttemptState,
+    /// The attempts to each of the remote addresses, starting with the primary address
+    candidates: Vec<Candidate>,
+    /// The error of the first attempt which failed
+    error: Option<connection::Error>,
 }
 
 impl Attempt {
@@ -91,33 +349,99 @@ impl Attempt {
     /// * The attempt returns a `Self` while holding on to the oneshot receiver
     /// * The application polls the `Attempt` until either a successful `Connection` or `connection::Error` is
     ///   received over the oneshot receiver.
+    ///
+    /// If the `Connect` has a retry policy, retryable failures transition the attempt back to
+    /// submitting a `Request`, which the endpoint delays by the backoff.
+    ///
+    /// If the `Connect` has candidate addresses, a `Request` is submitted for each address, which
+    /// the endpoint staggers by the candidate delay. The first connection to be received wins the
+    /// race and the receivers for the remaining requests are dropped.
     pub(crate) fn new(opener: &ConnectorSender, connect: Connect) -> Self {
+        let candidate_delay = connect.candidate_delay;
+        let candidates = connect
+            .into_candidates()
+            .zip(0u32..)
+            .map(|(connect, index)| {
+                let delay =
+                    Some(candidate_delay.saturating_mul(index)).filter(|delay| !delay.is_zero());
+                Candidate::new(opener, connect, delay)
+            })
+            .collect();
+
+        Self {
+            candidates,
+            error: None,
+        }
+    }
+}
+
+/// An attempt to connect to a single remote address
+struct Candidate {
+    state: AttemptState,
+    /// The attempt information required for a retry
+    ///
+    /// This is only set if the `Connect` has a retry policy.
+    retry: Option<RetryState>,
+}
+
+struct RetryState {
+    connect: Connect,
+    policy: RetryPolicy,
+    opener: ConnectorSender,
+    attempts: u32,
+}
+
+impl Candidate {
+    fn new(opener: &ConnectorSender, connect: Connect, delay: Option<Duration>) -> Self {
+        let retry = connect.retry_policy.map(|policy| RetryState {
+            connect: connect.clone(),
+            policy,
+            opener: opener.clone(),
+            attempts: 1,
+        });
+        let (request, receiver) = Self::request(connect, None, delay);
+        Self {
+            state: AttemptState::Connect(request, opener.clone(), receiver),
+            retry,
+        }
+    }
+
+    fn request(
+        connect: Connect,
+        retry: Option<Retry>,
+        delay: Option<Duration>,
+    ) -> (Request, ConnectionReceiver) {
         // open a oneshot channel to receive the connection or error after the endpoint attempted the handshake
         let (response, receiver) = oneshot::channel();
         // The request includes both the connection info and response onshot channel
         let request = Request {
             connect,
             sender: response,
+            retry,
+            delay,
         };
-        Self {
-            state: AttemptState::Connect(request, opener.clone(), receiver),
-        }
+        (request, receiver)
     }
-}
 
-enum AttemptState {
-    /// The attempt is currently waiting for capacity in the `ConnectorSender` to make the `Request`
-    Connect(Request, ConnectorSender, ConnectionReceiver),
-    /// The attempt is currently waiting for a response back from the endpoint on the `ConnectionReceiver`
-    Waiting(ConnectionReceiver),
-    /// This is an intermediate state and should not persist across calls to `poll`
-    Unreachable,
-}
-
-impl Future for Attempt {
-    type Output = Result<Connection, connection::Error>;
+    /// Returns the next state if the failed attempt should be retried
+    fn on_error(&mut self, error: connection::Error) -> Option<AttemptState> {
+        let state = self.retry.as_mut()?;
+        let backoff = state.policy.backoff(state.attempts, &error)?;
+        let retry = Retry {
+            attempts: state.attempts,
+            backoff,
+            error,
+        };
+        state.attempts += 1;
+        let (request, receiver) = Self::request(state.connect.clone(), Some(retry), None);
+        Some(AttemptState::Connect(
+            request,
+            state.opener.clone(),
+            receiver,
+        ))
+    }
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Result<Connection, connection::Error>> {
         loop {
             match core::mem::replace(&mut self.state, AttemptState::Unreachable) {
                 AttemptState::Connect(request, mut opener, response) => {
@@ -157,6 +481,13 @@ impl Future for Attempt {
                 }
                 AttemptState::Waiting(mut response) => {
                     return match Pin::new(&mut response).poll(cx) {
+                        Poll::Ready(Ok(Err(error))) => {
+                            if let Some(state) = self.on_error(error) {
+                                self.state = state;
+                                continue;
+                            }
+                            Err(error).into()
+                        }
                         Poll::Ready(Ok(res)) => Poll::Ready(res),
                         Poll::Ready(Err(_)) => {
                             // The endpoint has closed
@@ -177,3 +508,114 @@ impl Future for Attempt {
         }
     }
 }
+
+enum AttemptState {
+    /// The attempt is currently waiting for capacity in the `ConnectorSender` to make the `Request`
+    Connect(Request, ConnectorSender, ConnectionReceiver),
+    /// The attempt is currently waiting for a response back from the endpoint on the `ConnectionReceiver`
+    Waiting(ConnectionReceiver),
+    /// This is an intermediate state and should not persist across calls to `poll`
+    Unreachable,
+}
+
+impl Future for Attempt {
+    type Output = Result<Connection, connection::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut index = 0;
+        while let Some(candidate) = self.candidates.get_mut(index) {
+            match candidate.poll(cx) {
+                Poll::Ready(Ok(connection)) => {
+                    // abandon the attempts to the remaining candidates
+                    self.candidates.clear();
+                    return Ok(connection).into();
+                }
+                Poll::Ready(Err(error)) => {
+                    self.candidates.remove(index);
+                    self.error.get_or_insert(error);
+                }
+                Poll::Pending => index += 1,
+            }
+        }
+
+        if self.candidates.is_empty() {
+            let error = self
+                .error
+                .take()
+                .unwrap_or_else(connection::Error::unspecified);
+            return Err(error).into();
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_policy_test() {
+        let policy = RetryPolicy::new(4)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(250));
+        let timeout = connection::Error::idle_timer_expired();
+
+        assert_eq!(
+            policy.backoff(1, &timeout),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.backoff(2, &timeout),
+            Some(Duration::from_millis(200))
+        );
+        // the backoff is capped
+        assert_eq!(
+            policy.backoff(3, &timeout),
+            Some(Duration::from_millis(250))
+        );
+        // the attempts are exhausted
+        assert_eq!(policy.backoff(4, &timeout), None);
+
+        let refused = connection::Error::from(transport::Error::CONNECTION_REFUSED);
+        assert!(policy.backoff(1, &refused).is_some());
+        assert!(policy
+            .with_retry_on_refused(false)
+            .backoff(1, &refused)
+            .is_none());
+
+        let version_negotiation =
+            connection::Error::from(transport::Error::VERSION_NEGOTIATION_ERROR);
+        assert!(policy.backoff(1, &version_negotiation).is_none());
+        assert!(policy
+            .with_retry_on_version_negotiation(true)
+            .backoff(1, &version_negotiation)
+            .is_some());
+
+        // application errors are never retried
+        let application = connection::Error::application(1u8.into());
+        assert!(policy.backoff(1, &application).is_none());
+    }
+
+    #[test]
+    fn candidates_test() {
+        let primary = SocketAddress::default();
+        let candidate: SocketAddress = s2n_quic_core::inet::IpV4Address::new([192, 0, 2, 1])
+            .with_port(443)
+            .into();
+
+        let connect = Connect::new(primary)
+            .with_server_name("localhost")
+            .with_candidate_addresses([candidate]);
+
+        let candidates: Vec<_> = connect.into_candidates().collect();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(*candidates[0].remote_address, primary);
+        assert_eq!(*candidates[1].remote_address, candidate);
+
+        for connect in candidates {
+            // the split attempts don't race any other addresses
+            assert!(connect.candidate_addresses.is_empty());
+            assert_eq!(connect.server_name.as_deref(), Some("localhost"));
+        }
+    }
+}
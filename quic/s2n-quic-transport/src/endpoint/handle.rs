@@ -5,15 +5,22 @@
 
 use crate::{
     connection,
-    connection::Connection,
-    endpoint::{close, close::CloseHandle, connect},
+    connection::{connection_id_mapper, Connection, IdMapMetrics},
+    endpoint::{
+        close,
+        close::CloseHandle,
+        connect,
+        lifecycle::{self, ClosedConnections},
+        statistics,
+    },
 };
 use core::{
     pin::Pin,
-    task::{Context, Poll, Waker},
+    task::{Context, Poll},
 };
 use futures_channel::mpsc;
 use futures_core::Stream;
+use s2n_quic_core::connection::Statistics;
 
 /// Held by application. Used to accept new connections.
 pub(crate) type AcceptorReceiver = mpsc::UnboundedReceiver<Connection>;
@@ -26,9 +33,9 @@ pub(crate) type ConnectorReceiver = mpsc::Receiver<connect::Request>;
 pub(crate) type ConnectorSender = mpsc::Sender<connect::Request>;
 
 /// Held by library. Used to receive close attempts from the application.
-pub(crate) type CloseReceiver = mpsc::Receiver<Waker>;
+pub(crate) type CloseReceiver = mpsc::Receiver<close::Request>;
 /// Held by the application. Used to submit connection close attempts to the library.
-pub(crate) type CloseSender = mpsc::Sender<Waker>;
+pub(crate) type CloseSender = mpsc::Sender<close::Request>;
 
 /// The [`Handle`] allows applications to accept and open QUIC connections on an `Endpoint`.
 #[derive(Debug)]
@@ -41,7 +48,15 @@ impl Handle {
     /// Creates a new `Handle` with a limit opening connection limit.
     pub(crate) fn new(
         max_opening_connections: usize,
-    ) -> (Self, AcceptorSender, ConnectorReceiver, CloseHandle) {
+        statistics: statistics::Counters,
+        id_map_metrics: connection_id_mapper::MetricsHandle,
+    ) -> (
+        Self,
+        AcceptorSender,
+        ConnectorReceiver,
+        CloseHandle,
+        lifecycle::Notifier,
+    ) {
         let (acceptor_sender, acceptor_receiver) = mpsc::unbounded();
         let (connector_sender, connector_receiver) = mpsc::channel(max_opening_connections);
 
@@ -49,13 +64,24 @@ impl Handle {
 
         let endpoint_state = close::EndpointState::default();
         let closer = close::Closer::new(close_sender, endpoint_state.clone());
+
+        let (subscription_sender, subscription_receiver) = mpsc::unbounded();
+        let closed_subscriber = lifecycle::Subscriber::new(subscription_sender);
+
         let handle = Self {
             acceptor: Acceptor {
                 acceptor: acceptor_receiver,
+                closer: closer.clone(),
+                statistics: statistics.clone(),
+                id_map_metrics: id_map_metrics.clone(),
+                closed_subscriber: closed_subscriber.clone(),
             },
             connector: Connector {
                 connector: connector_sender,
                 closer,
+                statistics,
+                id_map_metrics,
+                closed_subscriber,
             },
         };
         (
@@ -63,6 +89,7 @@ impl Handle {
             acceptor_sender,
             connector_receiver,
             CloseHandle::new(close_receiver, endpoint_state),
+            lifecycle::Notifier::new(subscription_receiver),
         )
     }
 }
@@ -70,6 +97,10 @@ impl Handle {
 #[derive(Debug)]
 pub struct Acceptor {
     acceptor: AcceptorReceiver,
+    closer: close::Closer,
+    statistics: statistics::Counters,
+    id_map_metrics: connection_id_mapper::MetricsHandle,
+    closed_subscriber: lifecycle::Subscriber,
 }
 
 impl Acceptor {
@@ -92,12 +123,42 @@ impl Acceptor {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    /// Stops accepting new connections and polls for the endpoint to close
+    ///
+    /// Any connections which are still open after the drain timeout are closed with the
+    /// configured error.
+    pub fn poll_drain(
+        &mut self,
+        context: &mut Context,
+        drain: close::Drain,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.closer.poll_drain(context, drain)
+    }
+
+    /// Returns the statistics aggregated over all of the connections on the endpoint
+    pub fn statistics(&self) -> Statistics {
+        self.statistics.snapshot()
+    }
+
+    /// Returns the occupancy of the endpoint's connection ID maps
+    pub fn id_map_metrics(&self) -> IdMapMetrics {
+        self.id_map_metrics.snapshot()
+    }
+
+    /// Returns a stream of the connections which are removed from the endpoint after this call
+    pub fn closed_connections(&self) -> ClosedConnections {
+        self.closed_subscriber.subscribe()
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Connector {
     connector: ConnectorSender,
     closer: close::Closer,
+    statistics: statistics::Counters,
+    id_map_metrics: connection_id_mapper::MetricsHandle,
+    closed_subscriber: lifecycle::Subscriber,
 }
 
 impl Connector {
@@ -110,4 +171,19 @@ impl Connector {
     pub fn poll_close(&mut self, context: &mut Context) -> Poll<Result<(), connection::Error>> {
         self.closer.poll_close(context)
     }
+
+    /// Returns the statistics aggregated over all of the connections on the endpoint
+    pub fn statistics(&self) -> Statistics {
+        self.statistics.snapshot()
+    }
+
+    /// Returns the occupancy of the endpoint's connection ID maps
+    pub fn id_map_metrics(&self) -> IdMapMetrics {
+        self.id_map_metrics.snapshot()
+    }
+
+    /// Returns a stream of the connections which are removed from the endpoint after this call
+    pub fn closed_connections(&self) -> ClosedConnections {
+        self.closed_subscriber.subscribe()
+    }
 }
@@ -17,6 +17,7 @@ use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     crypto::{tls, tls::Endpoint as TLSEndpoint, CryptoSuite, InitialKey},
     datagram::{Endpoint, PreConnectionInfo},
+    endpoint::{limits::ConnectionOpened, Limiter as _},
     event::{self, supervisor, ConnectionPublisher, IntoEvent, Subscriber as _},
     inet::{datagram, DatagramInfo},
     packet::initial::ProtectedInitial,
@@ -60,6 +61,20 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         let remote_address = header.path.remote_address();
 
+        let limits = self
+            .config
+            .context()
+            .connection_limits
+            .on_connection(&LimitsInfo::new(&remote_address));
+
+        // Reject the datagram before creating any connection state if it exceeds the limit the
+        // connection would advertise to the peer
+        if datagram.payload_len as u64 > limits.max_udp_payload_size() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("datagram exceeds max_udp_payload_size")
+                .into());
+        }
+
         // The first connection ID to persist and use for routing incoming packets
         let initial_connection_id;
         // The randomly generated destination connection ID that was sent from the client
@@ -86,11 +101,10 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             // The destination connection ID on the packet was randomly generated by the client
             // so we'll generate a new initial_connection_id.
             let connection_info = ConnectionInfo::new(&remote_address);
-            initial_connection_id = self
-                .config
-                .context()
+            let context = self.config.context();
+            initial_connection_id = context
                 .connection_id_format
-                .generate(&connection_info);
+                .generate_with_random(&connection_info, context.random_generator);
         }
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
@@ -164,12 +178,6 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         let mut transport_parameters = ServerTransportParameters::default();
 
-        let limits = self
-            .config
-            .context()
-            .connection_limits
-            .on_connection(&LimitsInfo::new(&remote_address));
-
         transport_parameters.load_limits(&limits);
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
@@ -218,6 +226,10 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         .try_into()
         .unwrap();
 
+        // look up the path before borrowing the endpoint context
+        let cached_path = self.cached_path(&remote_address, datagram.timestamp);
+        let mtu_config = self.path_mtu_config(&remote_address, cached_path.as_ref());
+
         let endpoint_context = self.config.context();
 
         transport_parameters.max_datagram_frame_size = endpoint_context
@@ -226,22 +238,32 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .try_into()
             .expect("Failed to convert max_datagram_frame_size");
 
+        transport_parameters.version_information =
+            Some(endpoint::version::version_information(packet.version));
+
         let tls_session = endpoint_context
             .tls
             .new_server_session(&transport_parameters);
 
         let path_info =
-            congestion_controller::PathInfo::new(self.mtu_config.initial_mtu, &remote_address);
+            congestion_controller::PathInfo::new(mtu_config.initial_mtu, &remote_address)
+                .with_cached(cached_path);
         let congestion_controller = endpoint_context
             .congestion_controller
             .new_congestion_controller(path_info);
 
         let quic_version = packet.version;
 
+        let attempt_id = self
+            .attempt_id_generator
+            .attempt_id(original_destination_connection_id.as_bytes());
+
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Config::ENDPOINT_TYPE,
             id: internal_connection_id.into(),
+            attempt_id,
             timestamp: datagram.timestamp,
+            application_context: Default::default(),
         };
 
         let supervisor_context = supervisor::Context::new(
@@ -253,7 +275,11 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         let mut event_context = endpoint_context.event_subscriber.create_connection_context(
             &meta.clone().into_event(),
-            &event::builder::ConnectionInfo {}.into_event(),
+            &event::builder::ConnectionInfo {
+                remote_address: (&*remote_address).into_event(),
+                server_name: None,
+            }
+            .into_event(),
         );
 
         let mut publisher = event::ConnectionPublisherSubscriber::new(
@@ -269,12 +295,19 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             initial_key,
             initial_header_key,
             datagram.timestamp,
+            self.packet_space_pool.clone(),
             &mut publisher,
         );
 
-        let mtu_config = self.mtu_config;
+        let padding = connection::padding::Controller::new(
+            limits.padding_policy(),
+            endpoint_context.random_generator,
+        );
+
         let connection_parameters = connection::Parameters {
             internal_connection_id,
+            attempt_id,
+            endpoint_statistics: self.statistics.clone(),
             local_id_registry,
             peer_id_registry,
             space_manager,
@@ -290,6 +323,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             quic_version,
             limits,
             mtu_config,
+            padding,
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
@@ -364,6 +398,9 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                     &mut false,
                 )?;
 
+                connection
+                    .on_datagram_processed(datagram.timestamp, endpoint_context.event_subscriber)?;
+
                 Ok(())
             };
 
@@ -410,6 +447,19 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         self.connections
             .insert_server_connection(connection, internal_connection_id);
 
+        // track the attempt address so the limiter is notified with it once the connection is
+        // removed, even if the peer migrates
+        let remote_address = *remote_address;
+        self.config
+            .context()
+            .endpoint_limits
+            .on_connection_opened(&ConnectionOpened::new(
+                &remote_address,
+                datagram.timestamp.into_event(),
+            ));
+        self.opened_connections
+            .insert(internal_connection_id, remote_address);
+
         Ok(())
     }
 }
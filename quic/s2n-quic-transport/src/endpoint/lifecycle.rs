@@ -0,0 +1,158 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Notifies applications when connections are removed from the endpoint
+//!
+//! A connection is removed once it has finished closing or draining. This happens regardless of
+//! whether the application still holds a handle to the connection, which allows resource managers
+//! to reliably release anything associated with the connection.
+
+use crate::connection;
+use alloc::vec::Vec;
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_channel::mpsc;
+use futures_core::Stream;
+use s2n_quic_core::connection::Statistics;
+
+/// Held by library. Used to receive subscriptions from the application.
+pub(crate) type SubscriptionReceiver = mpsc::UnboundedReceiver<mpsc::UnboundedSender<Closed>>;
+/// Held by application. Used to submit subscriptions to the library.
+pub(crate) type SubscriptionSender = mpsc::UnboundedSender<mpsc::UnboundedSender<Closed>>;
+
+/// Describes a connection which was removed from the endpoint
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct Closed {
+    /// The internal identifier of the connection
+    ///
+    /// This matches the `id` reported in connection events.
+    pub id: u64,
+
+    /// The error which closed the connection
+    pub error: connection::Error,
+
+    /// The final statistics for the connection
+    pub statistics: Statistics,
+}
+
+impl Closed {
+    #[inline]
+    pub(crate) fn new(id: u64, error: connection::Error, statistics: Statistics) -> Self {
+        Self {
+            id,
+            error,
+            statistics,
+        }
+    }
+}
+
+/// Held by library. Used to notify subscribers of closed connections.
+#[derive(Debug)]
+pub(crate) struct Notifier {
+    subscriptions: SubscriptionReceiver,
+    subscribers: Vec<mpsc::UnboundedSender<Closed>>,
+}
+
+impl Notifier {
+    pub fn new(subscriptions: SubscriptionReceiver) -> Self {
+        Self {
+            subscriptions,
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Notifies all of the current subscribers that a connection was removed
+    #[inline]
+    pub fn on_closed<F: FnOnce() -> Closed>(&mut self, closed: F) {
+        while let Ok(Some(subscriber)) = self.subscriptions.try_next() {
+            self.subscribers.push(subscriber);
+        }
+
+        // avoid building the notification if no one is listening
+        if self.subscribers.is_empty() {
+            return;
+        }
+
+        let closed = closed();
+        // remove any subscribers which are no longer interested
+        self.subscribers
+            .retain(|subscriber| subscriber.unbounded_send(closed.clone()).is_ok());
+    }
+}
+
+/// Held by application. Used to subscribe to closed connections.
+#[derive(Clone, Debug)]
+pub(crate) struct Subscriber(SubscriptionSender);
+
+impl Subscriber {
+    pub fn new(sender: SubscriptionSender) -> Self {
+        Self(sender)
+    }
+
+    /// Returns a stream of the connections which are removed after this call
+    pub fn subscribe(&self) -> ClosedConnections {
+        let (sender, receiver) = mpsc::unbounded();
+        // if the endpoint has shut down, the returned stream immediately ends
+        let _ = self.0.unbounded_send(sender);
+        ClosedConnections(receiver)
+    }
+}
+
+/// A stream of connections which were removed from the endpoint
+///
+/// The stream ends once the endpoint has shut down.
+#[derive(Debug)]
+pub struct ClosedConnections(mpsc::UnboundedReceiver<Closed>);
+
+impl ClosedConnections {
+    /// Polls for the next closed connection
+    pub fn poll_next_closed(&mut self, context: &mut Context) -> Poll<Option<Closed>> {
+        Stream::poll_next(Pin::new(&mut self.0), context)
+    }
+}
+
+impl Stream for ClosedConnections {
+    type Item = Closed;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>> {
+        self.poll_next_closed(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::endpoint;
+
+    #[test]
+    fn notifier_test() {
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber = Subscriber::new(sender);
+        let mut notifier = Notifier::new(receiver);
+        let error = connection::Error::closed(endpoint::Location::Local);
+
+        // nothing is built without any subscribers
+        notifier.on_closed(|| panic!("no subscribers"));
+
+        let mut a = subscriber.subscribe();
+        let mut b = subscriber.subscribe();
+
+        notifier.on_closed(|| Closed::new(1, error, Statistics::default()));
+        drop(b);
+        notifier.on_closed(|| Closed::new(2, error, Statistics::default()));
+        assert_eq!(notifier.subscribers.len(), 1);
+
+        assert_eq!(a.0.try_next().unwrap().unwrap().id, 1);
+        assert_eq!(a.0.try_next().unwrap().unwrap().id, 2);
+
+        // the stream ends once the endpoint is dropped
+        drop(notifier);
+        assert!(a.0.try_next().unwrap().is_none());
+
+        b = subscriber.subscribe();
+        assert!(b.0.try_next().unwrap().is_none());
+    }
+}
@@ -13,27 +13,35 @@ use crate::{
     endpoint,
     endpoint::close::CloseHandle,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{self, PacketSpaceManager},
     wakeup_queue::WakeupQueue,
 };
-use alloc::collections::VecDeque;
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    vec::Vec,
+};
 use core::{
     convert::TryInto,
     task::{self, Poll},
 };
 use s2n_codec::{DecoderBuffer, DecoderBufferMut};
 use s2n_quic_core::{
+    application,
     connection::{
-        id::{ConnectionInfo, Generator},
+        id::{ConnectionInfo, Generator, Validator as _},
         InitialId, LocalId, PeerId,
     },
     crypto::{tls, tls::Endpoint as _, CryptoSuite, InitialKey},
     datagram::{Endpoint as DatagramEndpoint, PreConnectionInfo},
-    endpoint::{limits::Outcome, Limiter as _},
+    endpoint::{
+        challenge,
+        limits::{ChallengeResponse, ConnectionClosed, Outcome},
+        Limiter as _,
+    },
     event::{
         self, supervisor, ConnectionPublisher, EndpointPublisher as _, IntoEvent, Subscriber as _,
     },
-    inet::{datagram, DatagramInfo},
+    inet::{self, datagram, DatagramInfo},
     io::{rx, tx},
     packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket},
     path,
@@ -45,15 +53,19 @@ use s2n_quic_core::{
     transport::parameters::ClientTransportParameters,
 };
 
+mod attempt;
 pub mod close;
 mod config;
 pub mod connect;
 pub mod handle;
 mod initial;
+pub mod lifecycle;
 mod packet_buffer;
+pub mod peer;
 mod retry;
 mod stateless_reset;
-mod version;
+pub(crate) mod statistics;
+pub(crate) mod version;
 
 // exports
 pub use config::{Config, Context};
@@ -72,11 +84,20 @@ pub struct Endpoint<Cfg: Config> {
     connection_id_generator: InternalConnectionIdGenerator,
     /// Maps from external to internal connection IDs
     connection_id_mapper: ConnectionIdMapper,
+    /// Derives IDs used to correlate the events of a single connection attempt
+    attempt_id_generator: attempt::Generator,
+    /// Statistics aggregated over all of the connections on the endpoint
+    statistics: statistics::Counters,
     /// Allows to wakeup the endpoint task which might be blocked on waiting for packets
     /// from application tasks (which e.g. enqueued new data to send).
     wakeup_queue: WakeupQueue<InternalConnectionId>,
     /// Used to receive close attempts and track close state.
     close_handle: CloseHandle,
+    /// Set once the application requests the endpoint to be drained. New connection attempts
+    /// are rejected while draining.
+    is_draining: bool,
+    /// The time at which any remaining connections are closed with the given error
+    drain_deadline: Option<(Timestamp, application::Error)>,
     /// This queue contains wakeups we retrieved from the [`Self::wakeup_queue`] earlier.
     /// This is not a local variable in order to reuse the allocated queue capacity in between
     /// [`Endpoint`] interactions.
@@ -87,6 +108,20 @@ pub struct Endpoint<Cfg: Config> {
     close_packet_buffer: packet_buffer::Buffer,
     /// Configuration for the maximum transmission unit (MTU) that can be sent on a path
     mtu_config: mtu::Config,
+    /// MTU clamps applied to paths with matching peer addresses
+    mtu_clamps: Vec<mtu::Clamp>,
+    /// The characteristics of paths observed by previous connections, used to seed new
+    /// connections to the same remote prefix
+    path_cache: Option<path::cache::Cache>,
+    /// Recycles the buffers of discarded packet spaces between connections
+    packet_space_pool: space::Pool,
+    /// The local ports which client connections are assigned to
+    local_ports: Vec<u16>,
+    /// The attempt addresses of server connections which were reported to the endpoint limits
+    opened_connections: BTreeMap<InternalConnectionId, inet::SocketAddress>,
+    /// Client connection requests which are waiting for their retry backoff or candidate delay
+    /// to expire
+    delayed_connections: Vec<(Timestamp, connect::Request)>,
 }
 
 impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
@@ -113,6 +148,10 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
             self.receive_datagram(&mut header, payload, timestamp)
         });
+
+        if let Some(timestamp) = now {
+            self.on_connections_removed(timestamp);
+        }
     }
 
     fn transmit<Tx, C>(&mut self, queue: &mut Tx, clock: &C)
@@ -139,6 +178,10 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                 return ConnectionContainerIterationResult::BreakAndInsertAtFront;
             }
 
+            endpoint_context
+                .event_subscriber
+                .on_profile_scope_entered(event::profile::Scope::Transmit);
+
             // ignore the transmission error and just query the queue capacity instead
             let _ = connection.on_transmit(
                 queue,
@@ -147,6 +190,10 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                 endpoint_context.packet_interceptor,
             );
 
+            endpoint_context
+                .event_subscriber
+                .on_profile_scope_exited(event::profile::Scope::Transmit);
+
             // flush the TX queue between connections
             queue.flush();
 
@@ -158,6 +205,7 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                 event::builder::EndpointMeta {
                     endpoint_type: Cfg::ENDPOINT_TYPE,
                     timestamp,
+                    attempt_id: None,
                 },
                 None,
                 endpoint_context.event_subscriber,
@@ -167,6 +215,8 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             self.stateless_reset_dispatch
                 .on_transmit(queue, &mut publisher);
         }
+
+        self.on_connections_removed(timestamp);
     }
 
     fn poll_wakeups<C: Clock>(
@@ -174,7 +224,17 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         cx: &mut task::Context<'_>,
         clock: &C,
     ) -> Poll<Result<usize, s2n_quic_core::endpoint::CloseError>> {
-        if self.close_handle.poll_interest().is_ready() // poll for close interest
+        // poll for close interest
+        let close_interest = self.close_handle.poll_interest(cx).is_ready();
+
+        if let Some(drain) = self.close_handle.take_drain() {
+            // stop accepting new connections and give the existing ones until the deadline to
+            // close on their own
+            self.is_draining = true;
+            self.drain_deadline = Some((clock.get_time() + drain.timeout, drain.error));
+        }
+
+        if close_interest
             && self.connections.is_empty() // wait for all connections to close gracefully
             && self.connections.is_open()
         {
@@ -183,6 +243,13 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
             // stop accepting new connections and prepare to close the endpoint
             self.connections.close();
+
+            // reject any connection attempts which were waiting to be retried
+            for (_, request) in self.delayed_connections.drain(..) {
+                let _ = request
+                    .sender
+                    .send(Err(connection::Error::endpoint_closing()));
+            }
         }
 
         // Drop the endpoint if there is no more progress to be made.
@@ -213,6 +280,7 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                     timestamp,
                     endpoint_context.event_subscriber,
                     endpoint_context.datagram,
+                    endpoint_context.random_generator,
                 ) {
                     conn.close(
                         error,
@@ -235,10 +303,12 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                         wakeup_count += 1;
 
                         let time = clock.get_time();
-                        if let Err(err) = self.create_client_connection(request, time) {
-                            // TODO report that the connection was not successfully created
-                            // TODO emit event
-                            dbg!(err);
+                        if let Some(retry) = request.retry {
+                            self.on_connection_retry(request, retry, time);
+                        } else if let Some(delay) = request.delay {
+                            self.delayed_connections.push((time + delay, request));
+                        } else {
+                            self.open_client_connection(request, time);
                         }
                     }
                     Poll::Ready(None) => {
@@ -249,6 +319,10 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             }
         }
 
+        if let Some(timestamp) = now {
+            self.on_connections_removed(timestamp);
+        }
+
         if wakeup_count > 0 {
             Poll::Ready(Ok(wakeup_count))
         } else {
@@ -258,7 +332,22 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
     #[inline]
     fn timeout(&self) -> Option<Timestamp> {
-        self.connections.next_expiration()
+        let retry_expiration = self
+            .delayed_connections
+            .iter()
+            .map(|(expiration, _)| *expiration)
+            .min();
+
+        let drain_expiration = self.drain_deadline.map(|(deadline, _)| deadline);
+
+        [
+            self.connections.next_expiration(),
+            retry_expiration,
+            drain_expiration,
+        ]
+        .into_iter()
+        .flatten()
+        .min()
     }
 
     #[inline]
@@ -266,6 +355,16 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         self.mtu_config = mtu_config
     }
 
+    #[inline]
+    fn set_mtu_clamps(&mut self, clamps: &[mtu::Clamp]) {
+        self.mtu_clamps = clamps.to_vec();
+    }
+
+    #[inline]
+    fn set_local_ports(&mut self, ports: &[u16]) {
+        self.local_ports = ports.to_vec();
+    }
+
     #[inline]
     fn subscriber(&mut self) -> &mut Self::Subscriber {
         self.config.context().event_subscriber
@@ -296,39 +395,97 @@ impl<Cfg: Config> Endpoint<Cfg> {
     fn new(mut config: Cfg) -> (Self, handle::Handle) {
         // TODO make this limit configurable
         let max_opening_connections = 1000;
-        let (handle, acceptor_sender, connector_receiver, close_handle) =
-            handle::Handle::new(max_opening_connections);
-
+        let statistics = statistics::Counters::default();
         let connection_id_mapper =
             ConnectionIdMapper::new(config.context().random_generator, Cfg::ENDPOINT_TYPE);
+        let (handle, acceptor_sender, connector_receiver, close_handle, closed_notifier) =
+            handle::Handle::new(
+                max_opening_connections,
+                statistics.clone(),
+                connection_id_mapper.metrics_handle(),
+            );
+
+        let attempt_id_generator = attempt::Generator::new(config.context().random_generator);
+        let path_cache = config.path_cache().map(path::cache::Cache::new);
+        let packet_space_pool = space::Pool::new(config.packet_space_pool_capacity());
 
         let endpoint = Self {
             config,
-            connections: ConnectionContainer::new(acceptor_sender, connector_receiver),
+            connections: ConnectionContainer::new(
+                acceptor_sender,
+                connector_receiver,
+                closed_notifier,
+            ),
             connection_id_generator: InternalConnectionIdGenerator::new(),
             connection_id_mapper,
+            attempt_id_generator,
+            statistics,
             wakeup_queue: WakeupQueue::new(),
             close_handle,
+            is_draining: false,
+            drain_deadline: None,
             dequeued_wakeups: VecDeque::new(),
             version_negotiator: version::Negotiator::default(),
             retry_dispatch: retry::Dispatch::default(),
             stateless_reset_dispatch: stateless_reset::Dispatch::default(),
             close_packet_buffer: Default::default(),
             mtu_config: Default::default(),
+            mtu_clamps: Vec::new(),
+            path_cache,
+            packet_space_pool,
+            local_ports: Vec::new(),
+            opened_connections: BTreeMap::new(),
+            delayed_connections: Vec::new(),
         };
 
         (endpoint, handle)
     }
 
+    /// Returns the MTU configuration for a path with the given peer address
+    ///
+    /// If the characteristics of a previous path to the peer are provided, the initial MTU is
+    /// raised to the datagram size validated on that path.
+    #[inline]
+    fn path_mtu_config(
+        &self,
+        remote_address: &inet::SocketAddress,
+        cached: Option<&path::cache::Characteristics>,
+    ) -> mtu::Config {
+        let mtu_config = mtu::Clamp::apply(&self.mtu_clamps, self.mtu_config, remote_address);
+        match cached {
+            Some(cached) => {
+                mtu_config.with_validated_datagram_size(cached.max_datagram_size, remote_address)
+            }
+            None => mtu_config,
+        }
+    }
+
+    /// Returns the characteristics observed by a previous connection to the peer's prefix, if
+    /// the endpoint has a path cache
+    #[inline]
+    fn cached_path(
+        &mut self,
+        remote_address: &inet::SocketAddress,
+        timestamp: Timestamp,
+    ) -> Option<path::cache::Characteristics> {
+        self.path_cache.as_mut()?.get(remote_address, timestamp)
+    }
+
     /// Determine the next step when a peer attempts a connection
+    #[allow(clippy::too_many_arguments)]
     fn connection_allowed(
         &mut self,
         header: &datagram::Header<Cfg::PathHandle>,
         packet: &ProtectedInitial,
         payload_len: usize,
+        attempt_id: Option<u64>,
+        is_address_validated: bool,
+        is_challenge_solved: bool,
         timestamp: Timestamp,
     ) -> Option<()> {
-        if !self.connections.can_accept() {
+        use event::builder::ConnectionAttemptAction as Action;
+
+        if !self.connections.can_accept() || self.is_draining {
             return None;
         }
 
@@ -339,22 +496,66 @@ impl<Cfg: Config> Endpoint<Cfg> {
             self.connections.len(),
             &remote_address,
             timestamp.into_event(),
-        );
+        )
+        .with_address_validated(is_address_validated)
+        .with_challenge_solved(is_challenge_solved);
 
         let context = self.config.context();
         let outcome = context.endpoint_limits.on_connection_attempt(&attempt);
+
+        let mut challenge_buffer = [0u8; challenge::MAX_LEN];
+        let mut challenge_len = 0;
+
+        let action = match outcome {
+            Outcome::Allow { .. } => None,
+            // the address was already validated so the attempt is allowed to continue
+            Outcome::Retry { .. } if is_address_validated => None,
+            Outcome::Retry { .. } => Some(Action::Retry),
+            Outcome::Challenge { .. } if is_challenge_solved => None,
+            // the challenge can only be delivered in a Retry packet, which can't be sent again
+            Outcome::Challenge { .. } if is_address_validated => Some(Action::Drop),
+            Outcome::Challenge { .. } => {
+                match context
+                    .endpoint_limits
+                    .generate_challenge(&attempt, &mut challenge_buffer)
+                {
+                    Some(len) if len > 0 => {
+                        challenge_len = len.min(challenge::MAX_LEN);
+                        Some(Action::Challenge)
+                    }
+                    // the limiter didn't provide a challenge so fall back to a regular Retry
+                    _ => Some(Action::Retry),
+                }
+            }
+            Outcome::Close { .. } => Some(Action::Close),
+            // Outcome is non_exhaustive so drop on things we don't understand
+            _ => Some(Action::Drop),
+        };
+
+        let Some(action) = action else {
+            return Some(());
+        };
+
         let mut publisher = event::EndpointPublisherSubscriber::new(
             event::builder::EndpointMeta {
                 endpoint_type: Cfg::ENDPOINT_TYPE,
                 timestamp,
+                attempt_id,
             },
             None,
             context.event_subscriber,
         );
 
-        match outcome {
-            Outcome::Allow { .. } => Some(()),
-            Outcome::Retry { .. } => {
+        publisher.on_endpoint_connection_attempt_rejected(
+            event::builder::EndpointConnectionAttemptRejected {
+                action: action.clone(),
+                reason: outcome.reason(),
+                is_address_validated,
+            },
+        );
+
+        match action {
+            Action::Retry | Action::Challenge => {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                 //# A server can also use a Retry packet to defer the state and
                 //# processing costs of connection establishment.  Requiring the server
@@ -366,7 +567,9 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
                 let connection_info = ConnectionInfo::new(&remote_address);
 
-                let local_connection_id = context.connection_id_format.generate(&connection_info);
+                let local_connection_id = context
+                    .connection_id_format
+                    .generate_with_random(&connection_info, context.random_generator);
 
                 self.retry_dispatch.queue::<
                     _,
@@ -375,13 +578,15 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     header.path,
                     packet,
                     local_connection_id,
+                    attempt_id,
                     context.random_generator,
-                    context.token
+                    context.token,
+                    &challenge_buffer[..challenge_len],
                 );
 
                 None
             }
-            Outcome::Close { .. } => {
+            Action::Close => {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
                 //= type=TODO
                 //= tracking-issue=270
@@ -396,24 +601,52 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
                 None
             }
-            Outcome::Drop { .. } => {
-                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
-                    len: payload_len as u16,
-                    reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
-                });
-                None
-            }
             _ => {
                 publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
                     len: payload_len as u16,
                     reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
                 });
-                // Outcome is non_exhaustive so drop on things we don't understand
                 None
             }
         }
     }
 
+    /// Returns `true` if the datagram is addressed to one of the endpoint's connections
+    ///
+    /// This is used to route datagrams between endpoints which share a socket.
+    fn is_connection_datagram(
+        &mut self,
+        header: &datagram::Header<Cfg::PathHandle>,
+        payload: &[u8],
+    ) -> bool {
+        let remote_address = header.path.remote_address();
+        let connection_info = ConnectionInfo::new(&remote_address);
+        let connection_id_format = self.config.context().connection_id_format;
+
+        let destination_connection_id = match payload.first() {
+            // long header packets prefix the connection ID with its length after the version
+            Some(tag) if tag & 0x80 != 0 => DecoderBuffer::new(payload)
+                .skip(1 + core::mem::size_of::<u32>())
+                .and_then(|buffer| buffer.decode_slice_with_len_prefix::<u8>())
+                .ok()
+                .map(|(connection_id, _)| connection_id.into_less_safe_slice()),
+            // short header packets rely on the connection ID format to recover the length
+            Some(_) => payload.get(1..).and_then(|buffer| {
+                let len = connection_id_format.validate(&connection_info, buffer)?;
+                buffer.get(..len)
+            }),
+            None => None,
+        };
+
+        destination_connection_id
+            .and_then(connection::LocalId::try_from_bytes)
+            .and_then(|connection_id| {
+                self.connection_id_mapper
+                    .lookup_internal_connection_id(&connection_id)
+            })
+            .is_some()
+    }
+
     /// Ingests a single datagram
     fn receive_datagram(
         &mut self,
@@ -425,6 +658,34 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         // Try to decode the first packet in the datagram
         let payload_len = payload.len();
+
+        // Give the endpoint limits a chance to drop datagrams from abusive peers before spending
+        // any cycles parsing them
+        {
+            let remote_address = header.path.remote_address();
+            let attempt = s2n_quic_core::endpoint::limits::DatagramAttempt::new(
+                &remote_address,
+                payload_len,
+                timestamp.into_event(),
+            );
+
+            if !endpoint_context.endpoint_limits.on_datagram(&attempt) {
+                let mut publisher = event::EndpointPublisherSubscriber::new(
+                    event::builder::EndpointMeta {
+                        endpoint_type: Cfg::ENDPOINT_TYPE,
+                        timestamp,
+                        attempt_id: None,
+                    },
+                    None,
+                    endpoint_context.event_subscriber,
+                );
+                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
+                    len: payload_len as u16,
+                    reason: event::builder::DatagramDropReason::Blocked,
+                });
+                return;
+            }
+        }
         let buffer = DecoderBufferMut::new(payload);
 
         let buffer = {
@@ -476,6 +737,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     event::builder::EndpointMeta {
                         endpoint_type: Cfg::ENDPOINT_TYPE,
                         timestamp,
+                        attempt_id: None,
                     },
                     None,
                     self.config.context().event_subscriber,
@@ -489,10 +751,21 @@ impl<Cfg: Config> Endpoint<Cfg> {
             return;
         };
 
+        // Initial packets for unknown connections start a new attempt so all of the endpoint
+        // events are associated with the destination connection ID chosen by the client
+        let attempt_id = match (Cfg::ENDPOINT_TYPE, &packet) {
+            (s2n_quic_core::endpoint::Type::Server, ProtectedPacket::Initial(packet)) => Some(
+                self.attempt_id_generator
+                    .attempt_id(packet.destination_connection_id()),
+            ),
+            _ => None,
+        };
+
         let mut publisher = event::EndpointPublisherSubscriber::new(
             event::builder::EndpointMeta {
                 endpoint_type: Cfg::ENDPOINT_TYPE,
                 timestamp,
+                attempt_id,
             },
             packet.version(),
             endpoint_context.event_subscriber,
@@ -533,14 +806,15 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .source_connection_id()
             .and_then(PeerId::try_from_bytes);
 
-        let mut datagram = DatagramInfo {
+        let mut datagram = DatagramInfo::new(
             timestamp,
             payload_len,
-            ecn: header.ecn,
+            header.ecn,
             destination_connection_id,
-            destination_connection_id_classification: connection::id::Classification::Initial,
+            connection::id::Classification::Initial,
             source_connection_id,
-        };
+        );
+        datagram.queue_id = header.queue_id;
 
         // TODO validate the connection ID before looking up the connection in the map
         let close_packet_buffer = &mut self.close_packet_buffer;
@@ -552,7 +826,12 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .lookup_internal_connection_id(&destination_connection_id)
         {
             let mut check_for_stateless_reset = false;
-            let mtu_config = self.mtu_config;
+            // the fields are accessed directly since the endpoint context is still borrowed
+            let mtu_config = mtu::Clamp::apply(
+                &self.mtu_clamps,
+                self.mtu_config,
+                &header.path.remote_address(),
+            );
 
             datagram.destination_connection_id_classification = dcid_classification;
 
@@ -633,6 +912,22 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     return Err(());
                 }
 
+                // allow event subscribers to close the connection based on the events
+                // published while processing the datagram
+                if let Err(err) = conn
+                    .on_datagram_processed(datagram.timestamp, endpoint_context.event_subscriber)
+                {
+                    conn.close(
+                        err,
+                        endpoint_context.connection_close_formatter,
+                        close_packet_buffer,
+                        datagram.timestamp,
+                        endpoint_context.event_subscriber,
+                        endpoint_context.packet_interceptor,
+                    );
+                    return Err(());
+                }
+
                 Ok(())
             });
 
@@ -672,15 +967,61 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 //# was provided in a Retry packet, a server cannot send another Retry
                 //# packet; it can only refuse the connection or permit it to proceed.
                 let retry_token_dcid = if !packet.token().is_empty() {
+                    let mut is_challenge_solved = false;
+
+                    // the address token is framed with the challenge, if one was issued
+                    let Some(framed) = challenge::Token::decode(packet.token()) else {
+                        publisher.on_endpoint_datagram_dropped(
+                            event::builder::EndpointDatagramDropped {
+                                len: payload_len as u16,
+                                reason: event::builder::DatagramDropReason::InvalidRetryToken,
+                            },
+                        );
+                        return;
+                    };
+                    let token = framed.address_token();
+
+                    // clients which can't solve the challenge echo it back unchanged
+                    if let challenge::Token::Unsolved(_) = framed {
+                        publisher.on_endpoint_datagram_dropped(
+                            event::builder::EndpointDatagramDropped {
+                                len: payload_len as u16,
+                                reason:
+                                    event::builder::DatagramDropReason::InvalidChallengeResponse,
+                            },
+                        );
+                        return;
+                    }
+
+                    if let challenge::Token::Response(response) = framed {
+                        let info = ChallengeResponse::new(
+                            &remote_address,
+                            response.challenge,
+                            response.solution,
+                            timestamp.into_event(),
+                        );
+
+                        if !endpoint_context.endpoint_limits.verify_challenge(&info) {
+                            publisher.on_endpoint_datagram_dropped(
+                                event::builder::EndpointDatagramDropped {
+                                    len: payload_len as u16,
+                                    reason:
+                                        event::builder::DatagramDropReason::InvalidChallengeResponse,
+                                },
+                            );
+                            return;
+                        }
+
+                        is_challenge_solved = true;
+                    }
+
                     let mut context = token::Context::new(
                         &remote_address,
                         &source_connection_id,
                         endpoint_context.random_generator,
                     );
 
-                    let outcome = endpoint_context
-                        .token
-                        .validate_token(&mut context, packet.token());
+                    let outcome = endpoint_context.token.validate_token(&mut context, token);
 
                     if outcome.is_none() {
                         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
@@ -712,6 +1053,32 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
                     //# If the validation succeeds, the server SHOULD then allow
                     //# the handshake to proceed.
+                    if let Some(original_destination_connection_id) = &outcome {
+                        // The destination connection ID on the packet was chosen by the Retry
+                        // so continue the attempt started by the original one
+                        publisher.set_attempt_id(Some(
+                            self.attempt_id_generator
+                                .attempt_id(original_destination_connection_id.as_bytes()),
+                        ));
+                    }
+
+                    // validated attempts are still subject to the endpoint limits, which can
+                    // refuse the connection but can't send another Retry packet
+                    if self
+                        .connection_allowed(
+                            header,
+                            &packet,
+                            payload_len,
+                            publisher.attempt_id(),
+                            true,
+                            is_challenge_solved,
+                            timestamp,
+                        )
+                        .is_none()
+                    {
+                        return;
+                    }
+
                     outcome
                 } else {
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
@@ -719,7 +1086,15 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     //# address validation by sending a Retry packet (Section 17.2.5)
                     //# containing a token.
                     if self
-                        .connection_allowed(header, &packet, payload_len, timestamp)
+                        .connection_allowed(
+                            header,
+                            &packet,
+                            payload_len,
+                            attempt_id,
+                            false,
+                            false,
+                            timestamp,
+                        )
                         .is_none()
                     {
                         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.1
@@ -731,6 +1106,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     None
                 };
 
+                let attempt_id = publisher.attempt_id();
+
                 if let Err(err) = self.handle_initial_packet(
                     header,
                     &datagram,
@@ -743,6 +1120,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                         event::builder::EndpointMeta {
                             endpoint_type: Cfg::ENDPOINT_TYPE,
                             timestamp,
+                            attempt_id,
                         },
                         None,
                         self.config.context().event_subscriber,
@@ -853,6 +1231,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             event::builder::EndpointMeta {
                 endpoint_type: Cfg::ENDPOINT_TYPE,
                 timestamp,
+                attempt_id: None,
             },
             None,
             endpoint_context.event_subscriber,
@@ -882,20 +1261,141 @@ impl<Cfg: Config> Endpoint<Cfg> {
         Some(internal_id)
     }
 
+    /// Notifies the endpoint limits of any server connections which were removed and records the
+    /// characteristics of their paths in the path cache
+    fn on_connections_removed(&mut self, timestamp: Timestamp) {
+        let opened_connections = &mut self.opened_connections;
+        let path_cache = &mut self.path_cache;
+        let endpoint_limits = self.config.context().endpoint_limits;
+
+        self.connections.take_removed(|internal_id, path| {
+            if let Some(remote_address) = opened_connections.remove(&internal_id) {
+                endpoint_limits.on_connection_closed(&ConnectionClosed::new(
+                    &remote_address,
+                    timestamp.into_event(),
+                ));
+            }
+
+            if let (Some(path_cache), Some((remote_address, characteristics))) =
+                (path_cache.as_mut(), path)
+            {
+                path_cache.insert(&remote_address, characteristics, timestamp);
+            }
+        });
+    }
+
+    /// Delays a client connection request which retries a failed attempt
+    fn on_connection_retry(
+        &mut self,
+        request: connect::Request,
+        retry: connect::Retry,
+        timestamp: Timestamp,
+    ) {
+        let mut publisher = event::EndpointPublisherSubscriber::new(
+            event::builder::EndpointMeta {
+                endpoint_type: Cfg::ENDPOINT_TYPE,
+                timestamp,
+                attempt_id: None,
+            },
+            None,
+            self.config.context().event_subscriber,
+        );
+        publisher.on_endpoint_connection_attempt_retried(
+            event::builder::EndpointConnectionAttemptRetried {
+                attempts: retry.attempts,
+                backoff: retry.backoff,
+                error: retry.error,
+            },
+        );
+
+        self.delayed_connections
+            .push((timestamp + retry.backoff, request));
+    }
+
+    /// Opens any delayed client connection requests whose delay has expired
+    fn on_delayed_connections(&mut self, timestamp: Timestamp) {
+        if !self
+            .delayed_connections
+            .iter()
+            .any(|(expiration, _)| *expiration <= timestamp)
+        {
+            return;
+        }
+
+        for (expiration, request) in core::mem::take(&mut self.delayed_connections) {
+            // the application is no longer waiting for the connection, which happens when
+            // another candidate address won the race
+            if request.sender.is_canceled() {
+                continue;
+            }
+
+            if expiration <= timestamp {
+                self.open_client_connection(request, timestamp);
+            } else {
+                self.delayed_connections.push((expiration, request));
+            }
+        }
+    }
+
+    fn open_client_connection(&mut self, request: connect::Request, timestamp: Timestamp) {
+        if let Err(err) = self.create_client_connection(request, timestamp) {
+            // TODO report that the connection was not successfully created
+            // TODO emit event
+            dbg!(err);
+        }
+    }
+
+    /// Closes any connections which are still open once the drain deadline has passed
+    fn on_drain_timeout(&mut self, timestamp: Timestamp) {
+        let error = match self.drain_deadline {
+            Some((deadline, error)) if deadline.has_elapsed(timestamp) => error,
+            _ => return,
+        };
+        self.drain_deadline = None;
+
+        let error = connection::Error::application(error);
+        let close_packet_buffer = &mut self.close_packet_buffer;
+        let endpoint_context = self.config.context();
+
+        self.connections.for_each(|conn| {
+            conn.close(
+                error,
+                endpoint_context.connection_close_formatter,
+                close_packet_buffer,
+                timestamp,
+                endpoint_context.event_subscriber,
+                endpoint_context.packet_interceptor,
+            );
+        });
+    }
+
     fn on_timeout(&mut self, timestamp: Timestamp) {
+        self.on_delayed_connections(timestamp);
+        self.on_drain_timeout(timestamp);
+
         let connection_id_mapper = &mut self.connection_id_mapper;
         let close_packet_buffer = &mut self.close_packet_buffer;
         let endpoint_context = self.config.context();
 
         self.connections
             .iterate_timeout_list(timestamp, |conn, supervisor_context| {
-                if let Err(error) = conn.on_timeout(
+                endpoint_context
+                    .event_subscriber
+                    .on_profile_scope_entered(event::profile::Scope::Timers);
+
+                let result = conn.on_timeout(
                     connection_id_mapper,
                     timestamp,
                     supervisor_context,
                     endpoint_context.random_generator,
                     endpoint_context.event_subscriber,
-                ) {
+                );
+
+                endpoint_context
+                    .event_subscriber
+                    .on_profile_scope_exited(event::profile::Scope::Timers);
+
+                if let Err(error) = result {
                     conn.close(
                         error,
                         endpoint_context.connection_close_formatter,
@@ -913,6 +1413,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 let result = connection.on_new_connection_id(
                     endpoint_context.connection_id_format,
                     endpoint_context.stateless_reset_token_generator,
+                    endpoint_context.random_generator,
                     timestamp,
                 );
                 if result.is_ok() {
@@ -935,16 +1436,22 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 endpoint::connect::Connect {
                     remote_address,
                     server_name: hostname,
+                    pinned_spki,
+                    challenge_solver,
+                    ..
                 },
             sender,
+            ..
         } = request;
 
         let internal_connection_id = self.connection_id_generator.generate_id();
-        let local_connection_id = self
-            .config
-            .context()
-            .connection_id_format
-            .generate(&ConnectionInfo::new(&remote_address));
+        let local_connection_id = {
+            let context = self.config.context();
+            context.connection_id_format.generate_with_random(
+                &ConnectionInfo::new(&remote_address),
+                context.random_generator,
+            )
+        };
 
         let local_connection_id_expiration_time = self
             .config
@@ -977,6 +1484,10 @@ impl<Cfg: Config> Endpoint<Cfg> {
             )
         };
 
+        // look up the path before borrowing the endpoint context
+        let cached_path = self.cached_path(&remote_address, timestamp);
+        let mtu_config = self.path_mtu_config(&remote_address, cached_path.as_ref());
+
         let endpoint_context = self.config.context();
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.2
@@ -1006,7 +1517,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         let congestion_controller = {
             let path_info =
-                congestion_controller::PathInfo::new(self.mtu_config.initial_mtu, &remote_address);
+                congestion_controller::PathInfo::new(mtu_config.initial_mtu, &remote_address)
+                    .with_cached(cached_path);
             endpoint_context
                 .congestion_controller
                 .new_congestion_controller(path_info)
@@ -1017,10 +1529,16 @@ impl<Cfg: Config> Endpoint<Cfg> {
         //# 0x00000001.
         let quic_version = 0x00000001;
 
+        let attempt_id = self
+            .attempt_id_generator
+            .attempt_id(original_destination_connection_id.as_bytes());
+
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Cfg::ENDPOINT_TYPE,
             id: internal_connection_id.into(),
+            attempt_id,
             timestamp,
+            application_context: Default::default(),
         };
         let supervisor_context = supervisor::Context::new(
             self.connections.handshake_connections(),
@@ -1030,7 +1548,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
         );
         let mut event_context = endpoint_context.event_subscriber.create_connection_context(
             &meta.clone().into_event(),
-            &event::builder::ConnectionInfo {}.into_event(),
+            &event::builder::ConnectionInfo {
+                remote_address: (&*remote_address).into_event(),
+                server_name: hostname.as_deref(),
+            }
+            .into_event(),
         );
         let mut publisher = event::ConnectionPublisherSubscriber::new(
             meta,
@@ -1048,6 +1570,15 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .on_connection(&LimitsInfo::new(&remote_address));
         transport_parameters.load_limits(&limits);
 
+        // start the RTT estimate from the RTT previously observed on the path
+        let limits = cached_path
+            .and_then(|cached| {
+                limits
+                    .with_initial_round_trip_time(cached.smoothed_rtt)
+                    .ok()
+            })
+            .unwrap_or(limits);
+
         transport_parameters.max_datagram_frame_size = endpoint_context
             .datagram
             .max_datagram_frame_size(&PreConnectionInfo::new())
@@ -1060,6 +1591,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
         .try_into()
         .unwrap();
 
+        transport_parameters.version_information = Some(version::version_information(quic_version));
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.2
         //# The Destination Connection ID field from the first Initial packet
         //# sent by a client is used to determine packet protection keys for
@@ -1071,7 +1604,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             <<Cfg::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
                 original_destination_connection_id.as_bytes(),
             );
-        let tls_session = endpoint_context
+        let mut tls_session = endpoint_context
             .tls
             // TODO should SNI be optional? rustls expects a SNI but other tls providers dont seem
             // to require this value.
@@ -1079,26 +1612,65 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 &transport_parameters,
                 hostname.expect("application should provide a valid server name"),
             );
-        let space_manager = PacketSpaceManager::new(
+        if let Some(pins) = pinned_spki {
+            use tls::Session as _;
+            if let Err(error) = tls_session.pin_spki(pins) {
+                // let the application know why the attempt failed
+                let error = connection::Error::from(s2n_quic_core::transport::Error::from(error));
+                let _ = sender.send(Err(error));
+                return Err(error);
+            }
+        }
+        let mut space_manager = PacketSpaceManager::new(
             original_destination_connection_id,
             tls_session,
             initial_key,
             initial_header_key,
             timestamp,
+            self.packet_space_pool.clone(),
             &mut publisher,
         );
 
+        if let Some((space, _)) = space_manager.initial_mut() {
+            if let Some(solver) = challenge_solver {
+                space.set_challenge_solver(solver.0);
+            }
+
+            space.set_first_flight_limits(
+                limits.first_flight_probe_timeout(),
+                limits.max_first_flight_transmissions(),
+            );
+        }
+
         let wakeup_handle = self
             .wakeup_queue
             .create_wakeup_handle(internal_connection_id);
 
-        let path_handle =
+        let mut path_handle =
             <<Cfg as endpoint::Config>::PathHandle as path::Handle>::from_remote_address(
                 remote_address,
             );
 
+        // spread the connections across the local ports so they can't be linked by the port
+        if self.local_ports.len() > 1 {
+            let mut data = [0u8; 4];
+            endpoint_context
+                .random_generator
+                .public_random_fill(&mut data);
+            // the bias is negligible since the number of ports is small
+            let index = u32::from_ne_bytes(data) as usize % self.local_ports.len();
+            path_handle.set_local_port(self.local_ports[index]);
+        }
+
+        let padding = connection::padding::Controller::new(
+            limits.padding_policy(),
+            endpoint_context.random_generator,
+        );
+
         let connection_parameters = connection::Parameters {
             internal_connection_id,
+            attempt_id,
+            endpoint_statistics: self.statistics.clone(),
             local_id_registry,
             peer_id_registry,
             space_manager,
@@ -1110,7 +1682,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             timestamp,
             quic_version,
             limits,
-            mtu_config: self.mtu_config,
+            mtu_config,
+            padding,
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
@@ -0,0 +1,140 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A peer-to-peer endpoint which opens and accepts connections on the same socket
+//!
+//! Rendezvous protocols, like ICE, rely on both peers sending packets from the same address
+//! that the other peer uses to reach them. This is accomplished by pairing a server endpoint with
+//! a client endpoint and routing each received datagram to the endpoint which owns the
+//! destination connection ID. Datagrams which don't belong to any client connection are handed to
+//! the server, which is then able to accept new connections from the peer.
+//!
+//! Stateless resets don't carry a known connection ID, so they are checked against the stateless
+//! reset tokens of the client connections before being handed to the server, which checks its own
+//! connections.
+//!
+//! Each half publishes connection events to its own event subscriber. Events published by the IO
+//! provider for the endpoint as a whole are only delivered to the server's subscriber.
+
+use crate::endpoint::{self, handle, Config};
+use core::task::{self, Poll};
+use s2n_quic_core::{
+    endpoint::{CloseError, Endpoint as _},
+    io::{rx, tx},
+    path::mtu,
+    time::{Clock, Timestamp},
+};
+
+pub struct Endpoint<Server: Config, Client: Config<PathHandle = Server::PathHandle>> {
+    server: endpoint::Endpoint<Server>,
+    client: endpoint::Endpoint<Client>,
+}
+
+impl<Server: Config, Client: Config<PathHandle = Server::PathHandle>> Endpoint<Server, Client> {
+    /// Creates a new peer-to-peer endpoint using the given configurations
+    pub fn new(server: Server, client: Client) -> (Self, handle::Acceptor, handle::Connector) {
+        let (server, acceptor) = endpoint::Endpoint::new_server(server);
+        let (client, connector) = endpoint::Endpoint::new_client(client);
+        (Self { server, client }, acceptor, connector)
+    }
+}
+
+impl<Server: Config, Client: Config<PathHandle = Server::PathHandle>>
+    s2n_quic_core::endpoint::Endpoint for Endpoint<Server, Client>
+{
+    type PathHandle = Server::PathHandle;
+    type Subscriber = Server::EventSubscriber;
+
+    const ENDPOINT_TYPE: s2n_quic_core::endpoint::Type = Server::ENDPOINT_TYPE;
+
+    fn receive<Rx, C>(&mut self, queue: &mut Rx, clock: &C)
+    where
+        Rx: rx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        let mut now: Option<Timestamp> = None;
+
+        queue.for_each(|mut header, payload| {
+            let timestamp = *now.get_or_insert_with(|| clock.get_time());
+
+            if self.client.is_connection_datagram(&header, payload) {
+                self.client
+                    .receive_datagram(&mut header, payload, timestamp)
+            } else if self
+                .client
+                .close_on_matching_stateless_reset(payload, timestamp)
+                .is_none()
+            {
+                // datagrams for unknown connections are handed to the server so the peer is able
+                // to open connections with us
+                self.server
+                    .receive_datagram(&mut header, payload, timestamp)
+            }
+        });
+
+        if let Some(timestamp) = now {
+            self.server.on_connections_removed(timestamp);
+            self.client.on_connections_removed(timestamp);
+        }
+    }
+
+    fn transmit<Tx, C>(&mut self, queue: &mut Tx, clock: &C)
+    where
+        Tx: tx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        self.server.transmit(queue, clock);
+        self.client.transmit(queue, clock);
+    }
+
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        let server = self.server.poll_wakeups(cx, clock);
+        let client = self.client.poll_wakeups(cx, clock);
+
+        match (server, client) {
+            (Poll::Ready(Ok(server)), Poll::Ready(Ok(client))) => Poll::Ready(Ok(server + client)),
+            (Poll::Ready(Ok(count)), _) | (_, Poll::Ready(Ok(count))) => Poll::Ready(Ok(count)),
+            // the endpoint is only closed once both sides have closed
+            (Poll::Ready(Err(_)), Poll::Ready(Err(error))) => Poll::Ready(Err(error)),
+            _ => Poll::Pending,
+        }
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Timestamp> {
+        [self.server.timeout(), self.client.timeout()]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    #[inline]
+    fn set_mtu_config(&mut self, mtu_config: mtu::Config) {
+        self.server.set_mtu_config(mtu_config);
+        self.client.set_mtu_config(mtu_config);
+    }
+
+    #[inline]
+    fn set_mtu_clamps(&mut self, clamps: &[mtu::Clamp]) {
+        self.server.set_mtu_clamps(clamps);
+        self.client.set_mtu_clamps(clamps);
+    }
+
+    #[inline]
+    fn set_local_ports(&mut self, ports: &[u16]) {
+        // server connections are accepted on the port the peer sent to
+        self.client.set_local_ports(ports);
+    }
+
+    /// Returns the server's event subscriber
+    ///
+    /// Endpoint events published by the IO provider aren't delivered to the client's subscriber.
+    #[inline]
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        self.server.subscriber()
+    }
+}
@@ -35,20 +35,25 @@ impl<Path: path::Handle> Dispatch<Path> {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn queue<T: token::Format, C: RetryKey>(
         &mut self,
         path_handle: Path,
         packet: &packet::initial::ProtectedInitial,
         local_connection_id: connection::LocalId,
+        attempt_id: Option<u64>,
         random: &mut dyn random::Generator,
         token_format: &mut T,
+        challenge: &[u8],
     ) {
         if let Some(transmission) = Transmission::new::<_, C>(
             path_handle,
             packet,
             local_connection_id,
+            attempt_id,
             random,
             token_format,
+            challenge,
         ) {
             self.transmissions.push_back(transmission);
         }
@@ -62,6 +67,8 @@ impl<Path: path::Handle> Dispatch<Path> {
         while let Some(transmission) = self.transmissions.pop_front() {
             match queue.push(&transmission) {
                 Ok(tx::Outcome { len, .. }) => {
+                    publisher.set_attempt_id(transmission.attempt_id);
+
                     publisher.on_endpoint_packet_sent(event::builder::EndpointPacketSent {
                         packet_header: event::builder::PacketHeader::Retry {
                             version: transmission.version,
@@ -72,6 +79,8 @@ impl<Path: path::Handle> Dispatch<Path> {
                         len: len as u16,
                         gso_offset: 0,
                     });
+
+                    publisher.set_attempt_id(None);
                 }
                 Err(_) => {
                     self.transmissions.push_front(transmission);
@@ -87,6 +96,8 @@ pub struct Transmission<Path: path::Handle> {
     packet: [u8; MINIMUM_MAX_DATAGRAM_SIZE as usize],
     packet_range: Range<usize>,
     version: u32,
+    /// The connection attempt which triggered the Retry
+    attempt_id: Option<u64>,
 }
 
 impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
@@ -104,16 +115,19 @@ impl<Path: path::Handle> Transmission<Path> {
         path: Path,
         packet: &packet::initial::ProtectedInitial,
         local_connection_id: connection::LocalId,
+        attempt_id: Option<u64>,
         random: &mut dyn random::Generator,
         token_format: &mut T,
+        challenge: &[u8],
     ) -> Option<Self> {
         let mut packet_buf = [0u8; MINIMUM_MAX_DATAGRAM_SIZE as usize];
-        let packet_range = packet::retry::Retry::encode_packet::<_, C>(
+        let packet_range = packet::retry::Retry::encode_packet_with_challenge::<_, C>(
             &path.remote_address(),
             packet,
             &local_connection_id,
             random,
             token_format,
+            challenge,
             &mut packet_buf,
         )?;
 
@@ -122,6 +136,7 @@ impl<Path: path::Handle> Transmission<Path> {
             packet: packet_buf,
             packet_range,
             version: packet.version,
+            attempt_id,
         })
     }
 }
@@ -0,0 +1,194 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::connection::ProcessingError;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+use s2n_quic_core::connection::Statistics;
+
+/// Statistics aggregated over all of the connections on an endpoint
+///
+/// The counters are shared between the endpoint, its connections and the application handles.
+#[derive(Clone, Debug, Default)]
+pub struct Counters(Arc<State>);
+
+#[derive(Debug, Default)]
+struct State {
+    duplicate_packets: AtomicU64,
+    retransmitted_bytes: AtomicU64,
+    spurious_retransmissions: AtomicU64,
+    coalesced_initial_packets: AtomicU64,
+    application_packets_sent: AtomicU64,
+    application_packet_bytes_sent: AtomicU64,
+    application_packet_capacity: AtomicU64,
+    padding_bytes_sent: AtomicU64,
+}
+
+impl Counters {
+    /// Adds the statistics recorded by a connection to the endpoint totals
+    #[inline]
+    pub fn record(&self, statistics: &Statistics) {
+        let state = &self.0;
+
+        macro_rules! add {
+            ($field:ident) => {
+                if statistics.$field > 0 {
+                    state.$field.fetch_add(statistics.$field, Ordering::Relaxed);
+                }
+            };
+        }
+
+        add!(duplicate_packets);
+        add!(retransmitted_bytes);
+        add!(spurious_retransmissions);
+        add!(coalesced_initial_packets);
+        add!(application_packets_sent);
+        add!(application_packet_bytes_sent);
+        add!(application_packet_capacity);
+        add!(padding_bytes_sent);
+    }
+
+    /// Returns a snapshot of the current totals
+    #[inline]
+    pub fn snapshot(&self) -> Statistics {
+        let state = &self.0;
+        let mut statistics = Statistics::default();
+        statistics.duplicate_packets = state.duplicate_packets.load(Ordering::Relaxed);
+        statistics.retransmitted_bytes = state.retransmitted_bytes.load(Ordering::Relaxed);
+        statistics.spurious_retransmissions =
+            state.spurious_retransmissions.load(Ordering::Relaxed);
+        statistics.coalesced_initial_packets =
+            state.coalesced_initial_packets.load(Ordering::Relaxed);
+        statistics.application_packets_sent =
+            state.application_packets_sent.load(Ordering::Relaxed);
+        statistics.application_packet_bytes_sent =
+            state.application_packet_bytes_sent.load(Ordering::Relaxed);
+        statistics.application_packet_capacity =
+            state.application_packet_capacity.load(Ordering::Relaxed);
+        statistics.padding_bytes_sent = state.padding_bytes_sent.load(Ordering::Relaxed);
+        statistics
+    }
+}
+
+/// Records the statistics for a single connection and forwards them to the endpoint totals
+#[derive(Debug)]
+pub struct Recorder {
+    connection: Statistics,
+    endpoint: Counters,
+}
+
+impl Recorder {
+    #[inline]
+    pub fn new(endpoint: Counters) -> Self {
+        Self {
+            connection: Statistics::default(),
+            endpoint,
+        }
+    }
+
+    /// Adds the given statistics to both the connection and endpoint totals
+    #[inline]
+    pub fn record(&mut self, statistics: Statistics) {
+        if statistics.is_empty() {
+            return;
+        }
+
+        self.connection += statistics;
+        self.endpoint.record(&statistics);
+    }
+
+    /// Called when a received packet was discarded since it had already been processed
+    #[inline]
+    pub fn on_duplicate_packet(&mut self) {
+        let mut statistics = Statistics::default();
+        statistics.duplicate_packets = 1;
+        self.record(statistics);
+    }
+
+    /// Called when an Initial packet was routed to a handshake that was already in progress
+    #[inline]
+    pub fn on_coalesced_initial_packet(&mut self) {
+        let mut statistics = Statistics::default();
+        statistics.coalesced_initial_packets = 1;
+        self.record(statistics);
+    }
+
+    /// Called after a burst of transmissions with the number of PADDING bytes that were written
+    #[inline]
+    pub fn on_padding_sent(&mut self, bytes: u64) {
+        let mut statistics = Statistics::default();
+        statistics.padding_bytes_sent = bytes;
+        self.record(statistics);
+    }
+
+    /// Records any statistics associated with a packet processing error
+    ///
+    /// The error is returned so this can be used with `map_err`.
+    #[inline]
+    pub fn on_processing_error(&mut self, error: ProcessingError) -> ProcessingError {
+        if matches!(error, ProcessingError::DuplicatePacket) {
+            self.on_duplicate_packet();
+        }
+        error
+    }
+
+    /// Returns the totals for the connection
+    #[inline]
+    pub fn snapshot(&self) -> Statistics {
+        self.connection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_test() {
+        let counters = Counters::default();
+        let handle = counters.clone();
+
+        let mut statistics = Statistics::default();
+        statistics.duplicate_packets = 1;
+        statistics.retransmitted_bytes = 100;
+        counters.record(&statistics);
+
+        statistics.spurious_retransmissions = 2;
+        counters.record(&statistics);
+
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.duplicate_packets, 2);
+        assert_eq!(snapshot.retransmitted_bytes, 200);
+        assert_eq!(snapshot.spurious_retransmissions, 2);
+    }
+
+    #[test]
+    fn recorder_test() {
+        let counters = Counters::default();
+        let mut a = Recorder::new(counters.clone());
+        let mut b = Recorder::new(counters.clone());
+
+        a.on_duplicate_packet();
+        b.on_duplicate_packet();
+        b.on_coalesced_initial_packet();
+        b.on_padding_sent(100);
+        b.on_padding_sent(0);
+
+        let mut statistics = Statistics::default();
+        statistics.retransmitted_bytes = 10;
+        b.record(statistics);
+
+        assert_eq!(a.snapshot().duplicate_packets, 1);
+        assert_eq!(a.snapshot().retransmitted_bytes, 0);
+        assert_eq!(b.snapshot().duplicate_packets, 1);
+        assert_eq!(b.snapshot().retransmitted_bytes, 10);
+        assert_eq!(b.snapshot().coalesced_initial_packets, 1);
+        assert_eq!(b.snapshot().padding_bytes_sent, 100);
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.duplicate_packets, 2);
+        assert_eq!(snapshot.retransmitted_bytes, 10);
+        assert_eq!(snapshot.coalesced_initial_packets, 1);
+        assert_eq!(snapshot.padding_bytes_sent, 100);
+    }
+}
@@ -12,6 +12,7 @@ use s2n_quic_core::{
     packet,
     packet::ProtectedPacket,
     path::{self, MINIMUM_MAX_DATAGRAM_SIZE},
+    transport::{self, parameters::VersionInformation},
 };
 
 #[derive(Debug)]
@@ -27,6 +28,42 @@ const SUPPORTED_VERSIONS: &[u32] = &[
     0x1, // Draft 34 / Version 1 (https://github.com/quicwg/base-drafts/wiki/21st-Implementation-Draft)
 ];
 
+/// Returns the version information transport parameter for a connection using `version`
+///
+/// Since none of the supported versions are compatible with each other, the chosen version is
+/// always the version of the packets which started the connection.
+pub fn version_information(version: u32) -> VersionInformation {
+    VersionInformation::new(version, SUPPORTED_VERSIONS)
+        .expect("supported versions should fit in the transport parameter")
+}
+
+/// Validates the version information transport parameter received from the peer
+///
+/// The transport parameters are authenticated by the handshake so checking that the peer chose
+/// the version the connection is using prevents an attacker from downgrading it.
+pub fn validate_version_information(
+    version: u32,
+    peer: Option<&VersionInformation>,
+) -> Result<(), transport::Error> {
+    // peers which don't support version negotiation don't send the parameter
+    let Some(peer) = peer else {
+        return Ok(());
+    };
+
+    if peer.chosen_version() != version {
+        return Err(transport::Error::VERSION_NEGOTIATION_ERROR
+            .with_reason("version_information chosen version mismatch"));
+    }
+
+    // the peer must list the version it chose as one it supports
+    if !peer.available_versions().is_empty() && !peer.is_available(version) {
+        return Err(transport::Error::VERSION_NEGOTIATION_ERROR
+            .with_reason("version_information chosen version not available"));
+    }
+
+    Ok(())
+}
+
 macro_rules! is_supported {
     ($packet:ident, $publisher:ident) => {{
         let supported = SUPPORTED_VERSIONS
@@ -150,8 +187,11 @@ impl<Config: endpoint::Config> Negotiator<Config> {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
                 //# Servers SHOULD respond with a Version
                 //# Negotiation packet, provided that the datagram is sufficiently long.
-                self.transmissions
-                    .push_back(Transmission::new(*path, packet));
+                self.transmissions.push_back(Transmission::new(
+                    *path,
+                    packet,
+                    publisher.attempt_id(),
+                ));
             }
         }
 
@@ -174,6 +214,8 @@ impl<Config: endpoint::Config> Negotiator<Config> {
         while let Some(transmission) = self.transmissions.pop_front() {
             match queue.push(&transmission) {
                 Ok(tx::Outcome { len, .. }) => {
+                    publisher.set_attempt_id(transmission.attempt_id);
+
                     publisher.on_endpoint_packet_sent(event::builder::EndpointPacketSent {
                         packet_header: event::builder::PacketHeader::VersionNegotiation {},
                     });
@@ -182,6 +224,8 @@ impl<Config: endpoint::Config> Negotiator<Config> {
                         len: len as u16,
                         gso_offset: 0,
                     });
+
+                    publisher.set_attempt_id(None);
                 }
                 Err(_) => {
                     self.transmissions.push_front(transmission);
@@ -197,6 +241,8 @@ struct Transmission<Path: path::Handle> {
     // The MINIMUM_MAX_DATAGRAM_SIZE size allows for at least 170 supported versions
     packet: [u8; MINIMUM_MAX_DATAGRAM_SIZE as usize],
     packet_len: usize,
+    /// The connection attempt which triggered the version negotiation
+    attempt_id: Option<u64>,
 }
 
 impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
@@ -211,7 +257,11 @@ impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
 }
 
 impl<Path: path::Handle> Transmission<Path> {
-    pub fn new(path: Path, initial_packet: &packet::initial::ProtectedInitial) -> Self {
+    pub fn new(
+        path: Path,
+        initial_packet: &packet::initial::ProtectedInitial,
+        attempt_id: Option<u64>,
+    ) -> Self {
         let mut packet_buf = [0u8; MINIMUM_MAX_DATAGRAM_SIZE as usize];
         let version_packet = packet::version_negotiation::VersionNegotiation::from_initial(
             initial_packet,
@@ -226,6 +276,7 @@ impl<Path: path::Handle> Transmission<Path> {
             path,
             packet: packet_buf,
             packet_len,
+            attempt_id,
         }
     }
 }
@@ -334,14 +385,14 @@ mod tests {
     fn datagram_info(payload_len: usize) -> (RemoteAddress, DatagramInfo) {
         (
             RemoteAddress::from(SocketAddress::default()),
-            DatagramInfo {
-                timestamp: time::now(),
+            DatagramInfo::new(
+                time::now(),
                 payload_len,
-                ecn: Default::default(),
-                destination_connection_id: connection::LocalId::TEST_ID,
-                destination_connection_id_classification: connection::id::Classification::Local,
-                source_connection_id: None,
-            },
+                Default::default(),
+                connection::LocalId::TEST_ID,
+                connection::id::Classification::Local,
+                None,
+            ),
         )
     }
 
@@ -658,6 +709,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn version_information_test() {
+        let local = version_information(0x1);
+        assert_eq!(local.chosen_version(), 0x1);
+        assert_eq!(local.available_versions(), SUPPORTED_VERSIONS);
+
+        // peers which don't support version negotiation are allowed
+        assert!(validate_version_information(0x1, None).is_ok());
+        assert!(validate_version_information(0x1, Some(&local)).is_ok());
+
+        // the peer must have chosen the version of the connection
+        let downgraded = VersionInformation::new(0xff00_001d, &[0x1, 0xff00_001d]).unwrap();
+        assert_eq!(
+            validate_version_information(0x1, Some(&downgraded))
+                .unwrap_err()
+                .code,
+            transport::Error::VERSION_NEGOTIATION_ERROR.code
+        );
+
+        // the chosen version must be listed as available
+        let unavailable = VersionInformation::new(0x1, &[0xff00_001d]).unwrap();
+        assert_eq!(
+            validate_version_information(0x1, Some(&unavailable))
+                .unwrap_err()
+                .code,
+            transport::Error::VERSION_NEGOTIATION_ERROR.code
+        );
+    }
+
     #[test]
     fn server_other_packets_test() {
         let mut server = Server::default();
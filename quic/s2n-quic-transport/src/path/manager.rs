@@ -27,7 +27,7 @@ use s2n_quic_core::{
         mtu, Handle as _, Id, MaxMtu,
     },
     random,
-    recovery::congestion_controller::{self, Endpoint as _},
+    recovery::congestion_controller::{self, CongestionController as _, Endpoint as _},
     stateless_reset,
     time::{timer, Timestamp},
     transport,
@@ -70,6 +70,15 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// The number of times the peer has moved the active path of the connection
+    migration_count: u32,
+
+    /// Measures the round-trip time of the active path on behalf of the application
+    pub(crate) probe: path::probe::Probe,
+
+    /// Exchanges observed addresses with the peer
+    pub(crate) observed_address: path::observed_address::ObservedAddress,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -80,6 +89,9 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            migration_count: 0,
+            probe: Default::default(),
+            observed_address: Default::default(),
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
@@ -194,6 +206,10 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: path_event!(new_path, new_path_id),
         });
 
+        let remote_address = new_path.remote_address().0;
+        self.observed_address
+            .on_active_path_updated(&remote_address);
+
         amplification_outcome
     }
 
@@ -349,6 +365,7 @@ impl<Config: endpoint::Config> Manager<Config> {
                 local_address: &local_address,
             }
             .into(),
+            migration_count: self.migration_count,
         }
         .into();
 
@@ -466,6 +483,13 @@ impl<Config: endpoint::Config> Manager<Config> {
             cause: MtuUpdatedCause::NewPath,
         });
 
+        publisher.on_congestion_controller_initialized(
+            event::builder::CongestionControllerInitialized {
+                path_id: new_path_id.into_event(),
+                algorithm: path.congestion_controller.algorithm(),
+            },
+        );
+
         // create a new path
         if new_path_idx < self.paths.len() {
             self.paths[new_path_idx] = path;
@@ -476,6 +500,17 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok((new_path_id, amplification_outcome))
     }
 
+    /// Starts a probe of the active path if the application requested one
+    pub fn on_probe_wakeup(&mut self, random_generator: &mut dyn random::Generator) {
+        if self.probe.is_requested() {
+            // use the same timeout as path validation
+            let timeout = 3 * self
+                .active_path()
+                .pto_period(PacketNumberSpace::ApplicationData);
+            self.probe.on_wakeup(timeout, random_generator);
+        }
+    }
+
     fn set_challenge(&mut self, path_id: Id, random_generator: &mut dyn random::Generator) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.1
         //# The endpoint MUST use unpredictable data in every PATH_CHALLENGE
@@ -529,7 +564,11 @@ impl<Config: endpoint::Config> Manager<Config> {
     /// Writes any frames the path manager wishes to transmit to the given context
     #[inline]
     pub fn on_transmit<W: transmission::WriteContext>(&mut self, context: &mut W) {
-        self.peer_id_registry.on_transmit(context)
+        self.peer_id_registry.on_transmit(context);
+
+        self.probe.on_transmit(context);
+
+        self.observed_address.on_transmit(context);
 
         // TODO Add in per-path constraints based on whether a Challenge needs to be
         // transmitted.
@@ -539,12 +578,30 @@ impl<Config: endpoint::Config> Manager<Config> {
     #[inline]
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.peer_id_registry.on_packet_ack(ack_set);
+        self.observed_address.on_packet_ack(ack_set);
     }
 
     /// Called when packets are lost
     #[inline]
     pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
         self.peer_id_registry.on_packet_loss(ack_set);
+        self.observed_address.on_packet_loss(ack_set);
+    }
+
+    /// Stores our address as observed by the peer
+    #[inline]
+    pub fn on_observed_address(
+        &mut self,
+        frame: &frame::ObservedAddress,
+    ) -> Result<(), transport::Error> {
+        // An endpoint that didn't ask to receive observed addresses treats the frame as a
+        // protocol violation
+        if !self.observed_address.on_frame_received(frame) {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("address discovery was not negotiated"));
+        }
+
+        Ok(())
     }
 
     #[inline]
@@ -663,6 +720,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         if !path_validation_probing.is_probing() && self.active_path_id() != path_id {
             amplification_outcome =
                 self.update_active_path(path_id, random_generator, publisher)?;
+            self.migration_count = self.migration_count.saturating_add(1);
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3
             //# After changing the address to which it sends non-probing packets, an
             //# endpoint can abandon any path validation for other addresses.
@@ -758,6 +816,8 @@ impl<Config: endpoint::Config> Manager<Config> {
             path.on_timeout(timestamp, path_id(id as u8), random_generator, publisher);
         }
 
+        self.probe.on_timeout(timestamp);
+
         let mut amplification_outcome = AmplificationOutcome::Unchanged;
 
         if self.active_path().failed_validation() {
@@ -865,6 +925,8 @@ impl<Config: endpoint::Config> timer::Provider for Manager<Config> {
             path.timers(query)?;
         }
 
+        self.probe.timers(query)?;
+
         Ok(())
     }
 }
@@ -910,6 +972,8 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Manager<Conf
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.peer_id_registry.transmission_interest(query)?;
+        self.probe.transmission_interest(query)?;
+        self.observed_address.transmission_interest(query)?;
 
         for path in self.paths.iter() {
             // query PATH_CHALLENGE and PATH_RESPONSE interest for each path
@@ -159,14 +159,14 @@ impl Model {
         // Handle is an alias to RemoteAddress so does not inherit the PathHandle
         // eq implementation which unmaps an ipv6 address into a ipv4 address
         let handle = path::RemoteAddress(handle.unmap());
-        let datagram = DatagramInfo {
-            timestamp: self.timestamp,
-            payload_len: payload_len as usize,
-            ecn: ExplicitCongestionNotification::NotEct,
-            destination_connection_id: local_id,
-            destination_connection_id_classification: connection::id::Classification::Local,
-            source_connection_id: None,
-        };
+        let datagram = DatagramInfo::new(
+            self.timestamp,
+            payload_len as usize,
+            ExplicitCongestionNotification::NotEct,
+            local_id,
+            connection::id::Classification::Local,
+            None,
+        );
         let mut migration_validator = path::migration::allow_all::Validator;
         let mut random_generator = Generator::default();
         let mut publisher = Publisher::no_snapshot();
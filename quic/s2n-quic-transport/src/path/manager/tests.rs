@@ -651,6 +651,7 @@ fn non_probing_should_update_path_to_active_path() {
     // Expectation:
     assert!(amplification_outcome.is_unchanged());
     assert_eq!(helper.manager.active, helper.second_path_id.as_u8());
+    assert_eq!(helper.manager.migration_count, 1);
 }
 
 #[test]
@@ -681,6 +682,7 @@ fn probing_should_not_update_path_to_active_path() {
     // Expectation:
     assert!(amplification_outcome.is_unchanged());
     assert_eq!(helper.manager.active, helper.first_path_id.as_u8());
+    assert_eq!(helper.manager.migration_count, 0);
 }
 
 #[test]
@@ -720,14 +722,14 @@ fn test_adding_new_path() {
     assert_eq!(manager.paths.len(), 1);
 
     // Trigger:
-    let datagram = DatagramInfo {
-        timestamp: NoopClock {}.get_time(),
-        payload_len: 0,
-        ecn: ExplicitCongestionNotification::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        NoopClock {}.get_time(),
+        0,
+        ExplicitCongestionNotification::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
     let (path_id, amplification_outcome) = manager
         .on_datagram_received(
             &new_addr,
@@ -781,14 +783,14 @@ fn do_not_add_new_path_if_handshake_not_confirmed() {
     assert_eq!(manager.paths.len(), 1);
 
     // Trigger:
-    let datagram = DatagramInfo {
-        timestamp: NoopClock {}.get_time(),
-        payload_len: 0,
-        ecn: ExplicitCongestionNotification::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        NoopClock {}.get_time(),
+        0,
+        ExplicitCongestionNotification::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
     let handshake_confirmed = false;
     let on_datagram_result = manager.on_datagram_received(
         &new_addr,
@@ -844,14 +846,14 @@ fn do_not_add_new_path_if_client() {
     assert_eq!(manager.paths.len(), 1);
 
     // Trigger:
-    let datagram = DatagramInfo {
-        timestamp: NoopClock {}.get_time(),
-        payload_len: 0,
-        ecn: ExplicitCongestionNotification::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        NoopClock {}.get_time(),
+        0,
+        ExplicitCongestionNotification::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
     let on_datagram_result = manager.on_datagram_received(
         &new_addr,
         &datagram,
@@ -935,14 +937,14 @@ fn limit_number_of_connection_migrations() {
         let new_addr = SocketAddress::from(new_addr);
         let new_addr = RemoteAddress::from(new_addr);
         let now = NoopClock {}.get_time();
-        let datagram = DatagramInfo {
-            timestamp: now,
-            payload_len: 0,
-            ecn: ExplicitCongestionNotification::default(),
-            destination_connection_id: connection::LocalId::TEST_ID,
-            destination_connection_id_classification: connection::id::Classification::Local,
-            source_connection_id: None,
-        };
+        let datagram = DatagramInfo::new(
+            now,
+            0,
+            ExplicitCongestionNotification::default(),
+            connection::LocalId::TEST_ID,
+            connection::id::Classification::Local,
+            None,
+        );
 
         let res = manager.handle_connection_migration(
             &new_addr,
@@ -993,14 +995,14 @@ fn connection_migration_challenge_behavior() {
     let new_addr = SocketAddress::from(new_addr);
     let new_addr = RemoteAddress::from(new_addr);
     let now = NoopClock {}.get_time();
-    let datagram = DatagramInfo {
-        timestamp: now,
-        payload_len: 0,
-        ecn: ExplicitCongestionNotification::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        now,
+        0,
+        ExplicitCongestionNotification::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
 
     let (path_id, _amplification_outcome) = manager
         .handle_connection_migration(
@@ -1088,14 +1090,14 @@ fn connection_migration_use_max_ack_delay_from_active_path() {
     let new_addr = SocketAddress::from(new_addr);
     let new_addr = RemoteAddress::from(new_addr);
     let now = NoopClock {}.get_time();
-    let datagram = DatagramInfo {
-        timestamp: now,
-        payload_len: 0,
-        ecn: ExplicitCongestionNotification::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        now,
+        0,
+        ExplicitCongestionNotification::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
 
     // Trigger 1:
     let (second_path_id, _amplification_outcome) = manager
@@ -1168,14 +1170,14 @@ fn connection_migration_new_path_abandon_timer() {
     let new_addr = SocketAddress::from(new_addr);
     let new_addr = RemoteAddress::from(new_addr);
     let now = NoopClock {}.get_time();
-    let datagram = DatagramInfo {
-        timestamp: now,
-        payload_len: 0,
-        ecn: ExplicitCongestionNotification::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        now,
+        0,
+        ExplicitCongestionNotification::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
 
     let (second_path_id, _amplification_outcome) = manager
         .handle_connection_migration(
@@ -1423,14 +1425,14 @@ fn pending_paths_should_return_paths_pending_validation() {
 fn temporary_until_authenticated() {
     let mut publisher = Publisher::snapshot();
     let now = NoopClock {}.get_time();
-    let datagram = DatagramInfo {
-        timestamp: now,
-        payload_len: 0,
-        ecn: ExplicitCongestionNotification::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        now,
+        0,
+        ExplicitCongestionNotification::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
 
     // create an initial path
     let first_addr: SocketAddr = "127.0.0.1:8001".parse().unwrap();
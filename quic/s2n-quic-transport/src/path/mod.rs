@@ -20,6 +20,8 @@ use s2n_quic_core::{
 
 mod challenge;
 mod manager;
+pub mod observed_address;
+pub mod probe;
 
 pub use challenge::Challenge;
 pub use manager::*;
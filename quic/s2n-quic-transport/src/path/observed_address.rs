@@ -0,0 +1,292 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implements the QUIC address discovery extension
+//!
+//! Endpoints that negotiated the extension report the remote address they observe on the active
+//! path with an OBSERVED_ADDRESS frame. This allows a peer behind a NAT to learn the reflexive
+//! address assigned to it, which is useful for peer-to-peer rendezvous and NAT diagnostics.
+
+use crate::{contexts::WriteContext, transmission};
+use s2n_quic_core::{
+    ack, frame, inet::SocketAddress, packet::number::PacketNumber,
+    transport::parameters::AddressDiscovery, varint::VarInt,
+};
+
+#[derive(Debug, Default)]
+pub struct ObservedAddress {
+    /// Set if the peer asked to receive observed addresses and we agreed to provide them
+    is_providing: bool,
+
+    /// Set if we asked the peer to report our observed address
+    is_receiving: bool,
+
+    /// The latest address reported to the peer
+    reported: Option<frame::ObservedAddress>,
+
+    /// The sequence number for the next address reported to the peer
+    next_sequence_number: VarInt,
+
+    transmission: Transmission,
+
+    /// The largest sequence number received from the peer
+    largest_received: Option<VarInt>,
+
+    /// Our address as observed by the peer
+    observed: Option<SocketAddress>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+enum Transmission {
+    #[default]
+    Idle,
+
+    /// The reported address needs to be transmitted
+    RequiresTransmission,
+
+    /// The reported address was lost and needs to be retransmitted
+    RequiresRetransmission,
+
+    /// The reported address was sent and is pending acknowledgement
+    InFlight(PacketNumber),
+}
+
+impl ObservedAddress {
+    /// Enables the extension based on the locally configured mode and the peer's transport
+    /// parameter
+    pub fn on_transport_parameters(
+        &mut self,
+        local: Option<AddressDiscovery>,
+        peer: Option<AddressDiscovery>,
+        remote_address: &SocketAddress,
+    ) {
+        self.is_providing = local.map_or(false, AddressDiscovery::provides)
+            && peer.map_or(false, AddressDiscovery::receives);
+        self.is_receiving = local.map_or(false, AddressDiscovery::receives);
+
+        self.on_active_path_updated(remote_address);
+    }
+
+    /// Reports the remote address of the active path to the peer, if it changed
+    pub fn on_active_path_updated(&mut self, remote_address: &SocketAddress) {
+        if !self.is_providing {
+            return;
+        }
+
+        // report the address in the format it was sent by the peer
+        let address = remote_address.unmap();
+
+        if self.reported.map(|frame| frame.address) == Some(address) {
+            return;
+        }
+
+        self.reported = Some(frame::ObservedAddress {
+            sequence_number: self.next_sequence_number,
+            address,
+        });
+        self.next_sequence_number = self.next_sequence_number.saturating_add(VarInt::from_u8(1));
+        self.transmission = Transmission::RequiresTransmission;
+    }
+
+    /// Called when an OBSERVED_ADDRESS frame is received from the peer
+    ///
+    /// Returns `true` if the address was accepted
+    pub fn on_frame_received(&mut self, frame: &frame::ObservedAddress) -> bool {
+        if !self.is_receiving {
+            return false;
+        }
+
+        // frames that are reordered are ignored so the most recent observation is kept
+        if self
+            .largest_received
+            .map_or(true, |largest| frame.sequence_number > largest)
+        {
+            self.largest_received = Some(frame.sequence_number);
+            self.observed = Some(frame.address);
+        }
+
+        true
+    }
+
+    /// Returns our address as observed by the peer
+    #[inline]
+    pub fn address(&self) -> Option<SocketAddress> {
+        self.observed
+    }
+
+    /// Writes the OBSERVED_ADDRESS frame, if needed
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        let constraint = context.transmission_constraint();
+        let can_transmit = match self.transmission {
+            Transmission::RequiresTransmission => constraint.can_transmit(),
+            Transmission::RequiresRetransmission => constraint.can_retransmit(),
+            _ => false,
+        };
+
+        if !can_transmit {
+            return;
+        }
+
+        if let Some(frame) = self.reported.as_ref() {
+            if let Some(packet_number) = context.write_frame(frame) {
+                self.transmission = Transmission::InFlight(packet_number);
+            }
+        }
+    }
+
+    /// Called when packets are acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let Transmission::InFlight(packet_number) = self.transmission {
+            if ack_set.contains(packet_number) {
+                self.transmission = Transmission::Idle;
+            }
+        }
+    }
+
+    /// Called when packets are lost
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let Transmission::InFlight(packet_number) = self.transmission {
+            if ack_set.contains(packet_number) {
+                self.transmission = Transmission::RequiresRetransmission;
+            }
+        }
+    }
+}
+
+impl transmission::interest::Provider for ObservedAddress {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        match self.transmission {
+            Transmission::RequiresTransmission => query.on_new_data(),
+            Transmission::RequiresRetransmission => query.on_lost_data(),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        contexts::testing::{MockWriteContext, OutgoingFrameBuffer},
+        transmission::interest::{Interest, Provider as _},
+    };
+    use s2n_quic_core::{
+        endpoint,
+        inet::{IpV4Address, IpV6Address},
+        packet::number::PacketNumberRange,
+        time::clock::testing as time,
+    };
+
+    fn transmit(observed_address: &mut ObservedAddress) -> Option<frame::ObservedAddress> {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+        observed_address.on_transmit(&mut context);
+
+        let frame = context.frame_buffer.pop_front()?;
+        match frame.as_frame() {
+            frame::Frame::ObservedAddress(frame) => Some(frame),
+            frame => panic!("unexpected frame {frame:?}"),
+        }
+    }
+
+    #[test]
+    fn provide_test() {
+        let address: SocketAddress = IpV4Address::new([192, 0, 2, 1]).with_port(4433).into();
+        let mut observed_address = ObservedAddress::default();
+
+        // the peer didn't ask for observed addresses
+        observed_address.on_transport_parameters(
+            Some(AddressDiscovery::ProvideAndReceive),
+            Some(AddressDiscovery::ProvideOnly),
+            &address,
+        );
+        assert_eq!(observed_address.get_transmission_interest(), Interest::None);
+
+        observed_address.on_transport_parameters(
+            Some(AddressDiscovery::ProvideOnly),
+            Some(AddressDiscovery::ReceiveOnly),
+            &address,
+        );
+        assert_eq!(
+            observed_address.get_transmission_interest(),
+            Interest::NewData
+        );
+
+        let frame = transmit(&mut observed_address).unwrap();
+        assert_eq!(frame.sequence_number, VarInt::from_u8(0));
+        assert_eq!(frame.address, address);
+        assert_eq!(observed_address.get_transmission_interest(), Interest::None);
+
+        // the frame is retransmitted if it is lost
+        let packet_number = match observed_address.transmission {
+            Transmission::InFlight(packet_number) => packet_number,
+            ref transmission => panic!("unexpected state {transmission:?}"),
+        };
+        observed_address.on_packet_loss(&PacketNumberRange::new(packet_number, packet_number));
+        assert_eq!(
+            observed_address.get_transmission_interest(),
+            Interest::LostData
+        );
+        assert_eq!(transmit(&mut observed_address), Some(frame));
+
+        // the same address isn't reported again
+        observed_address.on_active_path_updated(&address);
+        assert_eq!(observed_address.get_transmission_interest(), Interest::None);
+
+        // a new address is reported with the next sequence number
+        let address: SocketAddress =
+            IpV6Address::new([0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1])
+                .with_port(4433)
+                .into();
+        observed_address.on_active_path_updated(&address);
+        let frame = transmit(&mut observed_address).unwrap();
+        assert_eq!(frame.sequence_number, VarInt::from_u8(1));
+        assert_eq!(frame.address, address);
+    }
+
+    #[test]
+    fn receive_test() {
+        let first: SocketAddress = IpV4Address::new([192, 0, 2, 1]).with_port(4433).into();
+        let second: SocketAddress = IpV4Address::new([192, 0, 2, 2]).with_port(4433).into();
+        let mut observed_address = ObservedAddress::default();
+
+        let frame = |sequence_number: u8, address: SocketAddress| frame::ObservedAddress {
+            sequence_number: VarInt::from_u8(sequence_number),
+            address,
+        };
+
+        // frames aren't accepted unless we asked for them
+        observed_address.on_transport_parameters(
+            Some(AddressDiscovery::ProvideOnly),
+            Some(AddressDiscovery::ProvideAndReceive),
+            &first,
+        );
+        assert!(!observed_address.on_frame_received(&frame(0, first)));
+        assert_eq!(observed_address.address(), None);
+
+        observed_address.on_transport_parameters(
+            Some(AddressDiscovery::ReceiveOnly),
+            Some(AddressDiscovery::ProvideOnly),
+            &first,
+        );
+        assert!(observed_address.on_frame_received(&frame(1, first)));
+        assert_eq!(observed_address.address(), Some(first));
+
+        assert!(observed_address.on_frame_received(&frame(3, second)));
+        assert_eq!(observed_address.address(), Some(second));
+
+        // reordered frames are ignored
+        assert!(observed_address.on_frame_received(&frame(2, first)));
+        assert_eq!(observed_address.address(), Some(second));
+    }
+}
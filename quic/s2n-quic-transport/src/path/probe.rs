@@ -0,0 +1,229 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures the round-trip time of the active path on demand
+//!
+//! A probe sends a single PATH_CHALLENGE frame on the active path and completes once the matching
+//! PATH_RESPONSE is received or the probe times out. Unlike path validation, the outcome doesn't
+//! change the validation state of any path and the RTT sample isn't fed to the RTT estimator.
+
+use super::challenge::Data;
+use crate::{contexts::WriteContext, transmission};
+use core::task::{Context, Poll, Waker};
+use s2n_quic_core::{
+    ct::ConstantTimeEq,
+    frame, random,
+    time::{timer, Duration, Timer, Timestamp},
+};
+
+#[derive(Debug, Default)]
+pub struct Probe {
+    state: State,
+    timer: Timer,
+    waker: Option<Waker>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    Idle,
+
+    /// The application requested a probe and is waiting for the connection to generate the
+    /// challenge data
+    Requested,
+
+    /// The PATH_CHALLENGE frame must be sent
+    RequiresTransmission { data: Data, timeout: Duration },
+
+    /// The PATH_CHALLENGE frame was sent and we are awaiting a response until the timer expires
+    PendingResponse { data: Data, sent: Timestamp },
+
+    /// The probe completed with the round-trip time, or `None` if it timed out
+    Completed(Option<Duration>),
+}
+
+impl Probe {
+    /// Polls for the outcome of a probe, starting a new one if none is in progress
+    ///
+    /// Returns `Some(Duration)` with the round-trip time of the probe, or `None` if the probe
+    /// timed out.
+    pub fn poll(&mut self, context: &Context) -> Poll<Option<Duration>> {
+        match self.state {
+            State::Completed(outcome) => {
+                self.state = State::Idle;
+                self.waker = None;
+                return Poll::Ready(outcome);
+            }
+            State::Idle => self.state = State::Requested,
+            _ => {}
+        }
+
+        self.waker = Some(context.waker().clone());
+        Poll::Pending
+    }
+
+    /// Returns `true` if the application is waiting for the probe to be started
+    #[inline]
+    pub fn is_requested(&self) -> bool {
+        matches!(self.state, State::Requested)
+    }
+
+    /// Generates the challenge data for a requested probe
+    pub fn on_wakeup(&mut self, timeout: Duration, random_generator: &mut dyn random::Generator) {
+        if self.is_requested() {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.1
+            //# The endpoint MUST use unpredictable data in every PATH_CHALLENGE
+            //# frame so that it can associate the peer's response with the
+            //# corresponding PATH_CHALLENGE.
+            let mut data: Data = [0; frame::path_challenge::DATA_LEN];
+            random_generator.public_random_fill(&mut data);
+            self.state = State::RequiresTransmission { data, timeout };
+        }
+    }
+
+    /// Writes the PATH_CHALLENGE frame for the probe, if needed
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        if let State::RequiresTransmission { data, timeout } = self.state {
+            let frame = frame::PathChallenge { data: &data };
+            if context.write_frame(&frame).is_some() {
+                let sent = context.current_time();
+                self.state = State::PendingResponse { data, sent };
+                self.timer.set(sent + timeout);
+            }
+        }
+    }
+
+    /// Completes the probe if the PATH_RESPONSE data matches the probe
+    ///
+    /// Returns `true` if the response belonged to the probe.
+    pub fn on_path_response(&mut self, response: &[u8], timestamp: Timestamp) -> bool {
+        if let State::PendingResponse { data, sent } = self.state {
+            if ConstantTimeEq::ct_eq(&data[..], response).into() {
+                self.complete(Some(timestamp.saturating_duration_since(sent)));
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn on_timeout(&mut self, timestamp: Timestamp) {
+        if self.timer.poll_expiration(timestamp).is_ready() {
+            self.complete(None);
+        }
+    }
+
+    /// Wakes the application so it can observe the connection error
+    pub fn on_connection_error(&mut self) {
+        self.timer.cancel();
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn complete(&mut self, outcome: Option<Duration>) {
+        self.state = State::Completed(outcome);
+        self.timer.cancel();
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl timer::Provider for Probe {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)?;
+
+        Ok(())
+    }
+}
+
+impl transmission::interest::Provider for Probe {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        match self.state {
+            State::RequiresTransmission { .. } => {
+                query.on_interest(transmission::Interest::NewData)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        contexts::testing::{MockWriteContext, OutgoingFrameBuffer},
+        transmission::interest::{Interest, Provider as _},
+    };
+    use futures_test::task::new_count_waker;
+    use s2n_quic_core::{
+        endpoint,
+        time::{Clock, NoopClock},
+    };
+
+    fn transmit(probe: &mut Probe, now: Timestamp) -> Data {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            now,
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+        probe.on_transmit(&mut context);
+
+        assert_eq!(context.frame_buffer.len(), 1);
+        match context.frame_buffer.pop_front().unwrap().as_frame() {
+            frame::Frame::PathChallenge(frame) => *frame.data,
+            frame => panic!("unexpected frame {frame:?}"),
+        }
+    }
+
+    #[test]
+    fn probe_test() {
+        let (waker, wake_count) = new_count_waker();
+        let context = Context::from_waker(&waker);
+        let mut random = random::testing::Generator(123);
+        let now = NoopClock {}.get_time();
+        let timeout = Duration::from_millis(100);
+        let mut probe = Probe::default();
+
+        assert!(probe.poll(&context).is_pending());
+        assert!(probe.is_requested());
+        assert_eq!(probe.get_transmission_interest(), Interest::None);
+
+        probe.on_wakeup(timeout, &mut random);
+        assert_eq!(probe.get_transmission_interest(), Interest::NewData);
+
+        let data = transmit(&mut probe, now);
+        assert_eq!(probe.get_transmission_interest(), Interest::None);
+        assert!(probe.poll(&context).is_pending());
+
+        // a response with other data is ignored
+        assert!(!probe.on_path_response(&[0; 8], now));
+        assert!(probe.on_path_response(&data, now + Duration::from_millis(10)));
+        assert_eq!(wake_count.get(), 1);
+        assert_eq!(
+            probe.poll(&context),
+            Poll::Ready(Some(Duration::from_millis(10)))
+        );
+
+        // start another probe which times out
+        assert!(probe.poll(&context).is_pending());
+        probe.on_wakeup(timeout, &mut random);
+        let data = transmit(&mut probe, now);
+        probe.on_timeout(now + timeout);
+        assert_eq!(wake_count.get(), 2);
+        assert_eq!(probe.poll(&context), Poll::Ready(None));
+
+        // late responses are ignored
+        assert!(!probe.on_path_response(&data, now + timeout));
+        assert!(!probe.timer.is_armed());
+    }
+}
@@ -14,6 +14,7 @@ use s2n_quic_core::{
     frame,
     frame::ack::EcnCounts,
     inet::ExplicitCongestionNotification,
+    interval_set::IntervalSet,
     packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace},
     recovery::{congestion_controller, persistent_congestion, CongestionController, Pto},
     time::{timer, timer::Provider, Timer, Timestamp},
@@ -66,6 +67,17 @@ pub struct Manager<Config: endpoint::Config> {
     //
     // Used for updating the PTO timer at the end of a transmission burst.
     pto_update_pending: bool,
+
+    // Packets which were declared lost, used to detect spurious retransmissions if the peer
+    // later acknowledges them
+    lost_packets: IntervalSet<PacketNumber>,
+
+    // The number of spurious retransmissions detected since the last call to
+    // `take_spurious_retransmissions`
+    spurious_retransmissions: u64,
+
+    // Replaces the PTO period derived from the RTT estimate, before the backoff is applied
+    pto_period_override: Option<Duration>,
 }
 
 /// Initial capacity of the SmallVec used for keeping track of packets
@@ -73,6 +85,12 @@ pub struct Manager<Config: endpoint::Config> {
 // TODO: Determine if there is a more appropriate default
 const ACKED_PACKETS_INITIAL_CAPACITY: usize = 32;
 
+/// The maximum number of lost packet ranges which are remembered for detecting spurious
+/// retransmissions
+///
+/// Once the limit is reached, the oldest ranges are forgotten.
+const MAX_LOST_PACKET_INTERVALS: usize = 16;
+
 macro_rules! recovery_event {
     ($path_id:ident, $path:ident) => {
         event::builder::RecoveryMetrics {
@@ -124,9 +142,32 @@ impl<Config: endpoint::Config> Manager<Config> {
             baseline_ecn_counts: EcnCounts::default(),
             sent_packet_ecn_counts: EcnCounts::default(),
             pto_update_pending: false,
+            lost_packets: IntervalSet::new(),
+            spurious_retransmissions: 0,
+            pto_period_override: None,
         }
     }
 
+    /// Overrides the PTO period derived from the RTT estimate, or clears the override with `None`
+    ///
+    /// The PTO backoff is still applied to the overridden period.
+    pub fn set_pto_period_override(&mut self, period: Option<Duration>) {
+        self.pto_period_override = period;
+    }
+
+    /// Returns the number of spurious retransmissions detected since the last call and resets
+    /// the counter
+    #[inline]
+    pub fn take_spurious_retransmissions(&mut self) -> u64 {
+        core::mem::take(&mut self.spurious_retransmissions)
+    }
+
+    /// Releases any sent packet tracking capacity which isn't currently in use
+    #[inline]
+    pub fn hibernate(&mut self) {
+        self.sent_packets.shrink_to_fit();
+    }
+
     /// Invoked when the Client processes a Retry packet.
     ///
     /// Reset congestion controller state by discarding sent bytes and replacing recovery
@@ -154,6 +195,9 @@ impl<Config: endpoint::Config> Manager<Config> {
         *self = Self::new(self.space);
     }
 
+    /// Handles the loss and PTO timers
+    ///
+    /// Returns `true` if the PTO timer expired.
     pub fn on_timeout<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
         timestamp: Timestamp,
@@ -161,9 +205,11 @@ impl<Config: endpoint::Config> Manager<Config> {
         max_pto_backoff: u32,
         context: &mut Ctx,
         publisher: &mut Pub,
-    ) {
+    ) -> bool {
         debug_assert!(!self.pto_update_pending);
 
+        let mut pto_expired = false;
+
         if self.loss_timer.is_armed() {
             if self.loss_timer.poll_expiration(timestamp).is_ready() {
                 self.detect_and_remove_lost_packets(
@@ -179,10 +225,8 @@ impl<Config: endpoint::Config> Manager<Config> {
                 );
             }
         } else {
-            let pto_expired = self
-                .pto
-                .on_timeout(!self.sent_packets.is_empty(), timestamp)
-                .is_ready();
+            let packets_in_flight = !self.sent_packets.is_empty();
+            pto_expired = self.pto.on_timeout(packets_in_flight, timestamp).is_ready();
 
             //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2
             //# A PTO timer expiration event does not indicate packet loss and MUST
@@ -199,6 +243,18 @@ impl<Config: endpoint::Config> Manager<Config> {
                     timestamp,
                     context.is_handshake_confirmed(),
                 );
+
+                if let Some(space) = handshake_key_space(self.space) {
+                    let pto_backoff = context.active_path().pto_backoff;
+                    let reason = if packets_in_flight {
+                        event::builder::HandshakeRetransmissionReason::ProbeTimeout { pto_backoff }
+                    } else {
+                        event::builder::HandshakeRetransmissionReason::AntiDeadlock { pto_backoff }
+                    };
+                    publisher.on_handshake_retransmission(
+                        event::builder::HandshakeRetransmission { space, reason },
+                    );
+                }
             }
         }
 
@@ -207,6 +263,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         let path_id = context.path_id().as_u8();
         let path = context.path_mut();
         publisher.on_recovery_metrics(recovery_event!(path_id, path));
+
+        pto_expired
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9002#appendix-A.5
@@ -360,8 +418,12 @@ impl<Config: endpoint::Config> Manager<Config> {
                 now
             };
 
-            self.pto
-                .update(pto_base_timestamp, active_path.pto_period(self.space));
+            let pto_period = match self.pto_period_override {
+                Some(period) => period.saturating_mul(active_path.pto_backoff),
+                None => active_path.pto_period(self.space),
+            };
+
+            self.pto.update(pto_base_timestamp, pto_period);
         })();
 
         self.check_consistency(active_path, is_handshake_confirmed);
@@ -517,6 +579,10 @@ impl<Config: endpoint::Config> Manager<Config> {
             // notify components of packets acked
             context.on_packet_ack(timestamp, &pn_range);
 
+            if !self.lost_packets.is_empty() {
+                self.detect_spurious_retransmissions(&pn_range);
+            }
+
             let mut newly_acked_range: Option<(PacketNumber, PacketNumber)> = None;
 
             for (packet_number, acked_packet_info) in self.sent_packets.remove_range(pn_range) {
@@ -559,6 +625,25 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok((largest_newly_acked, includes_ack_eliciting))
     }
 
+    /// Counts the acknowledged packets which had already been declared lost
+    fn detect_spurious_retransmissions(&mut self, pn_range: &PacketNumberRange) {
+        let (start, end) = (pn_range.start(), pn_range.end());
+
+        for interval in self.lost_packets.intervals() {
+            let overlap_start = interval.start_inclusive().max(start);
+            let overlap_end = interval.end_inclusive().min(end);
+
+            if overlap_start <= overlap_end {
+                self.spurious_retransmissions += overlap_end.as_u64() - overlap_start.as_u64() + 1;
+            }
+        }
+
+        // ACK frames repeat ranges so make sure the packets are only counted once
+        self.lost_packets
+            .remove(start..=end)
+            .expect("lost_packets has no interval limit");
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn update_congestion_control<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
@@ -601,6 +686,25 @@ impl<Config: endpoint::Config> Manager<Config> {
                 largest_acked_packet_number.space(),
             );
 
+            publisher.on_rtt_sample(event::builder::RttSample {
+                path: event::builder::Path {
+                    local_addr: path.local_address().into_event(),
+                    local_cid: path.local_connection_id.into_event(),
+                    remote_addr: path.remote_address().into_event(),
+                    remote_cid: path.peer_connection_id.into_event(),
+                    id: largest_newly_acked_info.path_id.as_u8() as u64,
+                    is_active: path.is_active(),
+                },
+                packet_header: event::builder::PacketHeader::new(
+                    largest_acked_packet_number,
+                    publisher.quic_version(),
+                ),
+                latest_rtt: path.rtt_estimator.latest_rtt(),
+                ack_delay,
+                min_rtt: path.rtt_estimator.min_rtt(),
+                smoothed_rtt: path.rtt_estimator.smoothed_rtt(),
+            });
+
             // Update the congestion controller with the latest RTT estimate
             path.congestion_controller.on_rtt_update(
                 largest_newly_acked_info.time_sent,
@@ -943,6 +1047,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         publisher: &mut Pub,
     ) {
         let current_path_id = context.path_id();
+        let handshake_space = handshake_key_space(self.space);
         let mut is_congestion_event = false;
         let mut prev_lost_packet_number = None;
 
@@ -1002,6 +1107,16 @@ impl<Config: endpoint::Config> Manager<Config> {
                 is_mtu_probe: sent_info.transmission_mode.is_mtu_probing(),
             });
 
+            if let Some(space) = handshake_space {
+                publisher.on_handshake_retransmission(event::builder::HandshakeRetransmission {
+                    space,
+                    reason: event::builder::HandshakeRetransmissionReason::PacketLost {
+                        packet_number: packet_number.as_u64(),
+                        bytes_lost: sent_info.sent_bytes,
+                    },
+                });
+            }
+
             // Notify the MTU controller of packet loss even if it wasn't a probe since it uses
             // that information for blackhole detection.
             path.mtu_controller.on_packet_loss(
@@ -1032,6 +1147,18 @@ impl<Config: endpoint::Config> Manager<Config> {
                 path.rtt_estimator.on_persistent_congestion();
             }
 
+            if sent_info.ack_elicitation.is_ack_eliciting()
+                && !sent_info.transmission_mode.is_mtu_probing()
+            {
+                self.lost_packets
+                    .insert_value(packet_number)
+                    .expect("lost_packets has no interval limit");
+
+                while self.lost_packets.interval_len() > MAX_LOST_PACKET_INTERVALS {
+                    self.lost_packets.pop_min();
+                }
+            }
+
             prev_lost_packet_number = Some(packet_number);
         }
 
@@ -1083,6 +1210,16 @@ impl<Config: endpoint::Config> Manager<Config> {
     }
 }
 
+/// Returns the key space of the packet number space if it's only used during the handshake
+#[inline]
+fn handshake_key_space(space: PacketNumberSpace) -> Option<event::builder::KeySpace> {
+    match space {
+        PacketNumberSpace::Initial => Some(event::builder::KeySpace::Initial),
+        PacketNumberSpace::Handshake => Some(event::builder::KeySpace::Handshake),
+        PacketNumberSpace::ApplicationData => None,
+    }
+}
+
 impl<Config: endpoint::Config> timer::Provider for Manager<Config> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
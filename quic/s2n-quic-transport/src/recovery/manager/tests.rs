@@ -3018,6 +3018,55 @@ fn on_timeout_packet_lost() {
     assert!(manager.pto.is_armed());
 }
 
+#[test]
+fn spurious_retransmissions() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = ServerManager::new(space);
+    let now = time::now() + Duration::from_secs(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let ecn = ExplicitCongestionNotification::default();
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let random = &mut random::testing::Generator::default();
+
+    // Remove amplification limits
+    context.path_mut().on_handshake_packet();
+
+    manager.largest_acked_packet = Some(space.new_packet_number(VarInt::from_u8(2)));
+
+    for packet_number in [1, 3] {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 1,
+                bytes_progressed: 0,
+            },
+            now - Duration::from_secs(5),
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Packet 1 is declared lost
+    manager.loss_timer.set(now - Duration::from_secs(5));
+    manager.on_timeout(now, random, u32::MAX, &mut context, &mut publisher);
+    assert_eq!(1, context.on_packet_loss_count);
+    assert_eq!(0, manager.take_spurious_retransmissions());
+
+    // The peer acknowledges packet 1 after it was declared lost
+    ack_packets(1..=3, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(1, manager.take_spurious_retransmissions());
+
+    // Acknowledging the same range again doesn't count the packet twice
+    ack_packets(1..=3, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(0, manager.take_spurious_retransmissions());
+}
+
 // Test that multiple PTO timeouts only doubles the PTO backoff once
 #[test]
 fn max_pto_backoff() {
@@ -3127,14 +3176,14 @@ fn helper_ack_packets_on_path(
             .new_packet_number(VarInt::new(*range.end()).unwrap()),
     );
 
-    let datagram = DatagramInfo {
-        timestamp: ack_receive_time,
-        payload_len: 0,
-        ecn: Default::default(),
-        destination_connection_id: connection::LocalId::TEST_ID,
-        destination_connection_id_classification: connection::id::Classification::Local,
-        source_connection_id: None,
-    };
+    let datagram = DatagramInfo::new(
+        ack_receive_time,
+        0,
+        Default::default(),
+        connection::LocalId::TEST_ID,
+        connection::id::Classification::Local,
+        None,
+    );
 
     let mut ack_range = ack::Ranges::new(acked_packets.count());
 
@@ -3343,14 +3392,14 @@ fn helper_generate_multi_path_manager(
 
     // insert and confirm we have two paths
     {
-        let datagram = DatagramInfo {
-            timestamp: clock.get_time(),
-            payload_len: 0,
-            ecn: ExplicitCongestionNotification::default(),
-            destination_connection_id: connection::LocalId::TEST_ID,
-            destination_connection_id_classification: connection::id::Classification::Local,
-            source_connection_id: None,
-        };
+        let datagram = DatagramInfo::new(
+            clock.get_time(),
+            0,
+            ExplicitCongestionNotification::default(),
+            connection::LocalId::TEST_ID,
+            connection::id::Classification::Local,
+            None,
+        );
         let _ = path_manager
             .on_datagram_received(
                 &second_addr,
@@ -10,26 +10,27 @@ use crate::{
     recovery,
     recovery::CongestionController,
     space::{
-        datagram, keep_alive::KeepAlive, CryptoStream, HandshakeStatus, PacketSpace,
-        TxPacketNumbers,
+        datagram, keep_alive::KeepAlive, transmit_tick::TransmitTick, CryptoStream,
+        HandshakeStatus, PacketSpace, TxPacketNumbers,
     },
     stream::Manager as _,
     sync::flag,
     transmission,
     transmission::interest::Provider,
 };
-use core::{convert::TryInto, fmt, marker::PhantomData};
+use core::{convert::TryInto, fmt, marker::PhantomData, time::Duration};
 use once_cell::sync::OnceCell;
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
+    connection::Statistics,
     counter::{Counter, Saturating},
     crypto::{application::KeySet, limited, tls, CryptoSuite},
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
         ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        NewConnectionId, NewToken, ObservedAddress, PathChallenge, PathResponse, ResetStream,
+        RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::{
@@ -39,7 +40,7 @@ use s2n_quic_core::{
     },
     path::MaxMtu,
     random::Generator,
-    recovery::MAX_BURST_PACKETS,
+    recovery::{ProbePolicy, MAX_BURST_PACKETS},
     time::{timer, Timestamp},
     transport,
 };
@@ -72,11 +73,16 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
 
     ping: flag::Ping,
     keep_alive: KeepAlive,
+    transmit_tick: TransmitTick,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
     pub datagram_manager: datagram::Manager<Config>,
     /// Counter used for detecting an Optimistic Ack attack
     skip_counter: Option<Counter<u32, Saturating>>,
+    /// The statistics recorded since the last call to `take_statistics`
+    statistics: Statistics,
+    /// Selects the contents of the probes sent when the PTO timer expires
+    probe_policy: ProbePolicy,
     /// Keeps track of if the TLS session still exists. If it does, we buffer
     /// the crypto frames received. If not there's no chance that these messages will be read.
     pub buffer_crypto_frames: bool,
@@ -106,6 +112,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         keep_alive: KeepAlive,
         max_mtu: MaxMtu,
         datagram_manager: datagram::Manager<Config>,
+        crypto_stream: CryptoStream,
     ) -> Self {
         let key_set = KeySet::new(key, Self::key_limits(max_mtu));
 
@@ -114,15 +121,18 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             ack_manager,
             spin_bit: SpinBit::Zero,
             stream_manager,
-            crypto_stream: CryptoStream::new(),
+            crypto_stream,
             key_set,
             header_key,
             ping: flag::Ping::default(),
             keep_alive,
+            transmit_tick: TransmitTick::default(),
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
             datagram_manager,
             skip_counter: None,
+            statistics: Statistics::default(),
+            probe_policy: ProbePolicy::default(),
             buffer_crypto_frames: Config::ENDPOINT_TYPE.is_client(),
         }
     }
@@ -206,10 +216,14 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let transmission_mode = context.transmission_mode;
         let min_packet_len = context.min_packet_len;
         let bytes_progressed = self.stream_manager.outgoing_bytes_progressed();
+        // move any previous retransmissions into the statistics so only the ones in this packet
+        // are counted below
+        self.statistics.retransmitted_bytes += self.stream_manager.take_retransmitted_bytes();
 
         let payload = transmission::Transmission {
             config: PhantomData::<Config>,
             outcome: &mut outcome,
+            padding: context.padding,
             packet_number,
             payload: transmission::application::Payload::<Config>::new(
                 context.path_id,
@@ -223,6 +237,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 &mut self.recovery_manager,
                 &mut self.crypto_stream,
                 &mut self.datagram_manager,
+                self.probe_policy,
             ),
             timestamp: context.timestamp,
             transmission_constraint,
@@ -256,6 +271,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         outcome.bytes_progressed +=
             (self.stream_manager.outgoing_bytes_progressed() - bytes_progressed).as_u64() as usize;
+        let retransmitted_bytes = self.stream_manager.take_retransmitted_bytes();
+        self.statistics.retransmitted_bytes += retransmitted_bytes;
 
         self.on_packet_sent(
             context,
@@ -265,6 +282,20 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             skipped_packet_number,
         );
 
+        if transmission_mode.is_loss_recovery_probing() {
+            context
+                .publisher
+                .on_pto_probe_sent(event::builder::PtoProbeSent {
+                    packet_header: event::builder::PacketHeader::new(
+                        packet_number,
+                        context.publisher.quic_version(),
+                    ),
+                    policy: self.probe_policy.into_event(),
+                    new_stream_bytes: outcome.bytes_progressed as u64,
+                    retransmitted_stream_bytes: retransmitted_bytes,
+                });
+        }
+
         Ok((outcome, buffer))
     }
 
@@ -300,6 +331,11 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             self.keep_alive.reset(context.timestamp);
         }
 
+        self.statistics.application_packets_sent += 1;
+        self.statistics.application_packet_bytes_sent += outcome.bytes_sent as u64;
+        self.statistics.application_packet_capacity +=
+            context.path().max_datagram_size(context.transmission_mode) as u64;
+
         // Decrement the skip_counter on each transmit
         if let Some(skip_counter) = &mut self.skip_counter {
             *skip_counter -= 1_u32;
@@ -315,6 +351,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 packet_len: outcome.bytes_sent,
             });
 
+        self.stream_manager.publish_marker_events(context.publisher);
+
         if let Some(skip_packet_number) = skipped_packet_number.pto {
             Self::packet_skipped_event(
                 context,
@@ -377,6 +415,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let payload = transmission::Transmission {
             config: PhantomData::<Config>,
             outcome: &mut outcome,
+            padding: context.padding,
             packet_number,
             payload: transmission::connection_close::Payload {
                 connection_close,
@@ -511,6 +550,12 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             // send a ping after timing out
             self.ping();
         }
+
+        if self.transmit_tick.on_timeout(timestamp).is_ready() {
+            // flush any ACKs which would otherwise be delayed so they are sent along with the
+            // data produced for the current tick
+            self.ack_manager.flush();
+        }
     }
 
     fn arm_skip_counter(
@@ -539,6 +584,16 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.recovery_manager.requires_probe()
     }
 
+    /// Returns the number of spurious retransmissions detected since the last call
+    pub fn take_spurious_retransmissions(&mut self) -> u64 {
+        self.recovery_manager.take_spurious_retransmissions()
+    }
+
+    /// Returns the packet fill counters recorded since the last call
+    pub fn take_statistics(&mut self) -> Statistics {
+        core::mem::take(&mut self.statistics)
+    }
+
     pub fn ping(&mut self) {
         self.ping.send()
     }
@@ -547,6 +602,37 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.keep_alive.update(enabled);
     }
 
+    /// Releases any transient buffers while the connection is hibernating
+    ///
+    /// The buffers are allocated again as traffic resumes.
+    pub fn hibernate(&mut self) {
+        self.ack_manager.hibernate();
+        self.recovery_manager.hibernate();
+        self.stream_manager.hibernate();
+        self.crypto_stream.hibernate();
+    }
+
+    pub fn set_transmit_tick(&mut self, period: Option<Duration>) {
+        self.transmit_tick.update(period);
+    }
+
+    pub fn set_tx_coalescing(&mut self, delay: Option<Duration>) {
+        self.stream_manager.set_tx_coalescing(delay);
+    }
+
+    pub fn set_probe_policy(&mut self, policy: ProbePolicy) {
+        self.probe_policy = policy;
+    }
+
+    /// Called when the connection is woken up by the application
+    pub fn on_wakeup(&mut self, timestamp: Timestamp, path: &Path<Config>) {
+        self.transmit_tick.arm(timestamp);
+        self.stream_manager.on_wakeup(
+            timestamp,
+            path.max_datagram_size(transmission::Mode::Normal),
+        );
+    }
+
     /// Returns the Packet Number to be used when encoding outgoing packets
     fn packet_number_encoder(&self) -> PacketNumber {
         self.tx_packet_numbers.largest_sent_packet_number_acked()
@@ -656,7 +742,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         // We perform decryption prior to checking for duplicate to avoid short-circuiting
         // and maintain constant-time operation.
         if self.is_duplicate(packet_number, path_id, path, publisher) {
-            return Err(ProcessingError::Other);
+            return Err(ProcessingError::DuplicatePacket);
         }
 
         if decrypted.is_ok() {
@@ -707,6 +793,7 @@ impl<Config: endpoint::Config> timer::Provider for ApplicationSpace<Config> {
         self.key_set.timers(query)?;
         self.stream_manager.timers(query)?;
         self.keep_alive.timers(query)?;
+        self.transmit_tick.timers(query)?;
 
         Ok(())
     }
@@ -804,6 +891,8 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.crypto_stream.on_packet_ack(packet_number_range);
         self.ping.on_packet_ack(packet_number_range);
         self.stream_manager.on_packet_ack(packet_number_range);
+        self.stream_manager.publish_marker_events(publisher);
+        self.datagram_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
     }
@@ -824,6 +913,8 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
             .on_packet_loss(packet_number_range, publisher);
         self.ping.on_packet_loss(packet_number_range);
         self.stream_manager.on_packet_loss(packet_number_range);
+        self.stream_manager.publish_marker_events(publisher);
+        self.datagram_manager.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
     }
@@ -1078,6 +1169,14 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_observed_address_frame(
+        &mut self,
+        frame: ObservedAddress,
+        path_manager: &mut path::Manager<Config>,
+    ) -> Result<(), transport::Error> {
+        path_manager.on_observed_address(&frame)
+    }
+
     fn handle_path_response_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         frame: PathResponse,
@@ -1086,6 +1185,11 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         handshake_status: &mut HandshakeStatus,
         publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
+        // responses to application probes don't validate any paths
+        if path_manager.probe.on_path_response(frame.data, timestamp) {
+            return Ok(());
+        }
+
         let amplification_outcome = path_manager.on_path_response(&frame, publisher);
         if amplification_outcome.is_active_path_unblocked() {
             self.on_amplification_unblocked(
@@ -1143,6 +1247,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         self.processed_packet_numbers
             .insert(processed_packet.packet_number)
             .expect("packet number was already checked");
+        self.stream_manager.publish_peer_stream_events(publisher);
 
         Ok(())
     }
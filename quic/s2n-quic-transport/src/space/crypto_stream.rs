@@ -74,6 +74,21 @@ impl CryptoStream {
         }
     }
 
+    /// Clears all of the stream state while retaining its buffers
+    ///
+    /// This is used to reuse the stream in a new packet space.
+    pub fn reset(&mut self) {
+        self.tx.reset();
+        self.rx.reset();
+        self.is_finished = false;
+    }
+
+    /// Releases any transient buffers while the connection is hibernating
+    pub fn hibernate(&mut self) {
+        self.tx.shrink_to_fit();
+        self.rx.shrink_to_fit();
+    }
+
     pub fn on_crypto_frame(&mut self, frame: CryptoRef) -> Result<(), transport::Error> {
         //= https://www.rfc-editor.org/rfc/rfc9001#section-4.1.3
         //# *  If the packet is from a previously installed encryption level, it
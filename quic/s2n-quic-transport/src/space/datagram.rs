@@ -9,11 +9,13 @@ use crate::{
         WriteContext,
     },
 };
+use alloc::collections::VecDeque;
 use core::task::Poll;
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{
     datagram::{Endpoint, ReceiveContext, Receiver, Sender, WriteError},
     frame::{self, datagram::DatagramRef},
+    packet::number::{PacketNumber, PacketNumberRange},
     query,
     varint::VarInt,
 };
@@ -26,6 +28,10 @@ pub struct Manager<Config: endpoint::Config> {
     pub sender: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Sender,
     pub receiver: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Receiver,
     max_datagram_payload: u64,
+    /// Packets containing datagrams which haven't been acknowledged or declared lost yet
+    ///
+    /// Packet numbers are monotonically increasing so the queue is always sorted.
+    pending_packets: VecDeque<PacketNumber>,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -38,6 +44,7 @@ impl<Config: endpoint::Config> Manager<Config> {
             sender,
             receiver,
             max_datagram_payload,
+            pending_packets: VecDeque::new(),
         }
     }
 
@@ -48,13 +55,45 @@ impl<Config: endpoint::Config> Manager<Config> {
         stream_manager: &mut Config::StreamManager,
         datagrams_prioritized: bool,
     ) {
+        let packet_number = context.packet_number();
         let mut packet = Packet {
             context,
             has_pending_streams: stream_manager.has_pending_streams(),
             datagrams_prioritized,
             max_datagram_payload: self.max_datagram_payload,
+            has_written: false,
         };
         self.sender.on_transmit(&mut packet);
+
+        if packet.has_written && self.pending_packets.back() != Some(&packet_number) {
+            self.pending_packets.push_back(packet_number);
+        }
+    }
+
+    /// Notifies the sender of any acknowledged packets which contained datagrams
+    pub fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        let sender = &mut self.sender;
+        self.pending_packets.retain(|packet_number| {
+            if packet_number_range.contains(*packet_number) {
+                sender.on_packet_ack(packet_number.as_u64());
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Notifies the sender of any lost packets which contained datagrams
+    pub fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        let sender = &mut self.sender;
+        self.pending_packets.retain(|packet_number| {
+            if packet_number_range.contains(*packet_number) {
+                sender.on_packet_loss(packet_number.as_u64());
+                false
+            } else {
+                true
+            }
+        });
     }
 
     // A callback that allows users to access datagrams directly after they are
@@ -100,6 +139,7 @@ struct Packet<'a, C: WriteContext> {
     has_pending_streams: bool,
     datagrams_prioritized: bool,
     max_datagram_payload: u64,
+    has_written: bool,
 }
 
 impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
@@ -116,6 +156,11 @@ impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
             )
     }
 
+    /// Returns the number of the packet being written
+    fn packet_number(&self) -> u64 {
+        self.context.packet_number().as_u64()
+    }
+
     /// Writes a single datagram to a packet
     fn write_datagram(&mut self, data: &[u8]) -> Result<(), WriteError> {
         self.write_datagram_vectored(&[data])
@@ -137,6 +182,7 @@ impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
         self.context
             .write_frame(&frame)
             .ok_or(WriteError::ExceedsPacketCapacity)?;
+        self.has_written = true;
 
         Ok(())
     }
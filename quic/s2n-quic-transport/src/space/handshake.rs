@@ -62,12 +62,13 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::HandshakeHeaderKey,
         now: Timestamp,
         ack_manager: AckManager,
+        crypto_stream: CryptoStream,
     ) -> Self {
         Self {
             ack_manager,
             key,
             header_key,
-            crypto_stream: CryptoStream::new(),
+            crypto_stream,
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Handshake, now),
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::Handshake),
@@ -132,6 +133,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
         let payload = transmission::Transmission {
             config: PhantomData::<Config>,
             outcome: &mut outcome,
+            padding: context.padding,
             packet_number,
             payload: transmission::early::Payload {
                 ack_manager: &mut self.ack_manager,
@@ -220,6 +222,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
         let payload = transmission::Transmission {
             config: PhantomData::<Config>,
             outcome: &mut outcome,
+            padding: context.padding,
             packet_number,
             payload: transmission::connection_close::Payload {
                 connection_close,
@@ -320,6 +323,11 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
         self.recovery_manager.requires_probe()
     }
 
+    /// Returns the number of spurious retransmissions detected since the last call
+    pub fn take_spurious_retransmissions(&mut self) -> u64 {
+        self.recovery_manager.take_spurious_retransmissions()
+    }
+
     /// Returns the Packet Number to be used when decoding incoming packets
     pub fn packet_number_decoder(&self) -> PacketNumber {
         self.ack_manager.largest_received_packet_number_acked()
@@ -376,7 +384,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
             })?;
 
         if self.is_duplicate(packet.packet_number, path_id, path, publisher) {
-            return Err(ProcessingError::Other);
+            return Err(ProcessingError::DuplicatePacket);
         }
 
         let packet_header =
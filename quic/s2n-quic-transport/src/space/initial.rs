@@ -11,11 +11,13 @@ use crate::{
     space::{CryptoStream, HandshakeStatus, PacketSpace, TxPacketNumbers},
     transmission,
 };
-use core::{fmt, marker::PhantomData};
+use alloc::sync::Arc;
+use core::{fmt, marker::PhantomData, time::Duration};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
     connection::PeerId,
     crypto::{tls, CryptoSuite, InitialKey},
+    endpoint::challenge,
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{ack::AckRanges, crypto::CryptoRef, Ack, ConnectionClose},
     inet::DatagramInfo,
@@ -26,6 +28,7 @@ use s2n_quic_core::{
     },
     time::{timer, Timestamp},
     transport,
+    varint::VarInt,
 };
 use smallvec::SmallVec;
 
@@ -44,10 +47,24 @@ pub struct InitialSpace<Config: endpoint::Config> {
     pub crypto_stream: CryptoStream,
     pub tx_packet_numbers: TxPacketNumbers,
     pub received_hello_message: bool,
+    /// The number of datagrams which have carried the ClientHello
+    ///
+    /// This is only tracked by clients until the ClientHello has been transmitted once.
+    client_hello_datagrams: Option<u16>,
     //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.3
     //# Subsequent Initial packets from the client include the connection ID
     //# and token values from the Retry packet.
     retry_token: Vec<u8>,
+    /// Solves challenges delivered in Retry tokens
+    ///
+    /// This is only set by clients which were configured with a solver.
+    challenge_solver: Option<Arc<dyn challenge::Solver>>,
+    /// The number of times the client has transmitted its first flight
+    first_flight_transmissions: u32,
+    /// The number of first flight transmissions after which the client gives up on the server
+    ///
+    /// This is only set by clients and is cleared once the server responds.
+    max_first_flight_transmissions: Option<u32>,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
 }
@@ -69,20 +86,53 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
         now: Timestamp,
         ack_manager: AckManager,
+        crypto_stream: CryptoStream,
     ) -> Self {
         Self {
             ack_manager,
             key,
             header_key,
-            crypto_stream: CryptoStream::new(),
+            crypto_stream,
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Initial, now),
             received_hello_message: false,
+            client_hello_datagrams: Config::ENDPOINT_TYPE.is_client().then_some(0),
             retry_token: Vec::new(),
+            challenge_solver: None,
+            first_flight_transmissions: 1,
+            max_first_flight_transmissions: None,
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::Initial),
         }
     }
 
+    /// Sets the solver for challenges delivered in Retry tokens
+    pub fn set_challenge_solver(&mut self, solver: Arc<dyn challenge::Solver>) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.challenge_solver = Some(solver);
+    }
+
+    /// Sets how the client retransmits its first flight before the server responds
+    ///
+    /// The `probe_timeout` replaces the PTO period derived from the initial RTT and the client
+    /// gives up on the server after `max_transmissions` of the first flight.
+    pub fn set_first_flight_limits(
+        &mut self,
+        probe_timeout: Option<Duration>,
+        max_transmissions: Option<u32>,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.recovery_manager.set_pto_period_override(probe_timeout);
+        self.max_first_flight_transmissions = max_transmissions;
+    }
+
+    /// Called when the server responds to the first flight
+    #[inline]
+    fn on_first_flight_response(&mut self) {
+        if self.max_first_flight_transmissions.take().is_some() {
+            self.recovery_manager.set_pto_period_override(None);
+        }
+    }
+
     /// This method gets called when a Retry packet is processed.
     ///
     /// Reset the TLS stack and recover state when the first Retry packet is processed.
@@ -96,7 +146,14 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         publisher: &mut Pub,
     ) {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
-        self.retry_token = retry_token.to_vec();
+
+        // respond to the challenge if the server sent one and the application can solve it;
+        // otherwise the token is echoed back as-is and the server will drop the attempt
+        let response = self
+            .challenge_solver
+            .as_deref()
+            .and_then(|solver| challenge::Challenge::decode(retry_token)?.respond(solver));
+        self.retry_token = response.unwrap_or_else(|| retry_token.to_vec());
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.2
         //# Changing the Destination Connection ID field also results in
@@ -120,6 +177,9 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         // might have been sent/lost.
         self.recovery_manager
             .on_retry_packet(path, path_id, publisher);
+
+        // the server responded with the Retry so it's no longer a first flight
+        self.on_first_flight_response();
     }
 
     /// Returns true if the packet number has already been processed
@@ -175,11 +235,13 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
 
         let packet_number_encoder = self.packet_number_encoder();
         let mut outcome = transmission::Outcome::default();
+        let transmission_offset = self.crypto_stream.tx.transmission_offset();
 
         let destination_connection_id = context.path().peer_connection_id;
         let payload = transmission::Transmission {
             config: PhantomData::<Config>,
             outcome: &mut outcome,
+            padding: context.padding,
             packet_number,
             payload: transmission::early::Payload {
                 ack_manager: &mut self.ack_manager,
@@ -238,9 +300,44 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
                 packet_len: outcome.bytes_sent,
             });
 
+        self.on_client_hello_transmit(transmission_offset, context.publisher);
+
         Ok((outcome, buffer))
     }
 
+    /// Reports the ClientHello as fragmented once all of it has been transmitted across more than
+    /// one datagram
+    fn on_client_hello_transmit<Pub: event::ConnectionPublisher>(
+        &mut self,
+        prev_transmission_offset: VarInt,
+        publisher: &mut Pub,
+    ) {
+        let Some(datagrams) = self.client_hello_datagrams.as_mut() else {
+            return;
+        };
+
+        let tx = &self.crypto_stream.tx;
+
+        // only count packets which carried new ClientHello data
+        if tx.transmission_offset() > prev_transmission_offset {
+            *datagrams = datagrams.saturating_add(1);
+        }
+
+        if *datagrams == 0 || tx.unsent_len() > VarInt::from_u8(0) {
+            return;
+        }
+
+        let datagram_count = *datagrams;
+        self.client_hello_datagrams = None;
+
+        if datagram_count > 1 {
+            publisher.on_client_hello_fragmented(event::builder::ClientHelloFragmented {
+                len: tx.total_enqueued_len().as_u64(),
+                datagram_count,
+            });
+        }
+    }
+
     pub(super) fn on_transmit_burst_complete(
         &mut self,
         active_path: &Path<Config>,
@@ -269,6 +366,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         let payload = transmission::Transmission {
             config: PhantomData::<Config>,
             outcome: &mut outcome,
+            padding: context.padding,
             packet_number,
             payload: transmission::connection_close::Payload {
                 connection_close,
@@ -324,18 +422,32 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         timestamp: Timestamp,
         max_pto_backoff: u32,
         publisher: &mut Pub,
-    ) {
+    ) -> Result<(), connection::Error> {
         self.ack_manager.on_timeout(timestamp);
 
         let (recovery_manager, mut context) =
             self.recovery(handshake_status, path_id, path_manager);
-        recovery_manager.on_timeout(
+        let pto_expired = recovery_manager.on_timeout(
             timestamp,
             random_generator,
             max_pto_backoff,
             &mut context,
             publisher,
         );
+
+        if let (true, Some(max_transmissions)) = (pto_expired, self.max_first_flight_transmissions)
+        {
+            if self.first_flight_transmissions >= max_transmissions {
+                return Err(connection::Error::no_server_response(
+                    self.first_flight_transmissions,
+                ));
+            }
+
+            // the PTO retransmits the first flight
+            self.first_flight_transmissions += 1;
+        }
+
+        Ok(())
     }
 
     /// Called before the Initial packet space is discarded
@@ -356,6 +468,11 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         self.recovery_manager.requires_probe()
     }
 
+    /// Returns the number of spurious retransmissions detected since the last call
+    pub fn take_spurious_retransmissions(&mut self) -> u64 {
+        self.recovery_manager.take_spurious_retransmissions()
+    }
+
     /// Returns the Packet Number to be used when decoding incoming packets
     pub fn packet_number_decoder(&self) -> PacketNumber {
         self.ack_manager.largest_received_packet_number_acked()
@@ -411,7 +528,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             })?;
 
         if self.is_duplicate(packet.packet_number, path_id, path, publisher) {
-            return Err(ProcessingError::Other);
+            return Err(ProcessingError::DuplicatePacket);
         }
 
         let packet_header =
@@ -742,6 +859,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for InitialSpace<Config> {
         self.processed_packet_numbers
             .insert(processed_packet.packet_number)
             .expect("packet number was already checked");
+        self.on_first_flight_response();
         Ok(())
     }
 }
@@ -12,7 +12,15 @@ pub struct KeepAlive {
 }
 
 impl KeepAlive {
-    pub fn new(max_idle_timeout: Option<Duration>, max_period: Duration) -> Self {
+    pub fn new(
+        max_idle_timeout: Option<Duration>,
+        max_period: Duration,
+        period: Option<Duration>,
+    ) -> Self {
+        // if the application configured a period, keep-alive is enabled from the start
+        let enabled = period.is_some();
+        let max_period = period.unwrap_or(max_period);
+
         let period = if let Some(max_idle_timeout) = max_idle_timeout {
             // send a ping frame at 3/4 max idle timeout to ensure it is delivered in time
             (max_idle_timeout * 3 / 4).min(max_period)
@@ -32,7 +40,7 @@ impl KeepAlive {
         };
 
         Self {
-            enabled: false,
+            enabled,
             period,
             timer: Timer::default(),
         }
@@ -2,9 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack,
-    ack::AckManager,
-    connection, endpoint, path,
+    ack, connection, endpoint, path,
     path::{path_event, Path},
     processed_packet::ProcessedPacket,
     stream::Manager as _,
@@ -19,14 +17,14 @@ use core::{
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application::ServerName,
-    connection::{limits::Limits, InitialId, PeerId},
+    connection::{limits::Limits, ChannelBinding, Fingerprint, InitialId, PeerId, Statistics},
     crypto::{tls, tls::Session, CryptoSuite, Key},
     event::{self, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
         ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        NewConnectionId, NewToken, ObservedAddress, PathChallenge, PathResponse, ResetStream,
+        RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
@@ -42,7 +40,9 @@ mod handshake;
 mod handshake_status;
 mod initial;
 mod keep_alive;
+mod pool;
 mod session_context;
+mod transmit_tick;
 mod tx_packet_numbers;
 
 pub(crate) use application::ApplicationSpace;
@@ -50,6 +50,7 @@ pub(crate) use crypto_stream::CryptoStream;
 pub(crate) use handshake::HandshakeSpace;
 pub(crate) use handshake_status::HandshakeStatus;
 pub(crate) use initial::InitialSpace;
+pub(crate) use pool::Pool;
 pub(crate) use session_context::SessionContext;
 pub(crate) use tx_packet_numbers::TxPacketNumbers;
 
@@ -67,6 +68,8 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     zero_rtt_crypto:
         Option<Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>>,
     handshake_status: HandshakeStatus,
+    /// Recycles the buffers of discarded packet spaces
+    pool: Pool,
     /// Server Name Indication
     pub server_name: Option<ServerName>,
     //= https://www.rfc-editor.org/rfc/rfc9000#section-7
@@ -77,6 +80,12 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     //# another mechanism is used for agreeing on an application protocol,
     //# endpoints MUST use ALPN for this purpose.
     pub application_protocol: Bytes,
+    /// Set if the TLS session was resumed from a session ticket
+    pub is_resumed: bool,
+    /// Identifies the connection to both peers once the TLS exporter is ready
+    pub fingerprint: Option<Fingerprint>,
+    /// The RFC 9266 `tls-exporter` channel binding, once the TLS exporter is ready
+    pub channel_binding: Option<ChannelBinding>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for PacketSpaceManager<Config> {
@@ -90,6 +99,25 @@ impl<Config: endpoint::Config> fmt::Debug for PacketSpaceManager<Config> {
     }
 }
 
+impl<Config: endpoint::Config> Drop for PacketSpaceManager<Config> {
+    fn drop(&mut self) {
+        if let Some(space) = self.initial.as_mut() {
+            self.pool
+                .recycle(&mut space.crypto_stream, &mut space.ack_manager);
+        }
+
+        if let Some(space) = self.handshake.as_mut() {
+            self.pool
+                .recycle(&mut space.crypto_stream, &mut space.ack_manager);
+        }
+
+        if let Some(space) = self.application.as_mut() {
+            self.pool
+                .recycle(&mut space.crypto_stream, &mut space.ack_manager);
+        }
+    }
+}
+
 macro_rules! packet_space_api {
     ($ty:ty, $field:ident, $get_mut:ident) => {
         #[allow(dead_code)]
@@ -111,9 +139,11 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         initial_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
         now: Timestamp,
+        pool: Pool,
         publisher: &mut Pub,
     ) -> Self {
-        let ack_manager = AckManager::new(PacketNumberSpace::Initial, ack::Settings::EARLY);
+        let ack_manager = pool.ack_manager(PacketNumberSpace::Initial, ack::Settings::EARLY);
+        let crypto_stream = pool.crypto_stream();
 
         publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::Initial,
@@ -130,13 +160,18 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 header_key,
                 now,
                 ack_manager,
+                crypto_stream,
             ))),
             handshake: None,
             application: None,
             zero_rtt_crypto: None,
             handshake_status: HandshakeStatus::default(),
+            pool,
             server_name: None,
             application_protocol: Bytes::new(),
+            is_resumed: false,
+            fingerprint: None,
+            channel_binding: None,
         }
     }
 
@@ -169,6 +204,8 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             path_manager.active_path_mut().reset_pto_backoff();
             let path_id = path_manager.active_path_id();
             space.on_discard(path_manager.active_path_mut(), path_id, publisher);
+            self.pool
+                .recycle(&mut space.crypto_stream, &mut space.ack_manager);
 
             if let Some((handshake, handshake_status)) = self.handshake_mut() {
                 //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
@@ -215,6 +252,8 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             path_manager.active_path_mut().reset_pto_backoff();
             let path_id = path_manager.active_path_id();
             space.on_discard(path_manager.active_path_mut(), path_id, publisher);
+            self.pool
+                .recycle(&mut space.crypto_stream, &mut space.ack_manager);
             // Dropping handshake will clear the PTO timer for the handshake space.
             // The PTO timer for the application space is reset when the
             // handshake is confirmed.
@@ -264,9 +303,13 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 limits,
                 server_name: &mut self.server_name,
                 application_protocol: &mut self.application_protocol,
+                is_resumed: &mut self.is_resumed,
+                fingerprint: &mut self.fingerprint,
+                channel_binding: &mut self.channel_binding,
                 waker,
                 publisher,
                 datagram,
+                pool: &self.pool,
             };
 
             match session_info.session.poll(&mut context)? {
@@ -310,9 +353,13 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 limits,
                 server_name: &mut self.server_name,
                 application_protocol: &mut self.application_protocol,
+                is_resumed: &mut self.is_resumed,
+                fingerprint: &mut self.fingerprint,
+                channel_binding: &mut self.channel_binding,
                 waker,
                 publisher,
                 datagram,
+                pool: &self.pool,
             };
 
             session_info
@@ -342,7 +389,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         random_generator: &mut Config::RandomGenerator,
         timestamp: Timestamp,
         publisher: &mut Pub,
-    ) {
+    ) -> Result<(), connection::Error> {
         let path_id = path_manager.active_path_id();
         let path = path_manager.active_path();
 
@@ -358,7 +405,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 timestamp,
                 max_backoff,
                 publisher,
-            )
+            )?;
         }
         if let Some((space, handshake_status)) = self.handshake_mut() {
             space.on_timeout(
@@ -384,6 +431,8 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         }
 
         debug_assert!(path_manager.active_path().pto_backoff <= max_backoff);
+
+        Ok(())
     }
 
     /// Signals the connection was previously blocked by anti-amplification limits
@@ -447,6 +496,27 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         }
     }
 
+    /// Returns the statistics which were recorded by the packet spaces since the last call
+    pub fn take_statistics(&mut self) -> Statistics {
+        let mut statistics = Statistics::default();
+
+        if let Some((space, _)) = self.initial_mut() {
+            statistics.spurious_retransmissions += space.take_spurious_retransmissions();
+        }
+
+        if let Some((space, _)) = self.handshake_mut() {
+            statistics.spurious_retransmissions += space.take_spurious_retransmissions();
+        }
+
+        if let Some((space, _)) = self.application_mut() {
+            statistics += space.take_statistics();
+            statistics.spurious_retransmissions += space.take_spurious_retransmissions();
+            statistics.retransmitted_bytes += space.stream_manager.take_retransmitted_bytes();
+        }
+
+        statistics
+    }
+
     pub fn requires_probe(&self) -> bool {
         core::iter::empty()
             .chain(self.initial.iter().map(|space| space.requires_probe()))
@@ -785,6 +855,16 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
             .with_frame_type(frame.tag().into()))
     }
 
+    fn handle_observed_address_frame(
+        &mut self,
+        frame: ObservedAddress,
+        _path_manager: &mut path::Manager<Config>,
+    ) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag()))
+    }
+
     default_frame_handler!(handle_data_blocked_frame, DataBlocked);
     default_frame_handler!(handle_max_data_frame, MaxData);
     default_frame_handler!(handle_max_stream_data_frame, MaxStreamData);
@@ -838,9 +918,9 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
 
         macro_rules! on_frame_processed {
             ($frame:ident) => {{
-                let frame_type = $frame.tag();
+                let frame_type: VarInt = $frame.tag().into();
                 processed_packet.on_processed_frame(&$frame);
-                move |err: transport::Error| err.with_frame_type(VarInt::from_u8(frame_type))
+                move |err: transport::Error| err.with_frame_type(frame_type)
             }};
         }
 
@@ -1030,6 +1110,11 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     )
                     .map_err(on_error)?;
                 }
+                Frame::ObservedAddress(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_observed_address_frame(frame, path_manager)
+                        .map_err(on_error)?;
+                }
                 Frame::HandshakeDone(frame) => {
                     let on_error = on_frame_processed!(frame);
                     self.handle_handshake_done_frame(
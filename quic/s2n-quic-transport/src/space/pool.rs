@@ -0,0 +1,144 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ack::AckManager, space::CryptoStream};
+use alloc::sync::Arc;
+use s2n_quic_core::{ack, packet::number::PacketNumberSpace};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Recycles the buffers of discarded packet spaces between the connections on an endpoint
+///
+/// Each packet space owns a crypto stream and a set of ACK ranges which grow as the connection
+/// progresses. Rather than releasing them when the space is discarded, they are cleared and
+/// handed to the next packet space which is created on the endpoint.
+///
+/// A pool with a capacity of `0` is disabled and doesn't allocate any shared state.
+#[derive(Clone, Debug, Default)]
+pub struct Pool(Option<Arc<Mutex<State>>>);
+
+#[derive(Debug)]
+struct State {
+    capacity: usize,
+    crypto_streams: Vec<CryptoStream>,
+    ack_ranges: Vec<ack::Ranges>,
+}
+
+impl Pool {
+    /// Creates a pool which holds up to `capacity` of each kind of buffer
+    pub fn new(capacity: usize) -> Self {
+        if capacity == 0 {
+            return Self(None);
+        }
+
+        let state = State {
+            capacity,
+            crypto_streams: Vec::new(),
+            ack_ranges: Vec::new(),
+        };
+
+        Self(Some(Arc::new(Mutex::new(state))))
+    }
+
+    /// Returns a crypto stream for a new packet space
+    #[inline]
+    pub fn crypto_stream(&self) -> CryptoStream {
+        self.state()
+            .and_then(|mut state| state.crypto_streams.pop())
+            .unwrap_or_default()
+    }
+
+    /// Returns an `AckManager` for a new packet space
+    #[inline]
+    pub fn ack_manager(
+        &self,
+        packet_space: PacketNumberSpace,
+        ack_settings: ack::Settings,
+    ) -> AckManager {
+        let ack_manager = AckManager::new(packet_space, ack_settings);
+
+        match self.state().and_then(|mut state| state.ack_ranges.pop()) {
+            Some(ack_ranges) => ack_manager.with_ack_ranges(ack_ranges),
+            None => ack_manager,
+        }
+    }
+
+    /// Returns the buffers of a discarded packet space to the pool
+    ///
+    /// The buffers are released instead if the pool is already full.
+    #[inline]
+    pub fn recycle(&self, crypto_stream: &mut CryptoStream, ack_manager: &mut AckManager) {
+        let Some(mut state) = self.state() else {
+            return;
+        };
+
+        if state.crypto_streams.len() < state.capacity {
+            let mut crypto_stream = core::mem::take(crypto_stream);
+            crypto_stream.reset();
+            state.crypto_streams.push(crypto_stream);
+        }
+
+        if state.ack_ranges.len() < state.capacity {
+            state.ack_ranges.push(ack_manager.take_ack_ranges());
+        }
+    }
+
+    #[inline]
+    fn state(&self) -> Option<MutexGuard<State>> {
+        // the state is never left inconsistent so a poisoned lock can still be used
+        let state = self.0.as_ref()?;
+        Some(state.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::varint::VarInt;
+
+    /// Returns an `AckManager` whose ranges have allocated room for a few intervals
+    fn ack_manager(pool: &Pool) -> AckManager {
+        let space = PacketNumberSpace::Initial;
+        let mut ack_ranges = ack::Ranges::default();
+        for pn in [0u8, 2, 4, 6] {
+            let pn = space.new_packet_number(VarInt::from_u8(pn));
+            ack_ranges.insert_packet_number(pn).unwrap();
+        }
+        pool.ack_manager(space, ack::Settings::EARLY)
+            .with_ack_ranges(ack_ranges)
+    }
+
+    #[test]
+    fn recycle_test() {
+        let pool = Pool::new(1);
+
+        let mut crypto_stream = pool.crypto_stream();
+        crypto_stream.tx.push(bytes::Bytes::from_static(&[1, 2, 3]));
+        let mut ack_manager = ack_manager(&pool);
+        pool.recycle(&mut crypto_stream, &mut ack_manager);
+
+        // the recycled buffers are cleared before they're reused
+        assert!(pool.crypto_stream().tx.is_empty());
+        let mut ack_manager = pool.ack_manager(PacketNumberSpace::Handshake, ack::Settings::EARLY);
+        let ack_ranges = ack_manager.take_ack_ranges();
+        assert!(ack_ranges.is_empty());
+        assert!(ack_ranges.capacity() >= 4);
+
+        // the pool is now empty
+        let mut ack_manager = pool.ack_manager(PacketNumberSpace::Handshake, ack::Settings::EARLY);
+        assert_eq!(ack_manager.take_ack_ranges().capacity(), 0);
+    }
+
+    #[test]
+    fn disabled_test() {
+        let pool = Pool::new(0);
+
+        let mut crypto_stream = pool.crypto_stream();
+        let mut ack_manager = ack_manager(&pool);
+        pool.recycle(&mut crypto_stream, &mut ack_manager);
+
+        // nothing is retained
+        assert!(ack_manager.take_ack_ranges().capacity() >= 4);
+        let mut ack_manager = pool.ack_manager(PacketNumberSpace::Initial, ack::Settings::EARLY);
+        assert_eq!(ack_manager.take_ack_ranges().capacity(), 0);
+    }
+}
@@ -2,12 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
     connection::{self, limits::Limits},
     endpoint, path,
     space::{
         datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
-        InitialSpace,
+        InitialSpace, Pool,
     },
     stream,
 };
@@ -17,7 +16,7 @@ use s2n_codec::{DecoderBuffer, DecoderValue};
 use s2n_quic_core::{
     ack,
     application::ServerName,
-    connection::{InitialId, PeerId},
+    connection::{ChannelBinding, Fingerprint, InitialId, PeerId},
     crypto,
     crypto::{tls, CryptoSuite, Key},
     ct::ConstantTimeEq,
@@ -52,9 +51,13 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
     pub limits: &'a mut Limits,
     pub server_name: &'a mut Option<ServerName>,
     pub application_protocol: &'a mut Bytes,
+    pub is_resumed: &'a mut bool,
+    pub fingerprint: &'a mut Option<Fingerprint>,
+    pub channel_binding: &'a mut Option<ChannelBinding>,
     pub waker: &'a Waker,
     pub publisher: &'a mut Pub,
     pub datagram: &'a mut Config::DatagramEndpoint,
+    pub pool: &'a Pool,
 }
 
 impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
@@ -136,6 +139,11 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             (None, None) => {}
         }
 
+        endpoint::version::validate_version_information(
+            self.publisher.quic_version(),
+            peer_parameters.version_information.as_ref(),
+        )?;
+
         if let Some(peer_value) = peer_parameters.original_destination_connection_id {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
             //# The values provided by a peer for these transport parameters MUST
@@ -179,6 +187,13 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
+        let remote_address = self.path_manager.active_path().remote_address().0;
+        self.path_manager.observed_address.on_transport_parameters(
+            self.limits.address_discovery(),
+            peer_parameters.address_discovery,
+            &remote_address,
+        );
+
         let initial_flow_control_limits = peer_parameters.flow_control_limits();
         let active_connection_id_limit = peer_parameters.active_connection_id_limit;
         let datagram_limits = peer_parameters.datagram_limits();
@@ -234,9 +249,21 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .as_bytes(),
         )?;
 
+        endpoint::version::validate_version_information(
+            self.publisher.quic_version(),
+            peer_parameters.version_information.as_ref(),
+        )?;
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
+        let remote_address = self.path_manager.active_path().remote_address().0;
+        self.path_manager.observed_address.on_transport_parameters(
+            self.limits.address_discovery(),
+            peer_parameters.address_discovery,
+            &remote_address,
+        );
+
         let initial_flow_control_limits = peer_parameters.flow_control_limits();
         let active_connection_id_limit = peer_parameters.active_connection_id_limit;
         let datagram_limits = peer_parameters.datagram_limits();
@@ -313,7 +340,9 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             space.crypto_stream.finish()?;
         }
 
-        let ack_manager = AckManager::new(PacketNumberSpace::Handshake, ack::Settings::EARLY);
+        let ack_manager = self
+            .pool
+            .ack_manager(PacketNumberSpace::Handshake, ack::Settings::EARLY);
 
         let cipher_suite = key.cipher_suite().into_event();
         *self.handshake = Some(Box::new(HandshakeSpace::new(
@@ -321,6 +350,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             header_key,
             self.now,
             ack_manager,
+            self.pool.crypto_stream(),
         )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::Handshake,
@@ -390,7 +420,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             self.path_manager.active_path().rtt_estimator.min_rtt(),
         );
 
-        let ack_manager = AckManager::new(
+        let ack_manager = self.pool.ack_manager(
             PacketNumberSpace::ApplicationData,
             self.limits.ack_settings(),
         );
@@ -398,6 +428,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         let keep_alive = KeepAlive::new(
             self.limits.max_idle_timeout(),
             self.limits.max_keep_alive_period(),
+            self.limits.keep_alive_period(),
         );
 
         let conn_info =
@@ -425,6 +456,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             keep_alive,
             max_mtu,
             datagram_manager,
+            self.pool.crypto_stream(),
         )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::OneRtt { generation: 0 },
@@ -439,6 +471,17 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             .on_server_name_information(event::builder::ServerNameInformation {
                 chosen_server_name: &server_name,
             });
+
+        // servers create the connection context before the ClientHello is processed, so the
+        // server name wasn't available to the subscriber until now
+        if Config::ENDPOINT_TYPE.is_server() {
+            let remote_address = self.path_manager.active_path().remote_address().0;
+            self.publisher
+                .on_connection_info_updated(event::builder::ConnectionInfo {
+                    remote_address: (&remote_address).into_event(),
+                    server_name: Some(server_name.as_str()),
+                });
+        }
         *self.server_name = Some(server_name);
 
         Ok(())
@@ -458,10 +501,30 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_resumption_status(
+        &mut self,
+        status: tls::ResumptionStatus,
+    ) -> Result<(), transport::Error> {
+        *self.is_resumed = matches!(status, tls::ResumptionStatus::Accepted);
+
+        let status = match status {
+            tls::ResumptionStatus::Attempted => event::builder::SessionResumptionStatus::Attempted,
+            tls::ResumptionStatus::Accepted => event::builder::SessionResumptionStatus::Accepted,
+            tls::ResumptionStatus::Rejected => event::builder::SessionResumptionStatus::Rejected,
+        };
+        self.publisher
+            .on_session_resumption(event::builder::SessionResumption { status });
+
+        Ok(())
+    }
+
     fn on_tls_exporter_ready(
         &mut self,
         session: &impl tls::TlsSession,
     ) -> Result<(), transport::Error> {
+        *self.fingerprint = Fingerprint::new(session, self.initial_cid.as_bytes());
+        *self.channel_binding = ChannelBinding::new(session);
+
         self.publisher
             .on_tls_exporter_ready(event::builder::TlsExporterReady {
                 session: s2n_quic_core::event::TlsSession::new(session),
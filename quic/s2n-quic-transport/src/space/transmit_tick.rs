@@ -0,0 +1,91 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::{task::Poll, time::Duration};
+use s2n_quic_core::time::{timer, Timer, Timestamp};
+
+/// Periodically flushes pending transmissions at a fixed cadence
+///
+/// This is intended for real-time traffic, e.g. audio or video frames, which is produced at a
+/// regular interval and shouldn't wait on any transmission batching.
+#[derive(Debug, Default)]
+pub struct TransmitTick {
+    period: Option<Duration>,
+    timer: Timer,
+}
+
+impl TransmitTick {
+    /// Sets the period of the tick, or disables it with `None`
+    ///
+    /// The tick starts on the next call to `arm`.
+    #[inline]
+    pub fn update(&mut self, period: Option<Duration>) {
+        self.period = period.filter(|period| !period.is_zero());
+        self.timer.cancel();
+    }
+
+    /// Starts the tick if it is enabled and not already running
+    #[inline]
+    pub fn arm(&mut self, now: Timestamp) {
+        if let Some(period) = self.period {
+            if !self.timer.is_armed() {
+                self.timer.set(now + period);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn on_timeout(&mut self, now: Timestamp) -> Poll<()> {
+        let res = self.timer.poll_expiration(now);
+
+        if res.is_ready() {
+            if let Some(period) = self.period {
+                // schedule the next tick relative to the current time so a connection which
+                // was blocked for several periods doesn't fire a burst of ticks
+                self.timer.set(now + period);
+            }
+        }
+
+        res
+    }
+}
+
+impl timer::Provider for TransmitTick {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{Clock, NoopClock};
+
+    #[test]
+    fn transmit_tick_test() {
+        let now = NoopClock {}.get_time();
+        let period = Duration::from_millis(20);
+        let mut tick = TransmitTick::default();
+
+        // the tick is disabled by default
+        tick.arm(now);
+        assert!(tick.on_timeout(now + period).is_pending());
+
+        tick.update(Some(period));
+        assert!(tick.on_timeout(now + period).is_pending());
+        tick.arm(now);
+        assert!(tick.on_timeout(now + period / 2).is_pending());
+        assert!(tick.on_timeout(now + period).is_ready());
+        // the tick is rescheduled for the next period
+        assert!(tick.on_timeout(now + period).is_pending());
+        assert!(tick.on_timeout(now + period * 2).is_ready());
+
+        // a zero period disables the tick
+        tick.update(Some(Duration::ZERO));
+        assert_eq!(tick.period, None);
+        tick.arm(now);
+        assert!(tick.on_timeout(now + period * 10).is_pending());
+    }
+}
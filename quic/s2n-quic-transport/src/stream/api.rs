@@ -22,6 +22,13 @@ struct State {
     stream_id: StreamId,
     rx: ops::Status,
     tx: ops::Status,
+    /// The last observed offsets of each half of the stream
+    ///
+    /// These are cached so they remain available after the connection has closed.
+    rx_offsets: ops::rx::Offsets,
+    tx_offsets: ops::tx::Offsets,
+    /// The last observed statistics of the sending half of the stream
+    tx_stats: ops::tx::Stats,
 }
 
 impl State {
@@ -31,6 +38,9 @@ impl State {
             stream_id,
             rx: ops::Status::Open,
             tx: ops::Status::Open,
+            rx_offsets: Default::default(),
+            tx_offsets: Default::default(),
+            tx_stats: Default::default(),
         }
     }
 
@@ -39,8 +49,31 @@ impl State {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
+        if let Some(rx) = request.rx.as_mut() {
+            rx.offsets = true;
+        }
+
+        if let Some(tx) = request.tx.as_mut() {
+            tx.offsets = true;
+            tx.stats = true;
+        }
+
         let id = self.stream_id;
-        self.connection.poll_request(id, request, context)
+        let response = self.connection.poll_request(id, request, context)?;
+
+        if let Some(offsets) = response.rx.as_ref().and_then(|rx| rx.offsets.as_ref()) {
+            self.rx_offsets.merge(offsets);
+        }
+
+        if let Some(offsets) = response.tx.as_ref().and_then(|tx| tx.offsets.as_ref()) {
+            self.tx_offsets.merge(offsets);
+        }
+
+        if let Some(stats) = response.tx.as_ref().and_then(|tx| tx.stats) {
+            self.tx_stats = stats;
+        }
+
+        Ok(response)
     }
 
     fn request(&mut self) -> Request {
@@ -106,6 +139,29 @@ macro_rules! tx_stream_apis {
                 .into()
         }
 
+        /// Enqueues a chunk of data which is tagged with `marker` for sending it towards the peer.
+        ///
+        /// Stream marker events are published for every packet that carries the chunk, for the
+        /// loss of those packets, and once the chunk has been fully acknowledged by the peer.
+        ///
+        /// The return value is the same as for [`Self::poll_send`].
+        pub fn poll_send_with_marker(
+            &mut self,
+            chunk: &mut Bytes,
+            marker: u64,
+            cx: &mut Context,
+        ) -> Poll<Result<(), StreamError>> {
+            if chunk.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+
+            self.tx_request()?
+                .send(core::slice::from_mut(chunk))
+                .with_marker(marker)
+                .poll(Some(cx))?
+                .into()
+        }
+
         /// Enqueues a slice of chunks of data for sending it towards the peer.
         ///
         /// The method will return:
@@ -173,6 +229,32 @@ macro_rules! tx_stream_apis {
             }
         }
 
+        /// Enqueues a chunk of data for sending it towards the peer without registering a waker.
+        ///
+        /// The method will return:
+        /// - `Ok(())` if the data was enqueued for sending. The provided `Bytes` will be replaced
+        ///   with an empty `Bytes`.
+        /// - `Err(StreamError::SendingBlocked)` if the send buffer capacity is currently
+        ///   exhausted. The provided `Bytes` is left untouched so it can be retried later.
+        /// - `Err(stream_error)` if the data could not be sent, because the stream
+        ///   had previously entered an error state.
+        pub fn try_send(&mut self, chunk: &mut Bytes) -> Result<(), StreamError> {
+            if chunk.is_empty() {
+                return Ok(());
+            }
+
+            let response = self
+                .tx_request()?
+                .send(core::slice::from_mut(chunk))
+                .poll(None)?;
+
+            if response.tx().expect("invalid response").chunks.consumed == 0 {
+                return Err(StreamError::sending_blocked());
+            }
+
+            Ok(())
+        }
+
         /// Flushes the send buffer and waits for acknowledgement from the peer.
         ///
         /// The method will return:
@@ -185,6 +267,33 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.flush().poll(Some(cx))?.into()
         }
 
+        /// Waits for the peer to acknowledge all of the data sent on the stream up to `offset`.
+        ///
+        /// The method will return:
+        /// - `Poll::Ready(Ok(acknowledged))` with the current acknowledged offset, once it has
+        ///   reached `offset`.
+        /// - `Poll::Ready(Err(stream_error))` if the stream had previously entered an error state.
+        /// - `Poll::Pending` if the data has not been acknowledged yet. In this case, the caller
+        ///   should retry after the `Waker` on the provided `Context` is notified.
+        pub fn poll_acknowledged(
+            &mut self,
+            offset: u64,
+            cx: &mut Context,
+        ) -> Poll<Result<u64, StreamError>> {
+            let response = self.tx_request()?.wait_for_ack(offset).poll(Some(cx))?;
+
+            let acknowledged = self.0.tx_offsets.acknowledged;
+
+            if acknowledged >= offset {
+                Poll::Ready(Ok(acknowledged))
+            } else if response.will_wake {
+                Poll::Pending
+            } else {
+                // the stream has finished without sending data up to the offset
+                Poll::Ready(Err(StreamError::non_writable()))
+            }
+        }
+
         /// Marks the stream as finished.
         ///
         /// The method will return:
@@ -215,6 +324,52 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Sets the maximum number of bytes buffered for sending on the stream.
+        ///
+        /// This overrides the connection-wide send buffer size for this stream only.
+        pub fn set_buffer_size(&mut self, size: u32) -> Result<(), StreamError> {
+            self.tx_request()?.with_buffer_size(size).poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the transmission priority of the stream.
+        ///
+        /// Streams with a higher priority are scheduled for transmission before streams with a
+        /// lower priority, and are given `priority + 1` consecutive packets before yielding to
+        /// the other streams.
+        pub fn set_priority(&mut self, priority: u8) -> Result<(), StreamError> {
+            self.tx_request()?.with_priority(priority).poll(None)?;
+            Ok(())
+        }
+
+        /// Returns the offsets of the data sent on the stream.
+        ///
+        /// The offsets are refreshed if the stream is still open. Otherwise, the offsets observed
+        /// by the last operation on the stream are returned.
+        pub fn send_offsets(&mut self) -> Result<ops::tx::Offsets, StreamError> {
+            if let Ok(mut request) = self.tx_request() {
+                let _ = request.poll(None);
+            }
+            Ok(self.0.tx_offsets)
+        }
+
+        /// Returns the cumulative statistics of the data sent on the stream.
+        ///
+        /// The statistics are refreshed if the stream is still open. Otherwise, the statistics
+        /// observed by the last operation on the stream are returned.
+        pub fn send_stats(&mut self) -> Result<ops::tx::Stats, StreamError> {
+            if let Ok(mut request) = self.tx_request() {
+                let _ = request.poll(None);
+            }
+            Ok(self.0.tx_stats)
+        }
+
+        /// Returns the current usage of the stream's send buffer.
+        pub fn buffer_stats(&mut self) -> Result<ops::tx::BufferStats, StreamError> {
+            let response = self.tx_request()?.buffer_stats().poll(None)?;
+            Ok(response.buffer.unwrap_or_default())
+        }
     };
 }
 
@@ -279,6 +434,35 @@ macro_rules! rx_stream_apis {
             Poll::Ready(Ok((consumed, is_open)))
         }
 
+        /// Receives a chunk of data from the stream without registering a waker.
+        ///
+        /// The method will return:
+        /// - `Ok(Some(chunk))` if the stream is open and data was available
+        /// - `Ok(None)` if the stream was finished and all of the data was consumed
+        /// - `Err(StreamError::ReceivingBlocked)` if the stream is waiting to receive data from
+        ///   the peer.
+        /// - `Err(stream_error)` if the stream could not be read, because the stream
+        ///   had previously entered an error state.
+        pub fn try_receive(&mut self) -> Result<Option<Bytes>, StreamError> {
+            let mut chunk = Bytes::new();
+            let response = self
+                .rx_request()?
+                .receive(core::slice::from_mut(&mut chunk))
+                .poll(None)?;
+
+            let rx = response.rx().expect("invalid response");
+
+            if rx.chunks.consumed > 0 {
+                return Ok(Some(chunk));
+            }
+
+            if rx.status.is_open() || rx.status.is_finishing() {
+                return Err(StreamError::receiving_blocked());
+            }
+
+            Ok(None)
+        }
+
         /// Sends a `STOP_SENDING` message to the peer. This requests the peer to
         /// finish the `Stream` as soon as possible by issuing a `RESET` with the
         /// provided `error_code`.
@@ -293,6 +477,17 @@ macro_rules! rx_stream_apis {
             self.rx_request()?.stop_sending(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Returns the offsets of the data received on the stream.
+        ///
+        /// The offsets are refreshed if the stream is still open. Otherwise, the offsets observed
+        /// by the last operation on the stream are returned.
+        pub fn receive_offsets(&mut self) -> Result<ops::rx::Offsets, StreamError> {
+            if let Ok(mut request) = self.rx_request() {
+                let _ = request.poll(None);
+            }
+            Ok(self.0.rx_offsets)
+        }
     };
 }
 
@@ -485,6 +680,31 @@ macro_rules! tx_request_apis {
             self.request.flush();
             self
         }
+
+        pub fn with_buffer_size(&mut self, size: u32) -> &mut Self {
+            self.request.with_send_buffer_size(size);
+            self
+        }
+
+        pub fn with_priority(&mut self, priority: u8) -> &mut Self {
+            self.request.with_send_priority(priority);
+            self
+        }
+
+        pub fn buffer_stats(&mut self) -> &mut Self {
+            self.request.send_buffer_stats();
+            self
+        }
+
+        pub fn with_marker(&mut self, marker: u64) -> &mut Self {
+            self.request.with_send_marker(marker);
+            self
+        }
+
+        pub fn wait_for_ack(&mut self, offset: u64) -> &mut Self {
+            self.request.wait_for_ack(offset);
+            self
+        }
     };
 }
 
@@ -129,6 +129,21 @@ impl Controller {
         Poll::Ready(())
     }
 
+    /// This method is called when the local endpoint opens a reserved stream.
+    ///
+    /// Reserved streams are opened before the application has a chance to wait on capacity so
+    /// the caller is responsible for checking [`Self::available_local_initiated_stream_capacity`]
+    /// first.
+    pub fn on_open_reserved_local_stream(&mut self, stream_type: StreamType) {
+        debug_assert!(
+            self.available_local_initiated_stream_capacity(stream_type) > VarInt::from_u8(0),
+            "reserved streams should not exceed the available capacity"
+        );
+
+        let direction = self.direction(StreamId::initial(self.local_endpoint_type, stream_type));
+        self.on_open_stream(direction);
+    }
+
     /// This method is called when the remote peer wishes to open a new stream.
     ///
     /// Opening a Stream also opens all lower Streams of the same type. Therefore
@@ -204,6 +219,24 @@ impl Controller {
         }
     }
 
+    /// The number of streams of the given type that may be opened by the local application
+    #[inline]
+    pub fn available_local_initiated_stream_capacity(&self, stream_type: StreamType) -> VarInt {
+        match stream_type {
+            StreamType::Bidirectional => self.local_bidi_controller.available_stream_capacity(),
+            StreamType::Unidirectional => self.local_uni_controller.available_stream_capacity(),
+        }
+    }
+
+    /// Raises the number of streams of the given type that the peer may have open concurrently
+    #[inline]
+    pub fn raise_remote_initiated_stream_limit(&mut self, stream_type: StreamType, limit: VarInt) {
+        match stream_type {
+            StreamType::Bidirectional => self.remote_bidi_controller.raise_max_local_limit(limit),
+            StreamType::Unidirectional => self.remote_uni_controller.raise_max_local_limit(limit),
+        }
+    }
+
     /// This method is called when the stream manager is closed. All wakers will be woken
     /// to unblock waiting tasks.
     pub fn close(&mut self) {
@@ -372,13 +405,6 @@ mod tests {
     use s2n_quic_core::varint::VarInt;
 
     impl Controller {
-        pub fn available_local_initiated_stream_capacity(&self, stream_type: StreamType) -> VarInt {
-            match stream_type {
-                StreamType::Bidirectional => self.local_bidi_controller.available_stream_capacity(),
-                StreamType::Unidirectional => self.local_uni_controller.available_stream_capacity(),
-            }
-        }
-
         pub fn remote_initiated_max_streams_latest_value(&self, stream_type: StreamType) -> VarInt {
             match stream_type {
                 StreamType::Bidirectional => self.remote_bidi_controller.latest_limit(),
@@ -9,7 +9,7 @@ use crate::{
     transmission::WriteContext,
 };
 use core::{
-    task::{Context, Poll, Waker},
+    task::{Context, Poll},
     time::Duration,
 };
 use s2n_quic_core::{
@@ -17,13 +17,10 @@ use s2n_quic_core::{
     frame::{self, MaxStreams, StreamsBlocked},
     packet::number::PacketNumber,
     stream::{limits::LocalLimits, StreamId},
+    sync::wait_queue::WaitQueue,
     time::{timer, Timestamp},
     varint::VarInt,
 };
-use smallvec::SmallVec;
-
-// The amount of wakers that may be tracked before allocating to the heap.
-const WAKERS_INITIAL_CAPACITY: usize = 5;
 
 /// The LocalInitiated controller controls streams initiated locally
 #[derive(Debug)]
@@ -37,16 +34,13 @@ pub(super) struct LocalInitiated<L: LocalLimits, OpenNotify: OpenNotifyBehavior>
     ///
     /// Can be updated when MAX_STREAMS frame is received.
     peer_cumulative_stream_limit: VarInt,
-    wakers: SmallVec<[Waker; WAKERS_INITIAL_CAPACITY]>,
+    /// The handles waiting for stream capacity, in the order they started waiting
+    wait_queue: WaitQueue,
     streams_blocked_sync: PeriodicSync<VarInt, StreamsBlockedToFrameWriter>,
     /// opened_streams is needed to track the latest opened stream since
     /// peer_stream_limit is a cumulative limit.
     opened_streams: VarInt,
     closed_streams: VarInt,
-    /// Keeps track of all of the issued open tokens
-    token_counter: open_token::Counter,
-    /// Keeps track of all of the expired open tokens
-    expired_token: open_token::Token,
     open_notify: OpenNotify,
 }
 
@@ -55,12 +49,10 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
         Self {
             max_local_limit,
             peer_cumulative_stream_limit: initial_peer_maximum_streams,
-            wakers: SmallVec::new(),
+            wait_queue: WaitQueue::new(),
             streams_blocked_sync: PeriodicSync::new(),
             opened_streams: VarInt::from_u8(0),
             closed_streams: VarInt::from_u8(0),
-            token_counter: open_token::Counter::new(),
-            expired_token: open_token::Token::new(),
             open_notify: Default::default(),
         }
     }
@@ -95,18 +87,9 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
         context: &Context,
     ) -> Poll<()> {
         if self.available_stream_capacity() < VarInt::from_u32(1) {
-            if let Some(index) = open_token.index(&self.expired_token) {
-                let prev = &self.wakers[index];
-                // update the waker if it's changed
-                if !prev.will_wake(context.waker()) {
-                    self.wakers[index] = context.waker().clone();
-                }
-            } else {
-                // Store a waker that can be woken when we get more credit
-                self.wakers.push(context.waker().clone());
-                // give them a waker to remember their position in the list
-                *open_token = self.token_counter.next();
-            }
+            // Store a waker that can be woken when we get more credit. Handles which are already
+            // waiting keep their position in the queue.
+            self.wait_queue.register(open_token, context.waker());
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
             //# An endpoint that is unable to open a new stream due to the peer's
@@ -124,8 +107,9 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
             return Poll::Pending;
         }
 
-        // reset the open token since they're no longer blocked
-        open_token.clear();
+        // remove the handle from the queue since it's no longer blocked, otherwise it would
+        // take up one of the wake ups when more capacity is available
+        self.wait_queue.cancel(open_token);
 
         Poll::Ready(())
     }
@@ -165,25 +149,14 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
 
     /// Wake all wakers
     fn wake_all(&mut self) {
-        self.wakers
-            .drain(..self.wakers.len())
-            .for_each(|waker| waker.wake())
+        self.wait_queue.wake_all();
     }
 
     /// Wakes the wakers that have been unblocked by the current amount
     /// of available local stream capacity.
     fn wake_unblocked(&mut self) {
-        let unblocked_wakers_count = self
-            .wakers
-            .len()
-            .min(self.available_stream_capacity().as_u64() as usize);
-
-        self.wakers
-            .drain(..unblocked_wakers_count)
-            .for_each(|waker| waker.wake());
-
-        // keep track of the number of tokens that have expired
-        self.expired_token.expire(unblocked_wakers_count);
+        let capacity = self.available_stream_capacity().as_u64() as usize;
+        self.wait_queue.wake(capacity);
     }
 
     /// Returns the number of streams currently open
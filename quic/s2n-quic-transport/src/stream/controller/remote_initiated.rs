@@ -167,6 +167,32 @@ impl RemoteInitiated {
         self.max_streams_sync.update_latest_value(max_streams);
     }
 
+    /// Raises the number of streams the peer may have open concurrently
+    ///
+    /// The additional credit is advertised to the peer right away. The limit can't be lowered,
+    /// since the peer may already have used the credit which was advertised.
+    #[inline]
+    pub fn raise_max_local_limit(&mut self, max_local_limit: VarInt) {
+        let max_local_limit = max_local_limit.min(MAX_STREAMS_MAX_VALUE);
+        if max_local_limit <= self.max_local_limit {
+            return;
+        }
+
+        let increase = max_local_limit - self.max_local_limit;
+        self.max_local_limit = max_local_limit;
+        self.rtt_refill.grow(increase.as_u64());
+
+        // `synced_closed_streams` is derived from the difference between the two values so they
+        // are raised by the same amount
+        let max_streams = self
+            .max_streams_sync
+            .latest_value()
+            .saturating_add(increase)
+            .min(MAX_STREAMS_MAX_VALUE);
+        self.max_streams_sync
+            .update_latest_value_immediately(max_streams);
+    }
+
     pub fn close(&mut self) {
         self.max_streams_sync.stop_sync();
         self.rtt_refill.cancel();
@@ -15,6 +15,14 @@ use s2n_quic_core::{
     varint::VarInt,
 };
 
+/// Returns the amount of `window` the peer needs to consume before an update is sent
+///
+/// `percent` is the configured window update threshold, between 1 and 100.
+pub(super) fn window_update_threshold(window: u32, percent: u8) -> VarInt {
+    let threshold = u64::from(window) * u64::from(percent) / 100;
+    VarInt::from_u32(threshold as u32)
+}
+
 /// Writes `MAX_DATA` frames based on the connections flow control window.
 #[derive(Default, Debug)]
 pub(super) struct MaxDataToFrameWriter {}
@@ -49,12 +57,16 @@ struct IncomingConnectionFlowControllerImpl {
 }
 
 impl IncomingConnectionFlowControllerImpl {
-    pub fn new(initial_window_size: VarInt, desired_flow_control_window: u32) -> Self {
+    pub fn new(
+        initial_window_size: VarInt,
+        desired_flow_control_window: u32,
+        window_update_threshold: u8,
+    ) -> Self {
         Self {
             read_window_sync: IncrementalValueSync::new(
                 VarInt::from_u32(desired_flow_control_window),
                 initial_window_size,
-                VarInt::from_u32(desired_flow_control_window / 10),
+                self::window_update_threshold(desired_flow_control_window, window_update_threshold),
             ),
             desired_flow_control_window,
             acquired_window: VarInt::from_u32(0),
@@ -134,11 +146,19 @@ impl IncomingConnectionFlowController {
     /// `desired_flow_control_window`. This means if the window which is indicated
     /// to the peer is lower than this value the new value will be communicated
     /// to the peer.
-    pub fn new(initial_window_size: VarInt, desired_flow_control_window: u32) -> Self {
+    ///
+    /// An update is only sent once the peer has consumed `window_update_threshold` percent of
+    /// the window since the last acknowledged update.
+    pub fn new(
+        initial_window_size: VarInt,
+        desired_flow_control_window: u32,
+        window_update_threshold: u8,
+    ) -> Self {
         Self {
             inner: Rc::new(RefCell::new(IncomingConnectionFlowControllerImpl::new(
                 initial_window_size,
                 desired_flow_control_window,
+                window_update_threshold,
             ))),
         }
     }
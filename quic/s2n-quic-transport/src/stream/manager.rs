@@ -10,27 +10,32 @@ use crate::{
     stream::{
         self,
         incoming_connection_flow_controller::IncomingConnectionFlowController,
+        marker,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         stream_container::{StreamContainer, StreamContainerIterationResult},
         stream_events::StreamEvents,
         stream_impl::StreamConfig,
+        tx_coalescer::{HoldNewData, TxCoalescer},
         StreamError, StreamTrait,
     },
     transmission::{self, interest::Provider as _},
 };
+use alloc::vec::Vec;
 use core::{
     task::{ready, Context, Poll, Waker},
     time::Duration,
 };
 use s2n_quic_core::{
-    ack,
-    connection::error::Error,
+    ack, application,
+    connection::{error::Error, TransmissionStatus},
     endpoint,
+    event::{self, IntoEvent},
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
         StopSending, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::PacketNumberSpace,
+    recovery::ProbePolicy,
     stream::{iter::StreamIter, ops, StreamId, StreamType},
     time::{timer, Timestamp},
     transport::{self, parameters::InitialFlowControlLimits},
@@ -94,6 +99,34 @@ impl StreamIdSet {
     }
 }
 
+/// Holds the range of reserved local Stream IDs of each type which have not
+/// yet been handed out to the application
+#[derive(Debug, Default)]
+pub(super) struct ReservedStreamIds {
+    bidirectional: Option<StreamIter>,
+    unidirectional: Option<StreamIter>,
+}
+
+impl ReservedStreamIds {
+    /// Returns a mutable reference to the reserved range for the given Stream type
+    pub fn get_mut(&mut self, stream_type: StreamType) -> &mut Option<StreamIter> {
+        match stream_type {
+            StreamType::Bidirectional => &mut self.bidirectional,
+            StreamType::Unidirectional => &mut self.unidirectional,
+        }
+    }
+
+    /// Takes the lowest reserved Stream ID of the given type
+    pub fn take(&mut self, stream_type: StreamType) -> Option<StreamId> {
+        let range = self.get_mut(stream_type);
+        let stream_id = range.as_mut()?.next();
+        if stream_id.is_none() {
+            *range = None;
+        }
+        stream_id
+    }
+}
+
 /// Stores all required state for accepting incoming Streams via the
 /// `accept()` method
 #[derive(Debug)]
@@ -184,9 +217,16 @@ pub struct StreamManagerState<S> {
     close_reason: Option<connection::Error>,
     /// All state for accepting remotely initiated connections
     pub(super) accept_state: AcceptState,
+    /// The reserved local Stream IDs which were not yet opened by the application
+    pub(super) reserved_stream_ids: ReservedStreamIds,
+    /// The next remotely initiated Stream ID of each type for which an event
+    /// has not yet been published
+    unpublished_peer_stream_ids: StreamIdSet,
     /// Limits for the Stream manager. Since only Stream limits are utilized at
     /// the moment we only store those
     stream_limits: stream::Limits,
+    /// The percentage of a stream's receive window consumed by the peer before an update is sent
+    stream_window_update_threshold: u8,
 }
 
 impl<S: StreamTrait> StreamManagerState<S> {
@@ -245,6 +285,7 @@ impl<S: StreamTrait> StreamManagerState<S> {
             stream_id,
             initial_receive_window,
             desired_flow_control_window: initial_receive_window.as_u64() as u32,
+            window_update_threshold: self.stream_window_update_threshold,
             initial_send_window,
             max_send_buffer_size: self.stream_limits.max_send_buffer_size.as_u32(),
         }));
@@ -361,6 +402,39 @@ impl<S: StreamTrait> StreamManagerState<S> {
         Poll::Ready(Ok(first_unopened_id))
     }
 
+    /// Opens the first `count` local Streams of the given type and reserves
+    /// them for the application.
+    ///
+    /// Only the Streams which fit into the peer's initial stream limit are
+    /// reserved.
+    fn reserve_local_streams(&mut self, stream_type: StreamType, count: u8) {
+        let capacity = self
+            .stream_controller
+            .available_local_initiated_stream_capacity(stream_type);
+        let count = VarInt::from_u8(count).min(capacity).as_u64();
+
+        if count == 0 {
+            return;
+        }
+
+        let next_stream_id = self
+            .next_stream_ids
+            .get_mut(self.local_endpoint_type, stream_type);
+        let first_id = next_stream_id.expect("no streams have been opened yet");
+        let last_id = StreamId::nth(self.local_endpoint_type, stream_type, count - 1)
+            .expect("the reserved range is bounded by the peer limits");
+        *next_stream_id = last_id.next_of_type();
+
+        let stream_iter = StreamIter::new(first_id, last_id);
+        for stream_id in stream_iter {
+            self.stream_controller
+                .on_open_reserved_local_stream(stream_type);
+            self.insert_stream(stream_id);
+        }
+
+        *self.reserved_stream_ids.get_mut(stream_type) = Some(stream_iter);
+    }
+
     fn close(&mut self, error: connection::Error, flush: bool) {
         if self.close_reason.is_some() {
             return;
@@ -420,6 +494,13 @@ pub struct AbstractStreamManager<S> {
     pub(super) inner: StreamManagerState<S>,
     last_blocked_sync_period: Duration,
     last_min_rtt: Duration,
+    /// Events for application-marked stream data which are waiting to be published
+    marker_events: Vec<marker::Event>,
+    /// The number of stream bytes which were retransmitted since the last call to
+    /// `take_retransmitted_bytes`
+    retransmitted_bytes: u64,
+    /// Holds back small stream writes so they can be combined into fuller packets
+    tx_coalescer: TxCoalescer,
 }
 
 // Sending the `AbstractStreamManager` between threads is safe, since we never expose the `Rc`s
@@ -577,11 +658,12 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             "Receive window must not exceed 32bit range"
         );
 
-        Self {
+        let mut manager = Self {
             inner: StreamManagerState {
                 incoming_connection_flow_controller: IncomingConnectionFlowController::new(
                     initial_local_limits.max_data,
                     initial_local_limits.max_data.as_u64() as u32,
+                    connection_limits.connection_window_update_threshold(),
                 ),
                 outgoing_connection_flow_controller: OutgoingConnectionFlowController::new(
                     initial_peer_limits.max_data,
@@ -593,18 +675,35 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                     connection_limits.stream_limits(),
                     min_rtt,
                 ),
-                streams: StreamContainer::new(),
+                streams: StreamContainer::new(connection_limits.stream_scheduling_policy()),
                 next_stream_ids: StreamIdSet::initial(),
                 local_endpoint_type,
                 initial_local_limits,
                 initial_peer_limits,
                 close_reason: None,
                 accept_state: AcceptState::new(local_endpoint_type),
+                reserved_stream_ids: ReservedStreamIds::default(),
+                unpublished_peer_stream_ids: StreamIdSet::initial(),
                 stream_limits: connection_limits.stream_limits(),
+                stream_window_update_threshold: connection_limits.stream_window_update_threshold(),
             },
             last_blocked_sync_period: Duration::ZERO,
             last_min_rtt: min_rtt,
+            marker_events: Vec::new(),
+            retransmitted_bytes: 0,
+            tx_coalescer: TxCoalescer::default(),
+        };
+
+        // Reserved streams are opened before any other local streams so they
+        // always receive the lowest Stream IDs
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            manager.inner.reserve_local_streams(
+                stream_type,
+                connection_limits.reserved_local_streams(stream_type),
+            );
         }
+
+        manager
     }
 
     fn incoming_bytes_progressed(&self) -> VarInt {
@@ -709,6 +808,18 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         Ok(first_unopened_id).into()
     }
 
+    fn open_reserved_stream(
+        &mut self,
+        stream_type: StreamType,
+    ) -> Result<Option<StreamId>, connection::Error> {
+        // If StreamManager was closed, return the error
+        if let Some(error) = self.inner.close_reason {
+            return Err(error);
+        }
+
+        Ok(self.inner.reserved_stream_ids.take(stream_type))
+    }
+
     fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.inner
             .incoming_connection_flow_controller
@@ -718,6 +829,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             .on_packet_ack(ack_set);
         self.inner.stream_controller.on_packet_ack(ack_set);
 
+        let marker_events = &mut self.marker_events;
         self.inner.streams.iterate_frame_delivery_list(
             &mut self.inner.stream_controller,
             |stream| {
@@ -725,6 +837,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 // to carry wakers in another iteration
                 let mut events = StreamEvents::new();
                 stream.on_packet_ack(ack_set, &mut events);
+                stream.drain_marker_events(marker_events);
                 events.wake_all();
             },
         );
@@ -739,6 +852,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             .on_packet_loss(ack_set);
         self.inner.stream_controller.on_packet_loss(ack_set);
 
+        let marker_events = &mut self.marker_events;
         self.inner.streams.iterate_frame_delivery_list(
             &mut self.inner.stream_controller,
             |stream| {
@@ -746,6 +860,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 // to carry wakers in another iteration
                 let mut events = StreamEvents::new();
                 stream.on_packet_loss(ack_set, &mut events);
+                stream.drain_marker_events(marker_events);
                 events.wake_all();
             },
         );
@@ -799,6 +914,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
     }
 
     fn on_timeout(&mut self, now: Timestamp) {
+        self.tx_coalescer.on_timeout(now);
         self.inner.stream_controller.on_timeout(now);
         self.inner
             .outgoing_connection_flow_controller
@@ -812,6 +928,15 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         );
     }
 
+    fn hibernate(&mut self) {
+        self.inner
+            .streams
+            .iterate_streams(&mut self.inner.stream_controller, |stream| {
+                stream.hibernate();
+            });
+        self.marker_events.shrink_to_fit();
+    }
+
     fn close(&mut self, error: connection::Error) {
         self.inner.close(error, false);
     }
@@ -842,6 +967,8 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         // able to write all the desired data and added themselves as
         // transmit interested again
         let mut transmit_result = Ok(());
+        let marker_events = &mut self.marker_events;
+        let retransmitted_bytes = &mut self.retransmitted_bytes;
 
         if context.transmission_constraint().can_retransmit() {
             // ensure components only retransmit in this phase
@@ -853,6 +980,8 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 &mut self.inner.stream_controller,
                 |stream: &mut S| {
                     transmit_result = stream.on_transmit(&mut retransmission_context);
+                    stream.drain_marker_events(marker_events);
+                    *retransmitted_bytes += stream.take_retransmitted_bytes();
                     if transmit_result.is_err() {
                         StreamContainerIterationResult::BreakAndInsertAtBack
                     } else {
@@ -870,6 +999,8 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 &mut self.inner.stream_controller,
                 |stream: &mut S| {
                     transmit_result = stream.on_transmit(context);
+                    stream.drain_marker_events(marker_events);
+                    *retransmitted_bytes += stream.take_retransmitted_bytes();
                     if transmit_result.is_err() {
                         StreamContainerIterationResult::BreakAndInsertAtBack
                     } else {
@@ -877,6 +1008,9 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                     }
                 },
             );
+
+            // any held writes were able to join the packet
+            self.tx_coalescer.on_transmit();
         }
 
         // There is no `finalize_done_streams` here, since we do not expect to
@@ -885,6 +1019,43 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         transmit_result
     }
 
+    fn on_probe_transmit<W: WriteContext>(
+        &mut self,
+        context: &mut W,
+        policy: ProbePolicy,
+    ) -> Result<(), OnTransmitError> {
+        match policy {
+            ProbePolicy::RetransmitUnacknowledged => {
+                if !context.transmission_constraint().can_retransmit() {
+                    return Ok(());
+                }
+
+                let mut retransmission_context =
+                    transmission::context::RetransmissionContext::new(context);
+                let marker_events = &mut self.marker_events;
+                let retransmitted_bytes = &mut self.retransmitted_bytes;
+
+                // Streams with unacknowledged data aren't necessarily in any of the interest
+                // lists, so all of the streams are queried in the order of their IDs.
+                self.inner.streams.iterate_streams(
+                    &mut self.inner.stream_controller,
+                    |stream: &mut S| {
+                        let _ = stream.on_transmit(&mut retransmission_context);
+                        stream.drain_marker_events(marker_events);
+                        *retransmitted_bytes += stream.take_retransmitted_bytes();
+                    },
+                );
+
+                Ok(())
+            }
+            ProbePolicy::NewData => {
+                self.on_transmit(&mut transmission::context::NewDataContext::new(context))
+            }
+            ProbePolicy::PingOnly => Ok(()),
+            _ => self.on_transmit(context),
+        }
+    }
+
     // Frame reception
     // These functions are called from the packet delivery thread
 
@@ -982,17 +1153,161 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
-        self.perform_api_call(
+        let response = self.perform_api_call(
             stream_id,
             Err(StreamError::invalid_stream()),
             api_call_context,
             |stream| stream.poll_request(request, context),
+        )?;
+
+        if let (Some(tx_request), Some(tx_response)) = (request.tx.as_ref(), response.tx.as_ref()) {
+            let is_flush = tx_request.flush || tx_request.finish || tx_request.reset.is_some();
+
+            // the connection needs to be woken up to transmit the writes which were held back
+            if self
+                .tx_coalescer
+                .on_write(tx_response.bytes.consumed, is_flush)
+            {
+                api_call_context.wakeup_handle().wakeup();
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn close_streams(
+        &mut self,
+        api_call_context: &mut ConnectionApiCallContext,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        filter: &mut dyn FnMut(StreamId) -> bool,
+    ) -> usize {
+        let transmission_snapshot = self.transmission_snapshot();
+        let mut count = 0;
+
+        self.inner
+            .streams
+            .iterate_streams(&mut self.inner.stream_controller, |stream| {
+                if !filter(stream.stream_id()) {
+                    return;
+                }
+
+                // We have to wake inside the lock, since `StreamEvent`s has no capacity
+                // to carry wakers in another iteration
+                let mut events = StreamEvents::new();
+                stream.on_application_close(reset, stop_sending, &mut events);
+                events.wake_all();
+                count += 1;
+            });
+
+        if transmission_snapshot != self.transmission_snapshot() {
+            api_call_context.wakeup_handle().wakeup();
+        }
+
+        count
+    }
+
+    fn set_max_stream_data(
+        &mut self,
+        stream_id: StreamId,
+        api_call_context: &mut ConnectionApiCallContext,
+        window: u32,
+    ) -> Result<(), StreamError> {
+        self.perform_api_call(
+            stream_id,
+            Err(StreamError::invalid_stream()),
+            api_call_context,
+            |stream| {
+                stream.set_receive_window(window);
+                Ok(())
+            },
         )
     }
 
+    fn set_max_streams(
+        &mut self,
+        api_call_context: &mut ConnectionApiCallContext,
+        stream_type: StreamType,
+        limit: VarInt,
+    ) {
+        let transmission_snapshot = self.transmission_snapshot();
+
+        self.inner
+            .stream_controller
+            .raise_remote_initiated_stream_limit(stream_type, limit);
+
+        if transmission_snapshot != self.transmission_snapshot() {
+            api_call_context.wakeup_handle().wakeup();
+        }
+    }
+
     fn has_pending_streams(&self) -> bool {
         self.inner.streams.has_pending_streams()
     }
+
+    fn publish_marker_events<Pub: event::ConnectionPublisher>(&mut self, publisher: &mut Pub) {
+        for event in self.marker_events.drain(..) {
+            event.publish(publisher);
+        }
+    }
+
+    fn publish_peer_stream_events<Pub: event::ConnectionPublisher>(&mut self, publisher: &mut Pub) {
+        let peer_type = self.inner.local_endpoint_type.peer_type();
+
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            let end = *self.inner.next_stream_ids.get_mut(peer_type, stream_type);
+            let next = self
+                .inner
+                .unpublished_peer_stream_ids
+                .get_mut(peer_type, stream_type);
+
+            while let Some(stream_id) = *next {
+                if end.map_or(false, |end| stream_id >= end) {
+                    break;
+                }
+
+                publisher.on_peer_stream_opened(event::builder::PeerStreamOpened {
+                    id: stream_id.as_varint().as_u64(),
+                    stream_type: (&stream_type).into_event(),
+                });
+
+                *next = stream_id.next_of_type();
+            }
+        }
+    }
+
+    #[inline]
+    fn take_retransmitted_bytes(&mut self) -> u64 {
+        core::mem::take(&mut self.retransmitted_bytes)
+    }
+
+    #[inline]
+    fn set_tx_coalescing(&mut self, delay: Option<Duration>) {
+        self.tx_coalescer.update(delay);
+    }
+
+    #[inline]
+    fn on_wakeup(&mut self, now: Timestamp, max_datagram_size: usize) {
+        self.tx_coalescer.arm(now, max_datagram_size);
+    }
+
+    fn update_transmission_status(&self, status: &mut TransmissionStatus) {
+        let flow_controller = &self.inner.outgoing_connection_flow_controller;
+        status.connection_flow_limit = flow_controller.total_window().as_u64();
+        status.connection_flow_consumed = flow_controller.acquired_window().as_u64();
+
+        let streams = &self.inner.streams;
+        status.connection_flow_blocked_streams = streams.connection_flow_credits_list_len();
+        status.stream_flow_blocked_streams = streams.stream_flow_credits_list_len();
+
+        let controller = &self.inner.stream_controller;
+        status.available_bidirectional_streams = controller
+            .available_local_initiated_stream_capacity(StreamType::Bidirectional)
+            .as_u64();
+        status.available_unidirectional_streams = controller
+            .available_local_initiated_stream_capacity(StreamType::Unidirectional)
+            .as_u64();
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
@@ -1003,6 +1318,7 @@ impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
             .outgoing_connection_flow_controller
             .timers(query)?;
         self.inner.streams.timers(query)?;
+        self.tx_coalescer.timers(query)?;
         Ok(())
     }
 }
@@ -1013,7 +1329,14 @@ impl<S: StreamTrait> transmission::interest::Provider for AbstractStreamManager<
         &self,
         query: &mut Q,
     ) -> transmission::interest::Result {
-        self.inner.streams.transmission_interest(query)?;
+        if self.tx_coalescer.is_holding() {
+            // lost data is still retransmitted while new writes are being coalesced
+            self.inner
+                .streams
+                .transmission_interest(&mut HoldNewData(query))?;
+        } else {
+            self.inner.streams.transmission_interest(query)?;
+        }
         self.inner.stream_controller.transmission_interest(query)?;
         self.inner
             .incoming_connection_flow_controller
@@ -1114,15 +1437,11 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
 
     /// Returns the list of Stream IDs for Streams which are waiting for
     /// transmission.
+    ///
+    /// The list is inspected without iterating it, since iterating would
+    /// consume the transmission turns of prioritized Streams.
     pub fn streams_waiting_for_transmission(&mut self) -> Vec<StreamId> {
-        let mut results = Vec::new();
-        self.inner
-            .streams
-            .iterate_transmission_list(&mut self.inner.stream_controller, |stream| {
-                results.push(stream.stream_id());
-                StreamContainerIterationResult::Continue
-            });
-        results
+        self.inner.streams.transmission_list()
     }
 
     /// Returns the list of Stream IDs for Streams which are waiting for
@@ -15,6 +15,7 @@ use crate::{
     stream::{
         controller::MAX_STREAMS_SYNC_FRACTION,
         manager_api::Manager as _,
+        scheduler::StreamSchedulingPolicy,
         stream_impl::StreamConfig,
         stream_interests::{StreamInterestProvider, StreamInterests},
         testing::*,
@@ -390,6 +391,13 @@ fn assert_wakeups(wakeup_queue: &mut WakeupQueue<InternalConnectionId>, expected
 }
 
 fn create_stream_manager(local_ep_type: endpoint::Type) -> AbstractStreamManager<MockStream> {
+    create_stream_manager_with_scheduler(local_ep_type, StreamSchedulingPolicy::default())
+}
+
+fn create_stream_manager_with_scheduler(
+    local_ep_type: endpoint::Type,
+    scheduler: StreamSchedulingPolicy,
+) -> AbstractStreamManager<MockStream> {
     let initial_local_limits = create_default_initial_flow_control_limits();
     let initial_peer_limits = create_default_initial_flow_control_limits();
 
@@ -398,6 +406,8 @@ fn create_stream_manager(local_ep_type: endpoint::Type) -> AbstractStreamManager
         .with_max_open_local_bidirectional_streams(1000)
         .unwrap()
         .with_max_open_local_unidirectional_streams(1000)
+        .unwrap()
+        .with_stream_scheduling_policy(scheduler)
         .unwrap();
 
     AbstractStreamManager::<MockStream>::new(
@@ -535,6 +545,87 @@ fn opens_locally_initiated_streams() {
     }
 }
 
+#[test]
+fn reserved_streams_are_opened_first() {
+    const RESERVED: u8 = 3;
+
+    for local_ep_type in [endpoint::Type::Client, endpoint::Type::Server] {
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            let limits = ConnectionLimits::default()
+                .with_reserved_local_bidirectional_streams(RESERVED)
+                .unwrap()
+                .with_reserved_local_unidirectional_streams(RESERVED)
+                .unwrap();
+            let mut manager = AbstractStreamManager::<MockStream>::new(
+                &limits,
+                local_ep_type,
+                create_default_initial_flow_control_limits(),
+                create_default_initial_flow_control_limits(),
+                DEFAULT_INITIAL_RTT,
+            );
+
+            // the reserved streams are open before the application requests them
+            for n in 0..RESERVED as u64 {
+                let stream_id = StreamId::nth(local_ep_type, stream_type, n).unwrap();
+                assert!(manager.active_streams().contains(&stream_id));
+            }
+
+            // other streams are assigned the IDs after the reserved range
+            let stream_id = try_open(&mut manager, stream_type).unwrap();
+            assert_eq!(
+                StreamId::nth(local_ep_type, stream_type, RESERVED as u64).unwrap(),
+                stream_id
+            );
+
+            for n in 0..RESERVED as u64 {
+                assert_eq!(
+                    manager.open_reserved_stream(stream_type).unwrap(),
+                    Some(StreamId::nth(local_ep_type, stream_type, n).unwrap())
+                );
+            }
+            assert_eq!(manager.open_reserved_stream(stream_type).unwrap(), None);
+        }
+    }
+}
+
+#[test]
+fn reserved_streams_are_limited_by_peer() {
+    let mut peer_limits = create_default_initial_flow_control_limits();
+    peer_limits.max_open_remote_unidirectional_streams = VarInt::from_u8(1);
+
+    let limits = ConnectionLimits::default()
+        .with_reserved_local_unidirectional_streams(3)
+        .unwrap();
+    let mut manager = AbstractStreamManager::<MockStream>::new(
+        &limits,
+        endpoint::Type::Client,
+        create_default_initial_flow_control_limits(),
+        peer_limits,
+        DEFAULT_INITIAL_RTT,
+    );
+
+    assert_eq!(
+        manager
+            .open_reserved_stream(StreamType::Unidirectional)
+            .unwrap(),
+        Some(StreamId::initial(
+            endpoint::Type::Client,
+            StreamType::Unidirectional
+        ))
+    );
+    assert_eq!(
+        manager
+            .open_reserved_stream(StreamType::Unidirectional)
+            .unwrap(),
+        None
+    );
+
+    manager.close(connection::Error::unspecified());
+    assert!(manager
+        .open_reserved_stream(StreamType::Bidirectional)
+        .is_err());
+}
+
 #[test]
 fn open_returns_error_after_close() {
     let mut manager = create_stream_manager(endpoint::Type::Server);
@@ -3249,3 +3340,173 @@ fn stream_transmission_fairness_test() {
         }
     }
 }
+
+#[test]
+fn stream_transmission_priority_test() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let bulk_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let bulk_2 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let control = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    for stream_id in [bulk_1, bulk_2, control] {
+        manager.with_asserted_stream(stream_id, |stream| {
+            if stream_id == control {
+                // a priority of 2 gives the stream 3 turns per round
+                stream.interests.priority = 2;
+            }
+            stream.on_transmit_try_write_frames = 100;
+            stream.on_transmit_limit = Some(1);
+        });
+    }
+
+    // the prioritized stream is inserted ahead of the bulk streams
+    assert_eq!(
+        [control, bulk_1, bulk_2],
+        *manager.streams_waiting_for_transmission()
+    );
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+
+    // The first round is unconstrained so each stream uses all of its turns
+    //
+    // After that, each call to `on_transmit` only fits a single frame
+    for round in 1..=2 {
+        if round == 1 {
+            let _ = manager.on_transmit(&mut write_context);
+        } else {
+            for _ in 0..5 {
+                write_context.frame_buffer.flush();
+                write_context.frame_buffer.set_error_write_after_n_frames(1);
+                let _ = manager.on_transmit(&mut write_context);
+            }
+        }
+
+        for (stream_id, expected) in [(control, 3 * round), (bulk_1, round), (bulk_2, round)] {
+            manager.with_asserted_stream(stream_id, |stream| {
+                assert_eq!(100 - expected, stream.on_transmit_try_write_frames);
+            });
+        }
+
+        assert_eq!(
+            [control, bulk_1, bulk_2],
+            *manager.streams_waiting_for_transmission()
+        );
+    }
+}
+
+/// Transmits a single frame on each call and returns the transmission list after each one
+fn transmit_single_frames(
+    manager: &mut AbstractStreamManager<MockStream>,
+    transmissions: usize,
+) -> Vec<Vec<StreamId>> {
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+
+    (0..transmissions)
+        .map(|_| {
+            write_context.frame_buffer.flush();
+            write_context.frame_buffer.set_error_write_after_n_frames(1);
+            let _ = manager.on_transmit(&mut write_context);
+            manager.streams_waiting_for_transmission()
+        })
+        .collect()
+}
+
+#[test]
+fn stream_transmission_fifo_test() {
+    let mut manager =
+        create_stream_manager_with_scheduler(endpoint::Type::Server, StreamSchedulingPolicy::Fifo);
+
+    let first = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let second = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let control = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    for stream_id in [first, second, control] {
+        manager.with_asserted_stream(stream_id, |stream| {
+            if stream_id == control {
+                stream.interests.priority = 2;
+            }
+            stream.on_transmit_try_write_frames = 2;
+            stream.on_transmit_limit = Some(1);
+        });
+    }
+
+    // the priority is ignored so the streams are queued in the order they became ready
+    assert_eq!(
+        [first, second, control],
+        *manager.streams_waiting_for_transmission()
+    );
+
+    // each stream transmits all of its data before the next one is serviced
+    assert_eq!(
+        transmit_single_frames(&mut manager, 6),
+        [
+            vec![first, second, control],
+            vec![second, control],
+            vec![second, control],
+            vec![control],
+            vec![control],
+            vec![],
+        ]
+    );
+}
+
+#[test]
+fn stream_transmission_strict_priority_test() {
+    let mut manager = create_stream_manager_with_scheduler(
+        endpoint::Type::Server,
+        StreamSchedulingPolicy::StrictPriority,
+    );
+
+    let low_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let low_2 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let high = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    for stream_id in [low_1, low_2, high] {
+        manager.with_asserted_stream(stream_id, |stream| {
+            if stream_id == high {
+                // with round-robin scheduling this would only give the stream 2 turns per round
+                stream.interests.priority = 1;
+                stream.on_transmit_try_write_frames = 4;
+            } else {
+                stream.on_transmit_try_write_frames = 2;
+            }
+            stream.on_transmit_limit = Some(1);
+        });
+    }
+
+    assert_eq!(
+        [high, low_1, low_2],
+        *manager.streams_waiting_for_transmission()
+    );
+
+    // the prioritized stream transmits all of its data before the others, which are then
+    // serviced in the order they became ready
+    assert_eq!(
+        transmit_single_frames(&mut manager, 8),
+        [
+            vec![high, low_1, low_2],
+            vec![high, low_1, low_2],
+            vec![high, low_1, low_2],
+            vec![low_1, low_2],
+            vec![low_1, low_2],
+            vec![low_2],
+            vec![low_2],
+            vec![],
+        ]
+    );
+}
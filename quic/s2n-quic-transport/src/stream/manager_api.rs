@@ -13,11 +13,14 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    ack, endpoint,
+    ack, application,
+    connection::TransmissionStatus,
+    endpoint, event,
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
         StopSending, StreamDataBlocked, StreamsBlocked,
     },
+    recovery::ProbePolicy,
     stream::{ops, StreamId, StreamType},
     time::{timer, Timestamp},
     transport::{self, parameters::InitialFlowControlLimits},
@@ -63,6 +66,14 @@ pub trait Manager:
         context: &Context,
     ) -> Poll<Result<StreamId, connection::Error>>;
 
+    /// Opens the lowest reserved local stream of a certain type
+    ///
+    /// Returns `None` if all of the reserved streams have already been opened.
+    fn open_reserved_stream(
+        &mut self,
+        stream_type: StreamType,
+    ) -> Result<Option<StreamId>, connection::Error>;
+
     /// This method gets called when a packet delivery got acknowledged
     fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A);
 
@@ -75,6 +86,10 @@ pub trait Manager:
     /// Called when the connection timer expires
     fn on_timeout(&mut self, now: Timestamp);
 
+    /// Called when the connection hibernates to release any transient buffers held by the
+    /// streams
+    fn hibernate(&mut self);
+
     /// Closes the manager and resets all streams with the
     /// given error. The current implementation will still
     /// allow to forward frames to the contained Streams as well as to query them
@@ -94,6 +109,14 @@ pub trait Manager:
     /// Queries the component for any outgoing frames that need to get sent
     fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> Result<(), OnTransmitError>;
 
+    /// Queries the component for the stream data to include in a PTO probe, as selected by the
+    /// probe `policy`
+    fn on_probe_transmit<W: WriteContext>(
+        &mut self,
+        context: &mut W,
+        policy: ProbePolicy,
+    ) -> Result<(), OnTransmitError>;
+
     // Frame reception
     // These functions are called from the packet delivery thread
 
@@ -140,6 +163,56 @@ pub trait Manager:
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError>;
 
+    /// Resets the sending half and/or stops the receiving half of every stream for which
+    /// `filter` returns `true`
+    ///
+    /// All of the streams are closed in a single pass and the connection is woken up at most
+    /// once. Returns the number of streams which matched the filter.
+    fn close_streams(
+        &mut self,
+        api_call_context: &mut ConnectionApiCallContext,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        filter: &mut dyn FnMut(StreamId) -> bool,
+    ) -> usize;
+
+    /// Raises the flow control window of the receiving half of a stream to `window` bytes
+    fn set_max_stream_data(
+        &mut self,
+        stream_id: StreamId,
+        api_call_context: &mut ConnectionApiCallContext,
+        window: u32,
+    ) -> Result<(), StreamError>;
+
+    /// Raises the number of streams of the given type the peer may have open concurrently
+    fn set_max_streams(
+        &mut self,
+        api_call_context: &mut ConnectionApiCallContext,
+        stream_type: StreamType,
+        limit: VarInt,
+    );
+
     /// Returns whether or not streams have data to send
     fn has_pending_streams(&self) -> bool;
+
+    /// Publishes the events for application-marked stream data which were recorded since the
+    /// last call
+    fn publish_marker_events<Pub: event::ConnectionPublisher>(&mut self, publisher: &mut Pub);
+
+    /// Publishes an event for each stream which was opened by the peer since the last call
+    fn publish_peer_stream_events<Pub: event::ConnectionPublisher>(&mut self, publisher: &mut Pub);
+
+    /// Returns the number of stream bytes which were retransmitted since the last call and resets
+    /// the counter
+    fn take_retransmitted_bytes(&mut self) -> u64;
+
+    /// Sets the maximum amount of time small stream writes are held back to fill packets, or
+    /// disables coalescing with `None`
+    fn set_tx_coalescing(&mut self, delay: Option<Duration>);
+
+    /// Called when the connection is woken up by the application
+    fn on_wakeup(&mut self, now: Timestamp, max_datagram_size: usize);
+
+    /// Populates the flow control and stream limit fields of the `TransmissionStatus`
+    fn update_transmission_status(&self, status: &mut TransmissionStatus);
 }
@@ -0,0 +1,250 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks ranges of stream data which were tagged by the application with a marker
+//!
+//! This is a debugging facility which allows applications to follow a specific message through
+//! the transport. Events are emitted each time a packet carries the tagged bytes, when those
+//! packets are lost, and once all of the bytes have been acknowledged by the peer.
+
+use alloc::vec::Vec;
+use s2n_quic_core::{
+    event,
+    interval_set::{Interval, IntervalSet},
+    packet::number::PacketNumber,
+    stream::StreamId,
+    varint::VarInt,
+};
+
+/// A marker event which is waiting to be published
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub stream_id: StreamId,
+    pub marker: u64,
+    pub kind: Kind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Sent {
+        packet_number: PacketNumber,
+        is_retransmission: bool,
+    },
+    Lost,
+    Acknowledged,
+}
+
+impl Event {
+    /// Publishes the event
+    #[inline]
+    pub fn publish<Pub: event::ConnectionPublisher>(&self, publisher: &mut Pub) {
+        let stream_id = self.stream_id.as_varint().as_u64();
+        let marker = self.marker;
+
+        match self.kind {
+            Kind::Sent {
+                packet_number,
+                is_retransmission,
+            } => {
+                let packet_header =
+                    event::builder::PacketHeader::new(packet_number, publisher.quic_version());
+                publisher.on_stream_marker_sent(event::builder::StreamMarkerSent {
+                    stream_id,
+                    marker,
+                    packet_header,
+                    is_retransmission,
+                });
+            }
+            Kind::Lost => {
+                publisher
+                    .on_stream_marker_lost(event::builder::StreamMarkerLost { stream_id, marker });
+            }
+            Kind::Acknowledged => {
+                publisher.on_stream_marker_acknowledged(event::builder::StreamMarkerAcknowledged {
+                    stream_id,
+                    marker,
+                });
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Range {
+    marker: u64,
+    interval: Interval<VarInt>,
+}
+
+impl Range {
+    #[inline]
+    fn intersects(&self, set: &IntervalSet<VarInt>) -> bool {
+        set.intervals().any(|interval| {
+            interval.start_inclusive() <= self.interval.end_inclusive()
+                && self.interval.start_inclusive() <= interval.end_inclusive()
+        })
+    }
+}
+
+/// Tracks the marked ranges of a send stream
+#[derive(Debug, Default)]
+pub struct Tracker {
+    ranges: Vec<Range>,
+    events: Vec<(u64, Kind)>,
+}
+
+impl Tracker {
+    /// Returns `true` if no ranges are currently being tracked
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Starts tracking the given range of stream data
+    #[inline]
+    pub fn insert(&mut self, marker: u64, start: VarInt, end: VarInt) {
+        if start < end {
+            let interval = (start..end).into();
+            self.ranges.push(Range { marker, interval });
+        }
+    }
+
+    /// Called after the stream wrote data into a packet
+    ///
+    /// `sent` contains the ranges which were transmitted for the first time and `retransmitted`
+    /// contains the ranges which had been declared lost.
+    pub fn on_transmit(
+        &mut self,
+        packet_number: PacketNumber,
+        sent: &IntervalSet<VarInt>,
+        retransmitted: &IntervalSet<VarInt>,
+    ) {
+        for range in &self.ranges {
+            if range.intersects(retransmitted) {
+                self.events.push((
+                    range.marker,
+                    Kind::Sent {
+                        packet_number,
+                        is_retransmission: true,
+                    },
+                ));
+            } else if range.intersects(sent) {
+                self.events.push((
+                    range.marker,
+                    Kind::Sent {
+                        packet_number,
+                        is_retransmission: false,
+                    },
+                ));
+            }
+        }
+    }
+
+    /// Called with the ranges which were newly declared lost
+    pub fn on_loss(&mut self, lost: &IntervalSet<VarInt>) {
+        for range in &self.ranges {
+            if range.intersects(lost) {
+                self.events.push((range.marker, Kind::Lost));
+            }
+        }
+    }
+
+    /// Called with the ranges which are still waiting to be acknowledged
+    pub fn on_ack(&mut self, pending: &IntervalSet<VarInt>) {
+        let events = &mut self.events;
+        self.ranges.retain(|range| {
+            if range.intersects(pending) {
+                return true;
+            }
+            events.push((range.marker, Kind::Acknowledged));
+            false
+        });
+    }
+
+    /// Stops tracking all of the ranges without emitting any events
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// Moves all of the pending events into `events`
+    #[inline]
+    pub fn drain(&mut self, stream_id: StreamId, events: &mut Vec<Event>) {
+        events.extend(self.events.drain(..).map(|(marker, kind)| Event {
+            stream_id,
+            marker,
+            kind,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{endpoint, packet::number::PacketNumberSpace, stream::StreamType};
+
+    fn set(ranges: &[(u32, u32)]) -> IntervalSet<VarInt> {
+        let mut set = IntervalSet::new();
+        for (start, end) in ranges {
+            set.insert(VarInt::from_u32(*start)..VarInt::from_u32(*end))
+                .unwrap();
+        }
+        set
+    }
+
+    #[test]
+    fn tracker_test() {
+        let stream_id = StreamId::initial(endpoint::Type::Client, StreamType::Bidirectional);
+        let packet_number =
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
+        let mut tracker = Tracker::default();
+        let mut events = Vec::new();
+
+        tracker.insert(1, VarInt::from_u32(0), VarInt::from_u32(100));
+        tracker.insert(2, VarInt::from_u32(100), VarInt::from_u32(200));
+        // empty ranges aren't tracked
+        tracker.insert(3, VarInt::from_u32(200), VarInt::from_u32(200));
+
+        tracker.on_transmit(packet_number, &set(&[(50, 150)]), &set(&[]));
+        tracker.on_loss(&set(&[(50, 100)]));
+        tracker.on_transmit(packet_number, &set(&[]), &set(&[(50, 100)]));
+        tracker.on_ack(&set(&[(150, 200)]));
+        tracker.drain(stream_id, &mut events);
+
+        let kinds: Vec<_> = events
+            .iter()
+            .map(|event| (event.marker, event.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (
+                    1,
+                    Kind::Sent {
+                        packet_number,
+                        is_retransmission: false
+                    }
+                ),
+                (
+                    2,
+                    Kind::Sent {
+                        packet_number,
+                        is_retransmission: false
+                    }
+                ),
+                (1, Kind::Lost),
+                (
+                    1,
+                    Kind::Sent {
+                        packet_number,
+                        is_retransmission: true
+                    }
+                ),
+                (1, Kind::Acknowledged),
+            ]
+        );
+
+        assert!(!tracker.is_empty());
+        tracker.on_ack(&set(&[]));
+        assert!(tracker.is_empty());
+    }
+}
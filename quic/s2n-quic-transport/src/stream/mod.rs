@@ -8,13 +8,16 @@ mod controller;
 mod incoming_connection_flow_controller;
 mod manager;
 mod manager_api;
+mod marker;
 mod outgoing_connection_flow_controller;
 mod receive_stream;
+mod scheduler;
 mod send_stream;
 mod stream_container;
 mod stream_events;
 mod stream_impl;
 mod stream_interests;
+mod tx_coalescer;
 
 #[cfg(debug_assertions)]
 pub(crate) mod contract;
@@ -4,7 +4,7 @@
 use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
-        incoming_connection_flow_controller::IncomingConnectionFlowController,
+        incoming_connection_flow_controller::{self, IncomingConnectionFlowController},
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
         StreamError,
@@ -188,13 +188,17 @@ impl ReceiveStreamFlowController {
         connection_flow_controller: IncomingConnectionFlowController,
         initial_window: VarInt,
         desired_flow_control_window: u32,
+        window_update_threshold: u8,
     ) -> Self {
         Self {
             connection_flow_controller,
             read_window_sync: IncrementalValueSync::new(
                 VarInt::from_u32(desired_flow_control_window),
                 initial_window,
-                VarInt::from_u32(desired_flow_control_window / 10),
+                incoming_connection_flow_controller::window_update_threshold(
+                    desired_flow_control_window,
+                    window_update_threshold,
+                ),
             ),
             acquired_connection_window: VarInt::from_u32(0),
             released_connection_window: VarInt::from_u32(0),
@@ -297,6 +301,22 @@ impl ReceiveStreamFlowController {
         self.release_window(unreleased);
     }
 
+    /// Raises the relative flow control window the stream maintains
+    ///
+    /// The additional credit is advertised to the peer right away. Requests to lower the window
+    /// are ignored, since the peer may already have used the credit which was advertised.
+    fn raise_window(&mut self, desired_flow_control_window: u32) {
+        if desired_flow_control_window <= self.desired_flow_control_window {
+            return;
+        }
+
+        self.desired_flow_control_window = desired_flow_control_window;
+        self.read_window_sync.update_latest_value_immediately(
+            self.released_connection_window
+                .saturating_add(VarInt::from_u32(desired_flow_control_window)),
+        );
+    }
+
     /// Stop to synchronize the Streams flow control window to the peer
     fn stop_sync(&mut self) {
         self.read_window_sync.stop_sync();
@@ -355,6 +375,7 @@ impl ReceiveStream {
         connection_flow_controller: IncomingConnectionFlowController,
         initial_window: VarInt,
         desired_flow_control_window: u32,
+        window_update_threshold: u8,
     ) -> ReceiveStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -371,6 +392,7 @@ impl ReceiveStream {
                 connection_flow_controller,
                 initial_window,
                 desired_flow_control_window,
+                window_update_threshold,
             ),
             stop_sending_sync: OnceSync::new(),
             read_waiter: None,
@@ -559,6 +581,16 @@ impl ReceiveStream {
         Ok(())
     }
 
+    /// Releases any transient buffers while the connection is hibernating
+    pub fn hibernate(&mut self) {
+        self.receive_buffer.shrink_to_fit();
+    }
+
+    /// Raises the flow control window of the stream to `window` bytes
+    pub fn set_receive_window(&mut self, window: u32) {
+        self.flow_controller.raise_window(window);
+    }
+
     /// This method gets called when a stream gets reset due to a reason that is
     /// not related to a frame. E.g. due to a connection failure.
     pub fn on_internal_reset(&mut self, error: StreamError, events: &mut StreamEvents) {
@@ -738,6 +770,38 @@ impl ReceiveStream {
         &mut self,
         request: &mut ops::rx::Request,
         context: Option<&Context>,
+    ) -> Result<ops::rx::Response, StreamError> {
+        // The receive buffer is cleared once all of the data has been read so the final size
+        // needs to be captured beforehand
+        let final_size = self.receive_buffer.final_size();
+
+        let mut response = self.poll_request_impl(request, context)?;
+
+        if request.offsets {
+            response.offsets = Some(self.offsets(final_size));
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the current offsets of the stream
+    fn offsets(&self, final_size: Option<u64>) -> ops::rx::Offsets {
+        match (&self.state, final_size) {
+            (ReceiveStreamState::DataRead, Some(final_size)) => ops::rx::Offsets {
+                consumed: final_size,
+                received: final_size,
+            },
+            _ => ops::rx::Offsets {
+                consumed: self.receive_buffer.consumed_len(),
+                received: self.receive_buffer.total_received_len(),
+            },
+        }
+    }
+
+    fn poll_request_impl(
+        &mut self,
+        request: &mut ops::rx::Request,
+        context: Option<&Context>,
     ) -> Result<ops::rx::Response, StreamError> {
         let mut response = ops::rx::Response::default();
 
@@ -1119,6 +1119,59 @@ fn flow_control_window_update_is_only_sent_when_minimum_data_size_is_consumed()
     test_env.assert_write_frames(0);
 }
 
+#[test]
+fn flow_control_window_update_threshold_is_configurable() {
+    let mut test_env_config = TestEnvironmentConfig::new(endpoint::Type::Server);
+    test_env_config.stream_id = StreamId::initial(
+        test_env_config.local_endpoint_type.peer_type(),
+        StreamType::Unidirectional,
+    );
+    test_env_config.stream_window_update_threshold = 50;
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    let initial_window: u64 = test_env
+        .stream
+        .receive_stream
+        .flow_controller
+        .current_stream_receive_window()
+        .into();
+    let required_for_read_window_update = initial_window / 2;
+
+    // Consuming less than half of the window doesn't send an update
+    test_env.feed_data(
+        VarInt::from_u32(0),
+        required_for_read_window_update as usize - 1,
+    );
+    assert_eq!(
+        required_for_read_window_update as usize - 1,
+        test_env.consume_all_data()
+    );
+    assert_eq!(
+        stream_interests(&[]),
+        test_env.stream.get_stream_interests()
+    );
+    test_env.assert_write_frames(0);
+
+    // Consuming one more byte reaches the threshold
+    test_env.feed_data(VarInt::new(required_for_read_window_update - 1).unwrap(), 1);
+    assert_eq!(1, test_env.consume_all_data());
+    assert_eq!(
+        stream_interests(&["tx"]),
+        test_env.stream.get_stream_interests()
+    );
+
+    test_env.assert_write_frames(1);
+    let mut sent_frame = test_env.sent_frames.pop_front().expect("Frame is written");
+    assert_eq!(
+        Frame::MaxStreamData(MaxStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            maximum_stream_data: VarInt::new(initial_window + required_for_read_window_update)
+                .unwrap(),
+        }),
+        sent_frame.as_frame()
+    );
+}
+
 #[test]
 fn connection_flow_control_window_update_is_only_sent_when_minimum_data_size_is_consumed() {
     let test_env_config = conn_flow_control_test_env_config();
@@ -2350,6 +2403,7 @@ fn receive_multiple_chunks_and_finishing_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Finished,
+                offsets: None,
             }),
             ..Default::default()
         }),
@@ -2401,6 +2455,7 @@ fn receive_low_watermark_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                offsets: None,
             }),
             ..Default::default()
         })),
@@ -2452,6 +2507,7 @@ fn receive_low_watermark_with_data_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                offsets: None,
             }),
             ..Default::default()
         })),
@@ -2493,6 +2549,7 @@ fn receive_high_watermark_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                offsets: None,
             }),
             ..Default::default()
         })),
@@ -2517,6 +2574,7 @@ fn receive_high_watermark_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                offsets: None,
             }),
             ..Default::default()
         })),
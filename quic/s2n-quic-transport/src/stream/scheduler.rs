@@ -0,0 +1,126 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Disciplines for scheduling the transmission of `Stream`s on a connection
+
+use crate::stream::stream_interests::StreamInterests;
+pub use s2n_quic_core::connection::limits::StreamSchedulingPolicy;
+
+/// Where a Stream is inserted into the list of Streams waiting for transmission
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    /// Behind all of the Streams with the same or a higher priority
+    Priority,
+    /// At the front of the list
+    Front,
+    /// At the back of the list, regardless of priority
+    Back,
+}
+
+/// Decides the order in which Streams are given transmission capacity
+pub trait Scheduler {
+    /// Returns where a Stream which became ready to transmit is inserted
+    fn on_ready(&self) -> Placement;
+
+    /// Returns where a Stream which used all of its turns is inserted
+    fn on_yield(&self) -> Placement;
+
+    /// Returns the number of consecutive turns a Stream is given before yielding to the other
+    /// Streams
+    fn turns(&self, interests: &StreamInterests) -> u16;
+}
+
+/// Streams transmit in the order they became ready, until they run out of data
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fifo;
+
+impl Scheduler for Fifo {
+    #[inline]
+    fn on_ready(&self) -> Placement {
+        Placement::Back
+    }
+
+    #[inline]
+    fn on_yield(&self) -> Placement {
+        Placement::Back
+    }
+
+    #[inline]
+    fn turns(&self, _interests: &StreamInterests) -> u16 {
+        u16::MAX
+    }
+}
+
+/// Streams take `priority + 1` consecutive turns before moving to the back of the list
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoundRobin;
+
+impl Scheduler for RoundRobin {
+    #[inline]
+    fn on_ready(&self) -> Placement {
+        Placement::Priority
+    }
+
+    #[inline]
+    fn on_yield(&self) -> Placement {
+        Placement::Back
+    }
+
+    #[inline]
+    fn turns(&self, interests: &StreamInterests) -> u16 {
+        interests.transmission_weight()
+    }
+}
+
+/// Streams with a higher priority transmit all of their data before lower priority Streams
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StrictPriority;
+
+impl Scheduler for StrictPriority {
+    #[inline]
+    fn on_ready(&self) -> Placement {
+        Placement::Priority
+    }
+
+    #[inline]
+    fn on_yield(&self) -> Placement {
+        Placement::Priority
+    }
+
+    #[inline]
+    fn turns(&self, _interests: &StreamInterests) -> u16 {
+        u16::MAX
+    }
+}
+
+impl Scheduler for StreamSchedulingPolicy {
+    #[inline]
+    fn on_ready(&self) -> Placement {
+        match self {
+            Self::Fifo => Fifo.on_ready(),
+            Self::RoundRobin => RoundRobin.on_ready(),
+            Self::StrictPriority => StrictPriority.on_ready(),
+            _ => RoundRobin.on_ready(),
+        }
+    }
+
+    #[inline]
+    fn on_yield(&self) -> Placement {
+        match self {
+            Self::Fifo => Fifo.on_yield(),
+            Self::RoundRobin => RoundRobin.on_yield(),
+            Self::StrictPriority => StrictPriority.on_yield(),
+            _ => RoundRobin.on_yield(),
+        }
+    }
+
+    #[inline]
+    fn turns(&self, interests: &StreamInterests) -> u16 {
+        match self {
+            Self::Fifo => Fifo.turns(interests),
+            Self::RoundRobin => RoundRobin.turns(interests),
+            Self::StrictPriority => StrictPriority.turns(interests),
+            _ => RoundRobin.turns(interests),
+        }
+    }
+}
@@ -4,6 +4,7 @@
 use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
+        marker,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
@@ -16,6 +17,7 @@ use crate::{
     transmission,
     transmission::interest::Provider as _,
 };
+use alloc::vec::Vec;
 use bytes::Bytes;
 use core::{
     convert::TryFrom,
@@ -25,6 +27,7 @@ use core::{
 use s2n_quic_core::{
     ack, application,
     frame::{MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    interval_set::IntervalSet,
     packet::number::PacketNumber,
     stream::{ops, StreamId},
     time::{timer, Timestamp},
@@ -32,6 +35,10 @@ use s2n_quic_core::{
     varint::VarInt,
 };
 
+mod stats;
+
+use stats::Stall;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-3.1
 //#          o
 //#          | Create Stream (Sending)
@@ -457,6 +464,14 @@ pub struct SendStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// Tracks the ranges of data which were tagged by the application
+    markers: marker::Tracker,
+    /// The handle of a task that is waiting on the data up to an offset to be acknowledged
+    ack_waiter: Option<(Waker, u64)>,
+    /// The transmission priority of the stream, as set by the application
+    priority: u8,
+    /// Tracks the age of the stream and the time it spent stalled
+    stats: stats::Tracker,
 }
 
 impl SendStream {
@@ -485,6 +500,10 @@ impl SendStream {
             write_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            markers: marker::Tracker::default(),
+            ack_waiter: None,
+            priority: 0,
+            stats: stats::Tracker::default(),
         };
 
         if is_closed {
@@ -580,6 +599,16 @@ impl SendStream {
     /// This method gets called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents) {
         self.data_sender.on_packet_ack(ack_set);
+        if !self.markers.is_empty() {
+            self.markers.on_ack(self.data_sender.pending());
+        }
+        if let Some((_, offset)) = self.ack_waiter.as_ref() {
+            if self.data_sender.acknowledged_offset().as_u64() >= *offset {
+                if let Some((waker, _)) = self.ack_waiter.take() {
+                    events.store_ack_waker(waker);
+                }
+            }
+        }
         self.data_sender
             .flow_controller_mut()
             .on_packet_ack(ack_set);
@@ -639,7 +668,17 @@ impl SendStream {
 
     /// This method gets called when a packet loss is reported
     pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
-        self.data_sender.on_packet_loss(ack_set);
+        if self.markers.is_empty() {
+            self.data_sender.on_packet_loss(ack_set);
+        } else {
+            let previously_lost = self.data_sender.lost().clone();
+            self.data_sender.on_packet_loss(ack_set);
+            // only report the ranges which were newly declared lost
+            let mut lost = self.data_sender.lost().clone();
+            lost.difference(&previously_lost)
+                .expect("lost has no interval limit");
+            self.markers.on_loss(&lost);
+        }
         self.data_sender
             .flow_controller_mut()
             .on_packet_loss(ack_set);
@@ -651,14 +690,98 @@ impl SendStream {
         &mut self,
         stream_id: StreamId,
         context: &mut W,
+    ) -> Result<(), OnTransmitError> {
+        let now = context.current_time();
+        self.stats.on_transmit(now);
+
+        let result = self.on_transmit_impl(stream_id, context);
+
+        self.stats.on_stall(self.stall(&result), now);
+
+        result
+    }
+
+    fn on_transmit_impl<W: WriteContext>(
+        &mut self,
+        stream_id: StreamId,
+        context: &mut W,
     ) -> Result<(), OnTransmitError> {
         self.reset_sync.on_transmit(stream_id, context)?;
-        self.data_sender.on_transmit(stream_id.into(), context)?;
+
+        if self.markers.is_empty() {
+            self.data_sender.on_transmit(stream_id.into(), context)?;
+        } else {
+            self.on_transmit_marked_data(stream_id, context)?;
+        }
+
         self.data_sender
             .flow_controller_mut()
             .on_transmit(stream_id, context)
     }
 
+    /// Returns the reason the stream was unable to transmit all of its data, if any
+    fn stall(&self, result: &Result<(), OnTransmitError>) -> Option<Stall> {
+        if !matches!(self.state, SendStreamState::Sending) {
+            return None;
+        }
+
+        match self.data_sender.flow_controller().state() {
+            StreamFlowControllerState::BlockedOnStreamWindow => Some(Stall::StreamFlowControl),
+            StreamFlowControllerState::BlockedOnConnectionWindow => {
+                Some(Stall::ConnectionFlowControl)
+            }
+            _ if result.is_err()
+                && (self.data_sender.unsent_len() > VarInt::ZERO
+                    || !self.data_sender.lost().is_empty()) =>
+            {
+                Some(Stall::Congestion)
+            }
+            _ => None,
+        }
+    }
+
+    /// Transmits stream data while recording which of the marked ranges were written
+    fn on_transmit_marked_data<W: WriteContext>(
+        &mut self,
+        stream_id: StreamId,
+        context: &mut W,
+    ) -> Result<(), OnTransmitError> {
+        let packet_number = context.packet_number();
+        let transmission_offset = self.data_sender.transmission_offset();
+        let mut retransmitted = self.data_sender.lost().clone();
+
+        let result = self.data_sender.on_transmit(stream_id.into(), context);
+
+        // anything which is no longer in the lost set was written into this packet
+        retransmitted
+            .difference(self.data_sender.lost())
+            .expect("lost has no interval limit");
+
+        let mut sent = IntervalSet::new();
+        if transmission_offset < self.data_sender.transmission_offset() {
+            sent.insert(transmission_offset..self.data_sender.transmission_offset())
+                .expect("sent has no interval limit");
+        }
+
+        if !(sent.is_empty() && retransmitted.is_empty()) {
+            self.markers
+                .on_transmit(packet_number, &sent, &retransmitted);
+        }
+
+        result
+    }
+
+    /// Moves all of the pending marker events into `events`
+    pub fn drain_marker_events(&mut self, stream_id: StreamId, events: &mut Vec<marker::Event>) {
+        self.markers.drain(stream_id, events);
+    }
+
+    /// Returns the number of bytes which were retransmitted since the last call
+    #[inline]
+    pub fn take_retransmitted_bytes(&mut self) -> u64 {
+        self.data_sender.take_retransmitted_len()
+    }
+
     /// Updates the period at which `STREAM_DATA_BLOCKED` frames are sent to the peer
     /// if the application is blocked by peer limits.
     pub fn update_blocked_sync_period(&mut self, blocked_sync_period: Duration) {
@@ -672,6 +795,11 @@ impl SendStream {
         self.data_sender.flow_controller_mut().on_timeout(now)
     }
 
+    /// Releases any transient buffers while the connection is hibernating
+    pub fn hibernate(&mut self) {
+        self.data_sender.shrink_to_fit();
+    }
+
     /// A reset that is triggered without having received a `RESET` frame.
     pub fn on_internal_reset(&mut self, error: StreamError, events: &mut StreamEvents) {
         let _ = self.init_reset(
@@ -713,6 +841,16 @@ impl SendStream {
     /// If there is not a registered waker and the stream is in a terminal state,
     /// the stream will be finalized.
     fn wake(&mut self, events: &mut StreamEvents) {
+        // the requested offset can't be acknowledged once the stream is reset or finished so
+        // notify the acknowledgement waiter to observe the final state
+        let is_terminal = !matches!(self.state, SendStreamState::Sending)
+            || self.data_sender.state() == data_sender::State::Finished;
+        if is_terminal {
+            if let Some((waker, _offset)) = self.ack_waiter.take() {
+                events.store_ack_waker(waker);
+            }
+        }
+
         if let Some((waker, _should_flush)) = self.write_waiter.take() {
             events.store_write_waker(waker);
             return;
@@ -744,10 +882,72 @@ impl SendStream {
     ///
     /// If the `Stream` gets reset while waiting for acknowledgement of all
     /// outstanding data the method will return a [`StreamError`].
+    ///
+    /// A `buffer_size` in the request replaces the send buffer capacity of the
+    /// `Stream` before any of the provided chunks are enqueued.
     pub fn poll_request(
         &mut self,
         request: &mut ops::tx::Request,
         context: Option<&Context>,
+    ) -> Result<ops::tx::Response, StreamError> {
+        if let Some(buffer_size) = request.buffer_size {
+            self.data_sender.set_max_buffer_capacity(buffer_size);
+        }
+
+        if let Some(priority) = request.priority {
+            self.priority = priority;
+        }
+
+        let enqueued = self.data_sender.total_enqueued_len();
+
+        let mut response = self.poll_request_impl(request, context)?;
+
+        if let Some(marker) = request.marker {
+            self.markers
+                .insert(marker, enqueued, self.data_sender.total_enqueued_len());
+        }
+
+        if request.buffer_stats {
+            response.buffer = Some(self.buffer_stats());
+        }
+
+        if let Some(offset) = request.ack_offset {
+            if self.data_sender.acknowledged_offset().as_u64() < offset {
+                // notify the caller once the data up to the offset has been acknowledged
+                if let Some(context) = context {
+                    self.ack_waiter = Some((context.waker().clone(), offset));
+                    response.will_wake = true;
+                }
+            } else {
+                self.ack_waiter = None;
+            }
+        }
+
+        if request.offsets {
+            response.offsets = Some(ops::tx::Offsets {
+                enqueued: self.data_sender.total_enqueued_len().as_u64(),
+                acknowledged: self.data_sender.acknowledged_offset().as_u64(),
+            });
+        }
+
+        if request.stats {
+            let mut stats = ops::tx::Stats {
+                bytes_sent: self.data_sender.transmission_offset().as_u64(),
+                bytes_acknowledged: self.data_sender.acknowledged_offset().as_u64(),
+                bytes_retransmitted: self.data_sender.total_retransmitted_len(),
+                ..Default::default()
+            };
+            self.stats.update(&mut stats);
+            response.stats = Some(stats);
+        }
+
+        Ok(response)
+    }
+
+    fn poll_request_impl(
+        &mut self,
+        request: &mut ops::tx::Request,
+        context: Option<&Context>,
     ) -> Result<ops::tx::Response, StreamError> {
         let mut response = ops::tx::Response::default();
 
@@ -835,7 +1035,11 @@ impl SendStream {
                 self.data_sender
                     .push(core::mem::replace(chunk, Bytes::new()));
             }
-        } else if !request.finish && !request.flush && context.is_some() {
+        } else if !request.finish
+            && !request.flush
+            && request.ack_offset.is_none()
+            && context.is_some()
+        {
             // if `chunks` are `None` or `Some(&[])` and we're not ending or flushing the stream,
             // the caller is only interested in notifications of state changes.
 
@@ -921,6 +1125,15 @@ impl SendStream {
         }
     }
 
+    /// Returns the current usage of the send buffer
+    fn buffer_stats(&self) -> ops::tx::BufferStats {
+        ops::tx::BufferStats {
+            capacity: self.data_sender.max_buffer_capacity().as_u64(),
+            unsent: self.data_sender.unsent_len().as_u64(),
+            unacknowledged: self.data_sender.unacknowledged_len().as_u64(),
+        }
+    }
+
     /// Returns true if the caller can push additional data
     fn can_push(&self) -> bool {
         // We accept the data if there is at least 1 byte of space
@@ -984,6 +1197,7 @@ impl SendStream {
         // Clear the send buffer. Since we initiated a RESET, there is no need
         // to send or resend the remaining data.
         self.data_sender.stop_sending(error);
+        self.markers.clear();
 
         // For an internal reset (which provides no error_code) we do not need
         // to transmit the reset frame
@@ -1059,6 +1273,7 @@ impl StreamInterestProvider for SendStream {
 
         // let the stream container know we still have work to do
         interests.retained = true;
+        interests.priority = interests.priority.max(self.priority);
 
         // Check whether the flow controller reports being blocked on the
         // connection flow control window or the stream flow control window
@@ -0,0 +1,108 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+use s2n_quic_core::{stream::ops, time::Timestamp};
+
+/// The reason a stream was unable to transmit all of its data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stall {
+    /// The stream had data ready to send but the connection was unable to transmit it
+    Congestion,
+    /// The stream was blocked by the peer's stream flow control limit
+    StreamFlowControl,
+    /// The stream was blocked by the peer's connection flow control limit
+    ConnectionFlowControl,
+}
+
+/// Tracks how long a `SendStream` has existed and how long it was stalled
+///
+/// A stall starts after a transmission in which the stream was unable to send all of its data,
+/// and ends the next time the stream is given an opportunity to transmit.
+#[derive(Debug, Default)]
+pub struct Tracker {
+    /// The first time the stream was given an opportunity to transmit
+    started: Option<Timestamp>,
+    /// The most recent time the stream was given an opportunity to transmit
+    updated: Option<Timestamp>,
+    /// The ongoing stall and the time it started
+    stall: Option<(Stall, Timestamp)>,
+    congestion_blocked: Duration,
+    stream_flow_control_blocked: Duration,
+    connection_flow_control_blocked: Duration,
+}
+
+impl Tracker {
+    /// Called before the stream transmits, which ends any ongoing stall
+    #[inline]
+    pub fn on_transmit(&mut self, now: Timestamp) {
+        self.started.get_or_insert(now);
+        self.updated = Some(now);
+
+        if let Some((stall, since)) = self.stall.take() {
+            let elapsed = now.saturating_duration_since(since);
+            let total = match stall {
+                Stall::Congestion => &mut self.congestion_blocked,
+                Stall::StreamFlowControl => &mut self.stream_flow_control_blocked,
+                Stall::ConnectionFlowControl => &mut self.connection_flow_control_blocked,
+            };
+            *total += elapsed;
+        }
+    }
+
+    /// Called after the stream transmitted with the reason it couldn't send all of its data
+    #[inline]
+    pub fn on_stall(&mut self, stall: Option<Stall>, now: Timestamp) {
+        self.stall = stall.map(|stall| (stall, now));
+    }
+
+    /// Fills in the durations of the provided `stats`
+    #[inline]
+    pub fn update(&self, stats: &mut ops::tx::Stats) {
+        stats.congestion_blocked = self.congestion_blocked;
+        stats.stream_flow_control_blocked = self.stream_flow_control_blocked;
+        stats.connection_flow_control_blocked = self.connection_flow_control_blocked;
+        stats.age = match (self.started, self.updated) {
+            (Some(started), Some(updated)) => updated.saturating_duration_since(started),
+            _ => Duration::ZERO,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{Clock, NoopClock};
+
+    #[test]
+    fn stall_test() {
+        let now = NoopClock {}.get_time();
+        let ms = Duration::from_millis;
+        let mut tracker = Tracker::default();
+        let mut stats = ops::tx::Stats::default();
+
+        tracker.update(&mut stats);
+        assert_eq!(stats, ops::tx::Stats::default());
+
+        tracker.on_transmit(now);
+        tracker.on_stall(Some(Stall::StreamFlowControl), now);
+        tracker.on_transmit(now + ms(10));
+        tracker.on_stall(Some(Stall::Congestion), now + ms(10));
+        tracker.on_transmit(now + ms(15));
+        tracker.on_stall(None, now + ms(15));
+        tracker.on_transmit(now + ms(20));
+        tracker.on_stall(Some(Stall::ConnectionFlowControl), now + ms(20));
+
+        // the ongoing stall isn't included until the stream transmits again
+        tracker.update(&mut stats);
+        assert_eq!(stats.stream_flow_control_blocked, ms(10));
+        assert_eq!(stats.congestion_blocked, ms(5));
+        assert_eq!(stats.connection_flow_control_blocked, Duration::ZERO);
+        assert_eq!(stats.age, ms(20));
+
+        tracker.on_transmit(now + ms(50));
+        tracker.update(&mut stats);
+        assert_eq!(stats.connection_flow_control_blocked, ms(30));
+        assert_eq!(stats.age, ms(50));
+    }
+}
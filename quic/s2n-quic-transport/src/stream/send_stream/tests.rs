@@ -2728,6 +2728,9 @@ fn can_send_multiple_chunks() {
                                     ops::Status::Open
                                 },
                                 will_wake,
+                                buffer: None,
+                                offsets: None,
+                                stats: None,
                             }),
                             rx: None,
                         }),
@@ -2847,6 +2850,9 @@ fn can_query_stream_readiness() {
                         },
                         status: ops::Status::Open,
                         will_wake: with_context && expected_buffer_size == 0,
+                        buffer: None,
+                        offsets: None,
+                        stats: None,
                     }),
                     rx: None,
                 }),
@@ -2854,3 +2860,144 @@ fn can_query_stream_readiness() {
         }
     }
 }
+
+#[test]
+fn can_override_buffer_size() {
+    let test_env_config = TestEnvironmentConfig {
+        max_send_buffer_size: 1500,
+        stream_id: StreamId::initial(endpoint::Type::Client, StreamType::Unidirectional),
+        local_endpoint_type: endpoint::Type::Client,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    fn buffer_stats(test_env: &mut TestEnvironment) -> ops::tx::BufferStats {
+        test_env
+            .run_request(ops::Request::default().send_buffer_stats(), false)
+            .expect("request should succeed")
+            .tx
+            .and_then(|tx| tx.buffer)
+            .expect("buffer stats should be returned")
+    }
+
+    let response = test_env
+        .run_request(ops::Request::default().with_send_buffer_size(500), false)
+        .expect("request should succeed");
+    assert_eq!(response.tx.unwrap().bytes.available, 500);
+
+    assert_eq!(
+        buffer_stats(&mut test_env),
+        ops::tx::BufferStats {
+            capacity: 500,
+            unsent: 0,
+            unacknowledged: 0,
+        }
+    );
+
+    let response = test_env
+        .run_request(
+            ops::Request::default().send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[300])),
+            false,
+        )
+        .expect("request should succeed");
+    assert_eq!(response.tx.unwrap().bytes.available, 200);
+
+    assert_eq!(
+        buffer_stats(&mut test_env),
+        ops::tx::BufferStats {
+            capacity: 500,
+            unsent: 300,
+            unacknowledged: 0,
+        }
+    );
+
+    let mut packets = 0;
+    while test_env.transmit().is_some() {
+        packets += 1;
+    }
+
+    assert_eq!(
+        buffer_stats(&mut test_env),
+        ops::tx::BufferStats {
+            capacity: 500,
+            unsent: 0,
+            unacknowledged: 300,
+        }
+    );
+
+    for idx in 0..packets {
+        test_env.ack_packet(pn(idx), ExpectWakeup(None));
+    }
+
+    assert_eq!(
+        buffer_stats(&mut test_env),
+        ops::tx::BufferStats {
+            capacity: 500,
+            unsent: 0,
+            unacknowledged: 0,
+        }
+    );
+
+    // lowering the size below the buffered amount blocks further writes
+    test_env
+        .run_request(
+            ops::Request::default()
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u32(300), &[300])),
+            false,
+        )
+        .expect("request should succeed");
+
+    let response = test_env
+        .run_request(ops::Request::default().with_send_buffer_size(100), false)
+        .expect("request should succeed");
+    assert_eq!(response.tx.unwrap().bytes.available, 0);
+}
+
+#[test]
+fn wait_for_ack_test() {
+    let mut test_env = setup_send_only_test_env();
+
+    fn wait_for_ack(test_env: &mut TestEnvironment, offset: u64) -> ops::tx::Response {
+        test_env
+            .run_request(
+                ops::Request::default().wait_for_ack(offset).send_offsets(),
+                true,
+            )
+            .expect("request should succeed")
+            .tx
+            .unwrap()
+    }
+
+    assert_eq!(
+        test_env.poll_push(Bytes::from_static(b"123")),
+        Poll::Ready(Ok(()))
+    );
+    assert!(test_env.transmit().is_some());
+    assert_eq!(
+        test_env.poll_push(Bytes::from_static(b"456")),
+        Poll::Ready(Ok(()))
+    );
+    assert!(test_env.transmit().is_some());
+
+    let response = wait_for_ack(&mut test_env, 3);
+    assert!(response.will_wake);
+    assert_eq!(response.offsets.unwrap().acknowledged, 0);
+
+    // acknowledging the second packet leaves a gap so the offset isn't reached
+    test_env.ack_packet(pn(1), ExpectWakeup(Some(false)));
+    // acknowledging the first packet reaches the offset
+    test_env.ack_packet(pn(0), ExpectWakeup(Some(true)));
+
+    let response = wait_for_ack(&mut test_env, 3);
+    assert!(!response.will_wake);
+    assert_eq!(response.offsets.unwrap().acknowledged, 6);
+
+    // waiting for an offset which hasn't been sent yet wakes the caller on a reset
+    let response = wait_for_ack(&mut test_env, 10);
+    assert!(response.will_wake);
+    let mut events = StreamEvents::new();
+    test_env
+        .stream
+        .on_internal_reset(connection::Error::unspecified().into(), &mut events);
+    assert_eq!(events.waker_count(), 1);
+}
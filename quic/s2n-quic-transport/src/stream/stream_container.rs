@@ -9,11 +9,18 @@
 
 use crate::{
     stream,
-    stream::{stream_impl::StreamTrait, stream_interests::StreamInterests},
+    stream::{
+        scheduler::{Placement, Scheduler, StreamSchedulingPolicy},
+        stream_impl::StreamTrait,
+        stream_interests::StreamInterests,
+    },
     transmission,
 };
 use alloc::rc::Rc;
-use core::{cell::RefCell, ops::Deref};
+use core::{
+    cell::{Cell, RefCell},
+    ops::Deref,
+};
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
 };
@@ -79,6 +86,10 @@ struct StreamNode<S> {
     waiting_for_connection_flow_control_credits_link: LinkedListLink,
     /// Allows the Stream to be part of the `waiting_for_stream_flow_control_credits` collection
     waiting_for_stream_flow_control_credits_link: LinkedListLink,
+    /// The transmission priority last reported by the Stream
+    priority: Cell<u8>,
+    /// The number of consecutive transmission turns the Stream has used in the current round
+    transmission_turns: Cell<u16>,
 }
 
 impl<S> StreamNode<S> {
@@ -93,6 +104,8 @@ impl<S> StreamNode<S> {
             waiting_for_retransmission_link: LinkedListLink::new(),
             waiting_for_connection_flow_control_credits_link: LinkedListLink::new(),
             waiting_for_stream_flow_control_credits_link: LinkedListLink::new(),
+            priority: Cell::new(0),
+            transmission_turns: Cell::new(0),
         }
     }
 }
@@ -145,10 +158,12 @@ struct InterestLists<S> {
     /// stream flow control window to increase
     waiting_for_stream_flow_control_credits:
         LinkedList<WaitingForStreamFlowControlCreditsAdapter<S>>,
+    /// Orders the Streams in the `waiting_for_transmission` list
+    scheduler: StreamSchedulingPolicy,
 }
 
 impl<S: StreamTrait> InterestLists<S> {
-    fn new() -> Self {
+    fn new(scheduler: StreamSchedulingPolicy) -> Self {
         Self {
             done_streams: LinkedList::new(DoneStreamsAdapter::new()),
             waiting_for_frame_delivery: LinkedList::new(WaitingForFrameDeliveryAdapter::new()),
@@ -160,6 +175,7 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits: LinkedList::new(
                 WaitingForStreamFlowControlCreditsAdapter::new(),
             ),
+            scheduler,
         }
     }
 
@@ -170,6 +186,25 @@ impl<S: StreamTrait> InterestLists<S> {
         interests: StreamInterests,
         result: StreamContainerIterationResult,
     ) -> bool {
+        let placement = match result {
+            StreamContainerIterationResult::Continue => self.scheduler.on_ready(),
+            StreamContainerIterationResult::BreakAndInsertAtBack => Placement::Front,
+        };
+        self.update_interests_with_placement(node, interests, result, placement)
+    }
+
+    /// Update all interest lists based on latest interest reported by a Node
+    ///
+    /// `placement` controls where the Node is inserted in the `waiting_for_transmission`
+    /// list, if it wasn't already part of it.
+    fn update_interests_with_placement(
+        &mut self,
+        node: &Rc<StreamNode<S>>,
+        interests: StreamInterests,
+        result: StreamContainerIterationResult,
+        placement: Placement,
+    ) -> bool {
+        node.priority.set(interests.priority);
         // Note that all comparisons start by checking whether the stream is
         // already part of the given list. This is required in order for the
         // following operation to be safe. Inserting an element in a list while
@@ -206,11 +241,34 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_frame_delivery_link,
             waiting_for_frame_delivery
         );
-        sync_interests!(
-            matches!(interests.transmission, transmission::Interest::NewData),
-            waiting_for_transmission_link,
-            waiting_for_transmission
+        let transmission_interest =
+            matches!(interests.transmission, transmission::Interest::NewData);
+        if transmission_interest != node.waiting_for_transmission_link.is_linked() {
+            if transmission_interest {
+                match placement {
+                    Placement::Priority => self.insert_by_priority(node),
+                    Placement::Front => self.waiting_for_transmission.push_front(node.clone()),
+                    Placement::Back => self.waiting_for_transmission.push_back(node.clone()),
+                }
+            } else {
+                // Safety: We know that the node is only ever part of this list.
+                let mut cursor = unsafe {
+                    self.waiting_for_transmission
+                        .cursor_mut_from_ptr(node.deref() as *const StreamNode<S>)
+                };
+                cursor.remove();
+            }
+        }
+        debug_assert_eq!(
+            transmission_interest,
+            node.waiting_for_transmission_link.is_linked()
         );
+
+        if !transmission_interest {
+            // start a new round once the stream has data to transmit again
+            node.transmission_turns.set(0);
+        }
+
         sync_interests!(
             matches!(interests.transmission, transmission::Interest::LostData),
             waiting_for_retransmission_link,
@@ -238,6 +296,25 @@ impl<S: StreamTrait> InterestLists<S> {
             false
         }
     }
+
+    /// Inserts the Node into the `waiting_for_transmission` list behind all of the
+    /// Streams with the same or a higher priority
+    fn insert_by_priority(&mut self, node: &Rc<StreamNode<S>>) {
+        let priority = node.priority.get();
+        let mut cursor = self.waiting_for_transmission.back_mut();
+
+        // Streams with the default priority always end up at the back, so this only
+        // walks the list if the application prioritized any of the streams
+        while cursor
+            .get()
+            .map_or(false, |stream| stream.priority.get() < priority)
+        {
+            cursor.move_prev();
+        }
+
+        // if the cursor moved past the front, this inserts the node at the front
+        cursor.insert_after(node.clone());
+    }
 }
 
 /// A collection of all intrusive lists Streams are part of.
@@ -330,12 +407,12 @@ macro_rules! iterate_interruptible {
 }
 
 impl<S: StreamTrait> StreamContainer<S> {
-    /// Creates a new `StreamContainer`
-    pub fn new() -> Self {
+    /// Creates a new `StreamContainer` which schedules transmissions with the given discipline
+    pub fn new(scheduler: StreamSchedulingPolicy) -> Self {
         Self {
             stream_map: RBTree::new(StreamTreeAdapter::new()),
             nr_active_streams: 0,
-            interest_lists: InterestLists::new(),
+            interest_lists: InterestLists::new(scheduler),
         }
     }
 
@@ -362,6 +439,22 @@ impl<S: StreamTrait> StreamContainer<S> {
         self.nr_active_streams
     }
 
+    /// Returns the number of `Stream`s which are waiting for connection flow control credits
+    pub fn connection_flow_credits_list_len(&self) -> usize {
+        self.interest_lists
+            .waiting_for_connection_flow_control_credits
+            .iter()
+            .count()
+    }
+
+    /// Returns the number of `Stream`s which are waiting for stream flow control credits
+    pub fn stream_flow_credits_list_len(&self) -> usize {
+        self.interest_lists
+            .waiting_for_stream_flow_control_credits
+            .iter()
+            .count()
+    }
+
     /// Returns true if the container contains a Stream with the given ID
     pub fn contains(&self, stream_id: StreamId) -> bool {
         !self.stream_map.find(&stream_id).is_null()
@@ -540,19 +633,78 @@ impl<S: StreamTrait> StreamContainer<S> {
     /// Iterates over all `Stream`s which are waiting for transmission,
     /// and executes the given function on each `Stream`
     ///
+    /// The `Stream`s are ordered by the configured `StreamSchedulingPolicy`, which decides how
+    /// many consecutive turns each `Stream` is given and where it is reinserted once those are
+    /// used up. By default this is a weighted round-robin: each `Stream` is given `priority + 1`
+    /// consecutive turns before it is moved to the back of the list. A `Stream` which is
+    /// interrupted keeps its remaining turns and is queried first the next time transmission
+    /// capacity is available.
+    ///
     /// The `stream::Controller` will be notified of streams that have been
     /// closed to allow for further streams to be opened.
     pub fn iterate_transmission_list<F>(&mut self, controller: &mut stream::Controller, mut func: F)
     where
         F: FnMut(&mut S) -> StreamContainerIterationResult,
     {
-        iterate_interruptible!(
-            self,
-            waiting_for_transmission,
-            waiting_for_transmission_link,
-            controller,
-            func
-        );
+        let mut extracted_list = self.interest_lists.waiting_for_transmission.take();
+        let mut cursor = extracted_list.front_mut();
+
+        while let Some(stream) = cursor.remove() {
+            // Note that while we iterate over the intrusive lists here
+            // `stream` is part of no list anymore, since it also got dropped
+            // from list that is described by the `cursor`.
+            debug_assert!(!stream.waiting_for_transmission_link.is_linked());
+
+            let (result, mut interests) = {
+                let mut mut_stream = stream.inner.borrow_mut();
+                let result = func(&mut *mut_stream);
+                (result, mut_stream.get_stream_interests())
+            };
+
+            let mut placement = Placement::Front;
+            let mut turns = stream.transmission_turns.get();
+            let mut revisit = false;
+
+            if let StreamContainerIterationResult::Continue = result {
+                placement = self.interest_lists.scheduler.on_yield();
+
+                if matches!(interests.transmission, transmission::Interest::NewData) {
+                    turns = turns.saturating_add(1);
+
+                    if turns < self.interest_lists.scheduler.turns(&interests) {
+                        // keep the stream out of the list while it uses its remaining turns
+                        interests.transmission = transmission::Interest::None;
+                        revisit = true;
+                    } else {
+                        // the stream used all of its turns so yield to the other streams
+                        turns = 0;
+                    }
+                }
+            }
+
+            // Update the interests after the interaction
+            self.interest_lists
+                .update_interests_with_placement(&stream, interests, result, placement);
+            stream.transmission_turns.set(turns);
+
+            if revisit {
+                cursor.insert_before(stream);
+                cursor.move_prev();
+                continue;
+            }
+
+            if let StreamContainerIterationResult::BreakAndInsertAtBack = result {
+                self.interest_lists
+                    .waiting_for_transmission
+                    .front_mut()
+                    .splice_after(extracted_list);
+                break;
+            }
+        }
+
+        if !self.interest_lists.done_streams.is_empty() {
+            self.finalize_done_streams(controller);
+        }
     }
 
     /// Iterates over all `Stream`s which are waiting for retransmission,
@@ -615,6 +767,17 @@ impl<S: StreamTrait> StreamContainer<S> {
         }
     }
 
+    /// Returns the IDs of the `Stream`s which are waiting for transmission, in
+    /// the order in which they will be queried
+    #[cfg(test)]
+    pub fn transmission_list(&self) -> alloc::vec::Vec<StreamId> {
+        self.interest_lists
+            .waiting_for_transmission
+            .iter()
+            .map(|stream| stream.inner.borrow().stream_id())
+            .collect()
+    }
+
     /// Returns whether or not streams have data to send
     pub fn has_pending_streams(&self) -> bool {
         !self.interest_lists.waiting_for_transmission.is_empty()
@@ -14,6 +14,8 @@ pub struct StreamEvents {
     /// A task that is waiting on the writing half of the Stream that might have
     /// been woken up due to an action.
     pub write_wake: Option<Waker>,
+    /// A task that is waiting on data sent on the Stream to be acknowledged
+    pub ack_wake: Option<Waker>,
 }
 
 impl StreamEvents {
@@ -31,6 +33,9 @@ impl StreamEvents {
         if self.write_wake.is_some() {
             result += 1;
         }
+        if self.ack_wake.is_some() {
+            result += 1;
+        }
         result
     }
 
@@ -48,6 +53,13 @@ impl StreamEvents {
         self.write_wake = Some(waker);
     }
 
+    /// Stores a [`Waker`] which was waiting on data sent on the Stream to be
+    /// acknowledged. The [`Waker`] will get woken up by the consumer of
+    /// [`StreamEvents`].
+    pub fn store_ack_waker(&mut self, waker: Waker) {
+        self.ack_wake = Some(waker);
+    }
+
     /// Wakes all [`Waker`] instances that have been stored via `add_waker`.
     /// Waking the [`Waker`]s will remove them from the [`StreamEvents`]s struct.
     pub fn wake_all(&mut self) {
@@ -57,5 +69,8 @@ impl StreamEvents {
         if let Some(waker) = self.write_wake.take() {
             waker.wake();
         }
+        if let Some(waker) = self.ack_wake.take() {
+            waker.wake();
+        }
     }
 }
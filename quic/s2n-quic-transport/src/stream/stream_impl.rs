@@ -5,6 +5,7 @@ use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
         incoming_connection_flow_controller::IncomingConnectionFlowController,
+        marker,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         receive_stream::ReceiveStream,
         send_stream::SendStream,
@@ -13,9 +14,10 @@ use crate::{
         StreamError,
     },
 };
+use alloc::vec::Vec;
 use core::{task::Context, time::Duration};
 use s2n_quic_core::{
-    ack, endpoint,
+    ack, application, endpoint,
     frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
     stream::{ops, StreamId},
     time::{timer, Timestamp},
@@ -38,6 +40,8 @@ pub struct StreamConfig {
     pub initial_receive_window: VarInt,
     /// The desired flow control window that we want to maintain on the receiving side
     pub desired_flow_control_window: u32,
+    /// The percentage of the receive window consumed by the peer before an update is sent
+    pub window_update_threshold: u8,
     /// The initial flow control window for sending data
     pub initial_send_window: VarInt,
     /// The maximum buffered amount of data on the sending side
@@ -107,6 +111,12 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// Called when the connection timer expires
     fn on_timeout(&mut self, now: Timestamp);
 
+    /// Called when the connection hibernates to release any transient buffers
+    fn hibernate(&mut self) {}
+
+    /// Raises the flow control window of the receiving half of the stream to `window` bytes
+    fn set_receive_window(&mut self, _window: u32) {}
+
     /// This method gets called when a stream gets reset due to a reason that is
     /// not related to a frame. E.g. due to a connection failure.
     fn on_internal_reset(&mut self, error: StreamError, events: &mut StreamEvents);
@@ -122,6 +132,14 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// This method is called when a connection window is available
     fn on_connection_window_available(&mut self);
 
+    /// Moves any pending events for application-marked data into `events`
+    fn drain_marker_events(&mut self, _events: &mut Vec<marker::Event>) {}
+
+    /// Returns the number of payload bytes which were retransmitted since the last call
+    fn take_retransmitted_bytes(&mut self) -> u64 {
+        0
+    }
+
     // These functions are called from the client API
 
     fn poll_request(
@@ -129,6 +147,27 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError>;
+
+    /// Resets the sending half and/or stops the receiving half of the stream on behalf of the
+    /// application, without going through a handle to the stream itself
+    ///
+    /// Any tasks which are waiting on the closed halves are notified through `events`.
+    fn on_application_close(
+        &mut self,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        _events: &mut StreamEvents,
+    ) {
+        let mut request = ops::Request::default();
+        if let Some(error) = reset {
+            request.reset(error);
+        }
+        if let Some(error) = stop_sending {
+            request.stop_sending(error);
+        }
+        // closing the stream is best effort so ignore the result
+        let _ = self.poll_request(&mut request, None);
+    }
 }
 
 /// The implementation of a `Stream`.
@@ -204,6 +243,7 @@ impl StreamTrait for StreamImpl {
                 config.incoming_connection_flow_controller,
                 config.initial_receive_window,
                 config.desired_flow_control_window,
+                config.window_update_threshold,
             ),
             has_send: !send_is_closed,
             send_stream: SendStream::new(
@@ -299,6 +339,17 @@ impl StreamTrait for StreamImpl {
         self.send_stream.on_timeout(now)
     }
 
+    #[inline]
+    fn hibernate(&mut self) {
+        self.receive_stream.hibernate();
+        self.send_stream.hibernate();
+    }
+
+    #[inline]
+    fn set_receive_window(&mut self, window: u32) {
+        self.receive_stream.set_receive_window(window);
+    }
+
     #[inline]
     fn on_internal_reset(&mut self, error: StreamError, events: &mut StreamEvents) {
         self.receive_stream.on_internal_reset(error, events);
@@ -327,6 +378,16 @@ impl StreamTrait for StreamImpl {
         self.send_stream.on_connection_window_available()
     }
 
+    #[inline]
+    fn drain_marker_events(&mut self, events: &mut Vec<marker::Event>) {
+        self.send_stream.drain_marker_events(self.stream_id, events)
+    }
+
+    #[inline]
+    fn take_retransmitted_bytes(&mut self) -> u64 {
+        self.send_stream.take_retransmitted_bytes()
+    }
+
     // These functions are called from the client API
 
     fn poll_request(
@@ -344,6 +405,36 @@ impl StreamTrait for StreamImpl {
 
         result
     }
+
+    fn on_application_close(
+        &mut self,
+        reset: Option<application::Error>,
+        stop_sending: Option<application::Error>,
+        events: &mut StreamEvents,
+    ) {
+        let mut request = ops::Request::default();
+
+        // The waiting tasks belong to the stream's own handles, so take their wakers before the
+        // request clears them
+        if let Some(error) = reset.filter(|_| self.has_send) {
+            if let Some((waker, _)) = self.send_stream.write_waiter.take() {
+                events.store_write_waker(waker);
+            }
+            request.reset(error);
+        }
+
+        if let Some(error) = stop_sending {
+            if let Some((waker, _)) = self.receive_stream.read_waiter.take() {
+                events.store_read_waker(waker);
+            }
+            request.stop_sending(error);
+        }
+
+        if request.tx.is_some() || request.rx.is_some() {
+            // closing the stream is best effort so ignore the result
+            let _ = self.poll_request(&mut request, None);
+        }
+    }
 }
 
 impl timer::Provider for StreamImpl {
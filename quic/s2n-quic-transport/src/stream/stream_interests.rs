@@ -22,6 +22,8 @@ pub struct StreamInterests {
     pub delivery_notifications: bool,
     /// Transmission interest for the component
     pub transmission: Interest,
+    /// The priority used to schedule the `Stream` for transmission
+    pub priority: u8,
 }
 
 impl StreamInterests {
@@ -32,6 +34,14 @@ impl StreamInterests {
         self.retained |= other.retained;
         self.delivery_notifications |= other.delivery_notifications;
         let _ = self.transmission.on_interest(other.transmission);
+        self.priority = self.priority.max(other.priority);
+    }
+
+    /// Returns the number of consecutive transmission turns the `Stream` is given
+    /// before yielding to the other `Stream`s
+    #[inline]
+    pub fn transmission_weight(&self) -> u16 {
+        self.priority as u16 + 1
     }
 
     #[inline]
@@ -482,6 +482,8 @@ pub struct TestEnvironmentConfig {
     pub initial_connection_send_window_size: u64,
     pub initial_connection_receive_window_size: u64,
     pub desired_connection_flow_control_window: u32,
+    pub stream_window_update_threshold: u8,
+    pub connection_window_update_threshold: u8,
     pub max_send_buffer_size: usize,
     pub transmission_constraint: transmission::Constraint,
     pub local_endpoint_type: endpoint::Type,
@@ -511,6 +513,8 @@ impl TestEnvironmentConfig {
                 TestEnvironment::DEFAULT_INITIAL_CONNECTION_RECEIVE_WINDOW,
             desired_connection_flow_control_window:
                 TestEnvironment::DEFAULT_INITIAL_CONNECTION_RECEIVE_WINDOW as u32,
+            stream_window_update_threshold: 10,
+            connection_window_update_threshold: 10,
             max_send_buffer_size: TestEnvironment::DEFAULT_MAX_SEND_BUFFER_SIZE,
             transmission_constraint: transmission::Constraint::None,
             max_packet_size: None,
@@ -529,6 +533,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
     let rx_connection_flow_controller = IncomingConnectionFlowController::new(
         VarInt::new(config.initial_connection_receive_window_size).unwrap(),
         config.desired_connection_flow_control_window,
+        config.connection_window_update_threshold,
     );
 
     let tx_connection_flow_controller = OutgoingConnectionFlowController::new(
@@ -542,6 +547,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         stream_id: config.stream_id,
         initial_receive_window: VarInt::new(config.initial_receive_window).unwrap(),
         desired_flow_control_window: config.desired_flow_control_window,
+        window_update_threshold: config.stream_window_update_threshold,
         initial_send_window: VarInt::new(config.initial_send_window).unwrap(),
         max_send_buffer_size: config.max_send_buffer_size as u32,
     });
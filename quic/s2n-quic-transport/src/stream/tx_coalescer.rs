@@ -0,0 +1,169 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+use s2n_quic_core::{
+    time::{timer, Timer, Timestamp},
+    transmission::interest::{Interest, Query, Result},
+};
+
+/// Delays the transmission of small stream writes so they can be combined into fuller packets
+///
+/// This is similar to Nagle's algorithm in TCP: once the application writes a small amount of
+/// data, new stream data is held back until either enough has been written to fill a packet or
+/// the configured delay has passed. Coalescing is disabled by default.
+#[derive(Debug, Default)]
+pub struct TxCoalescer {
+    delay: Option<Duration>,
+    /// The number of bytes written by the application since stream data was last transmitted
+    pending: u64,
+    /// The number of pending bytes which fill a packet on the active path
+    packet_len: u64,
+    timer: Timer,
+}
+
+impl TxCoalescer {
+    /// Sets the maximum amount of time writes are held back, or disables coalescing with `None`
+    #[inline]
+    pub fn update(&mut self, delay: Option<Duration>) {
+        self.delay = delay.filter(|delay| !delay.is_zero());
+        self.pending = 0;
+        self.timer.cancel();
+    }
+
+    /// Called when the application writes `len` bytes to a stream
+    ///
+    /// Returns `true` if new stream data was being held back and can now be transmitted.
+    #[inline]
+    pub fn on_write(&mut self, len: usize, is_flush: bool) -> bool {
+        if self.delay.is_none() {
+            return false;
+        }
+
+        let was_holding = self.is_holding();
+
+        self.pending = self.pending.saturating_add(len as u64);
+
+        // finishing, resetting or flushing a stream shouldn't wait on more data
+        if is_flush {
+            self.timer.cancel();
+        }
+
+        was_holding && !self.is_holding()
+    }
+
+    /// Starts holding back pending writes, if coalescing is enabled
+    #[inline]
+    pub fn arm(&mut self, now: Timestamp, max_datagram_size: usize) {
+        self.packet_len = max_datagram_size as u64;
+
+        if let Some(delay) = self.delay {
+            if self.pending > 0 && !self.timer.is_armed() && self.is_below_packet_len() {
+                self.timer.set(now + delay);
+            }
+        }
+    }
+
+    /// Returns `true` if new stream data is currently being held back
+    #[inline]
+    pub fn is_holding(&self) -> bool {
+        self.timer.is_armed() && self.is_below_packet_len()
+    }
+
+    #[inline]
+    pub fn on_timeout(&mut self, now: Timestamp) {
+        // once the timer expires the pending writes are released
+        let _ = self.timer.poll_expiration(now);
+    }
+
+    /// Called after new stream data was transmitted
+    #[inline]
+    pub fn on_transmit(&mut self) {
+        self.pending = 0;
+        self.timer.cancel();
+    }
+
+    #[inline]
+    fn is_below_packet_len(&self) -> bool {
+        self.pending < self.packet_len
+    }
+}
+
+impl timer::Provider for TxCoalescer {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)?;
+        Ok(())
+    }
+}
+
+/// Filters out the `NewData` interest of any providers while writes are being coalesced
+pub struct HoldNewData<'a, Q: Query>(pub &'a mut Q);
+
+impl<'a, Q: Query> Query for HoldNewData<'a, Q> {
+    #[inline]
+    fn on_interest(&mut self, interest: Interest) -> Result {
+        if matches!(interest, Interest::NewData) {
+            return Ok(());
+        }
+
+        self.0.on_interest(interest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{Clock, NoopClock};
+
+    #[test]
+    fn tx_coalescer_test() {
+        let now = NoopClock {}.get_time();
+        let delay = Duration::from_millis(1);
+        let mut coalescer = TxCoalescer::default();
+
+        // coalescing is disabled by default
+        assert!(!coalescer.on_write(10, false));
+        coalescer.arm(now, 1200);
+        assert!(!coalescer.is_holding());
+
+        coalescer.update(Some(delay));
+
+        // small writes are held until the delay passes
+        assert!(!coalescer.on_write(10, false));
+        coalescer.arm(now, 1200);
+        assert!(coalescer.is_holding());
+        coalescer.on_timeout(now + delay / 2);
+        assert!(coalescer.is_holding());
+        coalescer.on_timeout(now + delay);
+        assert!(!coalescer.is_holding());
+        coalescer.on_transmit();
+
+        // writes are released once they fill a packet
+        assert!(!coalescer.on_write(1000, false));
+        coalescer.arm(now, 1200);
+        assert!(coalescer.is_holding());
+        assert!(coalescer.on_write(200, false));
+        assert!(!coalescer.is_holding());
+        coalescer.on_transmit();
+
+        // writes which already fill a packet aren't held
+        assert!(!coalescer.on_write(1500, false));
+        coalescer.arm(now, 1200);
+        assert!(!coalescer.is_holding());
+        coalescer.on_transmit();
+
+        // flushing releases the pending writes
+        assert!(!coalescer.on_write(10, false));
+        coalescer.arm(now, 1200);
+        assert!(coalescer.on_write(0, true));
+        assert!(!coalescer.is_holding());
+
+        // a zero delay disables coalescing
+        coalescer.update(Some(Duration::ZERO));
+        assert_eq!(coalescer.delay, None);
+        assert!(!coalescer.on_write(10, false));
+        coalescer.arm(now, 1200);
+        assert!(!coalescer.is_holding());
+    }
+}
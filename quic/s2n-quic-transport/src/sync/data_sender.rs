@@ -116,6 +116,11 @@ pub struct DataSender<FlowController, ChunkToFrameWriter> {
     pending: IntervalSet<VarInt>,
     /// All of the intervals that have been declared lost
     lost: IntervalSet<VarInt>,
+    /// The number of bytes which were transmitted again since the last call to
+    /// `take_retransmitted_len`
+    retransmitted_len: u64,
+    /// The total number of bytes which were transmitted again
+    total_retransmitted_len: u64,
     /// The maximum amount of bytes that are buffered within the sending stream.
     /// This capacity will not be exceeded - even if the remote provides us a
     /// bigger flow control window.
@@ -142,6 +147,8 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             transmission_offset: VarInt::from_u32(0),
             pending: IntervalSet::new(),
             lost: IntervalSet::new(),
+            retransmitted_len: 0,
+            total_retransmitted_len: 0,
             max_buffer_capacity: VarInt::from_u32(max_buffer_capacity),
             state: State::Sending,
         }
@@ -238,6 +245,91 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             .unwrap_or(usize::MAX)
     }
 
+    /// Returns the maximum amount of data that can be buffered for sending
+    pub fn max_buffer_capacity(&self) -> VarInt {
+        self.max_buffer_capacity
+    }
+
+    /// Sets the maximum amount of data that can be buffered for sending
+    ///
+    /// Lowering the capacity below the currently enqueued amount does not drop any data. Instead,
+    /// no more data will be accepted until enough of it has been acknowledged.
+    pub fn set_max_buffer_capacity(&mut self, max_buffer_capacity: u32) {
+        self.max_buffer_capacity = VarInt::from_u32(max_buffer_capacity);
+    }
+
+    /// Returns the offset up to which all of the data has been acknowledged
+    pub fn acknowledged_offset(&self) -> VarInt {
+        self.buffer.head()
+    }
+
+    /// Returns the amount of enqueued data which has not been transmitted yet
+    pub fn unsent_len(&self) -> VarInt {
+        self.buffer
+            .total_len()
+            .saturating_sub(self.transmission_offset)
+    }
+
+    /// Returns the amount of transmitted data which has not been acknowledged yet
+    ///
+    /// Data which has been declared lost is included until it is acknowledged.
+    pub fn unacknowledged_len(&self) -> VarInt {
+        let pending = VarInt::try_from(self.pending.count()).unwrap_or(VarInt::MAX);
+        pending.saturating_sub(self.unsent_len())
+    }
+
+    /// Returns the offset up to which data has been transmitted at least once
+    pub fn transmission_offset(&self) -> VarInt {
+        self.transmission_offset
+    }
+
+    /// Returns the ranges of data which have not been acknowledged yet
+    pub fn pending(&self) -> &IntervalSet<VarInt> {
+        &self.pending
+    }
+
+    /// Returns the number of bytes which were retransmitted since the last call and resets the
+    /// counter
+    #[inline]
+    pub fn take_retransmitted_len(&mut self) -> u64 {
+        core::mem::take(&mut self.retransmitted_len)
+    }
+
+    /// Returns the total number of bytes which were retransmitted
+    #[inline]
+    pub fn total_retransmitted_len(&self) -> u64 {
+        self.total_retransmitted_len
+    }
+
+    /// Returns the ranges of data which were declared lost and still need to be retransmitted
+    pub fn lost(&self) -> &IntervalSet<VarInt> {
+        &self.lost
+    }
+
+    /// Releases any buffer capacity which isn't currently in use
+    ///
+    /// The buffers will grow again as more data is enqueued and transmitted.
+    pub fn shrink_to_fit(&mut self) {
+        self.buffer.shrink_to_fit();
+        self.transmissions.shrink_to_fit();
+        self.pending.shrink_to_fit();
+        self.lost.shrink_to_fit();
+    }
+
+    /// Clears all of the enqueued data and transmissions while retaining the allocated buffers
+    ///
+    /// The sender returns to its initial state, apart from its flow controller.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.transmissions.clear();
+        self.transmission_offset = VarInt::from_u32(0);
+        self.pending.clear();
+        self.lost.clear();
+        self.retransmitted_len = 0;
+        self.total_retransmitted_len = 0;
+        self.state = State::Sending;
+    }
+
     /// Enqueues the data for transmission.
     ///
     /// It is only allowed to enqueue bytes if they do not overflow the maximum
@@ -387,13 +479,19 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         let mut transmitted_lost = false;
         // try to retransmit any lost ranges first
         if constraint.can_retransmit() {
-            transmitted_lost = self.transmissions.transmit_set(
+            let lost_len = self.lost.count();
+            let result = self.transmissions.transmit_set(
                 &self.buffer,
                 &mut self.lost,
                 &mut self.state,
                 writer_context,
                 context,
-            )?;
+            );
+            // anything which is no longer in the lost set was written to the packet
+            let retransmitted_len = (lost_len - self.lost.count()) as u64;
+            self.retransmitted_len += retransmitted_len;
+            self.total_retransmitted_len += retransmitted_len;
+            transmitted_lost = result?;
         }
 
         let is_blocked = self.flow_controller().is_blocked();
@@ -448,13 +546,14 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
 
                 let interval_end = interval.end_exclusive().min(starting_transmission_offset);
 
-                self.transmissions.transmit_interval(
+                let transmitted = self.transmissions.transmit_interval(
                     &mut viewer,
                     (interval.start_inclusive()..interval_end).into(),
                     &mut self.state,
                     writer_context,
                     context,
                 )?;
+                self.retransmitted_len += transmitted.len() as u64;
             }
         }
 
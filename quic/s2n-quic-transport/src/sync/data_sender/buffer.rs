@@ -74,6 +74,12 @@ impl Buffer {
         self.pending_len == VarInt::from_u8(0)
     }
 
+    /// Shrinks the capacity of the chunk queue as much as possible
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.chunks.shrink_to_fit();
+    }
+
     /// Sets the current offset of the buffer.
     ///
     /// This should only be used in testing.
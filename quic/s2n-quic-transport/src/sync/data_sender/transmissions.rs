@@ -230,6 +230,12 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.flow_controller.finish();
     }
 
+    /// Releases any tracking capacity which isn't currently in use
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.in_flight.shrink_to_fit();
+    }
+
     /// Get the inflight inclusive PacketNumberRange
     #[inline]
     pub fn get_inflight_range(&self) -> PacketNumberRange {
@@ -317,6 +323,15 @@ impl Set {
         self.packets.clear();
         self.overflow.clear();
     }
+
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.packets.shrink_to_fit();
+
+        if self.overflow.len == 0 {
+            self.overflow = TransmissionSlab::default();
+        }
+    }
 }
 
 struct SetRemoveIter<'a> {
@@ -73,6 +73,17 @@ impl<
         self.request_delivery_if_necessary();
     }
 
+    /// Sets the new value and requests it to be synchronized to the peer, even if the increase
+    /// is below the configured threshold
+    pub fn update_latest_value_immediately(&mut self, value: T) {
+        debug_assert!(value >= self.latest_value);
+        self.latest_value = value;
+
+        if !self.delivery.is_cancelled() && self.latest_value != self.value_ackd_up_to {
+            self.delivery = DeliveryState::Requested(self.latest_value);
+        }
+    }
+
     /// Stop to synchronize the value to the peer
     pub fn stop_sync(&mut self) {
         self.delivery.cancel();
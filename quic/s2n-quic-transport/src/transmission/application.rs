@@ -14,7 +14,7 @@ use crate::{
     transmission::{self, Mode, Provider as _},
 };
 use core::ops::RangeInclusive;
-use s2n_quic_core::packet::number::PacketNumberSpace;
+use s2n_quic_core::{packet::number::PacketNumberSpace, recovery::ProbePolicy};
 
 pub enum Payload<'a, Config: endpoint::Config> {
     Normal(Normal<'a, Config>),
@@ -39,6 +39,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         recovery_manager: &'a mut recovery::Manager<Config>,
         crypto_stream: &'a mut CryptoStream,
         datagram_manager: &'a mut datagram::Manager<Config>,
+        probe_policy: ProbePolicy,
     ) -> Self {
         if transmission_mode != Mode::PathValidationOnly {
             debug_assert_eq!(path_id, path_manager.active_path_id());
@@ -57,6 +58,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
                     crypto_stream,
                     datagram_manager,
                     prioritize_datagrams: false,
+                    probe_policy,
                 })
             }
             Mode::MtuProbing => transmission::application::Payload::MtuProbe(MtuProbe {
@@ -114,6 +116,7 @@ pub struct Normal<'a, Config: endpoint::Config> {
     crypto_stream: &'a mut CryptoStream,
     datagram_manager: &'a mut datagram::Manager<Config>,
     prioritize_datagrams: bool,
+    probe_policy: ProbePolicy,
 }
 
 impl<'a, Config: endpoint::Config> Normal<'a, Config> {
@@ -121,6 +124,14 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         let can_transmit = context.transmission_constraint().can_transmit()
             || context.transmission_constraint().can_retransmit();
 
+        // The probe policy only applies to packets sent after the PTO timer expired
+        let probe_policy = if context.transmission_mode().is_loss_recovery_probing() {
+            self.probe_policy
+        } else {
+            ProbePolicy::Default
+        };
+        let can_transmit_datagrams = can_transmit && probe_policy.sends_new_data();
+
         //= https://www.rfc-editor.org/rfc/rfc9221#section-5
         //# DATAGRAM frames cannot be fragmented;
         //
@@ -128,7 +139,7 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         // and filling the packet with other frames. This is because datagrams
         // cannot be fragmented across packets and we want to do the most to send
         // large datagrams.
-        if self.prioritize_datagrams && can_transmit {
+        if self.prioritize_datagrams && can_transmit_datagrams {
             self.datagram_manager.on_transmit(
                 context,
                 self.stream_manager,
@@ -138,12 +149,12 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         let did_send_ack = self.ack_manager.on_transmit(context);
 
         // Payloads can only transmit and retransmit
-        if can_transmit {
+        if can_transmit && !probe_policy.is_ping_only() {
             self.transmit_control_data(context);
 
             // If we did not prioritize datagrams in this packet, we send them just
             // before we send stream data.
-            if !self.prioritize_datagrams {
+            if !self.prioritize_datagrams && can_transmit_datagrams {
                 self.datagram_manager.on_transmit(
                     context,
                     self.stream_manager,
@@ -154,8 +165,13 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
             // The default sending behavior is to alternate between sending datagrams
             // and sending stream data. This can be configured by implementing a
             // custom datagram sender and choosing when to cede packet space for stream data.
-            let _ = self.stream_manager.on_transmit(context);
+            let _ = match probe_policy {
+                ProbePolicy::Default => self.stream_manager.on_transmit(context),
+                policy => self.stream_manager.on_probe_transmit(context, policy),
+            };
+        }
 
+        if can_transmit {
             // send PINGs last, since they might not actually be needed if there's an ack-eliciting
             // frame already present in the payload
             self.recovery_manager.on_transmit(context);
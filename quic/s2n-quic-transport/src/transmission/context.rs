@@ -284,3 +284,99 @@ impl<'a, C: WriteContext> WriteContext for RetransmissionContext<'a, C> {
         self.context.tag_len()
     }
 }
+
+// Overrides a context's transmission mode so loss recovery probes only include new data rather than
+// also re-sending data which is still in flight
+pub struct NewDataContext<'a, C: WriteContext> {
+    context: &'a mut C,
+}
+
+impl<'a, C: WriteContext> NewDataContext<'a, C> {
+    pub fn new(context: &'a mut C) -> Self {
+        Self { context }
+    }
+}
+
+impl<'a, C: WriteContext> WriteContext for NewDataContext<'a, C> {
+    #[inline]
+    fn current_time(&self) -> Timestamp {
+        self.context.current_time()
+    }
+
+    #[inline]
+    fn transmission_constraint(&self) -> transmission::Constraint {
+        self.context.transmission_constraint()
+    }
+
+    #[inline]
+    fn transmission_mode(&self) -> Mode {
+        match self.context.transmission_mode() {
+            Mode::LossRecoveryProbing => Mode::Normal,
+            mode => mode,
+        }
+    }
+
+    #[inline]
+    fn remaining_capacity(&self) -> usize {
+        self.context.remaining_capacity()
+    }
+
+    #[inline]
+    fn write_ack_frame<AckRanges: AckRangesTrait>(
+        &mut self,
+        ack_frame: &Ack<AckRanges>,
+    ) -> Option<PacketNumber> {
+        self.context.write_ack_frame(ack_frame)
+    }
+
+    #[inline]
+    fn write_frame<Frame>(&mut self, frame: &Frame) -> Option<PacketNumber>
+    where
+        Frame: EncoderValue + FrameTrait,
+        for<'frame> &'frame Frame: IntoEvent<event::builder::Frame>,
+    {
+        self.context.write_frame(frame)
+    }
+
+    #[inline]
+    fn write_fitted_frame<Frame>(&mut self, frame: &Frame) -> PacketNumber
+    where
+        Frame: EncoderValue + FrameTrait,
+        for<'frame> &'frame Frame: IntoEvent<event::builder::Frame>,
+    {
+        self.context.write_fitted_frame(frame)
+    }
+
+    fn write_frame_forced<Frame>(&mut self, frame: &Frame) -> Option<PacketNumber>
+    where
+        Frame: EncoderValue + FrameTrait,
+        for<'frame> &'frame Frame: IntoEvent<event::builder::Frame>,
+    {
+        self.context.write_frame_forced(frame)
+    }
+
+    #[inline]
+    fn ack_elicitation(&self) -> AckElicitation {
+        self.context.ack_elicitation()
+    }
+
+    #[inline]
+    fn packet_number(&self) -> PacketNumber {
+        self.context.packet_number()
+    }
+
+    #[inline]
+    fn local_endpoint_type(&self) -> endpoint::Type {
+        self.context.local_endpoint_type()
+    }
+
+    #[inline]
+    fn header_len(&self) -> usize {
+        self.context.header_len()
+    }
+
+    #[inline]
+    fn tag_len(&self) -> usize {
+        self.context.tag_len()
+    }
+}
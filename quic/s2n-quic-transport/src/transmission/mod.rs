@@ -14,7 +14,7 @@ pub use crate::contexts::WriteContext;
 pub use s2n_quic_core::transmission::*;
 
 use crate::{
-    endpoint, path,
+    connection, endpoint, path,
     space::TxPacketNumbers,
     transmission::{self, interest::Provider as _},
 };
@@ -40,6 +40,7 @@ pub trait Payload: interest::Provider {
 pub struct Transmission<'a, 'sub, Config: endpoint::Config, P: Payload> {
     pub config: PhantomData<Config>,
     pub outcome: &'a mut transmission::Outcome,
+    pub padding: &'a mut connection::padding::Controller,
     pub payload: P,
     pub packet_number: PacketNumber,
     pub timestamp: Timestamp,
@@ -104,8 +105,8 @@ impl<'a, 'sub, Config: endpoint::Config, P: Payload> PacketPayloadEncoder
                 length = remaining_capacity;
             }
 
-            if length > 0 {
-                context.write_frame(&Padding { length });
+            if length > 0 && context.write_frame(&Padding { length }).is_some() {
+                self.padding.on_padding(length);
             }
 
             {
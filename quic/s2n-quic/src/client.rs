@@ -14,10 +14,12 @@ use core::{
 use s2n_quic_transport::endpoint::{connect, handle::Connector};
 
 mod builder;
+mod pool;
 mod providers;
 
 pub use builder::*;
-pub use connect::Connect;
+pub use connect::{Connect, RetryPolicy};
+pub use pool::ConnectionPool;
 pub use providers::*;
 
 /// A QUIC client endpoint, capable of opening connections
@@ -86,6 +88,10 @@ impl Client {
 
     /// Establishes a connection to the specified endpoint
     ///
+    /// Failed attempts can be retried automatically by setting a [`RetryPolicy`] with
+    /// [`Connect::with_retry_policy`]. Each retry emits an `EndpointConnectionAttemptRetried`
+    /// event.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -166,6 +172,42 @@ impl Client {
     pub fn local_addr(&self) -> Result<std::net::SocketAddr, std::io::Error> {
         Ok(self.local_addr.into())
     }
+
+    /// Returns the delivery statistics aggregated over all of the connections on the client
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// # use s2n_quic::Client;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::bind("0.0.0.0:0")?;
+    ///
+    /// let statistics = client.statistics();
+    /// assert_eq!(statistics.retransmitted_bytes, 0);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn statistics(&self) -> crate::connection::Statistics {
+        self.connector.statistics()
+    }
+
+    /// Returns the occupancy of the client's connection ID maps
+    ///
+    /// This is mostly useful for monitoring endpoints with a large number of connections.
+    pub fn id_map_metrics(&self) -> crate::connection::IdMapMetrics {
+        self.connector.id_map_metrics()
+    }
+
+    /// Returns a stream of the connections which are removed from the client after this call
+    ///
+    /// A connection is removed once it has finished closing or draining, even if the application
+    /// dropped its [`Connection`] handle early. Each notification includes the error which
+    /// closed the connection and its final statistics.
+    pub fn closed_connections(&self) -> crate::connection::ClosedConnections {
+        self.connector.closed_connections()
+    }
 }
 
 #[must_use = "futures do nothing unless you `.await` or poll them"]
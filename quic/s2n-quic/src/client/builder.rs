@@ -4,15 +4,16 @@
 use crate::{
     client::{Client, ClientProviders, DefaultProviders},
     provider::*,
+    server::{self, ServerProviders},
 };
 
 /// A builder for configuring [`Client`] providers
 #[derive(Debug)]
-pub struct Builder<Providers>(pub(crate) Providers);
+pub struct Builder<Providers>(pub(crate) Providers, pub(crate) Settings);
 
 impl Default for Builder<DefaultProviders> {
     fn default() -> Self {
-        Self(Default::default())
+        Self(Default::default(), Default::default())
     }
 }
 
@@ -253,9 +254,30 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
-    #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Client`]
+        ///
+        /// The random provider is used for connection IDs, address tokens, path challenges,
+        /// and packet number skipping.
+        ///
+        /// # Examples
+        ///
+        /// Uses the AWS-LC DRBG, which is suitable for FIPS deployments
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::random};
+        /// #
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "provider-random-drbg", not(target_os = "windows")))]
+        /// let client = Client::builder()
+        ///     .with_random(random::drbg::Provider::default())?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
         with_random,
         random,
         ClientProviders
@@ -276,6 +298,80 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    /// Shares the characteristics of paths between connections to the same remote prefix
+    ///
+    /// When a connection is removed, the RTT, bandwidth and MTU observed on its active path are
+    /// cached for the remote address prefix. New connections to the same prefix start with an RTT
+    /// estimate, initial MTU and congestion window based on these observations instead of the
+    /// conservative defaults. This mostly benefits workloads which open a new connection per
+    /// request to the same peers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::{connection::PathCache, Client};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cache = PathCache::new()
+    ///     .with_capacity(4096)?
+    ///     .with_prefix_len(24, 48)?;
+    ///
+    /// let builder = Client::builder().with_path_cache(cache)?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn with_path_cache(
+        mut self,
+        cache: crate::connection::PathCache,
+    ) -> Result<Self, StartError> {
+        self.1.path_cache = Some(cache);
+        Ok(self)
+    }
+
+    /// Recycles the buffers of packet spaces between connections on the [`Client`]
+    ///
+    /// Each connection allocates a crypto stream and a set of ACK ranges per packet number space.
+    /// When a pool is configured, these buffers are cleared and reused by new connections instead
+    /// of being released when a space is discarded. Up to `capacity` of each kind of buffer is
+    /// retained, which reduces the pressure on the allocator for endpoints with high connection
+    /// churn. A `capacity` of `0`, which is the default, disables the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::Client;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let builder = Client::builder().with_packet_space_pool(1024)?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn with_packet_space_pool(mut self, capacity: usize) -> Result<Self, StartError> {
+        self.1.packet_space_pool = capacity;
+        Ok(self)
+    }
+
+    /// Restricts the [`Client`] to FIPS-approved algorithms
+    ///
+    /// The crypto backend must be a FIPS-validated module operating in FIPS mode. TLS providers
+    /// created from certificates, or the default TLS provider, are built in FIPS mode. A TLS
+    /// provider that was configured separately must have enabled FIPS mode on its own builder,
+    /// otherwise starting the [`Client`] fails.
+    #[cfg(feature = "fips")]
+    pub fn with_fips(mut self, enabled: bool) -> Result<Self, StartError> {
+        if enabled && !s2n_quic_crypto::fips::is_enabled() {
+            return Err(StartError::new(
+                "the crypto backend is not operating in FIPS mode",
+            ));
+        }
+        self.1.fips = enabled;
+        Ok(self)
+    }
+
     /// Starts the [`Client`] with the configured providers
     ///
     /// # Examples
@@ -295,6 +391,53 @@ impl<Providers: ClientProviders> Builder<Providers> {
     /// # }
     /// ```
     pub fn start(self) -> Result<Client, StartError> {
-        self.0.build().start()
+        self.0.build().start(self.1)
+    }
+
+    /// Starts the [`Client`] in peer-to-peer mode, along with a [`Server`](crate::Server) which accepts
+    /// connections on the same socket
+    ///
+    /// Connections are opened from, and accepted on, the address of the client's IO provider,
+    /// which allows peers behind NATs to connect to each other simultaneously. The IO provider
+    /// configured on the server builder is not used.
+    ///
+    /// Connection events are published to the event provider of the side which owns the
+    /// connection. Events published by the IO provider, such as the configured platform features,
+    /// are only published to the server's event provider.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::{client::Connect, Client, Server};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?;
+    ///
+    /// let (client, mut server) = Client::builder()
+    ///     .with_tls(Path::new("./certs/cert.pem"))?
+    ///     .with_io("0.0.0.0:4433")?
+    ///     .start_with_server(server)?;
+    ///
+    /// // race the addresses exchanged with the peer through a rendezvous service
+    /// let connect = Connect::new("192.0.2.1:4433".parse::<std::net::SocketAddr>()?)
+    ///     .with_candidate_addresses(["198.51.100.1:4433".parse::<std::net::SocketAddr>()?])
+    ///     .with_server_name("peer");
+    ///
+    /// tokio::select! {
+    ///     Ok(connection) = client.connect(connect) => { /* ... */ }
+    ///     Some(connection) = server.accept() => { /* ... */ }
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn start_with_server<Server: ServerProviders>(
+        self,
+        server: server::Builder<Server>,
+    ) -> Result<(Client, crate::Server), StartError> {
+        self.0.build().start_with_server(self.1, server)
     }
 }
@@ -0,0 +1,270 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::{Client, Connect},
+    connection::{self, Handle},
+    stream::{BidirectionalStream, SendStream},
+};
+use core::fmt;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Maintains a fixed number of connections to a single remote endpoint and spreads the streams
+/// opened by the application across them
+///
+/// Streams are opened on the pooled connections in a round-robin fashion. Each connection is
+/// established the first time it's selected. While it's being established, callers which select
+/// it move on to the next established connection instead of waiting. If none of the connections
+/// are established yet, the callers wait for the same handshake instead of starting their own.
+///
+/// If a stream can't be opened because its connection has failed, the connection is evicted from
+/// the pool and the stream is opened on the next connection instead. The evicted connection is
+/// reconnected the next time it's selected. If a connection can't be established, the next
+/// connection in the pool is tried, and an error is only returned once none of them can be used.
+/// A [`RetryPolicy`] can be set on the [`Connect`] passed to the pool to retry those attempts.
+///
+/// The pool can be cheaply cloned and shared between tasks.
+///
+/// [`RetryPolicy`]: crate::client::RetryPolicy
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// use s2n_quic::{
+///     client::{Connect, ConnectionPool},
+///     Client,
+/// };
+/// use std::{net::SocketAddr, path::Path};
+///
+/// # async fn connect() -> Result<(), Box<dyn Error>> {
+/// let client = Client::builder()
+///     .with_tls(Path::new("./certs/cert.pem"))?
+///     .with_io("0.0.0.0:0")?
+///     .start()?;
+///
+/// let addr: SocketAddr = "127.0.0.1:443".parse()?;
+/// let connect = Connect::new(addr).with_server_name("localhost");
+/// let pool = ConnectionPool::new(client, connect, 4);
+///
+/// let mut stream = pool.open_bidirectional_stream().await?;
+/// stream.send(vec![1, 2, 3].into()).await?;
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConnectionPool {
+    client: Client,
+    connect: Connect,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    connections: Vec<Slot>,
+    next: usize,
+    /// The id assigned to the next connection attempt
+    next_attempt: u64,
+}
+
+impl State {
+    #[inline]
+    fn has_connected(&self) -> bool {
+        self.connections
+            .iter()
+            .any(|slot| matches!(slot, Slot::Connected(_)))
+    }
+}
+
+type Attempt = Shared<BoxFuture<'static, connection::Result<Handle>>>;
+
+#[derive(Clone)]
+enum Slot {
+    Empty,
+    Connecting { id: u64, attempt: Attempt },
+    Connected(Handle),
+}
+
+impl fmt::Debug for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty"),
+            Self::Connecting { id, .. } => f.debug_struct("Connecting").field("id", id).finish(),
+            Self::Connected(handle) => f.debug_tuple("Connected").field(handle).finish(),
+        }
+    }
+}
+
+impl ConnectionPool {
+    /// Creates a pool which maintains `size` connections to the endpoint described by `connect`
+    ///
+    /// A `size` of `0` is treated as `1`. No connections are established until the first stream
+    /// is opened.
+    pub fn new(client: Client, connect: Connect, size: usize) -> Self {
+        let state = State {
+            connections: vec![Slot::Empty; size.max(1)],
+            next: 0,
+            next_attempt: 0,
+        };
+
+        Self {
+            client,
+            connect,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Returns the number of connections maintained by the pool
+    pub fn size(&self) -> usize {
+        self.state().connections.len()
+    }
+
+    /// Returns the number of pooled connections which are currently established
+    pub fn connected(&self) -> usize {
+        self.state()
+            .connections
+            .iter()
+            .filter(|slot| matches!(slot, Slot::Connected(_)))
+            .count()
+    }
+
+    /// Opens a new [`BidirectionalStream`] on the next connection in the pool
+    pub async fn open_bidirectional_stream(&self) -> connection::Result<BidirectionalStream> {
+        let mut attempts = self.size();
+
+        loop {
+            let (index, mut handle) = self.select().await?;
+
+            match handle.open_bidirectional_stream().await {
+                Ok(stream) => return Ok(stream),
+                Err(error) => {
+                    self.evict(index, &handle);
+                    attempts -= 1;
+                    if attempts == 0 {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Opens a new [`SendStream`] on the next connection in the pool
+    pub async fn open_send_stream(&self) -> connection::Result<SendStream> {
+        let mut attempts = self.size();
+
+        loop {
+            let (index, mut handle) = self.select().await?;
+
+            match handle.open_send_stream().await {
+                Ok(stream) => return Ok(stream),
+                Err(error) => {
+                    self.evict(index, &handle);
+                    attempts -= 1;
+                    if attempts == 0 {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the next established connection in the pool, reconnecting slots as they're
+    /// selected
+    async fn select(&self) -> connection::Result<(usize, Handle)> {
+        let mut error = None;
+
+        for _ in 0..self.size() {
+            let (index, id, attempt) = {
+                let mut state = self.state();
+                let index = state.next;
+                state.next = (index + 1) % state.connections.len();
+
+                let (id, attempt) = match &state.connections[index] {
+                    Slot::Connected(handle) => return Ok((index, handle.clone())),
+                    Slot::Connecting { id, attempt } => (*id, attempt.clone()),
+                    Slot::Empty => {
+                        let id = state.next_attempt;
+                        state.next_attempt += 1;
+                        let attempt = self.connect();
+                        state.connections[index] = Slot::Connecting {
+                            id,
+                            attempt: attempt.clone(),
+                        };
+                        (id, attempt)
+                    }
+                };
+
+                // Polling the attempt submits the connection request to the endpoint, which then
+                // performs the handshake on its own. The result is picked up by the next caller
+                // to select the slot.
+                match attempt.clone().now_or_never() {
+                    Some(result) => {
+                        Self::on_attempt(&mut state, index, id, &result);
+                        match result {
+                            Ok(handle) => return Ok((index, handle)),
+                            Err(err) => {
+                                error = Some(err);
+                                continue;
+                            }
+                        }
+                    }
+                    // use an established connection instead of waiting for the handshake
+                    None if state.has_connected() => continue,
+                    None => (index, id, attempt),
+                }
+            };
+
+            let result = attempt.await;
+            Self::on_attempt(&mut self.state(), index, id, &result);
+
+            match result {
+                Ok(handle) => return Ok((index, handle)),
+                Err(err) => error = Some(err),
+            }
+        }
+
+        Err(error.unwrap_or_else(connection::Error::unspecified))
+    }
+
+    /// Stores the result of a connection attempt in its slot
+    ///
+    /// Only the first caller to observe the result updates the slot.
+    fn on_attempt(state: &mut State, index: usize, id: u64, result: &connection::Result<Handle>) {
+        let slot = &mut state.connections[index];
+        if matches!(slot, Slot::Connecting { id: current, .. } if *current == id) {
+            *slot = match result {
+                Ok(handle) => Slot::Connected(handle.clone()),
+                // the next caller to select the slot tries again
+                Err(_) => Slot::Empty,
+            };
+        }
+    }
+
+    /// Starts establishing a connection which can be awaited by multiple callers
+    fn connect(&self) -> Attempt {
+        let attempt = self.client.connect(self.connect.clone());
+        async move {
+            let (handle, _acceptor) = attempt.await?.split();
+            Ok(handle)
+        }
+        .boxed()
+        .shared()
+    }
+
+    /// Removes the failed connection from the pool so it's replaced the next time it's selected
+    fn evict(&self, index: usize, handle: &Handle) {
+        let mut state = self.state();
+        let slot = &mut state.connections[index];
+        if matches!(slot, Slot::Connected(pooled) if pooled.id() == handle.id()) {
+            *slot = Slot::Empty;
+        }
+    }
+
+    #[inline]
+    fn state(&self) -> MutexGuard<State> {
+        // the state is never left inconsistent so a poisoned lock can still be used
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
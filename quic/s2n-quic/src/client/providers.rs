@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::server::{self, ServerProviders};
 use core::marker::PhantomData;
 use s2n_quic_core::{connection::id::Generator, crypto, path};
 use s2n_quic_transport::{connection, endpoint, stream};
@@ -56,7 +57,53 @@ impl<
         Datagram,
     >
 {
-    pub fn start(self) -> Result<Client, StartError> {
+    pub fn start(self, settings: Settings) -> Result<Client, StartError> {
+        let (endpoint_config, io) = self.into_endpoint_config::<IO::PathHandle>(settings)?;
+
+        let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
+
+        // Start the IO last
+        let local_addr = io.start(endpoint).map_err(StartError::new)?;
+
+        Ok(Client {
+            connector,
+            local_addr,
+        })
+    }
+
+    /// Starts the client along with a server which shares the client's IO provider
+    pub fn start_with_server<Server: ServerProviders>(
+        self,
+        settings: Settings,
+        server: server::Builder<Server>,
+    ) -> Result<(Client, crate::Server), StartError> {
+        let (client_config, io) = self.into_endpoint_config::<IO::PathHandle>(settings)?;
+        let (server_config, _io) = server
+            .0
+            .build()
+            .into_endpoint_config::<IO::PathHandle>(server.1)?;
+
+        let (endpoint, acceptor, connector) =
+            endpoint::peer::Endpoint::new(server_config, client_config);
+
+        // Start the IO last
+        let local_addr = io.start(endpoint).map_err(StartError::new)?;
+
+        let client = Client {
+            connector,
+            local_addr,
+        };
+        let server = crate::Server::new(acceptor, local_addr);
+
+        Ok((client, server))
+    }
+
+    /// Starts all of the providers, except for the IO provider, and returns the endpoint
+    /// configuration along with the IO provider
+    fn into_endpoint_config<PathHandle: path::Handle>(
+        self,
+        settings: Settings,
+    ) -> Result<(impl endpoint::Config<PathHandle = PathHandle>, IO), StartError> {
         let Self {
             congestion_controller,
             connection_close_formatter,
@@ -86,7 +133,12 @@ impl<
         let token = Token;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = PathMigration;
-        let tls = tls.start_client().map_err(StartError::new)?;
+        let tls = if settings.fips {
+            tls.start_fips_client().map_err(StartError::new)?
+        } else {
+            tls.start_client().map_err(StartError::new)?
+        };
+        settings.validate_tls(&tls)?;
         let datagram = datagram.start().map_err(StartError::new)?;
 
         // Validate providers
@@ -117,17 +169,11 @@ impl<
             path_handle: PhantomData,
             path_migration,
             datagram,
+            path_cache: settings.path_cache,
+            packet_space_pool: settings.packet_space_pool,
         };
 
-        let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
-
-        // Start the IO last
-        let local_addr = io.start(endpoint).map_err(StartError::new)?;
-
-        Ok(Client {
-            connector,
-            local_addr,
-        })
+        Ok((endpoint_config, io))
     }
 }
 
@@ -218,6 +264,8 @@ struct EndpointConfig<
     path_handle: PhantomData<PathHandle>,
     path_migration: PathMigration,
     datagram: Datagram,
+    path_cache: Option<path::cache::Config>,
+    packet_space_pool: usize,
 }
 
 impl<
@@ -321,4 +369,12 @@ impl<
             datagram: &mut self.datagram,
         }
     }
+
+    fn path_cache(&self) -> Option<path::cache::Config> {
+        self.path_cache
+    }
+
+    fn packet_space_pool_capacity(&self) -> usize {
+        self.packet_space_pool
+    }
 }
@@ -9,14 +9,31 @@ mod acceptor;
 #[macro_use]
 mod handle;
 
+pub mod request;
+
 pub use acceptor::*;
 pub use handle::*;
-pub use s2n_quic_core::connection::Error;
+pub use s2n_quic_core::{
+    connection::{
+        ChannelBinding, Error, Fingerprint, RecoveryStats, Statistics, TransmissionStatus,
+    },
+    path::cache::Config as PathCache,
+    recovery::ProbePolicy,
+};
+pub use s2n_quic_transport::{
+    connection::{IdMapMetrics, IdMapOccupancy},
+    endpoint::lifecycle::{Closed, ClosedConnections},
+};
 
 pub mod error {
     pub use s2n_quic_core::transport::error::Code;
 }
 
+#[cfg(any(test, feature = "unstable-ack-control"))]
+pub mod ack {
+    pub use s2n_quic_core::{ack::Mode, packet::number::PacketNumberSpace};
+}
+
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 pub struct Connection(Inner);
@@ -103,6 +103,96 @@ macro_rules! impl_handle_api {
             Ok(BidirectionalStream::new(stream)).into()
         }
 
+        /// Sends a request on a new bidirectional stream and waits for the peer's response
+        ///
+        /// The request is written to the stream and the stream is finished. The response is then
+        /// collected until the peer finishes its side of the stream. Responses are limited to the
+        /// default [`request::Limits`](crate::connection::request::Limits). Use
+        /// [`request_with`](Self::request_with) to configure the limits or bound how long to wait
+        /// for the response.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), s2n_quic::connection::request::Error> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let response = connection
+        ///     .request(bytes::Bytes::from_static(b"ping"))
+        ///     .await?;
+        /// println!("received {:?}", response.body());
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn request(
+            &mut self,
+            request: bytes::Bytes,
+        ) -> core::result::Result<
+            $crate::connection::request::Response,
+            $crate::connection::request::Error,
+        > {
+            self.request_with(request, Default::default(), core::future::pending::<()>())
+                .await
+        }
+
+        /// Sends a request on a new bidirectional stream and waits for the peer's response, or
+        /// for the `deadline` future to complete, whichever happens first.
+        ///
+        /// The `deadline` can be any future, such as a timer from the application's runtime. If
+        /// the deadline passes or the response exceeds the `limits`, the stream is dropped, which
+        /// notifies the peer to stop sending the response.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), s2n_quic::connection::request::Error> {
+        /// #   use s2n_quic::connection::request;
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let limits = request::Limits::new().with_max_response_size(4096);
+        /// let deadline = tokio::time::sleep(core::time::Duration::from_secs(1));
+        /// let response = connection
+        ///     .request_with(bytes::Bytes::from_static(b"ping"), limits, deadline)
+        ///     .await?;
+        /// println!("received {:?}", response.body());
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn request_with<D>(
+            &mut self,
+            request: bytes::Bytes,
+            limits: $crate::connection::request::Limits,
+            deadline: D,
+        ) -> core::result::Result<
+            $crate::connection::request::Response,
+            $crate::connection::request::Error,
+        >
+        where
+            D: core::future::Future,
+        {
+            let mut deadline = core::pin::pin!(deadline);
+            let mut exchange = core::pin::pin!(async {
+                let stream = self.open_bidirectional_stream().await?;
+                $crate::connection::request::exchange(stream, request, limits).await
+            });
+
+            ::futures::future::poll_fn(|cx| {
+                if let core::task::Poll::Ready(result) =
+                    core::future::Future::poll(exchange.as_mut(), cx)
+                {
+                    return core::task::Poll::Ready(result);
+                }
+
+                core::future::Future::poll(deadline.as_mut(), cx)
+                    .map(|_| Err($crate::connection::request::Error::TimedOut))
+            })
+            .await
+        }
+
         /// Opens a [`SendStream`](`crate::stream::SendStream`)
         ///
         /// # Examples
@@ -139,6 +229,60 @@ macro_rules! impl_handle_api {
             Ok(SendStream::new(stream.into())).into()
         }
 
+        /// Opens the lowest reserved [`BidirectionalStream`](`crate::stream::BidirectionalStream`)
+        ///
+        /// Reserved streams are configured with
+        /// [`Limits::with_reserved_local_bidirectional_streams`](crate::provider::limits::Limits::with_reserved_local_bidirectional_streams)
+        /// and are always assigned the lowest stream IDs, regardless of when the other streams on
+        /// the connection are opened.
+        ///
+        /// The method will return
+        ///  - `Ok(Some(stream))` if a reserved stream was opened
+        ///  - `Ok(None)` if all of the reserved streams have already been opened
+        ///  - `Err(stream_error)` if the stream could not be opened due to an error
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// if let Some(control) = handle.open_reserved_bidirectional_stream()? {
+        ///     println!("opened control stream {}", control.id());
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn open_reserved_bidirectional_stream(
+            &mut self,
+        ) -> $crate::connection::Result<Option<$crate::stream::BidirectionalStream>> {
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::BidirectionalStream;
+
+            let stream = self.0.open_reserved_stream(StreamType::Bidirectional)?;
+
+            Ok(stream.map(BidirectionalStream::new))
+        }
+
+        /// Opens the lowest reserved [`SendStream`](`crate::stream::SendStream`)
+        ///
+        /// Reserved streams are configured with
+        /// [`Limits::with_reserved_local_unidirectional_streams`](crate::provider::limits::Limits::with_reserved_local_unidirectional_streams).
+        /// See [`Self::open_reserved_bidirectional_stream`] for more details.
+        #[inline]
+        pub fn open_reserved_send_stream(
+            &mut self,
+        ) -> $crate::connection::Result<Option<$crate::stream::SendStream>> {
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::SendStream;
+
+            let stream = self.0.open_reserved_stream(StreamType::Unidirectional)?;
+
+            Ok(stream.map(|stream| SendStream::new(stream.into())))
+        }
+
         /// Returns the local address that this connection is bound to.
         #[inline]
         pub fn local_addr(&self) -> $crate::connection::Result<std::net::SocketAddr> {
@@ -151,6 +295,31 @@ macro_rules! impl_handle_api {
             self.0.remote_address().map(std::net::SocketAddr::from)
         }
 
+        /// Returns the local address of this connection as observed by the peer.
+        ///
+        /// The address is only reported by peers that support the QUIC address discovery
+        /// extension, which is enabled with
+        /// [`Limits::with_address_discovery`](crate::provider::limits::Limits::with_address_discovery).
+        /// `None` is returned until the peer has reported an address.
+        #[inline]
+        pub fn observed_addr(&self) -> $crate::connection::Result<Option<std::net::SocketAddr>> {
+            self.0
+                .observed_address()
+                .map(|address| address.map(std::net::SocketAddr::from))
+        }
+
+        /// Returns the maximum UDP payload size that has been validated on the active path.
+        ///
+        /// The value starts at the configured initial MTU and changes as path MTU discovery
+        /// probes for larger sizes or detects a black hole. It excludes the IP and UDP headers,
+        /// matching the `mtu` reported by the [`MtuUpdated`](crate::provider::event::events::MtuUpdated)
+        /// event. The MTU of paths to specific destinations can be capped with the `with_mtu_clamp`
+        /// option on the IO provider builder.
+        #[inline]
+        pub fn current_mtu(&self) -> $crate::connection::Result<u16> {
+            self.0.current_mtu()
+        }
+
         /// Returns the negotiated server name the connection is using.
         #[inline]
         pub fn server_name(&self) -> $crate::connection::Result<Option<$crate::server::Name>> {
@@ -163,6 +332,127 @@ macro_rules! impl_handle_api {
             self.0.application_protocol()
         }
 
+        /// Returns `true` if the TLS session was resumed from a session ticket
+        ///
+        /// Resumed connections skip certificate authentication, which makes this useful for
+        /// prioritizing returning clients when accepting connections. Both the s2n-tls and rustls
+        /// providers report resumption; `false` is returned by providers which don't.
+        #[inline]
+        pub fn is_resumed(&self) -> $crate::connection::Result<bool> {
+            self.0.is_resumed()
+        }
+
+        /// Returns a stable, non-reversible fingerprint which identifies the connection to both
+        /// peers
+        ///
+        /// The fingerprint is exported from the TLS session once the handshake completes, so the
+        /// client and server derive the same value. This makes it usable as a privacy-preserving
+        /// key for correlating the logs of both peers. `None` is returned if the TLS provider
+        /// does not support exporting keying material.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// if let Some(fingerprint) = connection.fingerprint()? {
+        ///     tracing::info!(%fingerprint, "connection established");
+        /// }
+        /// ```
+        #[inline]
+        pub fn fingerprint(
+            &self,
+        ) -> $crate::connection::Result<Option<$crate::connection::Fingerprint>> {
+            self.0.fingerprint()
+        }
+
+        /// Returns the [RFC 9266](https://www.rfc-editor.org/rfc/rfc9266) `tls-exporter` channel
+        /// binding for the connection
+        ///
+        /// Authentication protocols layered on top of the connection's streams can include this
+        /// value to bind their exchanges to the QUIC connection. The value is available once the
+        /// handshake completes and is the same for both peers. `None` is returned if the TLS
+        /// provider does not support exporting keying material.
+        #[inline]
+        pub fn channel_binding(
+            &self,
+        ) -> $crate::connection::Result<Option<$crate::connection::ChannelBinding>> {
+            self.0.channel_binding()
+        }
+
+        /// Returns a snapshot of the limits which currently apply to sending data on the
+        /// connection
+        ///
+        /// This can be used to determine why a transfer is making slow progress, e.g. whether the
+        /// connection is waiting on the congestion controller or on the peer to grant more flow
+        /// control credits.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// let status = connection.transmission_status()?;
+        ///
+        /// if status.is_connection_flow_limited() {
+        ///     println!("waiting on the peer to raise MAX_DATA");
+        /// }
+        /// ```
+        #[inline]
+        pub fn transmission_status(
+            &self,
+        ) -> $crate::connection::Result<$crate::connection::TransmissionStatus> {
+            self.0.transmission_status()
+        }
+
+        /// Returns the delivery statistics which have been recorded for the connection
+        ///
+        /// The statistics include the number of duplicate packets received, stream bytes
+        /// retransmitted and losses which turned out to be spurious, which can be used to account
+        /// for the overhead of delivering the application's data.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// let statistics = connection.statistics()?;
+        ///
+        /// println!("retransmitted {} bytes", statistics.retransmitted_bytes);
+        /// ```
+        #[inline]
+        pub fn statistics(&self) -> $crate::connection::Result<$crate::connection::Statistics> {
+            self.0.statistics()
+        }
+
+        /// Returns the current RTT estimate, probe timeout (PTO) count and congestion control
+        /// state of the connection's active path
+        ///
+        /// This samples the same values which are reported with the `RecoveryMetrics` event,
+        /// without needing to subscribe to each update.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// let stats = connection.query_stats()?;
+        ///
+        /// println!("srtt={:?} cwnd={}", stats.smoothed_rtt, stats.congestion_window);
+        /// ```
+        #[inline]
+        pub fn query_stats(&self) -> $crate::connection::Result<$crate::connection::RecoveryStats> {
+            self.0.query_stats()
+        }
+
+        /// Returns the QUIC version negotiated for the connection
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn example(connection: &s2n_quic::Connection) -> s2n_quic::connection::Result<()> {
+        /// // only QUIC version 1 is currently negotiated
+        /// assert_eq!(connection.quic_version()?, 1);
+        /// # Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn quic_version(&self) -> $crate::connection::Result<u32> {
+            self.0.quic_version()
+        }
+
         /// Returns the internal identifier for the [`Connection`](`crate::Connection`)
         ///
         /// Note: This internal identifier is not the same as the connection ID included in packet
@@ -178,6 +468,35 @@ macro_rules! impl_handle_api {
             self.0.ping()
         }
 
+        /// Sends a PATH_CHALLENGE frame on the active path and waits for the peer's response
+        ///
+        /// Returns the measured round-trip time, or `None` if the peer didn't respond before the
+        /// probe timed out. The probe doesn't contribute to the connection's RTT estimate or
+        /// congestion state.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// if let Some(rtt) = connection.probe_path().await? {
+        ///     println!("path round-trip time: {rtt:?}");
+        /// }
+        /// ```
+        #[inline]
+        pub async fn probe_path(
+            &mut self,
+        ) -> $crate::connection::Result<Option<core::time::Duration>> {
+            futures::future::poll_fn(|cx| self.poll_probe_path(cx)).await
+        }
+
+        /// Polls for the outcome of a path probe, starting a new one if none is in progress
+        #[inline]
+        pub fn poll_probe_path(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<Option<core::time::Duration>>> {
+            self.0.poll_probe_path(cx)
+        }
+
         /// Enables or disables the connection to actively keep the connection alive with the peer
         ///
         /// This can be useful for maintaining connections beyond the configured idle timeout. The
@@ -188,6 +507,172 @@ macro_rules! impl_handle_api {
             self.0.keep_alive(enabled)
         }
 
+        /// Sets the period at which the connection flushes pending transmissions, or disables it
+        /// with `None`
+        ///
+        /// This is intended for applications producing data at a regular cadence, e.g. audio or
+        /// video frames every 20ms. On each tick, any acknowledgements which would otherwise be
+        /// delayed are sent immediately, along with any buffered stream data and datagrams, without
+        /// the application needing to manage a timer for each packet.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// connection.set_transmit_tick(Some(core::time::Duration::from_millis(20)))?;
+        /// ```
+        #[inline]
+        pub fn set_transmit_tick(
+            &mut self,
+            period: Option<core::time::Duration>,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_transmit_tick(period)
+        }
+
+        /// Sets the maximum amount of time small stream writes are held back so they can be
+        /// combined into fuller packets, or disables coalescing with `None`
+        ///
+        /// This is similar to Nagle's algorithm in TCP and is intended for chatty protocols which
+        /// issue many small writes. New stream data is held until either enough has been written to
+        /// fill a packet or the delay has passed. Finishing, resetting or flushing a stream releases
+        /// any held data immediately. Coalescing is disabled by default.
+        ///
+        /// The effect can be evaluated with the packet fill counters in
+        /// [`Statistics`](crate::connection::Statistics).
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// connection.set_tx_coalescing(Some(core::time::Duration::from_millis(1)))?;
+        /// ```
+        #[inline]
+        pub fn set_tx_coalescing(
+            &mut self,
+            delay: Option<core::time::Duration>,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_tx_coalescing(delay)
+        }
+
+        /// Sets the policy which selects the contents of the probes sent when the probe
+        /// timeout (PTO) expires
+        ///
+        /// By default, probes carry new data followed by any unacknowledged data which fits.
+        /// Latency-sensitive request/response protocols may prefer to only send new data, while
+        /// bulk transfers may prefer to repair the oldest unacknowledged data first. The contents
+        /// of each probe are reported with the `PtoProbeSent` event.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use s2n_quic::connection::ProbePolicy;
+        ///
+        /// connection.set_probe_policy(ProbePolicy::NewData)?;
+        /// ```
+        #[inline]
+        pub fn set_probe_policy(
+            &mut self,
+            policy: $crate::connection::ProbePolicy,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_probe_policy(policy)
+        }
+
+        /// Controls the transmission of ACK frames for the given packet number space
+        ///
+        /// This is intended for tests which need to construct reproducible loss recovery
+        /// scenarios, e.g. withholding acknowledgements to trigger probe timeouts at the peer or
+        /// acknowledging every packet immediately. Changing the mode for a packet number space
+        /// that has already been discarded has no effect.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use s2n_quic::connection::ack;
+        ///
+        /// connection.set_ack_mode(ack::PacketNumberSpace::ApplicationData, ack::Mode::Withhold)?;
+        /// // ... wait for the peer to send probes
+        /// connection.set_ack_mode(ack::PacketNumberSpace::ApplicationData, ack::Mode::Default)?;
+        /// ```
+        #[cfg(any(test, feature = "unstable-ack-control"))]
+        #[inline]
+        pub fn set_ack_mode(
+            &mut self,
+            space: $crate::connection::ack::PacketNumberSpace,
+            mode: $crate::connection::ack::Mode,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_ack_mode(space, mode)
+        }
+
+        /// Attaches an application-defined context to the connection, replacing any previous value
+        ///
+        /// The context can be retrieved with [`Self::application_context`] and is also available
+        /// to event subscribers through
+        /// [`ConnectionMeta::application_context`](crate::provider::event::ConnectionMeta::application_context),
+        /// which allows correlating connection events with application state.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// use s2n_quic::provider::event::{events, ConnectionMeta, Subscriber};
+        ///
+        /// #[derive(Clone, Copy, Debug)]
+        /// struct RequestId(u64);
+        ///
+        /// struct MySubscriber;
+        ///
+        /// impl Subscriber for MySubscriber {
+        ///     type ConnectionContext = ();
+        ///
+        ///     fn create_connection_context(
+        ///         &mut self,
+        ///         _meta: &ConnectionMeta,
+        ///         _info: &events::ConnectionInfo,
+        ///     ) -> Self::ConnectionContext {
+        ///     }
+        ///
+        ///     fn on_packet_lost(
+        ///         &mut self,
+        ///         _context: &mut Self::ConnectionContext,
+        ///         meta: &ConnectionMeta,
+        ///         _event: &events::PacketLost,
+        ///     ) {
+        ///         if let Some(request_id) = meta.application_context.get::<RequestId>() {
+        ///             println!("packet lost while serving {request_id:?}");
+        ///         }
+        ///     }
+        /// }
+        ///
+        /// # fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// connection.set_application_context(Box::new(RequestId(123)))?;
+        ///
+        /// let request_id: Option<RequestId> = connection.application_context()?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_application_context(
+            &mut self,
+            context: Box<dyn core::any::Any + Send>,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_application_context(context)
+        }
+
+        /// Returns a copy of the context attached with [`Self::set_application_context`]
+        ///
+        /// `Ok(None)` is returned if no context has been set or if it is not of type `T`.
+        #[inline]
+        pub fn application_context<T>(&self) -> $crate::connection::Result<Option<T>>
+        where
+            T: 'static + Clone,
+        {
+            use s2n_quic_core::query;
+            let mut query = query::Once::new(|context: &T| context.clone());
+
+            self.0.query_application_context(&mut query)?;
+
+            Ok(core::result::Result::<T, query::Error>::from(query).ok())
+        }
+
         /// Closes the Connection with the provided error code
         ///
         /// This will immediately terminate all outstanding streams.
@@ -209,6 +694,145 @@ macro_rules! impl_handle_api {
             self.0.close(error_code)
         }
 
+        /// Resets the sending half of every open stream for which `predicate` returns `true`
+        ///
+        /// The predicate is called with the ID of each open stream, which carries the stream's
+        /// type and initiator. All of the matching streams are reset with `error_code` in a single
+        /// pass over the connection's streams, and the connection is woken up once to transmit
+        /// the resulting `RESET_STREAM` frames. Any tasks blocked on the matching streams are woken
+        /// up with an error.
+        ///
+        /// Returns the number of streams which matched the predicate.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// const MY_ERROR_CODE:u32 = 99;
+        /// let count = connection.reset_streams_where(MY_ERROR_CODE.into(), |id| {
+        ///     id.stream_type().is_unidirectional()
+        /// })?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn reset_streams_where<F: FnMut($crate::stream::Id) -> bool>(
+            &mut self,
+            error_code: $crate::application::Error,
+            mut predicate: F,
+        ) -> $crate::connection::Result<usize> {
+            self.0.close_streams(Some(error_code), None, &mut predicate)
+        }
+
+        /// Stops the receiving half of every open stream for which `predicate` returns `true`
+        ///
+        /// This behaves like [`Self::reset_streams_where`], except that the peer is asked to stop
+        /// sending on the matching streams with `STOP_SENDING` frames.
+        ///
+        /// Returns the number of streams which matched the predicate.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// use s2n_quic::stream::Type;
+        ///
+        /// const MY_ERROR_CODE:u32 = 99;
+        /// let count = connection.stop_sending_streams_where(MY_ERROR_CODE.into(), |id| {
+        ///     id.stream_type() == Type::Bidirectional
+        /// })?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn stop_sending_streams_where<F: FnMut($crate::stream::Id) -> bool>(
+            &mut self,
+            error_code: $crate::application::Error,
+            mut predicate: F,
+        ) -> $crate::connection::Result<usize> {
+            self.0.close_streams(None, Some(error_code), &mut predicate)
+        }
+
+        /// Raises the flow control window of the receiving half of a stream
+        ///
+        /// The stream allows the peer to send up to `window` bytes beyond the data which was
+        /// already consumed by the application, replacing the window configured in
+        /// [`Limits`](crate::provider::limits::Limits) when the stream was opened. The additional
+        /// credit is advertised to the peer right away with a `MAX_STREAM_DATA` frame.
+        ///
+        /// The window can only be raised, since the peer may already be using the credit which
+        /// was advertised. Smaller values are ignored, as are values larger than `u32::MAX`,
+        /// which are capped.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #   let stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// // allow the peer to send more data on a stream which turned out to be a bulk transfer
+        /// connection.set_max_stream_data(stream.id(), 16 * 1024 * 1024)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_max_stream_data(
+            &mut self,
+            stream_id: u64,
+            window: u64,
+        ) -> $crate::stream::Result<()> {
+            use s2n_quic_core::varint::VarInt;
+
+            let stream_id = VarInt::new(stream_id)
+                .map($crate::stream::Id::from_varint)
+                .map_err(|_| $crate::stream::Error::invalid_stream())?;
+            let window = u32::try_from(window).unwrap_or(u32::MAX);
+            self.0.set_max_stream_data(stream_id, window)
+        }
+
+        /// Raises the number of streams of the given type that the peer may have open concurrently
+        ///
+        /// This replaces the limit configured in [`Limits`](crate::provider::limits::Limits) for
+        /// the connection. The additional streams are advertised to the peer right away with a
+        /// `MAX_STREAMS` frame.
+        ///
+        /// The limit can only be raised, since the peer may already have opened the streams which
+        /// were advertised. Smaller values are ignored.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// use s2n_quic::stream::Type;
+        ///
+        /// // allow a well-behaved client to issue more concurrent requests
+        /// connection.set_max_streams(Type::Bidirectional, 1000)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_max_streams(
+            &mut self,
+            stream_type: $crate::stream::Type,
+            limit: u64,
+        ) -> $crate::connection::Result<()> {
+            use s2n_quic_core::varint::VarInt;
+
+            let limit = VarInt::new(limit).unwrap_or(VarInt::MAX);
+            self.0.set_max_streams(stream_type, limit)
+        }
+
         /// API for querying the connection's
         /// [`Subscriber::ConnectionContext`](crate::provider::event::Subscriber::ConnectionContext).
         ///
@@ -407,6 +1031,91 @@ macro_rules! impl_handle_api {
 
             query.into()
         }
+
+        /// Polls for an unreliable datagram received from the peer
+        ///
+        /// The datagram is taken from the connection's receive queue in the
+        /// [default datagram provider](crate::provider::datagram::default), which must be
+        /// configured on the endpoint with `with_datagram`. If the queue is empty, the task is
+        /// woken once a datagram arrives.
+        ///
+        /// See [`Self::datagram_recv`] for the `async` version of this method.
+        #[cfg(any(test, feature = "unstable-provider-datagram"))]
+        #[inline]
+        pub fn poll_datagram_recv(
+            &self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            core::result::Result<bytes::Bytes, $crate::provider::datagram::default::DatagramError>,
+        > {
+            use $crate::provider::datagram::default::Receiver;
+
+            self.datagram_mut(|receiver: &mut Receiver| receiver.poll_recv_datagram(cx))
+                .unwrap_or_else(|error| core::task::Poll::Ready(Err(error.into())))
+        }
+
+        /// Receives an unreliable datagram from the peer
+        ///
+        /// Returns an error once the connection is closed and no more datagrams are queued, or if
+        /// the connection isn't using the default datagram provider.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// while let Ok(datagram) = connection.datagram_recv().await {
+        ///     println!("received {datagram:?}");
+        /// }
+        /// ```
+        #[cfg(any(test, feature = "unstable-provider-datagram"))]
+        #[inline]
+        pub async fn datagram_recv(
+            &self,
+        ) -> core::result::Result<bytes::Bytes, $crate::provider::datagram::default::DatagramError>
+        {
+            futures::future::poll_fn(|cx| self.poll_datagram_recv(cx)).await
+        }
+
+        /// Polls for capacity to enqueue an unreliable datagram for the peer
+        ///
+        /// If the connection's send queue in the
+        /// [default datagram provider](crate::provider::datagram::default) is full, the datagram
+        /// is left in `datagram` and the task is woken once there is room.
+        ///
+        /// See [`Self::datagram_send`] for the `async` version of this method.
+        #[cfg(any(test, feature = "unstable-provider-datagram"))]
+        #[inline]
+        pub fn poll_datagram_send(
+            &self,
+            datagram: &mut bytes::Bytes,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            core::result::Result<(), $crate::provider::datagram::default::DatagramError>,
+        > {
+            use $crate::provider::datagram::default::Sender;
+
+            self.datagram_mut(|sender: &mut Sender| sender.poll_send_datagram(datagram, cx))
+                .unwrap_or_else(|error| core::task::Poll::Ready(Err(error.into())))
+        }
+
+        /// Sends an unreliable datagram to the peer, waiting for room in the send queue
+        ///
+        /// Datagrams may be lost and are never retransmitted. An error is returned if the datagram
+        /// is larger than the peer allows, the connection is closed, or the connection isn't using
+        /// the default datagram provider.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// connection.datagram_send(Bytes::from_static(b"ping")).await?;
+        /// ```
+        #[cfg(any(test, feature = "unstable-provider-datagram"))]
+        #[inline]
+        pub async fn datagram_send(
+            &self,
+            mut datagram: bytes::Bytes,
+        ) -> core::result::Result<(), $crate::provider::datagram::default::DatagramError> {
+            futures::future::poll_fn(|cx| self.poll_datagram_send(&mut datagram, cx)).await
+        }
     };
 }
 
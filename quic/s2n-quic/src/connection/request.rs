@@ -0,0 +1,173 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exchanges a single request and response over a bidirectional stream
+//!
+//! Request/response protocols commonly use a stream per message: the requester opens a stream,
+//! sends the request and finishes the stream, after which the responder replies and finishes the
+//! stream in turn. [`Handle::request`](crate::connection::Handle::request) implements this
+//! pattern in a single call, while bounding the size of the response to protect against
+//! misbehaving peers.
+
+use crate::stream::{self, BidirectionalStream};
+use bytes::{Bytes, BytesMut};
+use core::fmt;
+
+/// The default maximum size of a response, in bytes
+const DEFAULT_MAX_RESPONSE_SIZE: usize = 1 << 20;
+
+/// Limits applied to a request
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    max_response_size: usize,
+}
+
+impl Default for Limits {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Limits {
+    /// Creates limits with the default values
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+        }
+    }
+
+    /// Sets the maximum size of the response, in bytes
+    ///
+    /// The stream is closed with a `STOP_SENDING` frame if the peer exceeds the limit. Defaults
+    /// to 1MiB.
+    #[inline]
+    pub const fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    /// Returns the maximum size of the response, in bytes
+    #[inline]
+    pub const fn max_response_size(&self) -> usize {
+        self.max_response_size
+    }
+}
+
+/// The response to a request
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    stream_id: u64,
+    body: Bytes,
+}
+
+impl Response {
+    /// Returns the ID of the stream the request was sent on
+    #[inline]
+    pub fn stream_id(&self) -> u64 {
+        self.stream_id
+    }
+
+    /// Returns the body of the response
+    #[inline]
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Consumes the response and returns its body
+    #[inline]
+    pub fn into_body(self) -> Bytes {
+        self.body
+    }
+}
+
+/// An error which occurred while exchanging a request
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The stream or connection encountered an error
+    Stream(stream::Error),
+    /// The peer's response exceeded the configured limit
+    ResponseTooLarge { limit: usize },
+    /// The deadline passed before the response was received
+    TimedOut,
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Stream(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Stream(error) => write!(f, "{error}"),
+            Self::ResponseTooLarge { limit } => {
+                write!(f, "the response exceeded the limit of {limit} bytes")
+            }
+            Self::TimedOut => write!(f, "the deadline passed before the response was received"),
+        }
+    }
+}
+
+impl From<stream::Error> for Error {
+    #[inline]
+    fn from(error: stream::Error) -> Self {
+        Self::Stream(error)
+    }
+}
+
+impl From<crate::connection::Error> for Error {
+    #[inline]
+    fn from(error: crate::connection::Error) -> Self {
+        Self::Stream(error.into())
+    }
+}
+
+/// Sends the request on the stream and collects the response until the peer finishes the stream
+pub(crate) async fn exchange(
+    mut stream: BidirectionalStream,
+    request: Bytes,
+    limits: Limits,
+) -> Result<Response, Error> {
+    let stream_id = stream.id();
+
+    if !request.is_empty() {
+        stream.send(request).await?;
+    }
+    stream.finish()?;
+
+    let mut len = 0;
+    let mut chunks = Vec::new();
+
+    while let Some(chunk) = stream.receive().await? {
+        len += chunk.len();
+
+        // dropping the stream notifies the peer that we're no longer reading
+        if len > limits.max_response_size {
+            return Err(Error::ResponseTooLarge {
+                limit: limits.max_response_size,
+            });
+        }
+
+        chunks.push(chunk);
+    }
+
+    // avoid copying the response if it was received in a single chunk
+    let body = if chunks.len() > 1 {
+        let mut body = BytesMut::with_capacity(len);
+        for chunk in chunks {
+            body.extend_from_slice(&chunk);
+        }
+        body.freeze()
+    } else {
+        chunks.pop().unwrap_or_default()
+    };
+
+    Ok(Response { stream_id, body })
+}
@@ -50,6 +50,16 @@
 //!
 //! **NOTE**: this will override the platform detection and always use [`s2n-tls`][s2n-tls] by default.
 //!
+//! ### `dangerous-provider-tls-null`
+//!
+//! Enables the null TLS provider at `provider::tls::null`, which skips the handshake and disables
+//! packet protection. This is intended for benchmarking the transport and platform without the cost
+//! of cryptography, and for fuzzing transport logic which is otherwise unreachable behind packet
+//! authentication.
+//!
+//! **WARNING**: connections using this provider have no confidentiality, integrity or authenticity.
+//! This feature must never be enabled in production deployments.
+//!
 //! [s2n-tls]: https://github.com/aws/s2n-tls
 //! [rustls]: https://github.com/rustls/rustls
 
@@ -65,6 +75,14 @@ pub mod application {
     pub use s2n_quic_core::application::Error;
 }
 
+pub mod packet {
+    pub use s2n_quic_core::packet::number::{PacketNumber, PacketNumberSpace};
+}
+
+pub mod varint {
+    pub use s2n_quic_core::varint::{VarInt, VarIntError};
+}
+
 pub use client::Client;
 pub use connection::Connection;
 pub use server::Server;
@@ -82,9 +100,11 @@ mod tests;
             feature = "unstable-provider-io-testing",
             feature = "unstable-provider-io-turmoil",
             feature = "unstable-provider-io-xdp",
+            feature = "unstable-provider-io-tx-fuzz",
             feature = "unstable-provider-packet-interceptor",
-            feature = "unstable-provider-random",
             feature = "unstable-congestion-controller",
+            feature = "unstable-ack-control",
+            feature = "unstable-provider-tls-fault-injection",
             feature = "unstable_resumption",
         ),
         // any unstable features requires at least one of the following conditions
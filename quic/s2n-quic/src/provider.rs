@@ -14,12 +14,14 @@ pub mod endpoint_limits;
 pub mod event;
 pub mod io;
 pub mod limits;
+pub mod path_migration;
+pub mod random;
+pub mod reload;
 pub mod stateless_reset_token;
 pub mod tls;
 
 // These providers are not currently exposed to applications
 pub(crate) mod connection_close_formatter;
-pub(crate) mod path_migration;
 pub(crate) mod sync;
 
 cfg_if!(
@@ -30,14 +32,6 @@ cfg_if!(
     }
 );
 
-cfg_if!(
-    if #[cfg(any(test, feature = "unstable-provider-random"))] {
-        pub mod random;
-    } else {
-        pub(crate) mod random;
-    }
-);
-
 cfg_if!(
     if #[cfg(any(test, feature = "unstable-provider-datagram"))] {
         pub mod datagram;
@@ -46,6 +40,33 @@ cfg_if!(
     }
 );
 
+/// Endpoint-wide settings which apply across all of the providers
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Settings {
+    /// Only FIPS-approved algorithms may be used
+    pub fips: bool,
+    /// Path characteristics are shared between connections to the same remote prefix
+    pub path_cache: Option<s2n_quic_core::path::cache::Config>,
+    /// The number of packet space buffers which are recycled between connections
+    pub packet_space_pool: usize,
+}
+
+impl Settings {
+    /// Checks that the started TLS endpoint complies with the settings
+    pub(crate) fn validate_tls<T: s2n_quic_core::crypto::tls::Endpoint>(
+        &self,
+        tls: &T,
+    ) -> Result<(), StartError> {
+        if self.fips && !tls.is_fips_compliant() {
+            return Err(StartError::new(
+                "the TLS provider is not restricted to FIPS-approved algorithms",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// An error indicating a failure to start an endpoint
 pub struct StartError(Box<dyn 'static + fmt::Display + Send + Sync>);
 
@@ -6,9 +6,13 @@
 //! The default provider will randomly generate a 256 bit key. This key will be used to sign and
 //! verify tokens. The key can be rotated at a duration set by the user.
 //!
+//! Servers deployed as a fleet behind a load balancer can provide their own [`KeyRotation`] to
+//! share signing keys between endpoints, and their own [`ReplayFilter`] to share the record of
+//! tokens which were already used.
+//!
 //! The default provider does not support tokens delivered in a NEW_TOKEN frame.
 
-use core::{mem::size_of, time::Duration};
+use core::{fmt, mem::size_of, time::Duration};
 use hash_hasher::HashHasher;
 use s2n_codec::{DecoderBuffer, DecoderBufferMut};
 use s2n_quic_core::{
@@ -19,16 +23,105 @@ use std::hash::{Hash, Hasher};
 use zerocopy::{AsBytes, FromBytes, FromZeroes, Unaligned};
 use zeroize::Zeroizing;
 
+/// Provides the key material used to sign and verify tokens
+///
+/// Endpoints which share the same key material and rotation period accept the tokens issued by
+/// each other.
+pub trait KeyRotation: 'static + Send {
+    /// Fills `key` with the material for the key in slot `key_id`, which is either `0` or `1`
+    ///
+    /// This is called each time a key expires. Tokens carry the slot of the key they were signed
+    /// with, and each key remains valid for two rotation periods.
+    fn rotate_key(&mut self, key_id: u8, key: &mut [u8], random: &mut dyn random::Generator);
+}
+
+/// Generates random key material on each rotation
+#[derive(Debug, Default)]
+pub struct RandomKeys;
+
+impl KeyRotation for RandomKeys {
+    #[inline]
+    fn rotate_key(&mut self, _key_id: u8, key: &mut [u8], random: &mut dyn random::Generator) {
+        random.private_random_fill(key);
+    }
+}
+
+/// Detects tokens which are presented more than once
+pub trait ReplayFilter: 'static + Send {
+    /// Returns `true` if a token with the same `tag`, signed by the key in slot `key_id`, was
+    /// already accepted
+    fn contains(&mut self, key_id: u8, tag: &[u8]) -> bool;
+
+    /// Records the `tag` of a token, signed by the key in slot `key_id`, which was accepted
+    fn insert(&mut self, key_id: u8, tag: &[u8]);
+
+    /// Called when the key in slot `key_id` is rotated
+    ///
+    /// Tokens which were signed with the previous key can no longer be validated, so the replay
+    /// window only needs to cover the lifetime of each key.
+    fn on_key_rotated(&mut self, key_id: u8);
+}
+
+/// Records accepted tokens in a cuckoo filter for each key
+pub struct DuplicateFilter {
+    filters: [cuckoofilter::CuckooFilter<HashHasher>; 2],
+}
+
+impl Default for DuplicateFilter {
+    fn default() -> Self {
+        Self {
+            filters: [
+                cuckoofilter::CuckooFilter::with_capacity(cuckoofilter::DEFAULT_CAPACITY),
+                cuckoofilter::CuckooFilter::with_capacity(cuckoofilter::DEFAULT_CAPACITY),
+            ],
+        }
+    }
+}
+
+impl fmt::Debug for DuplicateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DuplicateFilter")
+            .field("len", &[self.filters[0].len(), self.filters[1].len()])
+            .finish()
+    }
+}
+
+impl ReplayFilter for DuplicateFilter {
+    #[inline]
+    fn contains(&mut self, key_id: u8, tag: &[u8]) -> bool {
+        self.filters[key_id as usize].contains(&Tag(tag))
+    }
+
+    #[inline]
+    fn insert(&mut self, key_id: u8, tag: &[u8]) {
+        // Ignore the outcome of adding a token to the filter because we always want to
+        // continue the connection if the filter fails.
+        let _ = self.filters[key_id as usize].add(&Tag(tag));
+    }
+
+    #[inline]
+    fn on_key_rotated(&mut self, key_id: u8) {
+        // TODO clear the filter instead of recreating. This is pending a merge to crates.io
+        // (https://github.com/axiomhq/rust-cuckoofilter/pull/52)
+        self.filters[key_id as usize] =
+            cuckoofilter::CuckooFilter::with_capacity(cuckoofilter::DEFAULT_CAPACITY);
+    }
+}
+
+/// Token hashes are taken from the hmac
+struct Tag<'a>(&'a [u8]);
+
+impl<'a> Hash for Tag<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(self.0);
+    }
+}
+
 struct BaseKey {
     active_duration: Duration,
 
     // HMAC key for signing and verifying
     key: Option<(Timestamp, hmac::Key)>,
-
-    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-    //# To protect against such attacks, servers MUST ensure that
-    //# replay of tokens is prevented or limited.
-    duplicate_filter: cuckoofilter::CuckooFilter<HashHasher>,
 }
 
 impl BaseKey {
@@ -36,18 +129,27 @@ impl BaseKey {
         Self {
             active_duration,
             key: None,
-            duplicate_filter: cuckoofilter::CuckooFilter::with_capacity(
-                cuckoofilter::DEFAULT_CAPACITY,
-            ),
         }
     }
 
-    pub fn hasher(&mut self, random: &mut dyn random::Generator) -> Option<hmac::Context> {
-        let key = self.poll_key(random)?;
+    pub fn hasher(
+        &mut self,
+        key_id: u8,
+        key_rotation: &mut dyn KeyRotation,
+        replay_filter: &mut dyn ReplayFilter,
+        random: &mut dyn random::Generator,
+    ) -> Option<hmac::Context> {
+        let key = self.poll_key(key_id, key_rotation, replay_filter, random)?;
         Some(hmac::Context::with_key(&key))
     }
 
-    fn poll_key(&mut self, random: &mut dyn random::Generator) -> Option<hmac::Key> {
+    fn poll_key(
+        &mut self,
+        key_id: u8,
+        key_rotation: &mut dyn KeyRotation,
+        replay_filter: &mut dyn ReplayFilter,
+        random: &mut dyn random::Generator,
+    ) -> Option<hmac::Key> {
         let now = s2n_quic_platform::time::now();
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-21.3
@@ -62,16 +164,12 @@ impl BaseKey {
 
         let expires_at = now.checked_add(self.active_duration)?;
 
-        // TODO in addition to generating new key material, clear out the filter used for detecting
-        // duplicates.
         let mut key_material = Zeroizing::new([0; digest::SHA256_OUTPUT_LEN]);
-        random.private_random_fill(&mut key_material[..]);
+        key_rotation.rotate_key(key_id, &mut key_material[..], random);
         let key = hmac::Key::new(hmac::HMAC_SHA256, key_material.as_ref());
 
-        // TODO clear the filter instead of recreating. This is pending a merge to crates.io
-        // (https://github.com/axiomhq/rust-cuckoofilter/pull/52)
-        self.duplicate_filter =
-            cuckoofilter::CuckooFilter::with_capacity(cuckoofilter::DEFAULT_CAPACITY);
+        // tokens signed with the previous key can no longer be replayed
+        replay_filter.on_key_rotated(key_id);
 
         self.key = Some((expires_at, key));
 
@@ -81,7 +179,6 @@ impl BaseKey {
 
 const DEFAULT_KEY_ROTATION_PERIOD: Duration = Duration::from_millis(1000);
 
-#[derive(Debug)]
 pub struct Provider {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
     //# Thus, a token SHOULD have an
@@ -91,16 +188,101 @@ pub struct Provider {
     /// To fulfill this SHOULD, we rotate the key periodically. This allows
     /// customers to control the token lifetime without adding bytes to the token itself.
     key_rotation_period: Duration,
+    key_rotation: Box<dyn KeyRotation>,
+    replay_filter: Box<dyn ReplayFilter>,
 }
 
 impl Default for Provider {
+    fn default() -> Self {
+        Builder::default().build().unwrap()
+    }
+}
+
+impl fmt::Debug for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Provider")
+            .field("key_rotation_period", &self.key_rotation_period)
+            .finish()
+    }
+}
+
+impl Provider {
+    /// Creates a builder for the provider
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+/// A builder for the default address token [`Provider`]
+pub struct Builder {
+    key_rotation_period: Duration,
+    key_rotation: Box<dyn KeyRotation>,
+    replay_filter: Box<dyn ReplayFilter>,
+}
+
+impl Default for Builder {
     fn default() -> Self {
         Self {
             key_rotation_period: DEFAULT_KEY_ROTATION_PERIOD,
+            key_rotation: Box::new(RandomKeys),
+            replay_filter: Box::<DuplicateFilter>::default(),
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the period at which the signing key is rotated (default: 1 second)
+    ///
+    /// Tokens are accepted for up to two rotation periods after they were issued.
+    pub fn with_key_rotation_period(mut self, period: Duration) -> Result<Self, Error> {
+        if period.is_zero() {
+            return Err(Error::InvalidKeyRotationPeriod);
+        }
+        self.key_rotation_period = period;
+        Ok(self)
+    }
+
+    /// Sets the source of the key material used to sign and verify tokens (default: [`RandomKeys`])
+    pub fn with_key_rotation<K: KeyRotation>(mut self, key_rotation: K) -> Result<Self, Error> {
+        self.key_rotation = Box::new(key_rotation);
+        Ok(self)
+    }
+
+    /// Sets the filter used to detect replayed tokens (default: [`DuplicateFilter`])
+    pub fn with_replay_filter<R: ReplayFilter>(mut self, replay_filter: R) -> Result<Self, Error> {
+        self.replay_filter = Box::new(replay_filter);
+        Ok(self)
+    }
+
+    /// Builds the [`Provider`]
+    pub fn build(self) -> Result<Provider, Error> {
+        Ok(Provider {
+            key_rotation_period: self.key_rotation_period,
+            key_rotation: self.key_rotation,
+            replay_filter: self.replay_filter,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The key rotation period must be greater than zero
+    InvalidKeyRotationPeriod,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidKeyRotationPeriod => {
+                write!(f, "the key rotation period must be greater than zero")
+            }
         }
     }
 }
 
+impl std::error::Error for Error {}
+
 impl super::Provider for Provider {
     type Format = Format;
     type Error = core::convert::Infallible;
@@ -116,6 +298,8 @@ impl super::Provider for Provider {
                 BaseKey::new(self.key_rotation_period * 2),
                 BaseKey::new(self.key_rotation_period * 2),
             ],
+            key_rotation: self.key_rotation,
+            replay_filter: self.replay_filter,
         };
 
         Ok(format)
@@ -143,6 +327,14 @@ pub struct Format {
 
     /// Key used to sign keys
     keys: [BaseKey; 2],
+
+    /// Provides the material for each key
+    key_rotation: Box<dyn KeyRotation>,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# To protect against such attacks, servers MUST ensure that
+    //# replay of tokens is prevented or limited.
+    replay_filter: Box<dyn ReplayFilter>,
 }
 
 impl Format {
@@ -165,7 +357,13 @@ impl Format {
         token: &Token,
         context: &mut super::Context<'_>,
     ) -> Option<hmac::Tag> {
-        let mut ctx = self.keys[token.header.key_id() as usize].hasher(context.random)?;
+        let key_id = token.header.key_id();
+        let mut ctx = self.keys[key_id as usize].hasher(
+            key_id,
+            &mut *self.key_rotation,
+            &mut *self.replay_filter,
+            context.random,
+        )?;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
         //# Tokens
@@ -199,10 +397,9 @@ impl Format {
         context: &mut super::Context<'_>,
         token: &Token,
     ) -> Option<connection::InitialId> {
-        if self.keys[token.header.key_id() as usize]
-            .duplicate_filter
-            .contains(token)
-        {
+        let key_id = token.header.key_id();
+
+        if self.replay_filter.contains(key_id, &token.hmac) {
             return None;
         }
 
@@ -211,12 +408,7 @@ impl Format {
         if constant_time::verify_slices_are_equal(&token.hmac, tag.as_ref()).is_ok() {
             // Only add the token once it has been validated. This will prevent the filter from
             // being filled with garbage tokens.
-
-            // Ignore the outcome of adding a token to the filter because we always want to
-            // continue the connection if the filter fails.
-            let _ = self.keys[token.header.key_id() as usize]
-                .duplicate_filter
-                .add(token);
+            self.replay_filter.insert(key_id, &token.hmac);
 
             return token.original_destination_connection_id();
         }
@@ -443,13 +635,6 @@ struct Token {
 
 s2n_codec::zerocopy_value_codec!(Token);
 
-impl Hash for Token {
-    /// Token hashes are taken from the hmac
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write(&self.hmac);
-    }
-}
-
 impl Token {
     pub fn original_destination_connection_id(&self) -> Option<connection::InitialId> {
         let dcid = self
@@ -462,6 +647,7 @@ impl Token {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::provider::address_token::Provider as _;
     use s2n_quic_core::{
         inet::SocketAddress,
         random,
@@ -481,6 +667,8 @@ mod tests {
             ],
             current_key_rotates_at: time::now(),
             current_key: 0,
+            key_rotation: Box::new(RandomKeys),
+            replay_filter: Box::<DuplicateFilter>::default(),
         }
     }
 
@@ -693,6 +881,119 @@ mod tests {
         assert!(format.validate_token(&mut context, &buf).is_none());
     }
 
+    /// Derives the keys from a secret shared between endpoints
+    struct SharedKeys;
+
+    impl KeyRotation for SharedKeys {
+        fn rotate_key(&mut self, key_id: u8, key: &mut [u8], _random: &mut dyn random::Generator) {
+            key.fill(key_id + 1);
+        }
+    }
+
+    #[test]
+    fn test_shared_key_rotation() {
+        let clock = Arc::new(time::testing::MockClock::new());
+        time::testing::set_local_clock(clock);
+
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let addr = SocketAddress::default();
+        let mut random = random::testing::Generator(5);
+
+        let shared_format = || {
+            let provider = Provider::builder()
+                .with_key_rotation(SharedKeys)
+                .unwrap()
+                .build()
+                .unwrap();
+            provider.start().unwrap()
+        };
+
+        let mut issuer = shared_format();
+        let mut buf = [0; Format::TOKEN_LEN];
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        issuer
+            .generate_retry_token(&mut context, &odcid, &mut buf)
+            .unwrap();
+
+        // an endpoint with randomly generated keys doesn't accept the token
+        let mut other = get_test_format();
+        assert!(other.validate_token(&mut context, &buf).is_none());
+
+        // an endpoint sharing the keys accepts the token
+        let mut validator = shared_format();
+        assert_eq!(validator.validate_token(&mut context, &buf), Some(odcid));
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedFilter(Arc<std::sync::Mutex<Vec<Vec<u8>>>>);
+
+    impl ReplayFilter for SharedFilter {
+        fn contains(&mut self, _key_id: u8, tag: &[u8]) -> bool {
+            self.0.lock().unwrap().iter().any(|entry| entry == tag)
+        }
+
+        fn insert(&mut self, _key_id: u8, tag: &[u8]) {
+            self.0.lock().unwrap().push(tag.to_vec());
+        }
+
+        fn on_key_rotated(&mut self, _key_id: u8) {}
+    }
+
+    #[test]
+    fn test_shared_replay_filter() {
+        let clock = Arc::new(time::testing::MockClock::new());
+        time::testing::set_local_clock(clock);
+
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let addr = SocketAddress::default();
+        let mut random = random::testing::Generator(5);
+        let filter = SharedFilter::default();
+
+        let shared_format = || {
+            let provider = Provider::builder()
+                .with_key_rotation(SharedKeys)
+                .unwrap()
+                .with_replay_filter(filter.clone())
+                .unwrap()
+                .build()
+                .unwrap();
+            provider.start().unwrap()
+        };
+
+        let mut first = shared_format();
+        let mut second = shared_format();
+        let mut buf = [0; Format::TOKEN_LEN];
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        first
+            .generate_retry_token(&mut context, &odcid, &mut buf)
+            .unwrap();
+
+        assert_eq!(first.validate_token(&mut context, &buf), Some(odcid));
+
+        // the token was recorded in the shared filter so the other endpoint rejects it
+        assert!(second.validate_token(&mut context, &buf).is_none());
+        assert_eq!(filter.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_builder() {
+        assert_eq!(
+            Provider::builder()
+                .with_key_rotation_period(Duration::ZERO)
+                .err(),
+            Some(Error::InvalidKeyRotationPeriod)
+        );
+
+        let provider = Provider::builder()
+            .with_key_rotation_period(Duration::from_secs(5))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(provider.key_rotation_period, Duration::from_secs(5));
+    }
+
     #[test]
     fn test_token_modification_detection() {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
@@ -17,7 +17,7 @@ cfg_if! {
         // Export the types needed to implement the CongestionController trait
         pub use s2n_quic_core::{
             random::Generator as RandomGenerator,
-            recovery::{congestion_controller::{CongestionController, PathInfo, Publisher}, RttEstimator},
+            recovery::{congestion_controller::{CongestionController, Publisher}, RttEstimator},
             time::Timestamp,
         };
     }
@@ -26,6 +26,37 @@ cfg_if! {
 pub use s2n_quic_core::recovery::{bbr::Endpoint as Bbr, cubic::Endpoint as Cubic};
 pub type Default = Cubic;
 
+/// Selects the congestion controller for each new path
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// use s2n_quic::{
+///     provider::congestion_controller::{Algorithm, Selector},
+///     Server,
+/// };
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let selector = Selector::new(|path_info| {
+///     if path_info.remote_address.port() == 443 {
+///         Algorithm::Bbr
+///     } else {
+///         Algorithm::Cubic
+///     }
+/// });
+///
+/// let server = Server::builder()
+///     .with_congestion_controller(selector)?
+///     .start()?;
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub use s2n_quic_core::recovery::congestion_controller::{
+    select::{Algorithm, Selector},
+    PathInfo,
+};
+
 impl_provider_utils!();
 
 impl<T: Endpoint> Provider for T {
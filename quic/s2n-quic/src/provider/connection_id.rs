@@ -31,9 +31,12 @@ pub mod default {
         time::Duration,
     };
     use rand::prelude::*;
-    use s2n_quic_core::connection::{
-        self,
-        id::{ConnectionInfo, Generator, Validator},
+    use s2n_quic_core::{
+        connection::{
+            self,
+            id::{ConnectionInfo, Generator, Validator},
+        },
+        random,
     };
 
     #[derive(Debug, Default)]
@@ -147,6 +150,18 @@ pub mod default {
             (&*id).try_into().expect("length already checked")
         }
 
+        fn generate_with_random(
+            &mut self,
+            _connection_info: &ConnectionInfo,
+            random: &mut dyn random::Generator,
+        ) -> connection::LocalId {
+            let mut id = [0u8; connection::id::MAX_LEN];
+            let id = &mut id[..self.len];
+            // connection IDs are sent in the clear
+            random.public_random_fill(id);
+            (&*id).try_into().expect("length already checked")
+        }
+
         fn lifetime(&self) -> Option<Duration> {
             self.lifetime
         }
@@ -190,6 +205,11 @@ pub mod default {
                 assert_eq!(id.len(), len);
                 assert_eq!(format.lifetime(), None);
                 assert!(format.rotate_handshake_connection_id());
+
+                let mut random = random::testing::Generator::default();
+                let id = format.generate_with_random(&connection_info, &mut random);
+                assert_eq!(format.validate(&connection_info, id.as_ref()), Some(len));
+                assert_eq!(id.len(), len);
             }
 
             assert_eq!(
@@ -4,7 +4,11 @@
 //! Allows applications to limit peer's ability to open new connections
 
 pub use s2n_quic_core::endpoint::{
-    limits::{ConnectionAttempt, Outcome},
+    challenge,
+    limits::{
+        ChallengeResponse, ConnectionAttempt, ConnectionClosed, ConnectionOpened, DatagramAttempt,
+        Outcome,
+    },
     Limiter,
 };
 use s2n_quic_core::{event::Timestamp, path::THROTTLED_PORTS_LEN};
@@ -17,9 +21,12 @@ pub trait Provider: 'static {
     fn start(self) -> Result<Self::Limits, Self::Error>;
 }
 
+pub use blocklist::Blocklist;
 use core::time::Duration;
 pub use default::Limits as Default;
 
+pub mod blocklist;
+
 impl_provider_utils!();
 
 impl<T: 'static + Limiter> Provider for T {
@@ -33,6 +40,7 @@ impl<T: 'static + Limiter> Provider for T {
 
 const THROTTLED_PORT_LIMIT: usize = 10;
 const THROTTLE_FREQUENCY: Duration = Duration::from_secs(1);
+const HANDSHAKE_RATE_WINDOW: Duration = Duration::from_secs(1);
 
 #[derive(Default, Debug, Clone, Copy)]
 struct BasicRateLimiter {
@@ -158,6 +166,17 @@ pub mod default {
 
     use super::*;
     use core::convert::Infallible;
+    use s2n_quic_core::event::api::SocketAddress;
+    use std::{
+        collections::hash_map::{DefaultHasher, RandomState},
+        hash::{BuildHasher, Hash, Hasher},
+        net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    };
+
+    const DEFAULT_IPV4_PREFIX_LEN: u8 = 32;
+    const DEFAULT_IPV6_PREFIX_LEN: u8 = 128;
+    /// The number of counters in each row of the address connection table
+    const ADDRESS_SLOTS: usize = 2048;
 
     /// Allows the endpoint limits to be built with specific values
     ///
@@ -177,9 +196,68 @@ pub mod default {
     ///     Ok(())
     /// # }
     /// ```
-    #[derive(Default)]
+    ///
+    /// Accept at most 50 new connections per second, sending a Retry packet to any additional
+    /// attempts.
+    ///
+    /// ```rust
+    /// use s2n_quic::provider::endpoint_limits;
+    /// # use std::error::Error;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let limits = endpoint_limits::Default::builder()
+    ///     .with_handshake_rate_limit(50)?
+    ///     .build();
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Limit each /24 IPv4 and /64 IPv6 network to 10 connections, dropping any additional
+    /// attempts.
+    ///
+    /// ```rust
+    /// use s2n_quic::provider::endpoint_limits::{self, Outcome};
+    /// # use std::error::Error;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let limits = endpoint_limits::Default::builder()
+    ///     .with_address_connection_limit(10)?
+    ///     .with_address_prefix_len(24, 64)?
+    ///     .with_address_connection_limit_outcome(Outcome::drop())?
+    ///     .build();
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
     pub struct Builder {
         max_inflight_handshake_limit: Option<usize>,
+        inflight_handshake_outcome: Outcome,
+        max_handshake_rate_limit: Option<u32>,
+        handshake_rate_limit_outcome: Outcome,
+        max_connection_limit: Option<usize>,
+        connection_limit_outcome: Outcome,
+        max_address_connection_limit: Option<usize>,
+        address_connection_limit_outcome: Outcome,
+        ipv4_prefix_len: u8,
+        ipv6_prefix_len: u8,
+    }
+
+    impl std::default::Default for Builder {
+        fn default() -> Self {
+            Self {
+                max_inflight_handshake_limit: None,
+                inflight_handshake_outcome: Outcome::retry(),
+                max_handshake_rate_limit: None,
+                handshake_rate_limit_outcome: Outcome::retry(),
+                max_connection_limit: None,
+                connection_limit_outcome: Outcome::drop(),
+                max_address_connection_limit: None,
+                address_connection_limit_outcome: Outcome::drop(),
+                ipv4_prefix_len: DEFAULT_IPV4_PREFIX_LEN,
+                ipv6_prefix_len: DEFAULT_IPV6_PREFIX_LEN,
+            }
+        }
     }
 
     impl Builder {
@@ -189,19 +267,128 @@ pub mod default {
             Ok(self)
         }
 
+        /// Sets the outcome of attempts exceeding the inflight handshake limit
+        ///
+        /// Defaults to `Outcome::retry()`.
+        pub fn with_inflight_handshake_outcome(
+            mut self,
+            outcome: Outcome,
+        ) -> Result<Self, Infallible> {
+            self.inflight_handshake_outcome = outcome;
+            Ok(self)
+        }
+
+        /// Sets limit on the number of connection attempts allowed to start a handshake each
+        /// second
+        pub fn with_handshake_rate_limit(mut self, limit: u32) -> Result<Self, Infallible> {
+            self.max_handshake_rate_limit = Some(limit);
+            Ok(self)
+        }
+
+        /// Sets the outcome of attempts exceeding the handshake rate limit
+        ///
+        /// Defaults to `Outcome::retry()`.
+        pub fn with_handshake_rate_limit_outcome(
+            mut self,
+            outcome: Outcome,
+        ) -> Result<Self, Infallible> {
+            self.handshake_rate_limit_outcome = outcome;
+            Ok(self)
+        }
+
+        /// Sets limit on established connections
+        ///
+        /// Connections which are still performing the handshake are not counted against this
+        /// limit.
+        pub fn with_connection_limit(mut self, limit: usize) -> Result<Self, Infallible> {
+            self.max_connection_limit = Some(limit);
+            Ok(self)
+        }
+
+        /// Sets the outcome of attempts exceeding the established connection limit
+        ///
+        /// Defaults to `Outcome::drop()`.
+        pub fn with_connection_limit_outcome(
+            mut self,
+            outcome: Outcome,
+        ) -> Result<Self, Infallible> {
+            self.connection_limit_outcome = outcome;
+            Ok(self)
+        }
+
+        /// Sets limit on open connections, including handshakes, from a single address prefix
+        ///
+        /// Peers are grouped with [`Self::with_address_prefix_len`], which defaults to
+        /// individual addresses.
+        pub fn with_address_connection_limit(mut self, limit: usize) -> Result<Self, Infallible> {
+            self.max_address_connection_limit = Some(limit);
+            Ok(self)
+        }
+
+        /// Sets the outcome of attempts exceeding the address connection limit
+        ///
+        /// Defaults to `Outcome::drop()`.
+        pub fn with_address_connection_limit_outcome(
+            mut self,
+            outcome: Outcome,
+        ) -> Result<Self, Infallible> {
+            self.address_connection_limit_outcome = outcome;
+            Ok(self)
+        }
+
+        /// Sets the prefix lengths used to group peer addresses for the address connection limit
+        ///
+        /// IPv4-mapped IPv6 addresses are grouped as IPv4 addresses. Lengths larger than the
+        /// address are clamped. Defaults to `32` and `128`.
+        pub fn with_address_prefix_len(mut self, ipv4: u8, ipv6: u8) -> Result<Self, Infallible> {
+            self.ipv4_prefix_len = ipv4.min(DEFAULT_IPV4_PREFIX_LEN);
+            self.ipv6_prefix_len = ipv6.min(DEFAULT_IPV6_PREFIX_LEN);
+            Ok(self)
+        }
+
         /// Build the limits
         pub fn build(self) -> Result<Limits, Infallible> {
             Ok(Limits {
                 max_inflight_handshake_limit: self.max_inflight_handshake_limit,
+                inflight_handshake_outcome: self.inflight_handshake_outcome,
+                max_handshake_rate_limit: self.max_handshake_rate_limit,
+                handshake_rate_limit_outcome: self.handshake_rate_limit_outcome,
+                handshake_rate: HandshakeRate::default(),
+                max_connection_limit: self.max_connection_limit,
+                connection_limit_outcome: self.connection_limit_outcome,
+                max_address_connection_limit: self.max_address_connection_limit,
+                address_connection_limit_outcome: self.address_connection_limit_outcome,
+                ipv4_prefix_len: self.ipv4_prefix_len,
+                ipv6_prefix_len: self.ipv6_prefix_len,
+                address_connections: self
+                    .max_address_connection_limit
+                    .map(|_| Box::new(AddressConnections::new())),
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             })
         }
     }
 
-    #[derive(Clone, Copy, Debug)]
+    #[derive(Clone, Debug)]
     pub struct Limits {
         /// Maximum number of handshakes to allow before Retry packets are queued
         max_inflight_handshake_limit: Option<usize>,
+        inflight_handshake_outcome: Outcome,
+        /// Maximum number of handshakes to start each second
+        max_handshake_rate_limit: Option<u32>,
+        handshake_rate_limit_outcome: Outcome,
+        handshake_rate: HandshakeRate,
+        /// Maximum number of established connections
+        max_connection_limit: Option<usize>,
+        connection_limit_outcome: Outcome,
+        /// Maximum number of open connections from a single address prefix
+        max_address_connection_limit: Option<usize>,
+        address_connection_limit_outcome: Outcome,
+        ipv4_prefix_len: u8,
+        ipv6_prefix_len: u8,
+        /// The number of open connections for each address prefix
+        ///
+        /// The table is only allocated when the address connection limit is set.
+        address_connections: Option<Box<AddressConnections>>,
         rate_limiter: [BasicRateLimiter; THROTTLED_PORTS_LEN],
     }
 
@@ -209,6 +396,33 @@ pub mod default {
         pub fn builder() -> Builder {
             Builder::default()
         }
+
+        /// Returns the prefix of the address used to count connections
+        fn address_prefix(&self, address: &SocketAddress) -> IpAddr {
+            match address {
+                SocketAddress::IpV4 { ip, .. } => self.ipv4_prefix(Ipv4Addr::from(**ip)),
+                SocketAddress::IpV6 { ip, .. } => {
+                    let ip = Ipv6Addr::from(**ip);
+                    if let Some(ip) = ip.to_ipv4_mapped() {
+                        self.ipv4_prefix(ip)
+                    } else {
+                        let mask = u128::MAX
+                            .checked_shl(128 - self.ipv6_prefix_len as u32)
+                            .unwrap_or(0);
+                        Ipv6Addr::from(u128::from(ip) & mask).into()
+                    }
+                }
+                // the address type is non-exhaustive but all current variants are handled above
+                _ => Ipv4Addr::UNSPECIFIED.into(),
+            }
+        }
+
+        fn ipv4_prefix(&self, ip: Ipv4Addr) -> IpAddr {
+            let mask = u32::MAX
+                .checked_shl(32 - self.ipv4_prefix_len as u32)
+                .unwrap_or(0);
+            Ipv4Addr::from(u32::from(ip) & mask).into()
+        }
     }
 
     /// Default implementation for the Limits
@@ -219,31 +433,199 @@ pub mod default {
                 return Outcome::drop();
             }
 
-            if let Some(port_index) = s2n_quic_core::path::remote_port_throttled_index(remote_port)
-            {
-                let rate_limiter = &mut self.rate_limiter[port_index];
-                if rate_limiter.should_throttle(THROTTLED_PORT_LIMIT, THROTTLE_FREQUENCY, info) {
-                    return Outcome::drop();
+            // Attempts with a validated address were already counted when they were sent the
+            // Retry packet, so only unvalidated attempts are counted against the port throttle.
+            if !info.is_address_validated {
+                if let Some(port_index) =
+                    s2n_quic_core::path::remote_port_throttled_index(remote_port)
+                {
+                    let rate_limiter = &mut self.rate_limiter[port_index];
+                    if rate_limiter.should_throttle(THROTTLED_PORT_LIMIT, THROTTLE_FREQUENCY, info)
+                    {
+                        return Outcome::drop();
+                    }
+                }
+            }
+
+            if let (Some(limit), Some(address_connections)) = (
+                self.max_address_connection_limit,
+                self.address_connections.as_deref(),
+            ) {
+                let prefix = self.address_prefix(&info.remote_address);
+                let count = address_connections.get(&prefix);
+                if count >= limit {
+                    if let Some(outcome) = exceeded(&self.address_connection_limit_outcome, info) {
+                        return outcome;
+                    }
+                }
+            }
+
+            if let Some(limit) = self.max_connection_limit {
+                let established = info
+                    .connection_count
+                    .saturating_sub(info.inflight_handshakes);
+                if established >= limit {
+                    if let Some(outcome) = exceeded(&self.connection_limit_outcome, info) {
+                        return outcome;
+                    }
                 }
             }
 
             if let Some(limit) = self.max_inflight_handshake_limit {
                 if info.inflight_handshakes >= limit {
-                    return Outcome::retry();
+                    if let Some(outcome) = exceeded(&self.inflight_handshake_outcome, info) {
+                        return outcome;
+                    }
+                }
+            }
+
+            // The handshake is only counted by the attempt which is allowed to start it, so a
+            // connection which was sent a Retry packet is counted once, when it comes back with
+            // the token.
+            if let Some(limit) = self.max_handshake_rate_limit {
+                if !self.handshake_rate.try_acquire(limit, info.timestamp) {
+                    if let Some(outcome) = exceeded(&self.handshake_rate_limit_outcome, info) {
+                        return outcome;
+                    }
                 }
             }
 
             Outcome::allow()
         }
+
+        #[inline]
+        fn on_connection_opened(&mut self, info: &ConnectionOpened) {
+            let prefix = self.address_prefix(&info.remote_address);
+            let Some(address_connections) = self.address_connections.as_mut() else {
+                return;
+            };
+
+            address_connections.increment(&prefix);
+        }
+
+        #[inline]
+        fn on_connection_closed(&mut self, info: &ConnectionClosed) {
+            let prefix = self.address_prefix(&info.remote_address);
+            let Some(address_connections) = self.address_connections.as_mut() else {
+                return;
+            };
+
+            address_connections.decrement(&prefix);
+        }
+    }
+
+    /// Returns the outcome for an attempt which exceeded a limit
+    ///
+    /// The endpoint allows validated attempts to continue when the outcome is a Retry, so `None`
+    /// is returned for those and the remaining limits are checked instead. This keeps the
+    /// attempt from skipping the handshake rate limit, which is where it gets counted.
+    #[inline]
+    fn exceeded(outcome: &Outcome, info: &ConnectionAttempt) -> Option<Outcome> {
+        if info.is_address_validated && matches!(outcome, Outcome::Retry { .. }) {
+            return None;
+        }
+
+        Some(*outcome)
+    }
+
+    /// Counts the open connections for each address prefix in a fixed amount of memory
+    ///
+    /// Each prefix is hashed to one counter in each row and its count is the smaller of the two.
+    /// Prefixes which share both counters also share a count, so an attempt may be limited
+    /// before its prefix reaches the limit, but a prefix is never allowed more connections than
+    /// the limit. The hashes are keyed randomly so peers can't pick prefixes which collide with
+    /// another peer's.
+    #[derive(Clone)]
+    struct AddressConnections {
+        keys: [u64; 2],
+        counts: [[u32; ADDRESS_SLOTS]; 2],
+    }
+
+    impl AddressConnections {
+        fn new() -> Self {
+            let state = RandomState::new();
+            Self {
+                keys: [state.hash_one(0u8), state.hash_one(1u8)],
+                counts: [[0; ADDRESS_SLOTS]; 2],
+            }
+        }
+
+        #[inline]
+        fn slot(&self, row: usize, prefix: &IpAddr) -> usize {
+            let mut hasher = DefaultHasher::new();
+            self.keys[row].hash(&mut hasher);
+            prefix.hash(&mut hasher);
+            hasher.finish() as usize % ADDRESS_SLOTS
+        }
+
+        fn get(&self, prefix: &IpAddr) -> usize {
+            (0..2)
+                .map(|row| self.counts[row][self.slot(row, prefix)])
+                .min()
+                .unwrap_or(0) as usize
+        }
+
+        fn increment(&mut self, prefix: &IpAddr) {
+            for row in 0..2 {
+                let slot = self.slot(row, prefix);
+                let count = &mut self.counts[row][slot];
+                *count = count.saturating_add(1);
+            }
+        }
+
+        fn decrement(&mut self, prefix: &IpAddr) {
+            for row in 0..2 {
+                let slot = self.slot(row, prefix);
+                let count = &mut self.counts[row][slot];
+                *count = count.saturating_sub(1);
+            }
+        }
+
+        #[cfg(test)]
+        fn is_empty(&self) -> bool {
+            self.counts.iter().flatten().all(|count| *count == 0)
+        }
+    }
+
+    impl core::fmt::Debug for AddressConnections {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("AddressConnections").finish_non_exhaustive()
+        }
+    }
+
+    /// Counts the handshakes started in fixed one second windows
+    #[derive(Clone, Copy, Debug, Default)]
+    struct HandshakeRate {
+        window_start: Option<Timestamp>,
+        count: u32,
+    }
+
+    impl HandshakeRate {
+        /// Returns `true` and counts the handshake if fewer than `limit` have started in the
+        /// current window
+        fn try_acquire(&mut self, limit: u32, timestamp: Timestamp) -> bool {
+            match self.window_start {
+                Some(start)
+                    if timestamp.saturating_duration_since(start) < HANDSHAKE_RATE_WINDOW => {}
+                _ => {
+                    self.window_start = Some(timestamp);
+                    self.count = 0;
+                }
+            }
+
+            if self.count >= limit {
+                return false;
+            }
+
+            self.count += 1;
+            true
+        }
     }
 
     /// Default limit values are as non-intrusive as possible
     impl std::default::Default for Limits {
         fn default() -> Self {
-            Self {
-                max_inflight_handshake_limit: None,
-                rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
-            }
+            Builder::default().build().unwrap()
         }
     }
 
@@ -255,6 +637,188 @@ pub mod default {
             .build()
             .unwrap();
         assert_eq!(elp.max_inflight_handshake_limit, Some(100));
+        assert_eq!(elp.inflight_handshake_outcome, Outcome::retry());
+        assert_eq!(elp.max_connection_limit, None);
+        assert_eq!(elp.max_address_connection_limit, None);
+        // the address connection table is only allocated when the limit is set
+        assert!(elp.address_connections.is_none());
+    }
+
+    #[test]
+    fn connection_limit_test() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            inet::SocketAddress,
+            time::{testing::Clock as MockClock, Clock},
+        };
+
+        let remote_address = SocketAddress::default();
+        let timestamp = MockClock::default().get_time().into_event();
+        let mut limits = Limits::builder()
+            .with_inflight_handshake_limit(2)
+            .unwrap()
+            .with_inflight_handshake_outcome(Outcome::drop())
+            .unwrap()
+            .with_connection_limit(3)
+            .unwrap()
+            .with_connection_limit_outcome(Outcome::close())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut attempt = |inflight_handshakes, connection_count| {
+            let info = ConnectionAttempt::new(
+                inflight_handshakes,
+                connection_count,
+                &remote_address,
+                timestamp,
+            );
+            limits.on_connection_attempt(&info)
+        };
+
+        assert_eq!(attempt(1, 3), Outcome::allow());
+        assert_eq!(attempt(2, 3), Outcome::drop());
+        // handshaking connections don't count as established
+        assert_eq!(attempt(1, 4), Outcome::close());
+    }
+
+    #[test]
+    fn handshake_rate_limit_test() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            inet::SocketAddress,
+            time::{testing::Clock as MockClock, Clock},
+        };
+
+        let remote_address = SocketAddress::default();
+        let mut clock = MockClock::default();
+        let mut limits = Limits::builder()
+            .with_handshake_rate_limit(2)
+            .unwrap()
+            .with_handshake_rate_limit_outcome(Outcome::close().with_reason("handshake rate"))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut attempt = |clock: &MockClock| {
+            let info = ConnectionAttempt::new(0, 0, &remote_address, clock.get_time().into_event());
+            limits.on_connection_attempt(&info)
+        };
+
+        assert_eq!(attempt(&clock), Outcome::allow());
+        assert_eq!(attempt(&clock), Outcome::allow());
+        let outcome = attempt(&clock);
+        assert_eq!(outcome, Outcome::close().with_reason("handshake rate"));
+        assert_eq!(outcome.reason(), Some("handshake rate"));
+
+        // the rate resets once the window has passed
+        clock.inc_by(Duration::from_millis(500));
+        assert!(matches!(attempt(&clock), Outcome::Close { .. }));
+        clock.inc_by(Duration::from_millis(500));
+        assert_eq!(attempt(&clock), Outcome::allow());
+    }
+
+    #[test]
+    fn validated_attempt_accounting_test() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            inet::SocketAddress,
+            time::{testing::Clock as MockClock, Clock},
+        };
+
+        let timestamp = MockClock::default().get_time().into_event();
+        let mut limits = Limits::builder()
+            .with_inflight_handshake_limit(0)
+            .unwrap()
+            .with_handshake_rate_limit(THROTTLED_PORT_LIMIT as u32 + 1)
+            .unwrap()
+            .with_handshake_rate_limit_outcome(Outcome::close())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // use a throttled port so the port counter is exercised as well
+        let port = (0..u16::MAX)
+            .find(|port| s2n_quic_core::path::remote_port_throttled_index(*port).is_some())
+            .unwrap();
+        let mut remote_address = SocketAddress::default();
+        remote_address.set_port(port);
+
+        let mut attempt = |is_address_validated| {
+            let info = ConnectionAttempt::new(0, 0, &remote_address, timestamp)
+                .with_address_validated(is_address_validated);
+            limits.on_connection_attempt(&info)
+        };
+
+        // each connection is sent a Retry packet and comes back with a validated address, which
+        // should only be charged once against both the port throttle and the handshake rate
+        for _ in 0..THROTTLED_PORT_LIMIT {
+            assert_eq!(attempt(false), Outcome::retry());
+            assert_eq!(attempt(true), Outcome::allow());
+        }
+
+        assert_eq!(limits.handshake_rate.count, THROTTLED_PORT_LIMIT as u32);
+
+        // the port throttle is now exhausted for new attempts
+        assert_eq!(attempt(false), Outcome::drop());
+
+        // the last validated attempt fits in the handshake rate before it's exhausted as well
+        assert_eq!(attempt(true), Outcome::allow());
+        assert_eq!(attempt(true), Outcome::close());
+    }
+
+    #[test]
+    fn address_connection_limit_test() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            inet::SocketAddress,
+            time::{testing::Clock as MockClock, Clock},
+        };
+        use std::net::SocketAddr;
+
+        let timestamp = MockClock::default().get_time().into_event();
+        let mut limits = Limits::builder()
+            .with_address_connection_limit(2)
+            .unwrap()
+            .with_address_prefix_len(24, 64)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let address = |addr: &str| SocketAddress::from(addr.parse::<SocketAddr>().unwrap());
+        let a = address("192.0.2.1:443");
+        let b = address("[::ffff:192.0.2.2]:443");
+        let c = address("192.0.3.1:443");
+        let d = address("[2001:db8::1]:443");
+        let e = address("[2001:db8::2]:443");
+
+        let attempt = |limits: &mut Limits, address: &SocketAddress| {
+            let info = ConnectionAttempt::new(0, 0, address, timestamp);
+            let outcome = limits.on_connection_attempt(&info);
+            if outcome == Outcome::allow() {
+                limits.on_connection_opened(&ConnectionOpened::new(address, timestamp));
+            }
+            outcome
+        };
+
+        // mapped addresses are counted in the same IPv4 prefix
+        assert_eq!(attempt(&mut limits, &a), Outcome::allow());
+        assert_eq!(attempt(&mut limits, &b), Outcome::allow());
+        assert_eq!(attempt(&mut limits, &a), Outcome::drop());
+        assert_eq!(attempt(&mut limits, &c), Outcome::allow());
+
+        assert_eq!(attempt(&mut limits, &d), Outcome::allow());
+        assert_eq!(attempt(&mut limits, &e), Outcome::allow());
+        assert_eq!(attempt(&mut limits, &d), Outcome::drop());
+
+        // closing a connection frees up a slot for the prefix
+        limits.on_connection_closed(&ConnectionClosed::new(&b, timestamp));
+        assert_eq!(attempt(&mut limits, &a), Outcome::allow());
+
+        for address in [&a, &b, &c, &d, &e] {
+            limits.on_connection_closed(&ConnectionClosed::new(address, timestamp));
+        }
+        assert!(limits.address_connections.unwrap().is_empty());
     }
 
     #[test]
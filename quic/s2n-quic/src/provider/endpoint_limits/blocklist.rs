@@ -0,0 +1,780 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A blocklist of peer addresses with automatic expiry
+
+use super::{
+    ChallengeResponse, ConnectionAttempt, ConnectionClosed, ConnectionOpened, DatagramAttempt,
+    Limiter, Outcome,
+};
+use crate::provider::event::{self, events, ConnectionInfo, ConnectionMeta};
+use arc_swap::ArcSwap;
+use core::{convert::Infallible, time::Duration};
+use s2n_quic_core::{
+    connection, endpoint,
+    inet::{IpAddress, IpV4Address, IpV6Address, SocketAddress},
+    transport,
+};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+const DEFAULT_MAX_ENTRIES: usize = 4096;
+const DEFAULT_VIOLATION_WINDOW: Duration = Duration::from_secs(60);
+const DEFAULT_VIOLATION_TTL: Duration = Duration::from_secs(300);
+
+/// Marks the current time as unknown, before the endpoint has received any datagrams
+const UNKNOWN_TIME: u64 = u64::MAX;
+
+/// Drops datagrams from blocked peer addresses before they are parsed
+///
+/// Entries can be inserted by the application with [`Blocklist::insert`] or automatically when a
+/// peer repeatedly commits protocol violations. In order to observe protocol violations, the
+/// blocklist must also be registered as an event subscriber.
+///
+/// Datagrams from blocked addresses are dropped before any parsing or cryptographic operations
+/// are performed. Each dropped datagram emits an `EndpointDatagramDropped` event with the
+/// `Blocked` reason and is counted in [`Blocklist::dropped_datagrams`]. The check doesn't take
+/// any locks: it reads a snapshot of the blocked addresses.
+///
+/// Inserting or removing an entry only marks the snapshot as stale. The endpoint rebuilds it
+/// before checking the next datagram, which copies all of the blocked addresses. Any number of
+/// updates between two datagrams therefore share a single copy.
+///
+/// The blocklist can be used as the endpoint limits by itself, or in front of other limits with
+/// [`Blocklist::with_limits`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use s2n_quic::{provider::endpoint_limits::{self, Blocklist}, Server};
+/// use std::{error::Error, time::Duration};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let blocklist = Blocklist::builder()
+///     .with_violation_limit(3)?
+///     .build()?;
+///
+/// blocklist.insert("192.0.2.1".parse()?, Duration::from_secs(60));
+///
+/// let limits = endpoint_limits::Default::builder()
+///     .with_inflight_handshake_limit(100)?
+///     .build()?;
+///
+/// let mut server = Server::builder()
+///     .with_endpoint_limits(blocklist.clone().with_limits(limits))?
+///     .with_event(blocklist)?
+///     .start()?;
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Blocklist {
+    /// The blocked addresses, read for every datagram
+    snapshot: Arc<ArcSwap<Snapshot>>,
+    /// The state used to update the snapshot
+    state: Arc<Mutex<State>>,
+    /// Set when the state has changed since the snapshot was last published
+    is_stale: Arc<AtomicBool>,
+    /// The last time observed by the endpoint, in nanoseconds since the start of the process
+    now: Arc<AtomicU64>,
+    dropped_datagrams: Arc<AtomicU64>,
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        Builder::default().build().unwrap()
+    }
+}
+
+impl Blocklist {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Blocks the given address for the duration of `ttl`
+    ///
+    /// Returns `false` if the blocklist is at capacity and the address could not be inserted.
+    pub fn insert(&self, address: IpAddr, ttl: Duration) -> bool {
+        self.update(|state, now| state.insert(ip_address_from_std(address), ttl, now))
+    }
+
+    /// Removes the given address from the blocklist
+    ///
+    /// Returns `true` if the address was previously blocked.
+    pub fn remove(&self, address: IpAddr) -> bool {
+        self.update(|state, _now| state.remove(&ip_address_from_std(address)))
+    }
+
+    /// Returns `true` if the given address is currently blocked
+    pub fn contains(&self, address: IpAddr) -> bool {
+        if let Ok(state) = self.state.lock() {
+            state.contains(&ip_address_from_std(address), self.now())
+        } else {
+            false
+        }
+    }
+
+    /// Returns the number of blocked addresses
+    pub fn len(&self) -> usize {
+        let now = self.now();
+        self.state.lock().map_or(0, |state| {
+            let entries = state
+                .entries
+                .values()
+                .filter(|expiration| now.map_or(true, |now| **expiration > now))
+                .count();
+            entries + state.pending.len()
+        })
+    }
+
+    /// Returns `true` if there are no blocked addresses
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total number of datagrams dropped by the blocklist
+    pub fn dropped_datagrams(&self) -> u64 {
+        self.dropped_datagrams.load(Ordering::Relaxed)
+    }
+
+    /// Returns a limiter which drops datagrams from blocked addresses and passes everything else
+    /// to `limits`
+    pub fn with_limits<L: Limiter>(self, limits: L) -> Limits<L> {
+        Limits {
+            blocklist: self,
+            limits,
+        }
+    }
+
+    /// Returns the last time observed by the endpoint, if any
+    fn now(&self) -> Option<Duration> {
+        match self.now.load(Ordering::Relaxed) {
+            UNKNOWN_TIME => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+
+    /// Calls `f` with the locked state and marks the snapshot as stale if it returns `true`
+    fn update<F: FnOnce(&mut State, Option<Duration>) -> bool>(&self, f: F) -> bool {
+        let mut state = if let Ok(state) = self.state.lock() {
+            state
+        } else {
+            return false;
+        };
+
+        let changed = f(&mut state, self.now());
+
+        if changed {
+            // the flag is set while holding the lock so `publish` can't miss the change
+            self.is_stale.store(true, Ordering::Release);
+        }
+
+        changed
+    }
+
+    /// Replaces the snapshot with the current state
+    ///
+    /// Any entries inserted before the endpoint observed the current time expire relative to
+    /// `now`.
+    fn publish(&self, now: Duration) {
+        let mut state = if let Ok(state) = self.state.lock() {
+            state
+        } else {
+            return;
+        };
+
+        state.resolve_pending(now);
+        self.is_stale.store(false, Ordering::Release);
+        self.snapshot.store(Arc::new(state.snapshot()));
+    }
+}
+
+/// Allows the blocklist to be built with specific values
+#[derive(Debug)]
+pub struct Builder {
+    max_entries: usize,
+    violation_limit: Option<u32>,
+    violation_window: Duration,
+    violation_ttl: Duration,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_MAX_ENTRIES,
+            violation_limit: None,
+            violation_window: DEFAULT_VIOLATION_WINDOW,
+            violation_ttl: DEFAULT_VIOLATION_TTL,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the maximum number of addresses that can be tracked (default: 4096)
+    ///
+    /// This bounds the memory used by both the blocked addresses and the protocol violation
+    /// counters.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Result<Self, Infallible> {
+        self.max_entries = max_entries;
+        Ok(self)
+    }
+
+    /// Sets the number of protocol violations after which a peer is automatically blocked
+    /// (default: disabled)
+    ///
+    /// A protocol violation is counted each time a connection with the peer is closed by the
+    /// local endpoint due to the peer misbehaving.
+    pub fn with_violation_limit(mut self, limit: u32) -> Result<Self, Infallible> {
+        self.violation_limit = Some(limit);
+        Ok(self)
+    }
+
+    /// Sets the window in which protocol violations are counted (default: 60 seconds)
+    pub fn with_violation_window(mut self, window: Duration) -> Result<Self, Infallible> {
+        self.violation_window = window;
+        Ok(self)
+    }
+
+    /// Sets the amount of time a peer is blocked for after exceeding the violation limit
+    /// (default: 300 seconds)
+    pub fn with_violation_ttl(mut self, ttl: Duration) -> Result<Self, Infallible> {
+        self.violation_ttl = ttl;
+        Ok(self)
+    }
+
+    /// Build the blocklist
+    pub fn build(self) -> Result<Blocklist, Infallible> {
+        let state = State {
+            max_entries: self.max_entries,
+            violation_limit: self.violation_limit,
+            violation_window: self.violation_window,
+            violation_ttl: self.violation_ttl,
+            entries: HashMap::new(),
+            pending: Vec::new(),
+            violations: HashMap::new(),
+        };
+
+        Ok(Blocklist {
+            snapshot: Arc::new(ArcSwap::from_pointee(Snapshot::default())),
+            state: Arc::new(Mutex::new(state)),
+            is_stale: Arc::new(AtomicBool::new(false)),
+            now: Arc::new(AtomicU64::new(UNKNOWN_TIME)),
+            dropped_datagrams: Arc::new(AtomicU64::new(0)),
+        })
+    }
+}
+
+/// An immutable copy of the blocked addresses
+#[derive(Debug, Default)]
+struct Snapshot {
+    /// Maps blocked addresses to their expiration time
+    entries: HashMap<IpAddress, Duration>,
+}
+
+impl Snapshot {
+    #[inline]
+    fn is_blocked(&self, address: &IpAddress, now: Duration) -> bool {
+        self.entries
+            .get(address)
+            .map_or(false, |expiration| *expiration > now)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    max_entries: usize,
+    violation_limit: Option<u32>,
+    violation_window: Duration,
+    violation_ttl: Duration,
+    /// Maps blocked addresses to their expiration time
+    entries: HashMap<IpAddress, Duration>,
+    /// Entries inserted before the endpoint observed the current time
+    pending: Vec<(IpAddress, Duration)>,
+    violations: HashMap<IpAddress, Violations>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Violations {
+    count: u32,
+    window_start: Duration,
+}
+
+impl State {
+    fn insert(&mut self, address: IpAddress, ttl: Duration, now: Option<Duration>) -> bool {
+        let now = if let Some(now) = now {
+            now
+        } else {
+            // we don't know what time it is yet so wait until the endpoint tells us
+            if self.pending.len() + self.entries.len() >= self.max_entries {
+                return false;
+            }
+            self.pending.push((address, ttl));
+            return true;
+        };
+
+        if !self.entries.contains_key(&address) && self.entries.len() >= self.max_entries {
+            // try to make room by removing the expired entries
+            self.entries.retain(|_, expiration| *expiration > now);
+
+            if self.entries.len() >= self.max_entries {
+                return false;
+            }
+        }
+
+        self.entries.insert(address, now.saturating_add(ttl));
+        true
+    }
+
+    fn remove(&mut self, address: &IpAddress) -> bool {
+        let len = self.pending.len();
+        self.pending.retain(|(pending, _)| pending != address);
+        let removed_pending = len != self.pending.len();
+
+        self.entries.remove(address).is_some() || removed_pending
+    }
+
+    fn contains(&self, address: &IpAddress, now: Option<Duration>) -> bool {
+        if self.pending.iter().any(|(pending, _)| pending == address) {
+            return true;
+        }
+
+        match (self.entries.get(address), now) {
+            (Some(expiration), Some(now)) => *expiration > now,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Inserts the pending entries now that the current time is known
+    fn resolve_pending(&mut self, now: Duration) -> bool {
+        let pending = core::mem::take(&mut self.pending);
+        let changed = !pending.is_empty();
+
+        for (address, ttl) in pending {
+            self.insert(address, ttl, Some(now));
+        }
+
+        changed
+    }
+
+    /// Returns `true` if the address was blocked
+    fn on_violation(&mut self, address: IpAddress, now: Duration) -> bool {
+        let limit = if let Some(limit) = self.violation_limit {
+            limit
+        } else {
+            return false;
+        };
+
+        let mut changed = self.resolve_pending(now);
+
+        if !self.violations.contains_key(&address) && self.violations.len() >= self.max_entries {
+            let window = self.violation_window;
+            self.violations
+                .retain(|_, violations| now.saturating_sub(violations.window_start) < window);
+
+            if self.violations.len() >= self.max_entries {
+                return changed;
+            }
+        }
+
+        let violations = self.violations.entry(address).or_insert(Violations {
+            count: 0,
+            window_start: now,
+        });
+
+        // start a new window if the previous one elapsed
+        if now.saturating_sub(violations.window_start) >= self.violation_window {
+            *violations = Violations {
+                count: 0,
+                window_start: now,
+            };
+        }
+
+        violations.count += 1;
+
+        if violations.count >= limit {
+            self.violations.remove(&address);
+            let ttl = self.violation_ttl;
+            changed |= self.insert(address, ttl, Some(now));
+        }
+
+        changed
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl Limiter for Blocklist {
+    #[inline]
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        Outcome::allow()
+    }
+
+    #[inline]
+    fn on_datagram(&mut self, info: &DatagramAttempt) -> bool {
+        let now = info.timestamp.duration_since_start();
+        self.now.store(now.as_nanos() as u64, Ordering::Relaxed);
+
+        if self.is_stale.load(Ordering::Acquire) {
+            self.publish(now);
+        }
+
+        let snapshot = self.snapshot.load();
+
+        if snapshot.entries.is_empty() {
+            return true;
+        }
+
+        let address = ip_address(&info.remote_address);
+        let is_blocked = snapshot.is_blocked(&address, now);
+
+        if is_blocked {
+            self.dropped_datagrams.fetch_add(1, Ordering::Relaxed);
+        }
+
+        !is_blocked
+    }
+}
+
+/// Drops datagrams from the addresses in a [`Blocklist`] and passes everything else to another
+/// limiter
+///
+/// This is created with [`Blocklist::with_limits`].
+#[derive(Clone, Debug)]
+pub struct Limits<L> {
+    blocklist: Blocklist,
+    limits: L,
+}
+
+impl<L: Limiter> Limiter for Limits<L> {
+    #[inline]
+    fn on_connection_attempt(&mut self, info: &ConnectionAttempt) -> Outcome {
+        self.limits.on_connection_attempt(info)
+    }
+
+    #[inline]
+    fn on_datagram(&mut self, info: &DatagramAttempt) -> bool {
+        self.blocklist.on_datagram(info) && self.limits.on_datagram(info)
+    }
+
+    #[inline]
+    fn on_connection_opened(&mut self, info: &ConnectionOpened) {
+        self.limits.on_connection_opened(info)
+    }
+
+    #[inline]
+    fn on_connection_closed(&mut self, info: &ConnectionClosed) {
+        self.limits.on_connection_closed(info)
+    }
+
+    #[inline]
+    fn generate_challenge(&mut self, info: &ConnectionAttempt, output: &mut [u8]) -> Option<usize> {
+        self.limits.generate_challenge(info, output)
+    }
+
+    #[inline]
+    fn verify_challenge(&mut self, info: &ChallengeResponse) -> bool {
+        self.limits.verify_challenge(info)
+    }
+}
+
+impl event::Subscriber for Blocklist {
+    /// The address of the peer, once known
+    type ConnectionContext = Option<IpAddress>;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        None
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::ConnectionStarted,
+    ) {
+        *context = Some(ip_address(&event.path.remote_addr));
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ConnectionClosed,
+    ) {
+        let address = if let Some(address) = context {
+            *address
+        } else {
+            return;
+        };
+
+        if !is_violation(&event.error) {
+            return;
+        }
+
+        let now = meta.timestamp.duration_since_start();
+        self.update(|state, _now| state.on_violation(address, now));
+    }
+}
+
+/// Returns `true` if the connection was closed because of the peer misbehaving
+fn is_violation(error: &connection::Error) -> bool {
+    let code = if let connection::Error::Transport {
+        code,
+        initiator: endpoint::Location::Local,
+        ..
+    } = error
+    {
+        *code
+    } else {
+        return false;
+    };
+
+    use transport::error::Code;
+
+    [
+        Code::FLOW_CONTROL_ERROR,
+        Code::STREAM_LIMIT_ERROR,
+        Code::STREAM_STATE_ERROR,
+        Code::FINAL_SIZE_ERROR,
+        Code::FRAME_ENCODING_ERROR,
+        Code::TRANSPORT_PARAMETER_ERROR,
+        Code::CONNECTION_ID_LIMIT_ERROR,
+        Code::PROTOCOL_VIOLATION,
+        Code::CRYPTO_BUFFER_EXCEEDED,
+        Code::KEY_UPDATE_ERROR,
+    ]
+    .contains(&code)
+}
+
+/// Normalizes the address so IPv4-mapped peers share an entry with their IPv4 form
+fn ip_address(address: &events::SocketAddress) -> IpAddress {
+    let address: IpAddress = match address {
+        events::SocketAddress::IpV4 { ip, .. } => IpV4Address::new(**ip).into(),
+        events::SocketAddress::IpV6 { ip, .. } => IpV6Address::new(**ip).into(),
+        // the address type is non-exhaustive but all current variants are handled above
+        _ => IpV4Address::UNSPECIFIED.into(),
+    };
+    address.unmap()
+}
+
+fn ip_address_from_std(address: IpAddr) -> IpAddress {
+    SocketAddress::from((address, 0)).ip().unmap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        event::IntoEvent,
+        time::{testing::Clock as MockClock, Clock},
+    };
+
+    fn attempt(blocklist: &mut Blocklist, address: IpAddr, clock: &MockClock) -> bool {
+        let remote_address = SocketAddress::from((address, 443));
+        let info = DatagramAttempt::new(&remote_address, 1200, clock.get_time().into_event());
+        blocklist.on_datagram(&info)
+    }
+
+    #[test]
+    fn insert_expiry_test() {
+        let mut clock = MockClock::default();
+        let mut blocklist = Blocklist::default();
+        let blocked: IpAddr = "192.0.2.1".parse().unwrap();
+        let allowed: IpAddr = "192.0.2.2".parse().unwrap();
+
+        assert!(blocklist.insert(blocked, Duration::from_secs(10)));
+        assert!(blocklist.contains(blocked));
+
+        assert!(!attempt(&mut blocklist, blocked, &clock));
+        assert!(attempt(&mut blocklist, allowed, &clock));
+        assert_eq!(blocklist.dropped_datagrams(), 1);
+
+        // IPv4-mapped addresses should match the IPv4 entry
+        let mapped: IpAddr = "::ffff:192.0.2.1".parse().unwrap();
+        assert!(!attempt(&mut blocklist, mapped, &clock));
+        assert_eq!(blocklist.dropped_datagrams(), 2);
+
+        clock.inc_by(Duration::from_secs(10));
+        assert!(attempt(&mut blocklist, blocked, &clock));
+        assert!(!blocklist.contains(blocked));
+        assert!(blocklist.is_empty());
+    }
+
+    #[test]
+    fn remove_test() {
+        let mut clock = MockClock::default();
+        let mut blocklist = Blocklist::default();
+        let address: IpAddr = "2001:db8::1".parse().unwrap();
+
+        assert!(blocklist.insert(address, Duration::from_secs(10)));
+        assert!(blocklist.remove(address));
+        assert!(!blocklist.remove(address));
+
+        clock.inc_by(Duration::from_secs(1));
+        assert!(attempt(&mut blocklist, address, &clock));
+    }
+
+    #[test]
+    fn max_entries_test() {
+        let clock = MockClock::default();
+        let mut blocklist = Blocklist::builder()
+            .with_max_entries(1)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // observe the current time
+        assert!(attempt(
+            &mut blocklist,
+            "192.0.2.1".parse().unwrap(),
+            &clock
+        ));
+
+        assert!(blocklist.insert("192.0.2.1".parse().unwrap(), Duration::from_secs(10)));
+        assert!(!blocklist.insert("192.0.2.2".parse().unwrap(), Duration::from_secs(10)));
+        assert_eq!(blocklist.len(), 1);
+    }
+
+    #[test]
+    fn violation_test() {
+        let mut clock = MockClock::default();
+        let blocklist = Blocklist::builder()
+            .with_violation_limit(2)
+            .unwrap()
+            .with_violation_window(Duration::from_secs(10))
+            .unwrap()
+            .with_violation_ttl(Duration::from_secs(30))
+            .unwrap()
+            .build()
+            .unwrap();
+        let address: IpAddr = "192.0.2.1".parse().unwrap();
+
+        let violation = |clock: &MockClock| {
+            let now: event::Timestamp = clock.get_time().into_event();
+            let now = now.duration_since_start();
+            blocklist.update(|state, _now| state.on_violation(ip_address_from_std(address), now));
+        };
+
+        violation(&clock);
+        assert!(!blocklist.contains(address));
+
+        // the window expires so the count resets
+        clock.inc_by(Duration::from_secs(10));
+        violation(&clock);
+        assert!(!blocklist.contains(address));
+
+        violation(&clock);
+        assert!(blocklist.contains(address));
+
+        let mut limiter = blocklist.clone();
+        clock.inc_by(Duration::from_secs(29));
+        assert!(!attempt(&mut limiter, address, &clock));
+        clock.inc_by(Duration::from_secs(1));
+        assert!(attempt(&mut limiter, address, &clock));
+    }
+
+    #[test]
+    fn pending_test() {
+        let mut clock = MockClock::default();
+        let mut blocklist = Blocklist::default();
+        let address: IpAddr = "192.0.2.1".parse().unwrap();
+
+        // the endpoint hasn't observed the current time yet
+        assert!(blocklist.insert(address, Duration::from_secs(10)));
+        assert!(blocklist.contains(address));
+
+        // the expiration is set once the first datagram is received
+        assert!(!attempt(&mut blocklist, address, &clock));
+        clock.inc_by(Duration::from_secs(9));
+        assert!(!attempt(&mut blocklist, address, &clock));
+        clock.inc_by(Duration::from_secs(1));
+        assert!(attempt(&mut blocklist, address, &clock));
+    }
+
+    #[test]
+    fn batch_test() {
+        let clock = MockClock::default();
+        let mut blocklist = Blocklist::default();
+        let allowed: IpAddr = "192.0.2.1".parse().unwrap();
+        let removed: IpAddr = "192.0.2.2".parse().unwrap();
+        let blocked: IpAddr = "192.0.2.3".parse().unwrap();
+
+        // observe the current time
+        assert!(attempt(&mut blocklist, allowed, &clock));
+
+        for i in 2..=255 {
+            let address = IpAddr::from([192, 0, 2, i]);
+            assert!(blocklist.insert(address, Duration::from_secs(10)));
+        }
+
+        // the snapshot is only rebuilt once the endpoint receives the next datagram
+        assert!(blocklist.snapshot.load().entries.is_empty());
+        assert!(!attempt(&mut blocklist, blocked, &clock));
+        assert_eq!(blocklist.snapshot.load().entries.len(), 254);
+        assert!(!blocklist.is_stale.load(Ordering::Relaxed));
+
+        assert!(blocklist.remove(removed));
+        assert!(attempt(&mut blocklist, removed, &clock));
+        assert!(!attempt(&mut blocklist, blocked, &clock));
+    }
+
+    #[test]
+    fn limits_test() {
+        let clock = MockClock::default();
+        let blocklist = Blocklist::default();
+        let blocked: IpAddr = "192.0.2.1".parse().unwrap();
+        let allowed: IpAddr = "192.0.2.2".parse().unwrap();
+
+        struct DropAll;
+
+        impl Limiter for DropAll {
+            fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+                Outcome::drop()
+            }
+        }
+
+        let mut limits = blocklist.clone().with_limits(DropAll);
+
+        assert!(blocklist.insert(blocked, Duration::from_secs(10)));
+
+        let datagram = |limits: &mut Limits<DropAll>, address: IpAddr| {
+            let remote_address = SocketAddress::from((address, 443));
+            let info = DatagramAttempt::new(&remote_address, 1200, clock.get_time().into_event());
+            limits.on_datagram(&info)
+        };
+
+        assert!(!datagram(&mut limits, blocked));
+        assert!(datagram(&mut limits, allowed));
+        assert_eq!(blocklist.dropped_datagrams(), 1);
+
+        // connection attempts are passed to the inner limits
+        let remote_address = SocketAddress::from((allowed, 443));
+        let info = ConnectionAttempt::new(0, 0, &remote_address, clock.get_time().into_event());
+        assert_eq!(limits.on_connection_attempt(&info), Outcome::drop());
+    }
+
+    #[test]
+    fn is_violation_test() {
+        let error = connection::Error::from(transport::Error::PROTOCOL_VIOLATION);
+        assert!(is_violation(&error));
+
+        let error = connection::Error::from(transport::Error::INTERNAL_ERROR);
+        assert!(!is_violation(&error));
+    }
+}
@@ -0,0 +1,698 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exposes the live state of an endpoint for management and introspection
+//!
+//! The [`Subscriber`] in this module tracks a summary of each open connection along with
+//! endpoint-wide metrics. A [`Handle`] to the subscriber can be cloned and queried from any thread
+//! to produce a [`Snapshot`], which implements [`serde::Serialize`]. This module doesn't provide a
+//! transport of its own; the snapshot is intended to be served by whatever RPC or HTTP interface
+//! the application already has, for example as JSON with `serde_json`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use s2n_quic::{
+//!     provider::{event::admin::Subscriber, limits::Limits},
+//!     Server,
+//! };
+//! use std::error::Error;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let limits = Limits::default();
+//! let subscriber = Subscriber::builder()
+//!     .with_limits(&limits)
+//!     .with_setting("address", "0.0.0.0:443")
+//!     .build();
+//! let admin = subscriber.handle();
+//!
+//! let mut server = Server::builder()
+//!     .with_limits(limits)?
+//!     .with_event(subscriber)?
+//!     .start()?;
+//!
+//! // serve `admin.snapshot()` from the application's management interface
+//! let snapshot = admin.snapshot();
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use super::{events, qlog::endpoint_type_name, ConnectionInfo, ConnectionMeta};
+use crate::provider::limits::Limits;
+use core::{fmt, time::Duration};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+/// The default duration of the rolling metrics window
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// The number of intervals the rolling metrics window is divided into
+const INTERVALS: u64 = 60;
+
+/// Marks an interval which hasn't recorded any metrics yet
+const EMPTY_EPOCH: u64 = u64::MAX;
+
+/// The state of the endpoint at the time [`Handle::snapshot`] was called
+#[non_exhaustive]
+#[derive(Clone, Debug, Serialize)]
+pub struct Snapshot {
+    pub endpoint: Endpoint,
+    /// The connection limits configured with [`Builder::with_limits`]
+    pub limits: Option<LimitSettings>,
+    /// The connections which are currently open, ordered by their internal ID
+    pub connections: Vec<Connection>,
+    /// The metrics recorded since the subscriber was created
+    pub totals: Metrics,
+    /// The metrics recorded in the most recent window
+    pub recent: Metrics,
+    /// The duration of the window for the `recent` metrics
+    pub window: Duration,
+}
+
+/// A summary of the endpoint
+#[non_exhaustive]
+#[derive(Clone, Debug, Serialize)]
+pub struct Endpoint {
+    /// Either `"client"` or `"server"`, once the first connection has been created
+    pub endpoint_type: Option<&'static str>,
+    /// The time since the subscriber was created
+    pub uptime: Duration,
+    /// The number of connections which are currently open
+    pub open_connections: usize,
+    /// The settings configured with [`Builder::with_setting`]
+    pub settings: BTreeMap<String, String>,
+}
+
+/// The connection limits the endpoint was configured with
+#[non_exhaustive]
+#[derive(Clone, Debug, Serialize)]
+pub struct LimitSettings {
+    pub max_idle_timeout: Option<Duration>,
+    pub max_handshake_duration: Duration,
+    pub max_keep_alive_period: Duration,
+    pub keep_alive_period: Option<Duration>,
+    pub initial_round_trip_time: Duration,
+    pub max_udp_payload_size: u64,
+    pub hibernation_timeout: Option<Duration>,
+}
+
+impl From<&Limits> for LimitSettings {
+    fn from(limits: &Limits) -> Self {
+        Self {
+            max_idle_timeout: limits.max_idle_timeout(),
+            max_handshake_duration: limits.max_handshake_duration(),
+            max_keep_alive_period: limits.max_keep_alive_period(),
+            keep_alive_period: limits.keep_alive_period(),
+            initial_round_trip_time: limits.initial_round_trip_time(),
+            max_udp_payload_size: limits.max_udp_payload_size(),
+            hibernation_timeout: limits.hibernation_timeout(),
+        }
+    }
+}
+
+/// A summary of an open connection
+#[non_exhaustive]
+#[derive(Clone, Debug, Serialize)]
+pub struct Connection {
+    /// The internal ID of the connection, which matches `ConnectionMeta::id` in other events
+    pub id: u64,
+    pub remote_address: String,
+    pub server_name: Option<String>,
+    /// The time since the connection was created
+    pub age: Duration,
+    /// One of `"in_progress"`, `"complete"` or `"confirmed"`
+    pub handshake: &'static str,
+    pub min_rtt: Duration,
+    pub smoothed_rtt: Duration,
+    pub congestion_window: u32,
+    pub bytes_in_flight: u32,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_lost: u64,
+}
+
+/// Endpoint-wide counters
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Metrics {
+    pub connections_opened: u64,
+    pub connections_closed: u64,
+    pub handshakes_confirmed: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_lost: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    connections_opened: AtomicU64,
+    connections_closed: AtomicU64,
+    handshakes_confirmed: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    packets_lost: AtomicU64,
+}
+
+impl Counters {
+    fn load(&self, metrics: &mut Metrics) {
+        metrics.connections_opened += self.connections_opened.load(Ordering::Relaxed);
+        metrics.connections_closed += self.connections_closed.load(Ordering::Relaxed);
+        metrics.handshakes_confirmed += self.handshakes_confirmed.load(Ordering::Relaxed);
+        metrics.bytes_sent += self.bytes_sent.load(Ordering::Relaxed);
+        metrics.bytes_received += self.bytes_received.load(Ordering::Relaxed);
+        metrics.packets_lost += self.packets_lost.load(Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        self.connections_opened.store(0, Ordering::Relaxed);
+        self.connections_closed.store(0, Ordering::Relaxed);
+        self.handshakes_confirmed.store(0, Ordering::Relaxed);
+        self.bytes_sent.store(0, Ordering::Relaxed);
+        self.bytes_received.store(0, Ordering::Relaxed);
+        self.packets_lost.store(0, Ordering::Relaxed);
+    }
+}
+
+/// The counters for a single interval of the rolling window
+#[derive(Debug)]
+struct Interval {
+    epoch: AtomicU64,
+    counters: Counters,
+}
+
+/// The connection summary along with the state needed to produce it
+#[derive(Debug)]
+struct ConnectionState {
+    summary: Connection,
+    created: Instant,
+}
+
+#[derive(Debug)]
+struct Shared {
+    started: Instant,
+    interval: Duration,
+    settings: BTreeMap<String, String>,
+    limits: Option<LimitSettings>,
+    totals: Counters,
+    intervals: Box<[Interval]>,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    endpoint_type: Option<&'static str>,
+    connections: BTreeMap<u64, Arc<Mutex<ConnectionState>>>,
+}
+
+impl Shared {
+    fn epoch(&self, now: Instant) -> u64 {
+        let elapsed = now.saturating_duration_since(self.started);
+        (elapsed.as_nanos() / self.interval.as_nanos()) as u64
+    }
+
+    /// Records metrics in the totals and the current interval
+    ///
+    /// Events are only published from the endpoint task, so there's a single writer and the
+    /// intervals can be reset without any additional synchronization.
+    fn record<F: Fn(&Counters)>(&self, f: F) {
+        f(&self.totals);
+
+        let epoch = self.epoch(Instant::now());
+        let interval = &self.intervals[(epoch % INTERVALS) as usize];
+        if interval.epoch.load(Ordering::Relaxed) != epoch {
+            interval.counters.reset();
+            interval.epoch.store(epoch, Ordering::Relaxed);
+        }
+        f(&interval.counters);
+    }
+
+    fn recent(&self, now: Instant) -> Metrics {
+        let now = self.epoch(now);
+        let mut metrics = Metrics::default();
+
+        for interval in self.intervals.iter() {
+            let epoch = interval.epoch.load(Ordering::Relaxed);
+            if epoch != EMPTY_EPOCH && epoch <= now && now - epoch < INTERVALS {
+                interval.counters.load(&mut metrics);
+            }
+        }
+
+        metrics
+    }
+}
+
+/// Builds a [`Subscriber`]
+#[derive(Debug)]
+pub struct Builder {
+    window: Duration,
+    settings: BTreeMap<String, String>,
+    limits: Option<LimitSettings>,
+}
+
+impl Builder {
+    /// Sets the duration of the window for the rolling metrics (default: 60 seconds)
+    pub fn with_window(mut self, window: Duration) -> Result<Self, Error> {
+        if window < Duration::from_millis(INTERVALS) {
+            return Err(Error::InvalidWindow);
+        }
+        self.window = window;
+        Ok(self)
+    }
+
+    /// Reports the limits the endpoint was configured with in each snapshot
+    pub fn with_limits(mut self, limits: &Limits) -> Self {
+        self.limits = Some(limits.into());
+        self
+    }
+
+    /// Reports an application-defined configuration setting in each snapshot
+    ///
+    /// This can be used to include configuration the subscriber can't observe, such as the
+    /// address the endpoint is bound to or the selected TLS provider.
+    pub fn with_setting<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.settings.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn build(self) -> Subscriber {
+        let intervals = (0..INTERVALS)
+            .map(|_| Interval {
+                epoch: AtomicU64::new(EMPTY_EPOCH),
+                counters: Counters::default(),
+            })
+            .collect();
+
+        Subscriber {
+            shared: Arc::new(Shared {
+                started: Instant::now(),
+                interval: self.window / INTERVALS as u32,
+                settings: self.settings,
+                limits: self.limits,
+                totals: Counters::default(),
+                intervals,
+                state: Default::default(),
+            }),
+        }
+    }
+}
+
+/// Tracks the state of an endpoint for a [`Handle`]
+#[derive(Debug)]
+pub struct Subscriber {
+    shared: Arc<Shared>,
+}
+
+impl Default for Subscriber {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Subscriber {
+    pub fn builder() -> Builder {
+        Builder {
+            window: DEFAULT_WINDOW,
+            settings: BTreeMap::new(),
+            limits: None,
+        }
+    }
+
+    /// Returns a handle which can be used to query the endpoint state from any thread
+    pub fn handle(&self) -> Handle {
+        Handle {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// Queries the state tracked by a [`Subscriber`]
+#[derive(Clone, Debug)]
+pub struct Handle {
+    shared: Arc<Shared>,
+}
+
+impl Handle {
+    /// Returns the current state of the endpoint
+    pub fn snapshot(&self) -> Snapshot {
+        let now = Instant::now();
+        let shared = &self.shared;
+
+        let (endpoint_type, connections) = {
+            let state = shared.state.lock().unwrap();
+            let connections: Vec<_> = state.connections.values().cloned().collect();
+            (state.endpoint_type, connections)
+        };
+
+        // the endpoint lock is released before each connection is locked so the endpoint task
+        // isn't blocked while the summaries are copied
+        let connections: Vec<_> = connections
+            .iter()
+            .map(|connection| {
+                let connection = connection.lock().unwrap();
+                let mut summary = connection.summary.clone();
+                summary.age = now.saturating_duration_since(connection.created);
+                summary
+            })
+            .collect();
+
+        let mut totals = Metrics::default();
+        shared.totals.load(&mut totals);
+
+        Snapshot {
+            endpoint: Endpoint {
+                endpoint_type,
+                uptime: now.saturating_duration_since(shared.started),
+                open_connections: connections.len(),
+                settings: shared.settings.clone(),
+            },
+            limits: shared.limits.clone(),
+            connections,
+            totals,
+            recent: shared.recent(now),
+            window: shared.interval * INTERVALS as u32,
+        }
+    }
+}
+
+/// The per-connection state of the [`Subscriber`]
+pub struct Context {
+    id: u64,
+    state: Arc<Mutex<ConnectionState>>,
+    shared: Arc<Shared>,
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Context").field("id", &self.id).finish()
+    }
+}
+
+impl Context {
+    #[inline]
+    fn update<F: FnOnce(&mut Connection)>(&self, f: F) {
+        f(&mut self.state.lock().unwrap().summary)
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.connections.remove(&self.id);
+        }
+    }
+}
+
+impl super::Subscriber for Subscriber {
+    type ConnectionContext = Context;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let state = Arc::new(Mutex::new(ConnectionState {
+            summary: Connection {
+                id: meta.id,
+                remote_address: info.remote_address.to_string(),
+                server_name: info.server_name.map(String::from),
+                age: Duration::ZERO,
+                handshake: "in_progress",
+                min_rtt: Duration::ZERO,
+                smoothed_rtt: Duration::ZERO,
+                congestion_window: 0,
+                bytes_in_flight: 0,
+                bytes_sent: 0,
+                bytes_received: 0,
+                packets_lost: 0,
+            },
+            created: Instant::now(),
+        }));
+
+        {
+            let mut endpoint = self.shared.state.lock().unwrap();
+            endpoint.endpoint_type = Some(endpoint_type_name(&meta.endpoint_type));
+            endpoint.connections.insert(meta.id, state.clone());
+        }
+
+        self.shared.record(|counters| {
+            counters.connections_opened.fetch_add(1, Ordering::Relaxed);
+        });
+
+        Context {
+            id: meta.id,
+            state,
+            shared: self.shared.clone(),
+        }
+    }
+
+    #[inline]
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramSent,
+    ) {
+        let len = event.len as u64;
+        context.update(|summary| summary.bytes_sent += len);
+        self.shared.record(|counters| {
+            counters.bytes_sent.fetch_add(len, Ordering::Relaxed);
+        });
+    }
+
+    #[inline]
+    fn on_datagram_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramReceived,
+    ) {
+        let len = event.len as u64;
+        context.update(|summary| summary.bytes_received += len);
+        self.shared.record(|counters| {
+            counters.bytes_received.fetch_add(len, Ordering::Relaxed);
+        });
+    }
+
+    #[inline]
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::PacketLost,
+    ) {
+        context.update(|summary| summary.packets_lost += 1);
+        self.shared.record(|counters| {
+            counters.packets_lost.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    #[inline]
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        context.update(|summary| {
+            summary.min_rtt = event.min_rtt;
+            summary.smoothed_rtt = event.smoothed_rtt;
+            summary.congestion_window = event.congestion_window;
+            summary.bytes_in_flight = event.bytes_in_flight;
+        });
+    }
+
+    #[inline]
+    fn on_handshake_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::HandshakeStatusUpdated,
+    ) {
+        use events::HandshakeStatus::*;
+
+        match event.status {
+            Complete { .. } => context.update(|summary| summary.handshake = "complete"),
+            Confirmed { .. } => {
+                context.update(|summary| summary.handshake = "confirmed");
+                self.shared.record(|counters| {
+                    counters
+                        .handshakes_confirmed
+                        .fetch_add(1, Ordering::Relaxed);
+                });
+            }
+            _ => {}
+        }
+    }
+
+    #[inline]
+    fn on_connection_closed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::ConnectionClosed,
+    ) {
+        // the connection summary is removed once the context is dropped
+        self.shared.record(|counters| {
+            counters.connections_closed.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The rolling metrics window was too small
+    InvalidWindow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidWindow => write!(f, "the window must be at least {INTERVALS}ms"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::event::Subscriber as _;
+    use s2n_quic_core::{
+        endpoint,
+        event::{builder, IntoEvent},
+        inet::SocketAddress,
+        time::{testing::Clock, Clock as _},
+    };
+
+    fn meta() -> ConnectionMeta {
+        builder::ConnectionMeta {
+            endpoint_type: endpoint::Type::Server,
+            id: 3,
+            attempt_id: 0,
+            timestamp: Clock::default().get_time(),
+            application_context: Default::default(),
+        }
+        .into_event()
+    }
+
+    #[test]
+    fn snapshot_test() {
+        let limits = Limits::default()
+            .with_max_idle_timeout(Duration::from_secs(5))
+            .unwrap();
+        let mut subscriber = Subscriber::builder()
+            .with_limits(&limits)
+            .with_setting("address", "127.0.0.1:443")
+            .build();
+        let handle = subscriber.handle();
+
+        let snapshot = handle.snapshot();
+        assert!(snapshot.endpoint.endpoint_type.is_none());
+        assert!(snapshot.connections.is_empty());
+        assert_eq!(
+            snapshot.limits.unwrap().max_idle_timeout,
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(snapshot.endpoint.settings["address"], "127.0.0.1:443");
+
+        let remote_address: std::net::SocketAddr = "127.0.0.1:1234".parse().unwrap();
+        let remote_address = SocketAddress::from(remote_address);
+        let info = builder::ConnectionInfo {
+            remote_address: (&remote_address).into_event(),
+            server_name: None,
+        }
+        .into_event();
+        let mut context = subscriber.create_connection_context(&meta(), &info);
+
+        let event = builder::DatagramSent {
+            len: 1200,
+            gso_offset: 0,
+        }
+        .into_event();
+        subscriber.on_datagram_sent(&mut context, &meta(), &event);
+
+        let event = builder::DatagramReceived {
+            len: 300,
+            queue_id: None,
+        }
+        .into_event();
+        subscriber.on_datagram_received(&mut context, &meta(), &event);
+
+        let event = builder::HandshakeStatusUpdated {
+            status: builder::HandshakeStatus::Confirmed,
+        }
+        .into_event();
+        subscriber.on_handshake_status_updated(&mut context, &meta(), &event);
+
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.endpoint.endpoint_type, Some("server"));
+        assert_eq!(snapshot.endpoint.open_connections, 1);
+
+        let connection = &snapshot.connections[0];
+        assert_eq!(connection.id, 3);
+        assert_eq!(connection.remote_address, "127.0.0.1:1234");
+        assert_eq!(connection.handshake, "confirmed");
+        assert_eq!(connection.bytes_sent, 1200);
+        assert_eq!(connection.bytes_received, 300);
+
+        let expected = Metrics {
+            connections_opened: 1,
+            handshakes_confirmed: 1,
+            bytes_sent: 1200,
+            bytes_received: 300,
+            ..Default::default()
+        };
+        assert_eq!(snapshot.totals, expected);
+        assert_eq!(snapshot.recent, expected);
+
+        let event = builder::ConnectionClosed {
+            error: s2n_quic_core::connection::Error::idle_timer_expired(),
+        }
+        .into_event();
+        subscriber.on_connection_closed(&mut context, &meta(), &event);
+        drop(context);
+
+        let snapshot = handle.snapshot();
+        assert!(snapshot.connections.is_empty());
+        assert_eq!(snapshot.totals.connections_closed, 1);
+    }
+
+    #[test]
+    fn window_test() {
+        assert_eq!(
+            Subscriber::builder()
+                .with_window(Duration::from_millis(1))
+                .unwrap_err(),
+            Error::InvalidWindow
+        );
+
+        let subscriber = Subscriber::builder()
+            .with_window(Duration::from_millis(60))
+            .unwrap()
+            .build();
+        let handle = subscriber.handle();
+
+        subscriber.shared.record(|counters| {
+            counters.packets_lost.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(handle.snapshot().recent.packets_lost, 1);
+
+        // the interval expires once the window has passed
+        std::thread::sleep(Duration::from_millis(120));
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.recent.packets_lost, 0);
+        assert_eq!(snapshot.totals.packets_lost, 1);
+    }
+}
@@ -0,0 +1,570 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Aggregates endpoint-wide connection metrics
+//!
+//! The [`Subscriber`] in this module maintains counters across all of the connections of an
+//! endpoint. A [`Handle`] to the subscriber can be cloned and polled from any thread to produce a
+//! [`Snapshot`], and [`Snapshot::rates_since`] derives per-second rates, such as handshakes per
+//! second, from two snapshots.
+//!
+//! With the `provider-event-metrics-rs` feature, the counters can also be published to the
+//! [`metrics`](https://docs.rs/metrics) facade with [`Builder::with_metrics_rs`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use s2n_quic::{provider::event::metrics::Subscriber, Server};
+//! use std::{error::Error, time::Duration};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let subscriber = Subscriber::default();
+//! let metrics = subscriber.handle();
+//!
+//! let mut server = Server::builder().with_event(subscriber)?.start()?;
+//!
+//! tokio::spawn(async move {
+//!     let mut previous = metrics.snapshot();
+//!     loop {
+//!         tokio::time::sleep(Duration::from_secs(10)).await;
+//!         let snapshot = metrics.snapshot();
+//!         let rates = snapshot.rates_since(&previous);
+//!         println!(
+//!             "connections: {}, handshakes/sec: {:.1}",
+//!             snapshot.active_connections, rates.handshakes_completed
+//!         );
+//!         previous = snapshot;
+//!     }
+//! });
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use super::{events, ConnectionInfo, ConnectionMeta};
+use core::{fmt, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// The endpoint-wide metrics at the time [`Handle::snapshot`] was called
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The time since the subscriber was created
+    pub uptime: Duration,
+    /// The number of connections which are currently open
+    pub active_connections: u64,
+    pub connections_opened: u64,
+    pub connections_closed: u64,
+    pub handshakes_completed: u64,
+    /// The number of bytes sent in UDP datagrams
+    pub bytes_sent: u64,
+    /// The number of bytes received in UDP datagrams
+    pub bytes_received: u64,
+    pub packets_lost: u64,
+    /// The number of times a probe timeout (PTO) expired and increased the PTO backoff
+    pub pto_count: u64,
+}
+
+impl Snapshot {
+    /// Returns the per-second rates of the counters since the `previous` snapshot
+    ///
+    /// All of the rates are zero if no time passed between the snapshots.
+    pub fn rates_since(&self, previous: &Snapshot) -> Rates {
+        let elapsed = self.uptime.saturating_sub(previous.uptime);
+        if elapsed.is_zero() {
+            return Rates::default();
+        }
+
+        let secs = elapsed.as_secs_f64();
+        let rate = |current: u64, previous: u64| current.saturating_sub(previous) as f64 / secs;
+
+        Rates {
+            connections_opened: rate(self.connections_opened, previous.connections_opened),
+            connections_closed: rate(self.connections_closed, previous.connections_closed),
+            handshakes_completed: rate(self.handshakes_completed, previous.handshakes_completed),
+            bytes_sent: rate(self.bytes_sent, previous.bytes_sent),
+            bytes_received: rate(self.bytes_received, previous.bytes_received),
+            packets_lost: rate(self.packets_lost, previous.packets_lost),
+            pto_count: rate(self.pto_count, previous.pto_count),
+        }
+    }
+}
+
+/// The per-second rates of the counters between two [`Snapshot`]s
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rates {
+    pub connections_opened: f64,
+    pub connections_closed: f64,
+    pub handshakes_completed: f64,
+    pub bytes_sent: f64,
+    pub bytes_received: f64,
+    pub packets_lost: f64,
+    pub pto_count: f64,
+}
+
+/// A counter which is optionally published to the `metrics` facade
+#[derive(Default)]
+struct Counter {
+    value: AtomicU64,
+    #[cfg(feature = "provider-event-metrics-rs")]
+    exporter: Option<::metrics::Counter>,
+}
+
+impl fmt::Debug for Counter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl Counter {
+    #[inline]
+    fn add(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+
+        #[cfg(feature = "provider-event-metrics-rs")]
+        if let Some(exporter) = self.exporter.as_ref() {
+            exporter.increment(value);
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// A gauge which is optionally published to the `metrics` facade
+#[derive(Default)]
+struct Gauge {
+    value: AtomicU64,
+    #[cfg(feature = "provider-event-metrics-rs")]
+    exporter: Option<::metrics::Gauge>,
+}
+
+impl fmt::Debug for Gauge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl Gauge {
+    #[inline]
+    fn increment(&self) {
+        self.value.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "provider-event-metrics-rs")]
+        if let Some(exporter) = self.exporter.as_ref() {
+            exporter.increment(1.0);
+        }
+    }
+
+    #[inline]
+    fn decrement(&self) {
+        self.value.fetch_sub(1, Ordering::Relaxed);
+
+        #[cfg(feature = "provider-event-metrics-rs")]
+        if let Some(exporter) = self.exporter.as_ref() {
+            exporter.decrement(1.0);
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    started: Instant,
+    active_connections: Gauge,
+    connections_opened: Counter,
+    connections_closed: Counter,
+    handshakes_completed: Counter,
+    bytes_sent: Counter,
+    bytes_received: Counter,
+    packets_lost: Counter,
+    pto_count: Counter,
+}
+
+impl Shared {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            active_connections: Default::default(),
+            connections_opened: Default::default(),
+            connections_closed: Default::default(),
+            handshakes_completed: Default::default(),
+            bytes_sent: Default::default(),
+            bytes_received: Default::default(),
+            packets_lost: Default::default(),
+            pto_count: Default::default(),
+        }
+    }
+
+    /// Registers each of the metrics with the `metrics` facade, prefixing their names
+    #[cfg(feature = "provider-event-metrics-rs")]
+    fn export(&mut self, prefix: &str) {
+        macro_rules! export {
+            ($($counter:ident),*) => {
+                $(
+                    self.$counter.exporter = Some(::metrics::counter!(format!(
+                        "{prefix}{}",
+                        stringify!($counter)
+                    )));
+                )*
+            };
+        }
+
+        self.active_connections.exporter =
+            Some(::metrics::gauge!(format!("{prefix}active_connections")));
+        export!(
+            connections_opened,
+            connections_closed,
+            handshakes_completed,
+            bytes_sent,
+            bytes_received,
+            packets_lost,
+            pto_count
+        );
+    }
+}
+
+/// Builds a [`Subscriber`]
+#[derive(Debug, Default)]
+pub struct Builder {
+    #[cfg(feature = "provider-event-metrics-rs")]
+    metrics_rs_prefix: Option<String>,
+}
+
+impl Builder {
+    /// Publishes the metrics to the [`metrics`](https://docs.rs/metrics) facade
+    ///
+    /// Each metric is registered with its [`Snapshot`] field name, prefixed with `prefix`, for
+    /// example `s2n_quic_bytes_sent` with a prefix of `"s2n_quic_"`. `active_connections` is
+    /// published as a gauge and the remaining metrics as counters.
+    ///
+    /// The metrics are registered with the global recorder when [`Self::build`] is called, so
+    /// the recorder should be installed before the subscriber is built.
+    #[cfg(feature = "provider-event-metrics-rs")]
+    pub fn with_metrics_rs<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.metrics_rs_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn build(self) -> Subscriber {
+        #[allow(unused_mut)]
+        let mut shared = Shared::new();
+
+        #[cfg(feature = "provider-event-metrics-rs")]
+        if let Some(prefix) = self.metrics_rs_prefix.as_deref() {
+            shared.export(prefix);
+        }
+
+        Subscriber {
+            shared: Arc::new(shared),
+        }
+    }
+}
+
+/// Aggregates the metrics of all of the connections of an endpoint for a [`Handle`]
+#[derive(Debug)]
+pub struct Subscriber {
+    shared: Arc<Shared>,
+}
+
+impl Default for Subscriber {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Subscriber {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns a handle which can be used to poll the metrics from any thread
+    pub fn handle(&self) -> Handle {
+        Handle {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// Polls the metrics aggregated by a [`Subscriber`]
+#[derive(Clone, Debug)]
+pub struct Handle {
+    shared: Arc<Shared>,
+}
+
+impl Handle {
+    /// Returns the current value of each metric
+    pub fn snapshot(&self) -> Snapshot {
+        let shared = &self.shared;
+
+        Snapshot {
+            uptime: shared.started.elapsed(),
+            active_connections: shared.active_connections.get(),
+            connections_opened: shared.connections_opened.get(),
+            connections_closed: shared.connections_closed.get(),
+            handshakes_completed: shared.handshakes_completed.get(),
+            bytes_sent: shared.bytes_sent.get(),
+            bytes_received: shared.bytes_received.get(),
+            packets_lost: shared.packets_lost.get(),
+            pto_count: shared.pto_count.get(),
+        }
+    }
+}
+
+/// The per-connection state of the [`Subscriber`]
+pub struct Context {
+    /// The PTO count of the most recent recovery metrics
+    pto_count: u32,
+    shared: Arc<Shared>,
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("pto_count", &self.pto_count)
+            .finish()
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.shared.active_connections.decrement();
+    }
+}
+
+impl super::Subscriber for Subscriber {
+    type ConnectionContext = Context;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        self.shared.connections_opened.add(1);
+        self.shared.active_connections.increment();
+
+        Context {
+            pto_count: 0,
+            shared: self.shared.clone(),
+        }
+    }
+
+    #[inline]
+    fn on_datagram_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramSent,
+    ) {
+        self.shared.bytes_sent.add(event.len as u64);
+    }
+
+    #[inline]
+    fn on_datagram_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramReceived,
+    ) {
+        self.shared.bytes_received.add(event.len as u64);
+    }
+
+    #[inline]
+    fn on_packet_lost(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::PacketLost,
+    ) {
+        self.shared.packets_lost.add(1);
+    }
+
+    #[inline]
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        // the PTO count is reset once an acknowledgement is received, so only increases are
+        // counted as expirations
+        let expirations = event.pto_count.saturating_sub(context.pto_count);
+        if expirations > 0 {
+            self.shared.pto_count.add(expirations as u64);
+        }
+        context.pto_count = event.pto_count;
+    }
+
+    #[inline]
+    fn on_handshake_status_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::HandshakeStatusUpdated,
+    ) {
+        if matches!(event.status, events::HandshakeStatus::Complete { .. }) {
+            self.shared.handshakes_completed.add(1);
+        }
+    }
+
+    #[inline]
+    fn on_connection_closed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::ConnectionClosed,
+    ) {
+        self.shared.connections_closed.add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::event::Subscriber as _;
+    use s2n_quic_core::{
+        endpoint,
+        event::{builder, IntoEvent},
+        inet::SocketAddress,
+        time::{testing::Clock, Clock as _},
+    };
+
+    fn meta() -> ConnectionMeta {
+        builder::ConnectionMeta {
+            endpoint_type: endpoint::Type::Server,
+            id: 3,
+            attempt_id: 0,
+            timestamp: Clock::default().get_time(),
+            application_context: Default::default(),
+        }
+        .into_event()
+    }
+
+    fn recovery_metrics(
+        subscriber: &mut Subscriber,
+        context: &mut Context,
+        remote_address: &SocketAddress,
+        pto_count: u32,
+    ) {
+        let path = builder::Path {
+            local_addr: remote_address.into_event(),
+            local_cid: builder::ConnectionId { bytes: &[] },
+            remote_addr: remote_address.into_event(),
+            remote_cid: builder::ConnectionId { bytes: &[] },
+            id: 0,
+            is_active: true,
+        };
+        let event = builder::RecoveryMetrics {
+            path,
+            min_rtt: Duration::from_millis(10),
+            smoothed_rtt: Duration::from_millis(10),
+            latest_rtt: Duration::from_millis(10),
+            rtt_variance: Duration::from_millis(5),
+            max_ack_delay: Duration::from_millis(25),
+            pto_count,
+            congestion_window: 12_000,
+            bytes_in_flight: 0,
+            congestion_limited: false,
+        }
+        .into_event();
+        subscriber.on_recovery_metrics(context, &meta(), &event);
+    }
+
+    #[test]
+    fn snapshot_test() {
+        let mut subscriber = Subscriber::default();
+        let handle = subscriber.handle();
+        assert_eq!(handle.snapshot().active_connections, 0);
+
+        let remote_address = SocketAddress::default();
+        let info = builder::ConnectionInfo {
+            remote_address: (&remote_address).into_event(),
+            server_name: None,
+        }
+        .into_event();
+        let mut context = subscriber.create_connection_context(&meta(), &info);
+
+        let event = builder::DatagramSent {
+            len: 1200,
+            gso_offset: 0,
+        }
+        .into_event();
+        subscriber.on_datagram_sent(&mut context, &meta(), &event);
+
+        let event = builder::DatagramReceived {
+            len: 300,
+            queue_id: None,
+        }
+        .into_event();
+        subscriber.on_datagram_received(&mut context, &meta(), &event);
+
+        let event = builder::HandshakeStatusUpdated {
+            status: builder::HandshakeStatus::Complete,
+        }
+        .into_event();
+        subscriber.on_handshake_status_updated(&mut context, &meta(), &event);
+
+        // the PTO count backs off twice, is reset by an ACK, and then backs off again
+        for pto_count in [0, 1, 2, 0, 1] {
+            recovery_metrics(&mut subscriber, &mut context, &remote_address, pto_count);
+        }
+
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.active_connections, 1);
+        assert_eq!(snapshot.connections_opened, 1);
+        assert_eq!(snapshot.handshakes_completed, 1);
+        assert_eq!(snapshot.bytes_sent, 1200);
+        assert_eq!(snapshot.bytes_received, 300);
+        assert_eq!(snapshot.pto_count, 3);
+
+        let event = builder::ConnectionClosed {
+            error: s2n_quic_core::connection::Error::idle_timer_expired(),
+        }
+        .into_event();
+        subscriber.on_connection_closed(&mut context, &meta(), &event);
+        drop(context);
+
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.active_connections, 0);
+        assert_eq!(snapshot.connections_closed, 1);
+    }
+
+    #[test]
+    fn rates_test() {
+        let previous = Snapshot {
+            uptime: Duration::from_secs(10),
+            handshakes_completed: 100,
+            bytes_sent: 1000,
+            ..Default::default()
+        };
+        let current = Snapshot {
+            uptime: Duration::from_secs(12),
+            handshakes_completed: 150,
+            bytes_sent: 5000,
+            ..Default::default()
+        };
+
+        let rates = current.rates_since(&previous);
+        assert_eq!(rates.handshakes_completed, 25.0);
+        assert_eq!(rates.bytes_sent, 2000.0);
+        assert_eq!(rates.packets_lost, 0.0);
+
+        // snapshots taken at the same time don't have a rate
+        assert_eq!(current.rates_since(&current), Rates::default());
+    }
+}
@@ -9,7 +9,7 @@ pub use s2n_quic_core::{
     event::{
         api as events,
         api::{ConnectionInfo, ConnectionMeta},
-        supervisor, Event, Meta, Subscriber, Timestamp,
+        supervisor, ApplicationContext, Event, Meta, Subscriber, Timestamp,
     },
     query,
 };
@@ -29,6 +29,25 @@ pub mod disabled;
 #[cfg(any(feature = "provider-event-tracing", test))]
 pub mod tracing;
 
+/// Provides a subscriber wrapper which only observes a subset of connections
+pub mod sampling;
+
+/// Provides a subscriber which records recent events for each connection and dumps them on failure
+pub mod replay;
+
+/// Provides a subscriber which writes a qlog trace for each connection
+pub mod qlog;
+
+/// Provides a subscriber which exposes the live state of an endpoint for management interfaces
+#[cfg(feature = "provider-event-admin")]
+pub mod admin;
+
+/// Provides a subscriber which aggregates endpoint-wide connection metrics
+pub mod metrics;
+
+/// Provides a subscriber which profiles the hot paths of the transport
+pub mod profile;
+
 /// Provides an implementation to emit perf metrics to the console
 #[cfg(feature = "provider-event-console-perf")]
 pub mod console_perf;
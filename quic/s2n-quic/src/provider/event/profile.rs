@@ -0,0 +1,365 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Profiles the hot paths of the transport
+//!
+//! The [`Subscriber`] in this module times the transport as it passes through each of the
+//! profiled [`Scope`]s and aggregates the durations into a histogram per scope. A [`Handle`] to
+//! the subscriber can be cloned and polled from any thread to produce a [`Snapshot`] of the
+//! histograms, which makes it possible to locate CPU regressions in production without attaching
+//! an external profiler.
+//!
+//! Reading the clock on every pass through a scope adds overhead to the paths being measured, so
+//! [`Builder::with_sample_rate`] can be used to only time a fraction of the passes.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use s2n_quic::{
+//!     provider::event::profile::{Scope, Subscriber},
+//!     Server,
+//! };
+//! use std::{error::Error, time::Duration};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let subscriber = Subscriber::builder().with_sample_rate(100).build();
+//! let profile = subscriber.handle();
+//!
+//! let mut server = Server::builder().with_event(subscriber)?.start()?;
+//!
+//! tokio::spawn(async move {
+//!     loop {
+//!         tokio::time::sleep(Duration::from_secs(60)).await;
+//!         let snapshot = profile.snapshot();
+//!         for scope in Scope::ALL {
+//!             let histogram = snapshot.get(*scope);
+//!             println!(
+//!                 "{}: mean={:?} p99={:?}",
+//!                 scope.as_str(),
+//!                 histogram.mean(),
+//!                 histogram.percentile(0.99)
+//!             );
+//!         }
+//!     }
+//! });
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use super::{ConnectionInfo, ConnectionMeta};
+use core::time::Duration;
+pub use s2n_quic_core::event::profile::Scope;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+const SCOPES: usize = Scope::ALL.len();
+
+/// The number of buckets in a [`Histogram`]
+///
+/// Bucket `i` holds the durations between `2^i` and `2^(i + 1)` nanoseconds.
+pub const BUCKETS: usize = 64;
+
+#[inline]
+fn bucket_index(nanos: u64) -> usize {
+    (u64::BITS - 1 - nanos.max(1).leading_zeros()) as usize
+}
+
+/// The durations recorded for a single [`Scope`]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Histogram {
+    /// The number of durations which were recorded
+    pub count: u64,
+    /// The sum of all of the recorded durations
+    pub total: Duration,
+    /// The number of durations in each of the power-of-two nanosecond buckets
+    pub buckets: [u64; BUCKETS],
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            total: Duration::ZERO,
+            buckets: [0; BUCKETS],
+        }
+    }
+}
+
+impl Histogram {
+    /// Returns the average of the recorded durations
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+
+        let nanos = self.total.as_nanos() / self.count as u128;
+        Duration::from_nanos(nanos.min(u64::MAX as u128) as u64)
+    }
+
+    /// Returns an upper bound of the duration below which the `quantile` of the recorded
+    /// durations fall
+    ///
+    /// The `quantile` is clamped between `0.0` and `1.0`. Since durations are recorded into
+    /// power-of-two buckets, the returned value is at most twice the actual duration.
+    pub fn percentile(&self, quantile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+
+        let target = ((self.count as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return Self::upper_bound(index);
+            }
+        }
+
+        Self::upper_bound(BUCKETS - 1)
+    }
+
+    #[inline]
+    fn upper_bound(index: usize) -> Duration {
+        Duration::from_nanos(1u64.checked_shl(index as u32 + 1).unwrap_or(u64::MAX))
+    }
+}
+
+/// The histograms of each of the profiled scopes at the time [`Handle::snapshot`] was called
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    scopes: [Histogram; SCOPES],
+}
+
+impl Snapshot {
+    /// Returns the histogram of the given `scope`
+    pub fn get(&self, scope: Scope) -> &Histogram {
+        &self.scopes[scope.index()]
+    }
+
+    /// Iterates over the histograms of all of the scopes
+    pub fn iter(&self) -> impl Iterator<Item = (Scope, &Histogram)> + '_ {
+        Scope::ALL.iter().copied().zip(self.scopes.iter())
+    }
+}
+
+#[derive(Debug)]
+struct SharedHistogram {
+    count: AtomicU64,
+    total_nanos: AtomicU64,
+    buckets: [AtomicU64; BUCKETS],
+}
+
+impl Default for SharedHistogram {
+    fn default() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            buckets: core::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+}
+
+impl SharedHistogram {
+    #[inline]
+    fn record(&self, duration: Duration) {
+        let nanos = duration.as_nanos().min(u64::MAX as u128) as u64;
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.buckets[bucket_index(nanos)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn load(&self) -> Histogram {
+        Histogram {
+            count: self.count.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            buckets: core::array::from_fn(|index| self.buckets[index].load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    scopes: [SharedHistogram; SCOPES],
+}
+
+/// Builds a [`Subscriber`]
+#[derive(Debug)]
+pub struct Builder {
+    sample_rate: u32,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self { sample_rate: 1 }
+    }
+}
+
+impl Builder {
+    /// Times one out of every `sample_rate` passes through each scope
+    ///
+    /// Defaults to `1`, which times every pass. A value of `0` is treated as `1`.
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate.max(1);
+        self
+    }
+
+    pub fn build(self) -> Subscriber {
+        Subscriber {
+            sample_rate: self.sample_rate,
+            scopes: Default::default(),
+            shared: Default::default(),
+        }
+    }
+}
+
+/// The timing state of a single scope in a [`Subscriber`]
+#[derive(Debug, Default)]
+struct ScopeState {
+    /// The number of passes through the scope since one was last timed
+    skipped: u32,
+    /// The time the current pass entered the scope, if it's being timed
+    entered: Option<Instant>,
+}
+
+/// Records the time spent in each of the profiled scopes of an endpoint for a [`Handle`]
+#[derive(Debug)]
+pub struct Subscriber {
+    sample_rate: u32,
+    scopes: [ScopeState; SCOPES],
+    shared: Arc<Shared>,
+}
+
+impl Default for Subscriber {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Subscriber {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns a handle which can be used to poll the histograms from any thread
+    pub fn handle(&self) -> Handle {
+        Handle {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// Polls the histograms recorded by a [`Subscriber`]
+#[derive(Clone, Debug)]
+pub struct Handle {
+    shared: Arc<Shared>,
+}
+
+impl Handle {
+    /// Returns the current histogram of each scope
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            scopes: core::array::from_fn(|index| self.shared.scopes[index].load()),
+        }
+    }
+}
+
+impl super::Subscriber for Subscriber {
+    type ConnectionContext = ();
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    #[inline]
+    fn on_profile_scope_entered(&mut self, scope: Scope) {
+        let state = &mut self.scopes[scope.index()];
+
+        state.skipped += 1;
+        if state.skipped < self.sample_rate {
+            state.entered = None;
+            return;
+        }
+
+        state.skipped = 0;
+        state.entered = Some(Instant::now());
+    }
+
+    #[inline]
+    fn on_profile_scope_exited(&mut self, scope: Scope) {
+        let index = scope.index();
+
+        if let Some(entered) = self.scopes[index].entered.take() {
+            self.shared.scopes[index].record(entered.elapsed());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::event::Subscriber as _;
+
+    #[test]
+    fn sample_rate_test() {
+        let mut subscriber = Subscriber::builder().with_sample_rate(4).build();
+        let handle = subscriber.handle();
+
+        for _ in 0..10 {
+            subscriber.on_profile_scope_entered(Scope::PacketDecrypt);
+            subscriber.on_profile_scope_entered(Scope::FrameProcessing);
+            subscriber.on_profile_scope_exited(Scope::FrameProcessing);
+            subscriber.on_profile_scope_exited(Scope::PacketDecrypt);
+        }
+
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.get(Scope::PacketDecrypt).count, 2);
+        assert_eq!(snapshot.get(Scope::FrameProcessing).count, 2);
+        assert_eq!(snapshot.get(Scope::Transmit).count, 0);
+
+        // exiting a scope which was never entered doesn't record anything
+        subscriber.on_profile_scope_exited(Scope::Timers);
+        assert_eq!(handle.snapshot().get(Scope::Timers).count, 0);
+
+        for (_scope, histogram) in snapshot.iter() {
+            assert_eq!(histogram.buckets.iter().sum::<u64>(), histogram.count);
+        }
+    }
+
+    #[test]
+    fn histogram_test() {
+        let histogram = SharedHistogram::default();
+        assert_eq!(histogram.load().mean(), Duration::ZERO);
+        assert_eq!(histogram.load().percentile(0.5), Duration::ZERO);
+
+        for nanos in [0, 1, 100, 1_000, 1_000_000] {
+            histogram.record(Duration::from_nanos(nanos));
+        }
+        histogram.record(Duration::from_nanos(1 << 40));
+
+        let histogram = histogram.load();
+        assert_eq!(histogram.count, 6);
+        assert_eq!(histogram.buckets[0], 2);
+        assert_eq!(histogram.buckets[6], 1);
+        assert_eq!(histogram.buckets[9], 1);
+        assert_eq!(histogram.buckets[19], 1);
+        assert_eq!(histogram.buckets[40], 1);
+
+        assert_eq!(histogram.percentile(0.0), Duration::from_nanos(2));
+        assert_eq!(histogram.percentile(0.5), Duration::from_nanos(128));
+        assert_eq!(histogram.percentile(0.8), Duration::from_nanos(1 << 20));
+        assert_eq!(histogram.percentile(1.0), Duration::from_nanos(1 << 41));
+    }
+}
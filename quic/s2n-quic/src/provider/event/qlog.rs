@@ -0,0 +1,1008 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes connection events as [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/)
+//! traces
+//!
+//! Each connection gets its own trace, using the JSON-SEQ serialization, which can be loaded
+//! into tools like [qvis](https://qvis.quictools.info/). Only the events with an equivalent in
+//! the qlog QUIC event definitions are written:
+//!
+//! * `transport:packet_sent` and `transport:packet_received`
+//! * `recovery:packet_lost`, `recovery:metrics_updated` and `recovery:congestion_state_updated`
+//! * `connectivity:connection_state_updated` and `connectivity:connection_closed`
+//!
+//! Events are written as they are emitted, so this subscriber is best combined with
+//! [`Sampled`](super::sampling::Sampled) when running with a large number of connections.
+//!
+//! Full JSON traces can grow large on busy servers. [`Format::Binary`] writes a compact
+//! [binary] encoding instead, which can be converted back to JSON-SEQ with
+//! [`binary::convert`]. The size of each trace can be capped with
+//! [`Subscriber::with_max_trace_size`], and [`Directory::with_max_files`] removes the oldest
+//! traces as new ones are created.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use s2n_quic::{
+//!     provider::event::qlog::{Directory, Subscriber},
+//!     Server,
+//! };
+//! use std::error::Error;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let subscriber = Subscriber::new(Directory::new("/var/log/quic"));
+//!
+//! let mut server = Server::builder().with_event(subscriber)?.start()?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+pub mod binary;
+
+use super::{events, replay::json_escape, ConnectionInfo, ConnectionMeta, Timestamp};
+use core::{fmt, fmt::Write as _, time::Duration};
+use std::{
+    collections::VecDeque,
+    fs,
+    fs::File,
+    io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The serialization used for each trace
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Format {
+    /// The qlog JSON-SEQ serialization
+    #[default]
+    Json,
+    /// A compact [binary] encoding which can be converted to JSON-SEQ with [`binary::convert`]
+    Binary,
+}
+
+impl Format {
+    /// Returns the file extension used for traces in the format
+    #[inline]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "sqlog",
+            Self::Binary => "bqlog",
+        }
+    }
+}
+
+/// Opens the writer for each connection's trace
+pub trait Output: 'static + Send {
+    type Writer: 'static + Send + io::Write;
+
+    /// Called when a connection is created
+    ///
+    /// If an error is returned, no events are written for the connection.
+    fn open(&mut self, meta: &ConnectionMeta) -> io::Result<Self::Writer>;
+
+    /// Called when a connection is created, along with the format of its trace
+    ///
+    /// By default, the format is ignored and [`Self::open`] is called.
+    #[inline]
+    fn open_with_format(
+        &mut self,
+        meta: &ConnectionMeta,
+        format: Format,
+    ) -> io::Result<Self::Writer> {
+        let _ = format;
+        self.open(meta)
+    }
+}
+
+impl<F, W> Output for F
+where
+    F: 'static + Send + FnMut(&ConnectionMeta) -> io::Result<W>,
+    W: 'static + Send + io::Write,
+{
+    type Writer = W;
+
+    #[inline]
+    fn open(&mut self, meta: &ConnectionMeta) -> io::Result<Self::Writer> {
+        (self)(meta)
+    }
+}
+
+/// Writes each trace to a new file in a directory
+///
+/// The files are named after the time the connection was created, the endpoint type and the
+/// connection ID, with the [extension](Format::extension) of the trace format.
+#[derive(Clone, Debug)]
+pub struct Directory {
+    path: PathBuf,
+    max_files: Option<usize>,
+    /// The traces created by the output, oldest first, if the number of files is limited
+    files: VecDeque<PathBuf>,
+}
+
+impl Directory {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            max_files: None,
+            files: VecDeque::new(),
+        }
+    }
+
+    /// Limits the number of traces kept in the directory
+    ///
+    /// Once the limit is reached, the oldest trace created by this output is removed each time a
+    /// new trace is created, even if its connection is still open. Files which were already in
+    /// the directory are never removed. At least one trace is always kept.
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = Some(max_files.max(1));
+        self
+    }
+
+    fn create(&mut self, meta: &ConnectionMeta, format: Format) -> io::Result<io::BufWriter<File>> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let name = format!(
+            "{now}-{}-{}.{}",
+            endpoint_type_name(&meta.endpoint_type),
+            meta.id,
+            format.extension()
+        );
+        let path = self.path.join(name);
+
+        if let Some(max_files) = self.max_files {
+            while self.files.len() >= max_files {
+                let Some(oldest) = self.files.pop_front() else {
+                    break;
+                };
+                // the file may have already been removed by someone else
+                let _ = fs::remove_file(oldest);
+            }
+        }
+
+        let file = File::create(&path)?;
+
+        if self.max_files.is_some() {
+            self.files.push_back(path);
+        }
+
+        Ok(io::BufWriter::new(file))
+    }
+}
+
+impl Output for Directory {
+    type Writer = io::BufWriter<File>;
+
+    fn open(&mut self, meta: &ConnectionMeta) -> io::Result<Self::Writer> {
+        self.create(meta, Format::Json)
+    }
+
+    fn open_with_format(
+        &mut self,
+        meta: &ConnectionMeta,
+        format: Format,
+    ) -> io::Result<Self::Writer> {
+        self.create(meta, format)
+    }
+}
+
+pub(super) fn endpoint_type_name(endpoint_type: &events::EndpointType) -> &'static str {
+    if matches!(endpoint_type, events::EndpointType::Server { .. }) {
+        "server"
+    } else {
+        "client"
+    }
+}
+
+/// A qlog event, independent of the format the trace is written in
+#[derive(Clone, Debug, PartialEq)]
+enum Event {
+    PacketSent { header: Header, length: u64 },
+    PacketReceived { header: Header },
+    PacketLost { header: Header, length: u64 },
+    MetricsUpdated(Metrics),
+    CongestionStateUpdated { trigger: Trigger },
+    ConnectionStateUpdated { state: ConnectionState },
+    ConnectionClosed { reason: String },
+}
+
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::PacketSent { .. } => "transport:packet_sent",
+            Self::PacketReceived { .. } => "transport:packet_received",
+            Self::PacketLost { .. } => "recovery:packet_lost",
+            Self::MetricsUpdated(_) => "recovery:metrics_updated",
+            Self::CongestionStateUpdated { .. } => "recovery:congestion_state_updated",
+            Self::ConnectionStateUpdated { .. } => "connectivity:connection_state_updated",
+            Self::ConnectionClosed { .. } => "connectivity:connection_closed",
+        }
+    }
+
+    /// Writes the members of the event's JSON data object
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Self::PacketSent { header, length } | Self::PacketLost { header, length } => {
+                header.write_json(out);
+                let _ = write!(out, r#","raw":{{"length":{length}}}"#);
+            }
+            Self::PacketReceived { header } => {
+                header.write_json(out);
+            }
+            Self::MetricsUpdated(metrics) => {
+                let _ = write!(
+                    out,
+                    r#""min_rtt":{},"smoothed_rtt":{},"latest_rtt":{},"rtt_variance":{},"pto_count":{},"congestion_window":{},"bytes_in_flight":{}"#,
+                    millis(metrics.min_rtt),
+                    millis(metrics.smoothed_rtt),
+                    millis(metrics.latest_rtt),
+                    millis(metrics.rtt_variance),
+                    metrics.pto_count,
+                    metrics.congestion_window,
+                    metrics.bytes_in_flight,
+                );
+            }
+            Self::CongestionStateUpdated { trigger } => {
+                let _ = write!(
+                    out,
+                    r#""old":"slow_start","new":"congestion_avoidance","trigger":"{}""#,
+                    trigger.as_str()
+                );
+            }
+            Self::ConnectionStateUpdated { state } => {
+                let _ = write!(out, r#""new":"{}""#, state.as_str());
+            }
+            Self::ConnectionClosed { reason } => {
+                out.push_str(r#""reason":""#);
+                json_escape(out, reason);
+                out.push('"');
+            }
+        }
+    }
+}
+
+/// Defines a field-less enum which can be converted to and from its index in `ALL`
+macro_rules! indexed_enum {
+    ($(#[$meta:meta])* enum $name:ident { $($variant:ident => $str:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
+            #[inline]
+            fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $str,)*
+                }
+            }
+
+            #[inline]
+            fn from_index(index: u8) -> Option<Self> {
+                Self::ALL.get(index as usize).copied()
+            }
+        }
+    };
+}
+
+indexed_enum!(
+    enum PacketType {
+        Initial => "initial",
+        Handshake => "handshake",
+        ZeroRtt => "0RTT",
+        OneRtt => "1RTT",
+        Retry => "retry",
+        VersionNegotiation => "version_negotiation",
+        StatelessReset => "stateless_reset",
+        Unknown => "unknown",
+    }
+);
+
+impl PacketType {
+    /// Returns `true` if packets of the type carry a packet number
+    #[inline]
+    fn has_number(self) -> bool {
+        matches!(
+            self,
+            Self::Initial | Self::Handshake | Self::ZeroRtt | Self::OneRtt
+        )
+    }
+}
+
+indexed_enum!(
+    enum Trigger {
+        PacketLoss => "packet_loss",
+        Ecn => "ecn",
+        Rtt => "rtt",
+        Other => "other",
+    }
+);
+
+indexed_enum!(
+    enum ConnectionState {
+        HandshakeComplete => "handshake_complete",
+        HandshakeConfirmed => "handshake_confirmed",
+    }
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Header {
+    packet_type: PacketType,
+    /// The packet number, which is only set if the packet type has one
+    number: Option<u64>,
+}
+
+impl Header {
+    fn write_json(&self, out: &mut String) {
+        let _ = write!(
+            out,
+            r#""header":{{"packet_type":"{}""#,
+            self.packet_type.as_str()
+        );
+        if let Some(number) = self.number {
+            let _ = write!(out, r#","packet_number":{number}"#);
+        }
+        out.push('}');
+    }
+}
+
+impl From<&events::PacketHeader> for Header {
+    fn from(header: &events::PacketHeader) -> Self {
+        use events::PacketHeader::*;
+
+        let (packet_type, number) = match header {
+            Initial { number, .. } => (PacketType::Initial, Some(*number)),
+            Handshake { number, .. } => (PacketType::Handshake, Some(*number)),
+            ZeroRtt { number, .. } => (PacketType::ZeroRtt, Some(*number)),
+            OneRtt { number, .. } => (PacketType::OneRtt, Some(*number)),
+            Retry { .. } => (PacketType::Retry, None),
+            VersionNegotiation { .. } => (PacketType::VersionNegotiation, None),
+            StatelessReset { .. } => (PacketType::StatelessReset, None),
+            _ => (PacketType::Unknown, None),
+        };
+
+        Self {
+            packet_type,
+            number,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Metrics {
+    min_rtt: Duration,
+    smoothed_rtt: Duration,
+    latest_rtt: Duration,
+    rtt_variance: Duration,
+    pto_count: u32,
+    congestion_window: u32,
+    bytes_in_flight: u32,
+}
+
+/// Writes the JSON-SEQ record which starts each trace
+fn write_json_header(out: &mut String, endpoint_type: &str, id: u64) {
+    // each JSON-SEQ record starts with the record separator character
+    out.push('\u{1e}');
+    out.push_str(r#"{"qlog_version":"0.3","qlog_format":"JSON-SEQ","title":"s2n-quic","trace":{"vantage_point":{"type":""#);
+    out.push_str(endpoint_type);
+    let _ = write!(
+        out,
+        r#""}},"common_fields":{{"group_id":"{id}","time_format":"relative"}}}}}}"#
+    );
+    out.push('\n');
+}
+
+/// Writes the JSON-SEQ record for a single event
+fn write_json_record(out: &mut String, time: Duration, event: &Event) {
+    out.push('\u{1e}');
+    let _ = write!(
+        out,
+        r#"{{"time":{},"name":"{}","data":{{"#,
+        millis(time),
+        event.name()
+    );
+    event.write_json(out);
+    out.push_str("}}\n");
+}
+
+/// Serializes the records of a trace
+enum Encoder {
+    Json(String),
+    Binary(binary::Encoder),
+}
+
+impl Encoder {
+    fn new(format: Format) -> Self {
+        match format {
+            Format::Json => Self::Json(String::new()),
+            Format::Binary => Self::Binary(binary::Encoder::default()),
+        }
+    }
+
+    fn header(&mut self, meta: &ConnectionMeta) {
+        match self {
+            Self::Json(out) => {
+                write_json_header(out, endpoint_type_name(&meta.endpoint_type), meta.id)
+            }
+            Self::Binary(encoder) => encoder.header(
+                matches!(meta.endpoint_type, events::EndpointType::Server { .. }),
+                meta.id,
+            ),
+        }
+    }
+
+    fn record(&mut self, time: Duration, event: &Event) {
+        match self {
+            Self::Json(out) => write_json_record(out, time, event),
+            Self::Binary(encoder) => encoder.record(time, event),
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Json(out) => out.as_bytes(),
+            Self::Binary(encoder) => encoder.as_bytes(),
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Self::Json(out) => out.clear(),
+            Self::Binary(encoder) => encoder.clear(),
+        }
+    }
+}
+
+/// The per-connection state of the [`Subscriber`]
+pub struct Context<W> {
+    /// The writer for the trace, or `None` if it failed or the connection closed
+    writer: Option<W>,
+    start: Timestamp,
+    encoder: Encoder,
+    /// The number of bytes which can still be written before the trace reaches its size cap
+    remaining: u64,
+}
+
+impl<W> fmt::Debug for Context<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("is_open", &self.writer.is_some())
+            .field("start", &self.start)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+impl<W: io::Write> Context<W> {
+    fn new(meta: &ConnectionMeta, writer: Option<W>, format: Format, max_size: u64) -> Self {
+        let mut context = Self {
+            writer,
+            start: meta.timestamp,
+            encoder: Encoder::new(format),
+            remaining: max_size,
+        };
+
+        context.encoder.header(meta);
+        context.flush_record();
+
+        context
+    }
+
+    /// Writes a single event record
+    #[inline]
+    fn write(&mut self, meta: &ConnectionMeta, event: Event) {
+        if self.writer.is_none() {
+            return;
+        }
+
+        let time = meta.timestamp.saturating_duration_since(self.start);
+        self.encoder.record(time, &event);
+        self.flush_record();
+    }
+
+    fn flush_record(&mut self) {
+        let record = self.encoder.as_bytes();
+        let len = record.len() as u64;
+
+        if let Some(writer) = self.writer.as_mut() {
+            if len > self.remaining {
+                // end the trace on the last complete record once it reaches the size cap
+                let _ = writer.flush();
+                self.writer = None;
+            } else if writer.write_all(record).is_err() {
+                // stop writing events for the connection rather than produce a corrupt trace
+                self.writer = None;
+            } else {
+                self.remaining -= len;
+            }
+        }
+
+        self.encoder.clear();
+    }
+
+    fn close(&mut self) {
+        if let Some(mut writer) = self.writer.take() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+#[inline]
+fn millis(duration: Duration) -> f64 {
+    duration.as_micros() as f64 / 1000.0
+}
+
+/// Writes a qlog trace for each connection
+pub struct Subscriber<O: Output> {
+    output: O,
+    format: Format,
+    max_trace_size: u64,
+}
+
+impl<O: Output> fmt::Debug for Subscriber<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Subscriber")
+            .field("format", &self.format)
+            .field("max_trace_size", &self.max_trace_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<O: Output> Subscriber<O> {
+    pub fn new(output: O) -> Self {
+        Self {
+            output,
+            format: Format::Json,
+            max_trace_size: u64::MAX,
+        }
+    }
+
+    /// Sets the format the traces are written in
+    ///
+    /// Defaults to [`Format::Json`].
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Caps the size of each connection's trace, in bytes
+    ///
+    /// Once writing an event would exceed the cap, the trace is ended after the last complete
+    /// record and no more events are written for the connection. Traces are uncapped by default.
+    pub fn with_max_trace_size(mut self, max_trace_size: u64) -> Self {
+        self.max_trace_size = max_trace_size;
+        self
+    }
+}
+
+impl<O: Output> super::Subscriber for Subscriber<O> {
+    type ConnectionContext = Context<O::Writer>;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let writer = self.output.open_with_format(meta, self.format).ok();
+        Context::new(meta, writer, self.format, self.max_trace_size)
+    }
+
+    #[inline]
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        context.write(
+            meta,
+            Event::PacketSent {
+                header: (&event.packet_header).into(),
+                length: event.packet_len as u64,
+            },
+        );
+    }
+
+    #[inline]
+    fn on_packet_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketReceived,
+    ) {
+        context.write(
+            meta,
+            Event::PacketReceived {
+                header: (&event.packet_header).into(),
+            },
+        );
+    }
+
+    #[inline]
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketLost,
+    ) {
+        context.write(
+            meta,
+            Event::PacketLost {
+                header: (&event.packet_header).into(),
+                length: event.bytes_lost as u64,
+            },
+        );
+    }
+
+    #[inline]
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        context.write(
+            meta,
+            Event::MetricsUpdated(Metrics {
+                min_rtt: event.min_rtt,
+                smoothed_rtt: event.smoothed_rtt,
+                latest_rtt: event.latest_rtt,
+                rtt_variance: event.rtt_variance,
+                pto_count: event.pto_count,
+                congestion_window: event.congestion_window,
+                bytes_in_flight: event.bytes_in_flight,
+            }),
+        );
+    }
+
+    #[inline]
+    fn on_slow_start_exited(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::SlowStartExited,
+    ) {
+        use events::SlowStartExitCause::*;
+
+        let trigger = match event.cause {
+            PacketLoss { .. } => Trigger::PacketLoss,
+            Ecn { .. } => Trigger::Ecn,
+            Rtt { .. } => Trigger::Rtt,
+            _ => Trigger::Other,
+        };
+
+        context.write(meta, Event::CongestionStateUpdated { trigger });
+    }
+
+    #[inline]
+    fn on_handshake_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::HandshakeStatusUpdated,
+    ) {
+        use events::HandshakeStatus::*;
+
+        let state = match event.status {
+            Complete { .. } => ConnectionState::HandshakeComplete,
+            Confirmed { .. } => ConnectionState::HandshakeConfirmed,
+            // the remaining statuses don't have an equivalent state
+            _ => return,
+        };
+
+        context.write(meta, Event::ConnectionStateUpdated { state });
+    }
+
+    #[inline]
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ConnectionClosed,
+    ) {
+        context.write(
+            meta,
+            Event::ConnectionClosed {
+                reason: event.error.to_string(),
+            },
+        );
+
+        // no more events will be written for the connection
+        context.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        endpoint,
+        event::{builder, IntoEvent},
+        inet::SocketAddress,
+        time::{testing::Clock, Clock as _},
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// Writes into a shared buffer so the trace can be inspected
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Buffer {
+        fn take(&self) -> Vec<u8> {
+            core::mem::take(&mut self.0.lock().unwrap())
+        }
+    }
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn meta(delay: Duration) -> ConnectionMeta {
+        let mut clock = Clock::default();
+        clock.inc_by(delay);
+        builder::ConnectionMeta {
+            endpoint_type: endpoint::Type::Client,
+            id: 3,
+            attempt_id: 0,
+            timestamp: clock.get_time(),
+            application_context: Default::default(),
+        }
+        .into_event()
+    }
+
+    #[test]
+    fn trace_test() {
+        use crate::provider::event::Subscriber as _;
+
+        let buffer = Buffer::default();
+        let output = {
+            let buffer = buffer.clone();
+            move |_meta: &ConnectionMeta| Ok::<_, io::Error>(buffer.clone())
+        };
+        let mut subscriber = Subscriber::new(output);
+
+        let start = meta(Duration::ZERO);
+        let remote_address = SocketAddress::default();
+        let info = builder::ConnectionInfo {
+            remote_address: (&remote_address).into_event(),
+            server_name: None,
+        }
+        .into_event();
+        let mut context = subscriber.create_connection_context(&start, &info);
+
+        let event: events::PacketSent = builder::PacketSent {
+            packet_header: builder::PacketHeader::OneRtt { number: 7 },
+            packet_len: 1200,
+        }
+        .into_event();
+        subscriber.on_packet_sent(&mut context, &meta(Duration::from_micros(1500)), &event);
+
+        let event: events::ConnectionClosed = builder::ConnectionClosed {
+            error: s2n_quic_core::connection::Error::idle_timer_expired(),
+        }
+        .into_event();
+        subscriber.on_connection_closed(&mut context, &meta(Duration::from_millis(2)), &event);
+
+        // events after the close are ignored
+        let event: events::PacketReceived = builder::PacketReceived {
+            packet_header: builder::PacketHeader::OneRtt { number: 8 },
+        }
+        .into_event();
+        subscriber.on_packet_received(&mut context, &meta(Duration::from_millis(3)), &event);
+
+        let out = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let records: Vec<_> = out.split('\u{1e}').skip(1).collect();
+        assert_eq!(records.len(), 3, "{out}");
+        assert!(records[0].contains(r#""vantage_point":{"type":"client"}"#));
+        assert!(records[0].contains(r#""group_id":"3""#));
+        assert_eq!(
+            records[1],
+            "{\"time\":1.5,\"name\":\"transport:packet_sent\",\"data\":{\"header\":{\"packet_type\":\"1RTT\",\"packet_number\":7},\"raw\":{\"length\":1200}}}\n"
+        );
+        assert!(records[2].starts_with(r#"{"time":2,"name":"connectivity:connection_closed""#));
+    }
+
+    /// Emits one of each of the supported events, along with repeated packets and metrics
+    fn emit_events<O: Output>(subscriber: &mut Subscriber<O>) {
+        use crate::provider::event::Subscriber as _;
+
+        let remote_address = SocketAddress::default();
+        let info = builder::ConnectionInfo {
+            remote_address: (&remote_address).into_event(),
+            server_name: None,
+        }
+        .into_event();
+        let mut context = subscriber.create_connection_context(&meta(Duration::ZERO), &info);
+
+        let event: events::HandshakeStatusUpdated = builder::HandshakeStatusUpdated {
+            status: builder::HandshakeStatus::Complete,
+        }
+        .into_event();
+        subscriber.on_handshake_status_updated(
+            &mut context,
+            &meta(Duration::from_micros(250)),
+            &event,
+        );
+
+        for (i, number) in [10, 11, 12, 9, 13].into_iter().enumerate() {
+            let time = Duration::from_millis(i as u64 + 1);
+            let event: events::PacketSent = builder::PacketSent {
+                packet_header: builder::PacketHeader::OneRtt { number },
+                packet_len: 1200,
+            }
+            .into_event();
+            subscriber.on_packet_sent(&mut context, &meta(time), &event);
+
+            let event: events::PacketReceived = builder::PacketReceived {
+                packet_header: builder::PacketHeader::Handshake { number, version: 1 },
+            }
+            .into_event();
+            subscriber.on_packet_received(&mut context, &meta(time), &event);
+
+            let path = builder::Path {
+                local_addr: (&remote_address).into_event(),
+                local_cid: builder::ConnectionId { bytes: &[] },
+                remote_addr: (&remote_address).into_event(),
+                remote_cid: builder::ConnectionId { bytes: &[] },
+                id: 0,
+                is_active: true,
+            };
+            let event: events::RecoveryMetrics = builder::RecoveryMetrics {
+                path,
+                min_rtt: Duration::from_millis(10),
+                smoothed_rtt: Duration::from_micros(12_345),
+                latest_rtt: Duration::from_millis(10 + i as u64),
+                rtt_variance: Duration::from_millis(3),
+                max_ack_delay: Duration::from_millis(25),
+                pto_count: 0,
+                congestion_window: 12_000,
+                bytes_in_flight: 1200 * (i as u32 + 1),
+                congestion_limited: false,
+            }
+            .into_event();
+            subscriber.on_recovery_metrics(&mut context, &meta(time), &event);
+        }
+
+        let event: events::PacketSent = builder::PacketSent {
+            packet_header: builder::PacketHeader::Retry { version: 1 },
+            packet_len: 100,
+        }
+        .into_event();
+        subscriber.on_packet_sent(&mut context, &meta(Duration::from_millis(7)), &event);
+
+        let event: events::SlowStartExited = builder::SlowStartExited {
+            path_id: 0,
+            cause: builder::SlowStartExitCause::Rtt,
+            congestion_window: 12_000,
+        }
+        .into_event();
+        subscriber.on_slow_start_exited(&mut context, &meta(Duration::from_millis(8)), &event);
+
+        let event: events::ConnectionClosed = builder::ConnectionClosed {
+            error: s2n_quic_core::connection::Error::idle_timer_expired(),
+        }
+        .into_event();
+        subscriber.on_connection_closed(&mut context, &meta(Duration::from_millis(9)), &event);
+    }
+
+    fn record_trace(format: Format, max_trace_size: u64) -> Vec<u8> {
+        let buffer = Buffer::default();
+        let output = {
+            let buffer = buffer.clone();
+            move |_meta: &ConnectionMeta| Ok::<_, io::Error>(buffer.clone())
+        };
+        let mut subscriber = Subscriber::new(output)
+            .with_format(format)
+            .with_max_trace_size(max_trace_size);
+        emit_events(&mut subscriber);
+
+        buffer.take()
+    }
+
+    #[test]
+    fn binary_conversion_test() {
+        let json = record_trace(Format::Json, u64::MAX);
+        let binary = record_trace(Format::Binary, u64::MAX);
+
+        // the binary trace converts to exactly the same records as the JSON trace
+        let mut converted = Vec::new();
+        binary::convert(&binary[..], &mut converted).unwrap();
+        assert_eq!(
+            String::from_utf8(converted).unwrap(),
+            String::from_utf8(json.clone()).unwrap()
+        );
+
+        assert!(
+            binary.len() * 5 < json.len(),
+            "binary: {}, json: {}",
+            binary.len(),
+            json.len()
+        );
+    }
+
+    #[test]
+    fn binary_truncated_test() {
+        let json = String::from_utf8(record_trace(Format::Json, u64::MAX)).unwrap();
+        let binary = record_trace(Format::Binary, u64::MAX);
+
+        // the records before the truncated record are still converted
+        let mut converted = Vec::new();
+        let error = binary::convert(&binary[..binary.len() - 1], &mut converted).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        let converted = String::from_utf8(converted).unwrap();
+        let last_record = json.rfind('\u{1e}').unwrap();
+        assert_eq!(converted, json[..last_record]);
+
+        let error = binary::convert(&b"not a trace"[..], &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn max_trace_size_test() {
+        for format in [Format::Json, Format::Binary] {
+            let full = record_trace(format, u64::MAX);
+
+            for max_trace_size in [0, 10, 100, 500, full.len() as u64 - 1] {
+                let trace = record_trace(format, max_trace_size);
+                assert!(trace.len() as u64 <= max_trace_size);
+                assert!(full.starts_with(&trace));
+
+                // the trace ends on a complete record
+                if !trace.is_empty() {
+                    match format {
+                        Format::Json => assert!(trace.ends_with(b"\n")),
+                        Format::Binary => binary::convert(&trace[..], io::sink()).unwrap(),
+                    }
+                }
+            }
+
+            assert_eq!(record_trace(format, full.len() as u64), full);
+        }
+    }
+
+    #[test]
+    fn directory_rotation_test() {
+        let path = std::env::temp_dir().join(format!("s2n-quic-qlog-{}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+
+        let mut directory = Directory::new(&path).with_max_files(2);
+        for id in 0..5 {
+            let mut meta = meta(Duration::ZERO);
+            meta.id = id;
+            let format = if id % 2 == 0 {
+                Format::Json
+            } else {
+                Format::Binary
+            };
+            directory.open_with_format(&meta, format).unwrap();
+        }
+
+        let mut files: Vec<_> = fs::read_dir(&path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        fs::remove_dir_all(&path).unwrap();
+
+        // only the two most recent traces are kept
+        assert_eq!(files.len(), 2, "{files:?}");
+        assert!(files.iter().any(|name| name.ends_with("-client-3.bqlog")));
+        assert!(files.iter().any(|name| name.ends_with("-client-4.sqlog")));
+    }
+}
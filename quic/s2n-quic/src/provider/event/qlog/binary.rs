@@ -0,0 +1,401 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A compact binary encoding of qlog traces
+//!
+//! A trace starts with [`MAGIC`], the format version, the endpoint type and the connection ID,
+//! followed by a record for each event. Every integer is written as an unsigned LEB128 varint,
+//! and the information which repeats between records of a connection is elided by encoding it
+//! relative to the previous record:
+//!
+//! * event times are the delta from the previous event
+//! * packet numbers are the zigzag-encoded delta from the previous packet number of the same
+//!   packet type
+//! * `recovery:metrics_updated` only includes the metrics which changed
+//!
+//! Traces can be converted to the qlog JSON-SEQ serialization with [`convert`].
+
+use super::{
+    write_json_header, write_json_record, ConnectionState, Event, Header, Metrics, PacketType,
+    Trigger,
+};
+use core::time::Duration;
+use std::io;
+
+/// The bytes at the start of each binary trace
+pub const MAGIC: [u8; 4] = *b"QLGB";
+
+const VERSION: u8 = 1;
+
+mod tag {
+    pub const PACKET_SENT: u8 = 0;
+    pub const PACKET_RECEIVED: u8 = 1;
+    pub const PACKET_LOST: u8 = 2;
+    pub const METRICS_UPDATED: u8 = 3;
+    pub const CONGESTION_STATE_UPDATED: u8 = 4;
+    pub const CONNECTION_STATE_UPDATED: u8 = 5;
+    pub const CONNECTION_CLOSED: u8 = 6;
+}
+
+mod metric {
+    pub const MIN_RTT: u8 = 1 << 0;
+    pub const SMOOTHED_RTT: u8 = 1 << 1;
+    pub const LATEST_RTT: u8 = 1 << 2;
+    pub const RTT_VARIANCE: u8 = 1 << 3;
+    pub const PTO_COUNT: u8 = 1 << 4;
+    pub const CONGESTION_WINDOW: u8 = 1 << 5;
+    pub const BYTES_IN_FLIGHT: u8 = 1 << 6;
+}
+
+/// The values of the previous record which the next record is encoded relative to
+#[derive(Debug)]
+struct Previous {
+    /// The time of the previous event, in microseconds
+    time: u64,
+    /// The previous packet number of each packet type
+    packet_numbers: [u64; PacketType::ALL.len()],
+    metrics: Metrics,
+}
+
+impl Default for Previous {
+    fn default() -> Self {
+        Self {
+            time: 0,
+            packet_numbers: [0; PacketType::ALL.len()],
+            metrics: Metrics::default(),
+        }
+    }
+}
+
+/// Encodes the records of a single trace
+#[derive(Debug, Default)]
+pub(super) struct Encoder {
+    buffer: Vec<u8>,
+    previous: Previous,
+}
+
+impl Encoder {
+    pub(super) fn header(&mut self, is_server: bool, id: u64) {
+        self.buffer.extend_from_slice(&MAGIC);
+        self.buffer.push(VERSION);
+        self.buffer.push(is_server as u8);
+        write_varint(&mut self.buffer, id);
+    }
+
+    pub(super) fn record(&mut self, time: Duration, event: &Event) {
+        let out = &mut self.buffer;
+        let previous = &mut self.previous;
+
+        let tag = match event {
+            Event::PacketSent { .. } => tag::PACKET_SENT,
+            Event::PacketReceived { .. } => tag::PACKET_RECEIVED,
+            Event::PacketLost { .. } => tag::PACKET_LOST,
+            Event::MetricsUpdated(_) => tag::METRICS_UPDATED,
+            Event::CongestionStateUpdated { .. } => tag::CONGESTION_STATE_UPDATED,
+            Event::ConnectionStateUpdated { .. } => tag::CONNECTION_STATE_UPDATED,
+            Event::ConnectionClosed { .. } => tag::CONNECTION_CLOSED,
+        };
+        out.push(tag);
+
+        // events are emitted in order so the delta is never negative
+        let time = micros(time);
+        let delta = time.saturating_sub(previous.time);
+        write_varint(out, delta);
+        previous.time += delta;
+
+        match event {
+            Event::PacketSent { header, length } | Event::PacketLost { header, length } => {
+                write_header(out, previous, header);
+                write_varint(out, *length);
+            }
+            Event::PacketReceived { header } => {
+                write_header(out, previous, header);
+            }
+            Event::MetricsUpdated(metrics) => {
+                let prev = core::mem::replace(&mut previous.metrics, *metrics);
+                let mask_index = out.len();
+                out.push(0);
+                let mut mask = 0;
+
+                let durations = [
+                    (metric::MIN_RTT, metrics.min_rtt, prev.min_rtt),
+                    (
+                        metric::SMOOTHED_RTT,
+                        metrics.smoothed_rtt,
+                        prev.smoothed_rtt,
+                    ),
+                    (metric::LATEST_RTT, metrics.latest_rtt, prev.latest_rtt),
+                    (
+                        metric::RTT_VARIANCE,
+                        metrics.rtt_variance,
+                        prev.rtt_variance,
+                    ),
+                ];
+                for (bit, value, prev) in durations {
+                    if value != prev {
+                        mask |= bit;
+                        write_varint(out, micros(value));
+                    }
+                }
+
+                let counts = [
+                    (metric::PTO_COUNT, metrics.pto_count, prev.pto_count),
+                    (
+                        metric::CONGESTION_WINDOW,
+                        metrics.congestion_window,
+                        prev.congestion_window,
+                    ),
+                    (
+                        metric::BYTES_IN_FLIGHT,
+                        metrics.bytes_in_flight,
+                        prev.bytes_in_flight,
+                    ),
+                ];
+                for (bit, value, prev) in counts {
+                    if value != prev {
+                        mask |= bit;
+                        write_varint(out, value as u64);
+                    }
+                }
+
+                out[mask_index] = mask;
+            }
+            Event::CongestionStateUpdated { trigger } => {
+                out.push(*trigger as u8);
+            }
+            Event::ConnectionStateUpdated { state } => {
+                out.push(*state as u8);
+            }
+            Event::ConnectionClosed { reason } => {
+                write_varint(out, reason.len() as u64);
+                out.extend_from_slice(reason.as_bytes());
+            }
+        }
+    }
+
+    #[inline]
+    pub(super) fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    #[inline]
+    pub(super) fn clear(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+#[inline]
+fn micros(duration: Duration) -> u64 {
+    duration.as_micros().try_into().unwrap_or(u64::MAX)
+}
+
+fn write_header(out: &mut Vec<u8>, previous: &mut Previous, header: &Header) {
+    out.push(header.packet_type as u8);
+
+    if let Some(number) = header.number {
+        let prev = &mut previous.packet_numbers[header.packet_type as usize];
+        let delta = number.wrapping_sub(*prev) as i64;
+        // zigzag encode the delta so small decreases are still small
+        write_varint(out, ((delta << 1) ^ (delta >> 63)) as u64);
+        *prev = number;
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Decodes the records of a single trace
+struct Decoder<'a> {
+    buffer: &'a [u8],
+    previous: Previous,
+}
+
+impl<'a> Decoder<'a> {
+    fn u8(&mut self) -> io::Result<u8> {
+        let (value, rest) = self.buffer.split_first().ok_or_else(truncated)?;
+        self.buffer = rest;
+        Ok(*value)
+    }
+
+    fn varint(&mut self) -> io::Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("varint is too long"))
+    }
+
+    fn bytes(&mut self, len: u64) -> io::Result<&'a [u8]> {
+        let len = usize::try_from(len).map_err(|_| truncated())?;
+        if len > self.buffer.len() {
+            return Err(truncated());
+        }
+        let (value, rest) = self.buffer.split_at(len);
+        self.buffer = rest;
+        Ok(value)
+    }
+
+    fn header(&mut self) -> io::Result<Header> {
+        let packet_type =
+            PacketType::from_index(self.u8()?).ok_or_else(|| invalid("unknown packet type"))?;
+
+        let number = if packet_type.has_number() {
+            let value = self.varint()?;
+            let delta = ((value >> 1) as i64) ^ -((value & 1) as i64);
+            let prev = &mut self.previous.packet_numbers[packet_type as usize];
+            *prev = prev.wrapping_add(delta as u64);
+            Some(*prev)
+        } else {
+            None
+        };
+
+        Ok(Header {
+            packet_type,
+            number,
+        })
+    }
+
+    fn metrics(&mut self) -> io::Result<Metrics> {
+        let mask = self.u8()?;
+        let mut metrics = self.previous.metrics;
+
+        for (bit, value) in [
+            (metric::MIN_RTT, &mut metrics.min_rtt),
+            (metric::SMOOTHED_RTT, &mut metrics.smoothed_rtt),
+            (metric::LATEST_RTT, &mut metrics.latest_rtt),
+            (metric::RTT_VARIANCE, &mut metrics.rtt_variance),
+        ] {
+            if mask & bit != 0 {
+                *value = Duration::from_micros(self.varint()?);
+            }
+        }
+
+        for (bit, value) in [
+            (metric::PTO_COUNT, &mut metrics.pto_count),
+            (metric::CONGESTION_WINDOW, &mut metrics.congestion_window),
+            (metric::BYTES_IN_FLIGHT, &mut metrics.bytes_in_flight),
+        ] {
+            if mask & bit != 0 {
+                *value = self
+                    .varint()?
+                    .try_into()
+                    .map_err(|_| invalid("metric is out of range"))?;
+            }
+        }
+
+        self.previous.metrics = metrics;
+        Ok(metrics)
+    }
+
+    /// Returns the next record, or `None` at the end of the trace
+    fn record(&mut self) -> io::Result<Option<(Duration, Event)>> {
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        let tag = self.u8()?;
+        let delta = self.varint()?;
+        self.previous.time = self.previous.time.saturating_add(delta);
+        let time = Duration::from_micros(self.previous.time);
+
+        let event = match tag {
+            tag::PACKET_SENT => Event::PacketSent {
+                header: self.header()?,
+                length: self.varint()?,
+            },
+            tag::PACKET_RECEIVED => Event::PacketReceived {
+                header: self.header()?,
+            },
+            tag::PACKET_LOST => Event::PacketLost {
+                header: self.header()?,
+                length: self.varint()?,
+            },
+            tag::METRICS_UPDATED => Event::MetricsUpdated(self.metrics()?),
+            tag::CONGESTION_STATE_UPDATED => Event::CongestionStateUpdated {
+                trigger: Trigger::from_index(self.u8()?)
+                    .ok_or_else(|| invalid("unknown congestion state trigger"))?,
+            },
+            tag::CONNECTION_STATE_UPDATED => Event::ConnectionStateUpdated {
+                state: ConnectionState::from_index(self.u8()?)
+                    .ok_or_else(|| invalid("unknown connection state"))?,
+            },
+            tag::CONNECTION_CLOSED => {
+                let len = self.varint()?;
+                let reason = core::str::from_utf8(self.bytes(len)?)
+                    .map_err(|_| invalid("close reason is not valid UTF-8"))?;
+                Event::ConnectionClosed {
+                    reason: reason.to_string(),
+                }
+            }
+            _ => return Err(invalid("unknown event")),
+        };
+
+        Ok(Some((time, event)))
+    }
+}
+
+fn truncated() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "binary qlog trace is truncated",
+    )
+}
+
+fn invalid(reason: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+/// Converts a binary trace into the qlog JSON-SEQ serialization
+///
+/// If the trace is truncated or corrupt, the records which were decoded before the error are
+/// still written to `output` before the error is returned.
+pub fn convert<R: io::Read, W: io::Write>(mut input: R, mut output: W) -> io::Result<()> {
+    let mut trace = Vec::new();
+    input.read_to_end(&mut trace)?;
+
+    let mut decoder = Decoder {
+        buffer: &trace,
+        previous: Previous::default(),
+    };
+
+    if decoder.bytes(MAGIC.len() as u64)? != MAGIC {
+        return Err(invalid("not a binary qlog trace"));
+    }
+    if decoder.u8()? != VERSION {
+        return Err(invalid("unsupported binary qlog version"));
+    }
+    let endpoint_type = if decoder.u8()? == 0 {
+        "client"
+    } else {
+        "server"
+    };
+    let id = decoder.varint()?;
+
+    let mut out = String::new();
+    write_json_header(&mut out, endpoint_type, id);
+    output.write_all(out.as_bytes())?;
+
+    let result = loop {
+        match decoder.record() {
+            Ok(Some((time, event))) => {
+                out.clear();
+                write_json_record(&mut out, time, &event);
+                output.write_all(out.as_bytes())?;
+            }
+            Ok(None) => break Ok(()),
+            Err(error) => break Err(error),
+        }
+    };
+
+    output.flush()?;
+    result
+}
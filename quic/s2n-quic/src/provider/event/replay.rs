@@ -0,0 +1,720 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records recent events for each connection and dumps them when the connection fails
+//!
+//! Rare failures in production are often impossible to reproduce, and recording every event for
+//! every connection is too expensive. The [`Subscriber`] in this module keeps the most recent
+//! events for each connection in a bounded ring buffer, which is discarded when the connection
+//! closes, unless the [`Policy`] decides the close warrants a dump. The recorded events are then
+//! passed to a [`Sink`], which can write them out as [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/)
+//! or a compact binary format.
+//!
+//! Each event is formatted when it's recorded, so this subscriber is more expensive than one which
+//! only counts events. It can be combined with [`Sampled`](super::sampling::Sampled) to limit the
+//! number of connections being recorded.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use s2n_quic::{
+//!     provider::event::replay::{Directory, Format, Subscriber, Trigger},
+//!     Server,
+//! };
+//! use std::error::Error;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let subscriber = Subscriber::builder(Directory::new("/var/log/quic", Format::Qlog))
+//!     .with_max_records(10_000)?
+//!     .with_policy(Trigger::OnUnexpectedClose)
+//!     .build();
+//!
+//! let mut server = Server::builder().with_event(subscriber)?.start()?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use super::{events, ConnectionInfo, ConnectionMeta, Event, Timestamp};
+use core::{fmt, fmt::Write as _, time::Duration};
+use s2n_quic_core::connection;
+use std::{
+    collections::VecDeque,
+    io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The default maximum number of events recorded for each connection
+const DEFAULT_MAX_RECORDS: usize = 1000;
+
+/// The default maximum number of formatted event bytes recorded for each connection
+const DEFAULT_MAX_BYTES: usize = 256 * 1024;
+
+/// Identifies the binary format
+const BINARY_MAGIC: &[u8] = b"s2nqrply";
+
+/// The version of the binary format
+const BINARY_VERSION: u8 = 1;
+
+/// Decides if the events for a closed connection should be dumped
+pub trait Policy: 'static + Send {
+    /// Called with the error the connection was closed with
+    fn should_dump(&mut self, error: &connection::Error) -> bool;
+}
+
+impl<F> Policy for F
+where
+    F: 'static + Send + FnMut(&connection::Error) -> bool,
+{
+    #[inline]
+    fn should_dump(&mut self, error: &connection::Error) -> bool {
+        (self)(error)
+    }
+}
+
+/// Built-in policies for dumping connection events
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Trigger {
+    /// Dumps the events of every connection
+    Always,
+    /// Dumps the events of connections which weren't closed gracefully
+    OnError,
+    /// Dumps the events of connections which were closed for reasons other than the
+    /// application closing them, the endpoint shutting down, or the idle timer expiring
+    #[default]
+    OnUnexpectedClose,
+}
+
+impl Policy for Trigger {
+    #[inline]
+    fn should_dump(&mut self, error: &connection::Error) -> bool {
+        use connection::Error;
+
+        match self {
+            Self::Always => true,
+            Self::OnError => !matches!(error, Error::Closed { .. }),
+            Self::OnUnexpectedClose => !matches!(
+                error,
+                Error::Closed { .. }
+                    | Error::Application { .. }
+                    | Error::IdleTimerExpired { .. }
+                    | Error::EndpointClosing { .. }
+            ),
+        }
+    }
+}
+
+/// Receives the events of connections selected by the [`Policy`]
+pub trait Sink: 'static + Send {
+    fn on_dump(&mut self, log: &Log);
+}
+
+impl<F> Sink for F
+where
+    F: 'static + Send + FnMut(&Log),
+{
+    #[inline]
+    fn on_dump(&mut self, log: &Log) {
+        (self)(log)
+    }
+}
+
+/// The format of dumped connection events
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// A qlog trace, using the JSON-SEQ serialization
+    Qlog,
+    /// A compact binary encoding
+    ///
+    /// The encoding starts with the magic bytes `s2nqrply` and a version byte, followed by
+    /// a sequence of varint-prefixed fields. See [`Log::write_binary`] for details.
+    Binary,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Qlog => "sqlog",
+            Self::Binary => "bin",
+        }
+    }
+}
+
+/// Writes each dump to a new file in a directory
+///
+/// The files are named after the time the dump was written, the endpoint type and the
+/// connection ID. Dumps that fail to be written are discarded.
+#[derive(Clone, Debug)]
+pub struct Directory {
+    path: PathBuf,
+    format: Format,
+}
+
+impl Directory {
+    pub fn new<P: Into<PathBuf>>(path: P, format: Format) -> Self {
+        Self {
+            path: path.into(),
+            format,
+        }
+    }
+
+    fn write(&self, log: &Log) -> io::Result<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let name = format!(
+            "{now}-{}-{}.{}",
+            log.endpoint_type_name(),
+            log.id,
+            self.format.extension()
+        );
+
+        let mut file = io::BufWriter::new(std::fs::File::create(self.path.join(name))?);
+        match self.format {
+            Format::Qlog => log.write_qlog(&mut file)?,
+            Format::Binary => log.write_binary(&mut file)?,
+        }
+        io::Write::flush(&mut file)
+    }
+}
+
+impl Sink for Directory {
+    #[inline]
+    fn on_dump(&mut self, log: &Log) {
+        // there's nothing to report the error to so the dump is dropped
+        let _ = self.write(log);
+    }
+}
+
+/// A recorded event
+#[derive(Clone, Debug)]
+pub struct Record {
+    time: Duration,
+    name: &'static str,
+    data: String,
+}
+
+impl Record {
+    /// Returns the time the event was emitted, relative to the creation of the connection
+    #[inline]
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Returns the name of the event
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the debug representation of the event
+    #[inline]
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+}
+
+/// The most recent events of a connection
+#[derive(Debug)]
+pub struct Log {
+    is_server: bool,
+    id: u64,
+    start: Timestamp,
+    records: VecDeque<Record>,
+    bytes: usize,
+    evicted: u64,
+    error: Option<connection::Error>,
+    limits: Limits,
+}
+
+impl Log {
+    fn new(meta: &ConnectionMeta, limits: Limits) -> Self {
+        Self {
+            is_server: matches!(meta.endpoint_type, events::EndpointType::Server { .. }),
+            id: meta.id,
+            start: meta.timestamp,
+            records: VecDeque::new(),
+            bytes: 0,
+            evicted: 0,
+            error: None,
+            limits,
+        }
+    }
+
+    /// Returns the internal ID of the connection
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns `true` if the connection was accepted by a server endpoint
+    #[inline]
+    pub fn is_server(&self) -> bool {
+        self.is_server
+    }
+
+    /// Returns the error the connection was closed with
+    #[inline]
+    pub fn error(&self) -> Option<&connection::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns the recorded events, from oldest to newest
+    #[inline]
+    pub fn records(&self) -> impl Iterator<Item = &Record> + ExactSizeIterator {
+        self.records.iter()
+    }
+
+    /// Returns the number of events which were evicted to stay within the limits
+    #[inline]
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+
+    fn endpoint_type_name(&self) -> &'static str {
+        if self.is_server {
+            "server"
+        } else {
+            "client"
+        }
+    }
+
+    fn push<E: Event>(&mut self, meta: &ConnectionMeta, event: &E) {
+        let time = meta.timestamp.saturating_duration_since(self.start);
+        let data = format!("{event:?}");
+
+        self.bytes += data.len();
+        self.records.push_back(Record {
+            time,
+            name: E::NAME,
+            data,
+        });
+
+        // evict the oldest records until we're within the limits, always keeping the newest one
+        while self.records.len() > 1
+            && (self.records.len() > self.limits.max_records || self.bytes > self.limits.max_bytes)
+        {
+            if let Some(record) = self.records.pop_front() {
+                self.bytes -= record.data.len();
+                self.evicted += 1;
+            }
+        }
+    }
+
+    /// Writes the events as a qlog trace, using the JSON-SEQ serialization
+    ///
+    /// The data of each event contains its debug representation in the `s2n_quic` field.
+    pub fn write_qlog<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut out = String::new();
+
+        // each JSON-SEQ record starts with the record separator character
+        out.push('\u{1e}');
+        out.push_str(r#"{"qlog_version":"0.3","qlog_format":"JSON-SEQ","title":"s2n-quic connection replay","trace":{"vantage_point":{"type":""#);
+        out.push_str(self.endpoint_type_name());
+        out.push_str(r#""},"common_fields":{"group_id":""#);
+        let _ = write!(out, "{}", self.id);
+        out.push_str(r#"","time_format":"relative"}}}"#);
+        out.push('\n');
+        w.write_all(out.as_bytes())?;
+
+        for record in &self.records {
+            out.clear();
+            out.push('\u{1e}');
+            let _ = write!(
+                out,
+                r#"{{"time":{},"name":""#,
+                record.time.as_micros() as f64 / 1000.0
+            );
+            json_escape(&mut out, record.name);
+            out.push_str(r#"","data":{"s2n_quic":""#);
+            json_escape(&mut out, &record.data);
+            out.push_str("\"}}\n");
+            w.write_all(out.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the events in a compact binary encoding
+    ///
+    /// The encoding is:
+    ///
+    /// ```text
+    /// Log {
+    ///   Magic (64) = "s2nqrply",
+    ///   Version (8) = 1,
+    ///   Endpoint Type (8),      // 0 for clients, 1 for servers
+    ///   Connection ID (v),
+    ///   Evicted Count (v),
+    ///   Record Count (v),
+    ///   Record (..) ...,
+    /// }
+    ///
+    /// Record {
+    ///   Time Micros (v),
+    ///   Name Length (v),
+    ///   Name (..),
+    ///   Data Length (v),
+    ///   Data (..),
+    /// }
+    /// ```
+    ///
+    /// where `(v)` is an unsigned LEB128 integer.
+    pub fn write_binary<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut out = Vec::with_capacity(self.bytes + self.records.len() * 32 + 32);
+
+        out.extend_from_slice(BINARY_MAGIC);
+        out.push(BINARY_VERSION);
+        out.push(self.is_server as u8);
+        write_varint(&mut out, self.id);
+        write_varint(&mut out, self.evicted);
+        write_varint(&mut out, self.records.len() as u64);
+
+        for record in &self.records {
+            write_varint(&mut out, record.time.as_micros() as u64);
+            write_varint(&mut out, record.name.len() as u64);
+            out.extend_from_slice(record.name.as_bytes());
+            write_varint(&mut out, record.data.len() as u64);
+            out.extend_from_slice(record.data.as_bytes());
+        }
+
+        w.write_all(&out)
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+pub(super) fn json_escape(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Limits {
+    max_records: usize,
+    max_bytes: usize,
+}
+
+/// The per-connection state of the [`Subscriber`]
+#[derive(Debug)]
+pub struct Context {
+    log: Log,
+}
+
+/// Records the most recent events of each connection and dumps them on close
+pub struct Subscriber<S: Sink> {
+    sink: S,
+    policy: Box<dyn Policy>,
+    limits: Limits,
+}
+
+impl<S: Sink> fmt::Debug for Subscriber<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Subscriber")
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+impl<S: Sink> Subscriber<S> {
+    /// Creates a subscriber with the default limits and policy
+    pub fn new(sink: S) -> Self {
+        Self::builder(sink).build()
+    }
+
+    pub fn builder(sink: S) -> Builder<S> {
+        Builder {
+            sink,
+            policy: Box::new(Trigger::default()),
+            limits: Limits {
+                max_records: DEFAULT_MAX_RECORDS,
+                max_bytes: DEFAULT_MAX_BYTES,
+            },
+        }
+    }
+}
+
+impl<S: Sink> super::Subscriber for Subscriber<S> {
+    type ConnectionContext = Context;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        Context {
+            log: Log::new(meta, self.limits),
+        }
+    }
+
+    #[inline]
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::ConnectionClosed,
+    ) {
+        // the close event is recorded after this callback so only note the error for now
+        context.log.error = Some(event.error);
+    }
+
+    #[inline]
+    fn on_connection_event<E: Event>(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &E,
+    ) {
+        context.log.push(meta, event);
+
+        if E::NAME != <events::ConnectionClosed as Event>::NAME {
+            return;
+        }
+
+        if let Some(error) = context.log.error {
+            if self.policy.should_dump(&error) {
+                self.sink.on_dump(&context.log);
+            }
+        }
+
+        // release the memory since the connection won't record any more events
+        context.log.records = VecDeque::new();
+        context.log.bytes = 0;
+    }
+}
+
+/// Allows the subscriber to be built with specific values
+pub struct Builder<S: Sink> {
+    sink: S,
+    policy: Box<dyn Policy>,
+    limits: Limits,
+}
+
+impl<S: Sink> Builder<S> {
+    /// Sets the maximum number of events recorded for each connection (default: 1000)
+    ///
+    /// The oldest events are evicted once the limit is reached.
+    pub fn with_max_records(mut self, max_records: usize) -> Result<Self, Error> {
+        if max_records == 0 {
+            return Err(Error::InvalidLimit);
+        }
+        self.limits.max_records = max_records;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of formatted event bytes recorded for each connection
+    /// (default: 256KiB)
+    ///
+    /// The oldest events are evicted once the limit is reached. The most recent event is always
+    /// kept, even if it exceeds the limit on its own.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Result<Self, Error> {
+        if max_bytes == 0 {
+            return Err(Error::InvalidLimit);
+        }
+        self.limits.max_bytes = max_bytes;
+        Ok(self)
+    }
+
+    /// Sets the policy which decides if a closed connection's events are dumped
+    /// (default: [`Trigger::OnUnexpectedClose`])
+    pub fn with_policy<P: Policy>(mut self, policy: P) -> Self {
+        self.policy = Box::new(policy);
+        self
+    }
+
+    /// Builds the [`Subscriber`]
+    pub fn build(self) -> Subscriber<S> {
+        Subscriber {
+            sink: self.sink,
+            policy: self.policy,
+            limits: self.limits,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The limit was zero
+    InvalidLimit,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLimit => write!(f, "limits must be greater than zero"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        endpoint,
+        event::{builder, IntoEvent},
+        time::{testing::Clock, Clock as _},
+    };
+
+    fn new_log(max_records: usize, max_bytes: usize) -> Log {
+        let meta = builder::ConnectionMeta {
+            endpoint_type: endpoint::Type::Server,
+            id: 7,
+            attempt_id: 0,
+            timestamp: Clock::default().get_time(),
+            application_context: Default::default(),
+        }
+        .into_event();
+        Log::new(
+            &meta,
+            Limits {
+                max_records,
+                max_bytes,
+            },
+        )
+    }
+
+    fn push(log: &mut Log, delay: Duration) {
+        let mut clock = Clock::default();
+        clock.inc_by(delay);
+        let meta = builder::ConnectionMeta {
+            endpoint_type: endpoint::Type::Server,
+            id: 7,
+            attempt_id: 0,
+            timestamp: clock.get_time(),
+            application_context: Default::default(),
+        }
+        .into_event();
+        let event: events::KeepAliveTimerExpired = builder::KeepAliveTimerExpired {
+            timeout: Duration::from_secs(1),
+        }
+        .into_event();
+        log.push(&meta, &event);
+    }
+
+    #[test]
+    fn eviction_test() {
+        let mut log = new_log(3, usize::MAX);
+
+        for i in 0..5 {
+            push(&mut log, Duration::from_millis(i));
+        }
+
+        assert_eq!(log.records().len(), 3);
+        assert_eq!(log.evicted(), 2);
+        let times: Vec<_> = log.records().map(|record| record.time()).collect();
+        assert_eq!(
+            times,
+            [2, 3, 4].map(Duration::from_millis).to_vec(),
+            "the oldest records should be evicted"
+        );
+
+        // the newest record is kept even if it exceeds the byte limit
+        let mut log = new_log(10, 1);
+        push(&mut log, Duration::ZERO);
+        push(&mut log, Duration::ZERO);
+        assert_eq!(log.records().len(), 1);
+        assert_eq!(log.evicted(), 1);
+    }
+
+    #[test]
+    fn qlog_test() {
+        let mut log = new_log(10, usize::MAX);
+        push(&mut log, Duration::from_millis(5));
+
+        let mut out = vec![];
+        log.write_qlog(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let records: Vec<_> = out.split('\u{1e}').skip(1).collect();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].contains(r#""vantage_point":{"type":"server"}"#));
+        assert!(
+            records[1].starts_with(r#"{"time":5,"name":"connectivity::keep_alive_timer_expired""#)
+        );
+        assert!(records[1].ends_with("}}\n"));
+    }
+
+    #[test]
+    fn binary_test() {
+        let mut log = new_log(10, usize::MAX);
+        push(&mut log, Duration::from_micros(300));
+
+        let mut out = vec![];
+        log.write_binary(&mut out).unwrap();
+
+        let (header, mut rest) = out.split_at(BINARY_MAGIC.len() + 2);
+        assert_eq!(&header[..BINARY_MAGIC.len()], BINARY_MAGIC);
+        assert_eq!(header[BINARY_MAGIC.len()], BINARY_VERSION);
+        assert_eq!(header[BINARY_MAGIC.len() + 1], 1);
+
+        // id, evicted, record count
+        assert_eq!(&rest[..3], &[7, 0, 1]);
+        rest = &rest[3..];
+
+        // 300 micros encoded as LEB128
+        assert_eq!(&rest[..2], &[0xac, 0x02]);
+        rest = &rest[2..];
+
+        let name = log.records().next().unwrap().name();
+        assert_eq!(rest[0] as usize, name.len());
+        assert_eq!(&rest[1..=name.len()], name.as_bytes());
+    }
+
+    #[test]
+    fn json_escape_test() {
+        let mut out = String::new();
+        json_escape(&mut out, "a\"b\\c\n\u{1}");
+        assert_eq!(out, r#"a\"b\\c\n\u0001"#);
+    }
+
+    #[test]
+    fn trigger_test() {
+        use s2n_quic_core::{application, transport};
+
+        let closed = connection::Error::closed(endpoint::Location::Local);
+        let application = connection::Error::application(application::Error::from(1u8));
+        let idle = connection::Error::idle_timer_expired();
+        let transport = connection::Error::from(transport::Error::PROTOCOL_VIOLATION);
+
+        assert!(Trigger::Always.should_dump(&closed));
+
+        assert!(!Trigger::OnError.should_dump(&closed));
+        assert!(Trigger::OnError.should_dump(&application));
+        assert!(Trigger::OnError.should_dump(&idle));
+
+        assert!(!Trigger::OnUnexpectedClose.should_dump(&closed));
+        assert!(!Trigger::OnUnexpectedClose.should_dump(&application));
+        assert!(!Trigger::OnUnexpectedClose.should_dump(&idle));
+        assert!(Trigger::OnUnexpectedClose.should_dump(&transport));
+    }
+}
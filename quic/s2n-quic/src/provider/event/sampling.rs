@@ -0,0 +1,276 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Restricts event subscribers to a subset of connections
+//!
+//! Subscribers which do a lot of work per event, such as those recording every packet, can be
+//! wrapped with [`Sampled`] so they only observe some of the connections on an endpoint. The
+//! decision is made once, when the connection is created, and the transport skips publishing
+//! connection events to the wrapped subscriber for the remaining connections.
+
+use super::{events, ConnectionInfo, ConnectionMeta};
+use core::fmt;
+use rand::Rng;
+use s2n_quic_core::application::matches_server_name;
+use std::net::{IpAddr, Ipv6Addr};
+
+pub use s2n_quic_core::event::{Sampled, Sampler};
+
+/// Selects connections by sampling rate, server name, or remote address
+///
+/// A connection is selected if it matches any of the configured server names or remote address
+/// prefixes. Otherwise, it is selected at random with the configured rate.
+///
+/// Servers learn the server name from the ClientHello, after the connection is created. Server
+/// connections which weren't selected when they were created are selected once the name matches
+/// a server name rule, and the subscriber observes the events from that point on.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use s2n_quic::{
+///     provider::event::{sampling::{Filter, Sampled}, tracing},
+///     Server,
+/// };
+/// use std::error::Error;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let filter = Filter::builder()
+///     .with_rate(0.01)?
+///     .with_remote_prefix("192.0.2.0".parse()?, 24)?
+///     .build();
+///
+/// let mut server = Server::builder()
+///     .with_event(Sampled::new(tracing::Subscriber::default(), filter))?
+///     .start()?;
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    rate: f64,
+    server_names: Vec<String>,
+    remote_prefixes: Vec<(IpAddr, u8)>,
+}
+
+impl Filter {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    fn matches_server_name(&self, server_name: Option<&str>) -> bool {
+        let Some(server_name) = server_name else {
+            return false;
+        };
+
+        self.server_names
+            .iter()
+            .any(|pattern| matches_server_name(pattern, server_name))
+    }
+
+    fn matches_remote_address(&self, address: IpAddr) -> bool {
+        self.remote_prefixes
+            .iter()
+            .any(|(prefix, len)| prefix_matches(*prefix, *len, address))
+    }
+}
+
+impl Sampler for Filter {
+    #[inline]
+    fn sample(&mut self, _meta: &ConnectionMeta, info: &ConnectionInfo) -> bool {
+        if self.matches_server_name(info.server_name) {
+            return true;
+        }
+
+        if let Some(address) = ip_address(&info.remote_address) {
+            if self.matches_remote_address(address) {
+                return true;
+            }
+        }
+
+        self.rate > 0.0 && rand::thread_rng().gen_bool(self.rate)
+    }
+
+    #[inline]
+    fn sample_updated(&mut self, _meta: &ConnectionMeta, info: &ConnectionInfo) -> bool {
+        // the remote address and rate were already considered when the connection was created
+        self.matches_server_name(info.server_name)
+    }
+}
+
+/// Allows the filter to be built with specific values
+#[derive(Debug, Default)]
+pub struct Builder {
+    filter: Filter,
+}
+
+impl Builder {
+    /// Sets the fraction of connections to select at random, between `0.0` and `1.0` (default: 0.0)
+    pub fn with_rate(mut self, rate: f64) -> Result<Self, Error> {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(Error::InvalidRate);
+        }
+        self.filter.rate = rate;
+        Ok(self)
+    }
+
+    /// Selects all connections to a server name matching `pattern`
+    ///
+    /// A pattern is either an exact server name or a wildcard of the form `*.example.com`, which
+    /// matches any subdomain of `example.com`.
+    pub fn with_server_name<P: Into<String>>(mut self, pattern: P) -> Self {
+        self.filter.server_names.push(pattern.into());
+        self
+    }
+
+    /// Selects all connections with a remote address in the given prefix
+    ///
+    /// IPv4-mapped IPv6 addresses and prefixes are matched in their IPv4 form.
+    pub fn with_remote_prefix(mut self, prefix: IpAddr, len: u8) -> Result<Self, Error> {
+        let (prefix, len) = match prefix {
+            IpAddr::V6(addr) => match addr.to_ipv4_mapped() {
+                Some(addr) if len >= 96 => (IpAddr::V4(addr), len - 96),
+                _ => (prefix, len),
+            },
+            IpAddr::V4(_) => (prefix, len),
+        };
+
+        let max_len = if prefix.is_ipv4() { 32 } else { 128 };
+        if len > max_len {
+            return Err(Error::InvalidPrefixLength);
+        }
+
+        self.filter.remote_prefixes.push((prefix, len));
+        Ok(self)
+    }
+
+    /// Build the filter
+    pub fn build(self) -> Filter {
+        self.filter
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The sampling rate was not between `0.0` and `1.0`
+    InvalidRate,
+    /// The prefix length was longer than the address
+    InvalidPrefixLength,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidRate => write!(f, "sampling rate must be between 0.0 and 1.0"),
+            Self::InvalidPrefixLength => write!(f, "prefix length exceeds the address length"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn ip_address(address: &events::SocketAddress) -> Option<IpAddr> {
+    let address = match address {
+        events::SocketAddress::IpV4 { ip, .. } => IpAddr::from(**ip),
+        events::SocketAddress::IpV6 { ip, .. } => {
+            let address = Ipv6Addr::from(**ip);
+            match address.to_ipv4_mapped() {
+                Some(address) => address.into(),
+                None => address.into(),
+            }
+        }
+        _ => return None,
+    };
+    Some(address)
+}
+
+fn prefix_matches(prefix: IpAddr, len: u8, address: IpAddr) -> bool {
+    match (prefix, address) {
+        (IpAddr::V4(prefix), IpAddr::V4(address)) => {
+            let mask = u32::MAX.checked_shl(32 - len as u32).unwrap_or(0);
+            u32::from(prefix) & mask == u32::from(address) & mask
+        }
+        (IpAddr::V6(prefix), IpAddr::V6(address)) => {
+            let mask = u128::MAX.checked_shl(128 - len as u32).unwrap_or(0);
+            u128::from(prefix) & mask == u128::from(address) & mask
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        endpoint,
+        event::{builder, IntoEvent},
+        inet::SocketAddress,
+        time::{testing::Clock, Clock as _},
+    };
+
+    fn sample(filter: &mut Filter, address: &str, server_name: Option<&str>) -> bool {
+        let meta = builder::ConnectionMeta {
+            endpoint_type: endpoint::Type::Client,
+            id: 0,
+            attempt_id: 0,
+            timestamp: Clock::default().get_time(),
+            application_context: Default::default(),
+        }
+        .into_event();
+        let address: IpAddr = address.parse().unwrap();
+        let remote_address = SocketAddress::from((address, 443));
+        let info = builder::ConnectionInfo {
+            remote_address: (&remote_address).into_event(),
+            server_name,
+        }
+        .into_event();
+        filter.sample(&meta, &info)
+    }
+
+    #[test]
+    fn filter_test() {
+        let mut filter = Filter::builder()
+            .with_server_name("*.example.com")
+            .with_remote_prefix("192.0.2.0".parse().unwrap(), 24)
+            .unwrap()
+            .with_remote_prefix("2001:db8::".parse().unwrap(), 32)
+            .unwrap()
+            .build();
+
+        assert!(sample(&mut filter, "198.51.100.1", Some("www.example.com")));
+        assert!(!sample(&mut filter, "198.51.100.1", Some("example.org")));
+        assert!(!sample(&mut filter, "198.51.100.1", None));
+
+        assert!(sample(&mut filter, "192.0.2.55", None));
+        assert!(sample(&mut filter, "::ffff:192.0.2.55", None));
+        assert!(!sample(&mut filter, "192.0.3.1", None));
+
+        assert!(sample(&mut filter, "2001:db8::1", None));
+        assert!(!sample(&mut filter, "2001:db9::1", None));
+    }
+
+    #[test]
+    fn rate_test() {
+        let mut none = Filter::default();
+        let mut all = Filter::builder().with_rate(1.0).unwrap().build();
+
+        for _ in 0..100 {
+            assert!(!sample(&mut none, "192.0.2.1", None));
+            assert!(sample(&mut all, "192.0.2.1", None));
+        }
+
+        assert_eq!(
+            Filter::builder().with_rate(1.5).unwrap_err(),
+            Error::InvalidRate
+        );
+        assert_eq!(
+            Filter::builder()
+                .with_remote_prefix("192.0.2.0".parse().unwrap(), 33)
+                .unwrap_err(),
+            Error::InvalidPrefixLength
+        );
+    }
+}
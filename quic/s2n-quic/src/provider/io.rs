@@ -19,6 +19,22 @@ pub trait Provider: 'static {
 #[cfg(any(test, feature = "unstable-provider-io-testing"))]
 pub mod testing;
 
+/// Mutates outgoing datagrams after encryption to validate the resilience of endpoints
+#[cfg(any(test, feature = "unstable-provider-io-tx-fuzz"))]
+pub mod fuzz {
+    pub use s2n_quic_platform::io::fuzz::{Action, Policy};
+}
+
+/// Copies encrypted datagrams to an application-provided channel for out-of-band inspection
+pub mod mirror {
+    pub use s2n_quic_platform::io::mirror::{channel, Direction, Record, Tap};
+}
+
+/// Counters for the syscalls and rings of the IO provider, for monitoring kernel-level health
+pub mod stats {
+    pub use s2n_quic_platform::socket::stats::{Snapshot, Stats};
+}
+
 #[cfg(feature = "unstable-provider-io-turmoil")]
 pub mod turmoil;
 
@@ -62,4 +78,22 @@ impl_socket_addrs!(std::net::SocketAddr);
 impl_socket_addrs!(std::net::SocketAddrV4);
 impl_socket_addrs!(std::net::SocketAddrV6);
 
+impl TryInto for std::net::UdpSocket {
+    type Error = io::Error;
+    type Provider = Default;
+
+    fn try_into(self) -> io::Result<Self::Provider> {
+        Default::builder().with_rx_socket(self)?.build()
+    }
+}
+
+impl TryInto for ::tokio::net::UdpSocket {
+    type Error = io::Error;
+    type Provider = Default;
+
+    fn try_into(self) -> io::Result<Self::Provider> {
+        Default::builder().with_tokio_rx_socket(self)?.build()
+    }
+}
+
 impl_provider_utils!();
@@ -3,7 +3,13 @@
 
 //! Provides limits support for a connection
 
-pub use s2n_quic_core::connection::limits::{ConnectionInfo, Limiter, Limits};
+pub use s2n_quic_core::{
+    connection::limits::{
+        ConnectionInfo, InitialPadding, Limiter, Limits, PacketCoalescing, PaddingPolicy,
+        StreamSchedulingPolicy,
+    },
+    transport::parameters::AddressDiscovery,
+};
 
 pub trait Provider {
     type Limits: 'static + Send + Limiter;
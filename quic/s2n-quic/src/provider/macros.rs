@@ -162,7 +162,7 @@ macro_rules! impl_providers_state {
                     $(
                         $rest: providers.$rest,
                     )*
-                })
+                }, self.1)
             }
         }
 
@@ -1,15 +1,49 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-// this functionality isn't public but should be assumed that it
-// eventually will be
-#[allow(unused_imports)]
+//! Provides path migration support for a server
+//!
+//! Each time a peer sends a packet from a new address, the validator is asked whether the
+//! connection is allowed to migrate to it. Denied attempts cause the packet to be dropped, without
+//! reserving any state for the new path.
+//!
+//! # Examples
+//!
+//! Only allows peers to rebind within the same IPv4 /24 and limits each connection to 4 changes of
+//! its active path.
+//!
+//! ```rust
+//! use s2n_quic::provider::{
+//!     event::events::SocketAddress,
+//!     path_migration::{Attempt, DenyReason, Outcome, Validator},
+//! };
+//!
+//! struct SameSubnet;
+//!
+//! impl Validator for SameSubnet {
+//!     fn on_migration_attempt(&mut self, attempt: &Attempt) -> Outcome {
+//!         if attempt.migration_count >= 4 {
+//!             return Outcome::Deny(DenyReason::Application);
+//!         }
+//!
+//!         match (&attempt.active_path.remote_addr, &attempt.packet.remote_address) {
+//!             (SocketAddress::IpV4 { ip: a, .. }, SocketAddress::IpV4 { ip: b, .. })
+//!                 if a[..3] == b[..3] =>
+//!             {
+//!                 Outcome::Allow
+//!             }
+//!             _ => Outcome::Deny(DenyReason::Application),
+//!         }
+//!     }
+//! }
+//! ```
+
 pub use s2n_quic_core::path::migration::{
     default::{self, Validator as Default},
-    disabled, Attempt, Outcome, Validator,
+    disabled, Attempt, DenyReason, Outcome, PacketInfo, Validator,
 };
 
-/// Provides limits support for an endpoint
+/// Provides path migration support for an endpoint
 pub trait Provider {
     type Validator: 'static + Send + Validator;
     type Error: 'static + core::fmt::Display + Send + Sync;
@@ -1,13 +1,25 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+//! Provides random number generation support for an endpoint
+//!
+//! The endpoint draws its randomness from the configured provider, including connection
+//! IDs, address tokens, path challenges, and skipped packet numbers. The default provider uses a
+//! ChaCha based generator which is periodically reseeded from the operating system. Deployments
+//! that require a FIPS-approved generator can use the `drbg` provider instead, which is
+//! available on platforms where the crypto library is AWS-LC.
+
 pub use s2n_quic_core::random::Generator;
 
 /// Provides random number generation support for an endpoint
+///
+/// The provider is started once per endpoint and the returned [`Generator`] is used for the
+/// lifetime of that endpoint.
 pub trait Provider: 'static {
     type Generator: 'static + Generator;
     type Error: core::fmt::Display + Send + Sync;
 
+    /// Starts the provider and returns the generator for the endpoint
     fn start(self) -> Result<Self::Generator, Self::Error>;
 }
 
@@ -15,6 +27,98 @@ pub use self::rand::Provider as Default;
 
 impl_provider_utils!();
 
+// The crypto library is `ring` on Windows, which draws directly from the operating system
+// rather than a DRBG, so the provider is only available where the crypto library is AWS-LC.
+#[cfg(all(feature = "provider-random-drbg", not(target_os = "windows")))]
+pub mod drbg {
+    //! A random provider backed by the CTR_DRBG (NIST SP 800-90A) of AWS-LC
+    //!
+    //! The DRBG is the FIPS-validated module when s2n-quic-crypto is built with its `fips`
+    //! feature.
+
+    use core::convert::Infallible;
+    use s2n_quic_core::random;
+    use s2n_quic_crypto::rand::{SecureRandom, SystemRandom};
+
+    #[derive(Debug, Default)]
+    pub struct Provider(Generator);
+
+    impl super::Provider for Provider {
+        type Generator = Generator;
+        type Error = Infallible;
+
+        fn start(self) -> Result<Self::Generator, Self::Error> {
+            Ok(self.0)
+        }
+    }
+
+    impl super::TryInto for Generator {
+        type Provider = Provider;
+        type Error = Infallible;
+
+        fn try_into(self) -> Result<Self::Provider, Self::Error> {
+            Ok(Provider(self))
+        }
+    }
+
+    /// Random bits generated by the AWS-LC DRBG
+    ///
+    /// AWS-LC maintains its own DRBG state so public and private values are drawn
+    /// from the same source.
+    #[derive(Debug)]
+    pub struct Generator(SystemRandom);
+
+    impl Default for Generator {
+        fn default() -> Self {
+            Self(SystemRandom::new())
+        }
+    }
+
+    impl Generator {
+        #[inline]
+        fn fill(&self, dest: &mut [u8]) {
+            self.0
+                .fill(dest)
+                .unwrap_or_else(|_| panic!("could not generate random bytes"))
+        }
+    }
+
+    impl random::Generator for Generator {
+        #[inline]
+        fn public_random_fill(&mut self, dest: &mut [u8]) {
+            self.fill(dest)
+        }
+
+        #[inline]
+        fn private_random_fill(&mut self, dest: &mut [u8]) {
+            self.fill(dest)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use s2n_quic_core::random::Generator;
+
+        #[test]
+        fn generator_test() {
+            let mut generator = super::Generator::default();
+
+            let mut dest_1 = [0; 20];
+            let mut dest_2 = [0; 20];
+
+            generator.public_random_fill(&mut dest_1);
+            generator.public_random_fill(&mut dest_2);
+
+            assert_ne!(dest_1, dest_2);
+
+            generator.private_random_fill(&mut dest_1);
+            generator.private_random_fill(&mut dest_2);
+
+            assert_ne!(dest_1, dest_2);
+        }
+    }
+}
+
 mod rand {
     use core::convert::Infallible;
     use rand::{
@@ -0,0 +1,356 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replaces provider configuration on a running endpoint
+//!
+//! Providers are normally fixed when the endpoint is started. Wrapping a provider's value in a
+//! [`Slot`] allows it to be replaced later through the [`Config`] it was created from. Existing
+//! connections keep the settings they were created with, while new connections pick up the
+//! replacement.
+//!
+//! Each update increments the config [`Epoch`]. Including the [`Subscriber`] in the endpoint's
+//! event subscribers records the epoch that was current when each connection was created, which
+//! can be queried with
+//! [`Connection::query_event_context`](crate::Connection::query_event_context).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use s2n_quic::{
+//!     provider::{
+//!         event::{sampling::{Filter, Sampled}, tracing},
+//!         limits::Limits,
+//!         reload::{Config, Epoch},
+//!     },
+//!     Server,
+//! };
+//! use std::{error::Error, time::Duration};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let config = Config::default();
+//! let limits = config.slot(Limits::default());
+//! let filter = config.slot(Filter::default());
+//!
+//! let mut server = Server::builder()
+//!     .with_limits(limits.clone())?
+//!     .with_event((
+//!         config.subscriber(),
+//!         Sampled::new(tracing::Subscriber::default(), filter.clone()),
+//!     ))?
+//!     .start()?;
+//!
+//! // later, apply a new policy to all new connections at once
+//! let epoch = config.update(|update| {
+//!     update.set(&limits, Limits::new().with_max_handshake_duration(Duration::from_secs(5))?);
+//!     update.set(&filter, Filter::builder().with_rate(0.1)?.build());
+//!     Ok::<_, Box<dyn Error>>(())
+//! })?;
+//!
+//! while let Some(connection) = server.accept().await {
+//!     let used = connection.query_event_context(|epoch: &Epoch| *epoch)?;
+//!     assert!(used <= epoch);
+//! }
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use crate::provider::{
+    address_token,
+    event::{self, ConnectionInfo, ConnectionMeta},
+    limits,
+};
+use core::fmt;
+use s2n_quic_core::connection;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex, RwLock,
+};
+
+/// Identifies a version of the configuration
+///
+/// The initial configuration is epoch `0` and each call to [`Config::update`] increments it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Epoch(u64);
+
+impl Epoch {
+    #[inline]
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    epoch: AtomicU64,
+    /// Held for writing while an update is applied so readers never observe a partial update
+    lock: RwLock<()>,
+}
+
+/// A handle for replacing the values of a group of [`Slot`]s
+///
+/// The handle can be cloned and shared with the task which is responsible for reloading the
+/// configuration.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    state: Arc<State>,
+}
+
+impl Config {
+    /// Creates a new slot with an initial value
+    ///
+    /// The slot implements the provider traits of the wrapped value and can be passed to the
+    /// endpoint builder in its place.
+    pub fn slot<T>(&self, value: T) -> Slot<T> {
+        Slot {
+            state: self.state.clone(),
+            value: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    /// Returns the current epoch
+    #[inline]
+    pub fn epoch(&self) -> Epoch {
+        Epoch(self.state.epoch.load(Ordering::Acquire))
+    }
+
+    /// Returns an event subscriber which records the epoch used by each connection
+    pub fn subscriber(&self) -> Subscriber {
+        Subscriber {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Atomically replaces the values of one or more slots
+    ///
+    /// Connections created after this call returns will observe all of the new values. If `f`
+    /// returns an error, the values which were already set are kept but the epoch is not
+    /// incremented.
+    pub fn update<F, E>(&self, f: F) -> Result<Epoch, E>
+    where
+        F: FnOnce(&mut Update) -> Result<(), E>,
+    {
+        let _guard = self
+            .state
+            .lock
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut update = Update { state: &self.state };
+        f(&mut update)?;
+
+        let epoch = self.state.epoch.fetch_add(1, Ordering::AcqRel) + 1;
+        Ok(Epoch(epoch))
+    }
+}
+
+/// Replaces slot values as part of a [`Config::update`]
+pub struct Update<'a> {
+    state: &'a Arc<State>,
+}
+
+impl Update<'_> {
+    /// Replaces the value of `slot`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slot was created by a different [`Config`].
+    pub fn set<T>(&mut self, slot: &Slot<T>, value: T) {
+        assert!(
+            Arc::ptr_eq(self.state, &slot.state),
+            "slot belongs to a different config"
+        );
+        *slot.lock() = value;
+    }
+}
+
+/// A provider value which can be replaced through its [`Config`]
+pub struct Slot<T> {
+    state: Arc<State>,
+    value: Arc<Mutex<T>>,
+}
+
+impl<T> Clone for Slot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Slot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Slot")
+            .field("value", &*self.lock())
+            .finish()
+    }
+}
+
+impl<T> Slot<T> {
+    #[inline]
+    fn lock(&self) -> std::sync::MutexGuard<T> {
+        self.value
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[inline]
+    fn with<F: FnOnce(&mut T) -> R, R>(&self, f: F) -> R {
+        // wait for any in-progress updates to finish
+        let _guard = self
+            .state
+            .lock
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut self.lock())
+    }
+}
+
+impl<T: limits::Limiter> limits::Limiter for Slot<T> {
+    #[inline]
+    fn on_connection(&mut self, info: &limits::ConnectionInfo) -> limits::Limits {
+        self.with(|limiter| limiter.on_connection(info))
+    }
+}
+
+impl<T: event::sampling::Sampler> event::sampling::Sampler for Slot<T> {
+    #[inline]
+    fn sample(&mut self, meta: &ConnectionMeta, info: &ConnectionInfo) -> bool {
+        self.with(|sampler| sampler.sample(meta, info))
+    }
+}
+
+/// Replacing the token format invalidates any tokens which were issued by the previous format.
+/// Clients presenting those tokens will be sent a new Retry packet.
+impl<T: address_token::Format> address_token::Format for Slot<T> {
+    const TOKEN_LEN: usize = T::TOKEN_LEN;
+
+    #[inline]
+    fn generate_new_token(
+        &mut self,
+        context: &mut address_token::Context<'_>,
+        source_connection_id: &connection::LocalId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        self.with(|format| format.generate_new_token(context, source_connection_id, output_buffer))
+    }
+
+    #[inline]
+    fn generate_retry_token(
+        &mut self,
+        context: &mut address_token::Context<'_>,
+        original_destination_connection_id: &connection::InitialId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        self.with(|format| {
+            format.generate_retry_token(context, original_destination_connection_id, output_buffer)
+        })
+    }
+
+    #[inline]
+    fn validate_token(
+        &mut self,
+        context: &mut address_token::Context<'_>,
+        token: &[u8],
+    ) -> Option<connection::InitialId> {
+        self.with(|format| format.validate_token(context, token))
+    }
+}
+
+impl<T: address_token::Format> address_token::Provider for Slot<T> {
+    type Format = Self;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Format, Self::Error> {
+        Ok(self)
+    }
+}
+
+/// Records the config [`Epoch`] used by each connection
+///
+/// The epoch is recorded when the connection is created and is exposed as the connection's
+/// event context.
+#[derive(Clone, Debug)]
+pub struct Subscriber {
+    state: Arc<State>,
+}
+
+impl event::Subscriber for Subscriber {
+    type ConnectionContext = Epoch;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let _guard = self
+            .state
+            .lock
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Epoch(self.state.epoch.load(Ordering::Acquire))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use limits::{Limiter as _, Limits};
+    use s2n_quic_core::inet::SocketAddress;
+
+    fn handshake_duration(slot: &mut Slot<Limits>) -> Duration {
+        let remote_address = SocketAddress::default();
+        let info = limits::ConnectionInfo::new(&remote_address);
+        slot.on_connection(&info).max_handshake_duration()
+    }
+
+    #[test]
+    fn update_test() {
+        let config = Config::default();
+        let mut limits = config.slot(Limits::default());
+        let mut clone = limits.clone();
+        assert_eq!(config.epoch(), Epoch(0));
+
+        let expected = handshake_duration(&mut limits) * 2;
+
+        let new_limits = Limits::default()
+            .with_max_handshake_duration(expected)
+            .unwrap();
+        let epoch = config
+            .update(|update| {
+                update.set(&limits, new_limits);
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+
+        assert_eq!(epoch, Epoch(1));
+        assert_eq!(config.epoch(), epoch);
+        assert_eq!(handshake_duration(&mut limits), expected);
+        assert_eq!(handshake_duration(&mut clone), expected);
+
+        // failed updates don't increment the epoch
+        assert!(config.update(|_| Err(())).is_err());
+        assert_eq!(config.epoch(), epoch);
+    }
+
+    #[test]
+    #[should_panic(expected = "slot belongs to a different config")]
+    fn foreign_slot_test() {
+        let config = Config::default();
+        let other = Config::default().slot(Limits::default());
+        let _ = config.update(|update| {
+            update.set(&other, Limits::default());
+            Ok::<_, ()>(())
+        });
+    }
+}
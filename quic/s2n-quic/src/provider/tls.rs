@@ -16,8 +16,37 @@ pub trait Provider {
 
     /// Creates a client endpoint for the given provider
     fn start_client(self) -> Result<Self::Client, Self::Error>;
+
+    /// Creates a server endpoint which only negotiates FIPS-approved algorithms
+    ///
+    /// Providers which are already configured start as usual; the endpoint checks their
+    /// compliance once started. Restricting a provider can fail even if starting it normally
+    /// can't, so this returns a separate error type.
+    fn start_fips_server(self) -> Result<Self::Server, FipsError>
+    where
+        Self: Sized,
+    {
+        self.start_server()
+            .map_err(|error| error.to_string().into())
+    }
+
+    /// Creates a client endpoint which only negotiates FIPS-approved algorithms
+    ///
+    /// Providers which are already configured start as usual; the endpoint checks their
+    /// compliance once started. Restricting a provider can fail even if starting it normally
+    /// can't, so this returns a separate error type.
+    fn start_fips_client(self) -> Result<Self::Client, FipsError>
+    where
+        Self: Sized,
+    {
+        self.start_client()
+            .map_err(|error| error.to_string().into())
+    }
 }
 
+/// The error returned when a TLS provider can't be started in FIPS mode
+pub type FipsError = Box<dyn std::error::Error + Send + Sync>;
+
 impl_provider_utils!();
 
 cfg_if! {
@@ -53,6 +82,47 @@ impl Provider for Default {
     fn start_client(self) -> Result<Self::Client, Self::Error> {
         Ok(Self::Client::default())
     }
+
+    fn start_fips_server(self) -> Result<Self::Server, FipsError> {
+        Ok(Self::Server::builder().with_fips(true)?.build()?)
+    }
+
+    fn start_fips_client(self) -> Result<Self::Client, FipsError> {
+        Ok(Self::Client::builder().with_fips(true)?.build()?)
+    }
+}
+
+fn start_server<C, K>(
+    certificate: C,
+    private_key: K,
+    fips: bool,
+) -> Result<default::Server, Box<dyn std::error::Error + Send + Sync>>
+where
+    C: default::certificate::IntoCertificate,
+    K: default::certificate::IntoPrivateKey,
+{
+    let server = default::Server::builder()
+        .with_fips(fips)?
+        .with_certificate(certificate, private_key)?
+        .build()?;
+
+    Ok(server)
+}
+
+fn start_client<C>(
+    certificate: C,
+    fips: bool,
+) -> Result<default::Client, Box<dyn std::error::Error + Send + Sync>>
+where
+    C: default::certificate::IntoCertificate,
+{
+    // TODO support private key
+    let client = default::Client::builder()
+        .with_fips(fips)?
+        .with_certificate(certificate)?
+        .build()?;
+
+    Ok(client)
 }
 
 impl Provider for (&std::path::Path, &std::path::Path) {
@@ -61,20 +131,19 @@ impl Provider for (&std::path::Path, &std::path::Path) {
     type Error = Box<dyn std::error::Error + Send + Sync>;
 
     fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let server = default::Server::builder()
-            .with_certificate(self.0, self.1)?
-            .build()?;
-
-        Ok(server)
+        start_server(self.0, self.1, false)
     }
 
     fn start_client(self) -> Result<Self::Client, Self::Error> {
-        // TODO support private key
-        let client = default::Client::builder()
-            .with_certificate(self.0)?
-            .build()?;
+        start_client(self.0, false)
+    }
+
+    fn start_fips_server(self) -> Result<Self::Server, FipsError> {
+        start_server(self.0, self.1, true)
+    }
 
-        Ok(client)
+    fn start_fips_client(self) -> Result<Self::Client, FipsError> {
+        start_client(self.0, true)
     }
 }
 
@@ -85,17 +154,20 @@ impl Provider for &std::path::Path {
 
     fn start_server(self) -> Result<Self::Server, Self::Error> {
         let empty_cert: &[u8] = &[];
-        let server = default::Server::builder()
-            .with_certificate(empty_cert, self)?
-            .build()?;
-
-        Ok(server)
+        start_server(empty_cert, self, false)
     }
 
     fn start_client(self) -> Result<Self::Client, Self::Error> {
-        let client = default::Client::builder().with_certificate(self)?.build()?;
+        start_client(self, false)
+    }
 
-        Ok(client)
+    fn start_fips_server(self) -> Result<Self::Server, FipsError> {
+        let empty_cert: &[u8] = &[];
+        start_server(empty_cert, self, true)
+    }
+
+    fn start_fips_client(self) -> Result<Self::Client, FipsError> {
+        start_client(self, true)
     }
 }
 
@@ -105,20 +177,19 @@ impl Provider for (&[u8], &[u8]) {
     type Error = Box<dyn std::error::Error + Send + Sync>;
 
     fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let server = default::Server::builder()
-            .with_certificate(self.0, self.1)?
-            .build()?;
-
-        Ok(server)
+        start_server(self.0, self.1, false)
     }
 
     fn start_client(self) -> Result<Self::Client, Self::Error> {
-        // TODO support private key
-        let client = default::Client::builder()
-            .with_certificate(self.0)?
-            .build()?;
+        start_client(self.0, false)
+    }
 
-        Ok(client)
+    fn start_fips_server(self) -> Result<Self::Server, FipsError> {
+        start_server(self.0, self.1, true)
+    }
+
+    fn start_fips_client(self) -> Result<Self::Client, FipsError> {
+        start_client(self.0, true)
     }
 }
 
@@ -129,17 +200,20 @@ impl Provider for &[u8] {
 
     fn start_server(self) -> Result<Self::Server, Self::Error> {
         let empty_cert = &[][..];
-        let server = default::Server::builder()
-            .with_certificate(empty_cert, self)?
-            .build()?;
-
-        Ok(server)
+        start_server(empty_cert, self, false)
     }
 
     fn start_client(self) -> Result<Self::Client, Self::Error> {
-        let client = default::Client::builder().with_certificate(self)?.build()?;
+        start_client(self, false)
+    }
+
+    fn start_fips_server(self) -> Result<Self::Server, FipsError> {
+        let empty_cert = &[][..];
+        start_server(empty_cert, self, true)
+    }
 
-        Ok(client)
+    fn start_fips_client(self) -> Result<Self::Client, FipsError> {
+        start_client(self, true)
     }
 }
 
@@ -149,20 +223,19 @@ impl Provider for (&str, &str) {
     type Error = Box<dyn std::error::Error + Send + Sync>;
 
     fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let server = default::Server::builder()
-            .with_certificate(self.0, self.1)?
-            .build()?;
-
-        Ok(server)
+        start_server(self.0, self.1, false)
     }
 
     fn start_client(self) -> Result<Self::Client, Self::Error> {
-        // TODO support private key
-        let client = default::Client::builder()
-            .with_certificate(self.0)?
-            .build()?;
+        start_client(self.0, false)
+    }
+
+    fn start_fips_server(self) -> Result<Self::Server, FipsError> {
+        start_server(self.0, self.1, true)
+    }
 
-        Ok(client)
+    fn start_fips_client(self) -> Result<Self::Client, FipsError> {
+        start_client(self.0, true)
     }
 }
 
@@ -173,17 +246,20 @@ impl Provider for &str {
 
     fn start_server(self) -> Result<Self::Server, Self::Error> {
         let empty_cert = "";
-        let server = default::Server::builder()
-            .with_certificate(empty_cert, self)?
-            .build()?;
-
-        Ok(server)
+        start_server(empty_cert, self, false)
     }
 
     fn start_client(self) -> Result<Self::Client, Self::Error> {
-        let client = default::Client::builder().with_certificate(self)?.build()?;
+        start_client(self, false)
+    }
 
-        Ok(client)
+    fn start_fips_server(self) -> Result<Self::Server, FipsError> {
+        let empty_cert = "";
+        start_server(empty_cert, self, true)
+    }
+
+    fn start_fips_client(self) -> Result<Self::Client, FipsError> {
+        start_client(self, true)
     }
 }
 
@@ -238,6 +314,39 @@ mod default_tls {
     // TODO stub out default that fails with error when started
 }
 
+#[cfg(any(test, feature = "dangerous-provider-tls-null"))]
+pub mod null {
+    //! Provides a TLS implementation which performs no handshake or encryption
+    //!
+    //! **WARNING**: this disables all of the cryptographic protections of QUIC. It should only be
+    //! used for benchmarking or fuzzing the transport, and must never be used in production.
+
+    pub use s2n_quic_core::crypto::tls::null::Endpoint;
+
+    /// A TLS provider which only exchanges transport parameters and leaves packets unprotected
+    ///
+    /// Both peers must be configured with the null provider to establish a connection.
+    #[derive(Debug, Default)]
+    pub struct Provider(());
+
+    impl super::Provider for Provider {
+        type Server = Endpoint;
+        type Client = Endpoint;
+        type Error = core::convert::Infallible;
+
+        fn start_server(self) -> Result<Self::Server, Self::Error> {
+            Ok(Endpoint::default())
+        }
+
+        fn start_client(self) -> Result<Self::Client, Self::Error> {
+            Ok(Endpoint::default())
+        }
+    }
+}
+
+#[cfg(any(test, feature = "unstable-provider-tls-fault-injection"))]
+pub mod fault;
+
 #[cfg(feature = "s2n-quic-rustls")]
 pub mod rustls {
     //! Provides the [rustls](https://docs.rs/rustls/) implementation of TLS
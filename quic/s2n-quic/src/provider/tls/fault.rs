@@ -0,0 +1,612 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Injects failures into the handshake of another TLS provider
+//!
+//! This is intended for integration testing the error handling paths of applications against
+//! realistic TLS failures, such as rejected certificates, stalled flights, and oversized messages.
+//! It must never be enabled in production deployments.
+//!
+//! # Examples
+//!
+//! Reject the server's certificate on the client:
+//!
+//! ```rust,ignore
+//! use s2n_quic::{
+//!     provider::tls::fault::{Error, Fault, Point, Provider},
+//!     Client,
+//! };
+//!
+//! let tls = Provider::new("./certs/cert.pem")
+//!     .with_fault(Point::HandshakeFlight, Fault::Alert(Error::BAD_CERTIFICATE));
+//!
+//! let client = Client::builder().with_tls(tls)?.start()?;
+//! ```
+
+use bytes::Bytes;
+use core::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Poll, Waker},
+    time::Duration,
+};
+pub use s2n_quic_core::crypto::tls::Error;
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::{
+        tls::{self, ApplicationParameters, HandshakeType, HelloOffsets, ResumptionStatus},
+        CryptoSuite,
+    },
+    transport,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// A point in the handshake at which a fault can be injected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Point {
+    /// The first flight sent in the Initial packet space
+    ///
+    /// This is the ClientHello for clients and the ServerHello for servers.
+    InitialFlight,
+    /// The first flight sent in the Handshake packet space
+    ///
+    /// For servers, this flight carries the certificate. For clients, it's sent after the
+    /// server's certificate was verified and carries the client's Finished message.
+    HandshakeFlight,
+}
+
+/// The failure injected at a [`Point`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Fault {
+    /// Fails the handshake with the provided TLS alert instead of sending the flight
+    Alert(Error),
+    /// Holds back the flight, and anything sent after it, until [`Handle::release`] is called
+    Hold,
+    /// Holds back the flight, and anything sent after it, for the provided duration
+    ///
+    /// The delay is measured with the system clock, so it's not suited for simulated IO
+    /// providers. Use [`Fault::Hold`] instead to control the delay from the test.
+    Delay(Duration),
+    /// Appends a handshake message with a payload of `len` bytes to the flight
+    Oversize { len: usize },
+}
+
+/// The handshake message type used for oversized messages
+///
+/// This is a reserved value which isn't assigned to any TLS handshake message.
+const OVERSIZED_MESSAGE_TYPE: u8 = 0xff;
+
+#[derive(Debug, Default)]
+struct Shared {
+    /// The number of times [`Handle::release`] has been called
+    releases: AtomicU64,
+    /// The wakers of sessions which are holding back a flight
+    wakers: Mutex<Vec<Waker>>,
+}
+
+/// Releases flights held back by [`Fault::Hold`]
+#[derive(Clone, Debug)]
+pub struct Handle {
+    shared: Arc<Shared>,
+}
+
+impl Handle {
+    /// Sends all of the flights which are currently held back by the provider's sessions
+    pub fn release(&self) {
+        self.shared.releases.fetch_add(1, Ordering::SeqCst);
+
+        let wakers = core::mem::take(&mut *self.shared.wakers.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Wraps a TLS provider and injects the configured faults into its handshakes
+#[derive(Debug)]
+pub struct Provider<P> {
+    inner: P,
+    faults: Vec<(Point, Fault)>,
+    shared: Arc<Shared>,
+}
+
+impl<P: super::Provider> Provider<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            faults: Vec::new(),
+            shared: Default::default(),
+        }
+    }
+
+    /// Injects the `fault` when the handshake reaches the `point`
+    ///
+    /// Only the first fault configured for a point is injected.
+    pub fn with_fault(mut self, point: Point, fault: Fault) -> Self {
+        self.faults.push((point, fault));
+        self
+    }
+
+    /// Returns a handle which releases the flights held back by [`Fault::Hold`]
+    pub fn handle(&self) -> Handle {
+        Handle {
+            shared: self.shared.clone(),
+        }
+    }
+
+    fn wrap<E>(faults: Vec<(Point, Fault)>, shared: Arc<Shared>, inner: E) -> Endpoint<E> {
+        Endpoint {
+            inner,
+            faults: faults.into(),
+            shared,
+        }
+    }
+}
+
+impl<P: super::Provider> super::Provider for Provider<P> {
+    type Server = Endpoint<P::Server>;
+    type Client = Endpoint<P::Client>;
+    type Error = P::Error;
+
+    fn start_server(self) -> Result<Self::Server, Self::Error> {
+        let inner = self.inner.start_server()?;
+        Ok(Self::wrap(self.faults, self.shared, inner))
+    }
+
+    fn start_client(self) -> Result<Self::Client, Self::Error> {
+        let inner = self.inner.start_client()?;
+        Ok(Self::wrap(self.faults, self.shared, inner))
+    }
+
+    fn start_fips_server(self) -> Result<Self::Server, super::FipsError> {
+        let inner = self.inner.start_fips_server()?;
+        Ok(Self::wrap(self.faults, self.shared, inner))
+    }
+
+    fn start_fips_client(self) -> Result<Self::Client, super::FipsError> {
+        let inner = self.inner.start_fips_client()?;
+        Ok(Self::wrap(self.faults, self.shared, inner))
+    }
+}
+
+/// A TLS endpoint which injects faults into the sessions of the wrapped endpoint
+#[derive(Debug)]
+pub struct Endpoint<E> {
+    inner: E,
+    faults: Arc<[(Point, Fault)]>,
+    shared: Arc<Shared>,
+}
+
+impl<E> Endpoint<E> {
+    fn session<S>(&self, inner: S) -> Session<S> {
+        Session {
+            inner,
+            faults: self.faults.clone(),
+            shared: self.shared.clone(),
+            state: State::default(),
+        }
+    }
+}
+
+impl<E: tls::Endpoint> tls::Endpoint for Endpoint<E> {
+    type Session = Session<E::Session>;
+
+    fn new_server_session<Params: s2n_codec::EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        let inner = self.inner.new_server_session(transport_parameters);
+        self.session(inner)
+    }
+
+    fn new_client_session<Params: s2n_codec::EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+        server_name: ServerName,
+    ) -> Self::Session {
+        let inner = self
+            .inner
+            .new_client_session(transport_parameters, server_name);
+        self.session(inner)
+    }
+
+    fn max_tag_length(&self) -> usize {
+        self.inner.max_tag_length()
+    }
+
+    fn is_fips_compliant(&self) -> bool {
+        self.inner.is_fips_compliant()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Space {
+    Initial,
+    Handshake,
+    Application,
+}
+
+/// The condition which releases the held flights
+#[derive(Clone, Copy, Debug)]
+enum Release {
+    /// Released once [`Handle::release`] was called more times than the recorded count
+    Handle(u64),
+    /// Released once the deadline has passed
+    Deadline(Instant),
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// The points which have already been reached
+    initial_reached: bool,
+    handshake_reached: bool,
+    /// The error to return once the wrapped session yields
+    error: Option<transport::Error>,
+    /// The messages which are being held back, in the order they were sent
+    held: Vec<(Space, Bytes)>,
+    release: Option<Release>,
+    /// Set when the handshake completed while messages were held back
+    is_complete_held: bool,
+    /// Set once the wrapped session completed the handshake
+    is_complete: bool,
+}
+
+impl State {
+    fn is_released(&self, shared: &Shared) -> bool {
+        match self.release {
+            Some(Release::Handle(count)) => shared.releases.load(Ordering::SeqCst) > count,
+            Some(Release::Deadline(deadline)) => Instant::now() >= deadline,
+            None => true,
+        }
+    }
+}
+
+/// A TLS session which injects faults into the handshake of the wrapped session
+pub struct Session<S> {
+    inner: S,
+    faults: Arc<[(Point, Fault)]>,
+    shared: Arc<Shared>,
+    state: State,
+}
+
+impl<S: fmt::Debug> fmt::Debug for Session<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("inner", &self.inner)
+            .field("faults", &self.faults)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<S: CryptoSuite> CryptoSuite for Session<S> {
+    type HandshakeKey = S::HandshakeKey;
+    type HandshakeHeaderKey = S::HandshakeHeaderKey;
+    type InitialKey = S::InitialKey;
+    type InitialHeaderKey = S::InitialHeaderKey;
+    type OneRttKey = S::OneRttKey;
+    type OneRttHeaderKey = S::OneRttHeaderKey;
+    type ZeroRttKey = S::ZeroRttKey;
+    type ZeroRttHeaderKey = S::ZeroRttHeaderKey;
+    type RetryKey = S::RetryKey;
+}
+
+impl<S: tls::Session> Session<S> {
+    /// Sends the held messages once they've been released
+    ///
+    /// Returns `false` if the messages are still being held back.
+    fn poll_release<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Result<bool, transport::Error> {
+        if self.state.held.is_empty() && !self.state.is_complete_held {
+            return Ok(true);
+        }
+
+        if !self.state.is_released(&self.shared) {
+            if matches!(self.state.release, Some(Release::Handle(_))) {
+                let waker = context.waker().clone();
+                self.shared.wakers.lock().unwrap().push(waker);
+            }
+            return Ok(false);
+        }
+
+        self.state.release = None;
+
+        for (space, message) in self.state.held.drain(..) {
+            match space {
+                Space::Initial => context.send_initial(message),
+                Space::Handshake => context.send_handshake(message),
+                Space::Application => context.send_application(message),
+            }
+        }
+
+        if core::mem::take(&mut self.state.is_complete_held) {
+            context.on_handshake_complete()?;
+        }
+
+        Ok(true)
+    }
+}
+
+impl<S: tls::Session> tls::Session for Session<S> {
+    fn poll<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Poll<Result<(), transport::Error>> {
+        if let Some(error) = self.state.error {
+            return Poll::Ready(Err(error));
+        }
+
+        match self.poll_release(context) {
+            Ok(true) => {}
+            Ok(false) => return Poll::Pending,
+            Err(error) => return Poll::Ready(Err(error)),
+        }
+
+        // the wrapped session already finished so don't poll it again
+        if self.state.is_complete {
+            return Poll::Ready(Ok(()));
+        }
+
+        let mut faulty_context = FaultyContext {
+            context,
+            faults: &self.faults,
+            shared: &self.shared,
+            state: &mut self.state,
+        };
+
+        let outcome = self.inner.poll(&mut faulty_context);
+
+        if let Some(error) = self.state.error {
+            return Poll::Ready(Err(error));
+        }
+
+        if matches!(outcome, Poll::Ready(Ok(()))) {
+            self.state.is_complete = true;
+        }
+
+        match self.poll_release(context) {
+            Ok(true) => outcome,
+            // keep the handshake going until the held messages are sent
+            Ok(false) if outcome.is_ready() && !self.state.is_complete => outcome,
+            Ok(false) => Poll::Pending,
+            Err(error) => Poll::Ready(Err(error)),
+        }
+    }
+
+    fn process_post_handshake_message<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Result<(), transport::Error> {
+        let mut faulty_context = FaultyContext {
+            context,
+            faults: &[],
+            shared: &self.shared,
+            state: &mut self.state,
+        };
+        self.inner
+            .process_post_handshake_message(&mut faulty_context)
+    }
+
+    fn should_discard_session(&self) -> bool {
+        self.inner.should_discard_session()
+    }
+
+    fn pin_spki(&mut self, pins: tls::pin::SpkiPins) -> Result<(), Error> {
+        self.inner.pin_spki(pins)
+    }
+
+    fn parse_hello(
+        msg_type: HandshakeType,
+        header_chunk: &[u8],
+        total_received_len: u64,
+        max_hello_size: u64,
+    ) -> Result<Option<HelloOffsets>, transport::Error> {
+        S::parse_hello(msg_type, header_chunk, total_received_len, max_hello_size)
+    }
+}
+
+/// Wraps the transport's context and applies the faults to the messages sent by the session
+struct FaultyContext<'a, C> {
+    context: &'a mut C,
+    faults: &'a [(Point, Fault)],
+    shared: &'a Shared,
+    state: &'a mut State,
+}
+
+impl<'a, C> FaultyContext<'a, C> {
+    /// Returns the fault for the `point` if it's being reached for the first time
+    fn on_point(&mut self, point: Point) -> Option<Fault> {
+        let reached = match point {
+            Point::InitialFlight => &mut self.state.initial_reached,
+            Point::HandshakeFlight => &mut self.state.handshake_reached,
+        };
+
+        if core::mem::replace(reached, true) {
+            return None;
+        }
+
+        self.faults
+            .iter()
+            .find(|(p, _)| *p == point)
+            .map(|(_, fault)| *fault)
+    }
+
+    fn is_holding(&self) -> bool {
+        !self.state.held.is_empty()
+    }
+}
+
+impl<'a, S, C> FaultyContext<'a, C>
+where
+    S: tls::Session,
+    C: tls::Context<Session<S>>,
+{
+    fn send(&mut self, space: Space, point: Option<Point>, message: Bytes) {
+        if self.state.error.is_some() {
+            return;
+        }
+
+        let fault = point.and_then(|point| self.on_point(point));
+
+        match fault {
+            Some(Fault::Alert(error)) => {
+                self.state.error = Some(error.into());
+                return;
+            }
+            Some(Fault::Hold) => {
+                let count = self.shared.releases.load(Ordering::SeqCst);
+                self.state.release = Some(Release::Handle(count));
+            }
+            Some(Fault::Delay(delay)) => {
+                self.state.release = Some(Release::Deadline(Instant::now() + delay));
+
+                let waker = self.context.waker().clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(delay);
+                    waker.wake();
+                });
+            }
+            Some(Fault::Oversize { len }) => {
+                self.forward(space, message);
+
+                let header_len = core::mem::size_of::<u32>();
+                let mut oversized = vec![0; header_len + len];
+                let len = (len as u32).to_be_bytes();
+                oversized[0] = OVERSIZED_MESSAGE_TYPE;
+                oversized[1..header_len].copy_from_slice(&len[1..]);
+                self.forward(space, oversized.into());
+                return;
+            }
+            None => {}
+        }
+
+        self.forward(space, message);
+    }
+
+    fn forward(&mut self, space: Space, message: Bytes) {
+        // preserve the order of the messages once they're being held back
+        if self.is_holding() || self.state.release.is_some() {
+            self.state.held.push((space, message));
+            return;
+        }
+
+        match space {
+            Space::Initial => self.context.send_initial(message),
+            Space::Handshake => self.context.send_handshake(message),
+            Space::Application => self.context.send_application(message),
+        }
+    }
+}
+
+impl<'a, S, C> tls::Context<S> for FaultyContext<'a, C>
+where
+    S: tls::Session,
+    C: tls::Context<Session<S>>,
+{
+    fn on_handshake_keys(
+        &mut self,
+        key: S::HandshakeKey,
+        header_key: S::HandshakeHeaderKey,
+    ) -> Result<(), transport::Error> {
+        self.context.on_handshake_keys(key, header_key)
+    }
+
+    fn on_zero_rtt_keys(
+        &mut self,
+        key: S::ZeroRttKey,
+        header_key: S::ZeroRttHeaderKey,
+        application_parameters: ApplicationParameters,
+    ) -> Result<(), transport::Error> {
+        self.context
+            .on_zero_rtt_keys(key, header_key, application_parameters)
+    }
+
+    fn on_one_rtt_keys(
+        &mut self,
+        key: S::OneRttKey,
+        header_key: S::OneRttHeaderKey,
+        application_parameters: ApplicationParameters,
+    ) -> Result<(), transport::Error> {
+        self.context
+            .on_one_rtt_keys(key, header_key, application_parameters)
+    }
+
+    fn on_server_name(&mut self, server_name: ServerName) -> Result<(), transport::Error> {
+        self.context.on_server_name(server_name)
+    }
+
+    fn on_application_protocol(
+        &mut self,
+        application_protocol: Bytes,
+    ) -> Result<(), transport::Error> {
+        self.context.on_application_protocol(application_protocol)
+    }
+
+    fn on_resumption_status(&mut self, status: ResumptionStatus) -> Result<(), transport::Error> {
+        self.context.on_resumption_status(status)
+    }
+
+    fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
+        // the handshake can't complete before the held flights have been sent
+        if self.is_holding() {
+            self.state.is_complete_held = true;
+            return Ok(());
+        }
+
+        self.context.on_handshake_complete()
+    }
+
+    fn on_tls_exporter_ready(
+        &mut self,
+        session: &impl tls::TlsSession,
+    ) -> Result<(), transport::Error> {
+        self.context.on_tls_exporter_ready(session)
+    }
+
+    fn receive_initial(&mut self, max_len: Option<usize>) -> Option<Bytes> {
+        self.context.receive_initial(max_len)
+    }
+
+    fn receive_handshake(&mut self, max_len: Option<usize>) -> Option<Bytes> {
+        self.context.receive_handshake(max_len)
+    }
+
+    fn receive_application(&mut self, max_len: Option<usize>) -> Option<Bytes> {
+        self.context.receive_application(max_len)
+    }
+
+    fn can_send_initial(&self) -> bool {
+        self.context.can_send_initial()
+    }
+
+    fn send_initial(&mut self, transmission: Bytes) {
+        self.send(Space::Initial, Some(Point::InitialFlight), transmission)
+    }
+
+    fn can_send_handshake(&self) -> bool {
+        self.context.can_send_handshake()
+    }
+
+    fn send_handshake(&mut self, transmission: Bytes) {
+        self.send(Space::Handshake, Some(Point::HandshakeFlight), transmission)
+    }
+
+    fn can_send_application(&self) -> bool {
+        self.context.can_send_application()
+    }
+
+    fn send_application(&mut self, transmission: Bytes) {
+        self.send(Space::Application, None, transmission)
+    }
+
+    fn waker(&self) -> &Waker {
+        self.context.waker()
+    }
+}
@@ -5,9 +5,11 @@ use crate::{connection::Connection, provider::*};
 use core::{
     fmt,
     task::{Context, Poll},
+    time::Duration,
 };
-use s2n_quic_transport::endpoint::handle::Acceptor;
+use s2n_quic_transport::endpoint::{close::Drain, handle::Acceptor};
 
+pub mod accept;
 mod builder;
 mod providers;
 
@@ -19,6 +21,7 @@ pub use s2n_quic_core::application::ServerName as Name;
 pub struct Server {
     acceptor: Acceptor,
     local_addr: s2n_quic_core::inet::SocketAddress,
+    lanes: Option<accept::Lanes>,
 }
 
 impl fmt::Debug for Server {
@@ -30,6 +33,14 @@ impl fmt::Debug for Server {
 }
 
 impl Server {
+    pub(crate) fn new(acceptor: Acceptor, local_addr: s2n_quic_core::inet::SocketAddress) -> Self {
+        Self {
+            acceptor,
+            local_addr,
+            lanes: None,
+        }
+    }
+
     /// Returns a [`Builder`] which is able to configure the [`Server`] components.
     ///
     /// # Examples
@@ -94,6 +105,27 @@ impl Server {
     /// - `Poll::Ready(None)` the attempt failed because the server has closed. Once
     /// None is returned, this function should not be called again.
     pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
+        if let Some(lanes) = self.lanes.as_mut() {
+            // drain all of the established connections so the backlog can be prioritized
+            let mut is_closed = false;
+            loop {
+                match self.acceptor.poll_accept(cx) {
+                    Poll::Ready(Some(connection)) => lanes.push(Connection::new(connection)),
+                    Poll::Ready(None) => {
+                        is_closed = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+
+            return match lanes.pop() {
+                Some(connection) => Poll::Ready(Some(connection)),
+                None if is_closed => Poll::Ready(None),
+                None => Poll::Pending,
+            };
+        }
+
         match self.acceptor.poll_accept(cx) {
             Poll::Ready(Some(connection)) => Poll::Ready(Some(Connection::new(connection))),
             Poll::Ready(None) => Poll::Ready(None),
@@ -101,6 +133,62 @@ impl Server {
         }
     }
 
+    /// Prioritizes accepted connections with the given [`accept::Lanes`]
+    ///
+    /// Any connections which were already prioritized by previous lanes are moved into the new
+    /// lanes.
+    ///
+    /// See the [`accept`] module for more details.
+    pub fn set_accept_lanes(&mut self, mut lanes: accept::Lanes) {
+        if let Some(mut prev) = self.lanes.take() {
+            while let Some(connection) = prev.pop() {
+                lanes.push(connection);
+            }
+        }
+        self.lanes = Some(lanes);
+    }
+
+    /// Stops accepting new connections and waits for the existing connections to close
+    ///
+    /// Connection attempts from new peers are ignored once the drain starts. Existing
+    /// connections are given until `timeout` to finish on their own, after which any remaining
+    /// connections are closed with `error`. The returned future resolves once all of the
+    /// connections have been removed and the server has shut down.
+    ///
+    /// Connections which finished the handshake before the drain started can still be accepted
+    /// until the server has shut down.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path, time::Duration};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// const SHUTTING_DOWN: u32 = 1;
+    ///
+    /// server
+    ///     .drain(Duration::from_secs(30), SHUTTING_DOWN.into())
+    ///     .await?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn drain(
+        &mut self,
+        timeout: Duration,
+        error: crate::application::Error,
+    ) -> Result<(), crate::connection::Error> {
+        let drain = Drain { timeout, error };
+        futures::future::poll_fn(|cx| self.acceptor.poll_drain(cx, drain)).await
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port `0` to figure out which
@@ -126,6 +214,92 @@ impl Server {
     pub fn local_addr(&self) -> Result<std::net::SocketAddr, std::io::Error> {
         Ok(self.local_addr.into())
     }
+
+    /// Returns the delivery statistics aggregated over all of the connections on the server
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let statistics = server.statistics();
+    /// println!("duplicate packets: {}", statistics.duplicate_packets);
+    ///
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn statistics(&self) -> crate::connection::Statistics {
+        self.acceptor.statistics()
+    }
+
+    /// Returns the occupancy of the server's connection ID maps
+    ///
+    /// An uneven distribution of entries across the shards, or a growing number of collisions,
+    /// can indicate that peers are attempting to reuse connection IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let metrics = server.id_map_metrics();
+    /// println!("registered connection IDs: {}", metrics.local_ids.len);
+    ///
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn id_map_metrics(&self) -> crate::connection::IdMapMetrics {
+        self.acceptor.id_map_metrics()
+    }
+
+    /// Returns a stream of the connections which are removed from the server after this call
+    ///
+    /// A connection is removed once it has finished closing or draining, even if the application
+    /// dropped its [`Connection`] handle early. Each notification includes the error which
+    /// closed the connection and its final statistics, which can be used to reliably release any
+    /// resources associated with the connection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// # use futures::StreamExt;
+    /// #
+    /// # async fn closed() -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let mut closed = server.closed_connections();
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(connection) = closed.next().await {
+    ///         println!("connection {} closed: {}", connection.id, connection.error);
+    ///     }
+    /// });
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn closed_connections(&self) -> crate::connection::ClosedConnections {
+        self.acceptor.closed_connections()
+    }
 }
 
 impl futures::stream::Stream for Server {
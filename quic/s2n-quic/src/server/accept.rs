@@ -0,0 +1,244 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prioritized lanes for accepting connections
+//!
+//! By default, connections are returned from [`Server::accept`](crate::Server::accept) in the
+//! order they completed the handshake. When the application falls behind, a backlog of
+//! connections can build up, in which case it may be preferable to surface some connections
+//! first, e.g. resumed connections from interactive clients over batch jobs sharing the same
+//! endpoint.
+//!
+//! [`Lanes`] classifies each connection into a lane and serves the lanes with weighted round
+//! robin scheduling. Within each round, lanes are served in order of priority, with each lane
+//! surfacing up to its weight in connections. This ensures lower priority lanes still make
+//! progress while higher priority lanes are backlogged.
+
+use crate::connection::Connection;
+use core::fmt;
+use std::collections::VecDeque;
+
+/// Assigns accepted connections to lanes
+pub trait Classifier: 'static + Send {
+    /// Returns the index of the lane for the connection
+    ///
+    /// Lanes with lower indexes have higher priority. Indexes beyond the last lane are placed in
+    /// the last lane.
+    fn classify(&mut self, connection: &Connection) -> usize;
+}
+
+impl<F> Classifier for F
+where
+    F: 'static + Send + FnMut(&Connection) -> usize,
+{
+    #[inline]
+    fn classify(&mut self, connection: &Connection) -> usize {
+        (self)(connection)
+    }
+}
+
+/// Prioritizes accepted connections into lanes
+///
+/// # Examples
+///
+/// Resumed connections are surfaced ahead of new connections, with up to 4 resumed connections
+/// for every new connection.
+///
+/// ```rust,no_run
+/// # use std::{error::Error, path::Path};
+/// use s2n_quic::{server::accept::Lanes, Server};
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let mut server = Server::builder()
+///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+///     .with_io("127.0.0.1:443")?
+///     .start()?;
+///
+/// server.set_accept_lanes(Lanes::new([4, 1], |connection: &s2n_quic::Connection| {
+///     if connection.is_resumed().unwrap_or(false) {
+///         0
+///     } else {
+///         1
+///     }
+/// }));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub struct Lanes {
+    classifier: Box<dyn Classifier>,
+    scheduler: Scheduler<Connection>,
+}
+
+impl fmt::Debug for Lanes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lanes")
+            .field("scheduler", &self.scheduler)
+            .finish()
+    }
+}
+
+impl Lanes {
+    /// Creates lanes with the given weights, in order of priority
+    ///
+    /// Each weight is the number of connections the lane surfaces in each round. A weight of `0`
+    /// is treated as `1` so that every lane makes progress.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no weights are provided
+    pub fn new<W, C>(weights: W, classifier: C) -> Self
+    where
+        W: IntoIterator<Item = u32>,
+        C: Classifier,
+    {
+        Self {
+            classifier: Box::new(classifier),
+            scheduler: Scheduler::new(weights),
+        }
+    }
+
+    /// Returns the number of connections waiting to be accepted
+    pub fn len(&self) -> usize {
+        self.scheduler.len()
+    }
+
+    /// Returns `true` if no connections are waiting to be accepted
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, connection: Connection) {
+        let lane = self.classifier.classify(&connection);
+        self.scheduler.push(lane, connection);
+    }
+
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<Connection> {
+        self.scheduler.pop()
+    }
+}
+
+#[derive(Debug)]
+struct Scheduler<T> {
+    lanes: Vec<Lane<T>>,
+}
+
+#[derive(Debug)]
+struct Lane<T> {
+    weight: u32,
+    /// The number of entries the lane can still surface in the current round
+    credits: u32,
+    queue: VecDeque<T>,
+}
+
+impl<T> Scheduler<T> {
+    fn new<W: IntoIterator<Item = u32>>(weights: W) -> Self {
+        let lanes: Vec<_> = weights
+            .into_iter()
+            .map(|weight| {
+                let weight = weight.max(1);
+                Lane {
+                    weight,
+                    credits: weight,
+                    queue: VecDeque::new(),
+                }
+            })
+            .collect();
+
+        assert!(!lanes.is_empty(), "at least one lane is required");
+
+        Self { lanes }
+    }
+
+    fn len(&self) -> usize {
+        self.lanes.iter().map(|lane| lane.queue.len()).sum()
+    }
+
+    fn push(&mut self, lane: usize, value: T) {
+        let lane = lane.min(self.lanes.len() - 1);
+        self.lanes[lane].queue.push_back(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        // at most two passes are required, since every lane has credits after the round is reset
+        for _ in 0..2 {
+            for lane in self.lanes.iter_mut() {
+                if lane.credits == 0 {
+                    continue;
+                }
+
+                if let Some(value) = lane.queue.pop_front() {
+                    lane.credits -= 1;
+                    return Some(value);
+                }
+            }
+
+            if self.lanes.iter().all(|lane| lane.queue.is_empty()) {
+                return None;
+            }
+
+            // every lane with pending entries has used its credits so start a new round
+            for lane in self.lanes.iter_mut() {
+                lane.credits = lane.weight;
+            }
+        }
+
+        unreachable!("the second pass should always find an entry")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(scheduler: &mut Scheduler<u32>) -> Vec<u32> {
+        core::iter::from_fn(|| scheduler.pop()).collect()
+    }
+
+    #[test]
+    fn priority_test() {
+        let mut scheduler = Scheduler::new([2, 1]);
+
+        for value in 0..3 {
+            scheduler.push(1, value);
+        }
+        for value in 10..15 {
+            scheduler.push(0, value);
+        }
+        assert_eq!(scheduler.len(), 8);
+
+        // the high priority lane is served first, but the low priority lane gets a turn each round
+        assert_eq!(drain(&mut scheduler), [10, 11, 0, 12, 13, 1, 14, 2]);
+        assert_eq!(scheduler.len(), 0);
+    }
+
+    #[test]
+    fn idle_lane_test() {
+        let mut scheduler = Scheduler::new([3, 1]);
+
+        // the low priority lane isn't held back when the high priority lane is empty
+        for value in 0..3 {
+            scheduler.push(1, value);
+        }
+        assert_eq!(drain(&mut scheduler), [0, 1, 2]);
+
+        // lanes beyond the last are placed in the last lane
+        scheduler.push(5, 20);
+        scheduler.push(0, 10);
+        assert_eq!(drain(&mut scheduler), [10, 20]);
+    }
+
+    #[test]
+    fn zero_weight_test() {
+        let mut scheduler = Scheduler::new([0, 0]);
+
+        scheduler.push(1, 0);
+        scheduler.push(1, 1);
+        scheduler.push(0, 10);
+        scheduler.push(0, 11);
+
+        assert_eq!(drain(&mut scheduler), [10, 0, 11, 1]);
+    }
+}
@@ -8,11 +8,11 @@ use crate::{
 
 /// A builder for configuring [`Server`] providers
 #[derive(Debug)]
-pub struct Builder<Providers>(pub(crate) Providers);
+pub struct Builder<Providers>(pub(crate) Providers, pub(crate) Settings);
 
 impl Default for Builder<DefaultProviders> {
     fn default() -> Self {
-        Self(Default::default())
+        Self(Default::default(), Default::default())
     }
 }
 
@@ -126,6 +126,31 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    impl_provider_method!(
+        /// Sets the path migration provider for the [`Server`]
+        ///
+        /// # Examples
+        ///
+        /// Denies all connection migration attempts
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Server, provider::path_migration};
+        /// #
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let server = Server::builder()
+        ///     .with_path_migration(path_migration::disabled::Validator)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_path_migration,
+        path_migration,
+        ServerProviders
+    );
+
     impl_provider_method!(
         /// Sets the endpoint limits provider for the [`Server`]
         ///
@@ -303,9 +328,30 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
-    #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Server`]
+        ///
+        /// The random provider is used for connection IDs, address tokens, path challenges,
+        /// and packet number skipping.
+        ///
+        /// # Examples
+        ///
+        /// Uses the AWS-LC DRBG, which is suitable for FIPS deployments
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Server, provider::random};
+        /// #
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "provider-random-drbg", not(target_os = "windows")))]
+        /// let server = Server::builder()
+        ///     .with_random(random::drbg::Provider::default())?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
         with_random,
         random,
         ServerProviders
@@ -326,6 +372,80 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    /// Shares the characteristics of paths between connections to the same remote prefix
+    ///
+    /// When a connection is removed, the RTT, bandwidth and MTU observed on its active path are
+    /// cached for the remote address prefix. New connections to the same prefix start with an RTT
+    /// estimate, initial MTU and congestion window based on these observations instead of the
+    /// conservative defaults. This mostly benefits workloads which open a new connection per
+    /// request to the same peers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::{connection::PathCache, Server};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cache = PathCache::new()
+    ///     .with_capacity(4096)?
+    ///     .with_prefix_len(24, 48)?;
+    ///
+    /// let builder = Server::builder().with_path_cache(cache)?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn with_path_cache(
+        mut self,
+        cache: crate::connection::PathCache,
+    ) -> Result<Self, StartError> {
+        self.1.path_cache = Some(cache);
+        Ok(self)
+    }
+
+    /// Recycles the buffers of packet spaces between connections on the [`Server`]
+    ///
+    /// Each connection allocates a crypto stream and a set of ACK ranges per packet number space.
+    /// When a pool is configured, these buffers are cleared and reused by new connections instead
+    /// of being released when a space is discarded. Up to `capacity` of each kind of buffer is
+    /// retained, which reduces the pressure on the allocator for endpoints with high connection
+    /// churn. A `capacity` of `0`, which is the default, disables the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::Server;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let builder = Server::builder().with_packet_space_pool(1024)?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn with_packet_space_pool(mut self, capacity: usize) -> Result<Self, StartError> {
+        self.1.packet_space_pool = capacity;
+        Ok(self)
+    }
+
+    /// Restricts the [`Server`] to FIPS-approved algorithms
+    ///
+    /// The crypto backend must be a FIPS-validated module operating in FIPS mode. TLS providers
+    /// created from certificates, or the default TLS provider, are built in FIPS mode. A TLS
+    /// provider that was configured separately must have enabled FIPS mode on its own builder,
+    /// otherwise starting the [`Server`] fails.
+    #[cfg(feature = "fips")]
+    pub fn with_fips(mut self, enabled: bool) -> Result<Self, StartError> {
+        if enabled && !s2n_quic_crypto::fips::is_enabled() {
+            return Err(StartError::new(
+                "the crypto backend is not operating in FIPS mode",
+            ));
+        }
+        self.1.fips = enabled;
+        Ok(self)
+    }
+
     /// Starts the [`Server`] with the configured providers
     ///
     /// # Examples
@@ -345,6 +465,6 @@ impl<Providers: ServerProviders> Builder<Providers> {
     /// # }
     /// ```
     pub fn start(self) -> Result<Server, StartError> {
-        self.0.build().start()
+        self.0.build().start(self.1)
     }
 }
@@ -65,7 +65,23 @@ impl<
         Datagram,
     >
 {
-    pub fn start(self) -> Result<Server, StartError> {
+    pub fn start(self, settings: Settings) -> Result<Server, StartError> {
+        let (endpoint_config, io) = self.into_endpoint_config::<IO::PathHandle>(settings)?;
+
+        let (endpoint, acceptor) = endpoint::Endpoint::new_server(endpoint_config);
+
+        // Start the IO last
+        let local_addr = io.start(endpoint).map_err(StartError::new)?;
+
+        Ok(Server::new(acceptor, local_addr))
+    }
+
+    /// Starts all of the providers, except for the IO provider, and returns the endpoint
+    /// configuration along with the IO provider
+    pub(crate) fn into_endpoint_config<PathHandle: path::Handle>(
+        self,
+        settings: Settings,
+    ) -> Result<(impl endpoint::Config<PathHandle = PathHandle>, IO), StartError> {
         let Self {
             congestion_controller,
             connection_close_formatter,
@@ -98,7 +114,12 @@ impl<
         let address_token = address_token.start().map_err(StartError::new)?;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = path_migration.start().map_err(StartError::new)?;
-        let tls = tls.start_server().map_err(StartError::new)?;
+        let tls = if settings.fips {
+            tls.start_fips_server().map_err(StartError::new)?
+        } else {
+            tls.start_server().map_err(StartError::new)?
+        };
+        settings.validate_tls(&tls)?;
         let datagram = datagram.start().map_err(StartError::new)?;
 
         // Validate providers
@@ -129,17 +150,11 @@ impl<
             path_handle: PhantomData,
             path_migration,
             datagram,
+            path_cache: settings.path_cache,
+            packet_space_pool: settings.packet_space_pool,
         };
 
-        let (endpoint, acceptor) = endpoint::Endpoint::new_server(endpoint_config);
-
-        // Start the IO last
-        let local_addr = io.start(endpoint).map_err(StartError::new)?;
-
-        Ok(Server {
-            acceptor,
-            local_addr,
-        })
+        Ok((endpoint_config, io))
     }
 }
 
@@ -176,6 +191,8 @@ struct EndpointConfig<
     path_handle: PhantomData<PathHandle>,
     path_migration: PathMigration,
     datagram: Datagram,
+    path_cache: Option<path::cache::Config>,
+    packet_space_pool: usize,
 }
 
 impl<
@@ -291,4 +308,12 @@ impl<
             datagram: &mut self.datagram,
         }
     }
+
+    fn path_cache(&self) -> Option<path::cache::Config> {
+        self.path_cache
+    }
+
+    fn packet_space_pool_capacity(&self) -> usize {
+        self.packet_space_pool
+    }
 }
@@ -15,7 +15,13 @@ mod bidirectional;
 mod local;
 mod peer;
 
-pub use s2n_quic_core::stream::{StreamError as Error, StreamType as Type};
+pub use s2n_quic_core::stream::{
+    ops::{
+        rx::Offsets as ReceiveOffsets,
+        tx::{BufferStats, Offsets as SendOffsets, Stats as SendStats},
+    },
+    StreamError as Error, StreamId as Id, StreamType as Type,
+};
 
 pub use bidirectional::*;
 pub use local::*;
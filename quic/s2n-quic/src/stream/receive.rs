@@ -153,6 +153,52 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Receives a chunk of data from the stream without blocking the task.
+        ///
+        /// Unlike [`poll_receive`](Self::poll_receive), no [`Waker`](core::task::Waker) is
+        /// registered, so the caller is responsible for retrying at a later time.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(Some(chunk))` if the stream is open and data was available.
+        /// - `Ok(None)` if the stream was finished and all of the data was consumed.
+        /// - `Err(ReceivingBlocked)` if the stream is waiting to receive data from the peer.
+        ///   This error converts to [`std::io::ErrorKind::WouldBlock`].
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// match stream.try_receive() {
+        ///     Ok(Some(chunk)) => println!("received: {:?}", chunk),
+        ///     Ok(None) => println!("finished"),
+        ///     Err(s2n_quic::stream::Error::ReceivingBlocked { .. }) => println!("no data yet"),
+        ///     Err(e) => return Err(e),
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn try_receive(&mut self) -> $crate::stream::Result<Option<bytes::Bytes>> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable())
+                };
+                ($variant: expr) => {
+                    $variant.try_receive()
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Notifies the peer to stop sending data on the stream.
         ///
         /// This requests the peer to finish the stream as soon as possible
@@ -204,6 +250,55 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Returns the offsets of the data received on the stream.
+        ///
+        /// [`ReceiveOffsets::consumed`](crate::stream::ReceiveOffsets::consumed) is the number of
+        /// bytes which have been returned to the application. If the connection is interrupted, an
+        /// application protocol can request the peer to resume the transfer from this offset on a
+        /// new connection.
+        ///
+        /// The offsets remain available after the stream or connection has closed, in which case
+        /// the offsets observed by the last operation on the stream are returned.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(offsets)` with the current offsets of the stream.
+        /// - `Err(e)` if the stream is not readable.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// while let Ok(Some(_chunk)) = stream.receive().await {
+        ///     // process the chunk
+        /// }
+        ///
+        /// // resume the transfer from where it was interrupted
+        /// let resume_from = stream.receive_offsets()?.consumed;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn receive_offsets(
+            &mut self,
+        ) -> $crate::stream::Result<$crate::stream::ReceiveOffsets> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable())
+                };
+                ($variant: expr) => {
+                    $variant.receive_offsets()
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Create a batch request for receiving data
         #[inline]
         pub(crate) fn rx_request(
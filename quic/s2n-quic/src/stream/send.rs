@@ -66,6 +66,79 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Enqueues a chunk of data which is tagged with `marker` for sending it towards the peer.
+        ///
+        /// While the chunk is in flight, the connection publishes a
+        /// [`StreamMarkerSent`](crate::provider::event::events::StreamMarkerSent) event for each
+        /// packet carrying any of its bytes, a
+        /// [`StreamMarkerLost`](crate::provider::event::events::StreamMarkerLost) event when one of
+        /// those packets is declared lost, and a
+        /// [`StreamMarkerAcknowledged`](crate::provider::event::events::StreamMarkerAcknowledged)
+        /// event once the peer has acknowledged all of the bytes.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(())` if the data was enqueued for sending.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let request_id = 42;
+        /// let data = bytes::Bytes::from_static(&[1, 2, 3, 4]);
+        /// stream.send_with_marker(data, request_id).await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn send_with_marker(
+            &mut self,
+            mut data: bytes::Bytes,
+            marker: u64,
+        ) -> $crate::stream::Result<()> {
+            ::futures::future::poll_fn(|cx| self.poll_send_with_marker(&mut data, marker, cx)).await
+        }
+
+        /// Enqueues a chunk of data which is tagged with `marker` for sending it towards the peer.
+        ///
+        /// See [`Self::send_with_marker`] for the events which are published for the chunk.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Poll::Pending` if the stream's send buffer capacity is currently exhausted. In this case,
+        ///   the caller should retry sending after the [`Waker`](core::task::Waker) on the provided
+        ///   [`Context`](core::task::Context) is notified.
+        /// - `Poll::Ready(Ok(()))` if the data was enqueued for sending. The provided `chunk` will
+        ///   be replaced with an empty [`Bytes`](bytes::Bytes).
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn poll_send_with_marker(
+            &mut self,
+            chunk: &mut bytes::Bytes,
+            marker: u64,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<()>> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable()).into()
+                };
+                ($variant: expr) => {
+                    $variant.poll_send_with_marker(chunk, marker, cx)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Enqueues a slice of chunks of data for sending it towards the peer.
         ///
         /// # Return value
@@ -199,6 +272,53 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Enqueues a chunk of data for sending it towards the peer without blocking the task.
+        ///
+        /// Unlike [`poll_send`](Self::poll_send), no [`Waker`](core::task::Waker) is registered,
+        /// so the caller is responsible for retrying at a later time.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(())` if the data was enqueued for sending. The provided `chunk` will
+        ///   be replaced with an empty [`Bytes`](bytes::Bytes).
+        /// - `Err(SendingBlocked)` if the stream's send buffer capacity is currently exhausted.
+        ///   The provided `chunk` is left untouched. This error converts to
+        ///   [`std::io::ErrorKind::WouldBlock`].
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let mut data = bytes::Bytes::from_static(&[1, 2, 3, 4]);
+        /// match stream.try_send(&mut data) {
+        ///     Ok(()) => println!("sent"),
+        ///     Err(s2n_quic::stream::Error::SendingBlocked { .. }) => println!("try again later"),
+        ///     Err(e) => return Err(e),
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn try_send(&mut self, chunk: &mut bytes::Bytes) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.try_send(chunk)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Flushes the stream and waits for the peer to receive all outstanding data.
         ///
         /// # Return value
@@ -227,6 +347,63 @@ macro_rules! impl_send_stream_api {
             ::futures::future::poll_fn(|cx| self.poll_flush(cx)).await
         }
 
+        /// Flushes the stream and waits for the peer to receive all outstanding data, or for the
+        /// `deadline` future to complete, whichever happens first.
+        ///
+        /// The `deadline` can be any future, such as a timer from the application's runtime. This
+        /// allows the application to bound how long it waits on a message before deciding to keep
+        /// waiting, [`reset`](Self::reset) the stream, or give up on it.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(stats)` with the usage of the send buffer once the flush completes or the deadline
+        ///   passes. [`BufferStats::remaining`](crate::stream::BufferStats::remaining) returns `0`
+        ///   if all of the data was acknowledged by the peer.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let data = bytes::Bytes::from_static(&[1, 2, 3, 4]);
+        /// stream.send(data).await?;
+        ///
+        /// let deadline = tokio::time::sleep(core::time::Duration::from_millis(100));
+        /// let stats = stream.flush_by(deadline).await?;
+        ///
+        /// if stats.remaining() > 0 {
+        ///     // the peer didn't receive the message in time
+        ///     stream.reset(123u8.into())?;
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn flush_by<D>(
+            &mut self,
+            deadline: D,
+        ) -> $crate::stream::Result<$crate::stream::BufferStats>
+        where
+            D: core::future::Future,
+        {
+            let mut deadline = core::pin::pin!(deadline);
+
+            ::futures::future::poll_fn(|cx| {
+                if let core::task::Poll::Ready(result) = self.poll_flush(cx) {
+                    return core::task::Poll::Ready(result);
+                }
+
+                core::future::Future::poll(deadline.as_mut(), cx).map(|_| Ok(()))
+            })
+            .await?;
+
+            self.buffer_stats()
+        }
+
         /// Polls flushing the stream and waits for the peer to receive all outstanding data.
         ///
         /// # Return value
@@ -256,6 +433,72 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Waits for the peer to acknowledge all of the data sent on the stream up to `offset`.
+        ///
+        /// Unlike [`flush`](Self::flush), this doesn't wait for the entire send buffer to be
+        /// acknowledged, which allows applications to issue "delivered" receipts for individual
+        /// messages while continuing to send more data. The offset of a message is the
+        /// [`enqueued`](crate::stream::SendOffsets::enqueued) offset returned by
+        /// [`send_offsets`](Self::send_offsets) after it was sent.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(acknowledged)` with the acknowledged offset of the stream, which is at least `offset`.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error) or
+        ///   finished without sending data up to `offset`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// stream.send(bytes::Bytes::from_static(b"message")).await?;
+        /// let offset = stream.send_offsets()?.enqueued;
+        ///
+        /// stream.acknowledged(offset).await?;
+        /// // at this point, the peer has received the message
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn acknowledged(&mut self, offset: u64) -> $crate::stream::Result<u64> {
+            ::futures::future::poll_fn(|cx| self.poll_acknowledged(offset, cx)).await
+        }
+
+        /// Polls for the peer to acknowledge all of the data sent on the stream up to `offset`.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Poll::Pending` if the data has not been acknowledged yet. In this case, the caller
+        ///   should retry after the [`Waker`](core::task::Waker) on the provided
+        ///   [`Context`](core::task::Context) is notified.
+        /// - `Poll::Ready(Ok(acknowledged))` with the acknowledged offset of the stream, which is
+        ///   at least `offset`.
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error)
+        ///   or finished without sending data up to `offset`.
+        #[inline]
+        pub fn poll_acknowledged(
+            &mut self,
+            offset: u64,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<u64>> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable()).into()
+                };
+                ($variant: expr) => {
+                    $variant.poll_acknowledged(offset, cx)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Marks the stream as finished.
         ///
         /// This method returns immediately without notifying the caller that all of the outstanding
@@ -376,6 +619,217 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Sets the maximum number of bytes buffered for sending on the stream.
+        ///
+        /// By default, each stream uses the send buffer size configured in the
+        /// [`Limits`](crate::provider::limits::Limits) provider. Latency-sensitive streams can use a
+        /// smaller buffer to apply back-pressure earlier, while bulk transfers can use a larger
+        /// buffer to keep more data in flight.
+        ///
+        /// Lowering the size below the amount of currently buffered data does not discard any data.
+        /// Instead, the stream will not accept more data until enough of it has been acknowledged.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the buffer size was updated.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// stream.set_buffer_size(16 * 1024)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_buffer_size(&mut self, size: u32) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_buffer_size(size)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Sets the transmission priority of the stream.
+        ///
+        /// By default, all streams have a priority of `0` and share the connection's bandwidth in
+        /// a round-robin fashion. Streams with a higher priority are scheduled ahead of streams
+        /// with a lower priority, and are given `priority + 1` consecutive packets before yielding
+        /// to the other streams. This allows latency-sensitive streams, such as control messages,
+        /// to be transmitted before bulk transfers without starving them.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the priority was updated.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// stream.set_priority(7)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_priority(&mut self, priority: u8) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_priority(priority)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Returns the offsets of the data sent on the stream.
+        ///
+        /// [`SendOffsets::acknowledged`](crate::stream::SendOffsets::acknowledged) is the offset up
+        /// to which the peer has acknowledged all of the data. Note that the peer's application may
+        /// not have read all of it yet, so resuming a transfer should prefer the offset reported by
+        /// the receiving application, when available.
+        ///
+        /// The offsets remain available after the stream or connection has closed, in which case
+        /// the offsets observed by the last operation on the stream are returned.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(offsets)` with the current offsets of the stream.
+        /// - `Err(e)` if the stream is not writable.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let offsets = stream.send_offsets()?;
+        /// println!("{} of {} bytes acknowledged", offsets.acknowledged, offsets.enqueued);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn send_offsets(&mut self) -> $crate::stream::Result<$crate::stream::SendOffsets> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.send_offsets()
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Returns the cumulative statistics of the data sent on the stream.
+        ///
+        /// The [`SendStats`](crate::stream::SendStats) include the number of bytes sent,
+        /// acknowledged and retransmitted, along with how long the stream was stalled by the
+        /// congestion controller, the stream flow control limit and the connection flow control
+        /// limit. This makes it possible to find the stream which is misbehaving in a multiplexed
+        /// workload, where the connection statistics only show the aggregate.
+        ///
+        /// The statistics remain available after the stream or connection has closed, in which
+        /// case the statistics observed by the last operation on the stream are returned.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(stats)` with the current statistics of the stream.
+        /// - `Err(e)` if the stream is not writable.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let stats = stream.send_stats()?;
+        /// println!(
+        ///     "{} bytes retransmitted, {:?} blocked on flow control",
+        ///     stats.bytes_retransmitted,
+        ///     stats.stream_flow_control_blocked + stats.connection_flow_control_blocked,
+        /// );
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn send_stats(&mut self) -> $crate::stream::Result<$crate::stream::SendStats> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.send_stats()
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Returns the current usage of the stream's send buffer.
+        ///
+        /// The returned [`BufferStats`](crate::stream::BufferStats) separates the data which is
+        /// still waiting to be transmitted from the data waiting on an acknowledgement from the peer.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(stats)` with the current buffer usage.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let stats = stream.buffer_stats()?;
+        /// println!("{} bytes unsent, {} bytes unacknowledged", stats.unsent, stats.unacknowledged);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn buffer_stats(&mut self) -> $crate::stream::Result<$crate::stream::BufferStats> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.buffer_stats()
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
@@ -28,15 +28,57 @@ mod resumption;
 mod setup;
 use setup::*;
 
+mod accept_lanes;
+mod ack_mode;
+mod address_discovery;
+mod application_context;
+mod attempt_id;
 mod blackhole;
+mod bulk_close;
+mod challenge;
+mod closed_connections;
+mod congestion_controller;
+mod connect_retry;
 mod connection_migration;
+mod connection_pool;
+mod connection_verdict;
+mod drain;
+mod first_flight;
+mod flow_control_tuning;
+mod flush_by;
 mod handshake_cid_rotation;
+mod handshake_only;
+mod hibernation;
+mod initial_padding;
 mod interceptor;
+mod keep_alive;
+mod mirror;
 mod mtu;
 mod no_tls;
+mod packet_coalescing;
+mod padding_policy;
+mod path_cache;
+mod peer_to_peer;
+mod probe_path;
 mod pto;
+mod reload;
+mod replay;
+mod request;
+mod reserved_streams;
+mod sampling;
 mod self_test;
 mod skip_packets;
+mod spki_pinning;
+mod statistics;
+mod stream_acknowledged;
+mod stream_marker;
+mod stream_offsets;
+mod stream_stats;
+mod tls_fault;
+mod transmission_status;
+mod try_io;
+mod tx_coalescing;
+mod tx_fuzz;
 
 // TODO: https://github.com/aws/s2n-quic/issues/1726
 //
@@ -48,7 +90,14 @@ mod client_handshake_confirm;
 #[cfg(not(target_os = "windows"))]
 mod mtls;
 
+#[cfg(feature = "unstable-provider-datagram")]
+mod datagram_api;
+#[cfg(feature = "unstable-provider-datagram")]
+mod datagram_outcomes;
+
+mod channel_binding;
 mod exporter;
+mod fingerprint;
 mod initial_rtt;
 mod issue_1361;
 mod issue_1427;
@@ -0,0 +1,69 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{server::accept::Lanes, Connection};
+
+/// Connections in higher priority lanes are accepted ahead of a backlog of other connections
+#[test]
+fn accept_lanes_test() {
+    let model = Model::default();
+    let accepted = Arc::new(Mutex::new(vec![]));
+    let mut interactive_addr = None;
+
+    test(model, |handle| {
+        let batch = (0..3)
+            .map(|_| build_client(handle))
+            .collect::<io::Result<Vec<_>>>()?;
+        let interactive = build_client(handle)?;
+        let local_addr = interactive.local_addr()?;
+        interactive_addr = Some(local_addr);
+
+        let mut server = build_server(handle)?;
+        let addr = server.local_addr()?;
+        server.set_accept_lanes(Lanes::new([1, 1], move |connection: &Connection| {
+            if connection.remote_addr().ok() == Some(local_addr) {
+                0
+            } else {
+                1
+            }
+        }));
+
+        let accepted = accepted.clone();
+        spawn(async move {
+            // wait for all of the connections to complete the handshake to build a backlog
+            delay(Duration::from_secs(1)).await;
+
+            while let Some(connection) = server.accept().await {
+                accepted
+                    .lock()
+                    .unwrap()
+                    .push(connection.remote_addr().unwrap());
+            }
+        });
+
+        for client in batch {
+            primary::spawn(async move {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let _connection = client.connect(connect).await.unwrap();
+                delay(Duration::from_secs(2)).await;
+            });
+        }
+
+        primary::spawn(async move {
+            // the interactive client completes its handshake after the batch clients
+            delay(Duration::from_millis(100)).await;
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let _connection = interactive.connect(connect).await.unwrap();
+            delay(Duration::from_secs(2)).await;
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let accepted = accepted.lock().unwrap();
+    let interactive_addr = interactive_addr.unwrap();
+    assert_eq!(accepted.len(), 4);
+    assert_eq!(accepted[0], interactive_addr);
+}
@@ -0,0 +1,57 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::ack::{Mode, PacketNumberSpace};
+
+/// Withholding ACK frames causes the peer to enter probe timeouts, after which the connection
+/// recovers once the acknowledgements are released
+#[test]
+fn withhold_acks_test() {
+    let model = Model::default();
+    let pto_subscriber = recorder::Pto::new();
+    let pto_events = pto_subscriber.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), pto_subscriber))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            connection
+                .set_ack_mode(PacketNumberSpace::ApplicationData, Mode::Withhold)
+                .unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+
+            // give the server time to probe for the missing acknowledgements
+            delay(Duration::from_secs(1)).await;
+
+            connection
+                .set_ack_mode(PacketNumberSpace::ApplicationData, Mode::Default)
+                .unwrap();
+
+            let mut received = 0;
+            while let Ok(Some(chunk)) = stream.receive().await {
+                received += chunk.len();
+            }
+            assert_eq!(received, 5);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let pto_events = pto_events.lock().unwrap();
+    assert!(pto_events.iter().any(|pto_count| *pto_count > 0));
+}
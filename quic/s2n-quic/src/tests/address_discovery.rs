@@ -0,0 +1,81 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::{AddressDiscovery, Limits};
+
+fn limits(mode: AddressDiscovery) -> Limits {
+    Limits::new().with_address_discovery(mode).unwrap()
+}
+
+#[test]
+fn address_discovery_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_limits(limits(AddressDiscovery::ProvideAndReceive))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_limits(limits(AddressDiscovery::ReceiveOnly))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // exchange some data to give the server a chance to report the address
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.receive().await.unwrap();
+
+            let observed = connection.observed_addr().unwrap();
+            assert_eq!(observed, Some(connection.local_addr().unwrap()));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn address_discovery_not_negotiated_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_limits(limits(AddressDiscovery::ReceiveOnly))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.receive().await.unwrap();
+
+            // the server doesn't support the extension so nothing is reported
+            assert_eq!(connection.observed_addr().unwrap(), None);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::{events, ConnectionInfo, ConnectionMeta, Subscriber};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct RequestId(u64);
+
+/// Records the request id attached to the connection when each packet is sent
+struct RequestTracker;
+
+impl Subscriber for RequestTracker {
+    type ConnectionContext = Option<RequestId>;
+
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        // the application can't attach a context before the connection exists
+        assert!(!meta.application_context.is_set());
+        None
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        _event: &events::PacketSent,
+    ) {
+        if let Some(request_id) = meta.application_context.get::<RequestId>() {
+            *context = Some(*request_id);
+        }
+    }
+}
+
+#[test]
+fn application_context_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((RequestTracker, tracing_events()))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            assert_eq!(connection.application_context::<RequestId>().unwrap(), None);
+
+            connection
+                .set_application_context(Box::new(RequestId(123)))
+                .unwrap();
+
+            assert_eq!(
+                connection.application_context::<RequestId>().unwrap(),
+                Some(RequestId(123))
+            );
+            // a different type doesn't match the context
+            assert_eq!(connection.application_context::<u64>().unwrap(), None);
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+            while let Ok(Some(_)) = stream.receive().await {}
+
+            let observed = connection
+                .query_event_context(|context: &Option<RequestId>| *context)
+                .unwrap();
+            assert_eq!(observed, Some(RequestId(123)));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    endpoint_limits::{ConnectionAttempt, Limiter, Outcome},
+    event::{ConnectionInfo, ConnectionMeta, Subscriber},
+};
+
+/// Requires all of the connection attempts to perform a Retry
+struct AlwaysRetry;
+
+impl Limiter for AlwaysRetry {
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        Outcome::retry()
+    }
+}
+
+/// Records the attempt IDs of the Retry packets and the connections
+#[derive(Clone, Default)]
+struct AttemptTracker {
+    retries: Arc<Mutex<Vec<Option<u64>>>>,
+    connections: Arc<Mutex<Vec<u64>>>,
+}
+
+impl Subscriber for AttemptTracker {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        self.connections.lock().unwrap().push(meta.attempt_id);
+    }
+
+    fn on_endpoint_packet_sent(
+        &mut self,
+        meta: &events::EndpointMeta,
+        event: &events::EndpointPacketSent,
+    ) {
+        if matches!(event.packet_header, events::PacketHeader::Retry { .. }) {
+            self.retries.lock().unwrap().push(meta.attempt_id);
+        }
+    }
+}
+
+#[test]
+fn attempt_id_retry_test() {
+    let model = Model::default();
+    let tracker = AttemptTracker::default();
+    let retries = tracker.retries.clone();
+    let connections = tracker.connections.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracker, tracing_events()))?
+            .with_endpoint_limits(AlwaysRetry)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            client.connect(connect).await.unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let retries = retries.lock().unwrap();
+    let connections = connections.lock().unwrap();
+
+    // the connection created after the Retry continues the same attempt
+    assert_eq!(retries.len(), 1, "{retries:?}");
+    assert_eq!(connections.len(), 1, "{connections:?}");
+    assert_eq!(retries[0], Some(connections[0]));
+}
@@ -0,0 +1,63 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn bulk_close_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut bidi = vec![];
+            for _ in 0..3 {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                let chunk = stream.receive().await.unwrap().unwrap();
+                assert_eq!(chunk, &b"hello"[..]);
+                bidi.push(stream);
+            }
+            let mut uni = connection.open_send_stream().await.unwrap();
+            uni.send(Bytes::from_static(b"hello")).await.unwrap();
+
+            // block a task on one of the streams, which should be woken up by the bulk close
+            let (mut receiver, _sender) = bidi.pop().unwrap().split();
+            let woken = Arc::new(Mutex::new(false));
+            primary::spawn({
+                let woken = woken.clone();
+                async move {
+                    assert!(receiver.receive().await.is_err());
+                    *woken.lock().unwrap() = true;
+                }
+            });
+            delay(Duration::from_millis(10)).await;
+            assert!(!*woken.lock().unwrap());
+
+            let count = connection
+                .stop_sending_streams_where(123u8.into(), |id| id.stream_type().is_bidirectional())
+                .unwrap();
+            assert_eq!(count, 3);
+            delay(Duration::from_millis(10)).await;
+            assert!(*woken.lock().unwrap());
+            for stream in &mut bidi {
+                assert!(stream.receive().await.is_err());
+            }
+
+            let count = connection
+                .reset_streams_where(123u8.into(), |id| id.stream_type().is_unidirectional())
+                .unwrap();
+            assert_eq!(count, 1);
+            assert!(uni.send(Bytes::from_static(b"hello")).await.is_err());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,273 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    endpoint_limits::{ChallengeResponse, ConnectionAttempt, Limiter, Outcome},
+    event::{ConnectionInfo, ConnectionMeta, Subscriber},
+};
+use s2n_quic_core::connection;
+
+const CHALLENGE: &[u8] = b"server challenge";
+
+/// A toy challenge which is solved by reversing the bytes
+fn solve(challenge: &[u8]) -> Option<Vec<u8>> {
+    Some(challenge.iter().rev().copied().collect())
+}
+
+/// Requires all of the connection attempts to solve a challenge
+struct AlwaysChallenge;
+
+impl Limiter for AlwaysChallenge {
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        Outcome::challenge().with_reason("overloaded")
+    }
+
+    fn generate_challenge(
+        &mut self,
+        _info: &ConnectionAttempt,
+        output: &mut [u8],
+    ) -> Option<usize> {
+        output[..CHALLENGE.len()].copy_from_slice(CHALLENGE);
+        Some(CHALLENGE.len())
+    }
+
+    fn verify_challenge(&mut self, info: &ChallengeResponse) -> bool {
+        info.challenge == CHALLENGE && Some(info.solution.to_vec()) == solve(CHALLENGE)
+    }
+}
+
+#[derive(Debug)]
+struct Rejection {
+    action: events::ConnectionAttemptAction,
+    reason: Option<String>,
+    is_address_validated: bool,
+}
+
+/// Records the rejected connection attempts and dropped datagrams
+#[derive(Clone, Default)]
+struct AttemptTracker {
+    rejected: Arc<Mutex<Vec<Rejection>>>,
+    dropped: Arc<Mutex<Vec<events::EndpointDatagramDropped>>>,
+}
+
+impl Subscriber for AttemptTracker {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_endpoint_connection_attempt_rejected(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::EndpointConnectionAttemptRejected,
+    ) {
+        self.rejected.lock().unwrap().push(Rejection {
+            action: event.action.clone(),
+            reason: event.reason.map(String::from),
+            is_address_validated: event.is_address_validated,
+        });
+    }
+
+    fn on_endpoint_datagram_dropped(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::EndpointDatagramDropped,
+    ) {
+        self.dropped.lock().unwrap().push(event.clone());
+    }
+}
+
+/// Connects to a server which challenges every attempt and returns the outcome
+fn connect(with_solver: bool) -> (Result<(), crate::connection::Error>, AttemptTracker) {
+    let model = Model::default();
+    let tracker = AttemptTracker::default();
+    let outcome = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracker.clone(), tracing_events()))?
+            .with_endpoint_limits(AlwaysChallenge)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = build_client(handle)?;
+
+        let outcome = outcome.clone();
+        primary::spawn(async move {
+            let mut connect = Connect::new(addr).with_server_name("localhost");
+            if with_solver {
+                connect = connect.with_challenge_solver(solve);
+            }
+            let result = client.connect(connect).await.map(|_| ());
+            *outcome.lock().unwrap() = Some(result);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let outcome = outcome.lock().unwrap().take();
+    (outcome.expect("the attempt should complete"), tracker)
+}
+
+#[test]
+fn challenge_solved_test() {
+    let (result, tracker) = connect(true);
+    result.unwrap();
+
+    let rejected = tracker.rejected.lock().unwrap();
+    assert_eq!(rejected.len(), 1, "{rejected:?}");
+    assert!(matches!(
+        rejected[0].action,
+        events::ConnectionAttemptAction::Challenge { .. }
+    ));
+    assert_eq!(rejected[0].reason.as_deref(), Some("overloaded"));
+    assert!(!rejected[0].is_address_validated);
+}
+
+#[test]
+fn challenge_unsolved_test() {
+    // without a solver the client echoes the challenge back, which the server drops
+    let (result, tracker) = connect(false);
+    assert!(result.is_err());
+
+    let dropped = tracker.dropped.lock().unwrap();
+    assert!(
+        dropped.iter().any(|event| matches!(
+            event.reason,
+            events::DatagramDropReason::InvalidChallengeResponse { .. }
+        )),
+        "{dropped:?}"
+    );
+}
+
+/// Sends a Retry packet to every attempt which wasn't validated
+struct AlwaysRetry;
+
+impl Limiter for AlwaysRetry {
+    fn on_connection_attempt(&mut self, info: &ConnectionAttempt) -> Outcome {
+        if info.is_address_validated {
+            Outcome::allow()
+        } else {
+            Outcome::retry()
+        }
+    }
+}
+
+/// An address token format whose tokens start with the byte which tags challenges
+///
+/// The tokens aren't authenticated, which is only acceptable for testing.
+struct PrefixedTokens;
+
+impl PrefixedTokens {
+    const PREFIX: u8 = 0xff;
+}
+
+impl provider::address_token::Provider for PrefixedTokens {
+    type Format = Self;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Format, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl provider::address_token::Format for PrefixedTokens {
+    const TOKEN_LEN: usize = 2 + connection::id::MAX_LEN;
+
+    fn generate_new_token(
+        &mut self,
+        _context: &mut provider::address_token::Context<'_>,
+        _source_connection_id: &connection::LocalId,
+        _output_buffer: &mut [u8],
+    ) -> Option<()> {
+        None
+    }
+
+    fn generate_retry_token(
+        &mut self,
+        _context: &mut provider::address_token::Context<'_>,
+        original_destination_connection_id: &connection::InitialId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        let odcid = original_destination_connection_id.as_bytes();
+        output_buffer[0] = Self::PREFIX;
+        output_buffer[1] = odcid.len() as u8;
+        output_buffer[2..2 + odcid.len()].copy_from_slice(odcid);
+        Some(())
+    }
+
+    fn validate_token(
+        &mut self,
+        _context: &mut provider::address_token::Context<'_>,
+        token: &[u8],
+    ) -> Option<connection::InitialId> {
+        let (&prefix, token) = token.split_first()?;
+        let (&len, token) = token.split_first()?;
+        if prefix != Self::PREFIX {
+            return None;
+        }
+        connection::InitialId::try_from_bytes(token.get(..len as usize)?)
+    }
+}
+
+#[test]
+fn prefixed_address_token_test() {
+    let model = Model::default();
+    let tracker = AttemptTracker::default();
+    let outcome = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracker.clone(), tracing_events()))?
+            .with_endpoint_limits(AlwaysRetry)?
+            .with_address_token(PrefixedTokens)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = build_client(handle)?;
+
+        let outcome = outcome.clone();
+        primary::spawn(async move {
+            // the client has a solver but the token doesn't carry a challenge
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_challenge_solver(solve);
+            let result = client.connect(connect).await.map(|_| ());
+            *outcome.lock().unwrap() = Some(result);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // tokens starting with the challenge tag are still validated by the address token format
+    let outcome = outcome.lock().unwrap().take();
+    outcome.expect("the attempt should complete").unwrap();
+
+    let rejected = tracker.rejected.lock().unwrap();
+    assert_eq!(rejected.len(), 1, "{rejected:?}");
+    assert!(matches!(
+        rejected[0].action,
+        events::ConnectionAttemptAction::Retry { .. }
+    ));
+
+    let dropped = tracker.dropped.lock().unwrap();
+    assert!(
+        !dropped.iter().any(|event| matches!(
+            event.reason,
+            events::DatagramDropReason::InvalidRetryToken { .. }
+                | events::DatagramDropReason::InvalidChallengeResponse { .. }
+        )),
+        "{dropped:?}"
+    );
+}
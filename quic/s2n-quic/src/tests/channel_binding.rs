@@ -0,0 +1,66 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Both peers derive the same channel binding for a connection, while each connection gets a
+/// distinct value
+#[test]
+fn channel_binding_test() {
+    let model = Model::default();
+
+    let bindings = Arc::new(Mutex::new(Vec::new()));
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let binding = connection.channel_binding().unwrap().unwrap();
+                spawn(async move {
+                    while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await
+                    {
+                        stream
+                            .send(Bytes::copy_from_slice(binding.as_bytes()))
+                            .await
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        for _ in 0..2 {
+            let client = client.clone();
+            let bindings = bindings.clone();
+            primary::spawn(async move {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+                let binding = connection.channel_binding().unwrap().unwrap();
+
+                // the binding uses a different label than the fingerprint
+                let fingerprint = connection.fingerprint().unwrap().unwrap();
+                assert_ne!(&binding.as_bytes()[..16], fingerprint.as_bytes());
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                let server_binding = stream.receive().await.unwrap().unwrap();
+                assert_eq!(binding.as_bytes(), &server_binding[..]);
+
+                bindings.lock().unwrap().push(binding);
+            });
+        }
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let bindings = bindings.lock().unwrap();
+    assert_eq!(bindings.len(), 2);
+    assert_ne!(bindings[0], bindings[1]);
+}
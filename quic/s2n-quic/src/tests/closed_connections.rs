@@ -0,0 +1,46 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection;
+use futures::StreamExt;
+
+#[test]
+fn closed_connections_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let mut closed = server.closed_connections();
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            connection.close(123u8.into());
+            // the notification doesn't depend on the application holding the handle
+            drop(connection);
+
+            let closed = closed.next().await.unwrap();
+            assert!(
+                matches!(
+                    closed.error,
+                    connection::Error::Application { error, .. } if error == 123u8.into()
+                ),
+                "{closed:?}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
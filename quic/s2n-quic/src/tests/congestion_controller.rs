@@ -0,0 +1,64 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::congestion_controller::{Cubic, Provider};
+
+fn initialized_events<C: Provider>(
+    congestion_controller: C,
+) -> Vec<events::CongestionControllerInitialized> {
+    let model = Model::default();
+    let subscriber = recorder::CongestionControllerInitialized::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_congestion_controller(congestion_controller)?
+            .start()?;
+        let client = build_client(handle)?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events_handle = events.lock().unwrap();
+    events_handle.clone()
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn cubic_parameters_test() {
+    let congestion_controller = Cubic::builder()
+        .with_beta(0.8)
+        .unwrap()
+        .with_c(0.6)
+        .unwrap()
+        .build();
+
+    let events = initialized_events(congestion_controller);
+
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        events[0].algorithm,
+        events::CongestionControlAlgorithm::Cubic { beta, c, .. } if beta == 0.8 && c == 0.6
+    ));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn new_reno_test() {
+    let congestion_controller = Cubic::builder().with_new_reno(true).unwrap().build();
+
+    let events = initialized_events(congestion_controller);
+
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        events[0].algorithm,
+        events::CongestionControlAlgorithm::NewReno { beta, .. } if beta == 0.5
+    ));
+}
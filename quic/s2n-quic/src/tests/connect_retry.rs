@@ -0,0 +1,129 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    client::RetryPolicy,
+    connection,
+    provider::{
+        event::{ConnectionInfo, ConnectionMeta, Subscriber},
+        limits::Limits,
+    },
+};
+
+/// Records the connection attempts which were retried
+#[derive(Clone, Default)]
+struct RetryTracker {
+    retries: Arc<Mutex<Vec<u32>>>,
+}
+
+impl Subscriber for RetryTracker {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_endpoint_connection_attempt_retried(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::EndpointConnectionAttemptRetried,
+    ) {
+        assert!(
+            matches!(
+                event.error,
+                connection::Error::MaxHandshakeDurationExceeded { .. }
+                    | connection::Error::IdleTimerExpired { .. }
+            ),
+            "{:?}",
+            event.error
+        );
+        self.retries.lock().unwrap().push(event.attempts);
+    }
+}
+
+#[test]
+fn connect_retry_test() {
+    let model = Model::default();
+    let tracker = RetryTracker::default();
+    let retries = tracker.retries.clone();
+
+    // drop everything until the first attempt has timed out
+    model.set_drop_rate(1.0);
+
+    test(model.clone(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracker, tracing_events()))?
+            .with_limits(Limits::new().with_max_handshake_duration(Duration::from_secs(1))?)?
+            .start()?;
+
+        spawn(async move {
+            delay(Duration::from_millis(1050)).await;
+            model.set_drop_rate(0.0);
+        });
+
+        primary::spawn(async move {
+            let policy = RetryPolicy::new(3)
+                .with_backoff(Duration::from_millis(100), Duration::from_secs(1));
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_retry_policy(policy);
+            client.connect(connect).await.unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // only the first attempt failed
+    assert_eq!(*retries.lock().unwrap(), [1]);
+}
+
+#[test]
+fn connect_retry_exhausted_test() {
+    let model = Model::default();
+    let tracker = RetryTracker::default();
+    let retries = tracker.retries.clone();
+
+    model.set_drop_rate(1.0);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracker, tracing_events()))?
+            .with_limits(Limits::new().with_max_handshake_duration(Duration::from_secs(1))?)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_retry_policy(RetryPolicy::new(3));
+            client.connect(connect).await.unwrap_err();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(*retries.lock().unwrap(), [1, 2]);
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::provider::path_migration;
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     event::api::Subject,
@@ -218,3 +219,102 @@ fn port_rebind_test() {
 fn ip_and_port_rebind_test() {
     run_test(|addr| rebind_ip(rebind_port(addr)));
 }
+
+/// Records each migration attempt and denies any after `max_migrations`
+#[derive(Clone, Default)]
+struct LimitedMigrations {
+    max_migrations: u32,
+    attempts: Arc<Mutex<Vec<u32>>>,
+}
+
+impl path_migration::Validator for LimitedMigrations {
+    fn on_migration_attempt(
+        &mut self,
+        attempt: &path_migration::Attempt,
+    ) -> path_migration::Outcome {
+        self.attempts.lock().unwrap().push(attempt.migration_count);
+
+        if attempt.migration_count < self.max_migrations {
+            path_migration::Outcome::Allow
+        } else {
+            path_migration::Outcome::Deny(path_migration::DenyReason::Application)
+        }
+    }
+}
+
+#[test]
+fn path_migration_provider_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    let rebind_rate = rtt * 2;
+    let rebind_count = 4;
+
+    model.set_delay(rtt / 2);
+
+    let validator = LimitedMigrations {
+        max_migrations: 2,
+        ..Default::default()
+    };
+    let attempts = validator.attempts.clone();
+
+    let on_socket = move |socket: io::Socket| {
+        spawn(async move {
+            let mut local_addr = socket.local_addr().unwrap();
+            for _ in 0..rebind_count {
+                local_addr = rebind_port(local_addr);
+                delay(rebind_rate).await;
+                socket.rebind(local_addr);
+            }
+        });
+    };
+
+    let active_paths = recorder::ActivePathUpdated::new();
+    let active_path_sub = active_paths.clone();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), active_path_sub))?
+            .with_random(Random::with_seed(456))?
+            .with_path_migration(validator)?
+            .start()?;
+
+        let client_io = handle.builder().on_socket(on_socket).build()?;
+
+        let client = Client::builder()
+            .with_io(client_io)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(b"A")).await.unwrap();
+
+            delay(rebind_rate / 2).await;
+
+            // the server stops receiving packets once the migrations are denied, so ignore any
+            // errors from the stream
+            for _ in 0..rebind_count {
+                let _ = stream.send(Bytes::from_static(b"B")).await;
+                delay(rebind_rate).await;
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(&attempts[..3], &[0, 1, 2]);
+    // denied attempts don't count as migrations
+    assert!(attempts[3..].iter().all(|count| *count == 2));
+
+    assert_eq!(active_paths.events().lock().unwrap().len(), 2);
+}
@@ -0,0 +1,107 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::client::ConnectionPool;
+
+#[test]
+fn connection_pool_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let pool = ConnectionPool::new(client, connect, 2);
+            assert_eq!(pool.size(), 2);
+            assert_eq!(pool.connected(), 0);
+
+            // opens a stream and checks the server echoes data back on it
+            let pool = &pool;
+            let open = || async move {
+                let mut stream = pool.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                let chunk = stream.receive().await.unwrap().unwrap();
+                assert_eq!(chunk, &b"hello"[..]);
+                stream
+            };
+
+            // the first stream waits for a connection to be established
+            let first = open().await.connection().id();
+            assert_eq!(pool.connected(), 1);
+
+            // the second connection is being established so the stream uses the first one
+            assert_eq!(open().await.connection().id(), first);
+            delay(Duration::from_millis(500)).await;
+
+            // the streams alternate between the two connections once both are established
+            let second = open().await.connection().id();
+            assert_ne!(first, second);
+            assert_eq!(pool.connected(), 2);
+            assert_eq!(open().await.connection().id(), first);
+            assert_eq!(open().await.connection().id(), second);
+
+            // close the first connection
+            let stream = open().await;
+            assert_eq!(stream.connection().id(), first);
+            stream.connection().close(123u8.into());
+            drop(stream);
+            delay(Duration::from_millis(100)).await;
+
+            assert_eq!(open().await.connection().id(), second);
+            assert_eq!(pool.connected(), 2);
+
+            // the failed connection is evicted and the stream is opened on the healthy one
+            assert_eq!(open().await.connection().id(), second);
+            assert_eq!(pool.connected(), 1);
+
+            // the evicted connection is reconnected without holding up the stream
+            assert_eq!(open().await.connection().id(), second);
+            delay(Duration::from_millis(500)).await;
+
+            let id = open().await.connection().id();
+            assert_ne!(id, first);
+            assert_ne!(id, second);
+            assert_eq!(pool.connected(), 2);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn connection_pool_concurrent_select_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let pool = ConnectionPool::new(client, connect, 1);
+
+            // open streams concurrently while the connection is still being established
+            let pool = &pool;
+            let streams = futures::future::join_all(
+                (0..4).map(|_| async move { pool.open_bidirectional_stream().await.unwrap() }),
+            )
+            .await;
+
+            // all of the callers waited for the same handshake
+            let id = streams[0].connection().id();
+            for stream in &streams {
+                assert_eq!(stream.connection().id(), id);
+            }
+            assert_eq!(pool.connected(), 1);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
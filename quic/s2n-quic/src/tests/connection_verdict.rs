@@ -0,0 +1,102 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection,
+    provider::event::{events, supervisor, ConnectionInfo, ConnectionMeta, Subscriber},
+};
+
+const MAX_RESETS: usize = 3;
+const ERROR_CODE: u8 = 42;
+
+/// Closes connections which reset more than `MAX_RESETS` streams
+struct ResetPolicy;
+
+impl Subscriber for ResetPolicy {
+    type ConnectionContext = usize;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        0
+    }
+
+    fn on_frame_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::FrameReceived,
+    ) {
+        if matches!(event.frame, events::Frame::ResetStream { .. }) {
+            *context += 1;
+        }
+    }
+
+    fn connection_verdict(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+    ) -> supervisor::Outcome {
+        if *context > MAX_RESETS {
+            supervisor::Outcome::Close {
+                error_code: ERROR_CODE.into(),
+            }
+        } else {
+            supervisor::Outcome::Continue
+        }
+    }
+}
+
+#[test]
+fn connection_verdict_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((ResetPolicy, tracing_events()))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut resets = 0;
+            let error = loop {
+                match connection.open_send_stream().await {
+                    Ok(mut stream) => {
+                        let _ = stream.send(Bytes::from_static(b"hello")).await;
+                        let _ = stream.reset(1u8.into());
+                        resets += 1;
+                    }
+                    Err(error) => break error,
+                }
+
+                delay(Duration::from_millis(10)).await;
+            };
+
+            // the server only closes the connection after the policy is exceeded
+            assert!(resets > MAX_RESETS);
+            assert!(
+                matches!(
+                    error,
+                    connection::Error::Application { error, .. } if error == ERROR_CODE.into()
+                ),
+                "{error:?}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
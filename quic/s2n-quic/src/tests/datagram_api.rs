@@ -0,0 +1,107 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use provider::datagram::default;
+
+/// Datagrams sent with the connection API are echoed back by the peer
+#[test]
+fn datagram_echo_test() {
+    let model = Model::default();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let client_received = received.clone();
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_datagram(default::Endpoint::builder().build()?)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(connection) = server.accept().await {
+                spawn(async move {
+                    while let Ok(datagram) = connection.datagram_recv().await {
+                        let _ = connection.datagram_send(datagram).await;
+                    }
+                });
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_datagram(default::Endpoint::builder().build()?)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            for i in 0..10u8 {
+                connection
+                    .datagram_send(Bytes::from(vec![i; 100]))
+                    .await
+                    .unwrap();
+            }
+
+            for _ in 0..10 {
+                let datagram = connection.datagram_recv().await.unwrap();
+                client_received.lock().unwrap().push(datagram);
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let received = received.lock().unwrap();
+    let expected: Vec<_> = (0..10u8).map(|i| Bytes::from(vec![i; 100])).collect();
+    assert_eq!(*received, expected);
+}
+
+/// The connection API reports an error if the endpoint isn't using the default datagram provider
+#[test]
+fn datagram_unsupported_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            assert_eq!(
+                connection.datagram_send(Bytes::from_static(b"hello")).await,
+                Err(default::DatagramError::Unsupported)
+            );
+            assert_eq!(
+                connection.datagram_recv().await,
+                Err(default::DatagramError::Unsupported)
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,139 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use provider::datagram::{
+    default, ConnectionInfo, Endpoint, Packet, PreConnectionInfo, ReceiveContext, Receiver, Sender,
+};
+use s2n_quic_core::{connection, transport::parameters::MaxDatagramFrameSize};
+
+#[derive(Debug, Default)]
+struct Outcomes {
+    sent: Vec<u64>,
+    acked: Vec<u64>,
+    lost: Vec<u64>,
+}
+
+/// Sends a fixed number of datagrams and records the outcome of each packet they were sent in
+struct RecordingEndpoint {
+    remaining: usize,
+    outcomes: Arc<Mutex<Outcomes>>,
+}
+
+impl Endpoint for RecordingEndpoint {
+    type Sender = RecordingSender;
+    type Receiver = NoopReceiver;
+
+    fn create_connection(&mut self, _info: &ConnectionInfo) -> (Self::Sender, Self::Receiver) {
+        let sender = RecordingSender {
+            remaining: self.remaining,
+            outcomes: self.outcomes.clone(),
+        };
+        (sender, NoopReceiver)
+    }
+
+    fn max_datagram_frame_size(&self, _info: &PreConnectionInfo) -> u64 {
+        MaxDatagramFrameSize::RECOMMENDED
+    }
+}
+
+struct RecordingSender {
+    remaining: usize,
+    outcomes: Arc<Mutex<Outcomes>>,
+}
+
+impl Sender for RecordingSender {
+    fn on_transmit<P: Packet>(&mut self, packet: &mut P) {
+        let mut written = false;
+        while self.remaining > 0 && packet.write_datagram(&[1; 100]).is_ok() {
+            self.remaining -= 1;
+            written = true;
+        }
+
+        if written {
+            let packet_number = packet.packet_number();
+            self.outcomes.lock().unwrap().sent.push(packet_number);
+        }
+    }
+
+    fn has_transmission_interest(&self) -> bool {
+        self.remaining > 0
+    }
+
+    fn on_connection_error(&mut self, _error: connection::Error) {}
+
+    fn on_packet_ack(&mut self, packet_number: u64) {
+        self.outcomes.lock().unwrap().acked.push(packet_number);
+    }
+
+    fn on_packet_loss(&mut self, packet_number: u64) {
+        self.outcomes.lock().unwrap().lost.push(packet_number);
+    }
+}
+
+struct NoopReceiver;
+
+impl Receiver for NoopReceiver {
+    fn on_datagram(&mut self, _context: &ReceiveContext<'_>, _datagram: &[u8]) {}
+
+    fn on_connection_error(&mut self, _error: connection::Error) {}
+}
+
+/// Each packet containing datagrams is reported as either acknowledged or lost exactly once
+#[test]
+fn datagram_outcomes_test() {
+    let model = Model::default();
+    model.set_drop_rate(0.1);
+
+    let outcomes = Arc::new(Mutex::new(Outcomes::default()));
+    let endpoint = RecordingEndpoint {
+        remaining: 1000,
+        outcomes: outcomes.clone(),
+    };
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_datagram(
+                default::Endpoint::builder()
+                    .with_recv_capacity(1000)?
+                    .build()?,
+            )?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_datagram(endpoint)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            // keep the connection open long enough for all of the outcomes to be known
+            delay(Duration::from_secs(5)).await;
+
+            drop(connection);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let outcomes = outcomes.lock().unwrap();
+    assert!(!outcomes.sent.is_empty());
+    assert!(!outcomes.acked.is_empty(), "{outcomes:?}");
+    assert!(!outcomes.lost.is_empty(), "{outcomes:?}");
+
+    let mut reported = outcomes.acked.clone();
+    reported.extend(&outcomes.lost);
+    reported.sort_unstable();
+    assert_eq!(reported, outcomes.sent);
+}
@@ -0,0 +1,96 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection;
+
+/// Ensures connections which are still open after the drain timeout are closed with the
+/// configured error
+#[test]
+fn drain_timeout_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        spawn(async move {
+            // hold on to the connection so it isn't closed by the application
+            let connection = server.accept().await.unwrap();
+
+            let start = io::now();
+            let timeout = Duration::from_secs(1);
+            server.drain(timeout, 123u8.into()).await.unwrap();
+            assert!(io::now().saturating_duration_since(start) >= timeout);
+
+            // the server no longer yields connections once it has shut down
+            assert!(server.accept().await.is_none());
+            drop(connection);
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let error = connection.accept().await.unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    connection::Error::Application { error, .. } if error == 123u8.into()
+                ),
+                "{error:?}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the drain completes as soon as the existing connections have closed
+#[test]
+fn drain_idle_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connection = server.accept().await.unwrap();
+            connection.close(1u8.into());
+            drop(connection);
+
+            let start = io::now();
+            let timeout = Duration::from_secs(60);
+            server.drain(timeout, 123u8.into()).await.unwrap();
+            assert!(io::now().saturating_duration_since(start) < timeout);
+        });
+
+        spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let _connection = client.connect(connect).await.unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
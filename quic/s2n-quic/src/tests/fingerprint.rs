@@ -0,0 +1,62 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Both peers derive the same fingerprint for a connection, while each connection gets a
+/// distinct fingerprint
+#[test]
+fn fingerprint_test() {
+    let model = Model::default();
+
+    let fingerprints = Arc::new(Mutex::new(Vec::new()));
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let fingerprint = connection.fingerprint().unwrap().unwrap();
+                spawn(async move {
+                    while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await
+                    {
+                        stream
+                            .send(Bytes::copy_from_slice(fingerprint.as_bytes()))
+                            .await
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        for _ in 0..2 {
+            let client = client.clone();
+            let fingerprints = fingerprints.clone();
+            primary::spawn(async move {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+                let fingerprint = connection.fingerprint().unwrap().unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                let server_fingerprint = stream.receive().await.unwrap().unwrap();
+                assert_eq!(fingerprint.as_bytes(), &server_fingerprint[..]);
+
+                fingerprints.lock().unwrap().push(fingerprint);
+            });
+        }
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let fingerprints = fingerprints.lock().unwrap();
+    assert_eq!(fingerprints.len(), 2);
+    assert_ne!(fingerprints[0], fingerprints[1]);
+}
@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{connection, provider::limits::Limits};
+
+#[test]
+fn first_flight_no_server_response_test() {
+    let model = Model::default();
+    let outcome = Arc::new(Mutex::new(None));
+
+    // the server never receives anything
+    model.set_drop_rate(1.0);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let limits = Limits::new()
+            .with_first_flight_probe_timeout(Duration::from_millis(100))?
+            .with_max_first_flight_transmissions(3)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_limits(limits)?
+            .start()?;
+
+        let outcome = outcome.clone();
+        primary::spawn(async move {
+            let start = io::now();
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let error = client.connect(connect).await.unwrap_err();
+            let elapsed = io::now().saturating_duration_since(start);
+            *outcome.lock().unwrap() = Some((error, elapsed));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let (error, elapsed) = outcome.lock().unwrap().take().unwrap();
+    assert!(
+        matches!(
+            error,
+            connection::Error::NoServerResponse {
+                transmissions: 3,
+                ..
+            }
+        ),
+        "{error:?}"
+    );
+
+    // the PTO doubles after each transmission: 100ms + 200ms + 400ms
+    assert!(elapsed >= Duration::from_millis(700), "{elapsed:?}");
+    // the attempt gives up well before the handshake or idle timers would expire
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+}
+
+#[test]
+fn first_flight_response_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    let limits = Limits::new()
+        .with_first_flight_probe_timeout(Duration::from_millis(50))
+        .unwrap()
+        .with_max_first_flight_transmissions(1)
+        .unwrap();
+
+    // the limit no longer applies once the server responds
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_limits(limits)?
+            .start()?;
+        start_client(client, addr, Data::new(10_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,77 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::stream::Type;
+
+/// Ensures a server can allow a peer to open more concurrent streams at runtime
+#[test]
+fn set_max_streams_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_limits(
+                provider::limits::Limits::default()
+                    // only allow 1 concurrent stream from the peer
+                    .with_max_open_remote_bidirectional_streams(1)
+                    .unwrap(),
+            )?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+
+            // hold on to the first stream so the peer can't open another one
+            let mut first = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            let chunk = first.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, &b"first"[..]);
+
+            // unknown streams are rejected
+            assert!(connection.set_max_stream_data(1000, 1_000_000).is_err());
+            connection
+                .set_max_stream_data(first.id(), 1_000_000)
+                .unwrap();
+
+            connection.set_max_streams(Type::Bidirectional, 2).unwrap();
+
+            let mut second = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            while let Ok(Some(chunk)) = second.receive().await {
+                let _ = second.send(chunk).await;
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut first = connection.open_bidirectional_stream().await.unwrap();
+            first.send(Bytes::from_static(b"first")).await.unwrap();
+
+            // this stream can only be opened once the server raises the limit
+            let mut second = connection.open_bidirectional_stream().await.unwrap();
+            second.send(Bytes::from_static(b"second")).await.unwrap();
+            let chunk = second.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, &b"second"[..]);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
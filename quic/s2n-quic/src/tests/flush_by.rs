@@ -0,0 +1,54 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn flush_by_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream
+                .send(Bytes::from_static(&[42; 10_000]))
+                .await
+                .unwrap();
+
+            // the peer can't acknowledge the data before the deadline
+            let stats = stream
+                .flush_by(delay(Duration::from_millis(1)))
+                .await
+                .unwrap();
+            assert_eq!(stats.remaining(), 10_000);
+
+            let stats = stream
+                .flush_by(delay(Duration::from_secs(10)))
+                .await
+                .unwrap();
+            assert_eq!(stats.remaining(), 0);
+
+            stream.finish().unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
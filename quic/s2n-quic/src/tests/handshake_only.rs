@@ -0,0 +1,53 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{connection, provider::limits::Limits};
+
+#[test]
+fn handshake_only_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_limits(Limits::default().with_handshake_only(123u8.into()).unwrap())?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            if let Some(connection) = server.accept().await {
+                panic!("handshake-only connections should not be accepted: {connection:?}");
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+
+            // the client may or may not observe the handshake completing before the server
+            // closes the connection
+            let error = match client.connect(connect).await {
+                Ok(mut connection) => connection.accept().await.unwrap_err(),
+                Err(error) => error,
+            };
+
+            assert!(
+                matches!(
+                    error,
+                    connection::Error::Application { error, .. } if error == 123u8.into()
+                ),
+                "{error:?}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
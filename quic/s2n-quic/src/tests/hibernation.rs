@@ -0,0 +1,68 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+
+/// Connections continue to transfer data after hibernating between bursts of traffic
+#[test]
+fn hibernation_test() {
+    let model = Model::default();
+
+    let limits = Limits::default()
+        .with_hibernation_timeout(Duration::from_millis(100))
+        .unwrap();
+
+    let server_subscriber = recorder::ConnectionHibernated::new();
+    let server_events = server_subscriber.events();
+    let client_subscriber = recorder::ConnectionHibernated::new();
+    let client_events = client_subscriber.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((server_subscriber, tracing_events()))?
+            .with_limits(limits)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((client_subscriber, tracing_events()))?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            let mut hibernated = (0, 0);
+
+            for _ in 0..3 {
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+
+                let mut received = 0;
+                while received < 5 {
+                    received += stream.receive().await.unwrap().unwrap().len();
+                }
+                assert_eq!(received, 5);
+
+                // wait for both peers to hibernate before sending more data
+                delay(Duration::from_secs(1)).await;
+
+                // each idle period should have hibernated both connections
+                let server_count = server_events.lock().unwrap().len();
+                let client_count = client_events.lock().unwrap().len();
+                assert!(server_count > hibernated.0);
+                assert!(client_count > hibernated.1);
+                hibernated = (server_count, client_count);
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
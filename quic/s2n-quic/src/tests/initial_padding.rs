@@ -0,0 +1,103 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    limits::{InitialPadding, Limits},
+    tls,
+};
+
+const MTU: u16 = 1500;
+
+/// Returns the length of the client's first datagram along with any ClientHello fragmentation
+fn first_flight(
+    padding: InitialPadding,
+    protocols: &[Vec<u8>],
+) -> (u16, Vec<events::ClientHelloFragmented>) {
+    let model = Model::default();
+    let datagrams = recorder::DatagramSent::new();
+    let datagram_events = datagrams.events();
+    let fragmented = recorder::ClientHelloFragmented::new();
+    let fragmented_events = fragmented.events();
+
+    test(model, |handle| {
+        let server_tls = tls::default::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .with_application_protocols(protocols.iter())?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(MTU).build()?)?
+            .with_tls(server_tls)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client_tls = tls::default::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_application_protocols(protocols.iter())?
+            .build()?;
+        let client = Client::builder()
+            .with_io(
+                handle
+                    .builder()
+                    .with_max_mtu(MTU)
+                    .with_initial_mtu(MTU)
+                    .build()?,
+            )?
+            .with_tls(client_tls)?
+            .with_limits(Limits::default().with_initial_padding(padding)?)?
+            .with_event((tracing_events(), (datagrams, fragmented)))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let first_datagram = datagram_events.lock().unwrap()[0];
+    let fragmented = fragmented_events.lock().unwrap().clone();
+    (first_datagram, fragmented)
+}
+
+fn default_protocols() -> Vec<Vec<u8>> {
+    vec![b"h3".to_vec()]
+}
+
+/// Enough protocols to push the ClientHello past a single datagram
+fn large_protocols() -> Vec<Vec<u8>> {
+    (0..8u8)
+        .map(|idx| {
+            let mut protocol = vec![b'a' + idx; 200];
+            protocol[0] = b'x';
+            protocol
+        })
+        .collect()
+}
+
+#[test]
+fn initial_padding_full_test() {
+    let (len, fragmented) = first_flight(InitialPadding::Full, &default_protocols());
+    // the initial datagram fills the initial MTU, minus the IP and UDP headers
+    assert!(len > 1200, "{len}");
+    assert!(fragmented.is_empty());
+}
+
+#[test]
+fn initial_padding_minimum_test() {
+    let (len, fragmented) = first_flight(InitialPadding::MINIMUM, &default_protocols());
+    assert_eq!(len, 1200);
+    assert!(fragmented.is_empty());
+}
+
+#[test]
+fn initial_padding_fragmented_client_hello_test() {
+    let (len, fragmented) = first_flight(InitialPadding::MINIMUM, &large_protocols());
+    assert_eq!(len, 1200);
+
+    assert_eq!(fragmented.len(), 1);
+    let event = &fragmented[0];
+    assert!(event.len > 1600, "{}", event.len);
+    assert_eq!(event.datagram_count, 2);
+}
@@ -0,0 +1,55 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+
+/// Idle connections stay open past the idle timeout when a keep-alive period is configured
+#[test]
+fn keep_alive_period_test() {
+    let model = Model::default();
+
+    let server_limits = Limits::default()
+        .with_max_idle_timeout(Duration::from_secs(1))
+        .unwrap();
+    let client_limits = server_limits
+        .with_keep_alive_period(Duration::from_millis(200))
+        .unwrap();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_limits(server_limits)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_limits(client_limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // stay idle for several idle timeouts
+            delay(Duration::from_secs(5)).await;
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+
+            let mut received = 0;
+            while received < 5 {
+                received += stream.receive().await.unwrap().unwrap().len();
+            }
+            assert_eq!(received, 5);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
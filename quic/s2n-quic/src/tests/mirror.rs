@@ -0,0 +1,51 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Copies the server's datagrams to a tap and ensures the connection is unaffected
+
+use super::*;
+use crate::provider::io::mirror::{self, Direction};
+
+#[test]
+fn mirror_test() {
+    let model = Model::default();
+    let (tap, records) = mirror::channel(10_000);
+    let tap = tap.with_snap_len(64);
+    let server_tap = tap.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_mirror(server_tap).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let records: Vec<_> = records.try_iter().collect();
+    assert_eq!(tap.dropped(), 0);
+
+    for direction in [Direction::Ingress, Direction::Egress] {
+        assert!(records.iter().any(|record| record.direction == direction));
+    }
+
+    for record in &records {
+        assert!(record.payload.len() <= 64);
+        assert_eq!(record.payload.len(), record.len.min(64));
+    }
+
+    // the client's Initial is at least 1200 bytes, so it should have been truncated
+    assert!(records
+        .iter()
+        .any(|record| record.direction == Direction::Ingress && record.len >= 1200));
+}
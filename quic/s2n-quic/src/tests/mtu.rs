@@ -5,6 +5,7 @@ use super::*;
 use s2n_codec::encoder::scatter;
 use s2n_quic_core::{
     event::api::Subject,
+    inet::IpV4Address,
     packet::interceptor::{Interceptor, Packet},
     path::{BaseMtu, InitialMtu},
 };
@@ -94,6 +95,54 @@ fn mtu_probe_jumbo_frame_test() {
     assert_eq!(last_probe.mtu, 8943);
 }
 
+// if the peer matches an MTU clamp, probing should stop at the clamped MTU even
+// if the network supports jumbo frames
+#[test]
+fn mtu_clamp_test() {
+    let model = Model::default();
+    let max_mtu = 9_001;
+    let clamp_mtu = 1_400;
+    let subscriber = recorder::MtuUpdated::new();
+    let events = subscriber.events();
+
+    model.set_max_udp_payload(max_mtu);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(
+                handle
+                    .builder()
+                    .with_max_mtu(max_mtu)
+                    .with_mtu_clamp(IpV4Address::UNSPECIFIED.into(), 0, clamp_mtu)
+                    .build()?,
+            )?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(max_mtu).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+
+    // clamp_mtu - UDP_HEADER_LEN - IPV4_HEADER_LEN
+    let max_udp_payload = clamp_mtu - 28;
+    assert!(events.iter().all(|event| event.mtu <= max_udp_payload));
+    assert!(events.last().unwrap().mtu > 1200);
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event.cause, events::MtuUpdatedCause::Blackhole { .. })));
+}
+
 // if we specify jumbo frames on the endpoint and the network does not support
 // them, the connection should gracefully complete with a smaller mtu
 #[test]
@@ -272,6 +321,101 @@ fn mtu_blackhole() {
     assert_eq!(1200, events.lock().unwrap().last().unwrap().mtu);
 }
 
+// if the client advertises a small max_udp_payload_size, the server should never
+// end up using a larger MTU, even if the network supports it
+#[test]
+fn max_udp_payload_size() {
+    let model = Model::default();
+    let max_mtu = 9001;
+    let max_udp_payload_size = 1400;
+    let subscriber = recorder::MtuUpdated::new();
+    let events = subscriber.events();
+
+    model.set_max_udp_payload(max_mtu);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(max_mtu).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(max_mtu).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_max_udp_payload_size(max_udp_payload_size)
+                    .unwrap(),
+            )?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        // we need a large payload to allow for multiple rounds of MTU probing
+        start_client(client, addr, Data::new(10_000_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    assert!(events.iter().all(|event| event.mtu <= max_udp_payload_size));
+}
+
+// the connection handle reports the MTU discovered on the active path
+#[test]
+fn current_mtu_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the handshake is padded to 1200, so that's the starting MTU
+            assert_eq!(connection.current_mtu().unwrap(), 1200);
+
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(1_000_000);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+
+            // probing should have raised the MTU to 1500 (minus headers = 1472)
+            assert_eq!(connection.current_mtu().unwrap(), 1472);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
 // ensure the server enforces the minimum MTU for all initial packets
 #[test]
 fn minimum_initial_packet() {
@@ -2,25 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use crate::provider::tls::Provider;
-use s2n_quic_core::crypto::tls::null;
-
-#[derive(Default)]
-pub struct NoTlsProvider {}
-
-impl Provider for NoTlsProvider {
-    type Server = null::Endpoint;
-    type Client = null::Endpoint;
-    type Error = String;
-
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        Ok(Self::Server::default())
-    }
-
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        Ok(Self::Client::default())
-    }
-}
+use crate::provider::tls::null;
 
 #[test]
 fn no_tls_test() {
@@ -29,13 +11,13 @@ fn no_tls_test() {
     test(model, |handle| {
         let server = Server::builder()
             .with_io(handle.builder().build()?)?
-            .with_tls(NoTlsProvider::default())?
+            .with_tls(null::Provider::default())?
             .with_event(tracing_events())?
             .with_random(Random::with_seed(456))?
             .start()?;
         let client = Client::builder()
             .with_io(handle.builder().build()?)?
-            .with_tls(NoTlsProvider::default())?
+            .with_tls(null::Provider::default())?
             .with_event(tracing_events())?
             .with_random(Random::with_seed(456))?
             .start()?;
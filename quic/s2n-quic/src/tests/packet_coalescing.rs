@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::{Limits, PacketCoalescing};
+
+const MTU: u16 = 1500;
+
+/// Returns the coalescing events of both endpoints during a transfer
+fn coalescing_events(coalescing: PacketCoalescing) -> Vec<events::DatagramCoalesced> {
+    let model = Model::default();
+    let recorder = recorder::DatagramCoalesced::new();
+    let events = recorder.events();
+
+    test(model, |handle| {
+        let limits = Limits::default().with_packet_coalescing(coalescing)?;
+
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(MTU).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_limits(limits)?
+            .with_event((tracing_events(), recorder.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(MTU).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits)?
+            .with_event((tracing_events(), recorder))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    events.clone()
+}
+
+#[test]
+fn packet_coalescing_enabled_test() {
+    let events = coalescing_events(PacketCoalescing::Enabled);
+
+    assert!(events
+        .iter()
+        .all(|event| matches!(event.decision, events::CoalescingDecision::Coalesced)));
+    assert!(
+        events.iter().any(|event| event.packet_count > 1),
+        "{events:?}"
+    );
+}
+
+#[test]
+fn packet_coalescing_limited_test() {
+    let events = coalescing_events(PacketCoalescing::Limited(1200));
+
+    assert!(!events.is_empty());
+    for event in &events {
+        assert!(
+            matches!(
+                event.decision,
+                events::CoalescingDecision::Limited { max_len: 1200 }
+            ),
+            "{event:?}"
+        );
+        assert!(event.len <= 1200, "{event:?}");
+    }
+}
+
+#[test]
+fn packet_coalescing_disabled_test() {
+    let events = coalescing_events(PacketCoalescing::Disabled);
+
+    assert!(!events.is_empty());
+    for event in &events {
+        assert!(
+            matches!(event.decision, events::CoalescingDecision::Disabled),
+            "{event:?}"
+        );
+        assert_eq!(event.packet_count, 1, "{event:?}");
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::Statistics,
+    provider::limits::{Limits, PaddingPolicy},
+};
+
+const MTU: u16 = 1500;
+
+/// Sends a few small writes from a client with the padding policy and returns the lengths of the
+/// client datagrams along with its connection statistics
+fn transfer(policy: PaddingPolicy) -> (Vec<u16>, Statistics) {
+    let model = Model::default();
+    let datagrams = recorder::DatagramSent::new();
+    let datagram_events = datagrams.events();
+    let statistics = Arc::new(Mutex::new(Statistics::default()));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(MTU).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(MTU).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(Limits::default().with_padding_policy(policy)?)?
+            .with_event((tracing_events(), datagrams))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let statistics = statistics.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            for _ in 0..10 {
+                stream.send(Bytes::from_static(&[1; 100])).await.unwrap();
+            }
+            stream.finish().unwrap();
+            while let Ok(Some(_)) = stream.receive().await {}
+
+            *statistics.lock().unwrap() = connection.statistics().unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let datagrams = datagram_events.lock().unwrap().clone();
+    let statistics = *statistics.lock().unwrap();
+    (datagrams, statistics)
+}
+
+/// Asserts that nearly all of the datagrams were padded to `len`
+///
+/// The CONNECTION_CLOSE packet is encoded before it's transmitted so it isn't padded.
+fn assert_padded(datagrams: &[u16], len: u16) {
+    assert!(!datagrams.is_empty());
+    assert!(datagrams.iter().all(|datagram| *datagram <= len));
+
+    let padded = datagrams
+        .iter()
+        .filter(|datagram| **datagram == len)
+        .count();
+    assert!(padded * 10 >= datagrams.len() * 9, "{datagrams:?}");
+}
+
+#[test]
+fn padding_policy_full_test() {
+    let (disabled_datagrams, disabled) = transfer(PaddingPolicy::Disabled);
+    let (datagrams, full) = transfer(PaddingPolicy::Full);
+
+    let max_len = *datagrams.iter().max().unwrap();
+    assert_padded(&datagrams, max_len);
+
+    // the Initial packets are always padded
+    assert!(disabled.padding_bytes_sent > 0);
+    assert!(
+        full.padding_bytes_sent > disabled.padding_bytes_sent,
+        "{full:?} {disabled:?}"
+    );
+    assert!(
+        disabled_datagrams
+            .iter()
+            .any(|datagram| *datagram < max_len),
+        "{disabled_datagrams:?}"
+    );
+}
+
+#[test]
+fn padding_policy_fixed_test() {
+    let (datagrams, _) = transfer(PaddingPolicy::Fixed(1200));
+    assert_padded(&datagrams, 1200);
+}
+
+#[test]
+fn padding_policy_handshake_test() {
+    let (_, disabled) = transfer(PaddingPolicy::Disabled);
+    let (_, handshake) = transfer(PaddingPolicy::Handshake);
+    let (_, full) = transfer(PaddingPolicy::Full);
+
+    // the Initial packets which carry the handshake are always padded, but the remaining
+    // Handshake packets are only padded by the policy
+    assert!(
+        disabled.padding_bytes_sent <= handshake.padding_bytes_sent,
+        "{disabled:?} {handshake:?}"
+    );
+    assert!(
+        handshake.padding_bytes_sent < full.padding_bytes_sent,
+        "{handshake:?} {full:?}"
+    );
+}
@@ -0,0 +1,54 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::PathCache;
+
+/// Connections to a previously seen remote start with the congestion window learned by the
+/// earlier connection instead of the initial window
+#[test]
+fn path_cache_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_path_cache(PathCache::new())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+
+            let mut connection = client.connect(connect.clone()).await.unwrap();
+            let initial_window = connection.query_stats().unwrap().congestion_window;
+
+            // grow the congestion window by transferring some data
+            let mut stream = connection.open_send_stream().await.unwrap();
+            let mut data = Data::new(1_000_000);
+            while let Some(chunk) = data.send_one(usize::MAX) {
+                stream.send(chunk).await.unwrap();
+            }
+            stream.close().await.unwrap();
+
+            // wait for the connection to close so its path is recorded
+            connection.close(0u8.into());
+            drop(connection);
+            delay(Duration::from_secs(1)).await;
+
+            let connection = client.connect(connect).await.unwrap();
+            let resumed_window = connection.query_stats().unwrap().congestion_window;
+            assert!(
+                resumed_window > initial_window,
+                "{resumed_window} > {initial_window}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
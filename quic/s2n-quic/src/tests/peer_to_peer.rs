@@ -0,0 +1,83 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shows how peers can connect to each other from the same socket on which they accept
+//! connections
+
+use super::*;
+
+fn start_peer(handle: &io::Handle) -> io::Result<(Client, Server)> {
+    let server = Server::builder()
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?;
+
+    let peer = Client::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event(tracing_events())?
+        .start_with_server(server)?;
+
+    Ok(peer)
+}
+
+#[test]
+fn simultaneous_open_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let (client_a, mut server_a) = start_peer(handle)?;
+        let (client_b, mut server_b) = start_peer(handle)?;
+
+        let addr_a = client_a.local_addr()?;
+        let addr_b = client_b.local_addr()?;
+
+        // the server shares the socket with the client
+        assert_eq!(server_a.local_addr()?, addr_a);
+        assert_eq!(server_b.local_addr()?, addr_b);
+
+        for (client, server, local_addr, remote_addr) in [
+            (client_a, server_a, addr_a, addr_b),
+            (client_b, server_b, addr_b, addr_a),
+        ] {
+            let mut server = server;
+            primary::spawn(async move {
+                let connect = Connect::new(remote_addr).with_server_name("localhost");
+                let connection = client.connect(connect).await.unwrap();
+                assert_eq!(connection.local_addr().unwrap(), local_addr);
+                assert_eq!(connection.remote_addr().unwrap(), remote_addr);
+
+                // the connection opened by the other peer is accepted on the same socket
+                let connection = server.accept().await.unwrap();
+                assert_eq!(connection.local_addr().unwrap(), local_addr);
+                assert_eq!(connection.remote_addr().unwrap(), remote_addr);
+            });
+        }
+
+        Ok(addr_a)
+    })
+    .unwrap();
+}
+
+#[test]
+fn candidate_addresses_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            // the primary address isn't reachable so the attempt to the candidate wins the race
+            let unreachable: SocketAddr = "192.0.2.1:443".parse().unwrap();
+            let connect = Connect::new(unreachable)
+                .with_candidate_addresses([addr])
+                .with_candidate_delay(Duration::from_millis(100))
+                .with_server_name("localhost");
+
+            let connection = client.connect(connect).await.unwrap();
+            assert_eq!(connection.remote_addr().unwrap(), addr);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
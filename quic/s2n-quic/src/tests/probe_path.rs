@@ -0,0 +1,43 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn probe_path_test() {
+    let model = Model::default();
+    let network_delay = Duration::from_millis(50);
+    model.set_delay(network_delay);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            for _ in 0..3 {
+                let rtt = connection
+                    .probe_path()
+                    .await
+                    .unwrap()
+                    .expect("the peer should respond to the probe");
+                assert!(rtt >= network_delay * 2, "{rtt:?}");
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
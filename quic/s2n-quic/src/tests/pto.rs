@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::connection::ProbePolicy;
 use s2n_codec::encoder::scatter;
 use s2n_quic_core::{
     event::api::{PacketHeader, Subject},
@@ -79,6 +80,175 @@ fn handshake_pto_timer_is_armed() {
     assert_eq!(expected_handshake_packet_count, handshake_packets_sent);
 }
 
+/// This test ensures the client reports why it retransmits handshake data when the server's
+/// handshake packets never arrive
+#[test]
+fn handshake_retransmission_events() {
+    let model = Model::default();
+    let subscriber = recorder::HandshakeRetransmission::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_packet_interceptor(DropHandshakeTx)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        spawn(async move {
+            assert!(server.accept().await.is_none());
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            assert!(client.connect(connect).await.is_err());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    let mut handshake_events = events
+        .iter()
+        .filter(|event| matches!(event.space, events::KeySpace::Handshake { .. }));
+
+    // The client has nothing in flight in the Handshake space, so the first PTO sends a probe to
+    // keep the server from being blocked by its anti-amplification limit
+    let first = handshake_events.next().expect("the client should probe");
+    assert!(
+        matches!(
+            first.reason,
+            events::HandshakeRetransmissionReason::AntiDeadlock { .. }
+        ),
+        "{first:?}"
+    );
+
+    // The probe is never acknowledged, so the following PTOs fire with packets in flight
+    assert!(handshake_events.any(|event| matches!(
+        event.reason,
+        events::HandshakeRetransmissionReason::ProbeTimeout { .. }
+    )));
+}
+
+/// Sends data from the client with the given probe policy while the network blackholes all
+/// packets for a period, and returns the probes sent by the client
+fn probe_policy(policy: ProbePolicy) -> Vec<events::PtoProbeSent> {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    let subscriber = recorder::PtoProbeSent::new();
+    let events = subscriber.events();
+
+    test(model.clone(), |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.set_probe_policy(policy).unwrap();
+
+            // drop all packets for a while so the PTO timer expires with data in flight
+            spawn(async move {
+                delay(Duration::from_millis(200)).await;
+                model.set_drop_rate(1.0);
+                delay(Duration::from_secs(1)).await;
+                model.set_drop_rate(0.0);
+            });
+
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(1_000_000);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    assert!(!events.is_empty(), "the client should send PTO probes");
+    events.clone()
+}
+
+#[test]
+fn default_probe_policy_test() {
+    let probes = probe_policy(ProbePolicy::Default);
+
+    for probe in &probes {
+        assert!(matches!(probe.policy, events::ProbePolicy::Default { .. }));
+    }
+}
+
+#[test]
+fn retransmit_unacknowledged_probe_policy_test() {
+    let probes = probe_policy(ProbePolicy::RetransmitUnacknowledged);
+
+    for probe in &probes {
+        assert!(matches!(
+            probe.policy,
+            events::ProbePolicy::RetransmitUnacknowledged { .. }
+        ));
+        assert_eq!(probe.new_stream_bytes, 0, "{probe:?}");
+    }
+
+    assert!(probes
+        .iter()
+        .any(|probe| probe.retransmitted_stream_bytes > 0));
+}
+
+#[test]
+fn new_data_probe_policy_test() {
+    let probes = probe_policy(ProbePolicy::NewData);
+
+    for probe in &probes {
+        assert!(matches!(probe.policy, events::ProbePolicy::NewData { .. }));
+    }
+
+    assert!(probes.iter().any(|probe| probe.new_stream_bytes > 0));
+}
+
+#[test]
+fn ping_only_probe_policy_test() {
+    let probes = probe_policy(ProbePolicy::PingOnly);
+
+    for probe in &probes {
+        assert!(matches!(probe.policy, events::ProbePolicy::PingOnly { .. }));
+        assert_eq!(probe.new_stream_bytes, 0, "{probe:?}");
+        assert_eq!(probe.retransmitted_stream_bytes, 0, "{probe:?}");
+    }
+}
+
 /// Drops all outgoing handshake packets
 struct DropHandshakeTx;
 
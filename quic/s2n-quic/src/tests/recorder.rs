@@ -59,6 +59,35 @@ macro_rules! event_recorder {
 event_recorder!(FrameSent, FrameSent, on_frame_sent);
 event_recorder!(PacketSent, PacketSent, on_packet_sent);
 event_recorder!(MtuUpdated, MtuUpdated, on_mtu_updated);
+event_recorder!(
+    DatagramSent,
+    DatagramSent,
+    on_datagram_sent,
+    u16,
+    |event: &events::DatagramSent, storage: &mut Vec<u16>| {
+        storage.push(event.len);
+    }
+);
+event_recorder!(
+    ClientHelloFragmented,
+    ClientHelloFragmented,
+    on_client_hello_fragmented
+);
+event_recorder!(DatagramCoalesced, DatagramCoalesced, on_datagram_coalesced);
+event_recorder!(
+    PeerStreamOpened,
+    PeerStreamOpened,
+    on_peer_stream_opened,
+    u64,
+    |event: &events::PeerStreamOpened, storage: &mut Vec<u64>| {
+        storage.push(event.id);
+    }
+);
+event_recorder!(
+    CongestionControllerInitialized,
+    CongestionControllerInitialized,
+    on_congestion_controller_initialized
+);
 event_recorder!(
     PathUpdated,
     RecoveryMetrics,
@@ -80,12 +109,24 @@ event_recorder!(
         storage.push(event.pto_count);
     }
 );
+event_recorder!(PtoProbeSent, PtoProbeSent, on_pto_probe_sent);
+event_recorder!(
+    HandshakeRetransmission,
+    HandshakeRetransmission,
+    on_handshake_retransmission
+);
 event_recorder!(
     HandshakeStatus,
     HandshakeStatusUpdated,
     on_handshake_status_updated
 );
 
+event_recorder!(
+    ConnectionHibernated,
+    ConnectionHibernated,
+    on_connection_hibernated
+);
+
 event_recorder!(
     ActivePathUpdated,
     ActivePathUpdated,
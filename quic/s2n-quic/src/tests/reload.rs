@@ -0,0 +1,68 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    limits::Limits,
+    reload::{Config, Epoch},
+};
+
+#[test]
+fn reload_epoch_test() {
+    let model = Model::default();
+    let config = Config::default();
+    let limits = config.slot(Limits::default());
+    let epochs = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits.clone())?
+            .with_event((config.subscriber(), tracing_events()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let config = config.clone();
+        let epochs = epochs.clone();
+        primary::spawn(async move {
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let connection = client.connect(connect).await.unwrap();
+                let epoch = connection
+                    .query_event_context(|epoch: &Epoch| *epoch)
+                    .unwrap();
+                epochs.lock().unwrap().push(epoch);
+
+                // existing connections keep their epoch after a reload
+                config
+                    .update(|update| {
+                        update.set(
+                            &limits,
+                            Limits::new().with_max_handshake_duration(Duration::from_secs(5))?,
+                        );
+                        Ok::<_, s2n_quic_core::connection::limits::ValidationError>(())
+                    })
+                    .unwrap();
+                assert_eq!(
+                    connection.query_event_context(|epoch: &Epoch| *epoch),
+                    Ok(epoch)
+                );
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(config.epoch().as_u64(), 2);
+    let epochs: Vec<_> = epochs.lock().unwrap().iter().map(|e| e.as_u64()).collect();
+    assert_eq!(epochs, [0, 1]);
+}
@@ -0,0 +1,72 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::replay::{Log, Subscriber, Trigger};
+
+/// Connects to a server and closes the connection with the application error
+fn dumps(trigger: Trigger) -> Vec<(u64, usize, Option<String>)> {
+    let model = Model::default();
+    let dumps = Arc::new(Mutex::new(vec![]));
+    let sink = {
+        let dumps = dumps.clone();
+        move |log: &Log| {
+            let last = log.records().last().map(|record| record.name().to_string());
+            dumps
+                .lock()
+                .unwrap()
+                .push((log.id(), log.records().len(), last));
+        }
+    };
+    let subscriber = Subscriber::builder(sink)
+        .with_max_records(16)
+        .unwrap()
+        .with_policy(trigger)
+        .build();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = build_client(handle)?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            connection.close(123u8.into());
+            // give the close time to reach the server
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let dumps = dumps.lock().unwrap();
+    dumps.clone()
+}
+
+#[test]
+fn replay_dump_on_error_test() {
+    let dumps = dumps(Trigger::OnError);
+
+    assert_eq!(dumps.len(), 1);
+    let (_id, len, last) = &dumps[0];
+    // the records are bounded by the configured limit
+    assert_eq!(*len, 16);
+    // the close event is the last one recorded
+    assert_eq!(
+        last.as_deref(),
+        Some(<events::ConnectionClosed as crate::provider::event::Event>::NAME)
+    );
+}
+
+#[test]
+fn replay_no_dump_on_application_close_test() {
+    let dumps = dumps(Trigger::OnUnexpectedClose);
+    assert!(dumps.is_empty());
+}
@@ -0,0 +1,85 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::request::{Error, Limits};
+
+fn request_test<F, C>(f: F)
+where
+    F: 'static + Send + FnOnce(crate::Connection) -> C,
+    C: 'static + core::future::Future<Output = ()> + Send,
+{
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            f(connection).await;
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn request_response_test() {
+    request_test(|mut connection| async move {
+        let mut data = Data::new(100_000);
+        let request = data.send_one(usize::MAX).unwrap();
+
+        // the server echoes the request back
+        let response = connection.request(request.clone()).await.unwrap();
+        assert_eq!(response.body(), &request);
+
+        // each request uses a new stream
+        let next = connection.request(request.clone()).await.unwrap();
+        assert_ne!(response.stream_id(), next.stream_id());
+
+        // empty requests receive empty responses
+        let response = connection.request(Bytes::new()).await.unwrap();
+        assert!(response.body().is_empty());
+    });
+}
+
+#[test]
+fn response_too_large_test() {
+    request_test(|mut connection| async move {
+        let limits = Limits::new().with_max_response_size(3);
+        let result = connection
+            .request_with(
+                Bytes::from_static(b"hello"),
+                limits,
+                core::future::pending::<()>(),
+            )
+            .await;
+        assert_eq!(result, Err(Error::ResponseTooLarge { limit: 3 }));
+
+        // the connection can still be used after a failed request
+        let response = connection
+            .request(Bytes::from_static(b"hello"))
+            .await
+            .unwrap();
+        assert_eq!(response.body(), &b"hello"[..]);
+    });
+}
+
+#[test]
+fn deadline_test() {
+    request_test(|mut connection| async move {
+        // the deadline passes before the peer is able to respond
+        let result = connection
+            .request_with(
+                Bytes::from_static(b"hello"),
+                Limits::default(),
+                delay(Duration::from_millis(10)),
+            )
+            .await;
+        assert_eq!(result, Err(Error::TimedOut));
+    });
+}
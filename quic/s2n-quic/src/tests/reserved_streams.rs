@@ -0,0 +1,70 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+
+#[test]
+fn reserved_streams_test() {
+    let model = Model::default();
+    let opened = recorder::PeerStreamOpened::new();
+    let opened_events = opened.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), opened))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let limits = Limits::default().with_reserved_local_bidirectional_streams(2)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // regular streams are assigned the IDs after the reserved range
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            assert_eq!(stream.id(), 8);
+            stream.send(Bytes::from_static(b"data")).await.unwrap();
+            stream.finish().unwrap();
+            while let Ok(Some(_)) = stream.receive().await {}
+
+            let mut control = connection
+                .open_reserved_bidirectional_stream()
+                .unwrap()
+                .unwrap();
+            assert_eq!(control.id(), 0);
+            control.send(Bytes::from_static(b"control")).await.unwrap();
+            control.finish().unwrap();
+            while let Ok(Some(_)) = control.receive().await {}
+
+            let control = connection
+                .open_reserved_bidirectional_stream()
+                .unwrap()
+                .unwrap();
+            assert_eq!(control.id(), 4);
+
+            assert!(connection
+                .open_reserved_bidirectional_stream()
+                .unwrap()
+                .is_none());
+            assert!(connection.open_reserved_send_stream().unwrap().is_none());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // opening the regular stream implicitly opened the reserved streams on the server
+    assert_eq!(*opened_events.lock().unwrap(), [0, 4, 8]);
+}
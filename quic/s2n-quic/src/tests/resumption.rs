@@ -58,3 +58,152 @@ fn resumption_handshake() {
     })
     .unwrap();
 }
+
+/// Connects to the server twice with the same client and returns whether each connection was
+/// resumed, as reported by the client and the server
+#[cfg(any(feature = "s2n-quic-tls", feature = "s2n-quic-rustls"))]
+fn is_resumed(
+    build: impl FnOnce(
+        &crate::provider::io::testing::Handle,
+    ) -> crate::provider::io::testing::Result<(crate::Server, crate::Client)>,
+) -> (Vec<bool>, Vec<bool>) {
+    use super::*;
+
+    let model = Model::default();
+    let client_resumed = Arc::new(Mutex::new(vec![]));
+    let server_resumed = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let (mut server, client) = build(handle)?;
+        let addr = server.local_addr()?;
+
+        let server_resumed = server_resumed.clone();
+        spawn(async move {
+            while let Some(connection) = server.accept().await {
+                server_resumed
+                    .lock()
+                    .unwrap()
+                    .push(connection.is_resumed().unwrap());
+            }
+        });
+
+        let client_resumed = client_resumed.clone();
+        primary::spawn(async move {
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let connection = client.connect(connect).await.unwrap();
+                client_resumed
+                    .lock()
+                    .unwrap()
+                    .push(connection.is_resumed().unwrap());
+
+                // give the server time to issue a session ticket
+                delay(Duration::from_millis(100)).await;
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let client_resumed = client_resumed.lock().unwrap().clone();
+    let server_resumed = server_resumed.lock().unwrap().clone();
+    (client_resumed, server_resumed)
+}
+
+#[cfg(feature = "s2n-quic-tls")]
+#[test]
+fn s2n_tls_is_resumed_test() {
+    use super::*;
+    use crate::provider::tls::s2n_tls;
+
+    let (client, server) = is_resumed(|handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(build_server_resumption_provider(
+                certificates::CERT_PEM,
+                certificates::KEY_PEM,
+            )?)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        let client_tls = s2n_tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_session_resumption(true)?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        Ok((server, client))
+    });
+
+    assert_eq!(client, [false, true]);
+    assert_eq!(server, [false, true]);
+}
+
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn rustls_is_resumed_test() {
+    use super::*;
+    use crate::provider::tls::rustls;
+
+    let (client, server) = is_resumed(|handle| {
+        let server_tls = rustls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        let client_tls = rustls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_session_resumption(true)?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        Ok((server, client))
+    });
+
+    assert_eq!(client, [false, true]);
+    assert_eq!(server, [false, true]);
+}
+
+/// The rustls client resumes sessions unless the application opts out
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn rustls_resumption_enabled_by_default_test() {
+    use super::*;
+    use crate::provider::tls::rustls;
+
+    let (client, server) = is_resumed(|handle| {
+        let server_tls = rustls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .start()?;
+
+        let client_tls = rustls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .start()?;
+
+        Ok((server, client))
+    });
+
+    assert_eq!(client, [false, true]);
+    assert_eq!(server, [false, true]);
+}
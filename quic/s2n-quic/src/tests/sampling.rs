@@ -0,0 +1,50 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::sampling::{Filter, Sampled};
+
+/// Connects to a server which samples connections by server name and returns the handshake
+/// events observed by the sampled subscriber
+fn sampled_handshake_events(pattern: &str) -> Vec<events::HandshakeStatusUpdated> {
+    let model = Model::default();
+    let subscriber = recorder::HandshakeStatus::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let filter = Filter::builder().with_server_name(pattern).build();
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), Sampled::new(subscriber, filter)))?
+            .start()?;
+
+        let client = build_client(handle)?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.open_bidirectional_stream().await.unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let observed = events.lock().unwrap();
+    observed.clone()
+}
+
+#[test]
+fn server_name_sampling_test() {
+    // the server only learns the server name from the ClientHello, after the connection was
+    // created, but the connection is still selected once the name is known
+    let observed = sampled_handshake_events("localhost");
+    assert!(observed
+        .iter()
+        .any(|event| matches!(event.status, events::HandshakeStatus::Complete { .. })));
+
+    let observed = sampled_handshake_events("*.example.com");
+    assert!(observed.is_empty(), "{observed:?}");
+}
@@ -0,0 +1,69 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic_core::{
+    connection::Error,
+    crypto::tls::{self, pin::SpkiHash},
+    transport,
+};
+
+/// SHA-256 of the subject public key info in `certificates::CERT_PEM`
+const CERT_SPKI: &str = "87086095bf3ef97d6f2c739116d7615fa20d010e26b8ed20e00402675e7b5005";
+
+fn spki_hash(hex: &str) -> SpkiHash {
+    let mut hash = SpkiHash::default();
+    for (idx, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[idx * 2..idx * 2 + 2], 16).unwrap();
+    }
+    hash
+}
+
+/// Connects to a server with the given pins and returns the outcome of the attempt
+fn connect(pins: Vec<SpkiHash>) -> Result<(), Error> {
+    let model = Model::default();
+    let outcome = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let client = build_client(handle)?;
+        let addr = start_server(server)?;
+
+        let outcome = outcome.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_pinned_spki(pins);
+            let result = client.connect(connect).await.map(|_| ());
+            *outcome.lock().unwrap() = Some(result);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let outcome = outcome.lock().unwrap().take();
+    outcome.expect("the attempt should complete")
+}
+
+#[test]
+fn spki_pinning_match_test() {
+    let other = [0x42; 32];
+    connect(vec![other, spki_hash(CERT_SPKI)]).unwrap();
+}
+
+#[test]
+fn spki_pinning_mismatch_test() {
+    let error = connect(vec![[0x42; 32]]).unwrap_err();
+
+    match error {
+        Error::Transport { code, reason, .. } => {
+            assert_eq!(
+                code,
+                transport::Error::from(tls::Error::BAD_CERTIFICATE).code
+            );
+            assert_eq!(reason, tls::pin::MISMATCH.reason);
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
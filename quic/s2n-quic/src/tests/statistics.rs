@@ -0,0 +1,232 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic_core::{
+    event::api::Subject,
+    packet::interceptor::{Datagram, Interceptor},
+};
+use DecoderBufferMut;
+
+#[test]
+fn statistics_test() {
+    let model = Model::default();
+    // drop some of the packets so the streams are forced to retransmit
+    model.set_drop_rate(0.05);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            assert!(client.statistics().is_empty());
+
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            for _ in 0..100 {
+                stream.send(Bytes::from_static(&[1; 1000])).await.unwrap();
+            }
+            stream.finish().unwrap();
+            while let Ok(Some(_)) = stream.receive().await {}
+
+            let statistics = connection.statistics().unwrap();
+            assert!(statistics.retransmitted_bytes > 0, "{statistics:?}");
+
+            // the connection statistics are included in the endpoint totals
+            let totals = client.statistics();
+            assert!(
+                totals.retransmitted_bytes >= statistics.retransmitted_bytes,
+                "{totals:?}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn id_map_metrics_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            assert_eq!(client.id_map_metrics().local_ids.len, 0);
+
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let metrics = client.id_map_metrics();
+            assert!(metrics.local_ids.len > 0, "{metrics:?}");
+            assert_eq!(metrics.local_ids.collisions, 0);
+
+            drop(connection);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Drops the first datagram received by the client so it retransmits its Initial packets
+#[derive(Default)]
+struct DropFirstDatagram {
+    dropped: bool,
+}
+
+impl Interceptor for DropFirstDatagram {
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &Subject,
+        _datagram: &Datagram,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        if core::mem::replace(&mut self.dropped, true) {
+            return payload;
+        }
+        DecoderBufferMut::new(&mut payload.into_less_safe_slice()[..0])
+    }
+}
+
+#[test]
+fn coalesced_initial_packets_test() {
+    let model = Model::default();
+
+    let server_statistics = Arc::new(Mutex::new(Vec::new()));
+    let server_statistics_pub = server_statistics.clone();
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(connection) = server.accept().await {
+                let statistics = connection.statistics().unwrap();
+                server_statistics_pub.lock().unwrap().push(statistics);
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_packet_interceptor(DropFirstDatagram::default())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            // the client doesn't coalesce any Initial packets
+            let statistics = connection.statistics().unwrap();
+            assert_eq!(statistics.coalesced_initial_packets, 0);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // the retransmitted Initial packets should be routed to the existing handshake
+    let server_statistics = server_statistics.lock().unwrap();
+    assert_eq!(server_statistics.len(), 1);
+    assert!(
+        server_statistics[0].coalesced_initial_packets > 0,
+        "{server_statistics:?}"
+    );
+}
+
+#[test]
+fn query_stats_test() {
+    let model = Model::default();
+    let network_delay = Duration::from_millis(50);
+    model.set_delay(network_delay);
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let client = build_client(handle)?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(&[1; 1000])).await.unwrap();
+            stream.finish().unwrap();
+            while let Ok(Some(_)) = stream.receive().await {}
+
+            let stats = connection.query_stats().unwrap();
+
+            // the RTT estimate should reflect the delay in both directions
+            let rtt = network_delay * 2;
+            assert!(stats.min_rtt >= rtt, "{stats:?}");
+            assert!(stats.latest_rtt >= stats.min_rtt, "{stats:?}");
+            assert!(stats.smoothed_rtt >= rtt, "{stats:?}");
+            assert_eq!(stats.pto_count, 0, "{stats:?}");
+            assert!(stats.congestion_window > 0, "{stats:?}");
+            assert!(stats.pacing_rate.is_some(), "{stats:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn quic_version_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let client = build_client(handle)?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            assert_eq!(
+                connection.quic_version().unwrap(),
+                s2n_quic_core::packet::long::VERSION_1
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
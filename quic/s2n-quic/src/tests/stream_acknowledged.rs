@@ -0,0 +1,56 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shows how an application protocol can issue delivery receipts for individual messages
+
+use super::*;
+
+#[test]
+fn stream_acknowledged_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            let mut receipts = vec![];
+            for message in 0..3u8 {
+                stream
+                    .send(Bytes::from(vec![message; 1_000]))
+                    .await
+                    .unwrap();
+                receipts.push(stream.send_offsets().unwrap().enqueued);
+            }
+
+            for offset in receipts {
+                let acknowledged = stream.acknowledged(offset).await.unwrap();
+                assert!(acknowledged >= offset);
+            }
+
+            // all of the messages were delivered without flushing the stream
+            assert_eq!(stream.send_offsets().unwrap().acknowledged, 3_000);
+
+            // an offset past the end of a finished stream can't be acknowledged
+            stream.close().await.unwrap();
+            assert!(stream.acknowledged(3_001).await.is_err());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::{events, ConnectionInfo, ConnectionMeta, Subscriber};
+
+#[derive(Debug, Default)]
+struct MarkerCounts {
+    sent: Vec<u64>,
+    acknowledged: Vec<u64>,
+}
+
+/// Records the markers observed on a connection
+struct MarkerTracker;
+
+impl Subscriber for MarkerTracker {
+    type ConnectionContext = MarkerCounts;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        MarkerCounts::default()
+    }
+
+    fn on_stream_marker_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::StreamMarkerSent,
+    ) {
+        context.sent.push(event.marker);
+    }
+
+    fn on_stream_marker_acknowledged(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::StreamMarkerAcknowledged,
+    ) {
+        context.acknowledged.push(event.marker);
+    }
+}
+
+#[test]
+fn stream_marker_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((MarkerTracker, tracing_events()))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // unmarked data doesn't produce any events
+            stream.send(Bytes::from_static(&[1; 1000])).await.unwrap();
+            stream
+                .send_with_marker(Bytes::from_static(&[2; 10_000]), 123)
+                .await
+                .unwrap();
+            stream.flush().await.unwrap();
+
+            let (sent, acknowledged) = connection
+                .query_event_context(|context: &MarkerCounts| {
+                    (context.sent.clone(), context.acknowledged.clone())
+                })
+                .unwrap();
+
+            // the marked data doesn't fit in a single packet
+            assert!(sent.len() > 1, "{sent:?}");
+            assert!(sent.iter().all(|marker| *marker == 123));
+            assert_eq!(acknowledged, [123]);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,69 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shows how an application protocol can use stream offsets to resume a transfer
+
+use super::*;
+use crate::stream::{ReceiveOffsets, SendOffsets};
+
+#[test]
+fn stream_offsets_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the server echoes the data back on the same stream
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            assert_eq!(stream.send_offsets().unwrap(), SendOffsets::default());
+            assert_eq!(stream.receive_offsets().unwrap(), ReceiveOffsets::default());
+
+            stream
+                .send(Bytes::from_static(&[42; 10_000]))
+                .await
+                .unwrap();
+            stream.flush().await.unwrap();
+
+            // once flushed, all of the data has been acknowledged by the peer
+            let offsets = stream.send_offsets().unwrap();
+            assert_eq!(offsets.enqueued, 10_000);
+            assert_eq!(offsets.acknowledged, 10_000);
+
+            // read part of the response before the transfer is interrupted
+            let mut received = 0;
+            while received < 5_000 {
+                let chunk = stream.receive().await.unwrap().unwrap();
+                received += chunk.len() as u64;
+            }
+
+            let offsets = stream.receive_offsets().unwrap();
+            assert_eq!(offsets.consumed, received);
+            assert!(offsets.received >= offsets.consumed);
+
+            connection.close(123u8.into());
+
+            // the offsets are still available after the connection has closed so the application
+            // can resume the transfer from `consumed` on a new connection
+            assert_eq!(stream.receive_offsets().unwrap().consumed, received);
+            assert_eq!(stream.send_offsets().unwrap().acknowledged, 10_000);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,57 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+
+#[test]
+fn stream_stats_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        // limit the stream window so the client is blocked on stream flow control
+        let limits = Limits::default().with_bidirectional_remote_data_window(10_000)?;
+
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_limits(limits)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            assert_eq!(stream.send_stats().unwrap(), Default::default());
+
+            stream
+                .send(Bytes::from_static(&[42; 100_000]))
+                .await
+                .unwrap();
+            stream.flush().await.unwrap();
+
+            let stats = stream.send_stats().unwrap();
+            assert_eq!(stats.bytes_sent, 100_000);
+            assert_eq!(stats.bytes_acknowledged, 100_000);
+            assert!(
+                stats.stream_flow_control_blocked > Duration::ZERO,
+                "{stats:?}"
+            );
+            assert!(stats.age >= stats.stream_flow_control_blocked, "{stats:?}");
+
+            connection.close(123u8.into());
+
+            // the statistics are still available after the connection has closed
+            assert_eq!(stream.send_stats().unwrap().bytes_acknowledged, 100_000);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,101 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::tls::fault::{Fault, Point, Provider};
+use s2n_quic_core::{
+    connection::Error,
+    crypto::tls::{self, testing::certificates::CERT_PEM},
+    transport,
+};
+
+/// Connects to a server with faults injected into the client's handshake
+///
+/// Returns the outcome of the attempt along with the time it took to complete.
+fn connect(
+    tls: Provider<&'static str>,
+    on_start: impl FnOnce() + Send + 'static,
+) -> (Result<(), Error>, Duration) {
+    let model = Model::default();
+    let outcome = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(tls)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        on_start();
+
+        let outcome = outcome.clone();
+        primary::spawn(async move {
+            let start = io::now();
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let result = client.connect(connect).await.map(|_| ());
+            *outcome.lock().unwrap() = Some((result, io::now() - start));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let outcome = outcome.lock().unwrap().take();
+    outcome.expect("the attempt should complete")
+}
+
+#[test]
+fn tls_fault_alert_test() {
+    let tls = Provider::new(CERT_PEM).with_fault(
+        Point::HandshakeFlight,
+        Fault::Alert(tls::Error::BAD_CERTIFICATE),
+    );
+
+    let (result, _) = connect(tls, || {});
+
+    match result.unwrap_err() {
+        Error::Transport { code, .. } => {
+            assert_eq!(
+                code,
+                transport::Error::from(tls::Error::BAD_CERTIFICATE).code
+            );
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
+
+#[test]
+fn tls_fault_hold_test() {
+    let hold_duration = Duration::from_secs(2);
+    let tls = Provider::new(CERT_PEM).with_fault(Point::HandshakeFlight, Fault::Hold);
+    let release = tls.handle();
+
+    let (result, elapsed) = connect(tls, move || {
+        primary::spawn(async move {
+            delay(hold_duration).await;
+            release.release();
+        });
+    });
+
+    // the handshake completes once the held flight is sent
+    result.unwrap();
+    assert!(elapsed >= hold_duration, "{elapsed:?}");
+}
+
+#[test]
+fn tls_fault_oversize_test() {
+    let tls = Provider::new(CERT_PEM).with_fault(
+        Point::InitialFlight,
+        Fault::Oversize {
+            len: u16::MAX as usize,
+        },
+    );
+
+    let (result, _) = connect(tls, || {});
+
+    // the server refuses to buffer a message of that size
+    assert!(result.is_err());
+}
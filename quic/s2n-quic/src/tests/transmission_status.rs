@@ -0,0 +1,46 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn transmission_status_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let before = connection.transmission_status().unwrap();
+            assert!(before.congestion_window > 0);
+            assert!(before.available_bidirectional_streams > 0);
+            assert!(!before.is_amplification_limited);
+            assert!(!before.is_connection_flow_limited());
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+            while let Ok(Some(_)) = stream.receive().await {}
+
+            let after = connection.transmission_status().unwrap();
+            assert!(after.connection_flow_consumed >= 5);
+            assert!(after.connection_flow_limit >= before.connection_flow_limit);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
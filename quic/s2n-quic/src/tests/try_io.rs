@@ -0,0 +1,79 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drives a stream from a custom polling loop with the nonblocking `try_*` APIs
+
+use super::*;
+use crate::stream::Error;
+
+#[test]
+fn try_send_receive_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the server echoes the data back on the same stream
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.set_buffer_size(1_000).unwrap();
+
+            // nothing has been sent yet so there's nothing to receive
+            assert!(matches!(
+                stream.try_receive(),
+                Err(Error::ReceivingBlocked { .. })
+            ));
+
+            let mut chunk = Bytes::from_static(&[42; 1_000]);
+            stream.try_send(&mut chunk).unwrap();
+            assert!(chunk.is_empty());
+
+            // the send buffer is full so the chunk is handed back
+            let mut chunk = Bytes::from_static(&[42; 1_000]);
+            assert!(matches!(
+                stream.try_send(&mut chunk),
+                Err(Error::SendingBlocked { .. })
+            ));
+            assert_eq!(chunk.len(), 1_000);
+
+            let mut received = 0;
+            while received < 2_000 {
+                if !chunk.is_empty() {
+                    match stream.try_send(&mut chunk) {
+                        Ok(()) => stream.finish().unwrap(),
+                        Err(Error::SendingBlocked { .. }) => {}
+                        Err(error) => panic!("unexpected error: {error}"),
+                    }
+                }
+
+                match stream.try_receive() {
+                    Ok(Some(data)) => received += data.len(),
+                    Ok(None) => break,
+                    Err(Error::ReceivingBlocked { .. }) => {
+                        delay(Duration::from_millis(1)).await;
+                    }
+                    Err(error) => panic!("unexpected error: {error}"),
+                }
+            }
+
+            assert!(chunk.is_empty());
+            assert_eq!(received, 2_000);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
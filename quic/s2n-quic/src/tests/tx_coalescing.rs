@@ -0,0 +1,80 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::Statistics;
+
+/// Sends a series of small writes, spaced apart, and returns the client connection statistics
+fn small_writes(coalescing: Option<Duration>) -> Statistics {
+    let model = Model::default();
+    let statistics = Arc::new(Mutex::new(None));
+    let result = statistics.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.set_tx_coalescing(coalescing).unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // capture the statistics after the handshake so only the writes are compared
+            let before = connection.statistics().unwrap();
+
+            for _ in 0..100 {
+                stream.send(Bytes::from_static(&[1; 10])).await.unwrap();
+                delay(Duration::from_micros(100)).await;
+            }
+            stream.finish().unwrap();
+            while let Ok(Some(_)) = stream.receive().await {}
+
+            let after = connection.statistics().unwrap();
+            let mut statistics = Statistics::default();
+            statistics.application_packets_sent =
+                after.application_packets_sent - before.application_packets_sent;
+            statistics.application_packet_bytes_sent =
+                after.application_packet_bytes_sent - before.application_packet_bytes_sent;
+            statistics.application_packet_capacity =
+                after.application_packet_capacity - before.application_packet_capacity;
+            *result.lock().unwrap() = Some(statistics);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let statistics = statistics.lock().unwrap().take();
+    statistics.expect("the client should finish sending")
+}
+
+/// Coalescing small writes results in fewer, fuller packets
+#[test]
+fn tx_coalescing_test() {
+    let uncoalesced = small_writes(None);
+    let coalesced = small_writes(Some(Duration::from_millis(5)));
+
+    assert!(
+        coalesced.application_packets_sent < uncoalesced.application_packets_sent,
+        "coalesced: {coalesced:?}, uncoalesced: {uncoalesced:?}"
+    );
+    assert!(
+        coalesced.application_packet_fill_rate().unwrap()
+            > uncoalesced.application_packet_fill_rate().unwrap(),
+        "coalesced: {coalesced:?}, uncoalesced: {uncoalesced:?}"
+    );
+}
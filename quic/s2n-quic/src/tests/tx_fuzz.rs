@@ -0,0 +1,75 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mutates datagrams after encryption and ensures the peer discards them and the connection
+//! recovers the lost data
+
+use super::*;
+use crate::provider::io::fuzz::{Action, Policy};
+use s2n_quic_core::inet::SocketAddress;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Applies the action to every `interval`th datagram and counts the number of mutations
+fn every(interval: usize, action: fn(&[u8]) -> Action, mutations: Arc<AtomicUsize>) -> impl Policy {
+    let mut count = 0;
+    move |_: &SocketAddress, payload: &[u8]| {
+        count += 1;
+        if count % interval != 0 {
+            return Action::Forward;
+        }
+        mutations.fetch_add(1, Ordering::Relaxed);
+        action(payload)
+    }
+}
+
+fn transfer(action: fn(&[u8]) -> Action) {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let mutations = Arc::new(AtomicUsize::new(0));
+    let server_policy = every(7, action, mutations.clone());
+    let client_policy = every(5, action, mutations.clone());
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_tx_fuzz(server_policy).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().with_tx_fuzz(client_policy).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        // the data is validated on receipt so any mutations that were accepted would be detected
+        start_client(client, addr, Data::new(100_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert!(mutations.load(Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn tx_fuzz_drop_test() {
+    transfer(|_| Action::Drop);
+}
+
+#[test]
+fn tx_fuzz_truncate_test() {
+    transfer(|payload| Action::Truncate {
+        len: payload.len() / 2,
+    });
+}
+
+#[test]
+fn tx_fuzz_flip_bits_test() {
+    // flip a bit in the authentication tag so the packet can still be routed by the peer
+    transfer(|payload| Action::FlipBits {
+        index: payload.len() - 1,
+        mask: 0b0000_0001,
+    });
+}
@@ -0,0 +1,46 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Converts binary qlog traces written by s2n-quic into the qlog JSON-SEQ serialization
+//!
+//! Usage: `qlog-convert <TRACE>...`
+//!
+//! Each trace is written next to the input with the `.sqlog` extension.
+
+use s2n_quic::provider::event::qlog::{binary, Format};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+fn main() -> ExitCode {
+    let traces: Vec<PathBuf> = std::env::args_os().skip(1).map(PathBuf::from).collect();
+
+    if traces.is_empty() {
+        eprintln!("usage: qlog-convert <TRACE>...");
+        return ExitCode::FAILURE;
+    }
+
+    let mut status = ExitCode::SUCCESS;
+
+    for input in traces {
+        let output = input.with_extension(Format::Json.extension());
+
+        let result = File::open(&input).and_then(|input| {
+            let output = File::create(&output)?;
+            binary::convert(BufReader::new(input), BufWriter::new(output))
+        });
+
+        match result {
+            Ok(()) => println!("{} -> {}", input.display(), output.display()),
+            Err(error) => {
+                eprintln!("{}: {error}", input.display());
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    status
+}